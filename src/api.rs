@@ -1,9 +1,70 @@
+pub mod analysis_api;
+pub mod analysis_history;
+pub mod analysis_migration;
+pub mod analysis_progress;
+pub mod analysis_sources;
+pub mod analysis_store;
+pub mod batch;
+pub mod context_diff;
+pub mod coverage;
+pub mod cpp_log_parser;
+pub mod custom_test_lists;
 pub mod deliverable;
+pub mod deliverable_limits;
+pub mod detection_rules;
+pub mod download_progress;
+pub mod environment_drift;
 pub mod file_operations;
+pub mod filtered_run;
+pub mod folder_structure;
+pub mod github;
+pub mod github_actions;
+pub mod go_log_parser;
+pub mod java_log_parser;
 pub mod javascript_log_parser;
+pub mod junit;
+pub mod language_defaults;
+pub mod line_matcher;
+pub mod line_stitch;
+pub mod llm_summary;
 pub mod log_analysis;
+pub mod log_discovery;
 pub mod log_parser;
+pub mod log_preprocess;
+pub mod log_slice;
+pub mod notifications;
+pub mod p2p_breadth;
+pub mod parser_corpus;
+pub mod parser_quality;
+pub mod parser_registry;
+pub mod patch_apply;
+pub mod patch_classes;
+pub mod patch_diff;
+pub mod path_guard;
 pub mod python_log_parser;
+pub mod report_export;
+pub mod report_schema;
+pub mod review_bundle;
+pub mod review_conflicts;
+pub mod review_queue;
+pub mod reviewer_platform;
+pub mod run_abort;
+pub mod run_script;
 pub mod rust_log_parser;
+pub mod scratch_parse;
+pub mod search_variants;
+pub mod server_config;
+pub mod sessions;
+pub mod sheets;
+pub mod skip_reasons;
+pub mod status_overrides;
+pub mod storage;
+pub mod swebench_dataset;
+pub mod test_binaries;
 pub mod test_detection;
+pub mod test_list_source;
+pub mod test_name;
+pub mod test_timings;
+pub mod trace_context;
+pub mod webhook;
 
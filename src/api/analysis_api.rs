@@ -0,0 +1,222 @@
+//! Direct analysis endpoint for automation that already has the deliverable files
+//!
+//! `POST /api/analyze` with `Authorization: Bearer <token>` and a JSON body:
+//!
+//! ```json
+//! {
+//!   "file_paths": ["/data/deliverables/task-1/logs/base.log", "..."],
+//!   "files": [{ "path": "logs/after.log", "content": "..." }]
+//! }
+//! ```
+//!
+//! `file_paths` must live under one of the configured `analysis_api.allowed_roots`;
+//! `files` is an uploaded bundle written to a scratch folder for the duration of the
+//! request. Either (or both) may be given. The response is the `LogAnalysisResult`.
+
+use std::path::{Component, Path, PathBuf};
+
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::api::server_config::{load_server_config, AnalysisApiConfig};
+use crate::app::types::LogAnalysisResult;
+
+pub const ANALYZE_ROUTE: &str = "/api/analyze";
+
+// Uploaded bundles carry whole logs, well beyond axum's 2 MB default
+pub const MAX_REQUEST_BYTES: usize = 256 * 1024 * 1024;
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct AnalyzeRequest {
+    #[serde(default)]
+    pub file_paths: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<BundleFile>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BundleFile {
+    /// Relative path inside the bundle, e.g. "logs/base.log" or "main/main.json"
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Serialize)]
+pub struct ApiError {
+    pub error: String,
+}
+
+type ApiResult = Result<Json<LogAnalysisResult>, (StatusCode, Json<ApiError>)>;
+
+fn api_error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<ApiError>) {
+    (status, Json(ApiError { error: message.into() }))
+}
+
+// Compares digests byte by byte without stopping early, so the response time says nothing
+// about how much of a guessed token was right
+fn token_matches(provided: &str, expected: &str) -> bool {
+    let (provided, expected) = (Sha256::digest(provided.as_bytes()), Sha256::digest(expected.as_bytes()));
+    provided.iter().zip(expected.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+fn check_token(config: &AnalysisApiConfig, headers: &HeaderMap) -> Result<(), (StatusCode, Json<ApiError>)> {
+    if config.tokens.is_empty() {
+        return Err(api_error(StatusCode::SERVICE_UNAVAILABLE, "Analysis API is not enabled on this server"));
+    }
+
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.trim());
+
+    match provided {
+        // Every configured token is checked, not just up to the first match
+        Some(token) if config.tokens.iter().filter(|t| !t.is_empty()).fold(false, |found, t| found | token_matches(token, t)) => Ok(()),
+        Some(_) => Err(api_error(StatusCode::FORBIDDEN, "Invalid API token")),
+        None => Err(api_error(StatusCode::UNAUTHORIZED, "Missing bearer token")),
+    }
+}
+
+/// Canonicalize a requested path and make sure it sits under an allowed root
+pub fn resolve_allowed_path(path: &str, allowed_roots: &[String]) -> Result<PathBuf, String> {
+    let canonical = std::fs::canonicalize(path).map_err(|e| format!("Cannot access {}: {}", path, e))?;
+
+    let allowed = allowed_roots
+        .iter()
+        .filter_map(|root| std::fs::canonicalize(root).ok())
+        .any(|root| canonical.starts_with(root));
+
+    if allowed {
+        Ok(canonical)
+    } else {
+        Err(format!("{} is outside the allowed roots", path))
+    }
+}
+
+/// Reject absolute paths and parent components so bundle files stay inside the scratch folder
+pub fn sanitize_bundle_path(path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(path);
+    if path.is_empty() || relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return Err(format!("Invalid bundle path: {}", path));
+    }
+    Ok(relative.to_path_buf())
+}
+
+// Scratch folder for an uploaded bundle, removed once the request finishes
+struct BundleDir {
+    path: PathBuf,
+}
+
+impl Drop for BundleDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+fn write_bundle(files: &[BundleFile]) -> Result<(BundleDir, Vec<String>), String> {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let base_temp_dir = temp_dir.path().parent().unwrap().join("swe-reviewer-temp");
+    let folder = format!("api-{}", uuid::Uuid::new_v4());
+    let bundle_dir = BundleDir { path: base_temp_dir.join(&folder) };
+
+    let mut relative_paths = Vec::new();
+    for file in files {
+        let relative = Path::new(&folder).join(sanitize_bundle_path(&file.path)?);
+        let target = base_temp_dir.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&target, &file.content).map_err(|e| format!("Failed to write {}: {}", file.path, e))?;
+        relative_paths.push(relative.to_string_lossy().to_string());
+    }
+
+    Ok((bundle_dir, relative_paths))
+}
+
+/// Axum handler for `POST /api/analyze`
+pub async fn analyze_handler(headers: HeaderMap, Json(request): Json<AnalyzeRequest>) -> ApiResult {
+    use crate::api::log_analysis::analyze_logs;
+    use crate::api::swebench_dataset::cross_check_with_dataset;
+
+    let config = load_server_config().analysis_api;
+    check_token(&config, &headers)?;
+
+    if request.file_paths.is_empty() && request.files.is_empty() {
+        return Err(api_error(StatusCode::BAD_REQUEST, "Provide file_paths and/or files"));
+    }
+
+    // Absolute paths pass through the temp-dir join in analyze_logs unchanged
+    let mut file_paths = Vec::new();
+    for path in &request.file_paths {
+        let resolved = resolve_allowed_path(path, &config.allowed_roots)
+            .map_err(|e| api_error(StatusCode::FORBIDDEN, e))?;
+        file_paths.push(resolved.to_string_lossy().to_string());
+    }
+
+    let (_bundle_dir, bundle_paths) = if request.files.is_empty() {
+        (None, vec![])
+    } else {
+        let (dir, paths) = write_bundle(&request.files).map_err(|e| api_error(StatusCode::BAD_REQUEST, e))?;
+        (Some(dir), paths)
+    };
+    file_paths.extend(bundle_paths);
+
+    let paths_for_analysis = file_paths.clone();
+    let analysis = tokio::task::spawn_blocking(move || analyze_logs(paths_for_analysis))
+        .await
+        .map_err(|e| api_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Analysis task failed: {}", e)))?;
+
+    match analysis {
+        Ok(mut analysis) => {
+            cross_check_with_dataset(&file_paths, &mut analysis).await;
+            Ok(Json(analysis))
+        }
+        Err(e) => Err(api_error(StatusCode::UNPROCESSABLE_ENTITY, format!("Failed to analyze logs: {}", e))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_token() {
+        let config = AnalysisApiConfig { tokens: vec![String::new(), "s3cret".to_string()], ..Default::default() };
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::AUTHORIZATION, value.parse().unwrap());
+            headers
+        };
+        assert!(check_token(&config, &headers("Bearer s3cret")).is_ok());
+        assert_eq!(check_token(&config, &headers("Bearer s3cre")).unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(check_token(&config, &headers("Bearer ")).unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(check_token(&config, &HeaderMap::new()).unwrap_err().0, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_sanitize_bundle_path() {
+        assert!(sanitize_bundle_path("logs/base.log").is_ok());
+        assert!(sanitize_bundle_path("../etc/passwd").is_err());
+        assert!(sanitize_bundle_path("/etc/passwd").is_err());
+        assert!(sanitize_bundle_path("logs/./base.log").is_ok());
+        assert!(sanitize_bundle_path("").is_err());
+    }
+
+    #[test]
+    fn test_resolve_allowed_path() {
+        let root = tempfile::TempDir::new().unwrap();
+        let file = root.path().join("base.log");
+        std::fs::write(&file, "").unwrap();
+        let roots = vec![root.path().to_string_lossy().to_string()];
+
+        assert!(resolve_allowed_path(&file.to_string_lossy(), &roots).is_ok());
+        assert!(resolve_allowed_path(&file.to_string_lossy(), &[]).is_err());
+        let escaped = root.path().join("..").join(root.path().file_name().unwrap()).join("base.log");
+        assert!(resolve_allowed_path(&escaped.to_string_lossy(), &roots).is_ok());
+    }
+}
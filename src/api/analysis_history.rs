@@ -0,0 +1,207 @@
+//! Earlier analyses of a deliverable, for comparing re-runs
+//!
+//! `reviews/analysis_result.json` only holds the latest analysis. Each analysis that comes
+//! out different from the one before is also appended, trimmed to statuses and rule
+//! results, to `reviews/analysis_history.json`. When a deliverable is re-analyzed after a
+//! parser fix, the diff of the last two snapshots shows which failing checks went away,
+//! i.e. were the tool's fault rather than the deliverable's.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use crate::api::analysis_store::deliverable_folder_id;
+use crate::api::storage::review_store;
+use crate::app::types::{AnalysisRunDiff, AnalysisRunInfo, AnalysisSnapshot, LogAnalysisResult, RuleChange, StageStatusSummary, StatusChange};
+
+pub const ANALYSIS_HISTORY_FILE: &str = "reviews/analysis_history.json";
+/// Snapshots kept per deliverable; older ones are dropped
+pub const MAX_ANALYSIS_SNAPSHOTS: usize = 10;
+
+const STAGES: &[&str] = &["base", "before", "after", "agent", "report"];
+
+lazy_static! {
+    // Serializes load-append-save of history files within this server
+    static ref HISTORY_LOCK: Mutex<()> = Mutex::new(());
+}
+
+pub fn snapshot_of(result: &LogAnalysisResult) -> AnalysisSnapshot {
+    let settings = result.settings.as_ref();
+    AnalysisSnapshot {
+        analyzed_at: settings.map(|s| s.analyzed_at).unwrap_or(0),
+        app_version: settings.map(|s| s.app_version.clone()).unwrap_or_default(),
+        parser: settings.map(|s| s.parser.clone()).unwrap_or_default(),
+        rules_evaluated: result.rules_evaluated.clone(),
+        test_statuses: result.test_statuses.clone(),
+        rule_violations: result.rule_violations.clone(),
+    }
+}
+
+/// Append the snapshot unless it matches the latest one; returns whether it was appended
+pub fn push_snapshot(history: &mut Vec<AnalysisSnapshot>, snapshot: AnalysisSnapshot) -> bool {
+    if history.last().is_some_and(|last| last.test_statuses == snapshot.test_statuses && last.rule_violations == snapshot.rule_violations) {
+        return false;
+    }
+    history.push(snapshot);
+    let excess = history.len().saturating_sub(MAX_ANALYSIS_SNAPSHOTS);
+    history.drain(..excess);
+    true
+}
+
+fn run_info(snapshot: &AnalysisSnapshot) -> AnalysisRunInfo {
+    AnalysisRunInfo {
+        analyzed_at: snapshot.analyzed_at,
+        app_version: snapshot.app_version.clone(),
+        parser: snapshot.parser.clone(),
+    }
+}
+
+fn status_changes(list: &str, previous: &HashMap<String, StageStatusSummary>, current: &HashMap<String, StageStatusSummary>) -> Vec<StatusChange> {
+    let names: BTreeSet<&String> = previous.keys().chain(current.keys()).collect();
+    let mut changes = Vec::new();
+    for name in names {
+        for stage in STAGES {
+            let before = previous.get(name).and_then(|s| s.status_for(stage)).unwrap_or("");
+            let after = current.get(name).and_then(|s| s.status_for(stage)).unwrap_or("");
+            if before != after {
+                changes.push(StatusChange {
+                    test_name: name.clone(),
+                    list: list.to_string(),
+                    stage: stage.to_string(),
+                    previous: before.to_string(),
+                    current: after.to_string(),
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Status and rule differences from `previous` to `current`
+pub fn diff_snapshots(previous: &AnalysisSnapshot, current: &AnalysisSnapshot) -> AnalysisRunDiff {
+    let mut changes = status_changes("f2p", &previous.test_statuses.f2p, &current.test_statuses.f2p);
+    changes.extend(status_changes("p2p", &previous.test_statuses.p2p, &current.test_statuses.p2p));
+
+    let rule_changes = previous
+        .rule_violations
+        .entries()
+        .into_iter()
+        .zip(current.rule_violations.entries())
+        .filter(|((_, was), (_, is))| was != is)
+        .map(|((code, was), (_, is))| RuleChange {
+            code: code.to_string(),
+            was_violated: was.has_problem,
+            is_violated: is.has_problem,
+            added_examples: is.examples.iter().filter(|e| !was.examples.contains(e)).cloned().collect(),
+            removed_examples: was.examples.iter().filter(|e| !is.examples.contains(e)).cloned().collect(),
+        })
+        .collect();
+
+    AnalysisRunDiff {
+        previous: run_info(previous),
+        current: run_info(current),
+        status_changes: changes,
+        rule_changes,
+    }
+}
+
+/// Snapshots of the deliverable's analyses, oldest first; empty before the first analysis
+pub fn load_analysis_history(file_paths: &[String]) -> Result<Vec<AnalysisSnapshot>, String> {
+    let Some(folder_id) = deliverable_folder_id(file_paths) else {
+        return Ok(vec![]);
+    };
+    match review_store()?.load(&folder_id, ANALYSIS_HISTORY_FILE)? {
+        // A file that no longer parses is kept for inspection rather than overwritten
+        Some(content) => serde_json::from_str(&content).map_err(|e| format!("Failed to parse analysis history: {}", e)),
+        None => Ok(vec![]),
+    }
+}
+
+/// Add the analysis to the deliverable's history when it differs from the last one
+pub fn record_analysis_snapshot(file_paths: &[String], result: &LogAnalysisResult) -> Result<(), String> {
+    let folder_id = deliverable_folder_id(file_paths).ok_or("No deliverable folder to store the analysis history in")?;
+    let _guard = HISTORY_LOCK.lock().map_err(|_| "Analysis history lock poisoned".to_string())?;
+    let mut history = load_analysis_history(file_paths)?;
+    if !push_snapshot(&mut history, snapshot_of(result)) {
+        return Ok(());
+    }
+    let content = serde_json::to_string_pretty(&history).map_err(|e| format!("Failed to serialize analysis history: {}", e))?;
+    review_store()?.save(&folder_id, ANALYSIS_HISTORY_FILE, &content)
+}
+
+/// Diff of the two latest differing analyses, once the deliverable was analyzed with different outcomes
+pub fn latest_run_diff(file_paths: &[String]) -> Result<Option<AnalysisRunDiff>, String> {
+    let history = load_analysis_history(file_paths)?;
+    Ok(match history.as_slice() {
+        [.., previous, current] => Some(diff_snapshots(previous, current)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::types::{GroupedTestStatuses, RuleViolation, RuleViolations};
+
+    fn summary(after: &str) -> StageStatusSummary {
+        StageStatusSummary {
+            base: "passed".to_string(),
+            before: "failed".to_string(),
+            after: after.to_string(),
+            agent: "passed".to_string(),
+            report: "passed".to_string(),
+        }
+    }
+
+    fn snapshot(after: &str, c2_examples: &[&str]) -> AnalysisSnapshot {
+        let rule_violations = RuleViolations {
+            c2_failed_in_after_present_in_f2p_or_p2p: RuleViolation {
+                has_problem: !c2_examples.is_empty(),
+                examples: c2_examples.iter().map(|e| e.to_string()).collect(),
+            },
+            ..Default::default()
+        };
+        AnalysisSnapshot {
+            analyzed_at: 0,
+            app_version: String::new(),
+            parser: "rust".to_string(),
+            rules_evaluated: vec![],
+            test_statuses: GroupedTestStatuses {
+                f2p: [("parse".to_string(), summary(after))].into_iter().collect(),
+                p2p: HashMap::new(),
+            },
+            rule_violations,
+        }
+    }
+
+    #[test]
+    fn test_push_snapshot_skips_repeats_and_trims() {
+        let mut history = vec![];
+        assert!(push_snapshot(&mut history, snapshot("failed", &["parse"])));
+        assert!(!push_snapshot(&mut history, snapshot("failed", &["parse"])));
+        for i in 0..MAX_ANALYSIS_SNAPSHOTS {
+            push_snapshot(&mut history, snapshot(if i % 2 == 0 { "passed" } else { "failed" }, &[]));
+        }
+        assert_eq!(history.len(), MAX_ANALYSIS_SNAPSHOTS);
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let diff = diff_snapshots(&snapshot("failed", &["parse"]), &snapshot("passed", &[]));
+        assert_eq!(diff.status_changes, vec![StatusChange {
+            test_name: "parse".to_string(),
+            list: "f2p".to_string(),
+            stage: "after".to_string(),
+            previous: "failed".to_string(),
+            current: "passed".to_string(),
+        }]);
+        assert_eq!(diff.rule_changes, vec![RuleChange {
+            code: "C2".to_string(),
+            was_violated: true,
+            is_violated: false,
+            added_examples: vec![],
+            removed_examples: vec!["parse".to_string()],
+        }]);
+    }
+}
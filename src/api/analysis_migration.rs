@@ -0,0 +1,175 @@
+//! Reading persisted analyses written by older versions of the app
+//!
+//! Results are stamped with `ANALYSIS_SCHEMA_VERSION`. Before deserializing a stored or
+//! imported result, the raw JSON is brought up to the current version one step at a time,
+//! so the review-time history and anything else reading old `analysis_result.json` files
+//! keeps working as `LogAnalysisResult` grows.
+
+use serde_json::{Map, Value};
+
+use crate::app::types::{LogAnalysisResult, ANALYSIS_SCHEMA_VERSION};
+
+// Rule codes and their fields in `rule_violations`, in C1..C11 order
+const RULE_FIELDS: &[(&str, &str)] = &[
+    ("C1", "c1_failed_in_base_present_in_p2p"),
+    ("C2", "c2_failed_in_after_present_in_f2p_or_p2p"),
+    ("C3", "c3_f2p_success_in_before"),
+    ("C4", "c4_p2p_missing_in_base_and_not_passing_in_before"),
+    ("C5", "c5_duplicates_in_same_log"),
+    ("C6", "c6_test_marked_failed_in_report_but_passing_in_agent"),
+    ("C7", "c7_f2p_tests_in_golden_source_diff"),
+    ("C8", "c8_test_lists_differ_from_dataset"),
+    ("C9", "c9_patch_failed_to_apply"),
+    ("C10", "c10_p2p_list_too_narrow"),
+    ("C11", "c11_f2p_skipped_in_after_or_agent"),
+];
+
+fn object_entry<'a>(object: &'a mut Map<String, Value>, key: &str) -> &'a mut Map<String, Value> {
+    let entry = object.entry(key.to_string()).or_insert_with(|| Value::Object(Map::new()));
+    if !entry.is_object() {
+        *entry = Value::Object(Map::new());
+    }
+    entry.as_object_mut().unwrap()
+}
+
+// 0 -> 1: unversioned results. Rules missing from `rule_violations` were never run, so they
+// are filled in as clean but left out of `rules_evaluated`; C8 was silently skipped without a
+// dataset, so an unflagged C8 doesn't count as evaluated either
+fn migrate_v0(result: &mut Map<String, Value>) {
+    let violations = object_entry(result, "rule_violations");
+    let mut evaluated = Vec::new();
+    for (code, field) in RULE_FIELDS {
+        match violations.get(*field) {
+            Some(violation) => {
+                let flagged = violation.get("has_problem").and_then(Value::as_bool).unwrap_or(false);
+                if *code != "C8" || flagged {
+                    evaluated.push(Value::String(code.to_string()));
+                }
+            }
+            None => {
+                violations.insert(field.to_string(), serde_json::json!({ "has_problem": false, "examples": [] }));
+            }
+        }
+    }
+    result.insert("rules_evaluated".to_string(), Value::Array(evaluated));
+
+    let debug_info = object_entry(result, "debug_info");
+    debug_info.entry("log_counts").or_insert_with(|| Value::Array(vec![]));
+    debug_info.entry("duplicate_examples_per_log").or_insert_with(|| Value::Object(Map::new()));
+}
+
+// 1 -> 2: match rates counted F2P tests in the before stage, where most are missing by design;
+// dropping them has them recomputed below
+fn migrate_v1(result: &mut Map<String, Value>) {
+    result.remove("match_rates");
+}
+
+/// Bring a stored result up to the current schema and deserialize it
+pub fn migrate_analysis_result(mut value: Value) -> Result<LogAnalysisResult, String> {
+    let result = value.as_object_mut().ok_or("Analysis result is not a JSON object")?;
+    let version = result.get("schema_version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version > ANALYSIS_SCHEMA_VERSION {
+        // Written by a newer app; unknown fields are ignored and the rest is read as is
+        eprintln!("Analysis result has schema version {}, newer than {}", version, ANALYSIS_SCHEMA_VERSION);
+    }
+    if version < 1 {
+        migrate_v0(result);
+    }
+    if version < 2 {
+        migrate_v1(result);
+    }
+    if version < ANALYSIS_SCHEMA_VERSION {
+        result.insert("schema_version".to_string(), Value::from(ANALYSIS_SCHEMA_VERSION));
+    }
+    let mut analysis: LogAnalysisResult = serde_json::from_value(value).map_err(|e| format!("Failed to read analysis result: {}", e))?;
+    // Derived from the statuses, so results stored before it was recorded get it here
+    if analysis.match_rates.is_empty() {
+        analysis.match_rates = analysis.test_statuses.match_rates();
+    }
+    Ok(analysis)
+}
+
+/// Parse a persisted or exported analysis, migrating it from older schema versions
+pub fn parse_analysis_result(content: &str) -> Result<LogAnalysisResult, String> {
+    let value: Value = serde_json::from_str(content).map_err(|e| format!("Invalid analysis JSON: {}", e))?;
+    migrate_analysis_result(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrates_unversioned_result() {
+        let content = r#"{
+            "test_statuses": { "f2p": {}, "p2p": {} },
+            "rule_violations": {
+                "c1_failed_in_base_present_in_p2p": { "has_problem": false, "examples": [] },
+                "c2_failed_in_after_present_in_f2p_or_p2p": { "has_problem": true, "examples": ["test_a"] },
+                "c3_f2p_success_in_before": { "has_problem": false, "examples": [] },
+                "c4_p2p_missing_in_base_and_not_passing_in_before": { "has_problem": false, "examples": [] },
+                "c5_duplicates_in_same_log": { "has_problem": false, "examples": [] },
+                "c6_test_marked_failed_in_report_but_passing_in_agent": { "has_problem": false, "examples": [] },
+                "c8_test_lists_differ_from_dataset": { "has_problem": false, "examples": [] }
+            },
+            "debug_info": { "log_counts": [] },
+            "review_effort": { "started_at": 10, "decided_at": 70, "seconds": 60, "verdict": "approved" }
+        }"#;
+
+        let analysis = parse_analysis_result(content).unwrap();
+        assert_eq!(analysis.schema_version, ANALYSIS_SCHEMA_VERSION);
+        assert_eq!(analysis.rules_evaluated, vec!["C1", "C2", "C3", "C4", "C5", "C6"]);
+        assert!(analysis.rule_violations.c2_failed_in_after_present_in_f2p_or_p2p.has_problem);
+        assert!(!analysis.rule_violations.c7_f2p_tests_in_golden_source_diff.has_problem);
+        assert_eq!(analysis.review_effort.map(|effort| effort.seconds), Some(60));
+    }
+
+    #[test]
+    fn test_current_results_pass_through() {
+        let content = r#"{
+            "schema_version": 2,
+            "rules_evaluated": ["C1", "C8"],
+            "test_statuses": { "f2p": {}, "p2p": {} },
+            "rule_violations": {
+                "c1_failed_in_base_present_in_p2p": { "has_problem": false, "examples": [] },
+                "c2_failed_in_after_present_in_f2p_or_p2p": { "has_problem": false, "examples": [] },
+                "c3_f2p_success_in_before": { "has_problem": false, "examples": [] },
+                "c4_p2p_missing_in_base_and_not_passing_in_before": { "has_problem": false, "examples": [] },
+                "c5_duplicates_in_same_log": { "has_problem": false, "examples": [] },
+                "c6_test_marked_failed_in_report_but_passing_in_agent": { "has_problem": false, "examples": [] },
+                "c7_f2p_tests_in_golden_source_diff": { "has_problem": false, "examples": [] }
+            },
+            "debug_info": { "log_counts": [], "duplicate_examples_per_log": {} }
+        }"#;
+
+        let analysis = parse_analysis_result(content).unwrap();
+        assert_eq!(analysis.rules_evaluated, vec!["C1", "C8"]);
+        assert!(parse_analysis_result("[]").is_err());
+    }
+
+    #[test]
+    fn test_v1_match_rates_are_recomputed() {
+        let content = r#"{
+            "schema_version": 1,
+            "test_statuses": {
+                "f2p": { "test_new": { "base": "missing", "before": "missing", "after": "passed", "agent": "no_log", "report": "passed" } },
+                "p2p": {}
+            },
+            "rule_violations": {
+                "c1_failed_in_base_present_in_p2p": { "has_problem": false, "examples": [] },
+                "c2_failed_in_after_present_in_f2p_or_p2p": { "has_problem": false, "examples": [] },
+                "c3_f2p_success_in_before": { "has_problem": false, "examples": [] },
+                "c4_p2p_missing_in_base_and_not_passing_in_before": { "has_problem": false, "examples": [] },
+                "c5_duplicates_in_same_log": { "has_problem": false, "examples": [] },
+                "c6_test_marked_failed_in_report_but_passing_in_agent": { "has_problem": false, "examples": [] },
+                "c7_f2p_tests_in_golden_source_diff": { "has_problem": false, "examples": [] }
+            },
+            "debug_info": { "log_counts": [], "duplicate_examples_per_log": {} },
+            "match_rates": [{ "stage": "before", "f2p_resolved": 0, "f2p_total": 1, "p2p_resolved": 0, "p2p_total": 0 }]
+        }"#;
+
+        let analysis = parse_analysis_result(content).unwrap();
+        let stages: Vec<&str> = analysis.match_rates.iter().map(|rate| rate.stage.as_str()).collect();
+        assert_eq!(stages, vec!["base", "after"]);
+    }
+}
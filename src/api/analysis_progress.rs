@@ -0,0 +1,162 @@
+//! Phase-by-phase progress of running analyses
+//!
+//! An analysis locates the logs, parses each stage, reads report.json, runs the rule checks
+//! and then cross-checks and stores the result; any of these can hang on a huge or odd log.
+//! The analysis reports each phase here under an id the browser picked, and the browser
+//! polls the id while it waits, so a stuck run shows the phase it's stuck in.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+
+use crate::app::types::{AnalysisPhase, AnalysisProgress};
+
+pub const PHASE_LOCATE: &str = "locate";
+pub const PHASE_REPORT: &str = "report";
+pub const PHASE_RULES: &str = "rules";
+pub const PHASE_FINISH: &str = "finish";
+
+// (key, label) in running order; stage phases use the stage name as key
+const PHASES: &[(&str, &str)] = &[
+    (PHASE_LOCATE, "Locating files"),
+    ("base", "Parsing base log"),
+    ("before", "Parsing before log"),
+    ("after", "Parsing after log"),
+    ("agent", "Parsing agent log"),
+    (PHASE_REPORT, "Parsing report.json"),
+    (PHASE_RULES, "Rule checks"),
+    (PHASE_FINISH, "Cross-checking and saving"),
+];
+
+// Finished runs are kept this long for a last poll, then dropped
+const KEEP_FOR: Duration = Duration::from_secs(10 * 60);
+// Ids come from the browser; anything longer isn't one of ours
+const MAX_ID_LEN: usize = 64;
+
+struct RunProgress {
+    phases: Vec<AnalysisPhase>,
+    // When each active phase started
+    started: HashMap<String, Instant>,
+    updated: Instant,
+}
+
+lazy_static! {
+    static ref RUNS: Mutex<HashMap<String, RunProgress>> = Mutex::new(HashMap::new());
+}
+
+/// Handle an analysis reports its phases through; a disabled one ignores every report
+#[derive(Clone, Debug, Default)]
+pub struct ProgressReporter {
+    id: Option<String>,
+}
+
+impl ProgressReporter {
+    /// Register a run with every phase pending; `None` or an unusable id gives a disabled reporter
+    pub fn start(id: Option<String>) -> Self {
+        let Some(id) = id.filter(|id| !id.is_empty() && id.len() <= MAX_ID_LEN) else {
+            return Self::default();
+        };
+        let phases = PHASES.iter().map(|(key, label)| AnalysisPhase {
+            key: key.to_string(),
+            label: label.to_string(),
+            status: "pending".to_string(),
+            elapsed_ms: 0,
+        }).collect();
+        if let Ok(mut runs) = RUNS.lock() {
+            runs.retain(|_, run| run.updated.elapsed() < KEEP_FOR);
+            runs.insert(id.clone(), RunProgress { phases, started: HashMap::new(), updated: Instant::now() });
+        }
+        Self { id: Some(id) }
+    }
+
+    fn update(&self, key: &str, status: &str) {
+        let Some(id) = &self.id else { return };
+        let Ok(mut runs) = RUNS.lock() else { return };
+        let Some(run) = runs.get_mut(id) else { return };
+        run.updated = Instant::now();
+        if status == "active" {
+            run.started.insert(key.to_string(), Instant::now());
+        }
+        let started = run.started.get(key).copied();
+        if let Some(phase) = run.phases.iter_mut().find(|phase| phase.key == key) {
+            if status != "active" {
+                phase.elapsed_ms = started.map(|started| started.elapsed().as_millis() as u64).unwrap_or(0);
+            }
+            phase.status = status.to_string();
+        }
+    }
+
+    pub fn begin(&self, key: &str) {
+        self.update(key, "active");
+    }
+
+    pub fn done(&self, key: &str) {
+        self.update(key, "done");
+    }
+
+    pub fn skip(&self, key: &str) {
+        self.update(key, "skipped");
+    }
+
+    /// Mark whichever phase is running as the one that failed
+    pub fn fail(&self) {
+        let active: Vec<String> = self.id.as_ref()
+            .and_then(|id| RUNS.lock().ok()?.get(id).map(|run| {
+                run.phases.iter().filter(|phase| phase.status == "active").map(|phase| phase.key.clone()).collect()
+            }))
+            .unwrap_or_default();
+        for key in active {
+            self.update(&key, "failed");
+        }
+    }
+}
+
+/// Current phases of run `id`, with active phases timed up to now
+pub fn analysis_progress(id: &str) -> Option<AnalysisProgress> {
+    let runs = RUNS.lock().ok()?;
+    let run = runs.get(id)?;
+    let phases = run.phases.iter().map(|phase| {
+        let mut phase = phase.clone();
+        if phase.status == "active" {
+            if let Some(started) = run.started.get(&phase.key) {
+                phase.elapsed_ms = started.elapsed().as_millis() as u64;
+            }
+        }
+        phase
+    }).collect();
+    Some(AnalysisProgress { id: id.to_string(), phases })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statuses(id: &str) -> Vec<(String, String)> {
+        analysis_progress(id).unwrap().phases.into_iter().map(|phase| (phase.key, phase.status)).collect()
+    }
+
+    #[test]
+    fn test_reports_phases_and_marks_the_failed_one() {
+        let reporter = ProgressReporter::start(Some("test-run-1".to_string()));
+        reporter.begin(PHASE_LOCATE);
+        reporter.done(PHASE_LOCATE);
+        reporter.skip("base");
+        reporter.begin("before");
+        reporter.fail();
+        let found = statuses("test-run-1");
+        assert_eq!(found[0], (PHASE_LOCATE.to_string(), "done".to_string()));
+        assert_eq!(found[1], ("base".to_string(), "skipped".to_string()));
+        assert_eq!(found[2], ("before".to_string(), "failed".to_string()));
+        assert!(found[3..].iter().all(|(_, status)| status == "pending"));
+    }
+
+    #[test]
+    fn test_disabled_reporter_registers_nothing() {
+        let reporter = ProgressReporter::start(None);
+        reporter.begin(PHASE_LOCATE);
+        assert!(ProgressReporter::start(Some("x".repeat(MAX_ID_LEN + 1))).id.is_none());
+        assert_eq!(analysis_progress("never-started"), None);
+    }
+}
@@ -0,0 +1,82 @@
+//! Input files recorded with an analysis
+//!
+//! An exported or stored analysis names the files it read for each stage, the parser that
+//! read each log, and every file's size and SHA-256, so a result can be reproduced from
+//! the same deliverable and a changed log is caught instead of silently re-analyzed.
+
+use std::io::Read;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::api::storage::deliverable_cache_dir;
+use crate::app::types::AnalysisSource;
+
+// Path inside the deliverable folder: the cache folder and the folder id are dropped
+fn deliverable_relative_path(path: &Path, cache_dir: Option<&Path>) -> String {
+    let relative = cache_dir
+        .and_then(|cache_dir| path.strip_prefix(cache_dir).ok())
+        .map(|relative| relative.components().skip(1).collect::<std::path::PathBuf>());
+    match relative {
+        Some(relative) if !relative.as_os_str().is_empty() => relative.to_string_lossy().replace('\\', "/"),
+        _ => path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+    }
+}
+
+fn hash_file(path: &Path) -> std::io::Result<(u64, String)> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    let mut size = 0u64;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        size += read as u64;
+        hasher.update(&buffer[..read]);
+    }
+    Ok((size, format!("{:x}", hasher.finalize())))
+}
+
+/// Describe one input file; `None` (with a log line) when it can't be read
+pub fn describe_source(role: &str, path: &str, parser: Option<&str>) -> Option<AnalysisSource> {
+    let path = Path::new(path);
+    match hash_file(path) {
+        Ok((size, sha256)) => Some(AnalysisSource {
+            role: role.to_string(),
+            path: deliverable_relative_path(path, deliverable_cache_dir().as_deref()),
+            parser: parser.map(str::to_string),
+            size,
+            sha256,
+        }),
+        Err(e) => {
+            eprintln!("Failed to hash {} for the analysis sources: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashes_and_sizes_the_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("after.log");
+        std::fs::write(&path, "abc").unwrap();
+        let source = describe_source("after", &path.to_string_lossy(), Some("pytest")).unwrap();
+        assert_eq!(source.size, 3);
+        assert_eq!(source.sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(source.parser.as_deref(), Some("pytest"));
+        assert!(describe_source("after", &dir.path().join("missing.log").to_string_lossy(), None).is_none());
+    }
+
+    #[test]
+    fn test_paths_are_relative_to_the_deliverable_folder() {
+        let cache = Path::new("/tmp/swe-reviewer-temp");
+        assert_eq!(deliverable_relative_path(&cache.join("abc123/logs/after.log"), Some(cache)), "logs/after.log");
+        assert_eq!(deliverable_relative_path(Path::new("/elsewhere/report.json"), Some(cache)), "report.json");
+    }
+}
@@ -0,0 +1,148 @@
+//! Latest analysis of a deliverable, persisted with the options it ran with
+//!
+//! Written as `reviews/analysis_result.json` of the deliverable's folder in the configured
+//! review store (a file next to the cached deliverable by default) each time a reviewer
+//! analyzes it. The embedded `AnalysisSettings` record the language, parser, rule config,
+//! noise filters and edited test lists, so a result can be audited and the same analysis
+//! reproduced later. Results written by older versions are migrated on load.
+//!
+//! The review timer lives next to it: `reviews/review_timer.json` is written when a reviewer
+//! loads the deliverable, and recording a decision turns it into the result's `review_effort`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::analysis_migration::parse_analysis_result;
+use crate::api::storage::review_store;
+use crate::app::types::{LogAnalysisResult, ReviewEffort, ReviewEffortSummary};
+
+pub const ANALYSIS_RESULT_FILE: &str = "reviews/analysis_result.json";
+pub const REVIEW_TIMER_FILE: &str = "reviews/review_timer.json";
+
+#[derive(Serialize, Deserialize)]
+struct ReviewTimer {
+    started_at: u64,
+}
+
+/// Cache folder of the deliverable behind a set of relative file paths (`<folder_id>/logs/...`)
+pub fn deliverable_folder_id(file_paths: &[String]) -> Option<String> {
+    file_paths.first()?.split('/').next().filter(|id| !id.is_empty()).map(str::to_string)
+}
+
+/// Persist the result, replacing the previous one for the deliverable; a recorded review time is kept
+pub fn save_analysis_result(file_paths: &[String], result: &LogAnalysisResult) -> Result<(), String> {
+    let folder_id = deliverable_folder_id(file_paths).ok_or("No deliverable folder to store the analysis in")?;
+    let mut result = result.clone();
+    if result.review_effort.is_none() {
+        result.review_effort = load_analysis_result(file_paths).and_then(|previous| previous.review_effort);
+    }
+    let content = serde_json::to_string_pretty(&result).map_err(|e| format!("Failed to serialize analysis: {}", e))?;
+    review_store()?.save(&folder_id, ANALYSIS_RESULT_FILE, &content)
+}
+
+/// The last persisted result for the deliverable, if any
+pub fn load_analysis_result(file_paths: &[String]) -> Option<LogAnalysisResult> {
+    load_folder_analysis_result(&deliverable_folder_id(file_paths)?)
+}
+
+/// The last persisted result for the deliverable cached in `folder_id` (its Drive folder id)
+pub fn load_folder_analysis_result(folder_id: &str) -> Option<LogAnalysisResult> {
+    let content = review_store()
+        .and_then(|store| store.load(folder_id, ANALYSIS_RESULT_FILE))
+        .map_err(|e| eprintln!("{}", e))
+        .ok()??;
+    parse_analysis_result(&content).map_err(|e| eprintln!("{}", e)).ok()
+}
+
+/// Start timing the review unless a timer for this deliverable is already running
+pub fn start_review_timer(file_paths: &[String], now: u64) -> Result<(), String> {
+    let folder_id = deliverable_folder_id(file_paths).ok_or("No deliverable folder to time the review in")?;
+    let store = review_store()?;
+    if store.load(&folder_id, REVIEW_TIMER_FILE)?.is_some() {
+        return Ok(());
+    }
+    let content = serde_json::to_string(&ReviewTimer { started_at: now }).map_err(|e| format!("Failed to serialize review timer: {}", e))?;
+    store.save(&folder_id, REVIEW_TIMER_FILE, &content)
+}
+
+/// Stop the running timer and record the review time with the analysis result
+pub fn stop_review_timer(file_paths: &[String], verdict: &str, now: u64) -> Option<ReviewEffort> {
+    let folder_id = deliverable_folder_id(file_paths)?;
+    let store = review_store().map_err(|e| eprintln!("{}", e)).ok()?;
+    let timer: ReviewTimer = serde_json::from_str(&store.load(&folder_id, REVIEW_TIMER_FILE).ok()??).ok()?;
+    if let Err(e) = store.remove(&folder_id, REVIEW_TIMER_FILE) {
+        eprintln!("Failed to clear review timer: {}", e);
+    }
+
+    let effort = ReviewEffort {
+        started_at: timer.started_at,
+        decided_at: now,
+        seconds: now.saturating_sub(timer.started_at),
+        verdict: verdict.to_string(),
+    };
+    if let Some(mut analysis) = load_analysis_result(file_paths) {
+        analysis.review_effort = Some(effort.clone());
+        if let Err(e) = save_analysis_result(file_paths, &analysis) {
+            eprintln!("Failed to record review time: {}", e);
+        }
+    }
+    Some(effort)
+}
+
+/// Count, total, mean and median of the given review times
+pub fn summarize_review_efforts(efforts: &[ReviewEffort]) -> ReviewEffortSummary {
+    if efforts.is_empty() {
+        return ReviewEffortSummary::default();
+    }
+    let mut seconds: Vec<u64> = efforts.iter().map(|effort| effort.seconds).collect();
+    seconds.sort_unstable();
+    let total_seconds: u64 = seconds.iter().sum();
+    let middle = seconds.len() / 2;
+    let median_seconds = if seconds.len().is_multiple_of(2) { (seconds[middle - 1] + seconds[middle]) / 2 } else { seconds[middle] };
+    ReviewEffortSummary {
+        reviews: seconds.len(),
+        total_seconds,
+        mean_seconds: total_seconds / seconds.len() as u64,
+        median_seconds,
+    }
+}
+
+/// Every analysis persisted on this server, migrated to the current schema; unreadable ones are skipped
+pub fn stored_analyses() -> Vec<LogAnalysisResult> {
+    let documents = review_store()
+        .and_then(|store| store.load_all(ANALYSIS_RESULT_FILE))
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            vec![]
+        });
+    documents.iter().filter_map(|content| parse_analysis_result(content).ok()).collect()
+}
+
+/// Review times of the analyses with a recorded decision
+pub fn review_effort_summary_of(analyses: &[LogAnalysisResult]) -> ReviewEffortSummary {
+    let efforts: Vec<ReviewEffort> = analyses.iter().filter_map(|analysis| analysis.review_effort.clone()).collect();
+    summarize_review_efforts(&efforts)
+}
+
+/// Review times across every cached deliverable with a recorded decision
+pub fn review_effort_summary() -> ReviewEffortSummary {
+    review_effort_summary_of(&stored_analyses())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effort(seconds: u64) -> ReviewEffort {
+        ReviewEffort { started_at: 0, decided_at: seconds, seconds, verdict: "approved".to_string() }
+    }
+
+    #[test]
+    fn test_summarize_review_efforts() {
+        let summary = summarize_review_efforts(&[effort(600), effort(60), effort(300), effort(1200)]);
+        assert_eq!(
+            summary,
+            ReviewEffortSummary { reviews: 4, total_seconds: 2160, mean_seconds: 540, median_seconds: 450 }
+        );
+        assert_eq!(summarize_review_efforts(&[]), ReviewEffortSummary::default());
+    }
+}
@@ -0,0 +1,181 @@
+//! Batch analysis of many deliverable links
+//!
+//! A list of links goes through the checker's own validate → download → analyze steps on the
+//! server, `limits.batch_concurrency` deliverables at a time, while the batch page polls each
+//! link's state and rule summary. Results are stored like any other analysis, so opening a
+//! deliverable from the batch table in the normal checker finds the same result.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use tokio::sync::Semaphore;
+
+use crate::api::analysis_history::record_analysis_snapshot;
+use crate::api::analysis_store::save_analysis_result;
+use crate::api::deliverable::{download_deliverable_impl, validate_deliverable_impl};
+use crate::api::download_progress::DownloadReporter;
+use crate::api::github_actions::extract_actions_artifacts;
+use crate::api::log_analysis::analyze_logs;
+use crate::api::review_queue::queue_link_key;
+use crate::api::server_config::load_server_config;
+use crate::api::swebench_dataset::cross_check_with_dataset;
+use crate::app::types::{BatchItem, BatchStatus, ACTIONS_FOLDER_PREFIX};
+
+pub const MAX_BATCH_LINKS: usize = 100;
+const DEFAULT_CONCURRENCY: usize = 3;
+// Finished batches stay this long for the page to poll, then are dropped
+const KEEP_FOR: Duration = Duration::from_secs(60 * 60);
+
+struct BatchRun {
+    items: Vec<BatchItem>,
+    updated: Instant,
+}
+
+lazy_static! {
+    static ref BATCHES: Mutex<HashMap<String, BatchRun>> = Mutex::new(HashMap::new());
+}
+
+fn update_item(batch_id: &str, index: usize, update: impl FnOnce(&mut BatchItem)) {
+    let Ok(mut batches) = BATCHES.lock() else { return };
+    if let Some(batch) = batches.get_mut(batch_id) {
+        batch.updated = Instant::now();
+        if let Some(item) = batch.items.get_mut(index) {
+            update(item);
+        }
+    }
+}
+
+fn set_status(batch_id: &str, index: usize, status: &str) {
+    update_item(batch_id, index, |item| item.status = status.to_string());
+}
+
+// Trimmed links without blanks or repeats; `?usp=sharing` variants of one folder are one link
+fn unique_links(links: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    links
+        .into_iter()
+        .map(|link| link.trim().to_string())
+        .filter(|link| !link.is_empty() && seen.insert(queue_link_key(link)))
+        .collect()
+}
+
+async fn process_link(batch_id: &str, index: usize, link: String) -> Result<(), String> {
+    set_status(batch_id, index, "validating");
+    let validation = validate_deliverable_impl(link, false).await.map_err(|e| e.to_string())?;
+    let folder_id = validation.folder_id.clone();
+    update_item(batch_id, index, |item| {
+        item.folder_id = Some(folder_id.clone());
+        item.status = "downloading".to_string();
+    });
+    let mut download = download_deliverable_impl(validation.files_to_download, validation.folder_id, &DownloadReporter::default())
+        .await
+        .map_err(|e| e.to_string())?;
+    if folder_id.starts_with(ACTIONS_FOLDER_PREFIX) {
+        download = extract_actions_artifacts(&folder_id, false).map_err(|e| e.to_string())?;
+    }
+
+    set_status(batch_id, index, "analyzing");
+    let file_paths: Vec<String> = download.downloaded_files.iter().map(|file| file.path.clone()).collect();
+    let paths_for_analysis = file_paths.clone();
+    let mut analysis = tokio::task::spawn_blocking(move || analyze_logs(paths_for_analysis))
+        .await
+        .map_err(|e| format!("Analysis task failed: {}", e))??;
+    cross_check_with_dataset(&file_paths, &mut analysis).await;
+    if let Err(e) = save_analysis_result(&file_paths, &analysis) {
+        eprintln!("Failed to persist analysis result: {}", e);
+    }
+    if let Err(e) = record_analysis_snapshot(&file_paths, &analysis) {
+        eprintln!("Failed to record analysis snapshot: {}", e);
+    }
+
+    let violated = analysis.rule_violations.entries()
+        .into_iter()
+        .filter(|(_, violation)| violation.has_problem)
+        .map(|(code, _)| code.to_string())
+        .collect();
+    let summary = analysis.rule_violations.summary();
+    update_item(batch_id, index, |item| {
+        item.status = "done".to_string();
+        item.summary = Some(summary);
+        item.violated = violated;
+    });
+    Ok(())
+}
+
+/// Queue every link and start working through them in the background
+pub fn start_batch(links: Vec<String>) -> Result<BatchStatus, String> {
+    let links = unique_links(links);
+    if links.is_empty() {
+        return Err("Paste at least one deliverable link".to_string());
+    }
+    if links.len() > MAX_BATCH_LINKS {
+        return Err(format!("A batch takes at most {} links, got {}", MAX_BATCH_LINKS, links.len()));
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let items = links.iter().map(|link| BatchItem {
+        link: link.clone(),
+        status: "queued".to_string(),
+        folder_id: None,
+        error: None,
+        summary: None,
+        violated: Vec::new(),
+    }).collect();
+    {
+        let mut batches = BATCHES.lock().map_err(|_| "Batch registry is unavailable".to_string())?;
+        batches.retain(|_, batch| batch.updated.elapsed() < KEEP_FOR);
+        batches.insert(id.clone(), BatchRun { items, updated: Instant::now() });
+    }
+
+    let concurrency = load_server_config().limits.batch_concurrency.filter(|n| *n > 0).unwrap_or(DEFAULT_CONCURRENCY);
+    let permits = Arc::new(Semaphore::new(concurrency));
+    for (index, link) in links.into_iter().enumerate() {
+        let permits = permits.clone();
+        let batch_id = id.clone();
+        tokio::spawn(async move {
+            let Ok(_permit) = permits.acquire_owned().await else { return };
+            if let Err(e) = process_link(&batch_id, index, link).await {
+                update_item(&batch_id, index, |item| {
+                    item.status = "failed".to_string();
+                    item.error = Some(e);
+                });
+            }
+        });
+    }
+    batch_status(&id).ok_or_else(|| "Batch disappeared before it started".to_string())
+}
+
+pub fn batch_status(id: &str) -> Option<BatchStatus> {
+    let batches = BATCHES.lock().ok()?;
+    let batch = batches.get(id)?;
+    Some(BatchStatus { id: id.to_string(), items: batch.items.clone() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_are_trimmed_and_deduplicated() {
+        let links = unique_links(vec![
+            " https://drive.google.com/drive/folders/abc123 ".to_string(),
+            String::new(),
+            "https://drive.google.com/drive/folders/abc123?usp=sharing".to_string(),
+            "https://drive.google.com/drive/folders/def456".to_string(),
+        ]);
+        assert_eq!(links, vec![
+            "https://drive.google.com/drive/folders/abc123".to_string(),
+            "https://drive.google.com/drive/folders/def456".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_empty_and_oversized_batches_are_refused() {
+        assert!(start_batch(vec!["  ".to_string()]).is_err());
+        let links = (0..=MAX_BATCH_LINKS).map(|i| format!("https://drive.google.com/drive/folders/f{}", i)).collect();
+        assert!(start_batch(links).unwrap_err().contains("at most"));
+        assert_eq!(batch_status("never-started"), None);
+    }
+}
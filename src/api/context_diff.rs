@@ -0,0 +1,133 @@
+//! What changed around a test between the before and after runs
+//!
+//! The lines following a test's failure report (assertion message, panic, stack trace) are
+//! cut from both logs and diffed line by line, so the reviewer sees how the failure changed
+//! instead of comparing two panes by eye. Timings and addresses differ on every run, so
+//! they are ignored when deciding whether two lines match.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::{ContextDiff, DiffLine, DiffLineKind};
+
+// Window around the anchor line: the report header is followed by the interesting part
+const LINES_BEFORE_ANCHOR: usize = 2;
+const LINES_AFTER_ANCHOR: usize = 30;
+
+lazy_static! {
+    // Headers of per-test failure sections: cargo `---- name stdout ----`, pytest `____ name ____`,
+    // jest `● Suite › name`, unittest `FAIL: name (...)`
+    static ref FAILURE_HEADER_RE: Regex = Regex::new(r"^\s*(?:-{3,} .+ (?:stdout|stderr) -{3,}|_{3,} .+ _{3,}|●\s|(?:FAIL|ERROR): )").unwrap();
+    static ref VOLATILE_RE: Regex = Regex::new(r"0x[0-9a-fA-F]+|\d+(?:\.\d+)?\s?(?:ms|s)\b").unwrap();
+}
+
+/// Line to anchor a test's window on: its first failure section header, else its last mention
+pub fn pick_anchor(lines: &[&str], matches: &[usize]) -> Option<usize> {
+    matches
+        .iter()
+        .copied()
+        .find(|&line| line.checked_sub(1).and_then(|index| lines.get(index)).is_some_and(|text| FAILURE_HEADER_RE.is_match(text)))
+        .or_else(|| matches.last().copied())
+}
+
+fn window(lines: &[&str], anchor: usize) -> (usize, Vec<String>) {
+    let first = anchor.saturating_sub(LINES_BEFORE_ANCHOR).max(1);
+    let last = (anchor + LINES_AFTER_ANCHOR).min(lines.len()).max(first - 1);
+    (first, lines[first - 1..last].iter().map(|line| line.trim_end().to_string()).collect())
+}
+
+fn comparable(line: &str) -> String {
+    VOLATILE_RE.replace_all(line.trim(), "_").into_owned()
+}
+
+/// Line diff of two windows by longest common subsequence, numbered from their first lines
+pub fn diff_lines(before: &[String], before_first: usize, after: &[String], after_first: usize) -> Vec<DiffLine> {
+    let old: Vec<String> = before.iter().map(|line| comparable(line)).collect();
+    let new: Vec<String> = after.iter().map(|line| comparable(line)).collect();
+
+    // common[i][j]: LCS length of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] { common[i + 1][j + 1] + 1 } else { common[i + 1][j].max(common[i][j + 1]) };
+        }
+    }
+
+    let removed = |i: usize| DiffLine { kind: DiffLineKind::Removed, text: before[i].clone(), before_line: Some(before_first + i), after_line: None };
+    let added = |j: usize| DiffLine { kind: DiffLineKind::Added, text: after[j].clone(), before_line: None, after_line: Some(after_first + j) };
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            out.push(DiffLine { kind: DiffLineKind::Context, text: after[j].clone(), before_line: Some(before_first + i), after_line: Some(after_first + j) });
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            out.push(removed(i));
+            i += 1;
+        } else {
+            out.push(added(j));
+            j += 1;
+        }
+    }
+    out.extend((i..old.len()).map(removed));
+    out.extend((j..new.len()).map(added));
+    out
+}
+
+/// Diff of the test's windows given each log's content and the 1-based lines mentioning the test
+pub fn build_context_diff(before: Option<(&str, &[usize])>, after: Option<(&str, &[usize])>) -> ContextDiff {
+    let cut = |log: Option<(&str, &[usize])>| -> (Option<usize>, usize, Vec<String>) {
+        let Some((content, matches)) = log else { return (None, 1, vec![]) };
+        let lines: Vec<&str> = content.lines().collect();
+        match pick_anchor(&lines, matches) {
+            Some(anchor) => {
+                let (first, context) = window(&lines, anchor);
+                (Some(anchor), first, context)
+            }
+            None => (None, 1, vec![]),
+        }
+    };
+    let (before_anchor, before_first, before_window) = cut(before);
+    let (after_anchor, after_first, after_window) = cut(after);
+    ContextDiff {
+        before_anchor,
+        after_anchor,
+        lines: diff_lines(&before_window, before_first, &after_window, after_first),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchors_on_failure_section() {
+        let lines = vec!["test parse ... FAILED", "", "---- parse stdout ----", "panicked at 'boom'"];
+        assert_eq!(pick_anchor(&lines, &[1, 3]), Some(3));
+        assert_eq!(pick_anchor(&lines[..2], &[1]), Some(1));
+        assert_eq!(pick_anchor(&lines, &[]), None);
+    }
+
+    #[test]
+    fn test_diffs_failure_context() {
+        let before = "test parse ... FAILED\n\n---- parse stdout ----\nthread 'parse' panicked at src/lib.rs:10:5:\nassertion failed: left == right\nfinished in 0.12s\n";
+        let after = "test parse ... FAILED\n\n---- parse stdout ----\nthread 'parse' panicked at src/lib.rs:12:5:\nassertion failed: left == right\nfinished in 0.31s\n";
+
+        let matches = [1, 3];
+        let diff = build_context_diff(Some((before, &matches[..])), Some((after, &matches[..])));
+        assert_eq!((diff.before_anchor, diff.after_anchor), (Some(3), Some(3)));
+        let changed: Vec<(DiffLineKind, &str)> = diff.lines.iter()
+            .filter(|line| line.kind != DiffLineKind::Context)
+            .map(|line| (line.kind, line.text.as_str()))
+            .collect();
+        assert_eq!(changed, vec![
+            (DiffLineKind::Removed, "thread 'parse' panicked at src/lib.rs:10:5:"),
+            (DiffLineKind::Added, "thread 'parse' panicked at src/lib.rs:12:5:"),
+        ]);
+        assert_eq!(diff.lines[0].before_line, Some(1));
+
+        let only_after = build_context_diff(None, Some((after, &matches[..1])));
+        assert!(only_after.lines.iter().all(|line| line.kind == DiffLineKind::Added));
+    }
+}
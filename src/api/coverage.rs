@@ -0,0 +1,245 @@
+//! Coverage report ingestion (Cobertura `coverage.xml` and `lcov.info`)
+//!
+//! Shows line coverage for the files touched by the golden patch and raises a soft
+//! warning for F2P tests whose target files were never executed.
+
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::{CoverageSummary, FileCoverage};
+
+/// Folder coverage reports are stored under inside the deliverable cache
+pub const COVERAGE_FOLDER: &str = "coverage";
+
+lazy_static! {
+    static ref COBERTURA_CLASS: Regex = Regex::new(r#"(?s)<class\b([^>]*)>(.*?)</class>"#).unwrap();
+    static ref COBERTURA_LINE: Regex = Regex::new(r#"<line\b([^>]*)>"#).unwrap();
+    static ref XML_ATTRIBUTE: Regex = Regex::new(r#"([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    static ref DIFF_FILE: Regex = Regex::new(r"(?m)^diff --git a/(\S+) b/(\S+)").unwrap();
+}
+
+pub fn is_coverage_file_name(name: &str) -> bool {
+    matches!(name.to_lowercase().as_str(), "coverage.xml" | "lcov.info" | "coverage.lcov")
+}
+
+/// Cache path of a coverage report found in `source_folder` of the deliverable (empty for its
+/// root), so reports sharing a file name in different folders don't overwrite each other
+pub fn coverage_cache_path(source_folder: &str, file_name: &str) -> String {
+    match source_folder.trim_matches('/') {
+        "" => format!("{}/{}", COVERAGE_FOLDER, file_name),
+        folder => format!("{}/{}/{}", COVERAGE_FOLDER, folder, file_name),
+    }
+}
+
+// Line number -> hit count, per file path as written in the report
+type LineHits = HashMap<String, HashMap<u32, u64>>;
+
+fn record_hit(files: &mut LineHits, path: &str, line: u32, hits: u64) {
+    let entry = files.entry(path.to_string()).or_default().entry(line).or_insert(0);
+    *entry = (*entry).max(hits);
+}
+
+// Value of a named attribute in a tag's attribute list, whatever order the writer used
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    XML_ATTRIBUTE
+        .captures_iter(attributes)
+        .find(|attr| &attr[1] == name)
+        .and_then(|attr| attr.get(2).or_else(|| attr.get(3)))
+        .map(|value| value.as_str())
+}
+
+pub fn parse_cobertura(content: &str) -> LineHits {
+    let mut files = LineHits::new();
+    for class in COBERTURA_CLASS.captures_iter(content) {
+        let Some(path) = attribute(&class[1], "filename") else { continue };
+        files.entry(path.to_string()).or_default();
+        for line in COBERTURA_LINE.captures_iter(&class[2]) {
+            let number = attribute(&line[1], "number").and_then(|n| n.parse().ok());
+            let hits = attribute(&line[1], "hits").and_then(|h| h.parse().ok());
+            if let (Some(number), Some(hits)) = (number, hits) {
+                record_hit(&mut files, path, number, hits);
+            }
+        }
+    }
+    files
+}
+
+pub fn parse_lcov(content: &str) -> LineHits {
+    let mut files = LineHits::new();
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("SF:") {
+            files.entry(path.to_string()).or_default();
+            current = Some(path.to_string());
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            let mut parts = data.split(',');
+            if let (Some(path), Some(Ok(number)), Some(Ok(hits))) = (
+                current.as_deref(),
+                parts.next().map(|n| n.parse::<u32>()),
+                parts.next().map(|h| h.parse::<u64>()),
+            ) {
+                record_hit(&mut files, path, number, hits);
+            }
+        } else if line == "end_of_record" {
+            current = None;
+        }
+    }
+    files
+}
+
+/// Files modified by a unified diff
+pub fn files_in_patch(patch: &str) -> Vec<String> {
+    let mut files: Vec<String> = DIFF_FILE.captures_iter(patch).map(|c| c[2].to_string()).collect();
+    files.dedup();
+    files
+}
+
+// Coverage paths are often relative to a source root, so compare by path suffix
+fn same_file(report_path: &str, patch_path: &str) -> bool {
+    let report_path = report_path.replace('\\', "/");
+    let report_path = report_path.trim_start_matches("./");
+    report_path == patch_path
+        || report_path.ends_with(&format!("/{}", patch_path))
+        || patch_path.ends_with(&format!("/{}", report_path))
+}
+
+fn file_stem(path: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.split('.').next().unwrap_or(name).to_lowercase()
+}
+
+// Patch files an F2P test most likely exercises: those whose module name appears in the
+// test ID (tests/test_parser.py::... -> parser.py), or every patched file when none do
+fn target_files<'a>(test_name: &str, patch_files: &'a [FileCoverage]) -> Vec<&'a FileCoverage> {
+    let test_name = test_name.to_lowercase();
+    let matched: Vec<&FileCoverage> = patch_files
+        .iter()
+        .filter(|file| {
+            let stem = file_stem(&file.path);
+            stem.len() > 2 && test_name.contains(&stem)
+        })
+        .collect();
+    if matched.is_empty() {
+        patch_files.iter().collect()
+    } else {
+        matched
+    }
+}
+
+/// Coverage for the golden patch files plus soft warnings for uncovered F2P targets
+pub fn summarize_coverage(reports: &[LineHits], gold_patch: &str, fail_to_pass: &[String]) -> CoverageSummary {
+    let files: Vec<FileCoverage> = files_in_patch(gold_patch)
+        .into_iter()
+        .map(|patch_path| {
+            let lines = reports
+                .iter()
+                .flat_map(|report| report.iter())
+                .filter(|(report_path, _)| same_file(report_path, &patch_path))
+                .map(|(_, lines)| lines)
+                .max_by_key(|lines| lines.values().filter(|hits| **hits > 0).count());
+
+            FileCoverage {
+                path: patch_path,
+                lines_total: lines.map_or(0, |l| l.len()),
+                lines_covered: lines.map_or(0, |l| l.values().filter(|hits| **hits > 0).count()),
+                in_report: lines.is_some(),
+            }
+        })
+        .collect();
+
+    let warnings = if files.is_empty() {
+        vec![]
+    } else {
+        fail_to_pass
+            .iter()
+            .filter_map(|test| {
+                let targets = target_files(test, &files);
+                if targets.iter().all(|file| file.lines_covered == 0) {
+                    let names: Vec<&str> = targets.iter().map(|file| file.path.as_str()).collect();
+                    Some(format!("{} (target files have zero coverage: {})", test, names.join(", ")))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    };
+
+    CoverageSummary { files, warnings }
+}
+
+/// Load any coverage reports among the deliverable files; `None` when there are none
+pub fn coverage_from_files(abs_paths: &[String], gold_patch: &str, fail_to_pass: &[String]) -> Option<CoverageSummary> {
+    let reports: Vec<LineHits> = abs_paths
+        .iter()
+        .filter(|path| {
+            let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+            is_coverage_file_name(name)
+        })
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            if path.to_lowercase().ends_with(".xml") {
+                Some(parse_cobertura(&content))
+            } else {
+                Some(parse_lcov(&content))
+            }
+        })
+        .collect();
+
+    if reports.is_empty() {
+        return None;
+    }
+    Some(summarize_coverage(&reports, gold_patch, fail_to_pass))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOLD_PATCH: &str = "diff --git a/src/pkg/parser.py b/src/pkg/parser.py\n--- a/src/pkg/parser.py\n+++ b/src/pkg/parser.py\n@@ -1 +1 @@\n-a\n+b\ndiff --git a/src/pkg/utils.py b/src/pkg/utils.py\n";
+
+    #[test]
+    fn test_parse_cobertura_and_summarize() {
+        let xml = r#"<coverage><packages><package><classes>
+            <class name="parser.py" filename="pkg/parser.py" line-rate="0.5"><lines>
+                <line number="1" hits="3"/><line number="2" hits="0"/>
+            </lines></class>
+            <class name="utils.py" filename="pkg/utils.py" line-rate="0"><lines>
+                <line number="1" hits="0"/>
+            </lines></class>
+        </classes></package></packages></coverage>"#;
+
+        let summary = summarize_coverage(
+            &[parse_cobertura(xml)],
+            GOLD_PATCH,
+            &["tests/test_parser.py::test_parse".to_string(), "tests/test_utils.py::test_helper".to_string()],
+        );
+
+        assert_eq!(summary.files.len(), 2);
+        assert_eq!((summary.files[0].lines_covered, summary.files[0].lines_total), (1, 2));
+        assert_eq!(summary.warnings, vec![
+            "tests/test_utils.py::test_helper (target files have zero coverage: src/pkg/utils.py)".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_cobertura_attributes_in_any_order() {
+        let xml = r#"<class filename='pkg/parser.py' name="parser.py"><lines>
+            <line hits="2" number="7" branch="false"/><line branch="true" number="8" hits="0"/>
+        </lines></class>"#;
+        let hits = parse_cobertura(xml);
+        assert_eq!(hits["pkg/parser.py"], HashMap::from([(7, 2), (8, 0)]));
+    }
+
+    #[test]
+    fn test_parse_lcov() {
+        let lcov = "TN:\nSF:/repo/src/pkg/parser.py\nDA:1,0\nDA:2,0\nend_of_record\n";
+        let summary = summarize_coverage(&[parse_lcov(lcov)], GOLD_PATCH, &["test_other".to_string()]);
+
+        assert!(summary.files[0].in_report);
+        assert!(!summary.files[1].in_report);
+        assert_eq!(summary.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,289 @@
+use regex::Regex;
+use std::collections::HashSet;
+use lazy_static::lazy_static;
+
+use super::log_parser::{LogParserTrait, ParsedLog, TestOutcome};
+
+lazy_static! {
+    // GoogleTest results: `[       OK ] Suite.Name (0 ms)`; parameterized names carry
+    // `, where GetParam() = ...` after the name. The summary repeats failures without a time.
+    static ref GTEST_RESULT_RE: Regex = Regex::new(r"^\s*\[\s*(OK|FAILED|SKIPPED)\s*\] ([A-Za-z_]\w*(?:/\w+)?\.\w+(?:/\w+)?)\b").unwrap();
+    static ref GTEST_RUN_RE: Regex = Regex::new(r"^\s*\[ RUN\s*\] ([A-Za-z_]\w*(?:/\w+)?\.\w+(?:/\w+)?)").unwrap();
+    // CTest per-test lines: ` 1/12 Test  #1: math_tests .......   Passed    0.01 sec`
+    // (`***Failed`, `***Exception: SegFault`, `***Timeout`, `***Not Run`, `***Skipped`)
+    static ref CTEST_RESULT_RE: Regex = Regex::new(r"^\s*\d+/\d+ Test\s+#\d+: (\S+) \.*\s*(?:\*+)?(Passed|Failed|Exception|Timeout|Not Run|Skipped|Disabled)").unwrap();
+    // Catch2 automake reporter: `:test-result: PASS Factorials are computed`
+    static ref CATCH2_AUTOMAKE_RE: Regex = Regex::new(r"^:test-result: (PASS|FAIL|XFAIL|SKIP) (.+?)\s*$").unwrap();
+    // Catch2 console reporter: each assertion block starts with `-----` lines around the test
+    // case (and section) names; `file:line: FAILED:` or `PASSED:` (with `-s`) follows
+    static ref CATCH2_RULE_RE: Regex = Regex::new(r"^-{20,}$").unwrap();
+    static ref CATCH2_OUTCOME_RE: Regex = Regex::new(r":\d+: (FAILED|PASSED|SKIPPED):").unwrap();
+    // Catch2 totals: `test cases: 3 | 2 passed | 1 failed` or `All tests passed (4 assertions in 3 test cases)`
+    static ref CATCH2_TOTALS_RE: Regex = Regex::new(r"^test cases:\s*\d+\s*\|(.*)$").unwrap();
+    static ref CATCH2_TOTAL_RE: Regex = Regex::new(r"(\d+) (passed|failed)( as expected)?").unwrap();
+    static ref CATCH2_ALL_PASSED_RE: Regex = Regex::new(r"^All tests passed \(\d+ assertions? in (\d+) test cases?\)").unwrap();
+}
+
+/// Framework names a run script can pin; the parser reads every format either way
+pub(crate) const PINNABLE_FRAMEWORKS: &[&str] = &["gtest", "catch2", "ctest"];
+
+#[derive(Default)]
+pub struct CppLogParser;
+
+impl CppLogParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LogParserTrait for CppLogParser {
+    fn get_language(&self) -> &'static str {
+        "cpp"
+    }
+
+    fn parse_content(&self, _file_path: &str, content: &str, _framework: Option<&str>) -> Result<ParsedLog, String> {
+        Ok(parse_cpp_log(content))
+    }
+}
+
+/// Whether a log looks like GoogleTest, Catch2 or CTest output
+pub(crate) fn looks_cpp_test_output(content: &str) -> bool {
+    content.contains("[ RUN      ]")
+        || content.contains("[==========]")
+        || content.contains(":test-result: ")
+        || (content.contains("test cases:") && content.contains("assertions:"))
+        || content.lines().any(|line| CTEST_RESULT_RE.is_match(line))
+}
+
+// Passing and failing test cases in the Catch2 totals of every binary in the log, or None
+// when no totals were printed (the run crashed, or this isn't Catch2 output)
+fn catch2_totals(lines: &[&str]) -> Option<(usize, usize)> {
+    let mut totals: Option<(usize, usize)> = None;
+    for line in lines {
+        let line = line.trim();
+        let (passed, failed) = if let Some(captures) = CATCH2_TOTALS_RE.captures(line) {
+            CATCH2_TOTAL_RE.captures_iter(&captures[1]).fold((0, 0), |(passed, failed), count| {
+                let number = count[1].parse::<usize>().unwrap_or(0);
+                // `failed as expected` is how `[!shouldfail]` cases pass
+                if &count[2] == "passed" || count.get(3).is_some() {
+                    (passed + number, failed)
+                } else {
+                    (passed, failed + number)
+                }
+            })
+        } else if let Some(captures) = CATCH2_ALL_PASSED_RE.captures(line) {
+            (captures[1].parse::<usize>().unwrap_or(0), 0)
+        } else {
+            continue;
+        };
+        let (total_passed, total_failed) = totals.unwrap_or((0, 0));
+        totals = Some((total_passed + passed, total_failed + failed));
+    }
+    totals
+}
+
+// Test cases of the Catch2 console reporter, from the header above each assertion block;
+// a case fails when any block under it did. Once the log carries Catch2 totals, a header
+// with no failing or skipped block is a pass: without `-s` passing assertions aren't printed
+fn parse_catch2_console(lines: &[&str], parsed: &mut ParsedLog) -> bool {
+    let totals = catch2_totals(lines);
+    let mut found = totals.is_some();
+    let mut headers: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        if CATCH2_RULE_RE.is_match(line.trim_end()) {
+            // `-----` / test case name / [section names] / `-----`
+            let closing = lines[index + 1..].iter().position(|line| CATCH2_RULE_RE.is_match(line.trim_end()));
+            if let Some(offset) = closing.filter(|offset| *offset > 0 && *offset <= 8) {
+                let name = lines[index + 1].trim();
+                if !name.is_empty() && !name.contains(" is a Catch") {
+                    if !headers.iter().any(|header| header == name) {
+                        headers.push(name.to_string());
+                    }
+                    current = Some(name.to_string());
+                }
+                index += offset + 2;
+                continue;
+            }
+        } else if let (Some(name), Some(captures)) = (&current, CATCH2_OUTCOME_RE.captures(line)) {
+            found = true;
+            let outcome = match &captures[1] {
+                "FAILED" => TestOutcome::Failed,
+                "PASSED" => TestOutcome::Passed,
+                _ => TestOutcome::Ignored,
+            };
+            parsed.record(name.trim(), outcome);
+        }
+        index += 1;
+    }
+
+    let Some((total_passed, total_failed)) = totals else {
+        return found;
+    };
+    let (mut named_passed, mut named_failed) = (0, 0);
+    for name in &headers {
+        if parsed.failed.contains(name) {
+            named_failed += 1;
+        } else if !parsed.ignored.contains(name) {
+            parsed.record(name.trim(), TestOutcome::Passed);
+            named_passed += 1;
+        }
+    }
+    if named_passed != total_passed || named_failed != total_failed {
+        parsed.note(format!(
+            "Catch2 counted {} passing and {} failing test case(s), but the log names {} passing and {} failing; \
+             passing cases are only listed when run with -s",
+            total_passed, total_failed, named_passed, named_failed
+        ));
+    }
+    found
+}
+
+pub fn parse_cpp_log(content: &str) -> ParsedLog {
+    let mut parsed = ParsedLog::new();
+    let mut formats: HashSet<&'static str> = HashSet::new();
+    // gtest test started without a result yet; a crash ends the binary without one
+    let mut running: Option<String> = None;
+
+    let lines: Vec<&str> = content.lines().collect();
+    for line in &lines {
+        if let Some(captures) = GTEST_RUN_RE.captures(line) {
+            formats.insert("gtest");
+            running = Some(captures[1].to_string());
+        } else if let Some(captures) = GTEST_RESULT_RE.captures(line) {
+            formats.insert("gtest");
+            let outcome = match &captures[1] {
+                "OK" => TestOutcome::Passed,
+                "FAILED" => TestOutcome::Failed,
+                _ => TestOutcome::Ignored,
+            };
+            if running.as_deref() == Some(&captures[2]) {
+                running = None;
+            }
+            parsed.record(captures[2].trim(), outcome);
+        } else if let Some(captures) = CTEST_RESULT_RE.captures(line) {
+            formats.insert("ctest");
+            let outcome = match &captures[2] {
+                "Passed" => TestOutcome::Passed,
+                "Not Run" | "Skipped" | "Disabled" => TestOutcome::Ignored,
+                _ => TestOutcome::Failed,
+            };
+            if let Some(name) = running.take() {
+                parsed.note(format!("{} started but never reported; counted as failed with its test binary", name));
+                parsed.record(name.trim(), TestOutcome::Failed);
+            }
+            parsed.record(captures[1].trim(), outcome);
+        } else if let Some(captures) = CATCH2_AUTOMAKE_RE.captures(line) {
+            formats.insert("catch2");
+            let outcome = match &captures[1] {
+                "PASS" | "XFAIL" => TestOutcome::Passed,
+                "FAIL" => TestOutcome::Failed,
+                _ => TestOutcome::Ignored,
+            };
+            parsed.record(captures[2].trim(), outcome);
+        }
+    }
+    if let Some(name) = running {
+        parsed.note(format!("{} started but never reported; counted as failed", name));
+        parsed.record(name.trim(), TestOutcome::Failed);
+    }
+    if !formats.contains("gtest") && parse_catch2_console(&lines, &mut parsed) {
+        formats.insert("catch2");
+    }
+
+    let parser = ["gtest", "catch2", "ctest"]
+        .into_iter()
+        .find(|format| formats.contains(format))
+        .unwrap_or("gtest");
+    parsed.finalize();
+    parsed.with_parser(parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_gtest_and_ctest_output() {
+        let log = "[==========] Running 4 tests from 2 test suites.\n\
+                   [ RUN      ] MathTest.Adds\n\
+                   [       OK ] MathTest.Adds (0 ms)\n\
+                   [ RUN      ] MathTest.Divides\n\
+                   math_test.cc:12: Failure\n\
+                   [  FAILED  ] MathTest.Divides (1 ms)\n\
+                   [ RUN      ] Sizes/VectorTest.Grows/0\n\
+                   [       OK ] Sizes/VectorTest.Grows/0 (0 ms)\n\
+                   [ RUN      ] MathTest.Later\n\
+                   [  SKIPPED ] MathTest.Later (0 ms)\n\
+                   [  FAILED  ] 1 test, listed below:\n\
+                   [  FAILED  ] MathTest.Divides\n\
+                   [ RUN      ] ParseTest.Crashes\n\
+                   1/2 Test #1: math_tests .......................***Failed    0.02 sec\n\
+                   2/2 Test #2: parse_tests ......................   Passed    0.01 sec\n";
+        let parsed = parse_cpp_log(log);
+        assert!(parsed.passed.contains("MathTest.Adds") && parsed.passed.contains("Sizes/VectorTest.Grows/0"));
+        assert!(parsed.failed.contains("MathTest.Divides") && parsed.failed.contains("ParseTest.Crashes"));
+        assert!(parsed.ignored.contains("MathTest.Later"));
+        assert!(parsed.failed.contains("math_tests") && parsed.passed.contains("parse_tests"));
+        assert_eq!(parsed.parser.as_deref(), Some("gtest"));
+    }
+
+    #[test]
+    fn test_parses_catch2_output() {
+        let console = "~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~\n\
+                       tests is a Catch2 v3.4.0 host application.\n\
+                       -------------------------------------------------------------------------------\n\
+                       Factorials are computed\n\
+                       -------------------------------------------------------------------------------\n\
+                       test.cpp:10\n\
+                       ...............................................................................\n\
+                       \n\
+                       test.cpp:12: FAILED:\n\
+                       -------------------------------------------------------------------------------\n\
+                       Vectors can be sized\n\
+                         resizing bigger\n\
+                       -------------------------------------------------------------------------------\n\
+                       test.cpp:20\n\
+                       ...............................................................................\n\
+                       \n\
+                       test.cpp:24: PASSED:\n\
+                       ===============================================================================\n\
+                       test cases: 2 | 1 passed | 1 failed\n\
+                       assertions: 2 | 1 passed | 1 failed\n";
+        let parsed = parse_cpp_log(console);
+        assert!(parsed.failed.contains("Factorials are computed"));
+        assert!(parsed.passed.contains("Vectors can be sized"));
+        assert!(parsed.warnings.is_empty());
+        assert_eq!(parsed.parser.as_deref(), Some("catch2"));
+
+        // Under `-s` a case without assertions still gets a header; the totals confirm it passed
+        let with_header_only = console
+            .replace("test cases: 2 | 1 passed | 1 failed", "test cases: 3 | 2 passed | 1 failed")
+            .replacen(
+                "===",
+                "-------------------------------------------------------------------------------\n\
+                 Strings are trimmed\n\
+                 -------------------------------------------------------------------------------\n\
+                 test.cpp:30\n\
+                 ...............................................................................\n\
+                 \n===",
+                1,
+            );
+        let parsed = parse_cpp_log(&with_header_only);
+        assert!(parsed.passed.contains("Strings are trimmed") && parsed.passed.contains("Vectors can be sized"));
+        assert!(parsed.warnings.is_empty());
+
+        // Without `-s` passing cases only show up in the totals
+        let parsed = parse_cpp_log("All tests passed (4 assertions in 3 test cases)\n");
+        assert!(parsed.all.is_empty());
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.parser.as_deref(), Some("catch2"));
+
+        let automake = ":test-result: PASS Factorials are computed\n:test-result: SKIP Slow path\n";
+        let parsed = parse_cpp_log(automake);
+        assert!(parsed.passed.contains("Factorials are computed") && parsed.ignored.contains("Slow path"));
+        assert!(looks_cpp_test_output(console) && looks_cpp_test_output(automake));
+    }
+}
@@ -0,0 +1,125 @@
+//! Reviewer-supplied test lists used in place of, or next to, main.json's lists
+//!
+//! Lists arrive as raw pasted or uploaded text: one test per line, a CSV whose first
+//! column is the test name, or a JSON array of names.
+
+use crate::app::types::{CustomTestLists, TestListProvenance, TestLists};
+
+// Header cells skipped when a CSV starts with a header row
+const CSV_HEADERS: &[&str] = &["test", "name", "test_name", "testname", "test name"];
+
+// First CSV field, honouring quotes and leaving commas inside brackets (parametrized ids) alone
+fn first_csv_field(line: &str) -> String {
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut in_quotes = false;
+    let mut depth = 0usize;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes || field.trim().is_empty() => in_quotes = !in_quotes,
+            '[' | '(' if !in_quotes => {
+                depth += 1;
+                field.push(c);
+            }
+            ']' | ')' if !in_quotes => {
+                depth = depth.saturating_sub(1);
+                field.push(c);
+            }
+            ',' | '\t' if !in_quotes && depth == 0 => break,
+            _ => field.push(c),
+        }
+    }
+    field.trim().to_string()
+}
+
+/// Test names in pasted or uploaded text, in order and without duplicates
+pub fn parse_test_list_text(text: &str) -> Vec<String> {
+    let names: Vec<String> = match serde_json::from_str::<Vec<String>>(text.trim()) {
+        Ok(names) => names.into_iter().map(|name| name.trim().to_string()).collect(),
+        Err(_) => text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(first_csv_field)
+            .collect(),
+    };
+
+    let mut out: Vec<String> = Vec::new();
+    for (i, name) in names.into_iter().enumerate() {
+        if name.is_empty() || (i == 0 && CSV_HEADERS.contains(&name.to_lowercase().as_str())) {
+            continue;
+        }
+        if !out.contains(&name) {
+            out.push(name);
+        }
+    }
+    out
+}
+
+fn merge_list(main: Vec<String>, custom_text: &str, replace: bool) -> Vec<String> {
+    let custom = parse_test_list_text(custom_text);
+    // An empty custom list leaves main.json's list untouched, even in replace mode
+    if custom.is_empty() {
+        return main;
+    }
+    if replace {
+        return custom;
+    }
+    let mut merged = main;
+    for name in custom {
+        if !merged.contains(&name) {
+            merged.push(name);
+        }
+    }
+    merged
+}
+
+/// main.json's lists with the reviewer's lists applied
+pub fn apply_custom_test_lists(lists: TestLists, custom: Option<&CustomTestLists>) -> TestLists {
+    let Some(custom) = custom else { return lists };
+    TestLists {
+        fail_to_pass: merge_list(lists.fail_to_pass, &custom.fail_to_pass, custom.replace),
+        pass_to_pass: merge_list(lists.pass_to_pass, &custom.pass_to_pass, custom.replace),
+        provenance: lists.provenance.map(|provenance| TestListProvenance { custom_lists_applied: true, ..provenance }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_test_list_text() {
+        assert_eq!(parse_test_list_text("a::b\n\n# comment\nc::d\na::b\n"), vec!["a::b", "c::d"]);
+        assert_eq!(
+            parse_test_list_text("test_name,status\ntests/t.py::test_x[1,2],failed\n\"quoted, name\",passed"),
+            vec!["tests/t.py::test_x[1,2]", "quoted, name"]
+        );
+        assert_eq!(parse_test_list_text(r#"["x", "y"]"#), vec!["x", "y"]);
+    }
+
+    #[test]
+    fn test_apply_custom_test_lists() {
+        let main = || TestLists { fail_to_pass: vec!["f1".to_string()], pass_to_pass: vec!["p1".to_string()], provenance: None };
+
+        let appended = apply_custom_test_lists(main(), Some(&CustomTestLists {
+            fail_to_pass: "f2\nf1".to_string(),
+            pass_to_pass: String::new(),
+            replace: false,
+        }));
+        assert_eq!(appended.fail_to_pass, vec!["f1", "f2"]);
+        assert_eq!(appended.pass_to_pass, vec!["p1"]);
+
+        let replaced = apply_custom_test_lists(main(), Some(&CustomTestLists {
+            fail_to_pass: "f2".to_string(),
+            pass_to_pass: String::new(),
+            replace: true,
+        }));
+        assert_eq!(replaced.fail_to_pass, vec!["f2"]);
+        assert_eq!(replaced.pass_to_pass, vec!["p1"]);
+    }
+}
@@ -1,8 +1,18 @@
+use std::collections::HashMap;
 use std::fs;
 use tempfile::TempDir;
-use crate::app::types::{FileInfo, ValidationResult, DownloadResult};
-use crate::drive::{extract_drive_folder_id, get_folder_metadata, get_folder_contents};
-use crate::auth::get_access_token;
+use crate::app::types::{ActionsRun, ApiError, ApiErrorCode, FileInfo, ValidationResult, DownloadResult, ACTIONS_FOLDER_PREFIX};
+use crate::drive::{extract_drive_folder_id, extract_drive_item_id, file_access_problem, get_folder_metadata, get_folder_contents, is_drive_link, resolve_deliverable_folder};
+use crate::api::reviewer_platform::resolve_platform_link;
+use crate::auth::{get_access_token, service_account_email};
+use crate::api::run_script::{is_run_script_name, RUN_SCRIPT_FOLDER};
+use crate::api::coverage::{coverage_cache_path, is_coverage_file_name, COVERAGE_FOLDER};
+use crate::api::log_discovery::LogPatterns;
+use crate::api::folder_structure::{check_structure, missing_required, render_checklist, FolderEntry, FolderListing};
+use crate::api::deliverable_limits::{is_logs_only_path, limit_problems, DeliverableLimits};
+use crate::api::path_guard::check_deliverable_paths;
+use crate::api::github_actions::{download_actions_artifacts, validate_actions_run};
+use crate::api::download_progress::DownloadReporter;
 
 
 async fn validate_cached_folder(
@@ -26,28 +36,19 @@ async fn validate_cached_folder(
         return Err("Missing required 'logs' folder in cache".to_string());
     }
 
-    let required_suffixes = vec![
-        "_after.log",
-        "_before.log", 
-        "_base.log",
-    ];
-    
-    let optional_suffixes = vec![
-        "_post_agent_patch.log",
-    ];
+    let patterns = LogPatterns::load();
+    let required_stages = ["after", "before", "base"];
 
-    for suffix in &required_suffixes {
-        let suffix_lower = suffix.to_lowercase();
+    for stage in &required_stages {
         let has_file = std::fs::read_dir(&logs_path)
             .map_err(|e| format!("Failed to read logs directory: {}", e))?
             .filter_map(|entry| entry.ok())
             .any(|entry| {
-                let file_name = entry.file_name().to_string_lossy().to_lowercase();
-                file_name.ends_with(&suffix_lower) && entry.path().is_file()
+                patterns.is_stage_log(stage, &entry.file_name().to_string_lossy()) && entry.path().is_file()
             });
 
         if !has_file {
-            return Err(format!("Missing required log file ending with: {} in cache", suffix));
+            return Err(format!("Missing required {} log matching: {} in cache", stage, patterns.stage_patterns(stage).join(", ")));
         }
     }
 
@@ -87,13 +88,12 @@ async fn validate_cached_folder(
         path: format!("main/{}", instance_json_name),
     });
 
-    for suffix in &required_suffixes {
+    for stage in &required_stages {
         if let Some(log_file) = std::fs::read_dir(&logs_path)
             .map_err(|e| format!("Failed to read logs directory: {}", e))?
             .filter_map(|entry| entry.ok())
             .find(|entry| {
-                let file_name = entry.file_name().to_string_lossy().to_lowercase();
-                file_name.ends_with(&suffix.to_lowercase()) && entry.path().is_file()
+                patterns.is_stage_log(stage, &entry.file_name().to_string_lossy()) && entry.path().is_file()
             }) {
             files_to_download.push(FileInfo {
                 id: "cached".to_string(),
@@ -103,21 +103,17 @@ async fn validate_cached_folder(
         }
     }
 
-    // Add optional log files if they exist
-    for suffix in &optional_suffixes {
-        if let Some(log_file) = std::fs::read_dir(&logs_path)
-            .map_err(|e| format!("Failed to read logs directory: {}", e))?
-            .filter_map(|entry| entry.ok())
-            .find(|entry| {
-                let file_name = entry.file_name().to_string_lossy().to_lowercase();
-                file_name.ends_with(&suffix.to_lowercase()) && entry.path().is_file()
-            }) {
-            files_to_download.push(FileInfo {
-                id: "cached".to_string(),
-                name: log_file.file_name().to_string_lossy().to_string(),
-                path: format!("logs/{}", log_file.file_name().to_string_lossy()),
-            });
-        }
+    // Every agent run is optional; deliverables may ship several
+    for log_file in std::fs::read_dir(&logs_path)
+        .map_err(|e| format!("Failed to read logs directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| patterns.is_stage_log("agent", &entry.file_name().to_string_lossy()) && entry.path().is_file())
+    {
+        files_to_download.push(FileInfo {
+            id: "cached".to_string(),
+            name: log_file.file_name().to_string_lossy().to_string(),
+            path: format!("logs/{}", log_file.file_name().to_string_lossy()),
+        });
     }
     let patches_files = std::fs::read_dir(&patches_path)
     .map_err(|e| format!("Failed to read patches directory: {}", e))?
@@ -132,6 +128,51 @@ for patch_file in patches_files {
     });
 }
 
+    // Add run scripts if they were cached
+    let scripts_path = cached_path.join(RUN_SCRIPT_FOLDER);
+    if scripts_path.is_dir() {
+        for script_file in std::fs::read_dir(&scripts_path)
+            .map_err(|e| format!("Failed to read scripts directory: {}", e))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+        {
+            files_to_download.push(FileInfo {
+                id: "cached".to_string(),
+                name: script_file.file_name().to_string_lossy().to_string(),
+                path: format!("{}/{}", RUN_SCRIPT_FOLDER, script_file.file_name().to_string_lossy()),
+            });
+        }
+    }
+
+    // Add coverage reports if they were cached, including those kept under their source folder
+    let coverage_path = cached_path.join(COVERAGE_FOLDER);
+    if coverage_path.is_dir() {
+        for entry in std::fs::read_dir(&coverage_path)
+            .map_err(|e| format!("Failed to read coverage directory: {}", e))?
+            .filter_map(|entry| entry.ok())
+        {
+            let folder_name = entry.file_name().to_string_lossy().to_string();
+            let (source_folder, coverage_files) = if entry.path().is_dir() {
+                let files: Vec<_> = std::fs::read_dir(entry.path())
+                    .map_err(|e| format!("Failed to read coverage directory: {}", e))?
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_file())
+                    .collect();
+                (folder_name, files)
+            } else {
+                (String::new(), vec![entry])
+            };
+            for coverage_file in coverage_files {
+                let name = coverage_file.file_name().to_string_lossy().to_string();
+                files_to_download.push(FileInfo {
+                    id: "cached".to_string(),
+                    path: coverage_cache_path(&source_folder, &name),
+                    name,
+                });
+            }
+        }
+    }
+
     // Add report.json only if it exists
     if has_report {
         files_to_download.push(FileInfo {
@@ -141,13 +182,40 @@ for patch_file in patches_files {
         });
     }
 
+    // The cache keeps the instance json under main/, so the root listing is rebuilt from it and the sub-folders
+    let mut root_entries = cached_entries(&cached_path.join("main")).unwrap_or_default();
+    let log_entries = cached_entries(&logs_path);
+    let results_entries = cached_entries(&results_path);
+    let patches_entries = cached_entries(&patches_path);
+    for (name, entries) in [("logs", &log_entries), ("results", &results_entries), ("patches", &patches_entries)] {
+        if entries.is_some() {
+            root_entries.push(FolderEntry::folder(name));
+        }
+    }
+    let structure = check_structure(&instance_json_name, &FolderListing {
+        root: &root_entries,
+        logs: log_entries.as_deref(),
+        results: results_entries.as_deref(),
+        patches: patches_entries.as_deref(),
+    }, &patterns);
+
     Ok(ValidationResult {
         files_to_download,
         folder_id: folder_id.to_string(),
+        structure,
     })
 }
 
 
+fn cached_entries(dir: &std::path::Path) -> Option<Vec<FolderEntry>> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    Some(entries.flatten().map(|entry| FolderEntry {
+        name: entry.file_name().to_string_lossy().to_string(),
+        is_folder: entry.path().is_dir(),
+    }).collect())
+}
+
+
 fn get_cached_file_list(cached_path: &std::path::Path) -> Vec<String> {
     let mut files = Vec::new();
     
@@ -174,14 +242,145 @@ fn get_cached_file_list(cached_path: &std::path::Path) -> Vec<String> {
 }
 
 
+// Drive failures only carry the HTTP status in their text; map the common ones to a category.
+// Drive answers 404 for items that exist but aren't shared, so both get the sharing hint.
+fn drive_error(message: &str, error: impl std::fmt::Display) -> ApiError {
+    let text = error.to_string();
+    let code = if text.contains("401") || text.contains("403") {
+        ApiErrorCode::PermissionDenied
+    } else if text.contains("404") || text.to_lowercase().contains("not found") {
+        ApiErrorCode::NotFound
+    } else {
+        ApiErrorCode::Network
+    };
+    match code {
+        ApiErrorCode::Network => ApiError::new(code, message).with_details(text),
+        _ => ApiError::new(code, message).with_details(share_hint(&[text])),
+    }
+}
+
+// Who to share with, followed by the items that couldn't be read
+fn share_hint(items: &[String]) -> String {
+    let account = service_account_email().unwrap_or_else(|| "the reviewer service account".to_string());
+    let listed: Vec<String> = items.iter().map(|item| format!("- {}", item)).collect();
+    format!("Share with {} (Viewer access is enough):\n{}", account, listed.join("\n"))
+}
+
+// Drive metadata requests the preflight keeps in flight at once
+const PREFLIGHT_CONCURRENCY: usize = 8;
+
+// Check every expected file before anything is downloaded, so one error lists all of them
+async fn preflight_file_access(files: &[FileInfo], access_token: &str) -> Result<(), ApiError> {
+    use futures::stream::{self, StreamExt};
+
+    // `buffered` keeps the files' order, so the list reads like the folder
+    let to_check: Vec<(String, String)> = files
+        .iter()
+        .filter(|file_info| file_info.id != "cached")
+        .map(|file_info| (file_info.id.clone(), file_info.path.clone()))
+        .collect();
+    let checks: Vec<_> = stream::iter(to_check)
+        .map(|(id, path)| {
+            let access_token = access_token.to_string();
+            async move { (path, file_access_problem(&id, &access_token).await) }
+        })
+        .buffered(PREFLIGHT_CONCURRENCY)
+        .collect()
+        .await;
+    let mut inaccessible = Vec::new();
+    for (path, check) in checks {
+        match check {
+            Ok(None) => {}
+            Ok(Some(problem)) => inaccessible.push(format!("{} ({})", path, problem)),
+            Err(e) => return Err(ApiError::new(ApiErrorCode::Network, format!("Failed to check access to {}", path)).with_details(e)),
+        }
+    }
+    if inaccessible.is_empty() {
+        return Ok(());
+    }
+    Err(ApiError::new(
+        ApiErrorCode::PermissionDenied,
+        format!("The service account can't read {} of the deliverable's files", inaccessible.len()),
+    ).with_details(share_hint(&inaccessible)))
+}
+
+fn storage_error(message: &str, error: impl std::fmt::Display) -> ApiError {
+    ApiError::new(ApiErrorCode::Storage, message).with_details(error)
+}
+
+// Listing of a sub-folder found in the deliverable root, or None when it isn't there
+async fn subfolder_files(folder: Option<&serde_json::Value>, name: &str, access_token: &str) -> Result<Option<Vec<serde_json::Value>>, ApiError> {
+    let Some(folder) = folder else {
+        return Ok(None);
+    };
+    let folder_id = folder["id"].as_str()
+        .ok_or_else(|| ApiError::new(ApiErrorCode::Network, format!("Invalid {} folder ID", name)))?;
+    let contents = get_folder_contents(folder_id, access_token).await
+        .map_err(|e| drive_error(&format!("Failed to get {} folder contents", name), e))?;
+    contents["files"].as_array()
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| ApiError::new(ApiErrorCode::Network, format!("Invalid {} folder contents response", name)))
+}
+
+fn drive_entries(files: &[serde_json::Value]) -> Vec<FolderEntry> {
+    files.iter().map(|file| FolderEntry {
+        name: file["name"].as_str().unwrap_or("").to_string(),
+        is_folder: file["mimeType"].as_str() == Some("application/vnd.google-apps.folder"),
+    }).collect()
+}
+
+
+/// Deliverable folder a link points at
+///
+/// Drive links may name the folder, a shortcut to it or a file inside it; those are resolved
+/// through the Drive API. Reviewer-platform task links resolve through the mapping API.
+pub async fn resolve_deliverable_link(link: &str) -> Result<String, ApiError> {
+    if is_drive_link(link) {
+        let item_id = extract_drive_item_id(link)
+            .ok_or_else(|| ApiError::new(ApiErrorCode::InvalidLink, "Could not find a folder or file ID in the Google Drive link"))?;
+        if link.contains("/folders/") {
+            return Ok(item_id);
+        }
+        let access_token = get_access_token()
+            .await
+            .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Failed to get access token").with_details(e))?;
+        return resolve_deliverable_folder(&item_id, &access_token).await
+            .map_err(|e| drive_error("Failed to resolve the Drive link to its deliverable folder", e));
+    }
+
+    match extract_drive_folder_id(link) {
+        Some(folder_id) => Ok(folder_id),
+        None => resolve_platform_link(link)
+            .await
+            .map(|task| task.folder_id)
+            .map_err(|e| ApiError::new(ApiErrorCode::InvalidLink, "Invalid Google Drive folder link. Please provide a valid folder URL.").with_details(e)),
+    }
+}
+
+
+/// Whether every downloaded file, as a path relative to the download cache, is still on disk
+pub fn downloads_present(file_paths: &[String]) -> Result<bool, ApiError> {
+    let temp_dir = TempDir::new().map_err(|e| storage_error("Failed to create temp directory", e))?;
+    let temp_path = temp_dir.path().to_string_lossy().to_string();
+    let base_temp_dir = std::path::Path::new(&temp_path).parent().unwrap().join("swe-reviewer-temp");
+    Ok(!file_paths.is_empty() && file_paths.iter().all(|rel| base_temp_dir.join(rel).exists()))
+}
+
+
+/// Files to download for a deliverable link, after checking its layout, access and size.
+/// With `logs_only`, only main.json and the logs are kept, for deliverables over the size limits.
 pub async fn validate_deliverable_impl(
     folder_link: String,
-) -> Result<ValidationResult, String> {
-    let folder_id = extract_drive_folder_id(&folder_link)
-        .ok_or("Invalid Google Drive folder link. Please provide a valid folder URL.")?;
+    logs_only: bool,
+) -> Result<ValidationResult, ApiError> {
+    if let Some(run) = ActionsRun::parse(&folder_link) {
+        return validate_actions_run(&run).await;
+    }
+    let folder_id = resolve_deliverable_link(&folder_link).await?;
 
     // Check if we have a cached folder first
-    let temp_dir = TempDir::new().map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let temp_dir = TempDir::new().map_err(|e| storage_error("Failed to create temp directory", e))?;
     let temp_path = temp_dir.path().to_string_lossy().to_string();
     let base_temp_dir = std::path::Path::new(&temp_path).parent().unwrap().join("swe-reviewer-temp");
     let persist_dir = base_temp_dir.join(&folder_id);
@@ -189,15 +388,15 @@ pub async fn validate_deliverable_impl(
     if persist_dir.exists() {
         let access_token = get_access_token()
             .await
-            .map_err(|e| format!("Failed to get access token: {}", e))?;
+            .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Failed to get access token").with_details(e))?;
 
         let folder_meta = get_folder_metadata(&folder_id, &access_token).await
-            .map_err(|e| format!("Failed to get folder metadata: {}", e))?;
+            .map_err(|e| drive_error("Failed to get folder metadata", e))?;
 
         let folder_name = folder_meta["name"].as_str().unwrap_or("");
         let instance_name = folder_name.split_whitespace()
             .next()
-            .ok_or("Could not extract instance name from folder name")?;
+            .ok_or_else(|| ApiError::new(ApiErrorCode::InvalidLink, "Could not extract instance name from folder name"))?;
 
         match validate_cached_folder(&folder_id, instance_name, &persist_dir).await {
             Ok(result) => {
@@ -214,102 +413,81 @@ pub async fn validate_deliverable_impl(
 
     let access_token = get_access_token()
         .await
-        .map_err(|e| format!("Failed to get access token: {}", e))?;
+        .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Failed to get access token").with_details(e))?;
 
     let folder_meta = get_folder_metadata(&folder_id, &access_token).await
-        .map_err(|e| format!("Failed to get folder metadata: {}", e))?;
+        .map_err(|e| drive_error("Failed to get folder metadata", e))?;
 
     let mime_type = folder_meta["mimeType"].as_str().unwrap_or("");
     let folder_name = folder_meta["name"].as_str().unwrap_or("");
 
     if mime_type != "application/vnd.google-apps.folder" {
-        return Err("The provided link is not a folder. Please provide a Google Drive folder link.".to_string());
+        return Err(ApiError::new(ApiErrorCode::InvalidLink, "The provided link is not a folder. Please provide a Google Drive folder link."));
     }
 
     let instance_name = folder_name.split_whitespace()
         .next()
-        .ok_or("Could not extract instance name from folder name")?;
+        .ok_or_else(|| ApiError::new(ApiErrorCode::InvalidLink, "Could not extract instance name from folder name"))?;
 
     let folder_contents = get_folder_contents(&folder_id, &access_token).await
-        .map_err(|e| format!("Failed to get folder contents: {}", e))?;
+        .map_err(|e| drive_error("Failed to get folder contents", e))?;
 
     let files = folder_contents["files"].as_array()
-        .ok_or("Invalid folder contents response")?;
+        .ok_or_else(|| ApiError::new(ApiErrorCode::Network, "Invalid folder contents response"))?;
 
     let instance_json_name = format!("{}.json", instance_name);
-    let file_names: Vec<String> = files.iter()
-        .filter_map(|file| file["name"].as_str())
-        .map(|name| name.to_string())
-        .collect();
-
-    let has_instance_json = files.iter().any(|file| {
-        let file_name = file["name"].as_str().unwrap_or("");
-        let file_mime = file["mimeType"].as_str().unwrap_or("");
-        file_name == instance_json_name && file_mime != "application/vnd.google-apps.folder"
-    });
-
-    if !has_instance_json {
-        return Err(format!(
-            "Missing required file: {}. Found files: [{}]",
-            instance_json_name,
-            file_names.join(", ")
-        ));
-    }
-
-    let logs_folder = files.iter().find(|file| {
-        let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-        file_name == "logs" &&
+    let find_folder = |name: &str| files.iter().find(|file| {
+        file["name"].as_str().unwrap_or("").eq_ignore_ascii_case(name) &&
         file["mimeType"].as_str() == Some("application/vnd.google-apps.folder")
     });
 
-    let logs_folder_id = match logs_folder {
-        Some(folder) => folder["id"].as_str().ok_or("Invalid logs folder ID")?,
-        None => return Err("Missing required 'logs' folder (case insensitive search)".to_string()),
-    };
-
-    let logs_contents = get_folder_contents(logs_folder_id, &access_token).await
-        .map_err(|e| format!("Failed to get logs folder contents: {}", e))?;
-
-    let log_files = logs_contents["files"].as_array()
-        .ok_or("Invalid logs folder contents response")?;
-
-    let required_suffixes = vec![
-        "_after.log",
-        "_before.log",
-        "_base.log",
-    ];
-    
-    let optional_suffixes = vec![
-        "_post_agent_patch.log",
-    ];
-
-    for suffix in &required_suffixes {
-        let suffix_lower = suffix.to_lowercase();
-        let has_file = log_files.iter().any(|file| {
-            let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-            file_name.ends_with(&suffix_lower) &&
-            file["mimeType"].as_str() != Some("application/vnd.google-apps.folder")
-        });
+    let log_files = subfolder_files(find_folder("logs"), "logs", &access_token).await?;
+    // results folder is now optional
+    let results_files = subfolder_files(find_folder("results"), "results", &access_token).await?;
+    let patches_files = subfolder_files(find_folder("patches"), "patches", &access_token).await?;
+
+    // Drive reports sizes as strings, and none at all for Docs files
+    let sizes: HashMap<String, u64> = files.iter()
+        .chain(log_files.iter().flatten())
+        .chain(results_files.iter().flatten())
+        .chain(patches_files.iter().flatten())
+        .filter_map(|file| Some((file["id"].as_str()?.to_string(), file["size"].as_str()?.parse().ok()?)))
+        .collect();
 
-        if !has_file {
-            return Err(format!("Missing required log file ending with: {} (case insensitive search)", suffix));
-        }
+    let patterns = LogPatterns::load();
+    let root_entries = drive_entries(files);
+    let log_entries = log_files.as_deref().map(drive_entries);
+    let results_entries = results_files.as_deref().map(drive_entries);
+    let patches_entries = patches_files.as_deref().map(drive_entries);
+    let structure = check_structure(&instance_json_name, &FolderListing {
+        root: &root_entries,
+        logs: log_entries.as_deref(),
+        results: results_entries.as_deref(),
+        patches: patches_entries.as_deref(),
+    }, &patterns);
+
+    let missing = missing_required(&structure);
+    if !missing.is_empty() {
+        let missing_paths: Vec<&str> = missing.iter().map(|item| item.path.as_str()).collect();
+        return Err(ApiError::new(ApiErrorCode::MissingFile, format!("Deliverable folder is missing: {}", missing_paths.join(", ")))
+            .with_details(render_checklist(&structure)));
     }
 
-    // results folder is now optional
-    let results_folder = files.iter().find(|file| {
-        let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-        file_name == "results" && file["mimeType"].as_str() == Some("application/vnd.google-apps.folder")
-    });
-
-    let report_file = if let Some(results_folder) = results_folder {
-        let results_folder_id = results_folder["id"].as_str().ok_or("Invalid results folder ID")?;
+    let log_files = log_files.unwrap_or_default();
+    let required_stages = ["after", "before", "base"];
 
-        let results_contents = get_folder_contents(results_folder_id, &access_token).await
-            .map_err(|e| format!("Failed to get results folder contents: {}", e))?;
+    // Coverage reports with the folder they were found in
+    let mut coverage_files: Vec<(&str, serde_json::Value)> = files.iter().filter(|file| {
+        is_coverage_file_name(file["name"].as_str().unwrap_or("")) &&
+        file["mimeType"].as_str() != Some("application/vnd.google-apps.folder")
+    }).map(|file| ("", file.clone())).collect();
 
-        let results_files = results_contents["files"].as_array()
-            .ok_or("Invalid results folder contents response")?;
+    let report_file = if let Some(results_files) = &results_files {
+        // Coverage reports may sit next to report.json
+        coverage_files.extend(results_files.iter().filter(|file| {
+            is_coverage_file_name(file["name"].as_str().unwrap_or("")) &&
+            file["mimeType"].as_str() != Some("application/vnd.google-apps.folder")
+        }).map(|file| ("results", file.clone())));
 
         // report.json is now optional - clone the found file to avoid borrowing issues
         results_files.iter().find(|file| {
@@ -333,24 +511,22 @@ pub async fn validate_deliverable_impl(
         });
     }
 
-    for suffix in &required_suffixes {
-        if let Some(log_file) = log_files.iter().find(|file| {
-            let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-            file_name.ends_with(&suffix.to_lowercase())
-        }) {
-            files_to_download.push(FileInfo {
-                id: log_file["id"].as_str().unwrap_or("").to_string(),
-                name: log_file["name"].as_str().unwrap_or("").to_string(),
-                path: format!("logs/{}", log_file["name"].as_str().unwrap_or("")),
-            });
-        }
+    // Run scripts and CI configs at the folder root pin the framework used for parsing
+    for script_file in files.iter().filter(|file| {
+        let file_name = file["name"].as_str().unwrap_or("");
+        is_run_script_name(file_name) &&
+        file["mimeType"].as_str() != Some("application/vnd.google-apps.folder")
+    }) {
+        files_to_download.push(FileInfo {
+            id: script_file["id"].as_str().unwrap_or("").to_string(),
+            name: script_file["name"].as_str().unwrap_or("").to_string(),
+            path: format!("{}/{}", RUN_SCRIPT_FOLDER, script_file["name"].as_str().unwrap_or("")),
+        });
     }
 
-    // Add optional log files if they exist
-    for suffix in &optional_suffixes {
+    for stage in &required_stages {
         if let Some(log_file) = log_files.iter().find(|file| {
-            let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-            file_name.ends_with(&suffix.to_lowercase())
+            patterns.is_stage_log(stage, file["name"].as_str().unwrap_or(""))
         }) {
             files_to_download.push(FileInfo {
                 id: log_file["id"].as_str().unwrap_or("").to_string(),
@@ -360,6 +536,26 @@ pub async fn validate_deliverable_impl(
         }
     }
 
+    // Every agent run is optional; deliverables may ship several
+    for log_file in log_files.iter().filter(|file| {
+        patterns.is_stage_log("agent", file["name"].as_str().unwrap_or("")) &&
+        file["mimeType"].as_str() != Some("application/vnd.google-apps.folder")
+    }) {
+        files_to_download.push(FileInfo {
+            id: log_file["id"].as_str().unwrap_or("").to_string(),
+            name: log_file["name"].as_str().unwrap_or("").to_string(),
+            path: format!("logs/{}", log_file["name"].as_str().unwrap_or("")),
+        });
+    }
+
+    for (source_folder, coverage_file) in &coverage_files {
+        files_to_download.push(FileInfo {
+            id: coverage_file["id"].as_str().unwrap_or("").to_string(),
+            name: coverage_file["name"].as_str().unwrap_or("").to_string(),
+            path: coverage_cache_path(source_folder, coverage_file["name"].as_str().unwrap_or("")),
+        });
+    }
+
     // Add report.json only if it exists
     if let Some(report_file) = report_file {
         files_to_download.push(FileInfo {
@@ -368,19 +564,7 @@ pub async fn validate_deliverable_impl(
             path: format!("results/{}", report_file["name"].as_str().unwrap_or("")),
         });
     }
-    let patches_folder = files.iter().find(|file| {
-        let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-        file_name == "patches" &&
-        file["mimeType"].as_str() == Some("application/vnd.google-apps.folder")
-    });
-    let patches_folder_id = match patches_folder {
-        Some(folder) => folder["id"].as_str().ok_or("Invalid patches folder ID")?,
-        None => return Err("Missing required 'patches' folder (case insensitive search)".to_string()),
-    };
-    let patches_contents = get_folder_contents(patches_folder_id, &access_token).await
-        .map_err(|e| format!("Failed to get patches folder contents: {}", e))?;
-    let patches_files = patches_contents["files"].as_array()
-        .ok_or("Invalid patches folder contents response")?;
+    let patches_files = patches_files.unwrap_or_default();
     for diff_file in patches_files.iter().filter(|file| {
         let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
         (file_name.ends_with(".diff") || file_name.ends_with(".patch")) &&
@@ -393,9 +577,29 @@ pub async fn validate_deliverable_impl(
             path: format!("patches/{}", diff_file["name"].as_str().unwrap_or("")),
         });
     }
+
+    if logs_only {
+        files_to_download.retain(|file_info| is_logs_only_path(&file_info.path));
+    }
+    let sized: Vec<(String, Option<u64>)> = files_to_download.iter()
+        .map(|file_info| (file_info.path.clone(), sizes.get(&file_info.id).copied()))
+        .collect();
+    let problems = limit_problems(&sized, &DeliverableLimits::load());
+    if !problems.is_empty() {
+        let message = if logs_only {
+            "Even the deliverable's logs are over this server's size limits"
+        } else {
+            "The deliverable is over this server's size limits"
+        };
+        return Err(ApiError::new(ApiErrorCode::TooLarge, message).with_details(problems.join("\n")));
+    }
+
+    preflight_file_access(&files_to_download, &access_token).await?;
+
     Ok(ValidationResult {
         files_to_download,
         folder_id: folder_id.to_string(),
+        structure,
     })
 }
 
@@ -403,19 +607,29 @@ pub async fn validate_deliverable_impl(
 pub async fn download_deliverable_impl(
     files_to_download: Vec<FileInfo>,
     folder_id: String,
-) -> Result<DownloadResult, String> {
+    progress: &DownloadReporter,
+) -> Result<DownloadResult, ApiError> {
     use reqwest::header::AUTHORIZATION;
 
+    // Both come back from the browser and decide where the files are written
+    let target_paths: Vec<String> = std::iter::once(folder_id.clone())
+        .chain(files_to_download.iter().map(|file_info| format!("{}/{}", folder_id, file_info.path)))
+        .collect();
+    check_deliverable_paths(&target_paths)?;
+    if folder_id.starts_with(ACTIONS_FOLDER_PREFIX) {
+        return download_actions_artifacts(files_to_download, folder_id, progress).await;
+    }
+
     let access_token = get_access_token()
         .await
-        .map_err(|e| format!("Failed to get access token: {}", e))?;
+        .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Failed to get access token").with_details(e))?;
 
-    let temp_dir = TempDir::new().map_err(|e| format!("Failed to create temp directory: {}", e))?;
+    let temp_dir = TempDir::new().map_err(|e| storage_error("Failed to create temp directory", e))?;
     let temp_path = temp_dir.path().to_string_lossy().to_string();
 
     let base_temp_dir = std::path::Path::new(&temp_path).parent().unwrap().join("swe-reviewer-temp");
     if !base_temp_dir.exists() {
-        fs::create_dir_all(&base_temp_dir).map_err(|e| format!("Failed to create base temp dir: {}", e))?;
+        fs::create_dir_all(&base_temp_dir).map_err(|e| storage_error("Failed to create base temp dir", e))?;
     }
 
     let persist_dir = base_temp_dir.join(&folder_id);
@@ -440,6 +654,9 @@ pub async fn download_deliverable_impl(
         }
 
         if all_files_cached && !cached_files.is_empty() {
+            for index in 0..cached_files.len() {
+                progress.finish_file(index);
+            }
             return Ok(DownloadResult {
                 downloaded_files: cached_files,
             });
@@ -452,9 +669,10 @@ pub async fn download_deliverable_impl(
     // Store files_to_download for later use with cached files
     let files_to_download = files_to_download.clone();
 
-    for file_info in &files_to_download {
+    for (index, file_info) in files_to_download.iter().enumerate() {
         // Skip files that are already cached (have placeholder ID)
         if file_info.id == "cached" {
+            progress.finish_file(index);
             continue;
         }
 
@@ -462,26 +680,34 @@ pub async fn download_deliverable_impl(
         let file_dir_path = file_path.parent().unwrap_or(std::path::Path::new(""));
         if !file_dir_path.exists() {
             fs::create_dir_all(&file_dir_path)
-                .map_err(|e| format!("Failed to create directory {}: {}", file_dir_path.display(), e))?;
+                .map_err(|e| storage_error(&format!("Failed to create directory {}", file_dir_path.display()), e))?;
         }
 
         let download_url = format!("https://www.googleapis.com/drive/v3/files/{}?alt=media&supportsAllDrives=true", file_info.id);
-        let file_resp = client
+        let mut file_resp = client
             .get(&download_url)
             .header(AUTHORIZATION, format!("Bearer {}", access_token))
             .send()
             .await
-            .map_err(|e| format!("Download error for {}: {}", file_info.name, e))?;
+            .map_err(|e| ApiError::new(ApiErrorCode::Network, format!("Download error for {}", file_info.name)).with_details(e))?;
 
         if !file_resp.status().is_success() {
-            return Err(format!("Failed to download file {}: {}", file_info.name, file_resp.status()));
+            return Err(drive_error(&format!("Failed to download file {}", file_info.name), file_resp.status()));
         }
 
-        let content = file_resp.bytes().await
-            .map_err(|e| format!("File read error for {}: {}", file_info.name, e))?;
+        // Read in chunks so the page can show how far each file got
+        progress.begin_file(index, file_resp.content_length());
+        let mut content = Vec::new();
+        while let Some(chunk) = file_resp.chunk().await
+            .map_err(|e| ApiError::new(ApiErrorCode::Network, format!("File read error for {}", file_info.name)).with_details(e))?
+        {
+            content.extend_from_slice(&chunk);
+            progress.add_bytes(index, chunk.len() as u64);
+        }
 
         fs::write(&file_path, content)
-            .map_err(|e| format!("Failed to write file {}: {}", file_info.name, e))?;
+            .map_err(|e| storage_error(&format!("Failed to write file {}", file_info.name), e))?;
+        progress.finish_file(index);
 
         downloaded_files.push(FileInfo {
             id: file_info.id.clone(),
@@ -490,7 +716,7 @@ pub async fn download_deliverable_impl(
         });
     }
 
-    fs::create_dir_all(&persist_dir).map_err(|e| format!("Failed to create persist dir: {}", e))?;
+    fs::create_dir_all(&persist_dir).map_err(|e| storage_error("Failed to create persist dir", e))?;
 
     // Copy newly downloaded files to persist directory
     for file_info in &downloaded_files {
@@ -499,10 +725,10 @@ pub async fn download_deliverable_impl(
         let dest = persist_dir.join(relative_path);
 
         if let Some(parent) = dest.parent() {
-            fs::create_dir_all(parent).map_err(|e| format!("Failed to create dest dir: {}", e))?;
+            fs::create_dir_all(parent).map_err(|e| storage_error("Failed to create dest dir", e))?;
         }
 
-        fs::copy(source, &dest).map_err(|e| format!("Failed to copy file: {}", e))?;
+        fs::copy(source, &dest).map_err(|e| storage_error("Failed to copy file", e))?;
     }
 
     // Build final file list including both cached and newly downloaded files
@@ -546,3 +772,31 @@ pub async fn download_deliverable_impl(
     })
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drive_error_codes() {
+        assert_eq!(drive_error("Failed to get folder metadata", "Failed to get folder metadata: 403 Forbidden").code, ApiErrorCode::PermissionDenied);
+        assert_eq!(drive_error("Failed to get folder contents", "Folder not found in personal drive or any accessible shared drives").code, ApiErrorCode::NotFound);
+        assert_eq!(drive_error("Failed to download file a.log", "500 Internal Server Error").code, ApiErrorCode::Network);
+    }
+
+    #[test]
+    fn test_share_hint_lists_items() {
+        let hint = share_hint(&["logs/after.log (403 Forbidden)".to_string(), "main/x.json (404 Not Found)".to_string()]);
+        assert!(hint.starts_with("Share with "));
+        assert!(hint.ends_with("(Viewer access is enough):\n- logs/after.log (403 Forbidden)\n- main/x.json (404 Not Found)"));
+    }
+
+    #[test]
+    fn test_api_error_serializes_as_code_message_details() {
+        let error = ApiError::new(ApiErrorCode::MissingFile, "Missing required before log").with_details("Expected a file matching: before.log");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({"code": "missing_file", "message": "Missing required before log", "details": "Expected a file matching: before.log"})
+        );
+    }
+}
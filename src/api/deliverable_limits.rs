@@ -0,0 +1,100 @@
+//! Size limits checked before a deliverable is downloaded
+//!
+//! Downloads are held in memory while they're written out, and every log is read whole
+//! for parsing, so one multi-gigabyte agent log can take the server down for everyone.
+//! Validation compares the sizes Drive reports against the `limits` section of the
+//! server config and refuses the download up front, naming the files over the limit.
+//! A deliverable refused this way can still be opened with only main.json and its logs.
+
+use crate::api::server_config::load_server_config;
+use crate::app::number_format::{format_count, format_size};
+
+const MB: u64 = 1024 * 1024;
+const DEFAULT_MAX_FILE_MB: u64 = 512;
+const DEFAULT_MAX_TOTAL_MB: u64 = 2048;
+const DEFAULT_MAX_FILES: usize = 500;
+
+/// Effective limits; `None` means unlimited
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeliverableLimits {
+    pub max_file_bytes: Option<u64>,
+    pub max_total_bytes: Option<u64>,
+    pub max_files: Option<usize>,
+}
+
+impl DeliverableLimits {
+    pub fn load() -> Self {
+        let config = load_server_config().limits;
+        let limit = |value: Option<u64>, default: u64| match value.unwrap_or(default) {
+            0 => None,
+            mb => Some(mb * MB),
+        };
+        DeliverableLimits {
+            max_file_bytes: limit(config.max_file_mb, DEFAULT_MAX_FILE_MB),
+            max_total_bytes: limit(config.max_total_mb, DEFAULT_MAX_TOTAL_MB),
+            max_files: Some(config.max_files.unwrap_or(DEFAULT_MAX_FILES)).filter(|max| *max > 0),
+        }
+    }
+}
+
+/// Files a logs-only download keeps: main.json and the stage and agent logs
+pub fn is_logs_only_path(path: &str) -> bool {
+    path.starts_with("main/") || path.starts_with("logs/")
+}
+
+/// One line per exceeded limit for `files` (path and the size Drive reports, when it does),
+/// largest offending file first; empty when the deliverable fits
+pub fn limit_problems(files: &[(String, Option<u64>)], limits: &DeliverableLimits) -> Vec<String> {
+    let mut problems = Vec::new();
+    if let Some(max_files) = limits.max_files {
+        if files.len() > max_files {
+            problems.push(format!("{} files, limit {}", format_count(files.len()), format_count(max_files)));
+        }
+    }
+    if let Some(max_file_bytes) = limits.max_file_bytes {
+        let mut oversized: Vec<(&str, u64)> = files.iter()
+            .filter_map(|(path, size)| size.filter(|size| *size > max_file_bytes).map(|size| (path.as_str(), size)))
+            .collect();
+        oversized.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+        for (path, size) in oversized {
+            problems.push(format!("{} is {}, limit {} per file", path, format_size(size), format_size(max_file_bytes)));
+        }
+    }
+    if let Some(max_total_bytes) = limits.max_total_bytes {
+        let total: u64 = files.iter().filter_map(|(_, size)| *size).sum();
+        if total > max_total_bytes {
+            problems.push(format!("{} in total, limit {}", format_size(total), format_size(max_total_bytes)));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_every_exceeded_limit() {
+        let limits = DeliverableLimits { max_file_bytes: Some(100 * MB), max_total_bytes: Some(150 * MB), max_files: Some(2) };
+        let files = vec![
+            ("main/x.json".to_string(), Some(MB / 2)),
+            ("logs/before.log".to_string(), Some(120 * MB)),
+            ("logs/agent.log".to_string(), Some(3 * 1024 * MB)),
+            ("patches/gold.diff".to_string(), None),
+        ];
+        assert_eq!(limit_problems(&files, &limits), vec![
+            "4 files, limit 2".to_string(),
+            "logs/agent.log is 3.0 GB, limit 100.0 MB per file".to_string(),
+            "logs/before.log is 120.0 MB, limit 100.0 MB per file".to_string(),
+            "3.1 GB in total, limit 150.0 MB".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_unlimited_and_within_limits() {
+        let files = vec![("logs/after.log".to_string(), Some(10 * MB))];
+        assert!(limit_problems(&files, &DeliverableLimits { max_file_bytes: None, max_total_bytes: None, max_files: None }).is_empty());
+        assert!(limit_problems(&files, &DeliverableLimits { max_file_bytes: Some(10 * MB), max_total_bytes: Some(10 * MB), max_files: Some(1) }).is_empty());
+        assert!(is_logs_only_path("logs/after.log") && !is_logs_only_path("patches/gold.diff"));
+    }
+}
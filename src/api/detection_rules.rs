@@ -0,0 +1,177 @@
+//! Operator-configured framework detection rules
+//!
+//! New in-house runners often print a recognizable banner but otherwise look like
+//! an existing framework. Rather than shipping a code change for each one, operators
+//! can point `parsers.detection_rules_file` in the server config at a JSON file such as:
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     { "language": "python", "pattern": "acme-runner v", "parser": "pytest_v2", "priority": 10 },
+//!     { "pattern": "^\\s*PASS\\s+\\[", "regex": true, "parser": "nextest" }
+//!   ]
+//! }
+//! ```
+//!
+//! The file is re-read on every analysis so edits take effect without a restart.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::api::parser_registry::canonical_language;
+use crate::api::server_config::ParsersConfig;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DetectionRule {
+    /// Restrict the rule to one language; applies to every language when omitted
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Substring (or regex when `regex` is set) searched for in the log content
+    pub pattern: String,
+    #[serde(default)]
+    pub regex: bool,
+    /// Parser/framework name handed to the language parser, e.g. "pytest_v2", "jest", "nextest"
+    pub parser: String,
+    /// Higher priority rules are tried first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DetectionRulesConfig {
+    #[serde(default)]
+    pub rules: Vec<DetectionRule>,
+}
+
+struct CompiledRule {
+    rule: DetectionRule,
+    regex: Option<Regex>,
+}
+
+/// Rules loaded from config, sorted by priority and ready to match
+pub struct DetectionRules {
+    rules: Vec<CompiledRule>,
+    // File the rules were read from; `None` when none was configured or it couldn't be read
+    source: Option<String>,
+}
+
+impl DetectionRules {
+    pub fn new(config: DetectionRulesConfig) -> Self {
+        let mut rules: Vec<CompiledRule> = config
+            .rules
+            .into_iter()
+            .filter_map(|rule| {
+                if !rule.regex {
+                    return Some(CompiledRule { rule, regex: None });
+                }
+                match Regex::new(&format!("(?m){}", rule.pattern)) {
+                    Ok(regex) => Some(CompiledRule { rule, regex: Some(regex) }),
+                    Err(e) => {
+                        eprintln!("Ignoring detection rule with invalid regex '{}': {}", rule.pattern, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        // Stable sort keeps file order among rules with equal priority
+        rules.sort_by_key(|compiled| std::cmp::Reverse(compiled.rule.priority));
+
+        Self { rules, source: None }
+    }
+
+    /// Load rules from the file named by `parsers.detection_rules_file`, if set
+    pub fn from_config(config: &ParsersConfig) -> Self {
+        let path = match config.detection_rules_file.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => path.to_string(),
+            _ => return Self::new(DetectionRulesConfig::default()),
+        };
+
+        let config = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read detection rules from {}: {}", path, e))
+            .and_then(|content| {
+                serde_json::from_str::<DetectionRulesConfig>(&content)
+                    .map_err(|e| format!("Failed to parse detection rules in {}: {}", path, e))
+            });
+
+        match config {
+            Ok(config) => Self { source: Some(path), ..Self::new(config) },
+            Err(e) => {
+                eprintln!("{}", e);
+                Self::new(DetectionRulesConfig::default())
+            }
+        }
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Return the parser name of the highest-priority rule matching the log content
+    pub fn match_parser(&self, language: &str, content: &str) -> Option<String> {
+        let language = canonical_language(language);
+
+        self.rules
+            .iter()
+            .filter(|compiled| {
+                compiled
+                    .rule
+                    .language
+                    .as_deref()
+                    .is_none_or(|l| canonical_language(l) == language)
+            })
+            .find(|compiled| match &compiled.regex {
+                Some(regex) => regex.is_match(content),
+                None => content.contains(&compiled.rule.pattern),
+            })
+            .map(|compiled| compiled.rule.parser.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(language: Option<&str>, pattern: &str, regex: bool, parser: &str, priority: i32) -> DetectionRule {
+        DetectionRule {
+            language: language.map(|l| l.to_string()),
+            pattern: pattern.to_string(),
+            regex,
+            parser: parser.to_string(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn test_priority_and_language_filtering() {
+        let rules = DetectionRules::new(DetectionRulesConfig {
+            rules: vec![
+                rule(None, "acme", false, "pytest", 0),
+                rule(Some("python"), "acme", false, "pytest_v2", 5),
+                rule(Some("ts"), "acme", false, "mocha", 10),
+            ],
+        });
+
+        assert_eq!(rules.match_parser("python", "acme runner").as_deref(), Some("pytest_v2"));
+        assert_eq!(rules.match_parser("javascript", "acme runner").as_deref(), Some("mocha"));
+        assert_eq!(rules.match_parser("rust", "acme runner").as_deref(), Some("pytest"));
+        assert_eq!(rules.match_parser("python", "plain output"), None);
+    }
+
+    #[test]
+    fn test_regex_rules_and_invalid_regex() {
+        let rules = DetectionRules::new(DetectionRulesConfig {
+            rules: vec![
+                rule(None, "([", true, "broken", 100),
+                rule(Some("rust"), r"^\s*PASS \[", true, "nextest", 0),
+            ],
+        });
+
+        assert_eq!(rules.match_parser("rust", "setup\n    PASS [ 0.1s] crate::t").as_deref(), Some("nextest"));
+        assert_eq!(rules.match_parser("rust", "test t ... ok"), None);
+    }
+}
@@ -0,0 +1,176 @@
+//! Per-file progress of running deliverable downloads, streamed to the page
+//!
+//! A large deliverable can take minutes to download, and the page had nothing to show but a
+//! spinner. The download reports bytes per file here under an id the browser picked, and
+//! `GET /api/download_progress/{job_id}` streams the job as server-sent events until the
+//! download ends.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::extract::Path;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::stream::{self, Stream};
+use lazy_static::lazy_static;
+
+use crate::app::types::{DownloadFileProgress, DownloadProgress, FileInfo};
+
+pub const DOWNLOAD_PROGRESS_ROUTE: &str = "/api/download_progress/{job_id}";
+
+// Finished jobs are kept this long for a late stream, then dropped
+const KEEP_FOR: Duration = Duration::from_secs(10 * 60);
+// Ids come from the browser; anything longer isn't one of ours
+const MAX_ID_LEN: usize = 64;
+// How often the stream checks for changes
+const PUSH_EVERY: Duration = Duration::from_millis(250);
+// The page opens the stream before the download registers the job; give up if it never does
+const START_GRACE: Duration = Duration::from_secs(30);
+
+struct JobProgress {
+    progress: DownloadProgress,
+    updated: Instant,
+}
+
+lazy_static! {
+    static ref JOBS: Mutex<HashMap<String, JobProgress>> = Mutex::new(HashMap::new());
+}
+
+/// Handle a download reports its files through; a disabled one ignores every report
+#[derive(Clone, Debug, Default)]
+pub struct DownloadReporter {
+    id: Option<String>,
+}
+
+impl DownloadReporter {
+    /// Register a job with every file at zero bytes; `None` or an unusable id gives a disabled reporter
+    pub fn start(id: Option<String>, files: &[FileInfo]) -> Self {
+        let Some(id) = id.filter(|id| !id.is_empty() && id.len() <= MAX_ID_LEN) else {
+            return Self::default();
+        };
+        let files = files.iter().map(|file| DownloadFileProgress {
+            name: file.path.clone(),
+            ..Default::default()
+        }).collect();
+        if let Ok(mut jobs) = JOBS.lock() {
+            jobs.retain(|_, job| job.updated.elapsed() < KEEP_FOR);
+            let progress = DownloadProgress { id: id.clone(), files, finished: false };
+            jobs.insert(id.clone(), JobProgress { progress, updated: Instant::now() });
+        }
+        Self { id: Some(id) }
+    }
+
+    fn update(&self, update: impl FnOnce(&mut DownloadProgress)) {
+        let Some(id) = &self.id else { return };
+        let Ok(mut jobs) = JOBS.lock() else { return };
+        let Some(job) = jobs.get_mut(id) else { return };
+        job.updated = Instant::now();
+        update(&mut job.progress);
+    }
+
+    /// File `index` started downloading, with the size Drive reported
+    pub fn begin_file(&self, index: usize, total: Option<u64>) {
+        self.update(|progress| {
+            if let Some(file) = progress.files.get_mut(index) {
+                file.total = total;
+                file.bytes = 0;
+            }
+        });
+    }
+
+    pub fn add_bytes(&self, index: usize, bytes: u64) {
+        self.update(|progress| {
+            if let Some(file) = progress.files.get_mut(index) {
+                file.bytes += bytes;
+            }
+        });
+    }
+
+    /// File `index` is on disk, downloaded now or found in the cache
+    pub fn finish_file(&self, index: usize) {
+        self.update(|progress| {
+            if let Some(file) = progress.files.get_mut(index) {
+                file.done = true;
+            }
+        });
+    }
+
+    /// The download is over, whether or not it succeeded; streams close after sending this
+    pub fn end(&self) {
+        self.update(|progress| progress.finished = true);
+    }
+}
+
+/// Current state of download `id`
+pub fn download_progress(id: &str) -> Option<DownloadProgress> {
+    JOBS.lock().ok()?.get(id).map(|job| job.progress.clone())
+}
+
+struct StreamState {
+    job_id: String,
+    last: Option<DownloadProgress>,
+    opened: Instant,
+}
+
+/// `GET /api/download_progress/{job_id}`: the job as JSON after every change, ending with the
+/// event whose `finished` is set
+pub async fn download_progress_handler(Path(job_id): Path<String>) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let state = StreamState { job_id, last: None, opened: Instant::now() };
+    let events = stream::unfold(state, |mut state| async move {
+        if state.last.as_ref().is_some_and(|last| last.finished) {
+            return None;
+        }
+        loop {
+            match download_progress(&state.job_id) {
+                Some(progress) if state.last.as_ref() != Some(&progress) => {
+                    let event = Event::default().json_data(&progress).unwrap_or_default();
+                    state.last = Some(progress);
+                    return Some((Ok(event), state));
+                }
+                Some(_) => {}
+                None if state.last.is_none() && state.opened.elapsed() < START_GRACE => {}
+                None => return None,
+            }
+            tokio::time::sleep(PUSH_EVERY).await;
+        }
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files() -> Vec<FileInfo> {
+        ["main/task.json", "logs/after.log"].iter().map(|path| FileInfo {
+            id: format!("id-{}", path),
+            name: path.rsplit('/').next().unwrap().to_string(),
+            path: path.to_string(),
+        }).collect()
+    }
+
+    #[test]
+    fn test_reports_bytes_per_file_until_the_job_ends() {
+        let reporter = DownloadReporter::start(Some("download-test-1".to_string()), &files());
+        reporter.finish_file(0);
+        reporter.begin_file(1, Some(400));
+        reporter.add_bytes(1, 100);
+        let progress = download_progress("download-test-1").unwrap();
+        assert_eq!(progress.files.iter().map(|file| file.percent()).collect::<Vec<_>>(), vec![Some(100), Some(25)]);
+        assert_eq!(progress.files[1].name, "logs/after.log");
+        assert!(!progress.finished);
+
+        reporter.end();
+        assert!(download_progress("download-test-1").unwrap().finished);
+    }
+
+    #[test]
+    fn test_unusable_ids_give_a_disabled_reporter() {
+        let reporter = DownloadReporter::start(Some("x".repeat(MAX_ID_LEN + 1)), &files());
+        reporter.add_bytes(0, 10);
+        reporter.end();
+        assert!(download_progress(&"x".repeat(MAX_ID_LEN + 1)).is_none());
+        DownloadReporter::start(None, &files()).end();
+    }
+}
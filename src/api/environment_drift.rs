@@ -0,0 +1,111 @@
+//! Tool and platform versions compared across stage logs
+//!
+//! Test runners and run scripts often print an environment banner: pytest's
+//! `platform linux -- Python 3.9.7, pytest-7.1.2`, `rustc --version`, `node -v`,
+//! `go version`. When base, before and after ran on different versions, base failures can come
+//! from the environment rather than the repository, so differing versions are reported.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::EnvironmentDrift;
+
+/// Stages whose environments should match; agent runs happen elsewhere
+pub const DRIFT_STAGES: [&str; 3] = ["base", "before", "after"];
+
+lazy_static! {
+    // Each pattern's groups map to the tools named next to it
+    static ref BANNER_PATTERNS: Vec<(Regex, &'static [&'static str])> = vec![
+        (Regex::new(r"^platform (\S+) -- Python (\S+), pytest-([^,\s]+)").unwrap(), &["platform", "python", "pytest"]),
+        (Regex::new(r"^Python (\d+\.\d+\.\d+\S*)\s*$").unwrap(), &["python"]),
+        (Regex::new(r"^rustc (\d+\.\d+\.\d+\S*)").unwrap(), &["rustc"]),
+        (Regex::new(r"^cargo (\d+\.\d+\.\d+\S*)").unwrap(), &["cargo"]),
+        (Regex::new(r"^(?:Node\.js|node(?: version)?:?)\s+v?(\d+\.\d+\.\d+)").unwrap(), &["node"]),
+        (Regex::new(r"^npm(?: version)?:?\s+v?(\d+\.\d+\.\d+)").unwrap(), &["npm"]),
+        (Regex::new(r"^go version go(\S+) (\S+)").unwrap(), &["go", "platform"]),
+        (Regex::new(r#"^(?:openjdk|java) version "([^"]+)""#).unwrap(), &["java"]),
+        (Regex::new(r"^Apache Maven (\S+)").unwrap(), &["maven"]),
+        (Regex::new(r"^Gradle (\d\S*)").unwrap(), &["gradle"]),
+    ];
+}
+
+/// Versions the log's banner lines report, as (tool, version) in first-seen order; a tool
+/// printed twice keeps its first version
+pub fn environment_banner(content: &str) -> Vec<(String, String)> {
+    let mut found: Vec<(String, String)> = Vec::new();
+    for line in content.lines().map(str::trim) {
+        for (pattern, tools) in BANNER_PATTERNS.iter() {
+            let Some(captures) = pattern.captures(line) else { continue };
+            for (i, tool) in tools.iter().enumerate() {
+                let Some(version) = captures.get(i + 1) else { continue };
+                if !found.iter().any(|(seen, _)| seen == tool) {
+                    found.push((tool.to_string(), version.as_str().to_string()));
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Tools whose version differs between stages, from (stage, log content) pairs; a tool only
+/// one stage printed can't drift
+pub fn environment_drift(stage_logs: &[(&str, &str)]) -> Vec<EnvironmentDrift> {
+    let banners: Vec<(&str, Vec<(String, String)>)> = stage_logs
+        .iter()
+        .map(|(stage, content)| (*stage, environment_banner(content)))
+        .collect();
+    let mut drift: Vec<EnvironmentDrift> = Vec::new();
+    for (_, banner) in &banners {
+        for (tool, _) in banner {
+            if drift.iter().any(|known| &known.tool == tool) {
+                continue;
+            }
+            let versions: Vec<(String, String)> = banners
+                .iter()
+                .filter_map(|(stage, banner)| {
+                    banner.iter().find(|(other, _)| other == tool).map(|(_, version)| (stage.to_string(), version.clone()))
+                })
+                .collect();
+            if versions.iter().any(|(_, version)| version != &versions[0].1) {
+                drift.push(EnvironmentDrift { tool: tool.clone(), versions });
+            }
+        }
+    }
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_pytest_and_toolchain_banners() {
+        let log = "+ rustc --version\nrustc 1.75.0 (82e1608df 2023-12-21)\n\
+            ============ test session starts ============\n\
+            platform linux -- Python 3.9.7, pytest-7.1.2, pluggy-1.0.0\n\
+            rootdir: /testbed\nPython 3.11.4\n";
+        assert_eq!(environment_banner(log), vec![
+            ("rustc".to_string(), "1.75.0".to_string()),
+            ("platform".to_string(), "linux".to_string()),
+            ("python".to_string(), "3.9.7".to_string()),
+            ("pytest".to_string(), "7.1.2".to_string()),
+        ]);
+        assert!(environment_banner("test_version_check PASSED\nnode_modules/.bin/jest").is_empty());
+    }
+
+    #[test]
+    fn test_only_differing_versions_drift() {
+        let base = "platform linux -- Python 3.8.10, pytest-7.1.2, pluggy-1.0.0\n";
+        let after = "platform linux -- Python 3.9.7, pytest-7.1.2, pluggy-1.0.0\nnode v18.17.0\n";
+        let drift = environment_drift(&[("base", base), ("before", after), ("after", after)]);
+        assert_eq!(drift, vec![EnvironmentDrift {
+            tool: "python".to_string(),
+            versions: vec![
+                ("base".to_string(), "3.8.10".to_string()),
+                ("before".to_string(), "3.9.7".to_string()),
+                ("after".to_string(), "3.9.7".to_string()),
+            ],
+        }]);
+        assert!(environment_drift(&[("base", after), ("after", after)]).is_empty());
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
-use crate::app::types::TestLists;
+use crate::app::types::{TestListProvenance, TestLists};
+use crate::api::report_schema::tests_status_list_counts;
+use crate::api::test_list_source::{read_test_list, FAIL_TO_PASS_KEYS, PASS_TO_PASS_KEYS};
+use crate::api::log_preprocess::normalize_line_endings;
 
 #[derive(Serialize, Deserialize)]
 pub struct GetFileContentRequest {
@@ -14,22 +17,29 @@ pub struct GetTestListsRequest {
 
 
 pub fn get_file_contents(file_type: String, file_paths: Vec<String>) -> Result<String, String> {
+    use crate::api::log_discovery::LogPatterns;
     use std::fs;
     use tempfile::TempDir;
     use std::path::PathBuf;
     
+    let patterns = LogPatterns::load();
     let file_extensions = match file_type.as_str() {
-        "base" => vec!["base.log", "base.txt"],
-        "before" => vec!["before.log", "before.txt"],
-        "after" => vec!["after.log", "after.txt"],
-        "agent" => vec!["post_agent_patch.log", "post_agent_patch"],
         "main_json" => vec!["main.json", "main/"],
         "report" => vec!["report.json", "analysis.json", "results.json", "results/report.json"],
+        // Stage logs are located by the configured file-name patterns below
+        "base" | "before" | "after" | "agent" => vec![""],
         _ => return Err(format!("Unknown file type: {}", file_type)),
     };
 
+    // With several agent runs, show the one the analysis uses by default
+    let file_paths: Vec<String> = match file_type.as_str() {
+        "agent" => patterns.default_agent_log(&file_paths).cloned().into_iter().collect(),
+        "base" | "before" | "after" => patterns.find(&file_type, &file_paths).cloned().into_iter().collect(),
+        _ => file_paths,
+    };
+
     // Check if this is an optional file type
-    let is_optional = matches!(file_type.as_str(), "agent" | "report");
+    let is_optional = matches!(file_type.as_str(), "base" | "before" | "after" | "agent" | "report");
 
     // Build absolute path candidates from relative paths: base_temp_dir/folder_id/<rel>
     // We reconstruct base_temp_dir using the TempDir parent pattern used in download_deliverable_impl
@@ -55,7 +65,8 @@ pub fn get_file_contents(file_type: String, file_paths: Vec<String>) -> Result<S
                 match fs::read_to_string(&abs_path) {
                     Ok(content) => {
                         eprintln!("Successfully read {} ({} bytes)", abs_path.display(), content.len());
-                        return Ok(content);
+                        // Same line endings as search so result line numbers match the viewer
+                        return Ok(normalize_line_endings(&content));
                     }
                     Err(e) => {
                         eprintln!("Failed to read file {}: {}", abs_path.display(), e);
@@ -102,25 +113,31 @@ pub fn get_test_lists(file_paths: Vec<String>) -> Result<TestLists, String> {
     let main_json: serde_json::Value = serde_json::from_str(&main_json_content)
         .map_err(|e| format!("Failed to parse main.json: {}", e))?;
     
-    let empty_vec: Vec<serde_json::Value> = vec![];
-    let fail_to_pass: Vec<String> = main_json.get("fail_to_pass")
-        .and_then(|v| v.as_array())
-        .unwrap_or(&empty_vec)
-        .iter()
-        .filter_map(|v| v.as_str())
-        .map(|s| s.to_string())
-        .collect();
-    
-    let pass_to_pass: Vec<String> = main_json.get("pass_to_pass")
-        .and_then(|v| v.as_array())
-        .unwrap_or(&empty_vec)
-        .iter()
-        .filter_map(|v| v.as_str())
-        .map(|s| s.to_string())
-        .collect();
-    
+    let (fail_to_pass, fail_to_pass_provenance) = read_test_list(&main_json, FAIL_TO_PASS_KEYS);
+    let (pass_to_pass, pass_to_pass_provenance) = read_test_list(&main_json, PASS_TO_PASS_KEYS);
+
+    // report.json is optional; its tests_status lists are only used to flag count mismatches
+    let report_counts = file_paths.iter()
+        .find(|rel| rel.to_lowercase().ends_with("report.json"))
+        .and_then(|rel| fs::read_to_string(base_temp_dir.join(rel)).ok())
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|report| tests_status_list_counts(&report));
+
+    let source_file = main_json_path.strip_prefix(&base_temp_dir)
+        .unwrap_or(&main_json_path)
+        .to_string_lossy()
+        .to_string();
+
     Ok(TestLists {
         fail_to_pass,
         pass_to_pass,
+        provenance: Some(TestListProvenance {
+            source_file,
+            fail_to_pass: fail_to_pass_provenance,
+            pass_to_pass: pass_to_pass_provenance,
+            report_fail_to_pass: report_counts.map(|(f2p, _)| f2p),
+            report_pass_to_pass: report_counts.map(|(_, p2p)| p2p),
+            custom_lists_applied: false,
+        }),
     })
 }
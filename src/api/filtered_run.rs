@@ -0,0 +1,122 @@
+//! Cargo runs that never reached the listed tests
+//!
+//! A run script that passes a test-name filter (`cargo test parser::` or `-- --skip slow`),
+//! or a build where every test binary prints `running 0 tests`, leaves the listed tests
+//! without results. Without a hint that looks like the patch broke them, so the stage is
+//! flagged as filtered instead.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::StageFilter;
+
+lazy_static! {
+    // `cargo test`, `cargo +nightly test` or `cargo nextest run`, possibly echoed by `set -x`
+    static ref CARGO_TEST_CMD_RE: Regex = Regex::new(r"^\s*(?:\+\s+)?(?:[$#>]\s+)?cargo\s+(?:\+\S+\s+)?(?:test|nextest\s+run)\b(.*)$").unwrap();
+    static ref RUNNING_TESTS_RE: Regex = Regex::new(r"^\s*running (\d+) tests?\s*$").unwrap();
+    static ref FILTERED_OUT_RE: Regex = Regex::new(r"^\s*test result: .*; (\d+) filtered out").unwrap();
+}
+
+// Cargo options that take a value, so the value is not mistaken for a test-name filter
+const CARGO_VALUE_OPTIONS: &[&str] = &[
+    "-p", "--package", "--exclude", "--test", "--bin", "--example", "--bench", "-F", "--features",
+    "--target", "--target-dir", "--manifest-path", "-j", "--jobs", "--profile", "--color",
+    "--message-format", "--config", "-Z", "-E", "--filterset", "--partition",
+];
+
+// libtest options after `--` that take a value; `--skip` is handled as a filter
+const LIBTEST_VALUE_OPTIONS: &[&str] = &["--test-threads", "--format", "--color", "--logfile", "-Z", "--shuffle-seed", "--report-time"];
+
+/// Whether a `cargo test` argument list narrows the run by test name
+fn command_filters_tests(args: &str) -> bool {
+    let mut after_separator = false;
+    let mut skip_value = false;
+    for arg in args.split_whitespace() {
+        if skip_value {
+            skip_value = false;
+            continue;
+        }
+        if arg == "--" {
+            after_separator = true;
+            continue;
+        }
+        // Shell plumbing ends the command
+        if ["|", "&&", "||", ";", ">", "2>&1", "|&"].contains(&arg) {
+            break;
+        }
+        if after_separator && (arg == "--skip" || arg.starts_with("--skip=")) {
+            return true;
+        }
+        let value_options = if after_separator { LIBTEST_VALUE_OPTIONS } else { CARGO_VALUE_OPTIONS };
+        if arg.starts_with('-') {
+            skip_value = !arg.contains('=') && value_options.contains(&arg);
+            continue;
+        }
+        return true;
+    }
+    false
+}
+
+/// Line showing the run was narrowed: a filtering `cargo test` command, a `N filtered out`
+/// summary, or `running 0 tests` from every test binary
+pub fn detect_filtered_run(content: &str) -> Option<String> {
+    let command = content.lines().find(|line| {
+        CARGO_TEST_CMD_RE.captures(line).is_some_and(|captures| command_filters_tests(&captures[1]))
+    });
+    if let Some(line) = command {
+        return Some(line.trim().to_string());
+    }
+
+    let filtered_out = content.lines().find(|line| {
+        FILTERED_OUT_RE.captures(line).is_some_and(|captures| &captures[1] != "0")
+    });
+    if let Some(line) = filtered_out {
+        return Some(line.trim().to_string());
+    }
+
+    let counts: Vec<&str> = content.lines()
+        .filter_map(|line| RUNNING_TESTS_RE.captures(line).map(|captures| captures.get(1).unwrap().as_str()))
+        .collect();
+    if !counts.is_empty() && counts.iter().all(|count| *count == "0") {
+        return Some("running 0 tests".to_string());
+    }
+    None
+}
+
+/// Filter flag for one stage log; only raised when listed tests have no result there
+pub fn stage_filter(stage: &str, content: &str, missing_tests: usize) -> Option<StageFilter> {
+    if missing_tests == 0 {
+        return None;
+    }
+    detect_filtered_run(content).map(|evidence| StageFilter {
+        stage: stage.to_string(),
+        evidence,
+        missing_tests,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_filtering_commands() {
+        assert!(detect_filtered_run("+ cargo test -p parser lexer:: -- --nocapture\n").is_some());
+        assert!(detect_filtered_run("$ cargo test --workspace -- --skip slow_\n").is_some());
+        assert!(detect_filtered_run("cargo test --features serde --test-threads=1\n").is_none());
+        assert!(detect_filtered_run("cargo test --workspace -- --test-threads 1 2>&1 | tee log\n").is_none());
+    }
+
+    #[test]
+    fn test_detects_filtered_and_empty_runs() {
+        let filtered = "running 2 tests\ntest a ... ok\ntest b ... ok\n\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 40 filtered out; finished in 0.01s\n";
+        assert_eq!(detect_filtered_run(filtered), Some("test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 40 filtered out; finished in 0.01s".to_string()));
+
+        let empty = "running 0 tests\n\ntest result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n\nrunning 0 tests\n";
+        assert_eq!(detect_filtered_run(empty), Some("running 0 tests".to_string()));
+
+        let full = "running 0 tests\nrunning 3 tests\ntest result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(detect_filtered_run(full), None);
+        assert_eq!(stage_filter("after", empty, 0), None);
+    }
+}
@@ -0,0 +1,214 @@
+//! Expected layout of a deliverable folder
+//!
+//! Validation lists the Drive folder (or the cached copy) and compares it with the
+//! layout reviewers are asked to ship: `<instance>.json` and the `logs/`, `patches/`
+//! and optional `results/` folders. The outcome is a checklist of present, missing and
+//! unexpected items, so a reviewer sees every layout problem at once rather than the
+//! first one validation trips over.
+
+use crate::api::coverage::is_coverage_file_name;
+use crate::api::log_discovery::LogPatterns;
+use crate::api::run_script::is_run_script_name;
+use crate::app::types::{StructureCheckItem, StructureItemStatus};
+
+const REQUIRED_STAGES: [&str; 3] = ["after", "before", "base"];
+
+// One entry of a folder listing
+#[derive(Clone, Debug, PartialEq)]
+pub struct FolderEntry {
+    pub name: String,
+    pub is_folder: bool,
+}
+
+impl FolderEntry {
+    pub fn file(name: impl Into<String>) -> Self {
+        Self { name: name.into(), is_folder: false }
+    }
+
+    pub fn folder(name: impl Into<String>) -> Self {
+        Self { name: name.into(), is_folder: true }
+    }
+}
+
+// Contents of the deliverable root and of the sub-folders that exist
+pub struct FolderListing<'a> {
+    pub root: &'a [FolderEntry],
+    pub logs: Option<&'a [FolderEntry]>,
+    pub results: Option<&'a [FolderEntry]>,
+    pub patches: Option<&'a [FolderEntry]>,
+}
+
+fn is_patch_file(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.ends_with(".diff") || name.ends_with(".patch")
+}
+
+fn item(path: impl Into<String>, status: StructureItemStatus, required: bool, note: Option<String>) -> StructureCheckItem {
+    StructureCheckItem { path: path.into(), status, required, note }
+}
+
+// Present when at least one file matches, noting which; missing otherwise, noting what was expected
+fn matched_files(path: &str, files: Vec<&str>, required: bool, expected: String) -> StructureCheckItem {
+    if files.is_empty() {
+        item(path, StructureItemStatus::Missing, required, Some(expected))
+    } else {
+        item(path, StructureItemStatus::Present, required, Some(files.join(", ")))
+    }
+}
+
+fn unexpected(folder: &str, entries: &[FolderEntry], known: impl Fn(&FolderEntry) -> bool) -> Vec<StructureCheckItem> {
+    entries
+        .iter()
+        .filter(|entry| !known(entry))
+        .map(|entry| {
+            let suffix = if entry.is_folder { "/" } else { "" };
+            item(format!("{}{}{}", folder, entry.name, suffix), StructureItemStatus::Unexpected, false, None)
+        })
+        .collect()
+}
+
+fn folder_item(name: &str, present: bool, required: bool) -> StructureCheckItem {
+    let status = if present { StructureItemStatus::Present } else { StructureItemStatus::Missing };
+    item(format!("{}/", name), status, required, None)
+}
+
+/// Checklist of the expected layout against what the folder holds, expected items first
+pub fn check_structure(instance_json_name: &str, listing: &FolderListing, patterns: &LogPatterns) -> Vec<StructureCheckItem> {
+    let mut items = Vec::new();
+    let root_files = |name: &str| listing.root.iter().any(|entry| !entry.is_folder && entry.name == name);
+
+    items.push(item(
+        instance_json_name,
+        if root_files(instance_json_name) { StructureItemStatus::Present } else { StructureItemStatus::Missing },
+        true,
+        None,
+    ));
+
+    items.push(folder_item("logs", listing.logs.is_some(), true));
+    if let Some(logs) = listing.logs {
+        let log_files = |stage: &str| -> Vec<&str> {
+            logs.iter()
+                .filter(|entry| !entry.is_folder && patterns.is_stage_log(stage, &entry.name))
+                .map(|entry| entry.name.as_str())
+                .collect()
+        };
+        for stage in REQUIRED_STAGES {
+            let expected = format!("Expected a file matching: {}", patterns.stage_patterns(stage).join(", "));
+            items.push(matched_files(&format!("logs/ {} log", stage), log_files(stage), true, expected));
+        }
+        let expected = format!("Optional; matches {}", patterns.stage_patterns("agent").join(", "));
+        items.push(matched_files("logs/ agent logs", log_files("agent"), false, expected));
+    }
+
+    items.push(folder_item("patches", listing.patches.is_some(), true));
+    if let Some(patches) = listing.patches {
+        let patch_files = patches.iter()
+            .filter(|entry| !entry.is_folder && is_patch_file(&entry.name))
+            .map(|entry| entry.name.as_str())
+            .collect();
+        items.push(matched_files("patches/ *.diff or *.patch", patch_files, false, "No patch files found".to_string()));
+    }
+
+    items.push(folder_item("results", listing.results.is_some(), false));
+    let has_report = listing.results.is_some_and(|results| {
+        results.iter().any(|entry| !entry.is_folder && entry.name.eq_ignore_ascii_case("report.json"))
+    });
+    items.push(item(
+        "results/report.json",
+        if has_report { StructureItemStatus::Present } else { StructureItemStatus::Missing },
+        false,
+        None,
+    ));
+
+    items.extend(unexpected("", listing.root, |entry| {
+        if entry.is_folder {
+            ["logs", "patches", "results"].iter().any(|known| entry.name.eq_ignore_ascii_case(known))
+        } else {
+            entry.name == instance_json_name || is_run_script_name(&entry.name) || is_coverage_file_name(&entry.name)
+        }
+    }));
+    if let Some(logs) = listing.logs {
+        items.extend(unexpected("logs/", logs, |entry| {
+            !entry.is_folder && REQUIRED_STAGES.iter().chain(["agent"].iter()).any(|stage| patterns.is_stage_log(stage, &entry.name))
+        }));
+    }
+    if let Some(patches) = listing.patches {
+        items.extend(unexpected("patches/", patches, |entry| !entry.is_folder && is_patch_file(&entry.name)));
+    }
+    if let Some(results) = listing.results {
+        items.extend(unexpected("results/", results, |entry| {
+            !entry.is_folder && (entry.name.eq_ignore_ascii_case("report.json") || is_coverage_file_name(&entry.name))
+        }));
+    }
+    items
+}
+
+/// Required items the folder lacks
+pub fn missing_required(items: &[StructureCheckItem]) -> Vec<&StructureCheckItem> {
+    items.iter().filter(|item| item.required && item.status == StructureItemStatus::Missing).collect()
+}
+
+/// Plain-text checklist, one line per item, for error details
+pub fn render_checklist(items: &[StructureCheckItem]) -> String {
+    items
+        .iter()
+        .map(|item| {
+            let mark = match item.status {
+                StructureItemStatus::Present => "[x]",
+                StructureItemStatus::Missing if item.required => "[ ]",
+                StructureItemStatus::Missing => "[-]",
+                StructureItemStatus::Unexpected => "[?]",
+            };
+            match &item.note {
+                Some(note) => format!("{} {} ({})", mark, item.path, note),
+                None => format!("{} {}", mark, item.path),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_of<'a>(items: &'a [StructureCheckItem], path: &str) -> Option<&'a StructureItemStatus> {
+        items.iter().find(|item| item.path == path).map(|item| &item.status)
+    }
+
+    #[test]
+    fn test_complete_folder_has_nothing_missing() {
+        let root = vec![
+            FolderEntry::file("django__django-1.json"),
+            FolderEntry::folder("logs"),
+            FolderEntry::folder("patches"),
+            FolderEntry::folder("results"),
+            FolderEntry::file("run.sh"),
+        ];
+        let logs = vec![FolderEntry::file("base.log"), FolderEntry::file("before.log"), FolderEntry::file("after.log")];
+        let patches = vec![FolderEntry::file("gold.diff")];
+        let results = vec![FolderEntry::file("report.json")];
+        let listing = FolderListing { root: &root, logs: Some(logs.as_slice()), results: Some(results.as_slice()), patches: Some(patches.as_slice()) };
+
+        let items = check_structure("django__django-1.json", &listing, &LogPatterns::default());
+        assert!(missing_required(&items).is_empty());
+        assert!(!items.iter().any(|item| item.status == StructureItemStatus::Unexpected));
+        assert_eq!(status_of(&items, "results/report.json"), Some(&StructureItemStatus::Present));
+        assert_eq!(status_of(&items, "logs/ agent logs"), Some(&StructureItemStatus::Missing));
+    }
+
+    #[test]
+    fn test_reports_missing_and_unexpected_items() {
+        let root = vec![FolderEntry::folder("Logs"), FolderEntry::file("notes.txt"), FolderEntry::folder("patches")];
+        let logs = vec![FolderEntry::file("before.log"), FolderEntry::file("after.log"), FolderEntry::file("scratch.txt")];
+        let patches: Vec<FolderEntry> = vec![];
+        let listing = FolderListing { root: &root, logs: Some(logs.as_slice()), results: None, patches: Some(patches.as_slice()) };
+
+        let items = check_structure("x.json", &listing, &LogPatterns::default());
+        let missing: Vec<&str> = missing_required(&items).iter().map(|item| item.path.as_str()).collect();
+        assert_eq!(missing, vec!["x.json", "logs/ base log"]);
+        assert_eq!(status_of(&items, "notes.txt"), Some(&StructureItemStatus::Unexpected));
+        assert_eq!(status_of(&items, "logs/scratch.txt"), Some(&StructureItemStatus::Unexpected));
+        assert!(render_checklist(&items).starts_with("[ ] x.json\n[x] logs/\n"));
+    }
+}
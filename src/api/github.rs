@@ -0,0 +1,116 @@
+//! GitHub integration: render the analysis as an issue/PR comment and post it
+
+use crate::app::types::{rule_description, LogAnalysisResult};
+use crate::api::server_config::load_server_config;
+
+// Keep comments readable; GitHub rejects bodies over 65536 characters anyway
+const MAX_EXAMPLES_PER_RULE: usize = 20;
+
+/// Render the analysis as GitHub-flavoured markdown
+pub fn format_github_comment(deliverable_link: &str, instance_id: &str, task_id: &str, analysis: &LogAnalysisResult) -> String {
+    let violations = analysis.rule_violations.entries();
+    let violated: Vec<_> = violations.iter().filter(|(_, v)| v.has_problem).collect();
+
+    let mut body = String::new();
+    let title = if instance_id.is_empty() { "deliverable".to_string() } else { format!("`{}`", instance_id) };
+    body.push_str(&format!("### SWE Reviewer analysis for {}\n\n", title));
+    if !task_id.is_empty() {
+        body.push_str(&format!("**Task:** `{}`\n\n", task_id));
+    }
+
+    if violated.is_empty() {
+        body.push_str("**Verdict:** ✅ No rule violations found\n\n");
+    } else {
+        body.push_str(&format!("**Verdict:** ❌ {} rule(s) violated\n\n", violated.len()));
+    }
+
+    body.push_str("| Rule | Description | Status | Examples |\n");
+    body.push_str("|---|---|---|---|\n");
+    for (code, violation) in &violations {
+        body.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            code,
+            rule_description(code),
+            if violation.has_problem { "❌" } else { "✅" },
+            violation.examples.len()
+        ));
+    }
+    body.push('\n');
+
+    for (code, violation) in violated {
+        if violation.examples.is_empty() {
+            continue;
+        }
+        body.push_str(&format!("<details><summary>{} examples ({})</summary>\n\n", code, violation.examples.len()));
+        for example in violation.examples.iter().take(MAX_EXAMPLES_PER_RULE) {
+            body.push_str(&format!("- `{}`\n", example.replace('`', "'")));
+        }
+        if violation.examples.len() > MAX_EXAMPLES_PER_RULE {
+            body.push_str(&format!("- … and {} more\n", violation.examples.len() - MAX_EXAMPLES_PER_RULE));
+        }
+        body.push_str("\n</details>\n\n");
+    }
+
+    if !analysis.overrides.is_empty() {
+        body.push_str(&format!("<details><summary>Manual status overrides ({})</summary>\n\n", analysis.overrides.len()));
+        for o in &analysis.overrides {
+            body.push_str(&format!("- `{}` [{}]: {}\n", o.test_name.replace('`', "'"), o.stage, o.describe()));
+        }
+        body.push_str("\n</details>\n\n");
+    }
+
+    body.push_str(&format!(
+        "F2P tests: {} · P2P tests: {}\n",
+        analysis.test_statuses.f2p.len(),
+        analysis.test_statuses.p2p.len()
+    ));
+    if !deliverable_link.is_empty() {
+        body.push_str(&format!("\n[Deliverable]({})\n", deliverable_link));
+    }
+
+    body
+}
+
+/// Post a comment on an issue or PR; returns the comment's URL
+pub async fn post_github_comment(
+    repo: &str,
+    issue_number: &str,
+    body: &str,
+    token: Option<String>,
+) -> Result<String, String> {
+    let repo = repo.trim().trim_matches('/');
+    if repo.split('/').count() != 2 {
+        return Err(format!("Invalid repository '{}', expected owner/name", repo));
+    }
+    let issue_number: u64 = issue_number
+        .trim()
+        .trim_start_matches('#')
+        .parse()
+        .map_err(|_| format!("Invalid issue/PR number '{}'", issue_number))?;
+
+    let token = token
+        .filter(|t| !t.trim().is_empty())
+        .or_else(|| load_server_config().github.token)
+        .filter(|t| !t.trim().is_empty())
+        .ok_or("No GitHub token provided and none configured on the server")?;
+
+    let url = format!("https://api.github.com/repos/{}/issues/{}/comments", repo, issue_number);
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .header(reqwest::header::AUTHORIZATION, format!("Bearer {}", token.trim()))
+        .header(reqwest::header::USER_AGENT, "swe-reviewer-web")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .json(&serde_json::json!({ "body": body }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach GitHub: {}", e))?;
+
+    let status = resp.status();
+    let json: serde_json::Value = resp.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = json["message"].as_str().unwrap_or("unknown error");
+        return Err(format!("GitHub responded with {}: {}", status, message));
+    }
+
+    Ok(json["html_url"].as_str().unwrap_or_default().to_string())
+}
@@ -0,0 +1,312 @@
+//! GitHub Actions runs as a deliverable source
+//!
+//! A run link (`https://github.com/<owner>/<repo>/actions/runs/<id>`) stands in for a Drive
+//! folder: validation lists the run's artifacts, the download step fetches each artifact's
+//! zip into the download cache, and extraction unpacks them into the Drive deliverable
+//! layout (`main/`, `logs/`, `results/`, `patches/`, ...), so every later step reads the
+//! same paths whichever source the deliverable came from. Artifact downloads need a token
+//! even for public repositories; the one under `github.token` in the server config is used.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::api::coverage::{coverage_cache_path, is_coverage_file_name, COVERAGE_FOLDER};
+use crate::api::deliverable_limits::{is_logs_only_path, limit_problems, DeliverableLimits};
+use crate::api::download_progress::DownloadReporter;
+use crate::api::log_discovery::LogPatterns;
+use crate::api::run_script::{is_run_script_name, RUN_SCRIPT_FOLDER};
+use crate::api::server_config::load_server_config;
+use crate::api::storage::deliverable_cache_dir;
+use crate::app::number_format::format_size;
+use crate::app::types::{ActionsRun, ApiError, ApiErrorCode, DownloadResult, FileInfo, ValidationResult};
+
+// Zips land here inside the run's cache folder until they're extracted
+const ARTIFACTS_FOLDER: &str = "artifacts";
+// Folders of the Drive layout an artifact may already be organized in
+const DELIVERABLE_FOLDERS: &[&str] = &["main", "logs", "results", "patches", COVERAGE_FOLDER, RUN_SCRIPT_FOLDER];
+
+fn github_token() -> Result<String, ApiError> {
+    load_server_config().github.token
+        .filter(|token| !token.trim().is_empty())
+        .ok_or_else(|| ApiError::new(ApiErrorCode::PermissionDenied, "No GitHub token is configured on the server")
+            .with_details("Set github.token in the server config; downloading Actions artifacts requires one"))
+}
+
+async fn github_get(url: &str, token: &str) -> Result<reqwest::Response, ApiError> {
+    let resp = reqwest::Client::new()
+        .get(url)
+        .header(reqwest::header::AUTHORIZATION, format!("Bearer {}", token.trim()))
+        .header(reqwest::header::USER_AGENT, "swe-reviewer-web")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .send()
+        .await
+        .map_err(|e| ApiError::new(ApiErrorCode::Network, "Failed to reach GitHub").with_details(e))?;
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+    let code = match status.as_u16() {
+        401 | 403 => ApiErrorCode::PermissionDenied,
+        404 | 410 => ApiErrorCode::NotFound,
+        _ => ApiErrorCode::Network,
+    };
+    let message = resp.json::<serde_json::Value>().await.ok()
+        .and_then(|json| json["message"].as_str().map(str::to_string))
+        .unwrap_or_default();
+    Err(ApiError::new(code, format!("GitHub responded with {}", status)).with_details(format!("{}: {}", url, message)))
+}
+
+// Artifact ids are carried as `<owner>/<repo>/<artifact id>` between validation and download
+fn artifact_file_id(run: &ActionsRun, artifact_id: u64) -> String {
+    format!("{}/{}/{}", run.owner, run.repo, artifact_id)
+}
+
+/// Artifacts of the run, as files to download; expired artifacts are left out. Zips can't be
+/// split, so a logs-only download is held to the same size limits as a full one.
+pub async fn validate_actions_run(run: &ActionsRun) -> Result<ValidationResult, ApiError> {
+    let token = github_token()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/runs/{}/artifacts?per_page=100",
+        run.owner, run.repo, run.run_id
+    );
+    let listing: serde_json::Value = github_get(&url, &token).await?
+        .json()
+        .await
+        .map_err(|e| ApiError::new(ApiErrorCode::Network, "Invalid artifact listing from GitHub").with_details(e))?;
+
+    let mut files_to_download = Vec::new();
+    let mut sized = Vec::new();
+    for artifact in listing["artifacts"].as_array().into_iter().flatten() {
+        let (Some(id), Some(name)) = (artifact["id"].as_u64(), artifact["name"].as_str()) else {
+            continue;
+        };
+        if artifact["expired"].as_bool() == Some(true) {
+            continue;
+        }
+        let path = format!("{}/{}.zip", ARTIFACTS_FOLDER, id);
+        sized.push((format!("{} ({})", path, name), artifact["size_in_bytes"].as_u64()));
+        files_to_download.push(FileInfo { id: artifact_file_id(run, id), name: name.to_string(), path });
+    }
+    if files_to_download.is_empty() {
+        return Err(ApiError::new(ApiErrorCode::MissingFile, "The run has no unexpired artifacts")
+            .with_details("Upload the deliverable folder (main.json, logs/, results/, patches/) as artifacts of the run"));
+    }
+
+    let problems = limit_problems(&sized, &DeliverableLimits::load());
+    if !problems.is_empty() {
+        return Err(ApiError::new(ApiErrorCode::TooLarge, "The run's artifacts are over this server's size limits").with_details(problems.join("\n")));
+    }
+
+    Ok(ValidationResult {
+        files_to_download,
+        folder_id: run.folder_id(),
+        structure: Vec::new(),
+    })
+}
+
+fn run_folder(folder_id: &str) -> Result<PathBuf, ApiError> {
+    deliverable_cache_dir()
+        .map(|root| root.join(folder_id))
+        .ok_or_else(|| ApiError::new(ApiErrorCode::Storage, "No deliverable cache folder"))
+}
+
+fn storage_error(message: &str, error: impl std::fmt::Display) -> ApiError {
+    ApiError::new(ApiErrorCode::Storage, message).with_details(error)
+}
+
+fn too_large(name: &str, limit: &str, max: u64) -> ApiError {
+    ApiError::new(ApiErrorCode::TooLarge, "The run's artifacts are over this server's size limits")
+        .with_details(format!("{} goes over the {} limit of {}", name, limit, format_size(max)))
+}
+
+/// Fetch each artifact's zip into the run's cache folder; zips already there are kept. Zips
+/// are streamed to disk and cut off at the size limits, whatever size the listing gave.
+pub async fn download_actions_artifacts(files_to_download: Vec<FileInfo>, folder_id: String, progress: &DownloadReporter) -> Result<DownloadResult, ApiError> {
+    let token = github_token()?;
+    let folder = run_folder(&folder_id)?;
+    let limits = DeliverableLimits::load();
+    let mut total_bytes = 0u64;
+    let mut downloaded_files = Vec::new();
+    for (index, file_info) in files_to_download.into_iter().enumerate() {
+        let target = folder.join(&file_info.path);
+        if !target.exists() {
+            let (owner, repo, artifact_id) = match file_info.id.splitn(3, '/').collect::<Vec<_>>().as_slice() {
+                [owner, repo, artifact_id] if artifact_id.parse::<u64>().is_ok() => (owner.to_string(), repo.to_string(), artifact_id.to_string()),
+                _ => return Err(ApiError::new(ApiErrorCode::InvalidLink, format!("Invalid artifact id {}", file_info.id))),
+            };
+            // GitHub answers with a redirect to short-lived blob storage, which reqwest follows
+            let url = format!("https://api.github.com/repos/{}/{}/actions/artifacts/{}/zip", owner, repo, artifact_id);
+            let mut resp = github_get(&url, &token).await?;
+            progress.begin_file(index, resp.content_length());
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| storage_error("Failed to create the artifacts folder", e))?;
+            }
+            // Written under a temporary name so an interrupted download isn't taken for a cached zip
+            let partial = target.with_extension("zip.part");
+            let mut file = std::fs::File::create(&partial).map_err(|e| storage_error(&format!("Failed to write artifact {}", file_info.name), e))?;
+            let mut file_bytes = 0u64;
+            let streamed = async {
+                while let Some(chunk) = resp.chunk().await
+                    .map_err(|e| ApiError::new(ApiErrorCode::Network, format!("Download error for artifact {}", file_info.name)).with_details(e))?
+                {
+                    file_bytes += chunk.len() as u64;
+                    total_bytes += chunk.len() as u64;
+                    if let Some(max) = limits.max_file_bytes.filter(|max| file_bytes > *max) {
+                        return Err(too_large(&file_info.name, "per-file", max));
+                    }
+                    if let Some(max) = limits.max_total_bytes.filter(|max| total_bytes > *max) {
+                        return Err(too_large(&file_info.name, "total", max));
+                    }
+                    file.write_all(&chunk).map_err(|e| storage_error(&format!("Failed to write artifact {}", file_info.name), e))?;
+                    progress.add_bytes(index, chunk.len() as u64);
+                }
+                Ok(())
+            }.await;
+            if let Err(e) = streamed {
+                let _ = std::fs::remove_file(&partial);
+                return Err(e);
+            }
+            drop(file);
+            std::fs::rename(&partial, &target).map_err(|e| storage_error(&format!("Failed to write artifact {}", file_info.name), e))?;
+        }
+        // Downloaded now or already in the cache
+        progress.finish_file(index);
+        downloaded_files.push(FileInfo {
+            path: format!("{}/{}", folder_id, file_info.path),
+            ..file_info
+        });
+    }
+    Ok(DownloadResult { downloaded_files })
+}
+
+/// Where a file from an artifact goes in the deliverable layout, or `None` to leave it out.
+/// Only the entry's own folder and file name are used, so entries can't climb out of the cache.
+pub fn deliverable_path(entry_name: &str, patterns: &LogPatterns) -> Option<String> {
+    let path = Path::new(entry_name);
+    let file_name = path.file_name()?.to_str()?;
+    let parent = path.parent()
+        .and_then(|parent| parent.file_name())
+        .and_then(|parent| parent.to_str())
+        .map(str::to_lowercase);
+    if let Some(folder) = parent.as_deref().filter(|parent| DELIVERABLE_FOLDERS.contains(parent)) {
+        return Some(format!("{}/{}", folder, file_name));
+    }
+    if is_coverage_file_name(file_name) {
+        return Some(coverage_cache_path(parent.as_deref().unwrap_or(""), file_name));
+    }
+    let lower = file_name.to_lowercase();
+    let folder = if ["base", "before", "after", "agent"].iter().any(|stage| patterns.is_stage_log(stage, file_name)) {
+        "logs"
+    } else if lower == "report.json" {
+        "results"
+    } else if lower.ends_with(".diff") || lower.ends_with(".patch") {
+        "patches"
+    } else if is_run_script_name(file_name) {
+        RUN_SCRIPT_FOLDER
+    } else if lower.ends_with(".json") {
+        "main"
+    } else {
+        return None;
+    };
+    Some(format!("{}/{}", folder, file_name))
+}
+
+/// Unpack the downloaded zips into the deliverable layout; `logs_only` keeps main.json and the logs
+pub fn extract_actions_artifacts(folder_id: &str, logs_only: bool) -> Result<DownloadResult, ApiError> {
+    let folder = run_folder(folder_id)?;
+    let patterns = LogPatterns::load();
+    let max_file_bytes = DeliverableLimits::load().max_file_bytes;
+    let mut zips: Vec<PathBuf> = std::fs::read_dir(folder.join(ARTIFACTS_FOLDER))
+        .map_err(|e| storage_error("No downloaded artifacts to extract", e))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "zip"))
+        .collect();
+    zips.sort();
+
+    let mut extracted: Vec<FileInfo> = Vec::new();
+    for zip_path in zips {
+        let file = std::fs::File::open(&zip_path).map_err(|e| storage_error("Failed to open an artifact", e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, format!("{} is not a valid zip", zip_path.display())).with_details(e))?;
+        for index in 0..archive.len() {
+            let mut entry = archive.by_index(index)
+                .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to read an artifact entry").with_details(e))?;
+            if entry.is_dir() {
+                continue;
+            }
+            let Some(relative) = deliverable_path(entry.name(), &patterns) else {
+                continue;
+            };
+            if logs_only && !is_logs_only_path(&relative) {
+                continue;
+            }
+            // The size in the zip header can't be trusted to stop a zip bomb; the read is capped too
+            if max_file_bytes.is_some_and(|max| entry.size() > max) {
+                return Err(ApiError::new(ApiErrorCode::TooLarge, "An artifact file is over this server's size limits")
+                    .with_details(format!("{} in {}", entry.name(), zip_path.display())));
+            }
+            let mut content = Vec::new();
+            let limit = max_file_bytes.map(|max| max + 1).unwrap_or(u64::MAX);
+            (&mut entry).take(limit).read_to_end(&mut content)
+                .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, format!("Failed to unpack {}", entry.name())).with_details(e))?;
+            if max_file_bytes.is_some_and(|max| content.len() as u64 > max) {
+                return Err(ApiError::new(ApiErrorCode::TooLarge, "An artifact file is over this server's size limits")
+                    .with_details(format!("{} in {}", entry.name(), zip_path.display())));
+            }
+
+            let target = folder.join(&relative);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| storage_error("Failed to create a deliverable folder", e))?;
+            }
+            std::fs::write(&target, content).map_err(|e| storage_error(&format!("Failed to write {}", relative), e))?;
+            let path = format!("{}/{}", folder_id, relative);
+            // Later artifacts win when two carry the same file
+            extracted.retain(|file_info| file_info.path != path);
+            extracted.push(FileInfo {
+                id: "cached".to_string(),
+                name: relative.rsplit('/').next().unwrap_or(&relative).to_string(),
+                path,
+            });
+        }
+    }
+
+    if !extracted.iter().any(|file_info| file_info.path.starts_with(&format!("{}/main/", folder_id))) {
+        return Err(ApiError::new(ApiErrorCode::MissingFile, "None of the run's artifacts holds the instance JSON")
+            .with_details("Expected a .json file at an artifact's root or in its main/ folder"));
+    }
+    Ok(DownloadResult { downloaded_files: extracted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_run_links() {
+        let run = ActionsRun::parse("https://github.com/octo/repo.js/actions/runs/123/job/456?pr=7").unwrap();
+        assert_eq!(run, ActionsRun { owner: "octo".to_string(), repo: "repo.js".to_string(), run_id: 123 });
+        assert_eq!(run.folder_id(), "gha-octo-repo_djs-123");
+        // Owners and repos may hold the separator and the escapes themselves
+        let folder = |owner: &str, repo: &str| ActionsRun { owner: owner.to_string(), repo: repo.to_string(), run_id: 1 }.folder_id();
+        assert_ne!(folder("a-b", "c"), folder("a", "b-c"));
+        assert_ne!(folder("a", "b.c"), folder("a", "b_c"));
+        assert_ne!(folder("a", "b_dc"), folder("a", "b.c"));
+        assert_eq!(ActionsRun::parse("https://github.com/octo/repo/pull/5"), None);
+        assert_eq!(ActionsRun::parse("https://drive.google.com/drive/folders/abc"), None);
+        assert_eq!(ActionsRun::parse("https://github.com/../repo/actions/runs/1"), None);
+    }
+
+    #[test]
+    fn test_maps_artifact_entries_to_the_deliverable_layout() {
+        let patterns = LogPatterns::default();
+        assert_eq!(deliverable_path("deliverable/logs/after.log", &patterns).as_deref(), Some("logs/after.log"));
+        assert_eq!(deliverable_path("before.log", &patterns).as_deref(), Some("logs/before.log"));
+        assert_eq!(deliverable_path("out/report.json", &patterns).as_deref(), Some("results/report.json"));
+        assert_eq!(deliverable_path("gold.diff", &patterns).as_deref(), Some("patches/gold.diff"));
+        assert_eq!(deliverable_path("django__django-1.json", &patterns).as_deref(), Some("main/django__django-1.json"));
+        assert_eq!(deliverable_path("../../etc/passwd", &patterns), None);
+        assert_eq!(deliverable_path("notes.txt", &patterns), None);
+        assert_eq!(deliverable_path("web/coverage.xml", &patterns).as_deref(), Some("coverage/web/coverage.xml"));
+        assert_eq!(deliverable_path("coverage.xml", &patterns).as_deref(), Some("coverage/coverage.xml"));
+    }
+}
@@ -0,0 +1,180 @@
+use regex::Regex;
+use std::collections::HashSet;
+use lazy_static::lazy_static;
+
+use super::log_parser::{LogParserTrait, ParsedLog, TestOutcome};
+
+lazy_static! {
+    // `go test -v` result lines; subtests are indented and named `TestParent/sub_case`
+    static ref VERBOSE_RESULT_RE: Regex = Regex::new(r"^\s*--- (PASS|FAIL|SKIP): (\S+) \([\d.]+s\)").unwrap();
+    static ref VERBOSE_RUN_RE: Regex = Regex::new(r"^=== RUN\s+(\S+)").unwrap();
+    // Package summary closing a package's tests: `ok  \tpkg\t0.01s`, `FAIL\tpkg\t0.3s`
+    static ref PACKAGE_RESULT_RE: Regex = Regex::new(r"^(ok|FAIL)\s+\S+\s").unwrap();
+    // gotestsum `testname` format: `PASS pkg/path.TestName (0.00s)`
+    static ref GOTESTSUM_RESULT_RE: Regex = Regex::new(r"^(PASS|FAIL|SKIP) \S+?\.((?:Test|Example|Fuzz)\S*) \([\d.]+s\)").unwrap();
+    // gotestsum summary sections: `=== FAIL: pkg/path TestName (0.00s)`
+    static ref GOTESTSUM_SUMMARY_RE: Regex = Regex::new(r"^=== (FAIL|SKIP): \S+ (\S+) \([\d.]+s\)").unwrap();
+}
+
+/// Framework names a run script can pin; the parser reads all three formats either way
+pub(crate) const PINNABLE_FRAMEWORKS: &[&str] = &["gotest", "gotestsum"];
+
+#[derive(Default)]
+pub struct GoLogParser;
+
+impl GoLogParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LogParserTrait for GoLogParser {
+    fn get_language(&self) -> &'static str {
+        "go"
+    }
+
+    fn parse_content(&self, _file_path: &str, content: &str, _framework: Option<&str>) -> Result<ParsedLog, String> {
+        Ok(parse_go_log(content))
+    }
+}
+
+/// Whether a log looks like `go test` (plain, `-json` or gotestsum) output
+pub(crate) fn looks_go_test_output(content: &str) -> bool {
+    content.contains("--- PASS: ")
+        || content.contains("--- FAIL: ")
+        || content.contains("=== RUN   Test")
+        || (content.contains("\"Action\":") && content.contains("\"Test\":"))
+        || content.lines().any(|line| GOTESTSUM_RESULT_RE.is_match(line))
+}
+
+// `pass`/`fail`/`skip`, as the JSON actions and the lowercased result lines spell them
+fn outcome(status: &str) -> TestOutcome {
+    match status {
+        "pass" => TestOutcome::Passed,
+        "fail" => TestOutcome::Failed,
+        _ => TestOutcome::Ignored,
+    }
+}
+
+// One `go test -json` event, as (action, test); package-level events have no test
+fn json_event(line: &str) -> Option<(String, Option<String>)> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('{') {
+        return None;
+    }
+    let event: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+    let action = event.get("Action")?.as_str()?.to_string();
+    let test = event.get("Test").and_then(|test| test.as_str()).map(str::to_string);
+    Some((action, test))
+}
+
+// End of a package's tests; when the package failed, tests it started without a result are failures
+fn close_package(parsed: &mut ParsedLog, pending: &mut Vec<String>, failed: bool) {
+    for name in pending.drain(..) {
+        if failed && !parsed.passed.contains(&name) && !parsed.failed.contains(&name) && !parsed.ignored.contains(&name) {
+            parsed.note(format!("{} started but never reported; counted as failed with its package", name));
+            parsed.record(name, TestOutcome::Failed);
+        }
+    }
+}
+
+pub fn parse_go_log(content: &str) -> ParsedLog {
+    let mut parsed = ParsedLog::new();
+    // Tests started in the current package that haven't reported a result yet; a panic ends
+    // the package without a `--- FAIL` line for them
+    let mut pending: Vec<String> = Vec::new();
+    let mut formats: HashSet<&'static str> = HashSet::new();
+
+    for line in content.lines() {
+        if let Some((action, test)) = json_event(line) {
+            formats.insert("go_test_json");
+            match (action.as_str(), test) {
+                ("run", Some(test)) => pending.push(test),
+                ("pass" | "fail" | "skip", Some(test)) => {
+                    pending.retain(|name| *name != test);
+                    parsed.record(test, outcome(&action));
+                }
+                ("pass" | "fail", None) => close_package(&mut parsed, &mut pending, action == "fail"),
+                _ => {}
+            }
+            continue;
+        }
+        if let Some(captures) = VERBOSE_RUN_RE.captures(line) {
+            pending.push(captures[1].to_string());
+        } else if let Some(captures) = VERBOSE_RESULT_RE.captures(line) {
+            formats.insert("go_test");
+            let name = captures[2].to_string();
+            pending.retain(|pending_name| *pending_name != name);
+            parsed.record(name, outcome(&captures[1].to_lowercase()));
+        } else if let Some(captures) = GOTESTSUM_RESULT_RE.captures(line).or_else(|| GOTESTSUM_SUMMARY_RE.captures(line)) {
+            formats.insert("gotestsum");
+            let name = captures[2].to_string();
+            pending.retain(|pending_name| *pending_name != name);
+            parsed.record(name, outcome(&captures[1].to_lowercase()));
+        } else if let Some(captures) = PACKAGE_RESULT_RE.captures(line) {
+            close_package(&mut parsed, &mut pending, &captures[1] == "FAIL");
+        }
+    }
+
+    let parser = ["go_test_json", "gotestsum", "go_test"]
+        .into_iter()
+        .find(|format| formats.contains(format))
+        .unwrap_or("go_test");
+    parsed.finalize();
+    parsed.with_parser(parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_verbose_output_with_subtests_and_panics() {
+        let log = "=== RUN   TestAdd\n\
+                   === RUN   TestAdd/small_numbers\n\
+                   --- PASS: TestAdd (0.00s)\n    \
+                   --- PASS: TestAdd/small_numbers (0.00s)\n\
+                   === RUN   TestSub\n\
+                   --- FAIL: TestSub (0.01s)\n\
+                   === RUN   TestLater\n\
+                   --- SKIP: TestLater (0.00s)\n\
+                   FAIL\n\
+                   FAIL\tgithub.com/acme/calc\t0.012s\n\
+                   === RUN   TestCrash\n\
+                   panic: runtime error: index out of range [recovered]\n\
+                   FAIL\tgithub.com/acme/calc/parse\t0.004s\n";
+        let parsed = parse_go_log(log);
+        assert!(parsed.passed.contains("TestAdd") && parsed.passed.contains("TestAdd/small_numbers"));
+        assert!(parsed.failed.contains("TestSub") && parsed.failed.contains("TestCrash"));
+        assert!(parsed.ignored.contains("TestLater"));
+        assert_eq!(parsed.all.len(), 5);
+        assert_eq!(parsed.parser.as_deref(), Some("go_test"));
+    }
+
+    #[test]
+    fn test_parses_json_and_gotestsum_output() {
+        let json = "{\"Action\":\"run\",\"Package\":\"acme/calc\",\"Test\":\"TestAdd\"}\n\
+                    {\"Action\":\"output\",\"Package\":\"acme/calc\",\"Test\":\"TestAdd\",\"Output\":\"--- FAIL: TestAdd (0.00s)\\n\"}\n\
+                    {\"Action\":\"pass\",\"Package\":\"acme/calc\",\"Test\":\"TestAdd\",\"Elapsed\":0}\n\
+                    {\"Action\":\"skip\",\"Package\":\"acme/calc\",\"Test\":\"TestSkip\",\"Elapsed\":0}\n\
+                    {\"Action\":\"run\",\"Package\":\"acme/calc\",\"Test\":\"TestHang\"}\n\
+                    {\"Action\":\"fail\",\"Package\":\"acme/calc\",\"Elapsed\":600}\n";
+        let parsed = parse_go_log(json);
+        assert!(parsed.passed.contains("TestAdd"));
+        assert!(parsed.ignored.contains("TestSkip"));
+        assert!(parsed.failed.contains("TestHang"));
+        assert_eq!(parsed.parser.as_deref(), Some("go_test_json"));
+
+        let gotestsum = "PASS internal/calc.TestAdd (0.00s)\n\
+                         PASS github.com/acme/calc.TestAdd/small (0.00s)\n\
+                         FAIL internal/calc.TestSub (0.01s)\n\n\
+                         === Failed\n\
+                         === FAIL: internal/calc TestSub (0.01s)\n\
+                         DONE 3 tests, 1 failure in 0.3s\n";
+        let parsed = parse_go_log(gotestsum);
+        assert!(parsed.passed.contains("TestAdd") && parsed.passed.contains("TestAdd/small"));
+        assert!(parsed.failed.contains("TestSub"));
+        assert_eq!(parsed.parser.as_deref(), Some("gotestsum"));
+        assert!(looks_go_test_output(gotestsum) && looks_go_test_output(json));
+    }
+}
@@ -0,0 +1,220 @@
+use regex::Regex;
+use std::collections::HashSet;
+use lazy_static::lazy_static;
+
+use super::log_parser::{LogParserTrait, ParsedLog, TestOutcome};
+
+lazy_static! {
+    // Surefire per-class summary: `Tests run: 3, Failures: 1, Errors: 0, Skipped: 0, Time elapsed: 0.1 s <<< FAILURE! - in com.acme.FooTest`,
+    // with `-- in` since Surefire 3
+    static ref SUREFIRE_CLASS_RE: Regex = Regex::new(
+        r"Tests run: (\d+), Failures: (\d+), Errors: (\d+), Skipped: (\d+).*? --? in (\S+)"
+    ).unwrap();
+    // Surefire per-test lines: `com.acme.FooTest.testBar -- Time elapsed: 0.01 s <<< FAILURE!` (3.x,
+    // older 3.x releases leave out the `--`) or `testBar(com.acme.FooTest)  Time elapsed: 0.01 s  <<< ERROR!`
+    // (2.x); passing tests only show up with the plain report format
+    static ref SUREFIRE_TEST_RE: Regex = Regex::new(
+        r"^(?:\[\w+\]\s+)?([\w$.\[\]]+?)(?:\(([\w$.]+)\))?\s+(?:--\s+)?Time elapsed: [\d.,]+ \w+(?:\s+<<< (FAILURE|ERROR|SKIPPED)!)?"
+    ).unwrap();
+    // Gradle `testLogging` events: `com.acme.FooTest > testBar() FAILED`, nested classes add more ` > `
+    static ref GRADLE_TEST_RE: Regex = Regex::new(r"^\s*(\S.*? > .+?) (PASSED|FAILED|SKIPPED)\s*$").unwrap();
+    // JUnit 5 console launcher tree: `│  ├─ addsTwoNumbers() ✔`
+    static ref JUNIT5_TREE_RE: Regex = Regex::new(r"^([│ ]*)[├└]─ (.+?) ([✔✘↷■])(?:\s.*)?$").unwrap();
+}
+
+/// Framework names a run script can pin; the parser reads every format either way
+pub(crate) const PINNABLE_FRAMEWORKS: &[&str] = &["maven", "gradle", "junit5"];
+
+// Engine roots of the JUnit 5 tree, not part of any test name
+const JUNIT5_ENGINES: &[&str] = &["JUnit Jupiter", "JUnit Vintage", "JUnit Platform Suite"];
+
+#[derive(Default)]
+pub struct JavaLogParser;
+
+impl JavaLogParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LogParserTrait for JavaLogParser {
+    fn get_language(&self) -> &'static str {
+        "java"
+    }
+
+    fn parse_content(&self, _file_path: &str, content: &str, _framework: Option<&str>) -> Result<ParsedLog, String> {
+        Ok(parse_java_log(content))
+    }
+}
+
+/// Whether a log looks like Maven Surefire, Gradle or JUnit 5 console output
+pub(crate) fn looks_java_test_output(content: &str) -> bool {
+    (content.contains("Tests run: ") && content.contains(", Failures: "))
+        || content.contains("JUnit Jupiter")
+        || content.lines().any(|line| GRADLE_TEST_RE.is_match(line))
+}
+
+// `testBar()` and `testBar` name the same method
+fn method_name(name: &str) -> &str {
+    name.trim().strip_suffix("()").unwrap_or(name.trim())
+}
+
+// Leaves of the JUnit 5 tree, named `Class.method` (nested classes joined with `.`)
+fn parse_junit5_tree(content: &str, parsed: &mut ParsedLog) -> bool {
+    let nodes: Vec<(usize, String, char)> = content
+        .lines()
+        .filter_map(|line| JUNIT5_TREE_RE.captures(line))
+        .map(|captures| (captures[1].chars().count() / 3, captures[2].trim().to_string(), captures[3].chars().next().unwrap_or('■')))
+        .collect();
+    let mut path: Vec<String> = Vec::new();
+    for (index, (depth, name, marker)) in nodes.iter().enumerate() {
+        path.truncate(*depth);
+        path.push(name.clone());
+        let is_leaf = nodes.get(index + 1).is_none_or(|(next_depth, _, _)| next_depth <= depth);
+        if !is_leaf {
+            continue;
+        }
+        let parts: Vec<&str> = path.iter()
+            .filter(|part| !JUNIT5_ENGINES.contains(&part.as_str()))
+            .map(|part| method_name(part))
+            .collect();
+        if parts.len() < 2 {
+            continue;
+        }
+        let outcome = match marker {
+            '✔' => TestOutcome::Passed,
+            '✘' => TestOutcome::Failed,
+            _ => TestOutcome::Ignored,
+        };
+        parsed.record(parts.join("."), outcome);
+    }
+    !nodes.is_empty()
+}
+
+// Surefire's count of a class's tests, checked against the per-test lines once the log is read
+struct ClassSummary {
+    class: String,
+    run: usize,
+    failed: usize,
+}
+
+// Classes whose per-test lines don't add up to their summary; failing tests are always printed,
+// so too few of them means lines the parser missed
+fn class_summary_warnings(parsed: &ParsedLog, summaries: &[ClassSummary]) -> Vec<String> {
+    summaries.iter().filter_map(|summary| {
+        let prefix = format!("{}.", summary.class);
+        let in_class = |set: &HashSet<String>| set.iter().filter(|name| name.starts_with(&prefix)).count();
+        let (listed, failed) = (in_class(&parsed.all), in_class(&parsed.failed));
+        (failed != summary.failed || listed > summary.run).then(|| format!(
+            "Surefire counted {} test(s) with {} failure(s) in {}, but the log lists {} with {} failing",
+            summary.run, summary.failed, summary.class, listed, failed
+        ))
+    }).collect()
+}
+
+pub fn parse_java_log(content: &str) -> ParsedLog {
+    let mut parsed = ParsedLog::new();
+    let mut formats: HashSet<&'static str> = HashSet::new();
+    let mut summaries = Vec::new();
+
+    for line in content.lines() {
+        if let Some(captures) = SUREFIRE_CLASS_RE.captures(line) {
+            formats.insert("maven");
+            // Not a test of its own; the counts only check the per-test lines
+            let count = |index: usize| captures[index].parse::<usize>().unwrap_or(0);
+            summaries.push(ClassSummary { class: captures[5].to_string(), run: count(1), failed: count(2) + count(3) });
+        } else if let Some(captures) = SUREFIRE_TEST_RE.captures(line) {
+            formats.insert("maven");
+            let name = match captures.get(2) {
+                Some(class) => format!("{}.{}", class.as_str(), &captures[1]),
+                None => captures[1].to_string(),
+            };
+            let outcome = match captures.get(3).map(|status| status.as_str()) {
+                Some("FAILURE" | "ERROR") => TestOutcome::Failed,
+                Some(_) => TestOutcome::Ignored,
+                None => TestOutcome::Passed,
+            };
+            parsed.record(name, outcome);
+        } else if let Some(captures) = GRADLE_TEST_RE.captures(line) {
+            formats.insert("gradle");
+            let name = captures[1].split(" > ").map(method_name).collect::<Vec<_>>().join(".");
+            let outcome = match &captures[2] {
+                "PASSED" => TestOutcome::Passed,
+                "FAILED" => TestOutcome::Failed,
+                _ => TestOutcome::Ignored,
+            };
+            parsed.record(name, outcome);
+        }
+    }
+    if parse_junit5_tree(content, &mut parsed) {
+        formats.insert("junit5");
+    }
+
+    let parser = ["gradle", "junit5", "maven"]
+        .into_iter()
+        .find(|format| formats.contains(format))
+        .unwrap_or("maven");
+    parsed.finalize();
+    for warning in class_summary_warnings(&parsed, &summaries) {
+        parsed.note(warning);
+    }
+    parsed.with_parser(parser)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_surefire_output() {
+        let log = "[INFO] Running com.acme.CalcTest\n\
+                   [ERROR] Tests run: 3, Failures: 1, Errors: 0, Skipped: 1, Time elapsed: 0.05 s <<< FAILURE! - in com.acme.CalcTest\n\
+                   [ERROR] com.acme.CalcTest.testSub  Time elapsed: 0.01 s  <<< FAILURE!\n\
+                   [INFO] com.acme.CalcTest.testAdd  Time elapsed: 0.002 s\n\
+                   [ERROR] testDiv(com.acme.OldTest)  Time elapsed: 0.003 s  <<< ERROR!\n\
+                   [INFO] Tests run: 2, Failures: 0, Errors: 0, Skipped: 0, Time elapsed: 0.01 s - in com.acme.ParseTest\n";
+        let parsed = parse_java_log(log);
+        assert!(parsed.failed.contains("com.acme.CalcTest.testSub"));
+        assert!(parsed.failed.contains("com.acme.OldTest.testDiv"));
+        assert!(parsed.passed.contains("com.acme.CalcTest.testAdd"));
+        // Class summaries are counts, not tests
+        assert!(!parsed.all.contains("com.acme.CalcTest") && !parsed.all.contains("com.acme.ParseTest"));
+        assert!(parsed.warnings.is_empty());
+        assert_eq!(parsed.parser.as_deref(), Some("maven"));
+
+        let surefire3 = "[ERROR] Tests run: 2, Failures: 2, Errors: 0, Skipped: 0, Time elapsed: 0.05 s <<< FAILURE! -- in com.acme.NewTest\n\
+                         [ERROR] com.acme.NewTest.testOne -- Time elapsed: 0.01 s <<< FAILURE!\n";
+        let parsed = parse_java_log(surefire3);
+        assert!(parsed.failed.contains("com.acme.NewTest.testOne"));
+        assert_eq!(parsed.all.len(), 1);
+        // The summary counted a second failure the log never showed
+        assert_eq!(parsed.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_parses_gradle_and_junit5_tree_output() {
+        let gradle = "com.acme.CalcTest > testAdd() PASSED\n\
+                      com.acme.CalcTest > Nested > testSub() FAILED\n\
+                      com.acme.CalcTest > testDiv SKIPPED\n\
+                      3 tests completed, 1 failed, 1 skipped\n";
+        let parsed = parse_java_log(gradle);
+        assert!(parsed.passed.contains("com.acme.CalcTest.testAdd"));
+        assert!(parsed.failed.contains("com.acme.CalcTest.Nested.testSub"));
+        assert!(parsed.ignored.contains("com.acme.CalcTest.testDiv"));
+        assert_eq!(parsed.parser.as_deref(), Some("gradle"));
+
+        let tree = "╷\n\
+                    ├─ JUnit Jupiter ✔\n\
+                    │  ├─ CalculatorTests ✔\n\
+                    │  │  ├─ addsTwoNumbers() ✔\n\
+                    │  │  ├─ divides() ✘ expected: <2> but was: <3>\n\
+                    │  │  └─ later() ↷ disabled\n\
+                    └─ JUnit Vintage ✔\n";
+        let parsed = parse_java_log(tree);
+        assert!(parsed.passed.contains("CalculatorTests.addsTwoNumbers"));
+        assert!(parsed.failed.contains("CalculatorTests.divides"));
+        assert!(parsed.ignored.contains("CalculatorTests.later"));
+        assert_eq!(parsed.all.len(), 3);
+        assert!(looks_java_test_output(tree) && looks_java_test_output(gradle));
+    }
+}
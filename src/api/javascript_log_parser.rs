@@ -1,13 +1,63 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use regex::Regex;
 use lazy_static::lazy_static;
-use crate::api::log_parser::{LogParserTrait, ParsedLog};
+use crate::api::log_parser::{CollapsedSuite, LogParserTrait, ParsedLog};
+use crate::api::server_config::ParsersConfig;
 use crate::api::test_detection::detect_js_testing_framework;
+use crate::app::types::BrowserRun;
+
+// Frameworks a run script or detection rule can pin; names match `get_parser_by_name`
+pub(crate) const PINNABLE_FRAMEWORKS: &[&str] = &[
+    "calypso", "mocha", "jest", "jest-json", "vitest", "karma", "tap", "chartjs", "marked",
+    "react-pdf", "p5js", "cypress", "playwright", "jasmine", "qunit", "ava",
+];
 
 pub struct JavaScriptLogParser {
     parser_name: String,
     project_path: Option<String>,
+    retry_policy: RetryPolicy,
+    // Prefix Jest test names with the file from the suite's PASS/FAIL header
+    jest_file_names: bool,
+}
+
+/// How retried attempts of a Playwright test combine into one status
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RetryPolicy {
+    /// Any failed attempt fails the test, so flaky tests stay visible
+    #[default]
+    AnyFail,
+    /// The last attempt decides, the way Playwright itself reports flaky tests
+    FinalAttempt,
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &ParsersConfig) -> Self {
+        match config.playwright_retry_policy.as_deref().map(str::trim) {
+            None | Some("") | Some("any_fail") => Self::AnyFail,
+            Some("final_attempt") => Self::FinalAttempt,
+            Some(other) => {
+                eprintln!("Unknown playwright_retry_policy '{}'; using any_fail", other);
+                Self::AnyFail
+            }
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::AnyFail => "any_fail",
+            Self::FinalAttempt => "final_attempt",
+        }
+    }
+
+    // Status of one project's attempts, in the order they ran
+    fn combine(self, attempts: &[TestStatus]) -> Option<TestStatus> {
+        let last = attempts.last()?.clone();
+        match self {
+            Self::AnyFail if attempts.contains(&TestStatus::Failed) => Some(TestStatus::Failed),
+            _ => Some(last),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -29,26 +79,117 @@ impl TestStatus {
     }
 }
 
+// A failure in any browser fails the test; a skip only stands when no browser ran it
+fn status_rank(status: &TestStatus) -> u8 {
+    match status {
+        TestStatus::Failed => 3,
+        TestStatus::Passed => 2,
+        TestStatus::Skipped => 1,
+        TestStatus::Pending => 0,
+    }
+}
+
+/// Split Karma's `Chrome 120.0.0.0 (Linux x86_64)` browser prefix off a line
+fn split_browser_prefix(line: &str) -> Option<(&str, &str)> {
+    lazy_static! {
+        static ref BROWSER_PREFIX_RE: Regex = Regex::new(r"^\s*([A-Z][\w.]*(?: [A-Z][\w.]*)* \d+(?:\.\d+)* \([^)]*\)):?(?:\s+(.*))?$").unwrap();
+    }
+    let captures = BROWSER_PREFIX_RE.captures(line)?;
+    let browser = captures.get(1)?.as_str();
+    let rest = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+    Some((browser, rest))
+}
+
+// Results of a run that may repeat every test once per browser
+#[derive(Default)]
+struct BrowserResults {
+    merged: HashMap<String, TestStatus>,
+    per_browser: BTreeMap<String, HashMap<String, TestStatus>>,
+}
+
+impl BrowserResults {
+    fn record(&mut self, browser: Option<&str>, name: String, status: TestStatus) {
+        if let Some(browser) = browser {
+            self.per_browser.entry(browser.to_string()).or_default().insert(name.clone(), status.clone());
+        }
+        let worse = self.merged.get(&name).is_none_or(|existing| status_rank(&status) > status_rank(existing));
+        if worse {
+            self.merged.insert(name, status);
+        }
+    }
+
+    // Merged statuses and the per-browser breakdown, from the one pass over the log
+    fn into_parts(self) -> (HashMap<String, TestStatus>, Vec<BrowserRun>) {
+        let runs = self.per_browser.into_iter().map(|(browser, results)| {
+            let mut failed_tests: Vec<String> = results.iter()
+                .filter(|(_, status)| **status == TestStatus::Failed)
+                .map(|(name, _)| name.clone())
+                .collect();
+            failed_tests.sort();
+            BrowserRun {
+                stage: String::new(),
+                browser,
+                passed: results.values().filter(|status| **status == TestStatus::Passed).count(),
+                failed: failed_tests.len(),
+                ignored: results.values().filter(|status| matches!(status, TestStatus::Skipped | TestStatus::Pending)).count(),
+                failed_tests,
+            }
+        }).collect();
+        (self.merged, runs)
+    }
+}
+
+lazy_static! {
+    // Newer Vitest collapses passing files to `✓ src/foo.test.ts (12 tests) 320ms`,
+    // or `❯ src/bar.test.ts (5 tests | 2 failed) 40ms` when some of them failed
+    static ref VITEST_SUITE_RE: Regex = Regex::new(r"^\s*([✓❯×↓])\s+(\S+\.[cm]?[jt]sx?)\s+\((\d+) tests?([^)]*)\)").unwrap();
+}
+
+/// Test files Vitest summarized without listing each test, with their test counts; skipped
+/// suites are left out since their unlisted tests did not pass
+fn vitest_collapsed_suites(log: &str) -> Vec<CollapsedSuite> {
+    let mut suites: Vec<CollapsedSuite> = log
+        .lines()
+        .filter_map(|line| VITEST_SUITE_RE.captures(line))
+        .filter(|captures| &captures[1] != "↓" && !captures[4].contains("skipped"))
+        .map(|captures| CollapsedSuite { file: captures[2].to_string(), tests: captures[3].parse().unwrap_or(0) })
+        .collect();
+    suites.dedup();
+    suites
+}
+
 impl JavaScriptLogParser {
+    /// Built-in settings; the parser registry builds it with `from_config` instead
     pub fn new() -> Self {
+        Self::from_config(&ParsersConfig::default())
+    }
+
+    /// Retry policy and Jest file names from the server config's `parsers` section
+    pub fn from_config(config: &ParsersConfig) -> Self {
         Self {
             parser_name: "auto".to_string(),
             project_path: None,
+            retry_policy: RetryPolicy::from_config(config),
+            jest_file_names: config.jest_file_names,
         }
     }
 
     pub fn new_with_parser(parser_name: &str) -> Self {
-        Self {
-            parser_name: parser_name.to_string(),
-            project_path: None,
-        }
+        Self { parser_name: parser_name.to_string(), ..Self::new() }
     }
 
     pub fn new_with_project_path(project_path: &str) -> Self {
-        Self {
-            parser_name: "auto".to_string(),
-            project_path: Some(project_path.to_string()),
-        }
+        Self { project_path: Some(project_path.to_string()), ..Self::new() }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_jest_file_names(mut self, jest_file_names: bool) -> Self {
+        self.jest_file_names = jest_file_names;
+        self
     }
 
     fn parse_log_calypso(&self, log: &str) -> HashMap<String, TestStatus> {
@@ -109,80 +250,59 @@ impl JavaScriptLogParser {
             static ref CROSS_RE: Regex = Regex::new(r"^\s*[×✕]\s+(.*)").unwrap();
             static ref PEND_RE: Regex = Regex::new(r"^\s*[-•]\s+(.*)").unwrap();
             static ref SUMMARY_RE: Regex = Regex::new(r"^\s*\d+\s+(passing|failing|pending)").unwrap();
+            static ref FAILURE_DETAIL_RE: Regex = Regex::new(r"^\s*\d+\)\s").unwrap();
             static ref DUR_TAIL_RE: Regex = Regex::new(r"\s+\([\d\.]+ ?[a-zA-Z]+\)$").unwrap();
         }
 
         let mut test_status_map = HashMap::new();
-        let mut suite_stack: Vec<String> = Vec::new();
-        let mut count_empty_lines = 0;
+        // Open suites with the indentation of their header; a line closes every suite indented as deep or deeper
+        let mut suite_stack: Vec<(usize, String)> = Vec::new();
+        // Between the `N passing` summary and the next run, mocha lists failures with their stack traces
+        let mut in_summary = false;
 
         for raw_line in log.lines() {
             let line = ANSI_RE.replace_all(raw_line.trim_end(), "").to_string();
 
+            // Reporters pad nested suites with blank lines; scope comes from indentation alone
             if line.is_empty() {
-                count_empty_lines += 1;
-                if count_empty_lines >= 2 {
-                    count_empty_lines = 0;
-                    suite_stack.clear();
-                }
                 continue;
             }
 
-            // Summary detected
+            let indent = line.len() - line.trim_start().len();
+
             if SUMMARY_RE.is_match(&line) {
                 suite_stack.clear();
+                in_summary = true;
                 continue;
             }
 
-            // Passing test
-            if let Some(captures) = PASS_RE.captures(&line) {
-                let mut test_name = captures.get(1).unwrap().as_str().trim().to_string();
-                test_name = DUR_TAIL_RE.replace(&test_name, "").to_string();
-                let full_name = if suite_stack.is_empty() {
-                    test_name
-                } else {
-                    format!("{} - {}", suite_stack.join(" - "), test_name)
-                };
-                test_status_map.insert(full_name, TestStatus::Passed);
-                continue;
-            }
-
-            // Failing test
-            if let Some(captures) = FAIL_RE.captures(&line).or_else(|| CROSS_RE.captures(&line)) {
-                let mut test_name = captures.get(1).unwrap().as_str().trim().to_string();
-                test_name = DUR_TAIL_RE.replace(&test_name, "").to_string();
-                let full_name = if suite_stack.is_empty() {
-                    test_name
-                } else {
-                    format!("{} - {}", suite_stack.join(" - "), test_name)
-                };
-                test_status_map.insert(full_name, TestStatus::Failed);
-                continue;
+            if in_summary {
+                // A top-level suite header that isn't a numbered failure starts the next run
+                if indent != 2 || FAILURE_DETAIL_RE.is_match(&line) {
+                    continue;
+                }
+                in_summary = false;
             }
 
-            // Pending test
-            if let Some(captures) = PEND_RE.captures(&line) {
-                let mut test_name = captures.get(1).unwrap().as_str().trim().to_string();
-                test_name = DUR_TAIL_RE.replace(&test_name, "").to_string();
-                let full_name = if suite_stack.is_empty() {
+            suite_stack.retain(|(suite_indent, _)| *suite_indent < indent);
+            let qualify = |test_name: &str| -> String {
+                let test_name = DUR_TAIL_RE.replace(test_name.trim(), "").to_string();
+                if suite_stack.is_empty() {
                     test_name
                 } else {
-                    format!("{} - {}", suite_stack.join(" - "), test_name)
-                };
-                test_status_map.insert(full_name, TestStatus::Pending);
-                continue;
-            }
-
-            // Suite header
-            let indent = line.len() - line.trim_start().len();
-            if indent >= 2 {
-                let level = (indent / 2) - 1;
-                if level < suite_stack.len() {
-                    suite_stack.truncate(level);
-                }
-                if level == suite_stack.len() {
-                    suite_stack.push(line.trim().to_string());
+                    let suites: Vec<&str> = suite_stack.iter().map(|(_, name)| name.as_str()).collect();
+                    format!("{} - {}", suites.join(" - "), test_name)
                 }
+            };
+
+            if let Some(captures) = PASS_RE.captures(&line) {
+                test_status_map.insert(qualify(&captures[1]), TestStatus::Passed);
+            } else if let Some(captures) = FAIL_RE.captures(&line).or_else(|| CROSS_RE.captures(&line)) {
+                test_status_map.insert(qualify(&captures[1]), TestStatus::Failed);
+            } else if let Some(captures) = PEND_RE.captures(&line) {
+                test_status_map.insert(qualify(&captures[1]), TestStatus::Pending);
+            } else if indent >= 2 {
+                suite_stack.push((indent, line.trim().to_string()));
             }
         }
 
@@ -192,11 +312,18 @@ impl JavaScriptLogParser {
     fn parse_log_jest(&self, log: &str) -> HashMap<String, TestStatus> {
         lazy_static! {
             static ref JEST_RE: Regex = Regex::new(r"^\s*(✓|✕|○)\s(.+?)(?:\s\((\d+\s*m?s)\))?$").unwrap();
+            // `PASS src/sum.test.ts (5.2 s)` ahead of each suite's results
+            static ref JEST_SUITE_RE: Regex = Regex::new(r"^\s*(?:PASS|FAIL)\s+(\S+\.[cm]?[jt]sx?)\b").unwrap();
         }
 
         let mut test_status_map = HashMap::new();
+        let mut current_file: Option<String> = None;
 
         for line in log.lines() {
+            if let Some(captures) = JEST_SUITE_RE.captures(line) {
+                current_file = Some(captures[1].to_string());
+                continue;
+            }
             if let Some(captures) = JEST_RE.captures(line.trim()) {
                 let status_symbol = captures.get(1).unwrap().as_str();
                 let test_name = captures.get(2).unwrap().as_str();
@@ -208,7 +335,11 @@ impl JavaScriptLogParser {
                     _ => continue,
                 };
 
-                test_status_map.insert(test_name.to_string(), status);
+                let test_name = match &current_file {
+                    Some(file) if self.jest_file_names => format!("{}::{}", file, test_name),
+                    _ => test_name.to_string(),
+                };
+                test_status_map.insert(test_name, status);
             }
         }
 
@@ -255,7 +386,7 @@ impl JavaScriptLogParser {
             let cleaned_line = Self::strip_bracket_codes(&cleaned_line);
             let trimmed = cleaned_line.trim();
             
-            if trimmed.is_empty() {
+            if trimmed.is_empty() || VITEST_SUITE_RE.is_match(trimmed) {
                 continue;
             }
             
@@ -327,20 +458,47 @@ impl JavaScriptLogParser {
     }
 
     fn parse_log_karma(&self, log: &str) -> HashMap<String, TestStatus> {
-        let mut test_status_map = HashMap::new();
+        self.karma_browser_results(log).merged
+    }
+
+    fn karma_browser_results(&self, log: &str) -> BrowserResults {
+        let mut results = BrowserResults::default();
         let mut current_indent = -1i32;
         let mut current_suite: Vec<String> = Vec::new();
         let mut started = false;
+        let mut browser: Option<String> = None;
 
         lazy_static! {
             static ref KARMA_RE: Regex = Regex::new(r"^(\s*)?([✔✖])?\s(.*)$").unwrap();
+            // Progress and dots reporters: `<browser> Suite should work FAILED`
+            static ref KARMA_BROWSER_STATUS_RE: Regex = Regex::new(r"^(.+?)\s+(FAILED|SKIPPED|SUCCESS)$").unwrap();
         }
 
-        for line in log.lines() {
-            if line.starts_with("SUMMARY:") {
-                return test_status_map;
+        for raw_line in log.lines() {
+            if raw_line.starts_with("SUMMARY:") {
+                return results;
             }
 
+            let line = match split_browser_prefix(raw_line) {
+                Some((name, rest)) => {
+                    browser = Some(name.to_string());
+                    if rest.is_empty() || rest.starts_with("Executed ") {
+                        continue;
+                    }
+                    if let Some(captures) = KARMA_BROWSER_STATUS_RE.captures(rest) {
+                        let test_status = match &captures[2] {
+                            "FAILED" => TestStatus::Failed,
+                            "SKIPPED" => TestStatus::Skipped,
+                            _ => TestStatus::Passed,
+                        };
+                        results.record(browser.as_deref(), captures[1].trim().to_string(), test_status);
+                        continue;
+                    }
+                    rest
+                }
+                None => raw_line,
+            };
+
             if line.contains("Starting browser") {
                 started = true;
                 continue;
@@ -380,12 +538,12 @@ impl JavaScriptLogParser {
                         _ => continue,
                     };
 
-                    test_status_map.insert(full_name, test_status);
+                    results.record(browser.as_deref(), full_name, test_status);
                 }
             }
         }
 
-        test_status_map
+        results
     }
 
     fn parse_log_tap(&self, log: &str) -> HashMap<String, TestStatus> {
@@ -601,49 +759,88 @@ impl JavaScriptLogParser {
     }
 
     fn parse_log_playwright(&self, log: &str) -> HashMap<String, TestStatus> {
-        let mut test_status_map = HashMap::new();
+        self.playwright_project_results(log).merged
+    }
 
+    // Tests repeat once per project and once per retry; retries combine by `retry_policy`,
+    // then a failure in any project fails the test
+    fn playwright_project_results(&self, log: &str) -> BrowserResults {
         lazy_static! {
-            static ref PLAYWRIGHT_PASS_RE: Regex = Regex::new(r"^\s*✓\s+(.+?)\s+\[.+?\]\s+\(\d+ms\)").unwrap();
-            static ref PLAYWRIGHT_FAIL_RE: Regex = Regex::new(r"^\s*✗\s+(.+?)\s+\[.+?\]\s+\(\d+ms\)").unwrap();
-            static ref PLAYWRIGHT_SKIP_RE: Regex = Regex::new(r"^\s*-\s+(.+?)\s+\[.+?\]").unwrap();
+            // `✓ title [chromium] (12ms)`, or the list reporter's `✘  3 [chromium] › a.spec.ts:4:5 › title (retry #1) (1.2s)`
+            static ref PLAYWRIGHT_RESULT_RE: Regex = Regex::new(r"^\s*([✓✔✗✘×-])\s+(?:\d+\s+)?(.*?)\s*\[([^\]]+)\](.*)$").unwrap();
+            static ref PLAYWRIGHT_RETRY_RE: Regex = Regex::new(r"\s*\(retry #\d+\)").unwrap();
+            static ref PLAYWRIGHT_DURATION_RE: Regex = Regex::new(r"\s*\(\d+(?:\.\d+)?m?s\)\s*$").unwrap();
+            static ref PLAYWRIGHT_LOCATION_RE: Regex = Regex::new(r"^(.+?\.(?:spec|test)\.[jt]sx?)(?::\d+)*$").unwrap();
             static ref PLAYWRIGHT_SPEC_RE: Regex = Regex::new(r"^\s*(.+?\.spec\.[jt]s)").unwrap();
         }
 
         let mut current_spec = String::new();
-        let lines: Vec<&str> = log.lines().collect();
+        // (test, project) -> attempt statuses in order, keeping first-seen order for stable output
+        let mut attempts: Vec<((String, String), Vec<TestStatus>)> = Vec::new();
+
+        for line in log.lines() {
+            if let Some(captures) = PLAYWRIGHT_RESULT_RE.captures(line) {
+                let tail = captures.get(4).unwrap().as_str();
+                let has_duration = PLAYWRIGHT_DURATION_RE.is_match(tail);
+                let status = match &captures[1] {
+                    "✓" | "✔" if has_duration => TestStatus::Passed,
+                    "✗" | "✘" | "×" if has_duration => TestStatus::Failed,
+                    "-" => TestStatus::Skipped,
+                    _ => continue,
+                };
+                let project = captures[3].trim().to_string();
+                let tail = PLAYWRIGHT_DURATION_RE.replace(tail, "");
+                let tail = PLAYWRIGHT_RETRY_RE.replace_all(&tail, "").trim().to_string();
+
+                let title = captures[2].trim();
+                let (spec, title) = if title.is_empty() {
+                    // List reporter: location and title follow the project, separated by `›`
+                    let mut parts = tail.trim_start_matches('›').split(" › ").map(str::trim);
+                    let location = parts.next().unwrap_or("");
+                    let rest: Vec<&str> = parts.collect();
+                    match PLAYWRIGHT_LOCATION_RE.captures(location) {
+                        Some(location) => (location[1].to_string(), rest.join(" › ")),
+                        None => (current_spec.clone(), std::iter::once(location).chain(rest).collect::<Vec<_>>().join(" › ")),
+                    }
+                } else {
+                    (current_spec.clone(), PLAYWRIGHT_RETRY_RE.replace_all(title, "").trim().to_string())
+                };
+                if title.is_empty() {
+                    continue;
+                }
+
+                let key = (format!("{}::{}", spec, title), project);
+                match attempts.iter_mut().find(|(existing, _)| *existing == key) {
+                    Some((_, history)) => history.push(status),
+                    None => attempts.push((key, vec![status])),
+                }
+                continue;
+            }
 
-        for line in lines {
-            // Extract spec file name
             if let Some(captures) = PLAYWRIGHT_SPEC_RE.captures(line) {
                 let spec_file = captures.get(1).unwrap().as_str();
                 if spec_file.contains(".spec.") {
                     current_spec = spec_file.to_string();
-                    continue;
                 }
             }
+        }
 
-            // Parse test results
-            if let Some(captures) = PLAYWRIGHT_PASS_RE.captures(line) {
-                let test_name = captures.get(1).unwrap().as_str().trim();
-                let full_name = format!("{}::{}", current_spec, test_name);
-                test_status_map.insert(full_name, TestStatus::Passed);
-            } else if let Some(captures) = PLAYWRIGHT_FAIL_RE.captures(line) {
-                let test_name = captures.get(1).unwrap().as_str().trim();
-                let full_name = format!("{}::{}", current_spec, test_name);
-                test_status_map.insert(full_name, TestStatus::Failed);
-            } else if let Some(captures) = PLAYWRIGHT_SKIP_RE.captures(line) {
-                let test_name = captures.get(1).unwrap().as_str().trim();
-                let full_name = format!("{}::{}", current_spec, test_name);
-                test_status_map.insert(full_name, TestStatus::Skipped);
+        let mut results = BrowserResults::default();
+        for ((name, project), history) in attempts {
+            if let Some(status) = self.retry_policy.combine(&history) {
+                results.record(Some(&project), name, status);
             }
         }
-
-        test_status_map
+        results
     }
 
     fn parse_log_jasmine(&self, log: &str) -> HashMap<String, TestStatus> {
-        let mut test_status_map = HashMap::new();
+        self.jasmine_browser_results(log).merged
+    }
+
+    fn jasmine_browser_results(&self, log: &str) -> BrowserResults {
+        let mut results = BrowserResults::default();
+        let mut browser: Option<String> = None;
 
         lazy_static! {
             static ref JASMINE_PASS_RE: Regex = Regex::new(r"^\s*✓\s+(.+?)$").unwrap();
@@ -654,11 +851,20 @@ impl JavaScriptLogParser {
         let mut current_suite = String::new();
         let lines: Vec<&str> = log.lines().collect();
 
-        for line in lines {
+        for raw_line in lines {
+            // karma-jasmine prints the browser ahead of each result
+            let line = match split_browser_prefix(raw_line) {
+                Some((name, rest)) => {
+                    browser = Some(name.to_string());
+                    rest
+                }
+                None => raw_line,
+            };
             let trimmed = line.trim();
             
             if trimmed.is_empty() || trimmed.starts_with("Jasmine") || 
-               trimmed.starts_with("Finished in") || trimmed.contains(" spec") {
+               trimmed.starts_with("Finished in") || trimmed.contains(" spec") ||
+               trimmed.starts_with("Executed ") {
                 continue;
             }
 
@@ -669,7 +875,7 @@ impl JavaScriptLogParser {
                 } else {
                     format!("{}::{}", current_suite, test_name)
                 };
-                test_status_map.insert(full_name, TestStatus::Passed);
+                results.record(browser.as_deref(), full_name, TestStatus::Passed);
             } else if let Some(captures) = JASMINE_FAIL_RE.captures(line) {
                 let test_name = captures.get(1).unwrap().as_str().trim();
                 let full_name = if current_suite.is_empty() {
@@ -677,7 +883,7 @@ impl JavaScriptLogParser {
                 } else {
                     format!("{}::{}", current_suite, test_name)
                 };
-                test_status_map.insert(full_name, TestStatus::Failed);
+                results.record(browser.as_deref(), full_name, TestStatus::Failed);
             } else if let Some(captures) = JASMINE_PENDING_RE.captures(line) {
                 let test_name = captures.get(1).unwrap().as_str().trim();
                 let full_name = if current_suite.is_empty() {
@@ -685,7 +891,7 @@ impl JavaScriptLogParser {
                 } else {
                     format!("{}::{}", current_suite, test_name)
                 };
-                test_status_map.insert(full_name, TestStatus::Pending);
+                results.record(browser.as_deref(), full_name, TestStatus::Pending);
             } else if !trimmed.starts_with("✓") && !trimmed.starts_with("✗") && 
                      !trimmed.starts_with("*") && !trimmed.contains("failures") && 
                      !line.starts_with("  ") {
@@ -694,7 +900,7 @@ impl JavaScriptLogParser {
             }
         }
 
-        test_status_map
+        results
     }
 
     fn parse_log_qunit(&self, log: &str) -> HashMap<String, TestStatus> {
@@ -885,9 +1091,27 @@ impl JavaScriptLogParser {
         }
     }
 
+    // Parse with one framework's parser; runners that repeat tests per browser also get the
+    // per-browser breakdown, from the same pass
+    fn parse_with_framework(&self, framework: &str, content: &str) -> ParsedLog {
+        let (test_status_map, browser_runs) = match framework {
+            "karma" => self.karma_browser_results(content).into_parts(),
+            "jasmine" => self.jasmine_browser_results(content).into_parts(),
+            "playwright" => self.playwright_project_results(content).into_parts(),
+            _ => (self.get_parser_by_name(framework)(self, content), Vec::new()),
+        };
+        let mut parsed = self.convert_to_parsed_log(test_status_map).with_parser(framework);
+        parsed.browser_runs = browser_runs;
+        if framework == "vitest" {
+            parsed.collapsed_suites = vitest_collapsed_suites(content);
+        }
+        parsed
+    }
+
     fn convert_to_parsed_log(&self, test_status_map: HashMap<String, TestStatus>) -> ParsedLog {
         let mut parsed_log = ParsedLog::new();
 
+        // Names stay as the runner printed them; status lookup compares them separator-blind
         for (test_name, status) in test_status_map {
             match status {
                 TestStatus::Passed => {
@@ -908,9 +1132,11 @@ impl JavaScriptLogParser {
 }
 
 impl LogParserTrait for JavaScriptLogParser {
-    fn parse_log_file(&self, file_path: &str) -> Result<ParsedLog, String> {
-        let content = std::fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read file {}: {}", file_path, e))?;
+    fn parse_content(&self, file_path: &str, content: &str, framework: Option<&str>) -> Result<ParsedLog, String> {
+        // An explicitly configured parser takes precedence over run script or rule hints
+        if let Some(pinned) = framework.filter(|name| self.parser_name == "auto" && PINNABLE_FRAMEWORKS.contains(name)) {
+            return Ok(self.parse_with_framework(pinned, content));
+        }
 
         // Try to extract project path from file path
         let project_path = if self.project_path.is_some() {
@@ -941,7 +1167,7 @@ impl LogParserTrait for JavaScriptLogParser {
             if let Some(ref proj_path) = project_path {
                 detect_js_testing_framework(proj_path)
             } else {
-                self.detect_test_framework(&content)
+                self.detect_test_framework(content)
             }
         } else {
             self.parser_name.clone()
@@ -950,27 +1176,7 @@ impl LogParserTrait for JavaScriptLogParser {
         eprintln!("DEBUG: Detected framework '{}' for file: {}", framework, file_path);
         eprintln!("DEBUG: Content preview (first 500 chars): {}", &content[..content.len().min(500)]);
 
-        let test_status_map = match framework.as_str() {
-            "calypso" => self.parse_log_calypso(&content),
-            "mocha" => self.parse_log_mocha_v2(&content),
-            "jest" => self.parse_log_jest(&content),
-            "jest-json" => self.parse_log_jest_json(&content),
-            "vitest" => self.parse_log_vitest(&content),
-            "karma" => self.parse_log_karma(&content),
-            "tap" => self.parse_log_tap(&content),
-            "chartjs" => self.parse_log_chart_js(&content),
-            "marked" => self.parse_log_marked(&content),
-            "react-pdf" => self.parse_log_react_pdf(&content),
-            "p5js" => self.parse_log_p5js(&content),
-            "cypress" => self.parse_log_cypress(&content),
-            "playwright" => self.parse_log_playwright(&content),
-            "jasmine" => self.parse_log_jasmine(&content),
-            "qunit" => self.parse_log_qunit(&content),
-            "ava" => self.parse_log_ava(&content),
-            _ => self.parse_log_vitest(&content), // Default fallback
-        };
-
-        Ok(self.convert_to_parsed_log(test_status_map))
+        Ok(self.parse_with_framework(&framework, content))
     }
 
     fn get_language(&self) -> &'static str {
@@ -998,6 +1204,99 @@ mod tests {
         assert_eq!(result.get("should skip test 3"), Some(&TestStatus::Skipped));
     }
 
+    #[test]
+    fn test_jest_file_names() {
+        let log = r#"
+PASS src/sum.test.ts (5.2 s)
+  ✓ handles zero (3 ms)
+FAIL src/product.test.ts
+  ✕ handles zero (2 ms)
+        "#;
+
+        let bare = JavaScriptLogParser::new_with_parser("jest").with_jest_file_names(false).parse_log_jest(log);
+        assert_eq!(bare.len(), 1);
+
+        let named = JavaScriptLogParser::new_with_parser("jest").with_jest_file_names(true).parse_log_jest(log);
+        assert_eq!(named.get("src/sum.test.ts::handles zero"), Some(&TestStatus::Passed));
+        assert_eq!(named.get("src/product.test.ts::handles zero"), Some(&TestStatus::Failed));
+    }
+
+    #[test]
+    fn test_mocha_nested_suites_with_spacing() {
+        // Spec reporter output with the blank lines nested `describe` blocks get
+        let log = r#"
+  Router
+
+
+    #route()
+
+
+      with params
+        ✓ should decode params (3ms)
+        1) should reject malformed params
+
+
+      - should support regexp routes
+    #use()
+      ✓ should mount middleware
+
+  utils
+    ✓ should escape html
+
+
+  3 passing (21ms)
+  1 pending
+  1 failing
+
+  1) Router
+       #route()
+         with params
+           should reject malformed params:
+     AssertionError: expected 400 to equal 200
+      + expected - actual
+      - 400
+      at Context.<anonymous> (test/router.js:42:10)
+"#;
+
+        let parser = JavaScriptLogParser::new_with_parser("mocha");
+        let result = parser.parse_log_mocha_v2(log);
+
+        assert_eq!(result.len(), 5);
+        assert_eq!(result.get("Router - #route() - with params - should decode params"), Some(&TestStatus::Passed));
+        assert_eq!(result.get("Router - #route() - with params - should reject malformed params"), Some(&TestStatus::Failed));
+        assert_eq!(result.get("Router - #route() - should support regexp routes"), Some(&TestStatus::Pending));
+        assert_eq!(result.get("Router - #use() - should mount middleware"), Some(&TestStatus::Passed));
+        assert_eq!(result.get("utils - should escape html"), Some(&TestStatus::Passed));
+    }
+
+    #[test]
+    fn test_mocha_consecutive_runs() {
+        // Two mocha invocations in one log; the first run's failure details must not leak into the second
+        let log = r#"
+  api
+    1) fetches users
+
+  0 passing (5ms)
+  1 failing
+
+  1) api
+       fetches users:
+     Error: connect ECONNREFUSED
+
+  cli
+    ✓ prints help
+
+  1 passing (2ms)
+"#;
+
+        let parser = JavaScriptLogParser::new_with_parser("mocha");
+        let result = parser.parse_log_mocha_v2(log);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("api - fetches users"), Some(&TestStatus::Failed));
+        assert_eq!(result.get("cli - prints help"), Some(&TestStatus::Passed));
+    }
+
     #[test]
     fn test_vitest_parsing() {
         let log = r#"
@@ -1063,6 +1362,10 @@ Running: cypress/e2e/example.cy.js
         assert_eq!(result.get("cypress/e2e/example.cy.js::should display welcome message"), Some(&TestStatus::Passed));
         assert_eq!(result.get("cypress/e2e/example.cy.js::should handle form submission"), Some(&TestStatus::Failed));
         assert_eq!(result.get("cypress/e2e/example.cy.js::should handle validation"), Some(&TestStatus::Pending));
+
+        let parsed = parser.convert_to_parsed_log(result);
+        assert!(parsed.passed.contains("cypress/e2e/example.cy.js::should display welcome message"));
+        assert!(parsed.ignored.contains("cypress/e2e/example.cy.js::should handle validation"));
     }
 
     #[test]
@@ -1083,6 +1386,31 @@ Running: cypress/e2e/example.cy.js
         assert_eq!(result.get("login.spec.ts::should skip this test"), Some(&TestStatus::Skipped));
     }
 
+    #[test]
+    fn test_playwright_projects_and_retries() {
+        let log = r#"
+  ✘  1 [chromium] › login.spec.ts:3:5 › auth › logs in (1.2s)
+  ✓  2 [firefox] › login.spec.ts:3:5 › auth › logs in (900ms)
+  ✓  3 [chromium] › login.spec.ts:3:5 › auth › logs in (retry #1) (1.1s)
+  ✓  4 [chromium] › login.spec.ts:9:5 › auth › logs out (300ms)
+  -  5 [firefox] › login.spec.ts:9:5 › auth › logs out
+        "#;
+
+        let any_fail = JavaScriptLogParser::new_with_parser("playwright").with_retry_policy(RetryPolicy::AnyFail);
+        let result = any_fail.parse_log_playwright(log);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("login.spec.ts::auth › logs in"), Some(&TestStatus::Failed));
+        assert_eq!(result.get("login.spec.ts::auth › logs out"), Some(&TestStatus::Passed));
+
+        let final_attempt = JavaScriptLogParser::new_with_parser("playwright").with_retry_policy(RetryPolicy::FinalAttempt);
+        let result = final_attempt.parse_log_playwright(log);
+        assert_eq!(result.get("login.spec.ts::auth › logs in"), Some(&TestStatus::Passed));
+
+        let runs = final_attempt.parse_with_framework("playwright", log).browser_runs;
+        assert_eq!(runs.iter().map(|run| run.browser.as_str()).collect::<Vec<_>>(), vec!["chromium", "firefox"]);
+        assert_eq!((runs[1].passed, runs[1].ignored), (1, 1));
+    }
+
     #[test]
     fn test_jasmine_parsing() {
         let log = r#"
@@ -1166,6 +1494,49 @@ Finished in 0.123 seconds
         assert_eq!(parser.detect_test_framework(ava_log), "ava");
     }
 
+    #[test]
+    fn test_karma_browser_prefixes() {
+        let log = "INFO [launcher]: Starting browser ChromeHeadless\n\
+                   Chrome Headless 120.0.6099.109 (Linux x86_64) Calculator adds numbers SUCCESS\n\
+                   Firefox 121.0 (Ubuntu 0.0.0) Calculator adds numbers FAILED\n\
+                   Chrome Headless 120.0.6099.109 (Linux x86_64) Calculator divides SKIPPED\n\
+                   Firefox 121.0 (Ubuntu 0.0.0) Calculator divides SUCCESS\n\
+                   Chrome Headless 120.0.6099.109 (Linux x86_64): Executed 2 of 2 SUCCESS (0.01 secs / 0.005 secs)\n\
+                   SUMMARY:";
+
+        let parser = JavaScriptLogParser::new_with_parser("karma");
+        let result = parser.parse_log_karma(log);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.get("Calculator adds numbers"), Some(&TestStatus::Failed));
+        assert_eq!(result.get("Calculator divides"), Some(&TestStatus::Passed));
+
+        let runs = parser.parse_with_framework("karma", log).browser_runs;
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].browser, "Chrome Headless 120.0.6099.109 (Linux x86_64)");
+        assert_eq!((runs[0].passed, runs[0].failed, runs[0].ignored), (1, 0, 1));
+        assert_eq!(runs[1].browser, "Firefox 121.0 (Ubuntu 0.0.0)");
+        assert_eq!(runs[1].failed_tests, vec!["Calculator adds numbers".to_string()]);
+    }
+
+    #[test]
+    fn test_vitest_suite_summaries() {
+        let log = r#"
+ ✓ src/utils.test.ts (12 tests) 320ms
+ ❯ src/api.test.ts (3 tests | 1 failed) 40ms
+   × src/api.test.ts > api > rejects bad tokens 5ms
+ ↓ src/slow.test.ts (2 tests | 2 skipped)
+"#;
+
+        let parser = JavaScriptLogParser::new_with_parser("vitest");
+        let result = parser.parse_log_vitest(log);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.get("api > rejects bad tokens"), Some(&TestStatus::Failed));
+        assert_eq!(vitest_collapsed_suites(log), vec![
+            CollapsedSuite { file: "src/utils.test.ts".to_string(), tests: 12 },
+            CollapsedSuite { file: "src/api.test.ts".to_string(), tests: 3 },
+        ]);
+    }
+
     #[test]
     fn test_vitest_parsing_debug() {
         let log_content = r#"  ✓ packages/esbuild-plugin-env/test/test.spec.js > esbuild-plugin-env > should inject env values
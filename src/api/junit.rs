@@ -0,0 +1,119 @@
+//! JUnit XML export of the analysis, one test case per rule check
+//!
+//! Lets the reviewer pipeline feed results into CI dashboards that already
+//! understand JUnit reports.
+
+use crate::app::types::{rule_description, LogAnalysisResult};
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab/newline aren't allowed in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render the analysis as a JUnit XML report
+pub fn format_junit_xml(instance_id: &str, task_id: &str, analysis: &LogAnalysisResult) -> String {
+    let violations = analysis.rule_violations.entries();
+    let failures = violations.iter().filter(|(_, v)| v.has_problem).count();
+    let suite_name = if instance_id.is_empty() { "deliverable" } else { instance_id };
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"swe-reviewer\" tests=\"{}\" failures=\"{}\">\n",
+        violations.len(),
+        failures
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+        escape_xml(suite_name),
+        violations.len(),
+        failures
+    ));
+    xml.push_str("    <properties>\n");
+    if !task_id.is_empty() {
+        xml.push_str(&format!("      <property name=\"task_id\" value=\"{}\"/>\n", escape_xml(task_id)));
+    }
+    xml.push_str(&format!("      <property name=\"f2p_count\" value=\"{}\"/>\n", analysis.test_statuses.f2p.len()));
+    xml.push_str(&format!("      <property name=\"p2p_count\" value=\"{}\"/>\n", analysis.test_statuses.p2p.len()));
+    for o in &analysis.overrides {
+        xml.push_str(&format!(
+            "      <property name=\"status_override\" value=\"{}\"/>\n",
+            escape_xml(&format!("{} [{}]: {}", o.test_name, o.stage, o.describe()))
+        ));
+    }
+    xml.push_str("    </properties>\n");
+
+    for (code, violation) in &violations {
+        let name = format!("{}: {}", code, rule_description(code));
+        if !violation.has_problem {
+            xml.push_str(&format!(
+                "    <testcase classname=\"swe_reviewer.rules\" name=\"{}\"/>\n",
+                escape_xml(&name)
+            ));
+            continue;
+        }
+
+        xml.push_str(&format!(
+            "    <testcase classname=\"swe_reviewer.rules\" name=\"{}\">\n",
+            escape_xml(&name)
+        ));
+        xml.push_str(&format!(
+            "      <failure message=\"{} violation(s)\" type=\"{}\">{}</failure>\n",
+            violation.examples.len(),
+            code,
+            escape_xml(&violation.examples.join("\n"))
+        ));
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n");
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::types::{RuleViolation, RuleViolations, StatusOverride};
+
+    #[test]
+    fn test_format_junit_xml_reports_violations_as_failures() {
+        let analysis = LogAnalysisResult {
+            rule_violations: RuleViolations {
+                c1_failed_in_base_present_in_p2p: RuleViolation {
+                    has_problem: true,
+                    examples: vec!["tests/test_a.py::test<x>".to_string()],
+                },
+                ..Default::default()
+            },
+            overrides: vec![StatusOverride {
+                test_name: "t<1>".to_string(),
+                stage: "after".to_string(),
+                status: "passed".to_string(),
+                reason: "parser missed it".to_string(),
+                author: "ana".to_string(),
+                created_at: 86_400,
+            }],
+            ..Default::default()
+        };
+        let rule_count = analysis.rule_violations.entries().len();
+
+        let xml = format_junit_xml("repo__name-1", "T-7", &analysis);
+        assert!(xml.contains("<property name=\"task_id\" value=\"T-7\"/>"));
+        assert!(xml.contains(&format!("<testsuite name=\"repo__name-1\" tests=\"{}\" failures=\"1\"", rule_count)));
+        assert!(xml.contains("<failure message=\"1 violation(s)\" type=\"C1\">tests/test_a.py::test&lt;x&gt;</failure>"));
+        assert!(xml.contains("<property name=\"status_override\" value=\"t&lt;1&gt; [after]: passed by ana on 1970-01-02 00:00 UTC: parser missed it\"/>"));
+        assert!(xml.contains("name=\"C2: Tests that failed in after but are present in F2P or P2P\"/>"));
+    }
+}
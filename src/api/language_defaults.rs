@@ -0,0 +1,131 @@
+//! Per-language defaults from the `languages` section of the server config
+//!
+//! Java and C++ failures print long assertion blocks and deep traces while Rust's fit in a few
+//! lines, and each ecosystem has its own install noise and name spellings. A deployment can
+//! set the search context, extra noise patterns and test-name rewrites once per language
+//! instead of adjusting them in every review.
+
+use regex::Regex;
+
+use crate::api::parser_registry::BUILTIN_PARSERS;
+use crate::api::server_config::{load_server_config, LanguageConfig, ServerConfig};
+
+/// Lines of context around a search hit when the language sets none
+pub const DEFAULT_SEARCH_CONTEXT: usize = 5;
+
+/// Defaults in effect for one language
+#[derive(Debug, Clone)]
+pub struct LanguageDefaults {
+    pub search_context: usize,
+    pub noise_patterns: Vec<String>,
+    name_rewrites: Vec<(Regex, String)>,
+}
+
+impl Default for LanguageDefaults {
+    fn default() -> Self {
+        Self { search_context: DEFAULT_SEARCH_CONTEXT, noise_patterns: Vec::new(), name_rewrites: Vec::new() }
+    }
+}
+
+// The language's own entry, else the entry of a language routed to the same parser
+fn language_config<'a>(config: &'a ServerConfig, language: &str) -> Option<&'a LanguageConfig> {
+    let language = language.trim().to_lowercase();
+    let entry = |key: &str| config.languages.iter().find(|(name, _)| name.eq_ignore_ascii_case(key)).map(|(_, entry)| entry);
+    entry(&language).or_else(|| {
+        let plugin = BUILTIN_PARSERS.iter().find(|plugin| plugin.languages.contains(&language.as_str()))?;
+        plugin.languages.iter().find_map(|alias| entry(alias))
+    })
+}
+
+impl LanguageDefaults {
+    pub fn from_config(config: &ServerConfig, language: &str) -> Self {
+        let Some(entry) = language_config(config, language) else {
+            return Self::default();
+        };
+        let name_rewrites = entry.name_rewrites
+            .iter()
+            .filter_map(|rewrite| match Regex::new(&rewrite.pattern) {
+                Ok(regex) => Some((regex, rewrite.replacement.clone())),
+                Err(e) => {
+                    eprintln!("Ignoring {} name rewrite with invalid regex '{}': {}", language, rewrite.pattern, e);
+                    None
+                }
+            })
+            .collect();
+        Self {
+            search_context: entry.search_context.unwrap_or(DEFAULT_SEARCH_CONTEXT),
+            noise_patterns: entry.noise_patterns.clone(),
+            name_rewrites,
+        }
+    }
+
+    pub fn load(language: &str) -> Self {
+        Self::from_config(&load_server_config(), language)
+    }
+
+    /// The configured rewrites applied in order; names come back unchanged when there are none
+    pub fn rewrite_name(&self, name: &str) -> String {
+        self.name_rewrites
+            .iter()
+            .fold(name.to_string(), |name, (pattern, replacement)| pattern.replace_all(&name, replacement.as_str()).into_owned())
+    }
+
+    pub fn has_name_rewrites(&self) -> bool {
+        !self.name_rewrites.is_empty()
+    }
+
+    /// The rewrites as `pattern → replacement`, for the analysis settings
+    pub fn describe_name_rewrites(&self) -> Vec<String> {
+        self.name_rewrites
+            .iter()
+            .map(|(pattern, replacement)| format!("{} → {}", pattern.as_str(), replacement))
+            .collect()
+    }
+}
+
+/// The deliverable's `main.json` language, lowercase, when main.json is among the paths
+pub fn deliverable_language(abs_paths: &[String]) -> Option<String> {
+    let main_json = abs_paths.iter().find(|path| path.to_lowercase().contains("main.json"))?;
+    let content = std::fs::read_to_string(main_json).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json.get("language").and_then(|v| v.as_str()).map(|language| language.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ServerConfig {
+        serde_json::from_value(serde_json::json!({
+            "languages": {
+                "java": {
+                    "search_context": 12,
+                    "name_rewrites": [
+                        { "pattern": "\\(\\)$", "replacement": "" },
+                        { "pattern": "^(\\w+)#", "replacement": "$1." },
+                        { "pattern": "[", "replacement": "" }
+                    ]
+                },
+                "javascript": { "noise_patterns": ["^yarn install"] }
+            }
+        })).unwrap()
+    }
+
+    #[test]
+    fn test_language_entries_and_parser_aliases() {
+        let config = config();
+        assert_eq!(LanguageDefaults::from_config(&config, "Java").search_context, 12);
+        assert_eq!(LanguageDefaults::from_config(&config, "typescript").noise_patterns, vec!["^yarn install".to_string()]);
+        let rust = LanguageDefaults::from_config(&config, "rust");
+        assert_eq!(rust.search_context, DEFAULT_SEARCH_CONTEXT);
+        assert!(!rust.has_name_rewrites());
+    }
+
+    #[test]
+    fn test_name_rewrites_apply_in_order_and_skip_invalid_patterns() {
+        let java = LanguageDefaults::from_config(&config(), "java");
+        assert_eq!(java.describe_name_rewrites().len(), 2);
+        assert_eq!(java.rewrite_name("CalcTest#testDiv()"), "CalcTest.testDiv");
+        assert_eq!(java.rewrite_name("CalcTest.testDiv"), "CalcTest.testDiv");
+    }
+}
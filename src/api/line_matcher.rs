@@ -0,0 +1,104 @@
+//! Log lines matched against a search query in one of the `SearchMode`s
+//!
+//! The default mode matches a test name through its search variants in canonical form, so
+//! it finds a test however the log spells it but can't say where in the line it matched.
+//! The other modes take the query as typed: plain text, plain text ignoring case, or a
+//! regex. Every mode reports the byte range it matched when it knows one, so the page can
+//! highlight the matched text rather than the whole line.
+
+use regex::{Regex, RegexBuilder};
+
+use crate::api::search_variants::searched_terms;
+use crate::api::test_name::canonical_test_name;
+use crate::app::types::SearchMode;
+
+// Compiled size cap for reviewer-typed patterns
+const MAX_REGEX_SIZE: usize = 1 << 20;
+
+pub enum LineMatcher {
+    // Raw variants for locating the match, canonical ones for deciding it
+    TestName { terms: Vec<String>, canonical: Vec<String> },
+    Plain(String),
+    // Case-insensitive text and regexes both run as a regex
+    Pattern(SearchMode, Regex),
+}
+
+impl LineMatcher {
+    pub fn test_name(test_name: &str) -> Self {
+        let terms = searched_terms(test_name);
+        let canonical = terms.iter().map(|term| canonical_test_name(term)).collect();
+        LineMatcher::TestName { terms, canonical }
+    }
+
+    /// Matcher for `query` in `mode`; an empty query or an invalid regex is an error
+    pub fn new(query: &str, mode: SearchMode) -> Result<Self, String> {
+        if query.is_empty() {
+            return Err("Nothing to search for".to_string());
+        }
+        let pattern = |pattern: &str| {
+            RegexBuilder::new(pattern)
+                .case_insensitive(mode == SearchMode::CaseInsensitive)
+                .size_limit(MAX_REGEX_SIZE)
+                .build()
+                .map_err(|e| format!("Invalid search pattern: {}", e))
+        };
+        Ok(match mode {
+            SearchMode::TestName => Self::test_name(query),
+            SearchMode::Plain => LineMatcher::Plain(query.to_string()),
+            SearchMode::CaseInsensitive => LineMatcher::Pattern(mode, pattern(&regex::escape(query))?),
+            SearchMode::Regex => LineMatcher::Pattern(mode, pattern(query)?),
+        })
+    }
+
+    pub fn mode(&self) -> SearchMode {
+        match self {
+            LineMatcher::TestName { .. } => SearchMode::TestName,
+            LineMatcher::Plain(_) => SearchMode::Plain,
+            LineMatcher::Pattern(mode, _) => *mode,
+        }
+    }
+
+    /// `None` when the line doesn't match, else the byte range of the match when one is known
+    pub fn find(&self, line: &str) -> Option<Option<(usize, usize)>> {
+        match self {
+            LineMatcher::TestName { terms, canonical } => {
+                // Lines are compared in canonical form too, so smart quotes or NBSPs on either side still match
+                let canonical_line = canonical_test_name(line);
+                if !canonical.iter().any(|term| canonical_line.contains(term.as_str())) {
+                    return None;
+                }
+                // Longest variant first, so the full name wins over its last segment
+                let mut terms: Vec<&String> = terms.iter().filter(|term| !term.is_empty()).collect();
+                terms.sort_by_key(|term| std::cmp::Reverse(term.len()));
+                Some(terms.into_iter().find_map(|term| line.find(term.as_str()).map(|start| (start, start + term.len()))))
+            }
+            LineMatcher::Plain(text) => line.find(text.as_str()).map(|start| Some((start, start + text.len()))),
+            LineMatcher::Pattern(_, regex) => regex.find(line).map(|found| Some((found.start(), found.end()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modes_report_the_matched_range() {
+        let line = "FAILED tests/test_io.py::TestRead::test_Empty - AssertionError";
+        assert_eq!(LineMatcher::new("test_Empty", SearchMode::Plain).unwrap().find(line), Some(Some((35, 45))));
+        assert_eq!(LineMatcher::new("test_empty", SearchMode::Plain).unwrap().find(line), None);
+        assert_eq!(LineMatcher::new("TEST_EMPTY", SearchMode::CaseInsensitive).unwrap().find(line), Some(Some((35, 45))));
+        assert_eq!(LineMatcher::new(r"Assertion\w+", SearchMode::Regex).unwrap().find(line), Some(Some((48, 62))));
+        assert!(LineMatcher::new("(", SearchMode::Regex).is_err());
+        assert!(LineMatcher::new("", SearchMode::Plain).is_err());
+    }
+
+    #[test]
+    fn test_test_name_mode_finds_the_name_in_the_raw_line() {
+        let matcher = LineMatcher::new("tests/test_io.py::TestRead::test_Empty", SearchMode::TestName).unwrap();
+        assert_eq!(matcher.mode(), SearchMode::TestName);
+        let line = "FAILED tests/test_io.py::TestRead::test_Empty - AssertionError";
+        assert_eq!(matcher.find(line), Some(Some((7, 45))));
+        assert_eq!(matcher.find("collected 12 items"), None);
+    }
+}
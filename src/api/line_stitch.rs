@@ -0,0 +1,154 @@
+//! Reassembling test lines split by interleaved output
+//!
+//! Parallel runners share one stdout, so a test's name and its status can land on
+//! different lines with other output in between (`test foo ... ` here, `ok` a few lines
+//! down). Before any parser sees the log, orphaned status lines are moved back onto the
+//! test line still waiting for one and blanked in place, so line numbers stay valid for
+//! search and the parsers only ever see whole test lines.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// How far below its test line an orphaned status is still attributed to it
+const MAX_STITCH_DISTANCE: usize = 200;
+// Lone JS result markers are joined with a title at most this many lines further down
+const MAX_MARKER_DISTANCE: usize = 3;
+
+lazy_static! {
+    // `test foo::bar ... ` with no status yet
+    static ref CARGO_OPEN_RE: Regex = Regex::new(r"^\s*test \S+ \.\.\.\s*$").unwrap();
+    // unittest/Django `test_foo (pkg.tests.FooTests) ... `, optionally with a docstring before the dots
+    static ref UNITTEST_OPEN_RE: Regex = Regex::new(r"^\s*\w+ \([\w.]+\)(?: .*)? \.\.\.\s*$").unwrap();
+    // pytest verbose `tests/test_foo.py::test_bar[param] ` with the status still to come
+    static ref PYTEST_OPEN_RE: Regex = Regex::new(r"^\s*\S+\.py::[^\s\[]+(?:\[.*\])?\s*$").unwrap();
+
+    static ref CARGO_STATUS_RE: Regex = Regex::new(r"^(?:ok|FAILED|ignored(?:, .*)?)$").unwrap();
+    static ref UNITTEST_STATUS_RE: Regex = Regex::new(r"^(?:ok|FAIL|ERROR|skipped\b.*|expected failure|unexpected success)$").unwrap();
+    static ref PYTEST_STATUS_RE: Regex = Regex::new(r"^(?:PASSED|FAILED|ERROR|SKIPPED|XFAIL|XPASS)(?:\s+\[\s*\d+%\])?$").unwrap();
+
+    // A mocha/jest/vitest result marker printed without its title
+    static ref JS_MARKER_RE: Regex = Regex::new(r"^(\s*)([✓✔√✕×✖↓○])\s*$").unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Runner {
+    Cargo,
+    Unittest,
+    Pytest,
+}
+
+impl Runner {
+    fn of_open_line(line: &str) -> Option<Self> {
+        if CARGO_OPEN_RE.is_match(line) {
+            Some(Runner::Cargo)
+        } else if UNITTEST_OPEN_RE.is_match(line) {
+            Some(Runner::Unittest)
+        } else if PYTEST_OPEN_RE.is_match(line) {
+            Some(Runner::Pytest)
+        } else {
+            None
+        }
+    }
+
+    fn accepts(self, status: &str) -> bool {
+        match self {
+            Runner::Cargo => CARGO_STATUS_RE.is_match(status),
+            Runner::Unittest => UNITTEST_STATUS_RE.is_match(status),
+            Runner::Pytest => PYTEST_STATUS_RE.is_match(status),
+        }
+    }
+}
+
+/// Move orphaned status lines back onto their test lines, keeping one output line per input line;
+/// also returns how many lines were moved
+pub fn stitch_split_status_lines(content: &str) -> (String, usize) {
+    let mut stitched = 0;
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+
+    // Test lines waiting for a status, oldest first; parallel runners finish tests roughly in start order
+    let mut open: Vec<(usize, Runner)> = Vec::new();
+    for i in 0..lines.len() {
+        open.retain(|(start, _)| i - start <= MAX_STITCH_DISTANCE);
+        if let Some(runner) = Runner::of_open_line(&lines[i]) {
+            open.push((i, runner));
+            continue;
+        }
+        let status = lines[i].trim();
+        if status.is_empty() {
+            continue;
+        }
+        if let Some(position) = open.iter().position(|(_, runner)| runner.accepts(status)) {
+            let (start, _) = open.remove(position);
+            lines[start] = format!("{} {}", lines[start].trim_end(), status);
+            lines[i].clear();
+            stitched += 1;
+        }
+    }
+
+    for i in 0..lines.len() {
+        let Some(captures) = JS_MARKER_RE.captures(&lines[i]) else { continue };
+        let marker = format!("{}{}", &captures[1], &captures[2]);
+        let title = (i + 1..lines.len().min(i + 1 + MAX_MARKER_DISTANCE))
+            .find(|&j| !lines[j].trim().is_empty())
+            .filter(|&j| !JS_MARKER_RE.is_match(&lines[j]) && Runner::of_open_line(&lines[j]).is_none());
+        if let Some(j) = title {
+            lines[i] = format!("{} {}", marker, lines[j].trim());
+            lines[j].clear();
+            stitched += 1;
+        }
+    }
+
+    (lines.join("\n"), stitched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stitches_interleaved_cargo_and_unittest_lines() {
+        let log = "test a::one ... \n\
+                   test a::two ... \n\
+                   [debug] warming cache\n\
+                   ok\n\
+                   FAILED\n\
+                   test_login (accounts.tests.LoginTests) ... \n\
+                   Creating test database\n\
+                   skipped 'needs network'";
+
+        let (stitched, moved) = stitch_split_status_lines(log);
+        assert_eq!(moved, 3);
+        let lines: Vec<&str> = stitched.split('\n').collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "test a::one ... ok");
+        assert_eq!(lines[1], "test a::two ... FAILED");
+        assert_eq!(lines[2], "[debug] warming cache");
+        assert_eq!(&lines[3..5], ["", ""]);
+        assert_eq!(lines[5], "test_login (accounts.tests.LoginTests) ... skipped 'needs network'");
+        assert_eq!(lines[7], "");
+    }
+
+    #[test]
+    fn test_stitches_pytest_and_js_markers() {
+        let log = "tests/test_api.py::test_get[a b] \n\
+                   [gw1] captured log line\n\
+                   PASSED [ 50%]\n\
+                   \x20 ✓\n\
+                   \x20 renders the header (5ms)";
+
+        let (stitched, moved) = stitch_split_status_lines(log);
+        assert_eq!(moved, 2);
+        let lines: Vec<&str> = stitched.split('\n').collect();
+        assert_eq!(lines[0], "tests/test_api.py::test_get[a b] PASSED [ 50%]");
+        assert_eq!(lines[2], "");
+        assert_eq!(lines[3], "  ✓ renders the header (5ms)");
+        assert_eq!(lines[4], "");
+    }
+
+    #[test]
+    fn test_leaves_unclaimed_status_words_alone() {
+        // No test line is waiting, and a pytest status never closes a cargo line
+        let log = "ok\ntest a::one ... \nPASSED\nrunning\n";
+        assert_eq!(stitch_split_status_lines(log), (log.to_string(), 0));
+    }
+}
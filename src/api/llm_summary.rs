@@ -0,0 +1,188 @@
+//! Optional natural-language summary of why a deliverable likely fails review
+//!
+//! Sends the rule violations plus a few matching log snippets to the OpenAI-compatible
+//! endpoint configured in the `llm` section of the server config. This is advisory
+//! only: it runs on demand, separately from the deterministic analysis.
+
+use serde_json::json;
+
+use crate::api::server_config::load_server_config;
+use crate::app::types::{default_search_stages, example_test_name, rule_description, LogAnalysisResult, SearchResult};
+
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+const MAX_TESTS_PER_RULE: usize = 3;
+// Keep the request well inside small context windows
+const MAX_PROMPT_CHARS: usize = 12_000;
+
+const SYSTEM_PROMPT: &str = "You review SWE-bench style deliverables. Given automated rule violations \
+and log excerpts, explain in at most five short sentences why the deliverable would likely fail review. \
+Be concrete, cite test names, and do not invent facts that are not in the input.";
+
+pub fn is_enabled() -> bool {
+    load_server_config()
+        .llm
+        .base_url
+        .is_some_and(|url| !url.trim().is_empty())
+}
+
+fn format_snippet(stage: &str, result: &SearchResult) -> String {
+    let mut snippet = format!("[{} log, line {}]\n", stage, result.line_number);
+    for line in &result.context_before {
+        snippet.push_str(&format!("  {}\n", line));
+    }
+    snippet.push_str(&format!("> {}\n", result.line_content));
+    for line in &result.context_after {
+        snippet.push_str(&format!("  {}\n", line));
+    }
+    snippet
+}
+
+/// Build the user prompt from the violated rules and log context for a few of their tests
+pub fn build_prompt(analysis: &LogAnalysisResult, snippets: &[(String, String)]) -> String {
+    let mut prompt = String::from("Rule violations:\n");
+    let violated: Vec<_> = analysis
+        .rule_violations
+        .entries()
+        .into_iter()
+        .filter(|(_, v)| v.has_problem)
+        .collect();
+
+    if violated.is_empty() {
+        prompt.push_str("- none\n");
+    }
+    for (code, violation) in violated {
+        prompt.push_str(&format!("- {} {} ({} examples)\n", code, rule_description(code), violation.examples.len()));
+        for example in violation.examples.iter().take(MAX_TESTS_PER_RULE) {
+            prompt.push_str(&format!("    - {}\n", example));
+        }
+    }
+
+    prompt.push_str(&format!(
+        "\nF2P tests: {}, P2P tests: {}\n",
+        analysis.test_statuses.f2p.len(),
+        analysis.test_statuses.p2p.len()
+    ));
+
+    if !snippets.is_empty() {
+        prompt.push_str("\nLog excerpts:\n");
+        for (test_name, snippet) in snippets {
+            let entry = format!("Test {}:\n{}\n", test_name, snippet);
+            if prompt.len() + entry.len() > MAX_PROMPT_CHARS {
+                break;
+            }
+            prompt.push_str(&entry);
+        }
+    }
+
+    prompt
+}
+
+// One excerpt per stage log for the first few tests of each violated rule
+fn collect_snippets(file_paths: &[String], analysis: &LogAnalysisResult) -> Vec<(String, String)> {
+    use crate::api::line_matcher::LineMatcher;
+    use crate::api::log_analysis::search_logs;
+
+    let mut test_names: Vec<String> = Vec::new();
+    for (_, violation) in analysis.rule_violations.entries() {
+        if !violation.has_problem {
+            continue;
+        }
+        for example in violation.examples.iter().take(MAX_TESTS_PER_RULE) {
+            let name = example_test_name(example).to_string();
+            if !test_names.contains(&name) {
+                test_names.push(name);
+            }
+        }
+    }
+
+    let mut snippets = Vec::new();
+    for test_name in test_names {
+        let Ok(results) = search_logs(file_paths.to_vec(), &LineMatcher::test_name(&test_name), &default_search_stages(), None, None, false) else {
+            continue;
+        };
+        let stages = [
+            ("base", results.base_results.first()),
+            ("before", results.before_results.first()),
+            ("after", results.after_results.first()),
+        ];
+        let snippet: String = stages
+            .iter()
+            .filter_map(|(stage, result)| result.map(|r| format_snippet(stage, r)))
+            .collect();
+        if !snippet.is_empty() {
+            snippets.push((test_name, snippet));
+        }
+    }
+    snippets
+}
+
+/// Ask the configured model for a short summary of the likely review failure
+pub async fn generate_failure_summary(file_paths: &[String], analysis: &LogAnalysisResult) -> Result<String, String> {
+    let config = load_server_config().llm;
+    let base_url = config
+        .base_url
+        .filter(|url| !url.trim().is_empty())
+        .ok_or("LLM summaries are not configured on this server")?;
+
+    let prompt = build_prompt(analysis, &collect_snippets(file_paths, analysis));
+    let body = json!({
+        "model": config.model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+        "temperature": 0.2,
+        "messages": [
+            { "role": "system", "content": SYSTEM_PROMPT },
+            { "role": "user", "content": prompt },
+        ],
+    });
+
+    let mut request = reqwest::Client::new()
+        .post(format!("{}/chat/completions", base_url.trim_end_matches('/')))
+        .timeout(std::time::Duration::from_secs(60))
+        .json(&body);
+    if let Some(key) = config.api_key.as_deref().filter(|k| !k.is_empty()) {
+        request = request.bearer_auth(key);
+    }
+
+    let resp = request.send().await.map_err(|e| format!("Failed to reach LLM endpoint: {}", e))?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        return Err(format!("LLM endpoint responded with {}: {}", status, text));
+    }
+
+    let json: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
+
+    json["choices"][0]["message"]["content"]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "LLM response contained no summary".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::types::{RuleViolation, RuleViolations};
+
+    #[test]
+    fn test_build_prompt_lists_violations_and_snippets() {
+        let analysis = LogAnalysisResult {
+            rule_violations: RuleViolations {
+                c3_f2p_success_in_before: RuleViolation {
+                    has_problem: true,
+                    examples: vec!["test_a".to_string()],
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let prompt = build_prompt(&analysis, &[("test_a".to_string(), "[before log, line 3]\n> test_a PASSED\n".to_string())]);
+        assert!(prompt.contains("- C3 Fail-to-pass tests that succeeded in before (1 examples)"));
+        assert!(prompt.contains("    - test_a\n"));
+        assert!(prompt.contains("Test test_a:\n[before log, line 3]\n> test_a PASSED"));
+        assert!(!prompt.contains("C1"));
+    }
+}
@@ -1,87 +1,258 @@
-use crate::app::types::{LogAnalysisResult, LogSearchResults, SearchResult};
+use std::collections::HashMap;
 
+use crate::api::analysis_progress::{ProgressReporter, PHASE_LOCATE};
+use crate::api::analysis_sources::describe_source;
+use crate::api::context_diff::build_context_diff;
+use crate::api::line_matcher::LineMatcher;
+use crate::api::log_discovery::{log_file_name, LogPatterns};
+use crate::api::language_defaults::{deliverable_language, LanguageDefaults};
+use crate::api::log_preprocess::{preprocess_language_log, preprocess_log_keeping_noise, read_log_file_with_stats};
+use crate::api::log_slice::read_log_slice;
+use crate::api::search_variants::{count_variant_hits, search_variants};
+use crate::api::test_name::canonical_test_name;
+use crate::api::trace_context::trace_around;
+use crate::app::types::{ContextDiff, CustomTestLists, DetectedDuplicate, LogAnalysisResult, LogSearchResults, LogSlice, ReportCategories, SearchResult, TestLists, TestNameInspection};
 
-pub fn search_logs(file_paths: Vec<String>, test_name: String) -> Result<LogSearchResults, String> {
-    use tempfile::TempDir;
-    use std::path::PathBuf;
-    // Resolve relative paths to absolute under base_temp_dir
-    let temp_dir = TempDir::new().map_err(|e| format!("Failed to create temp directory: {}", e))?;
-    let temp_path = temp_dir.path().to_string_lossy().to_string();
-    let base_temp_dir = std::path::Path::new(&temp_path).parent().unwrap().join("swe-reviewer-temp");
 
-    let abs_paths: Vec<PathBuf> = file_paths.iter().map(|rel| base_temp_dir.join(rel)).collect();
+/// Most lines of context a search hit can ask for; longer reads go through `log_slice`
+pub const MAX_SEARCH_CONTEXT: usize = 50;
 
-    let base_log = abs_paths.iter().find(|p| p.to_string_lossy().to_lowercase().contains("base.log"));
-    let before_log = abs_paths.iter().find(|p| p.to_string_lossy().to_lowercase().contains("before.log"));
-    let after_log = abs_paths.iter().find(|p| p.to_string_lossy().to_lowercase().contains("after.log"));
-    
-    let base_results = if let Some(path) = base_log {
-        search_in_log_file(&path.to_string_lossy(), &test_name)?
-    } else {
-        Vec::new()
-    };
-    
-    let before_results = if let Some(path) = before_log {
-        search_in_log_file(&path.to_string_lossy(), &test_name)?
-    } else {
-        Vec::new()
+/// Search the stage logs picked in `stages` ("base", "before", "after", "agent") for lines
+/// `matcher` accepts; other logs are not read. `context_lines` overrides the language's search
+/// context, up to `MAX_SEARCH_CONTEXT`; `include_noise` searches build/install output too
+pub fn search_logs(file_paths: Vec<String>, matcher: &LineMatcher, stages: &[String], agent_log: Option<String>, context_lines: Option<usize>, include_noise: bool) -> Result<LogSearchResults, String> {
+    let abs_paths = absolute_paths(&file_paths)?;
+    let patterns = LogPatterns::load();
+    let reading = LogReading::for_deliverable(&abs_paths).with_context(context_lines).with_noise(include_noise);
+    let selected = |stage: &str| stages.iter().any(|s| s == stage);
+
+    let search_stage = |stage: &str| -> Result<Vec<SearchResult>, String> {
+        match patterns.find(stage, &abs_paths) {
+            Some(path) if selected(stage) => search_in_log_file(path, matcher, &reading),
+            _ => Ok(Vec::new()),
+        }
     };
-    
-    let after_results = if let Some(path) = after_log {
-        search_in_log_file(&path.to_string_lossy(), &test_name)?
+    let base_results = search_stage("base")?;
+    let before_results = search_stage("before")?;
+    let after_results = search_stage("after")?;
+
+    let agent_results = if selected("agent") {
+        search_agent_path(find_agent_log(&abs_paths, agent_log, &patterns), matcher, &reading)?
     } else {
         Vec::new()
     };
-    
+
     Ok(LogSearchResults {
         base_results,
         before_results,
         after_results,
+        agent_results,
     })
 }
 
-pub fn search_agent_log(file_paths: Vec<String>, test_name: String) -> Result<Vec<SearchResult>, String> {
+pub fn search_agent_log(file_paths: Vec<String>, test_name: String, agent_log: Option<String>) -> Result<Vec<SearchResult>, String> {
+    let abs_paths = absolute_paths(&file_paths)?;
+    let reading = LogReading::for_deliverable(&abs_paths);
+    search_agent_path(find_agent_log(&abs_paths, agent_log, &LogPatterns::load()), &LineMatcher::test_name(&test_name), &reading)
+}
+
+/// Lines around `line` of a stage log, or around the first line mentioning `test_name` when no
+/// line is given; `None` when the log or the anchor can't be found
+pub fn log_slice(
+    file_paths: Vec<String>,
+    stage: String,
+    agent_log: Option<String>,
+    line: Option<usize>,
+    test_name: Option<String>,
+    radius: usize,
+) -> Result<Option<LogSlice>, String> {
+    let abs_paths = absolute_paths(&file_paths)?;
+    let patterns = LogPatterns::load();
+    let path = match stage.as_str() {
+        "agent" => find_agent_log(&abs_paths, agent_log, &patterns),
+        "base" | "before" | "after" => patterns.find(&stage, &abs_paths),
+        _ => return Err(format!("Unknown log stage: {}", stage)),
+    };
+    let Some(path) = path else { return Ok(None) };
+
+    let line = match (line, test_name) {
+        (Some(line), _) => line,
+        (None, Some(test_name)) => match search_in_log_file(path, &LineMatcher::test_name(&test_name), &LogReading::for_deliverable(&abs_paths))?.first() {
+            Some(result) => result.line_number,
+            None => return Ok(None),
+        },
+        (None, None) => return Err("A line number or test name is required".to_string()),
+    };
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    read_log_slice(std::io::BufReader::new(file), line, radius)
+}
+
+/// `log_slice` for many anchors, given as (stage, line, test name), reading each stage log once.
+/// An anchor whose log is missing, unreadable or never mentions the test gets `None`
+pub fn log_slices(file_paths: Vec<String>, agent_log: Option<String>, anchors: &[(String, Option<usize>, String)], radius: usize) -> Result<Vec<Option<LogSlice>>, String> {
+    use crate::api::log_slice::LogLines;
+
+    let abs_paths = absolute_paths(&file_paths)?;
+    let patterns = LogPatterns::load();
+    let reading = LogReading::for_deliverable(&abs_paths);
+    // Per stage: raw lines for the slices, and the content searches run on, read on first use
+    let mut logs: HashMap<&str, Option<(LogLines, Option<String>)>> = HashMap::new();
+    let mut slices = Vec::with_capacity(anchors.len());
+    for (stage, line, test_name) in anchors {
+        let log = logs.entry(stage.as_str()).or_insert_with(|| {
+            let path = match stage.as_str() {
+                "agent" => find_agent_log(&abs_paths, agent_log.clone(), &patterns),
+                "base" | "before" | "after" => patterns.find(stage, &abs_paths),
+                _ => None,
+            }?;
+            let file = std::fs::File::open(path).ok()?;
+            let lines = LogLines::read(std::io::BufReader::new(file)).ok()?;
+            Some((lines, reading.read(path).ok()))
+        });
+        let Some((lines, content)) = log else {
+            slices.push(None);
+            continue;
+        };
+        let line = match line {
+            Some(line) => Some(*line),
+            None => content.as_deref().and_then(|content| {
+                search_in_content(content, &LineMatcher::test_name(test_name), 0).first().map(|result| result.line_number)
+            }),
+        };
+        slices.push(line.and_then(|line| lines.slice(line, radius)));
+    }
+    Ok(slices)
+}
+
+/// The test's failure context in the before log diffed against the after log
+pub fn context_diff(file_paths: Vec<String>, test_name: String) -> Result<ContextDiff, String> {
+    let abs_paths = absolute_paths(&file_paths)?;
+    let patterns = LogPatterns::load();
+    let reading = LogReading::for_deliverable(&abs_paths);
+    let matcher = LineMatcher::test_name(&test_name);
+    let mut logs = Vec::new();
+    for stage in ["before", "after"] {
+        let log = match patterns.find(stage, &abs_paths) {
+            Some(path) => {
+                let content = reading.read(path)?;
+                let matches: Vec<usize> = search_in_content(&content, &matcher, reading.context).iter().map(|result| result.line_number).collect();
+                Some((content, matches))
+            }
+            None => None,
+        };
+        logs.push(log);
+    }
+    let before = logs[0].as_ref().map(|(content, matches)| (content.as_str(), matches.as_slice()));
+    let after = logs[1].as_ref().map(|(content, matches)| (content.as_str(), matches.as_slice()));
+    Ok(build_context_diff(before, after))
+}
+
+/// The test name's search variants and their hits in each stage log the deliverable has
+pub fn inspect_test_name(file_paths: Vec<String>, test_name: String, agent_log: Option<String>) -> Result<TestNameInspection, String> {
+    let abs_paths = absolute_paths(&file_paths)?;
+    let patterns = LogPatterns::load();
+    let reading = LogReading::for_deliverable(&abs_paths);
+    let mut variants = search_variants(&test_name);
+    for stage in ["base", "before", "after"] {
+        if let Some(path) = patterns.find(stage, &abs_paths) {
+            count_variant_hits(&mut variants, stage, &reading.read(path)?);
+        }
+    }
+    if let Some(path) = find_agent_log(&abs_paths, agent_log, &patterns) {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read agent log {}: {}", path, e))?;
+        count_variant_hits(&mut variants, "agent", &reading.preprocess(&content));
+    }
+    Ok(TestNameInspection {
+        canonical: canonical_test_name(&test_name),
+        test_name,
+        variants,
+    })
+}
+
+// Resolve relative paths to absolute under base_temp_dir
+fn absolute_paths(file_paths: &[String]) -> Result<Vec<String>, String> {
     use tempfile::TempDir;
-    use std::path::PathBuf;
-    use std::fs;
-    // Resolve relative paths to absolute under base_temp_dir
     let temp_dir = TempDir::new().map_err(|e| format!("Failed to create temp directory: {}", e))?;
     let temp_path = temp_dir.path().to_string_lossy().to_string();
     let base_temp_dir = std::path::Path::new(&temp_path).parent().unwrap().join("swe-reviewer-temp");
 
-    let abs_paths: Vec<PathBuf> = file_paths.iter().map(|rel| base_temp_dir.join(rel)).collect();
-    let agent_log = abs_paths.iter().find(|p| {
-        let s = p.to_string_lossy().to_lowercase();
-        s.contains("post_agent_patch") || s.ends_with("agent.log")
-    });
+    Ok(file_paths.iter().map(|rel| base_temp_dir.join(rel).to_string_lossy().to_string()).collect())
+}
+
+// The agent run picked by name, or the default one
+fn find_agent_log<'a>(abs_paths: &'a [String], agent_log: Option<String>, patterns: &LogPatterns) -> Option<&'a String> {
+    match agent_log {
+        Some(name) => abs_paths.iter().find(|p| log_file_name(p) == name),
+        None => patterns.default_agent_log(abs_paths),
+    }
+}
+
+// The deliverable's language, for its noise patterns, how much context its search hits get, and
+// whether noise lines are kept
+struct LogReading {
+    language: Option<String>,
+    context: usize,
+    include_noise: bool,
+}
+
+impl LogReading {
+    fn for_deliverable(abs_paths: &[String]) -> Self {
+        let language = deliverable_language(abs_paths);
+        let context = language.as_deref().map(LanguageDefaults::load).unwrap_or_default().search_context;
+        Self { language, context, include_noise: false }
+    }
+
+    fn with_noise(self, include_noise: bool) -> Self {
+        Self { include_noise, ..self }
+    }
+
+    fn with_context(self, context_lines: Option<usize>) -> Self {
+        match context_lines {
+            Some(context) => Self { context: context.min(MAX_SEARCH_CONTEXT), ..self },
+            None => self,
+        }
+    }
+
+    fn read(&self, path: &str) -> Result<String, String> {
+        if self.include_noise {
+            let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read log file {}: {}", path, e))?;
+            return Ok(preprocess_log_keeping_noise(&content));
+        }
+        read_log_file_with_stats(path, self.language.as_deref()).map(|(content, _)| content)
+    }
+
+    fn preprocess(&self, content: &str) -> String {
+        if self.include_noise {
+            return preprocess_log_keeping_noise(content);
+        }
+        preprocess_language_log(content, self.language.as_deref())
+    }
+}
 
+fn search_agent_path(agent_log: Option<&String>, matcher: &LineMatcher, reading: &LogReading) -> Result<Vec<SearchResult>, String> {
     if let Some(path) = agent_log {
-        let content = fs::read_to_string(path)
-            .map_err(|e| format!("Failed to read agent log {}: {}", path.to_string_lossy(), e))?;
-        Ok(search_in_content(&content, &test_name))
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read agent log {}: {}", path, e))?;
+        Ok(search_in_content(&reading.preprocess(&content), matcher, reading.context))
     } else {
         Ok(vec![])
     }
 }
 
-fn search_in_content(content: &str, test_name: &str) -> Vec<SearchResult> {
+fn search_in_content(content: &str, matcher: &LineMatcher, context: usize) -> Vec<SearchResult> {
     let lines: Vec<&str> = content.lines().collect();
     let mut results = Vec::new();
-    let search_terms = get_search_terms(test_name);
     for (line_number, line) in lines.iter().enumerate() {
-        let mut found_match = false;
-        for search_term in &search_terms {
-            if line.contains(search_term) { found_match = true; break; }
-        }
-        if found_match {
+        if let Some(match_range) = matcher.find(line) {
             let context_before: Vec<String> = lines.iter()
-                .skip(line_number.saturating_sub(5))
-                .take(5.min(line_number))
+                .skip(line_number.saturating_sub(context))
+                .take(context.min(line_number))
                 .map(|s| s.to_string())
                 .collect();
             let context_after: Vec<String> = lines.iter()
                 .skip(line_number + 1)
-                .take(5)
+                .take(context)
                 .map(|s| s.to_string())
                 .collect();
             results.push(SearchResult {
@@ -89,88 +260,62 @@ fn search_in_content(content: &str, test_name: &str) -> Vec<SearchResult> {
                 line_content: line.to_string(),
                 context_before,
                 context_after,
+                trace: trace_around(&lines, line_number),
+                mode: matcher.mode(),
+                match_range,
             });
         }
     }
     results
 }
 
-fn search_in_log_file(file_path: &str, test_name: &str) -> Result<Vec<SearchResult>, String> {
-    use std::fs;
-    
-    let content = fs::read_to_string(file_path)
-        .map_err(|e| format!("Failed to read log file {}: {}", file_path, e))?;
-    
-    let lines: Vec<&str> = content.lines().collect();
-    let mut results = Vec::new();
-    
-    let search_terms = get_search_terms(test_name);
-    
-    for (line_number, line) in lines.iter().enumerate() {
-        let mut found_match = false;
-        
-        for search_term in &search_terms {
-            if line.contains(search_term) {
-                found_match = true;
-                break;
-            }
-        }
-        
-        if found_match {
-            let context_before: Vec<String> = lines.iter()
-                .skip(line_number.saturating_sub(5))
-                .take(5.min(line_number))
-                .map(|s| s.to_string())
-                .collect();
-            
-            let context_after: Vec<String> = lines.iter()
-                .skip(line_number + 1)
-                .take(5)
-                .map(|s| s.to_string())
-                .collect();
-            
-            results.push(SearchResult {
-                line_number: line_number + 1,
-                line_content: line.to_string(),
-                context_before,
-                context_after,
-            });
-        }
-    }
-    
-    Ok(results)
+fn search_in_log_file(file_path: &str, matcher: &LineMatcher, reading: &LogReading) -> Result<Vec<SearchResult>, String> {
+    let content = reading.read(file_path)?;
+    Ok(search_in_content(&content, matcher, reading.context))
 }
 
-fn get_search_terms(test_name: &str) -> Vec<String> {
-    let mut search_terms = vec![test_name.to_string()];
-    
-    // Split on " - " (hyphen with spaces)
-    if let Some(last_part) = test_name.split(" - ").last() {
-        if last_part != test_name {
-            search_terms.push(last_part.to_string());
-        }
-    }
-    
-    // Split on " > " (greater than with spaces) for hierarchical test names
-    if let Some(last_part) = test_name.split(" > ").last() {
-        if last_part != test_name {
-            search_terms.push(last_part.to_string());
-        }
-    }
-    
-    search_terms.dedup();
-    search_terms
+pub fn analyze_logs(
+    file_paths: Vec<String>,
+) -> Result<LogAnalysisResult, String> {
+    analyze_logs_with_agent(file_paths, None)
 }
 
+/// Same as `analyze_logs`, with the `agent` stage taken from the named agent log
+pub fn analyze_logs_with_agent(
+    file_paths: Vec<String>,
+    agent_log: Option<String>,
+) -> Result<LogAnalysisResult, String> {
+    analyze_logs_with_lists(file_paths, agent_log, None)
+}
 
-pub fn analyze_logs(
+/// Same as `analyze_logs_with_agent`, with the reviewer's test lists applied over main.json's
+pub fn analyze_logs_with_lists(
+    file_paths: Vec<String>,
+    agent_log: Option<String>,
+    custom_test_lists: Option<CustomTestLists>,
+) -> Result<LogAnalysisResult, String> {
+    analyze_logs_with_progress(file_paths, agent_log, custom_test_lists, &ProgressReporter::default())
+}
+
+/// Same as `analyze_logs_with_lists`, reporting each phase to `progress`
+pub fn analyze_logs_with_progress(
     file_paths: Vec<String>,
+    agent_log: Option<String>,
+    custom_test_lists: Option<CustomTestLists>,
+    progress: &ProgressReporter,
 ) -> Result<LogAnalysisResult, String> {
+    use crate::api::coverage::coverage_from_files;
+    use crate::api::custom_test_lists::apply_custom_test_lists;
+    use crate::api::file_operations::get_test_lists;
+    use crate::api::status_overrides::load_status_overrides;
+    use crate::api::test_list_source::{read_test_list, FAIL_TO_PASS_KEYS, PASS_TO_PASS_KEYS};
     use crate::api::log_parser::LogParser;
+    use crate::api::patch_classes::load_patch_classes;
     use std::fs;
     use tempfile::TempDir;
     use std::path::PathBuf;
     
+    progress.begin(PHASE_LOCATE);
     // Resolve relative paths to absolute under base_temp_dir
     let temp_dir = TempDir::new().map_err(|e| format!("Failed to create temp directory: {}", e))?;
     let temp_path = temp_dir.path().to_string_lossy().to_string();
@@ -183,42 +328,101 @@ pub fn analyze_logs(
     let main_json_path = abs_paths_str.iter()
         .find(|path| path.to_lowercase().contains("main.json") || path.to_lowercase().contains("main/"));
     
-    let (fail_to_pass_tests, pass_to_pass_tests, language) = if let Some(path) = main_json_path {
+    let (fail_to_pass_tests, pass_to_pass_tests, language, gold_patch) = if let Some(path) = main_json_path {
         match fs::read_to_string(path) {
             Ok(content) => {
                 match serde_json::from_str::<serde_json::Value>(&content) {
                     Ok(main_json) => {
-                        let fail_to_pass: Vec<String> = main_json.get("fail_to_pass")
-                            .and_then(|v| v.as_array())
-                            .unwrap_or(&vec![])
-                            .iter()
-                            .filter_map(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .collect();
-                        
-                        let pass_to_pass: Vec<String> = main_json.get("pass_to_pass")
-                            .and_then(|v| v.as_array())
-                            .unwrap_or(&vec![])
-                            .iter()
-                            .filter_map(|v| v.as_str())
-                            .map(|s| s.to_string())
-                            .collect();
+                        let (fail_to_pass, _) = read_test_list(&main_json, FAIL_TO_PASS_KEYS);
+                        let (pass_to_pass, _) = read_test_list(&main_json, PASS_TO_PASS_KEYS);
                         let language = main_json.get("language")
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string().to_lowercase())
                             .unwrap_or(String::from("rust"));
-                        (fail_to_pass, pass_to_pass, language)
+                        let gold_patch = main_json.get("gold_patch")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("")
+                            .to_string();
+                        (fail_to_pass, pass_to_pass, language, gold_patch)
                     },
-                    Err(_) => (vec![], vec![], String::from("rust")),
+                    Err(_) => (vec![], vec![], String::from("rust"), String::new()),
                 }
             },
-            Err(_) => (vec![], vec![], String::from("rust")),
+            Err(_) => (vec![], vec![], String::from("rust"), String::new()),
         }
     } else {
-        (vec![], vec![], String::from("rust"))
+        (vec![], vec![], String::from("rust"), String::new())
     };
+    let provenance = get_test_lists(file_paths.clone()).ok().and_then(|lists| lists.provenance);
+    let TestLists { fail_to_pass: fail_to_pass_tests, pass_to_pass: pass_to_pass_tests, provenance } = apply_custom_test_lists(
+        TestLists { fail_to_pass: fail_to_pass_tests, pass_to_pass: pass_to_pass_tests, provenance },
+        custom_test_lists.as_ref(),
+    );
     
-    let log_checker = LogParser::new();
-    log_checker.analyze_logs(&abs_paths_str, &language, &fail_to_pass_tests, &pass_to_pass_tests)
+    let log_checker = LogParser::new()
+        .with_status_overrides(load_status_overrides(&file_paths)?)
+        .with_patch_classes(load_patch_classes(&file_paths)?)
+        .with_progress(progress.clone());
+    let mut result = log_checker.analyze_logs(&abs_paths_str, &language, &fail_to_pass_tests, &pass_to_pass_tests, agent_log.as_deref())?;
+    result.coverage = coverage_from_files(&abs_paths_str, &gold_patch, &fail_to_pass_tests);
+    if let Some(settings) = result.settings.as_mut() {
+        settings.custom_test_lists = custom_test_lists;
+    }
+    if let Some(path) = main_json_path {
+        result.sources.splice(0..0, describe_source("main", path, None));
+    }
+    result.test_list_provenance = provenance;
+    Ok(result)
+}
+
+
+/// Check one rule of a stored analysis again and replace its verdict in place, using the
+/// statuses the analysis found and the test lists it ran with; see `log_parser::recheck_rule`
+pub fn recheck_rule(file_paths: Vec<String>, code: &str, analysis: &mut LogAnalysisResult) -> Result<(), String> {
+    use crate::api::custom_test_lists::apply_custom_test_lists;
+    use crate::api::file_operations::get_test_lists;
+    use crate::api::log_parser;
+    use crate::api::patch_classes::load_patch_classes;
+
+    let abs_paths = absolute_paths(&file_paths)?;
+    let patch_classes = load_patch_classes(&file_paths)?;
+    let custom_test_lists = analysis.settings.as_ref().and_then(|settings| settings.custom_test_lists.clone());
+    let lists = apply_custom_test_lists(get_test_lists(file_paths)?, custom_test_lists.as_ref());
+    let violation = log_parser::recheck_rule(code, analysis, &abs_paths, &lists.fail_to_pass, &lists.pass_to_pass, &patch_classes)?;
+    let rule = analysis.rule_violations.get_mut(code).ok_or_else(|| format!("Unknown rule: {}", code))?;
+    *rule = violation;
+    if let Some(settings) = analysis.settings.as_mut() {
+        settings.patch_classes = patch_classes;
+    }
+    Ok(())
 }
 
+/// Tests reported more than once in one log of the deliverable, with each occurrence's line,
+/// status and context; `language` picks the preprocessing the analysis would use
+pub fn detect_duplicates(file_path: String, language: Option<String>) -> Result<Vec<DetectedDuplicate>, String> {
+    use crate::api::log_parser::detect_log_duplicates;
+
+    let abs_path = absolute_paths(std::slice::from_ref(&file_path))?.remove(0);
+    let (content, _) = read_log_file_with_stats(&abs_path, language.as_deref())?;
+    Ok(detect_log_duplicates(&content))
+}
+
+/// report.json's F2P/P2P successes and failures for the Report tab, `None` without a report;
+/// layouts without categories are split by the test lists the stored analysis ran with
+pub fn report_categories(file_paths: Vec<String>) -> Result<Option<ReportCategories>, String> {
+    use crate::api::analysis_store::load_analysis_result;
+    use crate::api::custom_test_lists::apply_custom_test_lists;
+    use crate::api::file_operations::get_test_lists;
+    use crate::api::report_schema;
+
+    let abs_paths = absolute_paths(&file_paths)?;
+    let Some(report_path) = abs_paths.iter().find(|path| path.to_lowercase().ends_with("report.json")) else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(report_path).map_err(|e| format!("Failed to read report.json: {}", e))?;
+    let report: serde_json::Value = serde_json::from_str(&content).map_err(|e| format!("Failed to parse report.json: {}", e))?;
+
+    let custom_test_lists = load_analysis_result(&file_paths).and_then(|analysis| analysis.settings).and_then(|settings| settings.custom_test_lists);
+    let lists = apply_custom_test_lists(get_test_lists(file_paths)?, custom_test_lists.as_ref());
+    report_schema::report_categories(&report, &lists.fail_to_pass, &lists.pass_to_pass).map(Some)
+}
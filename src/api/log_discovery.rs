@@ -0,0 +1,162 @@
+//! Which deliverable files hold which stage log
+//!
+//! File-name patterns come from the `log_discovery` section of the server config so a
+//! change in deliverable layout only needs a config edit. Patterns are matched against
+//! the lowercased file name and support `*` as a wildcard.
+
+use crate::api::server_config::{load_server_config, LogDiscoveryConfig};
+
+pub const DEFAULT_BASE_PATTERNS: &[&str] = &["*base.log", "*base.txt"];
+pub const DEFAULT_BEFORE_PATTERNS: &[&str] = &["*before.log", "*before.txt"];
+pub const DEFAULT_AFTER_PATTERNS: &[&str] = &["*after.log", "*after.txt"];
+// Earlier patterns win when picking the default agent run
+pub const DEFAULT_AGENT_PATTERNS: &[&str] = &["*post_agent_patch.log", "*agent*.log"];
+
+pub fn log_file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if text.len() < first.len() + last.len() || !text.starts_with(first) || !text.ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogPatterns {
+    pub base: Vec<String>,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+    pub agent: Vec<String>,
+}
+
+impl Default for LogPatterns {
+    fn default() -> Self {
+        Self::from_config(&LogDiscoveryConfig::default())
+    }
+}
+
+impl LogPatterns {
+    pub fn from_config(config: &LogDiscoveryConfig) -> Self {
+        let or_default = |configured: &Vec<String>, defaults: &[&str]| -> Vec<String> {
+            if configured.is_empty() {
+                defaults.iter().map(|p| p.to_string()).collect()
+            } else {
+                configured.iter().map(|p| p.to_lowercase()).collect()
+            }
+        };
+        Self {
+            base: or_default(&config.base, DEFAULT_BASE_PATTERNS),
+            before: or_default(&config.before, DEFAULT_BEFORE_PATTERNS),
+            after: or_default(&config.after, DEFAULT_AFTER_PATTERNS),
+            agent: or_default(&config.agent, DEFAULT_AGENT_PATTERNS),
+        }
+    }
+
+    pub fn load() -> Self {
+        Self::from_config(&load_server_config().log_discovery)
+    }
+
+    pub fn stage_patterns(&self, stage: &str) -> &[String] {
+        match stage {
+            "base" => &self.base,
+            "before" => &self.before,
+            "after" => &self.after,
+            "agent" => &self.agent,
+            _ => &[],
+        }
+    }
+
+    // Index of the first pattern of `stage` matching the file name
+    fn match_index(&self, stage: &str, path: &str) -> Option<usize> {
+        let name = log_file_name(path).to_lowercase();
+        self.stage_patterns(stage).iter().position(|pattern| wildcard_match(pattern, &name))
+    }
+
+    /// Whether the file is a log for `stage`; agent logs never double as base/before/after logs
+    pub fn is_stage_log(&self, stage: &str, path: &str) -> bool {
+        if self.match_index(stage, path).is_none() {
+            return false;
+        }
+        stage != "agent" || ["base", "before", "after"].iter().all(|other| self.match_index(other, path).is_none())
+    }
+
+    /// First file holding the `stage` log
+    pub fn find<'a>(&self, stage: &str, file_paths: &'a [String]) -> Option<&'a String> {
+        file_paths.iter().find(|path| self.is_stage_log(stage, path))
+    }
+
+    /// All agent logs among the deliverable files, ordered by file name
+    pub fn agent_logs<'a>(&self, file_paths: &'a [String]) -> Vec<&'a String> {
+        let mut paths: Vec<&String> = file_paths.iter().filter(|path| self.is_stage_log("agent", path)).collect();
+        paths.sort_by_key(|path| log_file_name(path).to_lowercase());
+        paths
+    }
+
+    /// The agent log analyzed unless the reviewer picks another: the run matching the earliest agent pattern
+    pub fn default_agent_log<'a>(&self, file_paths: &'a [String]) -> Option<&'a String> {
+        self.agent_logs(file_paths)
+            .into_iter()
+            .min_by_key(|path| self.match_index("agent", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*base.log", "repo__name-1_base.log"));
+        assert!(wildcard_match("*agent*.log", "agent_retry.log"));
+        assert!(wildcard_match("run_*_stdout.txt", "run_before_stdout.txt"));
+        assert!(!wildcard_match("*base.log", "base.log.txt"));
+        assert!(!wildcard_match("a*a", "a"));
+    }
+
+    #[test]
+    fn test_agent_log_discovery() {
+        let patterns = LogPatterns::default();
+        let file_paths: Vec<String> = [
+            "logs/repo__name-1_base.log",
+            "logs/repo__name-1_post_agent_patch_1.log",
+            "logs/agent_retry.log",
+            "logs/repo__name-1_post_agent_patch.log",
+            "logs/agent-tools__x-2_after.log",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let names: Vec<&str> = patterns.agent_logs(&file_paths).into_iter().map(|p| log_file_name(p)).collect();
+        assert_eq!(names, vec!["agent_retry.log", "repo__name-1_post_agent_patch.log", "repo__name-1_post_agent_patch_1.log"]);
+        assert_eq!(patterns.default_agent_log(&file_paths).map(|p| log_file_name(p)), Some("repo__name-1_post_agent_patch.log"));
+        assert_eq!(patterns.find("after", &file_paths).map(|p| log_file_name(p)), Some("agent-tools__x-2_after.log"));
+    }
+
+    #[test]
+    fn test_configured_patterns_replace_defaults() {
+        let config = LogDiscoveryConfig {
+            before: vec!["*_Pre_Patch.txt".to_string()],
+            ..Default::default()
+        };
+        let patterns = LogPatterns::from_config(&config);
+        let file_paths = vec!["logs/x_pre_patch.txt".to_string(), "logs/x_before.log".to_string()];
+
+        assert_eq!(patterns.find("before", &file_paths), Some(&file_paths[0]));
+        assert_eq!(patterns.base, vec!["*base.log", "*base.txt"]);
+    }
+}
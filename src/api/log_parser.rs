@@ -4,26 +4,93 @@ use std::fs;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::api::rust_log_parser::RustLogParser;
-use crate::api::python_log_parser::PythonLogParser;
-use crate::api::javascript_log_parser::JavaScriptLogParser;
+use crate::api::detection_rules::DetectionRules;
+use crate::api::environment_drift::{environment_drift, DRIFT_STAGES};
+use crate::api::filtered_run::stage_filter;
+use crate::api::analysis_progress::{ProgressReporter, PHASE_LOCATE, PHASE_REPORT, PHASE_RULES};
+use crate::api::analysis_sources::describe_source;
+use crate::api::javascript_log_parser::RetryPolicy;
+use crate::api::language_defaults::LanguageDefaults;
+use crate::api::log_discovery::{log_file_name, LogPatterns};
+use crate::api::log_preprocess::{read_language_log_file, read_log_file, read_log_file_with_stats, PreprocessStats};
+use crate::api::parser_registry::ParserRegistry;
+use crate::api::patch_apply::{detect_patch_failures, PATCHED_STAGES};
+use crate::api::patch_classes::{is_patch_file, patch_class};
+use crate::api::report_schema::{parse_report, ReportStatuses};
+use crate::api::run_abort::stage_abort;
+use crate::api::skip_reasons::extract_skip_reasons;
+use crate::api::status_overrides::apply_status_overrides;
+use crate::api::run_script::{find_run_script_hints, RUN_SCRIPT_FOLDER};
+use crate::api::server_config::{load_server_config, DuplicateDetectionConfig};
+use crate::api::p2p_breadth::{check_p2p_breadth, P2pBreadthThresholds};
+use crate::api::test_binaries::{binary_boundary, test_binary_runs};
 use crate::api::test_detection;
-use crate::app::types::{StageStatusSummary, GroupedTestStatuses, LogAnalysisResult, RuleViolations, RuleViolation, DebugInfo, LogCount};
+use crate::api::test_name::{canonical_js_separators, canonical_test_name, strip_js_test_file, JS_SUITE_SEPARATOR};
+use crate::api::test_timings::{extract_durations, runtime_regressions, slowest_tests};
+use crate::app::types::{ANALYSIS_SCHEMA_VERSION, NO_LOG_STATUS, StageStatusSummary, GroupedTestStatuses, LogAnalysisResult, RuleViolations, RuleViolation, DebugInfo, LogCount, AgentRunStatuses, XPassedTest, DuplicateTest, DuplicateOccurrence, DetectedDuplicate, StatusOverride, AnalysisSettings, BrowserRun, InferredPass, TestBinaryRun, ParseWarning, ParseWarningKind};
 
 
 
 // Trait for language-specific log parsers
 pub trait LogParserTrait {
-    fn parse_log_file(&self, file_path: &str) -> Result<ParsedLog, String>;
     fn get_language(&self) -> &'static str;
+
+    // Parse a log already run through `preprocess_language_log` for this language; `file_path` is
+    // where it was read from. `framework` is pinned by a run script or detection rule; parsers
+    // that can't use the hint ignore it
+    fn parse_content(&self, file_path: &str, content: &str, framework: Option<&str>) -> Result<ParsedLog, String>;
+
+    fn parse_log_file(&self, file_path: &str) -> Result<ParsedLog, String> {
+        self.parse_log_file_with_framework(file_path, None)
+    }
+
+    fn parse_log_file_with_framework(&self, file_path: &str, framework: Option<&str>) -> Result<ParsedLog, String> {
+        let content = read_language_log_file(file_path, self.get_language())?;
+        self.parse_content(file_path, &content, framework)
+    }
+}
+
+// A test file the runner only summarized, e.g. Vitest's `✓ src/foo.test.ts (12 tests)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollapsedSuite {
+    pub file: String,
+    // Tests the summary line counted
+    pub tests: usize,
 }
 
-#[derive(Debug, Clone)]
+// Result of one test line, as `ParsedLog::record` merges it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ParsedLog {
     pub passed: std::collections::HashSet<String>,
     pub failed: std::collections::HashSet<String>,
     pub ignored: std::collections::HashSet<String>,
     pub all: std::collections::HashSet<String>,
+    // Reason printed for ignored tests, filled in by `LogParser` after the language parser runs
+    pub skip_reasons: HashMap<String, String>,
+    // Tests marked xfail that passed; strict ones are also in `failed`, the rest in `passed`
+    pub xpassed: std::collections::HashSet<String>,
+    // Status of every attempt, in order, for tests retried by a rerun plugin
+    pub attempts: HashMap<String, Vec<String>>,
+    // Runtime in milliseconds for tests the runner printed a duration for
+    pub durations: HashMap<String, f64>,
+    // Parser/framework the language parser settled on
+    pub parser: Option<String>,
+    // Oddities noticed while parsing: notes from the language parser, then what `LogParser` finds
+    // about skipped or merged lines; `stage` is set when the result is assembled
+    pub warnings: Vec<ParseWarning>,
+    // Per-browser (Karma) or per-project (Playwright) results; `stage` is set when the result is assembled
+    pub browser_runs: Vec<BrowserRun>,
+    // Test files the runner only summarized
+    pub collapsed_suites: Vec<CollapsedSuite>,
+    // Listed tests passed on the strength of a collapsed suite; `stage` is set when the result is assembled
+    pub inferred_passes: Vec<InferredPass>,
 }
 
 impl ParsedLog {
@@ -33,6 +100,49 @@ impl ParsedLog {
             failed: std::collections::HashSet::new(),
             ignored: std::collections::HashSet::new(),
             all: std::collections::HashSet::new(),
+            skip_reasons: HashMap::new(),
+            xpassed: std::collections::HashSet::new(),
+            attempts: HashMap::new(),
+            durations: HashMap::new(),
+            parser: None,
+            warnings: Vec::new(),
+            browser_runs: Vec::new(),
+            collapsed_suites: Vec::new(),
+            inferred_passes: Vec::new(),
+        }
+    }
+
+    // A note from the language parser, shown with the log's counts in the Debug tab
+    pub fn note(&mut self, message: String) {
+        self.warnings.push(ParseWarning { stage: String::new(), kind: ParseWarningKind::ParserNote, message, count: 1, examples: vec![] });
+    }
+
+    pub fn with_parser(mut self, parser: &str) -> Self {
+        self.parser = Some(parser.to_string());
+        self
+    }
+
+    // Merge one result for a test that may be reported more than once (several packages,
+    // binaries or reruns): a failure wins over a pass, a skip only counts when nothing else does
+    pub fn record(&mut self, name: impl Into<String>, outcome: TestOutcome) {
+        let name = name.into();
+        match outcome {
+            TestOutcome::Passed => {
+                if !self.failed.contains(&name) {
+                    self.ignored.remove(&name);
+                    self.passed.insert(name);
+                }
+            }
+            TestOutcome::Failed => {
+                self.passed.remove(&name);
+                self.ignored.remove(&name);
+                self.failed.insert(name);
+            }
+            TestOutcome::Ignored => {
+                if !self.passed.contains(&name) && !self.failed.contains(&name) {
+                    self.ignored.insert(name);
+                }
+            }
         }
     }
 
@@ -45,26 +155,33 @@ impl ParsedLog {
 
 // Main log checker that coordinates between different language parsers
 pub struct LogParser {
-    parsers: HashMap<String, Box<dyn LogParserTrait + Send + Sync>>,
+    parsers: ParserRegistry,
+    // Reviewer overrides applied to the stage statuses before the rule checks
+    status_overrides: Vec<StatusOverride>,
+    // Reviewer classes of patch files by path from `patches/`, used by C7 over its file-name guess
+    patch_classes: HashMap<String, String>,
+    progress: ProgressReporter,
 }
 
 impl LogParser {
     pub fn new() -> Self {
-        let mut parsers: HashMap<String, Box<dyn LogParserTrait + Send + Sync>> = HashMap::new();
-        
-        // Register Rust parser
-        parsers.insert("rust".to_string(), Box::new(RustLogParser::new()));
-        
-        // Register Python parser
-        parsers.insert("python".to_string(), Box::new(PythonLogParser::new()));
-        
-        // Register JavaScript/TypeScript parsers
-        parsers.insert("javascript".to_string(), Box::new(JavaScriptLogParser::new()));
-        parsers.insert("typescript".to_string(), Box::new(JavaScriptLogParser::new()));
-        parsers.insert("js".to_string(), Box::new(JavaScriptLogParser::new()));
-        parsers.insert("ts".to_string(), Box::new(JavaScriptLogParser::new()));
-        
-        Self { parsers }
+        let parsers = ParserRegistry::from_config(&load_server_config().parsers);
+        Self { parsers, status_overrides: Vec::new(), patch_classes: HashMap::new(), progress: ProgressReporter::default() }
+    }
+
+    pub fn with_status_overrides(mut self, status_overrides: Vec<StatusOverride>) -> Self {
+        self.status_overrides = status_overrides;
+        self
+    }
+
+    pub fn with_patch_classes(mut self, patch_classes: HashMap<String, String>) -> Self {
+        self.patch_classes = patch_classes;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
     }
 
     pub fn analyze_logs(
@@ -73,6 +190,7 @@ impl LogParser {
         language: &str,
         fail_to_pass_tests: &[String],
         pass_to_pass_tests: &[String],
+        selected_agent_log: Option<&str>,
     ) -> Result<LogAnalysisResult, String> {
         println!("=== LOG CHECKER DEBUG ===");
         println!("Language: {}", language);
@@ -80,18 +198,23 @@ impl LogParser {
         println!("Fail to pass tests: {} tests", fail_to_pass_tests.len());
         println!("Pass to pass tests: {} tests", pass_to_pass_tests.len());
         
-        // Get the appropriate parser for the language
-        let parser = self.parsers.get(language)
-            .ok_or_else(|| format!("No parser available for language: {}", language))?;
-
         // Find log files
-        let base_log = file_paths.iter().find(|path| path.to_lowercase().contains("base.log"));
-        let before_log = file_paths.iter().find(|path| path.to_lowercase().contains("before.log"));
-        let after_log = file_paths.iter().find(|path| path.to_lowercase().contains("after.log"));
-        let agent_log = file_paths.iter().find(|path| 
-            path.to_lowercase().contains("post_agent_patch.log") || 
-            path.to_lowercase().contains("agent.log")
-        );
+        self.progress.begin(PHASE_LOCATE);
+        let patterns = LogPatterns::load();
+        let base_log = patterns.find("base", file_paths);
+        let before_log = patterns.find("before", file_paths);
+        let after_log = patterns.find("after", file_paths);
+        let agent_logs = patterns.agent_logs(file_paths);
+        let agent_log = match selected_agent_log {
+            Some(name) => Some(
+                agent_logs
+                    .iter()
+                    .find(|path| log_file_name(path) == name)
+                    .copied()
+                    .ok_or_else(|| format!("Agent log not found: {}", name))?,
+            ),
+            None => patterns.default_agent_log(file_paths),
+        };
 
         println!("Found log files:");
         println!("  Base log: {:?}", base_log);
@@ -99,67 +222,289 @@ impl LogParser {
         println!("  After log: {:?}", after_log);
         println!("  Agent log: {:?}", agent_log);
 
-        if base_log.is_none() || before_log.is_none() || after_log.is_none() {
-            return Err("Missing required log files (base.log, before.log, after.log)".to_string());
+        // Any stage may be absent; rules that need it are reported as not evaluated
+        for (stage, log) in [("base", base_log), ("before", before_log), ("after", after_log)] {
+            if log.is_none() {
+                println!("No {} log found (patterns: {})", stage, patterns.stage_patterns(stage).join(" | "));
+            }
+        }
+        if base_log.is_none() && before_log.is_none() && after_log.is_none() && agent_log.is_none() {
+            return Err(format!(
+                "No stage logs found (base: {}, before: {}, after: {})",
+                patterns.base.join(" | "), patterns.before.join(" | "), patterns.after.join(" | ")
+            ));
+        }
+
+        // Parser registered for the language, else the first whose sniff accepts a stage log
+        let (plugin, parser) = match self.parsers.plugin_for_language(language) {
+            Some(found) => found,
+            None => {
+                let detected = [base_log, before_log, after_log, agent_log]
+                    .into_iter()
+                    .flatten()
+                    .find_map(|path| read_log_file(path).ok().and_then(|content| self.parsers.detect(&content)));
+                let (plugin, parser) = detected.ok_or_else(|| format!("No parser available for language: {}", language))?;
+                println!("No parser registered for language '{}'; detected '{}' from the logs", language, plugin.name);
+                (plugin, parser)
+            }
+        };
+
+        // Pin the framework from a run script when the deliverable ships one
+        let run_script = find_run_script_hints(file_paths);
+        if let Some(ref hints) = run_script {
+            println!("Run script {} pins framework '{}' (command: {}, working dir: {:?})",
+                     hints.source_file, hints.framework, hints.command, hints.working_dir);
         }
+        let framework = run_script.as_ref().map(|h| h.framework.as_str());
+        self.progress.done(PHASE_LOCATE);
+
+        // Otherwise let operator-configured detection rules pick the parser per log
+        let detection_rules = DetectionRules::from_config(&load_server_config().parsers);
+        let parse_stage = |path: &str| -> Result<ParsedLog, String> {
+            // Read and preprocessed once; the parser, the rules and the warnings all use this copy
+            let (content, stats) = read_log_file_with_stats(path, Some(parser.get_language()))?;
+            let framework = match framework {
+                Some(name) => Some(name.to_string()),
+                None if !detection_rules.is_empty() => detection_rules.match_parser(language, &content),
+                None => None,
+            };
+            // A pin the parser can't honor falls back to its own format detection
+            let framework = framework.filter(|name| {
+                let accepted = plugin.accepts_framework(name);
+                if !accepted {
+                    println!("The {} parser can't pin framework '{}'; detecting the format of {} instead", plugin.name, name, path);
+                }
+                accepted
+            });
+            if let Some(ref name) = framework {
+                println!("Using framework '{}' for {}", name, path);
+            }
+            let mut parsed = parser.parse_content(path, &content, framework.as_deref())?;
+            if !parsed.ignored.is_empty() {
+                parsed.skip_reasons = extract_skip_reasons(&content, &parsed.ignored);
+            }
+            parsed.durations = extract_durations(&content, &parsed.all);
+            let warnings = parse_warnings(&parsed, &content, stats);
+            parsed.warnings.extend(warnings);
+            if !parsed.collapsed_suites.is_empty() {
+                let listed: Vec<String> = fail_to_pass_tests.iter().chain(pass_to_pass_tests).cloned().collect();
+                expand_collapsed_suites(&mut parsed, &listed);
+            }
+            Ok(parsed)
+        };
 
         // Parse log files
         println!("Parsing log files...");
-        let base_parsed = parser.parse_log_file(base_log.unwrap())?;
-        println!("Base log parsed: {} passed, {} failed, {} ignored, {} total", 
-                 base_parsed.passed.len(), base_parsed.failed.len(), 
-                 base_parsed.ignored.len(), base_parsed.all.len());
-        
-        let before_parsed = parser.parse_log_file(before_log.unwrap())?;
-        println!("Before log parsed: {} passed, {} failed, {} ignored, {} total", 
-                 before_parsed.passed.len(), before_parsed.failed.len(), 
-                 before_parsed.ignored.len(), before_parsed.all.len());
-        
-        let after_parsed = parser.parse_log_file(after_log.unwrap())?;
-        println!("After log parsed: {} passed, {} failed, {} ignored, {} total", 
-                 after_parsed.passed.len(), after_parsed.failed.len(), 
-                 after_parsed.ignored.len(), after_parsed.all.len());
+        let parse_optional_stage = |stage: &str, label: &str, path: Option<&String>| -> Result<Option<ParsedLog>, String> {
+            let Some(path) = path else {
+                self.progress.skip(stage);
+                return Ok(None);
+            };
+            self.progress.begin(stage);
+            let parsed = parse_stage(path)?;
+            println!("{} log parsed: {} passed, {} failed, {} ignored, {} total", label,
+                     parsed.passed.len(), parsed.failed.len(),
+                     parsed.ignored.len(), parsed.all.len());
+            self.progress.done(stage);
+            Ok(Some(parsed))
+        };
+        let base_parsed = parse_optional_stage("base", "Base", base_log)?;
+        let before_parsed = parse_optional_stage("before", "Before", before_log)?;
+        let after_parsed = parse_optional_stage("after", "After", after_log)?;
         
         let agent_parsed = if let Some(agent_path) = agent_log {
-            let parsed = parser.parse_log_file(agent_path)?;
+            self.progress.begin("agent");
+            let parsed = parse_stage(agent_path)?;
             println!("Agent log parsed: {} passed, {} failed, {} ignored, {} total", 
                      parsed.passed.len(), parsed.failed.len(), 
                      parsed.ignored.len(), parsed.all.len());
             Some(parsed)
         } else {
             println!("No agent log found");
+            self.progress.skip("agent");
             None
         };
 
+        // Per-run statuses so the reviewer can compare several agent attempts side by side
+        let language_defaults = LanguageDefaults::load(language);
+        let mut agent_runs = Vec::new();
+        let mut agent_run_sources = Vec::new();
+        if agent_logs.len() > 1 {
+            let universe: Vec<String> = pass_to_pass_tests.iter()
+                .chain(fail_to_pass_tests.iter())
+                .cloned()
+                .collect();
+            for path in &agent_logs {
+                let parsed = if Some(*path) == agent_log {
+                    agent_parsed.clone()
+                } else {
+                    parse_stage(path)
+                        .map_err(|e| eprintln!("Failed to parse agent log {}: {}", path, e))
+                        .ok()
+                };
+                if let Some(parsed) = parsed {
+                    if Some(*path) != agent_log {
+                        agent_run_sources.extend(describe_source("agent_run", path, parsed.parser.as_deref()));
+                    }
+                    agent_runs.push(AgentRunStatuses {
+                        log_name: log_file_name(path).to_string(),
+                        statuses: self.status_lookup(&universe, &parsed, &language_defaults),
+                    });
+                }
+            }
+        }
+        if agent_log.is_some() {
+            self.progress.done("agent");
+        }
+
         // Find and parse report.json if available
+        self.progress.begin(PHASE_REPORT);
         let report_data = self.find_and_parse_report(file_paths)?;
+        self.progress.done(PHASE_REPORT);
 
         // Generate analysis result
-        let analysis_result = self.generate_analysis_result(
-            &base_parsed,
-            &before_parsed,
-            &after_parsed,
+        self.progress.begin(PHASE_RULES);
+        let mut analysis_result = self.generate_analysis_result(
+            base_parsed.as_ref().zip(base_log.map(String::as_str)),
+            before_parsed.as_ref().zip(before_log.map(String::as_str)),
+            after_parsed.as_ref().zip(after_log.map(String::as_str)),
             agent_parsed.as_ref(),
             fail_to_pass_tests,
             pass_to_pass_tests,
-            base_log.unwrap(),
-            before_log.unwrap(),
-            after_log.unwrap(),
             report_data.as_ref(),
             file_paths,
             language,
         );
 
+        analysis_result.agent_runs.extend(agent_runs);
+        // Flag stages the infrastructure killed or a test filter narrowed so their gaps aren't
+        // blamed on the patch, stages that ran against a patch that never applied (C9), and
+        // tool versions that changed between stages
+        let mut patch_failures: Vec<String> = vec![];
+        let mut drift_logs: Vec<(&str, String)> = vec![];
+        for (stage, path) in [("base", base_log), ("before", before_log), ("after", after_log), ("agent", agent_log)] {
+            let Some(path) = path else { continue };
+            let Ok(content) = read_language_log_file(path, parser.get_language()) else { continue };
+            if let Some(abort) = stage_abort(stage, &content) {
+                println!("{} run aborted ({}): {}", stage, abort.reason, abort.evidence);
+                analysis_result.debug_info.aborted_stages.push(abort);
+            }
+            let missing_tests = analysis_result.test_statuses.f2p.values()
+                .chain(analysis_result.test_statuses.p2p.values())
+                .filter(|summary| summary.status_for(stage) == Some("missing"))
+                .count();
+            if let Some(filter) = stage_filter(stage, &content, missing_tests) {
+                println!("{} run filtered ({} listed tests missing): {}", stage, filter.missing_tests, filter.evidence);
+                analysis_result.debug_info.filtered_stages.push(filter);
+            }
+            analysis_result.debug_info.test_binaries.extend(
+                test_binary_runs(&content).into_iter().map(|binary| TestBinaryRun { stage: stage.to_string(), ..binary })
+            );
+            if PATCHED_STAGES.contains(&stage) {
+                patch_failures.extend(detect_patch_failures(stage, &content));
+            }
+            if DRIFT_STAGES.contains(&stage) {
+                drift_logs.push((stage, content));
+            }
+        }
+        let drift_logs: Vec<(&str, &str)> = drift_logs.iter().map(|(stage, content)| (*stage, content.as_str())).collect();
+        analysis_result.debug_info.environment_drift = environment_drift(&drift_logs);
+        for drift in &analysis_result.debug_info.environment_drift {
+            println!("Environment drift in {}: {:?}", drift.tool, drift.versions);
+        }
+        println!("C9 check: {} patch application failures", patch_failures.len());
+        analysis_result.rule_violations.c9_patch_failed_to_apply = RuleViolation {
+            has_problem: !patch_failures.is_empty(),
+            examples: patch_failures,
+        };
+        analysis_result.rules_evaluated.push("C9".to_string());
+        // C10: a P2P list covering a sliver of the base suite may hide regressions
+        let breadth = P2pBreadthThresholds::from_config(&load_server_config().rule_checks.p2p_breadth);
+        let base_test_count = base_parsed.as_ref().map(|parsed| parsed.all.len()).unwrap_or(0);
+        let narrow_p2p = check_p2p_breadth(pass_to_pass_tests.len(), base_test_count, &breadth);
+        println!("C10 check: {} P2P tests of {} in base", pass_to_pass_tests.len(), base_test_count);
+        analysis_result.rule_violations.c10_p2p_list_too_narrow = RuleViolation {
+            has_problem: narrow_p2p.is_some(),
+            examples: narrow_p2p.into_iter().collect(),
+        };
+        if breadth.min_percent > 0.0 {
+            analysis_result.rules_evaluated.push("C10".to_string());
+        }
+        // Inputs, so an exported result can be traced back to the files it came from
+        for (role, path, parsed) in [
+            ("base", base_log, base_parsed.as_ref()),
+            ("before", before_log, before_parsed.as_ref()),
+            ("after", after_log, after_parsed.as_ref()),
+            ("agent", agent_log, agent_parsed.as_ref()),
+        ] {
+            if let Some(path) = path {
+                analysis_result.sources.extend(describe_source(role, path, parsed.and_then(|parsed| parsed.parser.as_deref())));
+            }
+        }
+        analysis_result.sources.extend(agent_run_sources);
+        if let Some(path) = Self::report_json_path(file_paths) {
+            analysis_result.sources.extend(describe_source("report", path, analysis_result.debug_info.report_schema.as_deref()));
+        }
+        let run_script_path = run_script.as_ref().and_then(|hints| {
+            let suffix = format!("{}/{}", RUN_SCRIPT_FOLDER, hints.source_file);
+            file_paths.iter().find(|path| path.replace('\\', "/").ends_with(&suffix))
+        });
+        if let Some(path) = run_script_path {
+            analysis_result.sources.extend(describe_source("run_script", path, None));
+        }
+        analysis_result.debug_info.run_script = run_script;
+        analysis_result.debug_info.agent_log = agent_log.map(|path| log_file_name(path).to_string());
+        analysis_result.settings = Some(self.analysis_settings(language, parser.get_language(), &analysis_result.debug_info, &detection_rules));
+        self.progress.done(PHASE_RULES);
+
         Ok(analysis_result)
     }
 
-    fn find_and_parse_report(&self, file_paths: &[String]) -> Result<Option<serde_json::Value>, String> {
-        let report_json_path = file_paths.iter().find(|path| 
+    // Options in effect for this run; `log_analysis` adds the reviewer's custom test lists
+    fn analysis_settings(&self, language: &str, parser: &str, debug_info: &DebugInfo, detection_rules: &DetectionRules) -> AnalysisSettings {
+        let config = load_server_config();
+        let heuristics = DuplicateHeuristics::from_config(&config.rule_checks.duplicates);
+        let breadth = P2pBreadthThresholds::from_config(&config.rule_checks.p2p_breadth);
+        let language_defaults = LanguageDefaults::from_config(&config, language);
+        AnalysisSettings {
+            language: language.to_string(),
+            parser: parser.to_string(),
+            pinned_framework: debug_info.run_script.as_ref().map(|hints| hints.framework.clone()),
+            detection_rules_file: detection_rules.source().map(str::to_string),
+            agent_log: debug_info.agent_log.clone(),
+            custom_test_lists: None,
+            duplicate_min_distance: heuristics.min_distance,
+            duplicate_context_window: heuristics.context_window,
+            duplicate_require_status_conflict: heuristics.require_status_conflict,
+            p2p_breadth_min_percent: breadth.min_percent,
+            p2p_breadth_min_base_tests: breadth.min_base_tests,
+            noise_filter_disabled: config.noise_filter.disabled,
+            noise_patterns: config.noise_filter.patterns,
+            name_rewrites: language_defaults.describe_name_rewrites(),
+            search_context: language_defaults.search_context,
+            patch_classes: self.patch_classes.clone(),
+            language_noise_patterns: language_defaults.noise_patterns,
+            playwright_retry_policy: RetryPolicy::from_config(&config.parsers).name().to_string(),
+            jest_file_names: config.parsers.jest_file_names,
+            experimental_parsers: config.parsers.experimental,
+            disabled_parsers: config.parsers.disabled,
+            analyzed_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    fn report_json_path(file_paths: &[String]) -> Option<&String> {
+        file_paths.iter().find(|path| 
             path.to_lowercase().contains("results/report.json") || 
             path.to_lowercase().ends_with("report.json")
-        );
+        )
+    }
 
-        if let Some(report_path) = report_json_path {
+    fn find_and_parse_report(&self, file_paths: &[String]) -> Result<Option<serde_json::Value>, String> {
+        if let Some(report_path) = Self::report_json_path(file_paths) {
             match fs::read_to_string(report_path) {
                 Ok(content) => {
                     match serde_json::from_str::<serde_json::Value>(&content) {
@@ -180,17 +525,15 @@ impl LogParser {
         }
     }
 
+    // Each stage is its parsed log plus the file it came from, or None when the deliverable lacks it
     fn generate_analysis_result(
         &self,
-        base_parsed: &ParsedLog,
-        before_parsed: &ParsedLog,
-        after_parsed: &ParsedLog,
+        base: Option<(&ParsedLog, &str)>,
+        before: Option<(&ParsedLog, &str)>,
+        after: Option<(&ParsedLog, &str)>,
         agent_parsed: Option<&ParsedLog>,
         fail_to_pass_tests: &[String],
         pass_to_pass_tests: &[String],
-        base_path: &str,
-        before_path: &str,
-        after_path: &str,
         report_data: Option<&serde_json::Value>,
         file_paths: &[String],
         language: &str,
@@ -199,28 +542,44 @@ impl LogParser {
             .chain(fail_to_pass_tests.iter())
             .cloned()
             .collect();
+        let language_defaults = LanguageDefaults::load(language);
 
-        let base_s = self.status_lookup(&universe, base_parsed);
-        let before_s = self.status_lookup(&universe, before_parsed);
-        let after_s = self.status_lookup(&universe, after_parsed);
-        let agent_s = if let Some(agent_parsed) = agent_parsed {
-            self.status_lookup(&universe, agent_parsed)
+        let stage_statuses = |stage: Option<(&ParsedLog, &str)>| -> HashMap<String, String> {
+            match stage {
+                Some((parsed, _)) => self.status_lookup(&universe, parsed, &language_defaults),
+                None => universe.iter().map(|name| (name.clone(), NO_LOG_STATUS.to_string())).collect(),
+            }
+        };
+        let mut base_s = stage_statuses(base);
+        let mut before_s = stage_statuses(before);
+        let mut after_s = stage_statuses(after);
+        let mut agent_s = if let Some(agent_parsed) = agent_parsed {
+            self.status_lookup(&universe, agent_parsed, &language_defaults)
         } else {
             HashMap::new()
         };
+        for (stage, statuses) in [("base", &mut base_s), ("before", &mut before_s), ("after", &mut after_s), ("agent", &mut agent_s)] {
+            apply_status_overrides(statuses, stage, &self.status_overrides, &universe);
+        }
+        let overrides: Vec<StatusOverride> = self.status_overrides
+            .iter()
+            .filter(|o| universe.contains(&o.test_name))
+            .cloned()
+            .collect();
 
-        let report_s = if let Some(report_data) = report_data {
-            self.report_status_lookup(&universe, report_data)
-        } else {
-            HashMap::new()
+        let report = report_data.map(parse_report);
+        let parsed_report = report.as_ref().and_then(|r| r.as_ref().ok());
+        let report_s: HashMap<String, String> = match parsed_report {
+            Some(parsed) => universe.iter().map(|name| (name.clone(), parsed.status_of(name).to_string())).collect(),
+            None => HashMap::new(),
         };
 
         // Rule checks
-        let (rule_violations, dup_map) = self.perform_rule_checks(
+        let (rule_violations, dup_map, duplicates) = self.perform_rule_checks(
             &base_s, &before_s, &after_s, &agent_s, &report_s,
             fail_to_pass_tests, pass_to_pass_tests,
-            base_path, before_path, after_path, file_paths,
-            report_data, language
+            base.map(|(_, path)| path), before.map(|(_, path)| path), after.map(|(_, path)| path), file_paths,
+            parsed_report, language
         );
 
         // Build grouped test statuses structure
@@ -249,55 +608,123 @@ impl LogParser {
             p2p.insert(test_name.clone(), summary);
         }
 
-        // Debug info with all stages
-        let mut log_counts = vec![
-            LogCount {
-                label: "base".to_string(),
-                passed: base_parsed.passed.len(),
-                failed: base_parsed.failed.len(),
-                ignored: base_parsed.ignored.len(),
-                all: base_parsed.all.len(),
-            },
-            LogCount {
-                label: "before".to_string(),
-                passed: before_parsed.passed.len(),
-                failed: before_parsed.failed.len(),
-                ignored: before_parsed.ignored.len(),
-                all: before_parsed.all.len(),
-            },
-            LogCount {
-                label: "after".to_string(),
-                passed: after_parsed.passed.len(),
-                failed: after_parsed.failed.len(),
-                ignored: after_parsed.ignored.len(),
-                all: after_parsed.all.len(),
-            },
+        // Debug info for every stage that has a log
+        let mut log_counts = Vec::new();
+        let mut missing_stages = Vec::new();
+        let stages = [
+            ("base", base.map(|(parsed, _)| parsed)),
+            ("before", before.map(|(parsed, _)| parsed)),
+            ("after", after.map(|(parsed, _)| parsed)),
+            ("agent", agent_parsed),
         ];
-        
-        // Add agent log count if available
-        if let Some(agent_parsed) = agent_parsed {
-            log_counts.push(LogCount {
-                label: "agent".to_string(),
-                passed: agent_parsed.passed.len(),
-                failed: agent_parsed.failed.len(),
-                ignored: agent_parsed.ignored.len(),
-                all: agent_parsed.all.len(),
-            });
+
+        // Skip reasons for listed tests, keyed by stage
+        let mut skip_reasons: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut attempts: HashMap<String, HashMap<String, Vec<String>>> = HashMap::new();
+        let mut xpassed = Vec::new();
+        let mut slowest = Vec::new();
+        for (label, parsed) in stages {
+            let Some(parsed) = parsed else { continue };
+            for name in &universe {
+                if let Some(reason) = parsed.skip_reasons.get(name) {
+                    skip_reasons.entry(name.clone()).or_default().insert(label.to_string(), reason.clone());
+                }
+                if let Some(history) = parsed.attempts.get(name) {
+                    attempts.entry(name.clone()).or_default().insert(label.to_string(), history.clone());
+                }
+            }
+            let mut stage_xpassed: Vec<&String> = parsed.xpassed.iter().collect();
+            stage_xpassed.sort();
+            xpassed.extend(stage_xpassed.into_iter().map(|name| XPassedTest {
+                stage: label.to_string(),
+                test_name: name.clone(),
+                strict: parsed.failed.contains(name),
+            }));
+            slowest.extend(slowest_tests(label, &parsed.durations));
+        }
+        let regressions = match (stages[1].1, stages[2].1) {
+            (Some(before), Some(after)) => runtime_regressions(&before.durations, &after.durations),
+            _ => vec![],
+        };
+
+        let mut parse_warnings = Vec::new();
+        let mut browser_runs = Vec::new();
+        let mut inferred_passes = Vec::new();
+        for (label, parsed) in stages {
+            if let Some(parsed) = parsed {
+                parse_warnings.extend(parsed.warnings.iter().cloned().map(|warning| ParseWarning { stage: label.to_string(), ..warning }));
+                browser_runs.extend(parsed.browser_runs.iter().cloned().map(|run| BrowserRun { stage: label.to_string(), ..run }));
+                inferred_passes.extend(parsed.inferred_passes.iter().cloned().map(|inferred| InferredPass { stage: label.to_string(), ..inferred }));
+            }
+            match parsed {
+                Some(parsed) => log_counts.push(LogCount {
+                    label: label.to_string(),
+                    passed: parsed.passed.len(),
+                    failed: parsed.failed.len(),
+                    ignored: parsed.ignored.len(),
+                    all: parsed.all.len(),
+                    parser: parsed.parser.clone(),
+                    warnings: parsed.warnings.iter().filter(|warning| warning.kind.is_log_note()).map(|warning| warning.message.clone()).collect(),
+                }),
+                // The agent log has always been optional
+                None if label != "agent" => missing_stages.push(label.to_string()),
+                None => {}
+            }
         }
 
         let debug_info = DebugInfo {
             log_counts,
             duplicate_examples_per_log: dup_map,
+            duplicates,
+            run_script: None,
+            agent_log: None,
+            missing_stages,
+            report_schema: match &report {
+                Some(Ok(parsed)) => Some(parsed.schema.clone()),
+                _ => None,
+            },
+            report_diagnostic: match report {
+                Some(Err(diagnostic)) => Some(diagnostic),
+                _ => None,
+            },
+            aborted_stages: vec![],
+            filtered_stages: vec![],
+            xpassed,
+            slowest_tests: slowest,
+            runtime_regressions: regressions,
+            parse_warnings,
+            browser_runs,
+            inferred_passes,
+            test_binaries: vec![],
+            environment_drift: vec![],
         };
 
+        let test_statuses = GroupedTestStatuses { f2p, p2p };
         LogAnalysisResult {
-            test_statuses: GroupedTestStatuses { f2p, p2p },
+            schema_version: ANALYSIS_SCHEMA_VERSION,
+            // C8-C10 are added by the callers that run them
+            rules_evaluated: ["C1", "C2", "C3", "C4", "C5", "C6", "C7", "C11"].iter().map(|code| code.to_string()).collect(),
+            match_rates: test_statuses.match_rates(),
+            test_statuses,
             rule_violations,
             debug_info,
+            // Filled in from coverage reports by `log_analysis::analyze_logs`
+            coverage: None,
+            agent_runs: vec![],
+            skip_reasons,
+            attempts,
+            overrides,
+            // Filled in by `analyze_logs`
+            settings: None,
+            // Carried over by `analysis_store::save_analysis_result`
+            review_effort: None,
+            // Filled in by `analyze_logs` and `log_analysis::analyze_logs`
+            sources: vec![],
+            test_list_provenance: None,
         }
     }
 
-    fn status_lookup(&self, names: &[String], parsed: &ParsedLog) -> HashMap<String, String> {
+    fn status_lookup(&self, names: &[String], parsed: &ParsedLog, language_defaults: &LanguageDefaults) -> HashMap<String, String> {
         let mut out = HashMap::new();
         
         println!("=== STATUS LOOKUP DEBUG ===");
@@ -336,14 +763,64 @@ impl LogParser {
             }
         }
         
+        // Fall back to canonical names when main.json and the log spell a test differently
+        let canonical = |set: &std::collections::HashSet<String>| -> std::collections::HashSet<String> {
+            set.iter().map(|name| canonical_test_name(name)).collect()
+        };
+        let (failed_c, passed_c, ignored_c) = (canonical(&parsed.failed), canonical(&parsed.passed), canonical(&parsed.ignored));
+        // Last resort for JS names joined with ` - ` or `::` on one side and ` > ` on the other
+        let joined = |set: &std::collections::HashSet<String>| -> std::collections::HashSet<String> {
+            set.iter().map(|name| canonical_js_separators(name)).collect()
+        };
+        let (failed_j, passed_j, ignored_j) = (joined(&failed_c), joined(&passed_c), joined(&ignored_c));
+        // Jest names with or without their `file.test.ts::` prefix. A title is only matched across
+        // the two forms when one side names no file; the same title in two files stays two tests
+        let mut filed_titles: HashMap<String, usize> = HashMap::new();
+        for name in failed_j.iter().chain(&passed_j).chain(&ignored_j) {
+            let title = strip_js_test_file(name);
+            if title != name {
+                *filed_titles.entry(title.to_string()).or_default() += 1;
+            }
+        }
+        // Titles of names parsed without a file, for expected names that carry one
+        let bare = |set: &std::collections::HashSet<String>| -> std::collections::HashSet<String> {
+            set.iter().filter(|name| strip_js_test_file(name) == name.as_str()).cloned().collect()
+        };
+        let (failed_f, passed_f, ignored_f) = (bare(&failed_j), bare(&passed_j), bare(&ignored_j));
+        // Titles parsed under exactly one file, for expected names that carry none
+        let filed = |set: &std::collections::HashSet<String>| -> std::collections::HashSet<String> {
+            set.iter()
+                .map(|name| strip_js_test_file(name))
+                .filter(|title| filed_titles.get(*title) == Some(&1))
+                .map(str::to_string)
+                .collect()
+        };
+        let (failed_u, passed_u, ignored_u) = (filed(&failed_j), filed(&passed_j), filed(&ignored_j));
+        // The language's configured name rewrites, over the canonical names
+        let rewritten = |set: &std::collections::HashSet<String>| -> std::collections::HashSet<String> {
+            if !language_defaults.has_name_rewrites() {
+                return std::collections::HashSet::new();
+            }
+            set.iter().map(|name| language_defaults.rewrite_name(name)).collect()
+        };
+        let (failed_r, passed_r, ignored_r) = (rewritten(&failed_c), rewritten(&passed_c), rewritten(&ignored_c));
+
         for name in names {
-            if parsed.failed.contains(name) {
+            let name_c = canonical_test_name(name);
+            let name_j = canonical_js_separators(&name_c);
+            let name_f = strip_js_test_file(&name_j).to_string();
+            // The other name form of this test: a file-free title against either set
+            let fileless_in = |bare: &std::collections::HashSet<String>, filed: &std::collections::HashSet<String>| {
+                if name_f != name_j { bare.contains(&name_f) } else { filed.contains(&name_f) }
+            };
+            let name_r = language_defaults.rewrite_name(&name_c);
+            if parsed.failed.contains(name) || failed_c.contains(&name_c) || failed_j.contains(&name_j) || fileless_in(&failed_f, &failed_u) || failed_r.contains(&name_r) {
                 println!("MATCH: '{}' found in FAILED", name);
                 out.insert(name.clone(), "failed".to_string());
-            } else if parsed.passed.contains(name) {
+            } else if parsed.passed.contains(name) || passed_c.contains(&name_c) || passed_j.contains(&name_j) || fileless_in(&passed_f, &passed_u) || passed_r.contains(&name_r) {
                 println!("MATCH: '{}' found in PASSED", name);
                 out.insert(name.clone(), "passed".to_string());
-            } else if parsed.ignored.contains(name) {
+            } else if parsed.ignored.contains(name) || ignored_c.contains(&name_c) || ignored_j.contains(&name_j) || fileless_in(&ignored_f, &ignored_u) || ignored_r.contains(&name_r) {
                 println!("MATCH: '{}' found in IGNORED", name);
                 out.insert(name.clone(), "ignored".to_string());
             } else {
@@ -355,104 +832,6 @@ impl LogParser {
         out
     }
 
-    fn report_status_lookup(&self, names: &[String], report_data: &serde_json::Value) -> HashMap<String, String> {
-        let mut out = HashMap::new();
-        let mut report_failed_tests = std::collections::HashSet::new();
-        let mut report_passed_tests = std::collections::HashSet::new();
-        
-        // Parse report.json to extract test results using the same logic as C6 check
-        // Try different possible structures for report.json
-        if let Some(results_array) = report_data.get("results").and_then(|r| r.as_array()) {
-            for result in results_array {
-                if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
-                    match status.to_lowercase().as_str() {
-                        "failed" | "fail" => { report_failed_tests.insert(test_name.to_string()); }
-                        "passed" | "pass" | "success" => { report_passed_tests.insert(test_name.to_string()); }
-                        _ => {}
-                    }
-                }
-            }
-        } else if let Some(test_results) = report_data.get("test_results").and_then(|r| r.as_array()) {
-            for result in test_results {
-                if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
-                    match status.to_lowercase().as_str() {
-                        "failed" | "fail" => { report_failed_tests.insert(test_name.to_string()); }
-                        "passed" | "pass" | "success" => { report_passed_tests.insert(test_name.to_string()); }
-                        _ => {}
-                    }
-                }
-            }
-        } else if let Some(tests_obj) = report_data.get("tests").and_then(|t| t.as_object()) {
-            // Format: {"tests": {"test_name": {"status": "failed"}}}
-            for (test_name, test_data) in tests_obj {
-                if let Some(status) = test_data.get("status").and_then(|s| s.as_str()) {
-                    match status.to_lowercase().as_str() {
-                        "failed" | "fail" => { report_failed_tests.insert(test_name.clone()); }
-                        "passed" | "pass" | "success" => { report_passed_tests.insert(test_name.clone()); }
-                        _ => {}
-                    }
-                }
-            }
-        } else if let Some(obj) = report_data.as_object() {
-            // Check for SWE-bench format first
-            let mut found_swe_format = false;
-            for (_key, value) in obj {
-                if let Some(tests_status) = value.get("tests_status").and_then(|t| t.as_object()) {
-                    found_swe_format = true;
-                    
-                    // Parse all test categories
-                    for (_category, category_data) in tests_status {
-                        if let Some(category_obj) = category_data.as_object() {
-                            // Extract failed tests from "failure" arrays
-                            if let Some(failure_array) = category_obj.get("failure").and_then(|f| f.as_array()) {
-                                for test_item in failure_array {
-                                    if let Some(test_name) = test_item.as_str() {
-                                        report_failed_tests.insert(test_name.to_string());
-                                    }
-                                }
-                            }
-                            // Extract passed tests from "success" arrays
-                            if let Some(success_array) = category_obj.get("success").and_then(|f| f.as_array()) {
-                                for test_item in success_array {
-                                    if let Some(test_name) = test_item.as_str() {
-                                        report_passed_tests.insert(test_name.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    break; // Found SWE-bench format, no need to check other keys
-                }
-            }
-            
-            // If not SWE-bench format, try direct mapping format: {"test_name": "status"}
-            if !found_swe_format {
-                for (test_name, status_val) in obj {
-                    if let Some(status) = status_val.as_str() {
-                        match status.to_lowercase().as_str() {
-                            "failed" | "fail" => { report_failed_tests.insert(test_name.clone()); }
-                            "passed" | "pass" | "success" => { report_passed_tests.insert(test_name.clone()); }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        }
-        
-        // Map test names to their status
-        for name in names {
-            if report_failed_tests.contains(name) {
-                out.insert(name.clone(), "failed".to_string());
-            } else if report_passed_tests.contains(name) {
-                out.insert(name.clone(), "passed".to_string());
-            } else {
-                out.insert(name.clone(), "missing".to_string());
-            }
-        }
-        
-        out
-    }
-
     fn perform_rule_checks(
         &self,
         base_s: &HashMap<String, String>,
@@ -462,296 +841,78 @@ impl LogParser {
         report_s: &HashMap<String, String>,
         fail_to_pass_tests: &[String],
         pass_to_pass_tests: &[String],
-        base_path: &str,
-        before_path: &str,
-        after_path: &str,
+        base_path: Option<&str>,
+        before_path: Option<&str>,
+        after_path: Option<&str>,
         file_paths: &[String],
-        report_data: Option<&serde_json::Value>,
+        report: Option<&ReportStatuses>,
         language: &str,
-    ) -> (RuleViolations, HashMap<String, Vec<String>>) {
+    ) -> (RuleViolations, HashMap<String, Vec<String>>, Vec<DuplicateTest>) {
         println!("Performing rule checks...");
         
-        // C1: P2P tests that are failed in base
-        let c1_hits: Vec<String> = pass_to_pass_tests.iter()
-            .filter(|t| base_s.get(*t) == Some(&"failed".to_string()))
-            .cloned()
-            .collect();
+        let c1_hits = c1_hits(pass_to_pass_tests, base_s, base_path);
         let c1 = !c1_hits.is_empty();
         println!("C1 check: {} violations", c1_hits.len());
-
-        // C2: Any test that failed in after (not: "not passed")
-        let c2_hits: Vec<String> = fail_to_pass_tests.iter()
-            .chain(pass_to_pass_tests.iter())
-            .filter(|t| after_s.get(*t) == Some(&"failed".to_string()))
-            .cloned()
-            .collect();
+        let c2_hits = c2_hits(fail_to_pass_tests, pass_to_pass_tests, after_s, after_path);
         let c2 = !c2_hits.is_empty();
         println!("C2 check: {} violations", c2_hits.len());
-
-        // C3: F2P tests that are successful in before
-        let c3_hits: Vec<String> = fail_to_pass_tests.iter()
-            .filter(|t| before_s.get(*t) == Some(&"passed".to_string()))
-            .cloned()
-            .collect();
+        let c3_hits = c3_hits(fail_to_pass_tests, before_s, before_path);
         let c3 = !c3_hits.is_empty();
         println!("C3 check: {} violations", c3_hits.len());
-
-        // C4: P2P tests missing in base and not passing in before
-        // Logic:
-        // - If P2P passed in base → Skip (don't check)
-        // - If P2P is missing in base → Check before:
-        //   - If passing in before → No violation
-        //   - If missing or failed in before → Violation
-        let mut c4_hits: Vec<String> = vec![];
-        for t in pass_to_pass_tests {
-            let b = base_s.get(t).map(String::as_str).unwrap_or("missing");
-            let be = before_s.get(t).map(String::as_str).unwrap_or("missing");
-            
-            // If P2P passed in base, skip this test (no need to check before)
-            if b == "passed" {
-                continue;
-            }
-            
-            // If P2P is missing in base, check it in before
-            if b == "missing" {
-                // If P2P is NOT passing in before (missing or failed), it's a violation
-                if be != "passed" {
-                    c4_hits.push(format!("{t} (missing in base, {be} in before)"));
-                }
-            }
-        }
+        let c4_hits = c4_hits(pass_to_pass_tests, base_s, before_s, base_path, before_path);
         let c4 = !c4_hits.is_empty();
         println!("C4 check: {} violations", c4_hits.len());
 
         // C5: true duplicates per log using enhanced detection
         let mut dup_map = HashMap::new();
-        let base_txt = fs::read_to_string(base_path).unwrap_or_default();
-        let before_txt = fs::read_to_string(before_path).unwrap_or_default();
-        let after_txt = fs::read_to_string(after_path).unwrap_or_default();
-        
-        let base_dups = detect_same_file_duplicates(&base_txt);
-        let before_dups = detect_same_file_duplicates(&before_txt);
-        let after_dups = detect_same_file_duplicates(&after_txt);
-        
-        if !base_dups.is_empty() {
-            dup_map.insert("base".to_string(), base_dups.into_iter().take(50).collect::<Vec<_>>());
-        }
-        if !before_dups.is_empty() {
-            dup_map.insert("before".to_string(), before_dups.into_iter().take(50).collect::<Vec<_>>());
-        }
-        if !after_dups.is_empty() {
-            dup_map.insert("after".to_string(), after_dups.into_iter().take(50).collect::<Vec<_>>());
+        // Missing logs simply contribute no duplicates
+        let read_log = |path: Option<&str>| path.and_then(|p| read_language_log_file(p, language).ok()).unwrap_or_default();
+        let heuristics = DuplicateHeuristics::from_config(&load_server_config().rule_checks.duplicates);
+        let mut duplicates = Vec::new();
+        for (stage, path) in [("base", base_path), ("before", before_path), ("after", after_path)] {
+            let dups: Vec<(String, Vec<usize>)> = detect_same_file_duplicates(&read_log(path), &heuristics);
+            if dups.is_empty() {
+                continue;
+            }
+            dup_map.insert(
+                stage.to_string(),
+                dups.iter().map(|(name, lines)| describe_duplicate(name, lines)).collect::<Vec<_>>(),
+            );
+            // Structured copy for the Duplicates panel, which links each line to the log viewer
+            duplicates.extend(dups.into_iter().map(|(test_name, lines)| DuplicateTest {
+                stage: stage.to_string(),
+                test_name,
+                lines,
+            }));
         }
         let c5 = !dup_map.is_empty();
         println!("C5 check: {} logs with duplicates", dup_map.len());
 
         // C6: Test marked as failing in report.json but passing in post_agent_log
-        // This checks for inconsistencies between report.json and agent log results
-        let mut c6_hits: Vec<String> = vec![];
-        let c6 = match report_data {
-            Some(report_data_ref) => {
-                println!("Performing C6 check: comparing report.json with agent log results");
-                
-                // Parse report.json to extract test results
-                let mut report_failed_tests = std::collections::HashSet::new();
-                
-                // Try different possible structures for report.json
-                if let Some(results_array) = report_data_ref.get("results").and_then(|r| r.as_array()) {
-                    for result in results_array {
-                        if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
-                            if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                                report_failed_tests.insert(test_name.to_string());
-                            }
-                        }
-                    }
-                } else if let Some(test_results) = report_data_ref.get("test_results").and_then(|r| r.as_array()) {
-                    for result in test_results {
-                        if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
-                            if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                                report_failed_tests.insert(test_name.to_string());
-                            }
-                        }
-                    }
-                } else if let Some(tests_obj) = report_data_ref.get("tests").and_then(|t| t.as_object()) {
-                    // Format: {"tests": {"test_name": {"status": "failed"}}}
-                    for (test_name, test_data) in tests_obj {
-                        if let Some(status) = test_data.get("status").and_then(|s| s.as_str()) {
-                            if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                                report_failed_tests.insert(test_name.clone());
-                            }
-                        }
-                    }
-                } else if let Some(obj) = report_data_ref.as_object() {
-                    // Check for SWE-bench format first
-                    let mut found_swe_format = false;
-                    for (key, value) in obj {
-                        if let Some(tests_status) = value.get("tests_status").and_then(|t| t.as_object()) {
-                            println!("Found SWE-bench format report.json for key: {}", key);
-                            found_swe_format = true;
-                            
-                            // Parse all test categories that indicate failure
-                            for (category, category_data) in tests_status {
-                                if let Some(category_obj) = category_data.as_object() {
-                                    // Extract failed tests from "failure" arrays in all categories
-                                    if let Some(failure_array) = category_obj.get("failure").and_then(|f| f.as_array()) {
-                                        for test_item in failure_array {
-                                            if let Some(test_name) = test_item.as_str() {
-                                                report_failed_tests.insert(test_name.to_string());
-                                                println!("Found failed test in category {}: {}", category, test_name);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            break; // Found SWE-bench format, no need to check other keys
-                        }
-                    }
-                    
-                    // If not SWE-bench format, try direct mapping format: {"test_name": "status"}
-                    if !found_swe_format {
-                        for (test_name, status_val) in obj {
-                            if let Some(status) = status_val.as_str() {
-                                if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                                    report_failed_tests.insert(test_name.clone());
-                                }
-                            }
-                        }
-                    }
-                }
-                
-                println!("Found {} failed tests in report.json", report_failed_tests.len());
-                
-                // Check F2P and P2P tests for inconsistencies in both directions
-                let mut inconsistencies = 0;
-                for test_name in fail_to_pass_tests.iter().chain(pass_to_pass_tests.iter()) {
-                    let report_status = if report_failed_tests.contains(test_name) {
-                        "failed"
-                    } else if report_s.get(test_name) == Some(&"passed".to_string()) {
-                        "passed"
-                    } else {
-                        continue; // Skip tests that are missing in report.json
-                    };
-                    
-                    let agent_status = agent_s.get(test_name).map(String::as_str).unwrap_or("missing");
-                    
-                    // Check for status mismatches (excluding missing cases)
-                    if agent_status != "missing" && report_status != agent_status {
-                        match (report_status, agent_status) {
-                            ("failed", "passed") => {
-                                c6_hits.push(format!("{} (marked as failed in report.json but passing in agent log)", test_name));
-                                inconsistencies += 1;
-                            },
-                            ("passed", "failed") => {
-                                c6_hits.push(format!("{} (marked as passed in report.json but failing in agent log)", test_name));
-                                inconsistencies += 1;
-                            },
-                            _ => {} // Other combinations like "passed" vs "ignored" could be added if needed
-                        }
-                    }
-                }
-                
-                println!("C6 check found {} inconsistencies", inconsistencies);
-                inconsistencies > 0
+        let c6_hits = match report {
+            Some(report) => {
+                println!("Performing C6 check: comparing report.json ({}) with agent log results", report.schema);
+                println!("Found {} failed tests in report.json", report.failed.len());
+                c6_hits(report_s, agent_s, fail_to_pass_tests, pass_to_pass_tests)
             },
             None => {
-                println!("C6 check skipped: no report.json available");
-                false
+                println!("C6 check skipped: no report.json available or its schema is unknown");
+                vec![]
             }
         };
+        let c6 = !c6_hits.is_empty();
         println!("C6 check: {} violations", c6_hits.len());
 
         // C7: F2P tests found in golden source diff files but not in test diff files
-        let mut c7_hits: Vec<String> = vec![];
-        let c7 = {
-            println!("Performing C7 check: looking for F2P tests in golden source diff files (but not in test diffs)");
-            
-            // Find diff/patch files from patches folder
-            let diff_files: Vec<&String> = file_paths.iter()
-                .filter(|path| {
-                    let path_lower = path.to_lowercase();
-                    path_lower.contains("patches/") && (path_lower.ends_with(".diff") || path_lower.ends_with(".patch"))
-                })
-                .collect();
-            
-            println!("Found {} diff/patch files", diff_files.len());
-            
-            if !diff_files.is_empty() {
-                // Separate golden source diffs from test diffs
-                let (golden_source_diffs, test_diffs): (Vec<&String>, Vec<&String>) = diff_files.iter()
-                    .partition(|path| {
-                        let filename = path.split('/').last().unwrap_or("").to_lowercase();
-                        // Golden source diffs typically contain "gold", "golden", "src", "source"
-                        // Test diffs typically contain "test"
-                        (filename.contains("gold") || filename.contains("src") || filename.contains("source")) &&
-                        !filename.contains("test")
-                    });
-                
-                println!("Found {} golden source diff files and {} test diff files", 
-                         golden_source_diffs.len(), test_diffs.len());
-                
-                // Read all test diff contents to check if tests appear there
-                let mut test_diff_contents = String::new();
-                for test_diff in &test_diffs {
-                    if let Ok(content) = fs::read_to_string(test_diff) {
-                        test_diff_contents.push_str(&content);
-                        test_diff_contents.push('\n');
-                        println!("Read test diff file: {}", test_diff);
-                    }
-                }
-                
-                // Check golden source diffs for F2P tests
-                for golden_diff in &golden_source_diffs {
-                    println!("Checking golden source diff file: {}", golden_diff);
-                    
-                    if let Ok(diff_content) = fs::read_to_string(golden_diff) {
-                        println!("Read golden source diff successfully, {} bytes", diff_content.len());
-                        
-                        // Check if any F2P test names appear in this golden source diff
-                        for f2p_test in fail_to_pass_tests {
-                            // Extract the actual test name from module path (e.g., "tests::test_example" -> "test_example")
-                            let test_name_to_search = if f2p_test.contains("::") {
-                                f2p_test.split("::").last().unwrap_or(f2p_test)
-                            } else {
-                                f2p_test
-                            };
-                            
-                            let test_found_in_source = test_detection::contains_exact_test_name(&diff_content, test_name_to_search, language);
-                            
-                            if test_found_in_source {
-                                // Check if this test also appears in test diffs
-                                let test_found_in_test_diffs = if !test_diff_contents.is_empty() {
-                                    test_detection::contains_exact_test_name(&test_diff_contents, test_name_to_search, language)
-                                } else {
-                                    false
-                                };
-                                
-                                if test_found_in_test_diffs {
-                                    println!("F2P test '{}' found in both golden source and test diffs - not a violation", f2p_test);
-                                } else {
-                                    let search_term = if language == "python" { f2p_test } else { test_name_to_search };
-                                    let violation = format!("{} (found as '{}' in {} but not in test diffs)", 
-                                                          f2p_test, search_term, 
-                                                          golden_diff.split('/').last().unwrap_or(golden_diff));
-                                    c7_hits.push(violation);
-                                    println!("C7 violation: F2P test '{}' found as '{}' in golden source diff '{}' but not in test diffs", 
-                                             f2p_test, search_term, golden_diff);
-                                }
-                            }
-                        }
-                    } else {
-                        println!("Failed to read golden source diff file: {}", golden_diff);
-                    }
-                }
-            } else {
-                println!("No diff/patch files found in patches folder");
-            }
-            
-            let has_violations = !c7_hits.is_empty();
-            println!("C7 check completed: {} violations found", c7_hits.len());
-            has_violations
-        };
+        let c7_hits = c7_hits(fail_to_pass_tests, file_paths, &self.patch_classes, language);
+        let c7 = !c7_hits.is_empty();
         println!("C7 check: {} violations", c7_hits.len());
 
+        // C11: F2P tests skipped where the fix should have made them pass; C2 only sees failures
+        let c11_hits = c11_hits(fail_to_pass_tests, after_s, agent_s);
+        let c11 = !c11_hits.is_empty();
+        println!("C11 check: {} violations", c11_hits.len());
+
         let rule_violations = RuleViolations {
             c1_failed_in_base_present_in_p2p: RuleViolation {
                 has_problem: c1,
@@ -781,25 +942,481 @@ impl LogParser {
                 has_problem: c7,
                 examples: c7_hits,
             },
+            // Filled in by the dataset cross-check, which needs network/disk access
+            c8_test_lists_differ_from_dataset: RuleViolation::default(),
+            // Filled in from the raw stage logs by `analyze_logs`
+            c9_patch_failed_to_apply: RuleViolation::default(),
+            c10_p2p_list_too_narrow: RuleViolation::default(),
+            c11_f2p_skipped_in_after_or_agent: RuleViolation {
+                has_problem: c11,
+                examples: c11_hits,
+            },
         };
 
-        (rule_violations, dup_map)
+        (rule_violations, dup_map, duplicates)
+    }
+}
+
+// ---------------- Status and patch rules (C1-C4, C6, C7, C11) ----------------
+// Free of the parsed logs, so `recheck_rule` can run them on a stored analysis's statuses
+
+// C1: P2P tests that are failed in base
+fn c1_hits(pass_to_pass_tests: &[String], base_s: &HashMap<String, String>, base_path: Option<&str>) -> Vec<String> {
+    if let Some(note) = missing_log_note(&[("base", base_path)]) {
+        return vec![note];
+    }
+    pass_to_pass_tests.iter()
+        .filter(|t| base_s.get(*t) == Some(&"failed".to_string()))
+        .cloned()
+        .collect()
+}
+
+// C2: Any test that failed in after (not: "not passed")
+fn c2_hits(fail_to_pass_tests: &[String], pass_to_pass_tests: &[String], after_s: &HashMap<String, String>, after_path: Option<&str>) -> Vec<String> {
+    if let Some(note) = missing_log_note(&[("after", after_path)]) {
+        return vec![note];
+    }
+    fail_to_pass_tests.iter()
+        .chain(pass_to_pass_tests.iter())
+        .filter(|t| after_s.get(*t) == Some(&"failed".to_string()))
+        .cloned()
+        .collect()
+}
+
+// C3: F2P tests that are successful in before
+fn c3_hits(fail_to_pass_tests: &[String], before_s: &HashMap<String, String>, before_path: Option<&str>) -> Vec<String> {
+    if let Some(note) = missing_log_note(&[("before", before_path)]) {
+        return vec![note];
+    }
+    fail_to_pass_tests.iter()
+        .filter(|t| before_s.get(*t) == Some(&"passed".to_string()))
+        .cloned()
+        .collect()
+}
+
+// C4: P2P tests missing in base and not passing in before
+// Logic:
+// - If P2P passed in base → Skip (don't check)
+// - If P2P is missing in base → Check before:
+//   - If passing in before → No violation
+//   - If missing or failed in before → Violation
+fn c4_hits(
+    pass_to_pass_tests: &[String],
+    base_s: &HashMap<String, String>,
+    before_s: &HashMap<String, String>,
+    base_path: Option<&str>,
+    before_path: Option<&str>,
+) -> Vec<String> {
+    // The before log only matters for P2P tests missing in base
+    let needs_before = pass_to_pass_tests.iter().any(|t| base_s.get(t).map(String::as_str) == Some("missing"));
+    let required = if needs_before { vec![("base", base_path), ("before", before_path)] } else { vec![("base", base_path)] };
+    if let Some(note) = missing_log_note(&required) {
+        return vec![note];
+    }
+    let mut hits: Vec<String> = vec![];
+    for t in pass_to_pass_tests {
+        let b = base_s.get(t).map(String::as_str).unwrap_or("missing");
+        let be = before_s.get(t).map(String::as_str).unwrap_or("missing");
+
+        // If P2P passed in base, skip this test (no need to check before)
+        if b == "passed" {
+            continue;
+        }
+
+        // If P2P is missing in base, check it in before
+        if b == "missing" {
+            // If P2P is NOT passing in before (missing or failed), it's a violation
+            if be != "passed" {
+                hits.push(format!("{t} (missing in base, {be} in before)"));
+            }
+        }
+    }
+    hits
+}
+
+// C6: Test marked as failing in report.json but passing in post_agent_log, or the other way
+// round; tests missing from either side are skipped
+fn c6_hits(
+    report_s: &HashMap<String, String>,
+    agent_s: &HashMap<String, String>,
+    fail_to_pass_tests: &[String],
+    pass_to_pass_tests: &[String],
+) -> Vec<String> {
+    let mut hits: Vec<String> = vec![];
+    for test_name in fail_to_pass_tests.iter().chain(pass_to_pass_tests.iter()) {
+        let report_status = match report_s.get(test_name).map(String::as_str) {
+            Some("failed") => "failed",
+            Some("passed") => "passed",
+            _ => continue, // Skip tests that are missing in report.json
+        };
+
+        let agent_status = agent_s.get(test_name).map(String::as_str).unwrap_or("missing");
+
+        // Check for status mismatches (excluding missing cases)
+        match (report_status, agent_status) {
+            ("failed", "passed") => {
+                hits.push(format!("{} (marked as failed in report.json but passing in agent log)", test_name));
+            },
+            ("passed", "failed") => {
+                hits.push(format!("{} (marked as passed in report.json but failing in agent log)", test_name));
+            },
+            _ => {} // Other combinations like "passed" vs "ignored" could be added if needed
+        }
+    }
+    println!("C6 check found {} inconsistencies", hits.len());
+    hits
+}
+
+// C11: F2P tests skipped in after or in the agent run. A skipped test never ran the fix, so it
+// proves nothing either way; tests missing from a log are C2/C6 territory and left out here
+fn c11_hits(fail_to_pass_tests: &[String], after_s: &HashMap<String, String>, agent_s: &HashMap<String, String>) -> Vec<String> {
+    let skipped = |statuses: &HashMap<String, String>, test: &str| statuses.get(test).map(String::as_str) == Some("ignored");
+    fail_to_pass_tests.iter()
+        .filter_map(|t| match (skipped(after_s, t), skipped(agent_s, t)) {
+            (true, true) => Some(format!("{t} (skipped in after and agent)")),
+            (true, false) => Some(format!("{t} (skipped in after)")),
+            (false, true) => Some(format!("{t} (skipped in agent)")),
+            (false, false) => None,
+        })
+        .collect()
+}
+
+// C7: F2P tests found in golden source diff files but not in test diff files
+fn c7_hits(fail_to_pass_tests: &[String], file_paths: &[String], patch_classes: &HashMap<String, String>, language: &str) -> Vec<String> {
+    let mut hits: Vec<String> = vec![];
+    println!("Performing C7 check: looking for F2P tests in golden source diff files (but not in test diffs)");
+
+    // Find diff/patch files from patches folder
+    let diff_files: Vec<&String> = file_paths.iter().filter(|path| is_patch_file(path)).collect();
+
+    println!("Found {} diff/patch files", diff_files.len());
+
+    if diff_files.is_empty() {
+        println!("No diff/patch files found in patches folder");
+        return hits;
+    }
+
+    // Separate golden source diffs from test diffs, by the reviewer's classes or the file names
+    let (golden_source_diffs, test_diffs): (Vec<&String>, Vec<&String>) = diff_files.iter()
+        .filter(|path| patch_class(path, patch_classes) != "ignore")
+        .partition(|path| patch_class(path, patch_classes) == "source");
+
+    println!("Found {} golden source diff files and {} test diff files",
+             golden_source_diffs.len(), test_diffs.len());
+
+    // Read all test diff contents to check if tests appear there
+    let mut test_diff_contents = String::new();
+    for test_diff in &test_diffs {
+        if let Ok(content) = fs::read_to_string(test_diff) {
+            test_diff_contents.push_str(&content);
+            test_diff_contents.push('\n');
+            println!("Read test diff file: {}", test_diff);
+        }
+    }
+
+    // Check golden source diffs for F2P tests
+    for golden_diff in &golden_source_diffs {
+        println!("Checking golden source diff file: {}", golden_diff);
+
+        let Ok(diff_content) = fs::read_to_string(golden_diff) else {
+            println!("Failed to read golden source diff file: {}", golden_diff);
+            continue;
+        };
+        println!("Read golden source diff successfully, {} bytes", diff_content.len());
+
+        // Check if any F2P test names appear in this golden source diff
+        for f2p_test in fail_to_pass_tests {
+            // Extract the actual test name from module path (e.g., "tests::test_example" -> "test_example")
+            let test_name_to_search = if f2p_test.contains("::") {
+                f2p_test.split("::").last().unwrap_or(f2p_test)
+            } else {
+                f2p_test
+            };
+
+            if !test_detection::contains_exact_test_name(&diff_content, test_name_to_search, language) {
+                continue;
+            }
+            // Check if this test also appears in test diffs
+            let test_found_in_test_diffs = !test_diff_contents.is_empty()
+                && test_detection::contains_exact_test_name(&test_diff_contents, test_name_to_search, language);
+
+            if test_found_in_test_diffs {
+                println!("F2P test '{}' found in both golden source and test diffs - not a violation", f2p_test);
+            } else {
+                let search_term = if language == "python" { f2p_test } else { test_name_to_search };
+                let violation = format!("{} (found as '{}' in {} but not in test diffs)",
+                                      f2p_test, search_term,
+                                      golden_diff.split('/').last().unwrap_or(golden_diff));
+                hits.push(violation);
+                println!("C7 violation: F2P test '{}' found as '{}' in golden source diff '{}' but not in test diffs",
+                         f2p_test, search_term, golden_diff);
+            }
+        }
+    }
+
+    println!("C7 check completed: {} violations found", hits.len());
+    hits
+}
+
+/// Check rule `code` again from the stage statuses a stored `analysis` found, against the patches
+/// and logs now on disk, without parsing the logs again. Only `RECHECKABLE_RULES` can be checked
+/// on their own; the others need the parsed logs, the raw logs or the dataset
+pub fn recheck_rule(
+    code: &str,
+    analysis: &LogAnalysisResult,
+    file_paths: &[String],
+    fail_to_pass_tests: &[String],
+    pass_to_pass_tests: &[String],
+    patch_classes: &HashMap<String, String>,
+) -> Result<RuleViolation, String> {
+    let language = analysis.settings.as_ref().map(|settings| settings.language.as_str()).unwrap_or("rust");
+    let statuses = |stage: &str| -> HashMap<String, String> {
+        analysis.test_statuses.f2p.iter()
+            .chain(analysis.test_statuses.p2p.iter())
+            .filter_map(|(name, summary)| summary.status_for(stage).map(|status| (name.clone(), status.to_string())))
+            .collect()
+    };
+    let patterns = LogPatterns::load();
+    let stage_path = |stage: &str| patterns.find(stage, file_paths).map(String::as_str);
+
+    let hits = match code {
+        "C1" => c1_hits(pass_to_pass_tests, &statuses("base"), stage_path("base")),
+        "C2" => c2_hits(fail_to_pass_tests, pass_to_pass_tests, &statuses("after"), stage_path("after")),
+        "C3" => c3_hits(fail_to_pass_tests, &statuses("before"), stage_path("before")),
+        "C4" => c4_hits(pass_to_pass_tests, &statuses("base"), &statuses("before"), stage_path("base"), stage_path("before")),
+        // The stored analysis records whether report.json could be read
+        "C6" if analysis.debug_info.report_schema.is_some() => {
+            c6_hits(&statuses("report"), &statuses("agent"), fail_to_pass_tests, pass_to_pass_tests)
+        }
+        "C6" => vec![],
+        "C7" => c7_hits(fail_to_pass_tests, file_paths, patch_classes, language),
+        "C11" => c11_hits(fail_to_pass_tests, &statuses("after"), &statuses("agent")),
+        "C10" => {
+            let breadth = P2pBreadthThresholds::from_config(&load_server_config().rule_checks.p2p_breadth);
+            let base_test_count = analysis.debug_info.log_counts.iter()
+                .find(|count| count.label == "base")
+                .map(|count| count.all)
+                .unwrap_or(0);
+            check_p2p_breadth(pass_to_pass_tests.len(), base_test_count, &breadth).into_iter().collect()
+        }
+        _ => return Err(format!("{} can't be re-checked on its own; run the whole analysis again", code)),
+    };
+    println!("{} re-check: {} violations", code, hits.len());
+    Ok(RuleViolation { has_problem: !hits.is_empty(), examples: hits })
+}
+
+// Pass listed tests that belong to a collapsed suite but have no line of their own; only names
+// that carry their file (`src/foo.test.ts > suite > test`) can be tied to a suite, and only a
+// suite whose summary counts exactly the tests listed for it is expanded
+fn expand_collapsed_suites(parsed: &mut ParsedLog, listed: &[String]) {
+    let printed: std::collections::HashSet<String> = parsed.all.iter()
+        .map(|name| strip_js_test_file(&canonical_js_separators(&canonical_test_name(name))).to_string())
+        .collect();
+    let same_file = |a: &str, b: &str| a == b || a.ends_with(&format!("/{}", b)) || b.ends_with(&format!("/{}", a));
+
+    // Listed tests by the collapsed suite they belong to
+    let mut by_suite: Vec<Vec<&String>> = vec![Vec::new(); parsed.collapsed_suites.len()];
+    for name in listed {
+        let joined = canonical_js_separators(&canonical_test_name(name));
+        let Some((file, _)) = joined.split_once(JS_SUITE_SEPARATOR) else { continue };
+        if let Some(index) = parsed.collapsed_suites.iter().position(|suite| same_file(&suite.file, file)) {
+            by_suite[index].push(name);
+        }
+    }
+
+    for (suite, names) in parsed.collapsed_suites.clone().iter().zip(by_suite) {
+        // A count that differs from the list means tests were renamed, added or dropped
+        if suite.tests == 0 || suite.tests != names.len() {
+            continue;
+        }
+        for name in names {
+            let joined = canonical_js_separators(&canonical_test_name(name));
+            if parsed.all.contains(name) || printed.contains(strip_js_test_file(&joined)) {
+                continue;
+            }
+            parsed.passed.insert(name.clone());
+            parsed.all.insert(name.clone());
+            parsed.inferred_passes.push(InferredPass { stage: String::new(), test_name: name.clone(), suite: suite.file.clone() });
+        }
+    }
+
+    let count = parsed.inferred_passes.len();
+    if count > 0 {
+        parsed.warnings.push(ParseWarning {
+            stage: String::new(),
+            kind: ParseWarningKind::InferredPasses,
+            message: format!("{} pass{} inferred from suite summaries without per-test lines", count, if count == 1 { "" } else { "es" }),
+            count,
+            examples: parsed.inferred_passes.iter().take(MAX_PARSE_WARNING_EXAMPLES).map(|inferred| inferred.test_name.clone()).collect(),
+        });
+    }
+}
+
+const MAX_PARSE_WARNING_EXAMPLES: usize = 5;
+
+// Duplicated tests kept per log, by C5 and the duplicate endpoint alike
+const MAX_DUPLICATES_PER_LOG: usize = 50;
+
+// Statuses libtest and unittest print after ` ... ` that the parsers understand
+const KNOWN_DOTTED_STATUSES: &[&str] = &["ok", "FAILED", "FAIL", "ERROR", "ignored", "skipped", "expected", "unexpected", "bench:"];
+
+// Result markers the JS parsers understand
+const KNOWN_RESULT_SYMBOLS: &[char] = &['✓', '✔', '√', '✕', '×', '✖', '↓', '○'];
+
+// Symbol and emoji blocks reporters draw result markers from (✅, ❌, ⚠, 🔥)
+fn is_status_symbol(c: char) -> bool {
+    matches!(c as u32, 0x2600..=0x27BF | 0x1F300..=0x1FAFF)
+}
+
+// Last segment of a test name (`mod::test`, `file > suite > test`, `pkg.Class.test`), so a
+// result line can be matched against the parsed names however the parser joined them
+fn name_leaf(name: &str) -> &str {
+    let name = name.trim();
+    let leaf = [" > ", " › ", "::", "/"]
+        .iter()
+        .fold(name, |leaf, separator| leaf.rsplit(separator).next().unwrap_or(leaf));
+    if leaf.contains(char::is_whitespace) {
+        leaf
+    } else {
+        leaf.rsplit('.').next().unwrap_or(leaf)
+    }
+}
+
+// Signs that the wrong parser was picked or the log is incomplete, and lines the parser dropped
+// or preprocessing rewrote; the log is scanned once
+fn parse_warnings(parsed: &ParsedLog, content: &str, stats: PreprocessStats) -> Vec<ParseWarning> {
+    lazy_static! {
+        static ref CARGO_RESULT_RE: Regex = Regex::new(r"^\s*test (\S+) \.\.\. (\S+)").unwrap();
+        // `test_login (accounts.tests.LoginTests) ... ERROR`, with the method on its own
+        static ref UNITTEST_RESULT_RE: Regex = Regex::new(r"^\s*(\w+) \([\w.]+\) \.\.\. (\S+)").unwrap();
+    }
+    let warning = |kind: ParseWarningKind, message: String, count: usize, examples: Vec<String>| ParseWarning {
+        stage: String::new(),
+        kind,
+        message,
+        count,
+        examples,
+    };
+    let plural = |count: usize| if count == 1 { "" } else { "s" };
+    let parsed_names: std::collections::HashSet<&str> = parsed.all.iter().map(|name| name.trim()).chain(parsed.all.iter().map(|name| name_leaf(name))).collect();
+    let is_parsed = |name: &str| parsed_names.contains(name.trim()) || parsed_names.contains(name_leaf(name));
+
+    let mut warnings = Vec::new();
+    if parsed.all.is_empty() {
+        let message = if content.trim().is_empty() {
+            "Log is empty after preprocessing"
+        } else {
+            "No test results recognized; the log may need a different parser"
+        };
+        warnings.push(warning(ParseWarningKind::NoResults, message.to_string(), 1, vec![]));
+    }
+    let conflicting = parsed.passed.intersection(&parsed.failed).count();
+    if conflicting > 0 {
+        warnings.push(warning(
+            ParseWarningKind::ConflictingStatus,
+            format!("{} test{} reported as both passed and failed", conflicting, plural(conflicting)),
+            conflicting,
+            vec![],
+        ));
+    }
+
+    let mut unrecognized = Vec::new();
+    for line in content.lines() {
+        let dotted = CARGO_RESULT_RE.captures(line).or_else(|| UNITTEST_RESULT_RE.captures(line));
+        let flagged = match dotted {
+            Some(c) => !KNOWN_DOTTED_STATUSES.contains(&&c[2]) && !is_parsed(&c[1]),
+            None => {
+                let trimmed = line.trim_start();
+                match trimmed.chars().next() {
+                    Some(symbol) if is_status_symbol(symbol) && !KNOWN_RESULT_SYMBOLS.contains(&symbol) => {
+                        let rest = trimmed[symbol.len_utf8()..].trim();
+                        !rest.is_empty() && !is_parsed(rest)
+                    }
+                    _ => false,
+                }
+            }
+        };
+        if flagged {
+            unrecognized.push(line.trim().to_string());
+        }
+    }
+    if !unrecognized.is_empty() {
+        let count = unrecognized.len();
+        unrecognized.truncate(MAX_PARSE_WARNING_EXAMPLES);
+        warnings.push(warning(
+            ParseWarningKind::UnrecognizedStatus,
+            format!("{} result-like line{} with a status the parser did not recognize", count, plural(count)),
+            count,
+            unrecognized,
+        ));
+    }
+
+    // Names that only differ by case or whitespace are probably one test reported twice
+    let mut by_canonical: HashMap<String, Vec<&String>> = HashMap::new();
+    for name in &parsed.all {
+        by_canonical.entry(canonical_test_name(name).to_lowercase()).or_default().push(name);
+    }
+    let mut collisions: Vec<String> = by_canonical
+        .into_values()
+        .filter(|names| names.len() > 1)
+        .map(|mut names| {
+            names.sort();
+            names.iter().map(|name| name.as_str()).collect::<Vec<_>>().join(" | ")
+        })
+        .collect();
+    if !collisions.is_empty() {
+        collisions.sort();
+        let count = collisions.len();
+        collisions.truncate(MAX_PARSE_WARNING_EXAMPLES);
+        warnings.push(warning(
+            ParseWarningKind::NameCollision,
+            format!("{} group{} of test names differing only by case or whitespace", count, plural(count)),
+            count,
+            collisions,
+        ));
+    }
+
+    if stats.noise_lines > 0 {
+        warnings.push(warning(
+            ParseWarningKind::NoiseStripped,
+            format!("{} build/install line{} stripped as noise", stats.noise_lines, plural(stats.noise_lines)),
+            stats.noise_lines,
+            vec![],
+        ));
+    }
+    if stats.stitched_lines > 0 {
+        warnings.push(warning(
+            ParseWarningKind::LinesStitched,
+            format!("{} status line{} moved back onto split test lines", stats.stitched_lines, plural(stats.stitched_lines)),
+            stats.stitched_lines,
+            vec![],
+        ));
+    }
+    warnings
+}
+
+// A rule that needs a stage log the deliverable lacks fails with this note instead of passing vacuously
+fn missing_log_note(required: &[(&str, Option<&str>)]) -> Option<String> {
+    let missing: Vec<&str> = required.iter().filter(|(_, path)| path.is_none()).map(|(stage, _)| *stage).collect();
+    if missing.is_empty() {
+        None
+    } else {
+        Some(format!("Not evaluated: {} log missing", missing.join(" and ")))
     }
 }
 
 // ---------------- Duplicate detection (C5) parity----------------
 fn detect_file_boundary(line: &str) -> Option<String> {
-    // These patterns are now in RustLogParser, but for duplicate detection we need them here
+    // Cargo test binaries and compiletest files; the rest are older formats seen in the wild
     lazy_static! {
         static ref FILE_BOUNDARY_RE_1: Regex = Regex::new(r"(?i)Running\s+([^\s]+(?:/[^\s]+)*\.(?:rs|fixed))\s*\(").unwrap();
-        static ref FILE_BOUNDARY_RE_2: Regex = Regex::new(r"(?i)===\s*Running\s+(.+\.(?:rs|fixed))").unwrap();
         static ref FILE_BOUNDARY_RE_3: Regex = Regex::new(r"(?i)test\s+result:\s+ok\.\s+\d+\s+passed.*for\s+(.+\.(?:rs|fixed))").unwrap();
     }
-    
-    if let Some(c) = FILE_BOUNDARY_RE_1.captures(line) {
-        return Some(c.get(1).unwrap().as_str().to_string());
+
+    if let Some(binary) = binary_boundary(line) {
+        return Some(binary);
     }
-    if let Some(c) = FILE_BOUNDARY_RE_2.captures(line) {
+    if let Some(c) = FILE_BOUNDARY_RE_1.captures(line) {
         return Some(c.get(1).unwrap().as_str().to_string());
     }
     if let Some(c) = FILE_BOUNDARY_RE_3.captures(line) {
@@ -855,15 +1472,33 @@ struct Occur {
     context_after: Vec<String>,
 }
 
-fn is_true_duplicate(occ: &[Occur]) -> bool {
-    if occ.len() <= 1 { return false; }
-    let mut lines: Vec<usize> = occ.iter().map(|o| o.line_no).collect();
-    lines.sort_unstable();
-    let mut min_dist = usize::MAX;
-    for i in 1..lines.len() {
-        min_dist = std::cmp::min(min_dist, lines[i] - lines[i-1]);
+// Knobs for what counts as a true duplicate, from `rule_checks.duplicates` in the server config
+#[derive(Clone, Debug, PartialEq)]
+struct DuplicateHeuristics {
+    min_distance: usize,
+    context_window: usize,
+    require_status_conflict: bool,
+}
+
+impl Default for DuplicateHeuristics {
+    fn default() -> Self {
+        Self { min_distance: 10, context_window: 2, require_status_conflict: false }
     }
-    if min_dist < 10 { return true; }
+}
+
+impl DuplicateHeuristics {
+    fn from_config(config: &DuplicateDetectionConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            min_distance: config.min_distance.unwrap_or(defaults.min_distance),
+            context_window: config.context_window.unwrap_or(defaults.context_window),
+            require_status_conflict: config.require_status_conflict,
+        }
+    }
+}
+
+fn is_true_duplicate(occ: &[Occur], heuristics: &DuplicateHeuristics) -> bool {
+    if occ.len() <= 1 { return false; }
     let mut has_fail = false;
     let mut has_ok = false;
     for o in occ {
@@ -871,7 +1506,19 @@ fn is_true_duplicate(occ: &[Occur]) -> bool {
         if s == "failed" || s == "error" { has_fail = true; }
         if s == "ok" { has_ok = true; }
     }
-    if has_fail && has_ok { return true; }
+    let conflict = has_fail && has_ok;
+    if heuristics.require_status_conflict {
+        return conflict;
+    }
+    let mut lines: Vec<usize> = occ.iter().map(|o| o.line_no).collect();
+    lines.sort_unstable();
+    let mut min_dist = usize::MAX;
+    for i in 1..lines.len() {
+        min_dist = std::cmp::min(min_dist, lines[i] - lines[i-1]);
+    }
+    if min_dist < heuristics.min_distance { return true; }
+    if conflict { return true; }
+    if heuristics.context_window == 0 { return false; }
     let contexts: Vec<String> = occ.iter().map(|o| {
         let mut c = String::new();
         c.push_str(&o.context_before.join(" "));
@@ -884,7 +1531,27 @@ fn is_true_duplicate(occ: &[Occur]) -> bool {
     false
 }
 
-fn detect_same_file_duplicates(raw_content: &str) -> Vec<String> {
+fn describe_duplicate(name: &str, lines: &[usize]) -> String {
+    let places: Vec<String> = lines.iter().map(|line| format!("line {}", line)).collect();
+    format!("{} (appears {} times: {})", name, places.len(), places.join(", "))
+}
+
+// Duplicated tests with the 1-based log lines of each occurrence, sorted by name
+fn detect_same_file_duplicates(raw_content: &str, heuristics: &DuplicateHeuristics) -> Vec<(String, Vec<usize>)> {
+    detect_duplicate_occurrences(raw_content, heuristics)
+        .into_iter()
+        .map(|duplicate| (duplicate.test_name, duplicate.occurrences.iter().map(|o| o.line).collect()))
+        .collect()
+}
+
+/// Tests reported more than once in one log's content, with the duplicate heuristics of the
+/// server config; the same check C5 runs on each stage log
+pub fn detect_log_duplicates(raw_content: &str) -> Vec<DetectedDuplicate> {
+    detect_duplicate_occurrences(raw_content, &DuplicateHeuristics::from_config(&load_server_config().rule_checks.duplicates))
+}
+
+// Duplicated tests with each occurrence in line order, sorted by name and capped
+fn detect_duplicate_occurrences(raw_content: &str, heuristics: &DuplicateHeuristics) -> Vec<DetectedDuplicate> {
     if raw_content.is_empty() { return vec![]; }
     let lines: Vec<&str> = raw_content.split('\n').collect();
     let mut current_file = "unknown".to_string();
@@ -896,23 +1563,39 @@ fn detect_same_file_duplicates(raw_content: &str) -> Vec<String> {
             continue;
         }
         if let Some((name, status)) = extract_test_info_enhanced(line) {
-            let before = if i >= 2 { lines[i-2..i].iter().map(|s| s.to_string()).collect() } else { vec![] };
-            let after = if i+1 < lines.len() { lines[i+1..std::cmp::min(lines.len(), i+3)].iter().map(|s| s.to_string()).collect() } else { vec![] };
+            let window = heuristics.context_window;
+            let before = if i >= window { lines[i-window..i].iter().map(|s| s.to_string()).collect() } else { vec![] };
+            let after = if i+1 < lines.len() { lines[i+1..std::cmp::min(lines.len(), i+1+window)].iter().map(|s| s.to_string()).collect() } else { vec![] };
             per_file.entry(current_file.clone()).or_default().push(Occur{ test_name: name, status, line_no: i, context_before: before, context_after: after });
         }
     }
 
+    // The same name in two binaries is two different tests; a binary that ran twice
+    // (concatenated runs) keeps one label, so its repeats are still compared
     let mut out = vec![];
-    let mut by_name: HashMap<String, Vec<Occur>> = HashMap::new();
-    for (_file, occs) in per_file {
+    for (file, occs) in per_file {
+        let mut by_name: HashMap<String, Vec<Occur>> = HashMap::new();
         for o in occs { by_name.entry(o.test_name.clone()).or_default().push(o); }
-    }
-    for (name, list) in by_name {
-        if list.len() > 1 && is_true_duplicate(&list) {
-            let places: Vec<String> = list.iter().map(|o| format!("line {}", o.line_no)).collect();
-            out.push(format!("{} (appears {} times: {})", name, places.len(), places.join(", ")));
+        for (name, mut list) in by_name {
+            if list.len() > 1 && is_true_duplicate(&list, heuristics) {
+                list.sort_by_key(|o| o.line_no);
+                out.push(DetectedDuplicate {
+                    test_name: name,
+                    source: (file != "unknown").then(|| file.clone()),
+                    occurrences: list.into_iter().map(|o| DuplicateOccurrence {
+                        line: o.line_no + 1,
+                        status: o.status,
+                        context_before: o.context_before,
+                        context_after: o.context_after,
+                    }).collect(),
+                });
+            }
         }
     }
+    out.sort_by(|a, b| {
+        a.test_name.cmp(&b.test_name).then_with(|| a.occurrences.first().map(|o| o.line).cmp(&b.occurrences.first().map(|o| o.line)))
+    });
+    out.truncate(MAX_DUPLICATES_PER_LOG);
     out
 }
 
@@ -977,7 +1660,7 @@ test result: ok. 4 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; fini
 
         println!("Testing log analysis with file paths: {:?}", file_paths);
         
-        match log_checker.analyze_logs(&file_paths, "rust", &fail_to_pass_tests, &pass_to_pass_tests) {
+        match log_checker.analyze_logs(&file_paths, "rust", &fail_to_pass_tests, &pass_to_pass_tests, None) {
             Ok(result) => {
                 println!("Log analysis successful!");
                 let total = result.test_statuses.f2p.len() + result.test_statuses.p2p.len();
@@ -992,4 +1675,235 @@ test result: ok. 4 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; fini
         // Clean up
         std::fs::remove_dir_all(&temp_dir).unwrap();
     }
+
+    #[test]
+    fn test_missing_stage_log_is_reported_not_fatal() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let base_log_path = temp_dir.path().join("repo_base.log");
+        let after_log_path = temp_dir.path().join("repo_after.log");
+        fs::write(&base_log_path, "test keep_working ... ok\ntest new_feature ... FAILED\n").unwrap();
+        fs::write(&after_log_path, "test keep_working ... ok\ntest new_feature ... ok\n").unwrap();
+        let file_paths = vec![
+            base_log_path.to_string_lossy().to_string(),
+            after_log_path.to_string_lossy().to_string(),
+        ];
+
+        let result = LogParser::new()
+            .analyze_logs(&file_paths, "rust", &["new_feature".to_string()], &["keep_working".to_string()], None)
+            .unwrap();
+
+        assert_eq!(result.debug_info.missing_stages, vec!["before".to_string()]);
+        assert_eq!(result.test_statuses.f2p["new_feature"].before, NO_LOG_STATUS);
+        assert_eq!(result.test_statuses.f2p["new_feature"].after, "passed");
+        let violations = &result.rule_violations;
+        assert!(!violations.c1_failed_in_base_present_in_p2p.has_problem);
+        assert!(!violations.c2_failed_in_after_present_in_f2p_or_p2p.has_problem);
+        assert_eq!(violations.c3_f2p_success_in_before.examples, vec!["Not evaluated: before log missing".to_string()]);
+        assert!(violations.c4_p2p_missing_in_base_and_not_passing_in_before.examples.is_empty());
+    }
+
+    #[test]
+    fn test_skipped_f2p_in_after_is_c11_not_c2() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let before_log_path = temp_dir.path().join("repo_before.log");
+        let after_log_path = temp_dir.path().join("repo_after.log");
+        fs::write(&before_log_path, "test keep_working ... ok\ntest new_feature ... FAILED\n").unwrap();
+        fs::write(&after_log_path, "test keep_working ... ok\ntest new_feature ... ignored\n").unwrap();
+        let file_paths = vec![
+            before_log_path.to_string_lossy().to_string(),
+            after_log_path.to_string_lossy().to_string(),
+        ];
+
+        let result = LogParser::new()
+            .analyze_logs(&file_paths, "rust", &["new_feature".to_string()], &["keep_working".to_string()], None)
+            .unwrap();
+
+        assert_eq!(result.test_statuses.f2p["new_feature"].after, "ignored");
+        assert!(!result.rule_violations.c2_failed_in_after_present_in_f2p_or_p2p.has_problem);
+        assert_eq!(result.rule_violations.c11_f2p_skipped_in_after_or_agent.examples, vec!["new_feature (skipped in after)".to_string()]);
+        assert!(result.rules_evaluated.contains(&"C11".to_string()));
+    }
+
+    #[test]
+    fn test_recheck_rule_reads_stored_statuses_and_current_patches() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let base_log_path = temp_dir.path().join("repo_base.log");
+        let after_log_path = temp_dir.path().join("repo_after.log");
+        fs::write(&base_log_path, "test keep_working ... ok\ntest new_feature ... FAILED\n").unwrap();
+        fs::write(&after_log_path, "test keep_working ... ok\ntest new_feature ... ok\n").unwrap();
+        let mut file_paths = vec![
+            base_log_path.to_string_lossy().to_string(),
+            after_log_path.to_string_lossy().to_string(),
+        ];
+        let (f2p, p2p) = (vec!["new_feature".to_string()], vec!["keep_working".to_string()]);
+        let mut result = LogParser::new().analyze_logs(&file_paths, "rust", &f2p, &p2p, None).unwrap();
+        assert!(!result.rule_violations.c7_f2p_tests_in_golden_source_diff.has_problem);
+
+        // A source patch added after the analysis is picked up by C7
+        fs::create_dir(temp_dir.path().join("patches")).unwrap();
+        let patch_path = temp_dir.path().join("patches").join("golden_source.diff");
+        fs::write(&patch_path, "+#[test]\n+fn new_feature() {}\n").unwrap();
+        file_paths.push(patch_path.to_string_lossy().to_string());
+        let c7 = recheck_rule("C7", &result, &file_paths, &f2p, &p2p, &HashMap::new()).unwrap();
+        assert!(c7.has_problem);
+        assert!(c7.examples[0].starts_with("new_feature (found as 'new_feature' in golden_source.diff"));
+
+        // Status rules use the stored statuses rather than parsing the logs again
+        result.test_statuses.p2p.get_mut("keep_working").unwrap().after = "failed".to_string();
+        let c2 = recheck_rule("C2", &result, &file_paths, &f2p, &p2p, &HashMap::new()).unwrap();
+        assert_eq!(c2.examples, vec!["keep_working".to_string()]);
+        let ignored: HashMap<String, String> = [("patches/golden_source.diff".to_string(), "ignore".to_string())].into_iter().collect();
+        assert!(!recheck_rule("C7", &result, &file_paths, &f2p, &p2p, &ignored).unwrap().has_problem);
+        assert!(recheck_rule("C5", &result, &file_paths, &f2p, &p2p, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_duplicates_carry_one_based_lines() {
+        let log = "test flaky::retry ... FAILED\ntest stable ... ok\ntest flaky::retry ... ok\n";
+
+        let dups = detect_same_file_duplicates(log, &DuplicateHeuristics::default());
+        assert_eq!(dups, vec![("flaky::retry".to_string(), vec![1, 3])]);
+        assert_eq!(describe_duplicate("flaky::retry", &dups[0].1), "flaky::retry (appears 2 times: line 1, line 3)");
+    }
+
+    #[test]
+    fn test_duplicate_occurrences_keep_status_and_context() {
+        let log = "test flaky::retry ... FAILED\ntest stable ... ok\ntest flaky::retry ... ok\n";
+        let heuristics = DuplicateHeuristics { context_window: 1, ..Default::default() };
+        let dups = detect_duplicate_occurrences(log, &heuristics);
+        assert_eq!(dups.len(), 1);
+        assert_eq!(dups[0].source, None);
+        let statuses: Vec<(usize, &str)> = dups[0].occurrences.iter().map(|o| (o.line, o.status.as_str())).collect();
+        assert_eq!(statuses, vec![(1, "FAILED"), (3, "ok")]);
+        assert_eq!(dups[0].occurrences[1].context_before, vec!["test stable ... ok".to_string()]);
+    }
+
+    #[test]
+    fn test_duplicate_heuristics_from_config() {
+        // Same status twice, close together: only the proximity check flags it
+        let log = "test cache::warm ... ok\ntest other ... ok\ntest cache::warm ... ok\n";
+
+        let proximity_off = DuplicateHeuristics::from_config(&DuplicateDetectionConfig {
+            min_distance: Some(0),
+            context_window: Some(0),
+            require_status_conflict: false,
+        });
+        assert_eq!(detect_same_file_duplicates(log, &DuplicateHeuristics::default()).len(), 1);
+        assert!(detect_same_file_duplicates(log, &proximity_off).is_empty());
+
+        let conflict_only = DuplicateHeuristics { require_status_conflict: true, ..DuplicateHeuristics::default() };
+        assert!(detect_same_file_duplicates(log, &conflict_only).is_empty());
+        let conflicting = "test cache::warm ... FAILED\ntest cache::warm ... ok\n";
+        assert_eq!(detect_same_file_duplicates(conflicting, &conflict_only).len(), 1);
+    }
+
+    #[test]
+    fn test_duplicates_scoped_to_test_binary() {
+        // `tests::parse` exists in both binaries; only the rerun of the lib binary repeats it
+        let log = "     Running unittests src/lib.rs (target/debug/deps/parser-1a2b3c4d)\n\
+                   test tests::parse ... ok\n\
+                   \x20    Running tests/cli.rs (target/debug/deps/cli-4d5e6f7a)\n\
+                   test tests::parse ... FAILED\n\
+                   \x20    Running unittests src/lib.rs (target/debug/deps/parser-1a2b3c4d)\n\
+                   test tests::parse ... FAILED\n";
+
+        let dups = detect_same_file_duplicates(log, &DuplicateHeuristics::default());
+        assert_eq!(dups, vec![("tests::parse".to_string(), vec![2, 6])]);
+    }
+
+    #[test]
+    fn test_duplicates_capped_per_log() {
+        let log: String = (0..MAX_DUPLICATES_PER_LOG + 5)
+            .map(|i| format!("test case_{:03} ... ok\ntest case_{:03} ... ok\n", i, i))
+            .collect();
+        let dups = detect_duplicate_occurrences(&log, &DuplicateHeuristics::default());
+        assert_eq!(dups.len(), MAX_DUPLICATES_PER_LOG);
+        assert_eq!(dups.last().unwrap().test_name, format!("case_{:03}", MAX_DUPLICATES_PER_LOG - 1));
+    }
+
+    #[test]
+    fn test_parser_warnings() {
+        let stats = PreprocessStats { noise_lines: 0, stitched_lines: 0 };
+        let mut parsed = ParsedLog::new();
+        let warnings = parse_warnings(&parsed, "Compiling foo v0.1.0\n", stats);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ParseWarningKind::NoResults);
+        assert_eq!(warnings[0].message, "No test results recognized; the log may need a different parser");
+
+        parsed.passed.insert("flaky".to_string());
+        parsed.failed.insert("flaky".to_string());
+        parsed.finalize();
+        let warnings = parse_warnings(&parsed, "test flaky ... ok", stats);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ParseWarningKind::ConflictingStatus);
+        assert_eq!(warnings[0].message, "1 test reported as both passed and failed");
+    }
+
+    #[test]
+    fn test_status_lookup_keeps_files_apart() {
+        let mut parsed = ParsedLog::new();
+        parsed.failed.insert("src/a.test.ts > shared title".to_string());
+        parsed.passed.insert("src/b.test.ts > shared title".to_string());
+        parsed.passed.insert("src/a.test.ts > only in a".to_string());
+        parsed.passed.insert("bare title".to_string());
+        parsed.finalize();
+        let names: Vec<String> = ["src/a.test.ts > shared title", "src/c.test.ts > shared title", "shared title", "only in a", "src/a.test.ts > bare title"]
+            .iter().map(|name| name.to_string()).collect();
+        let statuses = LogParser::new().status_lookup(&names, &parsed, &LanguageDefaults::default());
+        assert_eq!(statuses["src/a.test.ts > shared title"], "failed");
+        // Another file's result for the same title is not this test's
+        assert_eq!(statuses["src/c.test.ts > shared title"], "missing");
+        // Nor is a title that two files share
+        assert_eq!(statuses["shared title"], "missing");
+        assert_eq!(statuses["only in a"], "passed");
+        assert_eq!(statuses["src/a.test.ts > bare title"], "passed");
+    }
+
+    #[test]
+    fn test_expand_collapsed_suites() {
+        let mut parsed = ParsedLog::new();
+        parsed.failed.insert("api > rejects bad tokens".to_string());
+        parsed.finalize();
+        let suite = |file: &str, tests: usize| CollapsedSuite { file: file.to_string(), tests };
+        parsed.collapsed_suites = vec![suite("src/utils.test.ts", 1), suite("src/api.test.ts", 2), suite("src/lib.test.ts", 5), suite("src/empty.test.ts", 0)];
+        let listed = vec![
+            "src/utils.test.ts > slugify > lowercases".to_string(),
+            // The summary counts five tests, but only one is listed
+            "src/lib.test.ts > lib > loads".to_string(),
+            "src/api.test.ts > api > rejects bad tokens".to_string(),
+            "src/api.test.ts > api > accepts good tokens".to_string(),
+            "src/other.test.ts > other".to_string(),
+            "slugify > trims".to_string(),
+        ];
+
+        expand_collapsed_suites(&mut parsed, &listed);
+        let mut inferred: Vec<&str> = parsed.inferred_passes.iter().map(|i| i.test_name.as_str()).collect();
+        inferred.sort();
+        assert_eq!(inferred, vec!["src/api.test.ts > api > accepts good tokens", "src/utils.test.ts > slugify > lowercases"]);
+        assert!(parsed.passed.contains("src/utils.test.ts > slugify > lowercases"));
+        assert!(!parsed.passed.contains("src/api.test.ts > api > rejects bad tokens"));
+        assert_eq!(parsed.warnings[0].kind, ParseWarningKind::InferredPasses);
+    }
+
+    #[test]
+    fn test_parse_quality_warnings() {
+        let mut parsed = ParsedLog::new();
+        parsed.passed.insert("cache::warm".to_string());
+        parsed.passed.insert("Cache::Warm".to_string());
+        parsed.failed.insert("✓ known marker".to_string());
+        parsed.finalize();
+        let log = "test cache::warm ... ok\n\
+                   test cache::cold ... TIMEOUT\n\
+                   ✅ renders the header\n\
+                   ✓ known marker\n\
+                   test_login (accounts.tests.LoginTests) ... FAILED";
+        let stats = PreprocessStats { noise_lines: 4, stitched_lines: 0 };
+
+        let warnings = parse_warnings(&parsed, log, stats);
+        let kinds: Vec<ParseWarningKind> = warnings.iter().map(|w| w.kind).collect();
+        assert_eq!(kinds, vec![ParseWarningKind::UnrecognizedStatus, ParseWarningKind::NameCollision, ParseWarningKind::NoiseStripped]);
+        assert_eq!(warnings[0].examples, vec!["test cache::cold ... TIMEOUT".to_string(), "✅ renders the header".to_string()]);
+        assert_eq!(warnings[1].examples, vec!["Cache::Warm | cache::warm".to_string()]);
+        assert_eq!(warnings[2].count, 4);
+    }
 }
@@ -0,0 +1,310 @@
+//! Log preprocessing shared by the parsers and log search
+//!
+//! Windows runners write `\r\n` line endings, sometimes mixed with stray `\r` from
+//! progress output; every ending is normalized to `\n` first so `$`-anchored regexes
+//! and rendered context lines behave the same for every log.
+//!
+//! CI systems often prefix every line with a timestamp (`2024-05-01T10:00:00.123Z `) or a
+//! step/service marker (`build | `), which breaks the line-anchored parser regexes. When
+//! most lines carry the same kind of prefix it is stripped before parsing; line numbers
+//! are preserved so search results still point at the original file.
+//!
+//! Container build and dependency install output (Docker layers, pip/npm installs, git
+//! clone progress) is blanked out the same way, using the `noise_filter` patterns from
+//! the server config plus the log's language's own `noise_patterns`, so it never reaches
+//! the parsers, duplicate detection or search.
+//!
+//! Last, test lines split apart by parallel runners are stitched back together (see
+//! `line_stitch`), so every language parser gets the reassembly the Rust parser used to
+//! do on its own.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::api::language_defaults::LanguageDefaults;
+use crate::api::line_stitch::stitch_split_status_lines;
+use crate::api::server_config::{load_server_config, NoiseFilterConfig};
+
+pub const DEFAULT_NOISE_PATTERNS: &[&str] = &[
+    // Docker classic and BuildKit output
+    r"^Step \d+/\d+ : ",
+    r"^ ---> ",
+    r"^Sending build context to Docker daemon",
+    r"^Removing intermediate container ",
+    r"^Successfully (?:built|tagged) ",
+    r"^#\d+ (?:\[|DONE |CACHED|sha256:|extracting |resolve |transferring |naming to |exporting |writing image )",
+    // pip
+    r"^(?:Collecting|Downloading|Obtaining|Building wheels? for|Installing collected packages|Successfully installed|Requirement already satisfied|Using cached|Attempting uninstall|Found existing installation|Uninstalling|Created wheel for|Stored in directory)[ :]",
+    r"^\s*(?:Downloading|Building wheel for|Running setup\.py|Preparing metadata|Getting requirements to build) ",
+    // npm / yarn
+    r"^npm (?:WARN|notice|http fetch) ",
+    r"^(?:added|removed|changed|up to date,?) .*packages? ",
+    r"^\d+ packages? (?:are|is) looking for funding",
+    r"^\[\d/\d\] (?:Resolving|Fetching|Linking|Building)",
+    // git clone progress
+    r"^Cloning into '",
+    r"^remote: (?:Enumerating|Counting|Compressing|Total) ",
+    r"^(?:Receiving objects|Resolving deltas|Updating files):\s+\d+%",
+];
+
+// Share of non-empty lines that must carry a prefix before it is treated as uniform
+const UNIFORM_PREFIX_RATIO: f64 = 0.8;
+const MIN_PREFIXED_LINES: usize = 3;
+// A timestamp and a step marker can be stacked
+const MAX_PREFIX_PASSES: usize = 2;
+
+lazy_static! {
+    static ref PREFIX_PATTERNS: Vec<Regex> = vec![
+        // ISO 8601, as written by GitHub Actions and most log shippers
+        Regex::new(r"^\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:[.,]\d+)?(?:Z|[+-]\d{2}:?\d{2})?\s?").unwrap(),
+        // Bracketed timestamps: [10:00:00], [10:00:00.123], [2024-05-01 10:00:00]
+        Regex::new(r"^\[(?:\d{4}-\d{2}-\d{2}[T ])?\d{2}:\d{2}:\d{2}(?:[.,]\d+)?(?:Z|[+-]\d{2}:?\d{2})?\]\s?").unwrap(),
+        // Step or service markers: `build | `, `web_1  | `
+        Regex::new(r"^[\w.-]+\s*\|\s?").unwrap(),
+    ];
+    // Compiled filters per language, recompiled only when the noise settings or the language's
+    // patterns in the config change
+    static ref NOISE_FILTERS: Mutex<HashMap<Option<String>, CachedNoiseFilter>> = Mutex::new(HashMap::new());
+}
+
+// The settings a cached filter was compiled from, and the filter
+type CachedNoiseFilter = (NoiseFilterConfig, Vec<String>, Arc<NoiseFilter>);
+
+fn non_empty_lines(content: &str) -> impl Iterator<Item = &str> {
+    content.lines().filter(|line| !line.trim().is_empty())
+}
+
+// The first prefix pattern carried by nearly every non-empty line
+fn uniform_prefix(content: &str) -> Option<&'static Regex> {
+    let total = non_empty_lines(content).count();
+    if total < MIN_PREFIXED_LINES {
+        return None;
+    }
+    PREFIX_PATTERNS.iter().find(|pattern| {
+        let prefixed = non_empty_lines(content).filter(|line| pattern.is_match(line)).count();
+        prefixed >= MIN_PREFIXED_LINES && prefixed as f64 >= total as f64 * UNIFORM_PREFIX_RATIO
+    })
+}
+
+/// Strip uniform timestamp/step prefixes, keeping one output line per input line
+pub fn strip_line_prefixes(content: &str) -> String {
+    let mut current = content.to_string();
+    for _ in 0..MAX_PREFIX_PASSES {
+        let Some(pattern) = uniform_prefix(&current) else { break };
+        let stripped: Vec<&str> = current
+            .lines()
+            .map(|line| pattern.find(line).map_or(line, |m| &line[m.end()..]))
+            .collect();
+        current = stripped.join("\n");
+    }
+    current
+}
+
+/// Compiled noise patterns; lines matching any of them are excluded from analysis
+pub struct NoiseFilter {
+    patterns: Vec<Regex>,
+}
+
+impl NoiseFilter {
+    pub fn from_config(config: &NoiseFilterConfig) -> Self {
+        Self::with_language_patterns(config, &[])
+    }
+
+    /// `from_config` plus a language's own patterns; turning the filter off turns those off too
+    pub fn with_language_patterns(config: &NoiseFilterConfig, language_patterns: &[String]) -> Self {
+        if config.disabled {
+            return Self { patterns: Vec::new() };
+        }
+        let mut configured: Vec<&str> = if config.patterns.is_empty() {
+            DEFAULT_NOISE_PATTERNS.to_vec()
+        } else {
+            config.patterns.iter().map(|p| p.as_str()).collect()
+        };
+        configured.extend(language_patterns.iter().map(|p| p.as_str()));
+        let patterns = configured
+            .into_iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    eprintln!("Ignoring noise filter pattern with invalid regex '{}': {}", pattern, e);
+                    None
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// The configured filter, with the language's patterns when the log's language is known
+    pub fn load(language: Option<&str>) -> Arc<Self> {
+        let config = load_server_config();
+        let language_patterns = language
+            .map(|language| LanguageDefaults::from_config(&config, language).noise_patterns)
+            .unwrap_or_default();
+        let key = language.map(str::to_string);
+        let mut filters = NOISE_FILTERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((cached_config, cached_patterns, filter)) = filters.get(&key) {
+            if *cached_config == config.noise_filter && *cached_patterns == language_patterns {
+                return filter.clone();
+            }
+        }
+        let filter = Arc::new(Self::with_language_patterns(&config.noise_filter, &language_patterns));
+        filters.insert(key, (config.noise_filter, language_patterns, filter.clone()));
+        filter
+    }
+
+    pub fn is_noise(&self, line: &str) -> bool {
+        self.patterns.iter().any(|pattern| pattern.is_match(line))
+    }
+
+    /// Number of non-empty lines `apply` would blank out
+    pub fn count_noise(&self, content: &str) -> usize {
+        if self.patterns.is_empty() {
+            return 0;
+        }
+        content.lines().filter(|line| !line.trim().is_empty() && self.is_noise(line)).count()
+    }
+
+    /// Blank out noise lines, keeping one output line per input line
+    pub fn apply(&self, content: &str) -> String {
+        if self.patterns.is_empty() {
+            return content.to_string();
+        }
+        content
+            .lines()
+            .map(|line| if self.is_noise(line) { "" } else { line })
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+}
+
+/// Turn `\r\n` and lone `\r` line endings into `\n`
+pub fn normalize_line_endings(content: &str) -> String {
+    if !content.contains('\r') {
+        return content.to_string();
+    }
+    content.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Normalize line endings, strip uniform line prefixes, drop build/install noise, then
+/// reassemble test lines split by interleaved output
+pub fn preprocess_log(content: &str) -> String {
+    preprocess_log_with_stats(content, None).0
+}
+
+/// `preprocess_log` with the language's noise patterns added
+pub fn preprocess_language_log(content: &str, language: Option<&str>) -> String {
+    preprocess_log_with_stats(content, language).0
+}
+
+/// `preprocess_log` without the noise filter, for searches that should see build/install output too
+pub fn preprocess_log_keeping_noise(content: &str) -> String {
+    stitch_split_status_lines(&strip_line_prefixes(&normalize_line_endings(content))).0
+}
+
+/// `preprocess_log` with the built-in noise patterns whatever the server config says, so
+/// fixture logs preprocess the same on every machine
+pub fn preprocess_log_with_default_noise(content: &str) -> String {
+    let stripped = strip_line_prefixes(&normalize_line_endings(content));
+    let noise_filter = NoiseFilter::from_config(&NoiseFilterConfig::default());
+    stitch_split_status_lines(&noise_filter.apply(&stripped)).0
+}
+
+/// What preprocessing changed in a log, reported as parser warnings
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PreprocessStats {
+    pub noise_lines: usize,
+    pub stitched_lines: usize,
+}
+
+/// `preprocess_language_log`, also counting the lines blanked as noise and the status lines stitched back
+pub fn preprocess_log_with_stats(content: &str, language: Option<&str>) -> (String, PreprocessStats) {
+    let stripped = strip_line_prefixes(&normalize_line_endings(content));
+    let noise_filter = NoiseFilter::load(language);
+    let noise_lines = noise_filter.count_noise(&stripped);
+    let (stitched, stitched_lines) = stitch_split_status_lines(&noise_filter.apply(&stripped));
+    (stitched, PreprocessStats { noise_lines, stitched_lines })
+}
+
+/// Read a log file and run it through `preprocess_log`
+pub fn read_log_file(file_path: &str) -> Result<String, String> {
+    read_log_file_with_stats(file_path, None).map(|(content, _)| content)
+}
+
+/// Read a log file and run it through `preprocess_language_log`
+pub fn read_language_log_file(file_path: &str, language: &str) -> Result<String, String> {
+    read_log_file_with_stats(file_path, Some(language)).map(|(content, _)| content)
+}
+
+/// Read a log file and run it through `preprocess_log_with_stats`
+pub fn read_log_file_with_stats(file_path: &str, language: Option<&str>) -> Result<(String, PreprocessStats), String> {
+    let content = std::fs::read_to_string(file_path)
+        .map_err(|e| format!("Failed to read log file {}: {}", file_path, e))?;
+    Ok(preprocess_log_with_stats(&content, language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_stacked_uniform_prefixes() {
+        let log = "2024-05-01T10:00:00.1234567Z build | running 2 tests\n\
+                   2024-05-01T10:00:01.0000000Z build | test parser::works ... ok\n\
+                   \n\
+                   2024-05-01T10:00:02Z build | test parser::fails ... FAILED\n\
+                   2024-05-01T10:00:03Z build | test result: FAILED. 1 passed; 1 failed";
+
+        let stripped = strip_line_prefixes(log);
+        let lines: Vec<&str> = stripped.lines().collect();
+        assert_eq!(lines[1], "test parser::works ... ok");
+        assert_eq!(lines[2], "");
+        assert_eq!(lines[3], "test parser::fails ... FAILED");
+    }
+
+    #[test]
+    fn test_leaves_mixed_logs_untouched() {
+        let log = "running 3 tests\n[10:00:00] note\ntest a ... ok\ntest b ... ok\ntest c ... FAILED";
+        assert_eq!(strip_line_prefixes(log), log);
+    }
+
+    #[test]
+    fn test_normalizes_mixed_line_endings() {
+        let log = "running 2 tests\r\ntest a ... ok\r\ndownloading 50%\rdownloading 100%\ntest b ... FAILED\r\n";
+        assert_eq!(
+            normalize_line_endings(log),
+            "running 2 tests\ntest a ... ok\ndownloading 50%\ndownloading 100%\ntest b ... FAILED\n"
+        );
+    }
+
+    #[test]
+    fn test_noise_filter_blanks_build_output() {
+        let log = "Step 3/7 : RUN pip install -e .\n\
+                   Collecting pytest==7.4.0\n\
+                   Successfully installed pytest-7.4.0\n\
+                   Cloning into 'repo'...\n\
+                   Receiving objects: 100% (120/120), done.\n\
+                   added 312 packages in 4s\n\
+                   tests/test_api.py::test_install PASSED";
+
+        let filter = NoiseFilter::from_config(&NoiseFilterConfig::default());
+        assert_eq!(filter.count_noise(log), 6);
+        let filtered = filter.apply(log);
+        let lines: Vec<&str> = filtered.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert!(lines[..6].iter().all(|line| line.is_empty()));
+        assert_eq!(lines[6], "tests/test_api.py::test_install PASSED");
+
+        let disabled = NoiseFilterConfig { disabled: true, ..Default::default() };
+        assert_eq!(NoiseFilter::from_config(&disabled).apply(log), log);
+    }
+
+    #[test]
+    fn test_loaded_filter_is_compiled_once() {
+        assert!(Arc::ptr_eq(&NoiseFilter::load(Some("python")), &NoiseFilter::load(Some("python"))));
+        let log = "Collecting pytest==7.4.0\r\ntests/test_api.py::test_install PASSED";
+        assert_eq!(preprocess_log_keeping_noise(log), "Collecting pytest==7.4.0\ntests/test_api.py::test_install PASSED");
+    }
+}
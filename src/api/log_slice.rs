@@ -0,0 +1,109 @@
+//! Lines of a log around one line number, for hover previews and the open-at-line viewer
+//!
+//! Previews on violation examples and duplicate entries only need a handful of lines, and
+//! the viewer opened from a search hit a few hundred, so
+//! the log is streamed up to the end of the window instead of being read (and shipped to
+//! the viewer) whole. Line endings are split the way `normalize_line_endings` does, so
+//! line numbers agree with the file viewer and the search results.
+
+use std::io::BufRead;
+
+use crate::app::types::LogSlice;
+
+pub const DEFAULT_SLICE_RADIUS: usize = 3;
+// Enough for the open-at-line viewer; keeps a request from turning into a file download
+pub const MAX_SLICE_RADIUS: usize = 500;
+
+/// Lines `line - radius ..= line + radius` (1-based) of the log; `None` past the end of the log
+pub fn read_log_slice<R: BufRead>(reader: R, line: usize, radius: usize) -> Result<Option<LogSlice>, String> {
+    let line = line.max(1);
+    let radius = radius.min(MAX_SLICE_RADIUS);
+    let first_line = line.saturating_sub(radius).max(1);
+    let last_line = line + radius;
+
+    let mut lines = Vec::new();
+    let mut number = 0;
+    'read: for chunk in reader.split(b'\n') {
+        let chunk = chunk.map_err(|e| format!("Failed to read log: {}", e))?;
+        // `\r\n` ends one line, a lone `\r` ends another
+        let chunk = chunk.strip_suffix(b"\r").unwrap_or(&chunk);
+        for part in chunk.split(|byte| *byte == b'\r') {
+            number += 1;
+            if number > last_line {
+                break 'read;
+            }
+            if number >= first_line {
+                lines.push(String::from_utf8_lossy(part).into_owned());
+            }
+        }
+    }
+
+    if number < line {
+        return Ok(None);
+    }
+    Ok(Some(LogSlice { line, first_line, lines }))
+}
+
+/// A whole log split into lines the way `read_log_slice` splits them, for callers that cut
+/// many slices out of the same log
+pub struct LogLines(Vec<String>);
+
+impl LogLines {
+    pub fn read<R: BufRead>(reader: R) -> Result<Self, String> {
+        let mut lines = Vec::new();
+        for chunk in reader.split(b'\n') {
+            let chunk = chunk.map_err(|e| format!("Failed to read log: {}", e))?;
+            let chunk = chunk.strip_suffix(b"\r").unwrap_or(&chunk);
+            lines.extend(chunk.split(|byte| *byte == b'\r').map(|part| String::from_utf8_lossy(part).into_owned()));
+        }
+        Ok(Self(lines))
+    }
+
+    /// Same window as `read_log_slice`
+    pub fn slice(&self, line: usize, radius: usize) -> Option<LogSlice> {
+        let line = line.max(1);
+        if line > self.0.len() {
+            return None;
+        }
+        let radius = radius.min(MAX_SLICE_RADIUS);
+        let first_line = line.saturating_sub(radius).max(1);
+        let last_line = (line + radius).min(self.0.len());
+        Some(LogSlice { line, first_line, lines: self.0[first_line - 1..last_line].to_vec() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slices_around_line() {
+        let log = "one\r\ntwo\rthree\nfour\nfive\nsix\n";
+
+        let slice = read_log_slice(log.as_bytes(), 3, 1).unwrap().unwrap();
+        assert_eq!(slice, LogSlice { line: 3, first_line: 2, lines: vec!["two".to_string(), "three".to_string(), "four".to_string()] });
+
+        let start = read_log_slice(log.as_bytes(), 1, 2).unwrap().unwrap();
+        assert_eq!((start.first_line, start.lines.len()), (1, 3));
+        let end = read_log_slice(log.as_bytes(), 6, 2).unwrap().unwrap();
+        assert_eq!(end.lines, vec!["four", "five", "six"]);
+        assert_eq!(read_log_slice(log.as_bytes(), 9, 2).unwrap(), None);
+    }
+
+    #[test]
+    fn test_log_lines_slice_like_read_log_slice() {
+        let log = "one\r\ntwo\rthree\nfour\nfive\nsix\n";
+        let lines = LogLines::read(log.as_bytes()).unwrap();
+        for (line, radius) in [(3, 1), (1, 2), (6, 2), (9, 2)] {
+            assert_eq!(lines.slice(line, radius), read_log_slice(log.as_bytes(), line, radius).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_radius_is_capped() {
+        let log = (1..=2000).map(|n| n.to_string()).collect::<Vec<_>>().join("\n");
+        let slice = read_log_slice(log.as_bytes(), 1000, usize::MAX).unwrap().unwrap();
+        assert_eq!(slice.first_line, 1000 - MAX_SLICE_RADIUS);
+        assert_eq!(slice.lines.len(), 2 * MAX_SLICE_RADIUS + 1);
+    }
+}
@@ -0,0 +1,90 @@
+//! Slack and Microsoft Teams notifications for recorded review decisions
+//!
+//! Both notifiers are optional and configured in the `notifications` section of the
+//! server config file. Delivery is best effort and never fails the decision request.
+
+use serde_json::json;
+
+use crate::api::server_config::load_server_config;
+use crate::app::types::ReviewDecision;
+
+fn verdict_label(verdict: &str) -> &'static str {
+    match verdict {
+        "approved" => "✅ Approved",
+        "rejected" => "❌ Rejected",
+        "needs_changes" => "⚠️ Needs changes",
+        _ => "❔ Unknown verdict",
+    }
+}
+
+fn decision_title(decision: &ReviewDecision) -> String {
+    let instance = if decision.instance_id.is_empty() { "deliverable" } else { &decision.instance_id };
+    format!("{} — {}", instance, verdict_label(&decision.verdict))
+}
+
+pub fn slack_payload(decision: &ReviewDecision) -> serde_json::Value {
+    let mut text = format!(
+        "*{}*\nReviewer: {}\n<{}|Open deliverable>",
+        decision_title(decision),
+        decision.reviewer,
+        decision.deliverable_link
+    );
+    if !decision.comment.trim().is_empty() {
+        text.push_str(&format!("\n> {}", decision.comment.trim()));
+    }
+    json!({ "text": text })
+}
+
+pub fn teams_payload(decision: &ReviewDecision) -> serde_json::Value {
+    let theme_color = match decision.verdict.as_str() {
+        "approved" => "2EB67D",
+        "rejected" => "E01E5A",
+        _ => "ECB22E",
+    };
+    json!({
+        "@type": "MessageCard",
+        "@context": "https://schema.org/extensions",
+        "themeColor": theme_color,
+        "summary": decision_title(decision),
+        "title": decision_title(decision),
+        "sections": [{
+            "facts": [
+                { "name": "Reviewer", "value": decision.reviewer },
+                { "name": "Verdict", "value": verdict_label(&decision.verdict) },
+                { "name": "Comment", "value": decision.comment },
+            ]
+        }],
+        "potentialAction": [{
+            "@type": "OpenUri",
+            "name": "Open deliverable",
+            "targets": [{ "os": "default", "uri": decision.deliverable_link }]
+        }]
+    })
+}
+
+async fn post_json(client: &reqwest::Client, target: &str, url: &str, payload: &serde_json::Value) {
+    match client
+        .post(url)
+        .timeout(std::time::Duration::from_secs(10))
+        .json(payload)
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => {}
+        Ok(resp) => eprintln!("{} notification responded with {}", target, resp.status()),
+        Err(e) => eprintln!("Failed to send {} notification: {}", target, e),
+    }
+}
+
+/// Post the decision to every configured chat integration
+pub async fn notify_decision(decision: &ReviewDecision) {
+    let config = load_server_config().notifications;
+    let client = reqwest::Client::new();
+
+    if let Some(url) = config.slack_webhook_url.as_deref().filter(|u| !u.is_empty()) {
+        post_json(&client, "Slack", url, &slack_payload(decision)).await;
+    }
+    if let Some(url) = config.teams_webhook_url.as_deref().filter(|u| !u.is_empty()) {
+        post_json(&client, "Teams", url, &teams_payload(decision)).await;
+    }
+}
@@ -0,0 +1,61 @@
+//! P2P list breadth (rule C10)
+//!
+//! A P2P list covering a sliver of the suite that ran in the base log is often
+//! cherry-picked, leaving regressions in the rest of the suite unchecked. The rule warns
+//! when the list falls under a configured share of the base log's tests; small suites are
+//! left alone since a handful of tests can legitimately be all there is.
+
+use crate::api::server_config::P2pBreadthConfig;
+
+pub const DEFAULT_MIN_PERCENT: f64 = 1.0;
+pub const DEFAULT_MIN_BASE_TESTS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct P2pBreadthThresholds {
+    /// Smallest P2P share of the base log's tests, in percent; 0 turns the rule off
+    pub min_percent: f64,
+    /// Base logs with fewer tests are not checked
+    pub min_base_tests: usize,
+}
+
+impl P2pBreadthThresholds {
+    pub fn from_config(config: &P2pBreadthConfig) -> Self {
+        Self {
+            min_percent: config.min_percent.unwrap_or(DEFAULT_MIN_PERCENT),
+            min_base_tests: config.min_base_tests.unwrap_or(DEFAULT_MIN_BASE_TESTS),
+        }
+    }
+}
+
+/// Warning when the P2P list is too small a share of the base log's tests
+pub fn check_p2p_breadth(p2p_count: usize, base_test_count: usize, thresholds: &P2pBreadthThresholds) -> Option<String> {
+    if thresholds.min_percent <= 0.0 || base_test_count == 0 || base_test_count < thresholds.min_base_tests {
+        return None;
+    }
+    let percent = p2p_count as f64 * 100.0 / base_test_count as f64;
+    if percent >= thresholds.min_percent {
+        return None;
+    }
+    Some(format!(
+        "P2P lists {} of the {} tests in the base log ({:.2}%, below {}%)",
+        p2p_count, base_test_count, percent, thresholds.min_percent
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_p2p_breadth() {
+        let thresholds = P2pBreadthThresholds::from_config(&P2pBreadthConfig::default());
+        assert_eq!(
+            check_p2p_breadth(3, 1000, &thresholds).as_deref(),
+            Some("P2P lists 3 of the 1000 tests in the base log (0.30%, below 1%)")
+        );
+        assert_eq!(check_p2p_breadth(10, 1000, &thresholds), None);
+        // Small suites and a disabled rule are never flagged
+        assert_eq!(check_p2p_breadth(0, 50, &thresholds), None);
+        assert_eq!(check_p2p_breadth(0, 1000, &P2pBreadthThresholds { min_percent: 0.0, ..thresholds }), None);
+    }
+}
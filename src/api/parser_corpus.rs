@@ -0,0 +1,268 @@
+//! Check the parsers against a corpus of real-world fixture logs before deploying
+//!
+//! Each fixture is a log, `<name>.log`, next to the statuses the parser should report for it,
+//! `<name>.expected.json`:
+//!
+//! ```json
+//! { "parser": "python", "framework": "pytest", "passed": ["..."], "failed": [], "ignored": [] }
+//! ```
+//!
+//! `parser` is a plugin name from the registry; without it the parser is picked by content
+//! sniff the way an analysis without a language would. `framework` pins the framework like a
+//! run script does. Folders are searched recursively, so the corpus can be grouped freely.
+//! Logs are preprocessed with the built-in noise filter, never the server config's.
+//! Run it with `swe-reviewer-web parser-corpus <dir>`; it prints every mismatch and exits
+//! non-zero when there is one, so it can gate a deploy.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::log_preprocess::preprocess_log_with_default_noise;
+use crate::api::parser_registry::ParserRegistry;
+
+pub const PARSER_CORPUS_COMMAND: &str = "parser-corpus";
+
+const EXPECTED_SUFFIX: &str = ".expected.json";
+
+#[derive(Deserialize, Default)]
+struct ExpectedOutput {
+    #[serde(default)]
+    parser: Option<String>,
+    #[serde(default)]
+    framework: Option<String>,
+    #[serde(default)]
+    passed: BTreeSet<String>,
+    #[serde(default)]
+    failed: BTreeSet<String>,
+    #[serde(default)]
+    ignored: BTreeSet<String>,
+}
+
+// Tests one status got wrong in one fixture
+#[derive(Serialize, Debug, PartialEq)]
+pub struct StatusMismatch {
+    pub status: String,
+    // Expected with this status but reported otherwise, or not at all
+    pub missing: Vec<String>,
+    // Reported with this status but not expected with it
+    pub unexpected: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FixtureResult {
+    pub log: String,
+    // Plugin name of the parser that ran, when one could be picked
+    pub parser: Option<String>,
+    // Why the fixture couldn't be checked: unreadable expectations, no parser, a parse error
+    pub error: Option<String>,
+    pub mismatches: Vec<StatusMismatch>,
+}
+
+impl FixtureResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.mismatches.is_empty()
+    }
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct CorpusReport {
+    pub fixtures: Vec<FixtureResult>,
+    // Logs without an expected-output file, skipped
+    pub unlabeled: Vec<String>,
+}
+
+impl CorpusReport {
+    pub fn failures(&self) -> usize {
+        self.fixtures.iter().filter(|fixture| !fixture.passed()).count()
+    }
+}
+
+fn collect_logs(dir: &Path, logs: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_logs(&path, logs)?;
+        } else if path.extension().is_some_and(|extension| extension == "log") {
+            logs.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn status_mismatch(status: &str, expected: &BTreeSet<String>, reported: &HashSet<String>) -> Option<StatusMismatch> {
+    let missing: Vec<String> = expected.iter().filter(|name| !reported.contains(*name)).cloned().collect();
+    let mut unexpected: Vec<String> = reported.iter().filter(|name| !expected.contains(*name)).cloned().collect();
+    unexpected.sort();
+    if missing.is_empty() && unexpected.is_empty() {
+        return None;
+    }
+    Some(StatusMismatch { status: status.to_string(), missing, unexpected })
+}
+
+fn check_fixture(registry: &ParserRegistry, log: &Path, expected_path: &Path) -> FixtureResult {
+    let mut result = FixtureResult {
+        log: log.display().to_string(),
+        parser: None,
+        error: None,
+        mismatches: Vec::new(),
+    };
+    let expected: ExpectedOutput = match std::fs::read_to_string(expected_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+    {
+        Ok(expected) => expected,
+        Err(e) => {
+            result.error = Some(format!("Invalid {}: {}", expected_path.display(), e));
+            return result;
+        }
+    };
+
+    // The built-in noise filter rather than the local config, so the corpus gates the same everywhere
+    let content = match std::fs::read_to_string(log) {
+        Ok(content) => preprocess_log_with_default_noise(&content),
+        Err(e) => {
+            result.error = Some(format!("Failed to read {}: {}", log.display(), e));
+            return result;
+        }
+    };
+    let parser = match &expected.parser {
+        Some(name) => registry.by_name(name).map(|parser| (name.clone(), parser)),
+        None => registry.detect(&content).map(|(plugin, parser)| (plugin.name.to_string(), parser)),
+    };
+    let Some((name, parser)) = parser else {
+        result.error = Some(match expected.parser {
+            Some(name) => format!("No parser named '{}'", name),
+            None => "No parser recognizes the log".to_string(),
+        });
+        return result;
+    };
+    result.parser = Some(name);
+
+    let parsed = match parser.parse_content(&log.to_string_lossy(), &content, expected.framework.as_deref()) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            result.error = Some(format!("Parse failed: {}", e));
+            return result;
+        }
+    };
+    result.mismatches = [
+        status_mismatch("passed", &expected.passed, &parsed.passed),
+        status_mismatch("failed", &expected.failed, &parsed.failed),
+        status_mismatch("ignored", &expected.ignored, &parsed.ignored),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    result
+}
+
+/// Run every fixture under `dir` through its parser, experimental parsers included
+pub fn run_parser_corpus(dir: &Path) -> Result<CorpusReport, String> {
+    let mut logs = Vec::new();
+    collect_logs(dir, &mut logs)?;
+    logs.sort();
+
+    let registry = ParserRegistry::all();
+    let mut report = CorpusReport::default();
+    for log in logs {
+        let stem = log.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        let expected_path = log.with_file_name(format!("{}{}", stem, EXPECTED_SUFFIX));
+        if expected_path.is_file() {
+            report.fixtures.push(check_fixture(&registry, &log, &expected_path));
+        } else {
+            report.unlabeled.push(log.display().to_string());
+        }
+    }
+    Ok(report)
+}
+
+/// `parser-corpus <dir> [--json]`: print the report, exit code 1 when a fixture fails
+pub fn run_parser_corpus_command(args: &[String]) -> i32 {
+    let Some(dir) = args.iter().find(|arg| !arg.starts_with("--")) else {
+        eprintln!("Usage: {} <fixture dir> [--json]", PARSER_CORPUS_COMMAND);
+        return 2;
+    };
+    let report = match run_parser_corpus(Path::new(dir)) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+
+    if args.iter().any(|arg| arg == "--json") {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Failed to serialize the report: {}", e),
+        }
+    } else {
+        for fixture in report.fixtures.iter().filter(|fixture| !fixture.passed()) {
+            println!("FAIL {} ({})", fixture.log, fixture.parser.as_deref().unwrap_or("no parser"));
+            if let Some(error) = &fixture.error {
+                println!("  {}", error);
+            }
+            for mismatch in &fixture.mismatches {
+                for name in &mismatch.missing {
+                    println!("  {}: missing {}", mismatch.status, name);
+                }
+                for name in &mismatch.unexpected {
+                    println!("  {}: unexpected {}", mismatch.status, name);
+                }
+            }
+        }
+        println!(
+            "{} fixture(s), {} failed, {} log(s) without expectations",
+            report.fixtures.len(),
+            report.failures(),
+            report.unlabeled.len()
+        );
+    }
+    if report.failures() > 0 { 1 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST_LOG: &str = "running 2 tests\n\
+                            test parser::parses ... ok\n\
+                            test parser::rejects_nul ... FAILED\n\
+                            \n\
+                            test result: FAILED. 1 passed; 1 failed; 0 ignored";
+
+    #[test]
+    fn test_corpus_reports_mismatches_and_unlabeled_logs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let nested = dir.path().join("rust");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("ok.log"), RUST_LOG).unwrap();
+        std::fs::write(nested.join("ok.expected.json"), r#"{"parser": "rust", "passed": ["parser::parses"], "failed": ["parser::rejects_nul"]}"#).unwrap();
+        std::fs::write(nested.join("wrong.log"), RUST_LOG).unwrap();
+        std::fs::write(nested.join("wrong.expected.json"), r#"{"passed": ["parser::parses", "parser::rejects_nul"]}"#).unwrap();
+        std::fs::write(dir.path().join("new.log"), RUST_LOG).unwrap();
+
+        let report = run_parser_corpus(dir.path()).unwrap();
+        assert_eq!(report.fixtures.len(), 2);
+        assert_eq!(report.unlabeled.len(), 1);
+        assert_eq!(report.failures(), 1);
+
+        let wrong = report.fixtures.iter().find(|fixture| fixture.log.ends_with("wrong.log")).unwrap();
+        assert_eq!(wrong.parser.as_deref(), Some("rust"));
+        assert_eq!(wrong.mismatches, vec![
+            StatusMismatch { status: "passed".to_string(), missing: vec!["parser::rejects_nul".to_string()], unexpected: vec![] },
+            StatusMismatch { status: "failed".to_string(), missing: vec![], unexpected: vec!["parser::rejects_nul".to_string()] },
+        ]);
+    }
+
+    #[test]
+    fn test_unknown_parser_is_an_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.log"), RUST_LOG).unwrap();
+        std::fs::write(dir.path().join("a.expected.json"), r#"{"parser": "cobol"}"#).unwrap();
+        let report = run_parser_corpus(dir.path()).unwrap();
+        assert_eq!(report.fixtures[0].error.as_deref(), Some("No parser named 'cobol'"));
+    }
+}
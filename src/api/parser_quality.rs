@@ -0,0 +1,119 @@
+//! How well each parser finds the listed tests, across every analyzed deliverable
+//!
+//! A listed test the parser can't find in a log reads as `missing`, which is sometimes the
+//! deliverable's fault but often the parser's. Pooling each analysis's match rates by
+//! language and parser shows which parsers lose tests most often and need work.
+
+use std::collections::BTreeMap;
+
+use crate::app::types::{LogAnalysisResult, ParserQuality, StageMatchRate};
+
+/// Sum the analyses' recorded match rates per language and parser, stages in pipeline order
+pub fn aggregate_match_rates(analyses: &[LogAnalysisResult]) -> Vec<ParserQuality> {
+    let mut groups: BTreeMap<(String, String), ParserQuality> = BTreeMap::new();
+    for analysis in analyses {
+        let (language, parser) = match &analysis.settings {
+            Some(settings) => (settings.language.clone(), settings.parser.clone()),
+            None => ("unknown".to_string(), "unknown".to_string()),
+        };
+        let group = groups.entry((language.clone(), parser.clone())).or_insert_with(|| ParserQuality {
+            language,
+            parser,
+            ..Default::default()
+        });
+        group.analyses += 1;
+        for rate in &analysis.match_rates {
+            let index = match group.stages.iter().position(|pooled| pooled.stage == rate.stage) {
+                Some(index) => index,
+                None => {
+                    group.stages.push(StageMatchRate { stage: rate.stage.clone(), ..Default::default() });
+                    group.stages.len() - 1
+                }
+            };
+            let pooled = &mut group.stages[index];
+            pooled.f2p_resolved += rate.f2p_resolved;
+            pooled.f2p_total += rate.f2p_total;
+            pooled.p2p_resolved += rate.p2p_resolved;
+            pooled.p2p_total += rate.p2p_total;
+        }
+    }
+    let order = ["base", "before", "after", "agent"];
+    let mut rows: Vec<ParserQuality> = groups.into_values().collect();
+    for row in &mut rows {
+        row.stages.sort_by_key(|rate| order.iter().position(|stage| *stage == rate.stage));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::types::{AnalysisSettings, StageStatusSummary, NO_LOG_STATUS};
+
+    fn analysis(parser: &str, f2p_after: &[&str], p2p_base: &[&str]) -> LogAnalysisResult {
+        let summary = |base: &str, after: &str| StageStatusSummary {
+            base: base.to_string(),
+            before: NO_LOG_STATUS.to_string(),
+            after: after.to_string(),
+            agent: NO_LOG_STATUS.to_string(),
+            report: "passed".to_string(),
+        };
+        let mut value = serde_json::json!({
+            "test_statuses": { "f2p": {}, "p2p": {} },
+            "rule_violations": {},
+            "debug_info": {},
+            "schema_version": 0,
+        });
+        value["settings"] = serde_json::to_value(AnalysisSettings {
+            language: "python".to_string(),
+            parser: parser.to_string(),
+            ..Default::default()
+        }).unwrap();
+        let mut result = crate::api::analysis_migration::migrate_analysis_result(value).unwrap();
+        for (i, status) in f2p_after.iter().enumerate() {
+            result.test_statuses.f2p.insert(format!("f2p_{}", i), summary("failed", status));
+        }
+        for (i, status) in p2p_base.iter().enumerate() {
+            result.test_statuses.p2p.insert(format!("p2p_{}", i), summary(status, "passed"));
+        }
+        result.match_rates = result.test_statuses.match_rates();
+        result
+    }
+
+    #[test]
+    fn test_match_rates_skip_stages_without_logs() {
+        let rates = analysis("pytest", &["passed", "missing"], &["passed"]).test_statuses.match_rates();
+        assert_eq!(rates.iter().map(|rate| rate.stage.as_str()).collect::<Vec<_>>(), vec!["base", "after"]);
+        assert_eq!(rates[1], StageMatchRate { stage: "after".to_string(), f2p_resolved: 1, f2p_total: 2, p2p_resolved: 1, p2p_total: 1 });
+        assert_eq!(rates[1].f2p_fraction(), Some(0.5));
+    }
+
+    #[test]
+    fn test_before_stage_leaves_out_f2p() {
+        let mut statuses = analysis("pytest", &["passed"], &["passed"]).test_statuses;
+        for summary in statuses.f2p.values_mut().chain(statuses.p2p.values_mut()) {
+            summary.before = "missing".to_string();
+        }
+        let before = statuses.match_rates().into_iter().find(|rate| rate.stage == "before").unwrap();
+        assert_eq!((before.f2p_total, before.p2p_resolved, before.p2p_total), (0, 0, 1));
+    }
+
+    #[test]
+    fn test_aggregates_per_parser() {
+        let rows = aggregate_match_rates(&[
+            analysis("pytest", &["passed", "missing"], &["missing"]),
+            analysis("pytest", &["passed", "passed"], &["passed"]),
+            analysis("unittest", &["passed"], &[]),
+        ]);
+        assert_eq!(rows.iter().map(|row| (row.parser.as_str(), row.analyses)).collect::<Vec<_>>(), vec![("pytest", 2), ("unittest", 1)]);
+        let after = rows[0].stages.iter().find(|rate| rate.stage == "after").unwrap();
+        assert_eq!((after.f2p_resolved, after.f2p_total), (3, 4));
+        let base = &rows[0].stages[0];
+        assert_eq!((base.stage.as_str(), base.p2p_resolved, base.p2p_total), ("base", 1, 2));
+        // The recorded rates are pooled, not recomputed from the statuses
+        let mut recorded = analysis("pytest", &["passed"], &[]);
+        recorded.match_rates[0].f2p_resolved = 0;
+        let rows = aggregate_match_rates(&[recorded]);
+        assert_eq!(rows[0].stages[0].f2p_resolved, 0);
+    }
+}
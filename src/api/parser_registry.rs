@@ -0,0 +1,243 @@
+//! Registry of the language log parsers
+//!
+//! Each parser describes itself with a `ParserPlugin`: the `main.json` languages it handles,
+//! the framework names a run script or detection rule can pin, and a content sniff used when
+//! the deliverable's language has no parser. Experimental parsers ship disabled and are
+//! switched on per deployment through the `parsers` section of the server config, so new
+//! language support can land before it is trusted by default.
+
+use crate::api::cpp_log_parser::{self, CppLogParser};
+use crate::api::go_log_parser::{self, GoLogParser};
+use crate::api::java_log_parser::{self, JavaLogParser};
+use crate::api::javascript_log_parser::{self, JavaScriptLogParser};
+use crate::api::log_parser::LogParserTrait;
+use crate::api::python_log_parser::{self, PythonLogParser};
+use crate::api::rust_log_parser::{self, RustLogParser};
+use crate::api::server_config::ParsersConfig;
+
+pub type BoxedLogParser = Box<dyn LogParserTrait + Send + Sync>;
+
+pub struct ParserPlugin {
+    /// Name used in the config's `experimental` and `disabled` lists
+    pub name: &'static str,
+    /// `main.json` language values routed to this parser, lowercase
+    pub languages: &'static [&'static str],
+    /// Framework names the parser accepts when pinned
+    pub frameworks: &'static [&'static str],
+    /// Disabled unless listed in the config's `experimental`
+    pub experimental: bool,
+    /// Whether a log looks like this parser's output
+    pub detect: fn(&str) -> bool,
+    /// Build the parser with the `parsers` settings the registry was made from
+    pub build: fn(&ParsersConfig) -> BoxedLogParser,
+}
+
+impl ParserPlugin {
+    pub fn accepts_framework(&self, framework: &str) -> bool {
+        self.frameworks.contains(&framework)
+    }
+}
+
+/// Name of the built-in parser a `main.json` language is routed to, so aliases such as `ts`
+/// and `golang` compare equal; unknown languages come back lowercased
+pub fn canonical_language(language: &str) -> String {
+    let language = language.trim().to_lowercase();
+    BUILTIN_PARSERS
+        .iter()
+        .find(|plugin| plugin.languages.contains(&language.as_str()))
+        .map(|plugin| plugin.name.to_string())
+        .unwrap_or(language)
+}
+
+fn detect_rust(content: &str) -> bool {
+    rust_log_parser::looks_nextest_format(content) || (content.contains("test result:") && content.contains(" ... "))
+}
+
+fn detect_python(content: &str) -> bool {
+    content.contains("pytest") || content.contains("short test summary info") || (content.contains("Ran ") && content.contains(" ... ok"))
+}
+
+fn detect_javascript(content: &str) -> bool {
+    ["Test Suites:", "Test Files", "vitest", "mocha", " passing (", "# pass "]
+        .iter()
+        .any(|marker| content.contains(marker))
+}
+
+fn detect_go(content: &str) -> bool {
+    go_log_parser::looks_go_test_output(content)
+}
+
+fn detect_java(content: &str) -> bool {
+    java_log_parser::looks_java_test_output(content)
+}
+
+fn detect_cpp(content: &str) -> bool {
+    cpp_log_parser::looks_cpp_test_output(content)
+}
+
+pub static BUILTIN_PARSERS: &[ParserPlugin] = &[
+    ParserPlugin {
+        name: "rust",
+        languages: &["rust"],
+        frameworks: &["cargo", "nextest"],
+        experimental: false,
+        detect: detect_rust,
+        build: |_| Box::new(RustLogParser::new()),
+    },
+    ParserPlugin {
+        name: "python",
+        languages: &["python"],
+        frameworks: python_log_parser::PINNABLE_FRAMEWORKS,
+        experimental: false,
+        detect: detect_python,
+        build: |_| Box::new(PythonLogParser::new()),
+    },
+    ParserPlugin {
+        name: "javascript",
+        languages: &["javascript", "typescript", "js", "ts"],
+        frameworks: javascript_log_parser::PINNABLE_FRAMEWORKS,
+        experimental: false,
+        detect: detect_javascript,
+        build: |config| Box::new(JavaScriptLogParser::from_config(config)),
+    },
+    ParserPlugin {
+        name: "go",
+        languages: &["go", "golang"],
+        frameworks: go_log_parser::PINNABLE_FRAMEWORKS,
+        experimental: false,
+        detect: detect_go,
+        build: |_| Box::new(GoLogParser::new()),
+    },
+    ParserPlugin {
+        name: "java",
+        languages: &["java"],
+        frameworks: java_log_parser::PINNABLE_FRAMEWORKS,
+        experimental: false,
+        detect: detect_java,
+        build: |_| Box::new(JavaLogParser::new()),
+    },
+    ParserPlugin {
+        name: "cpp",
+        languages: &["cpp", "c++"],
+        frameworks: cpp_log_parser::PINNABLE_FRAMEWORKS,
+        experimental: false,
+        detect: detect_cpp,
+        build: |_| Box::new(CppLogParser::new()),
+    },
+];
+
+pub struct ParserRegistry {
+    parsers: Vec<(&'static ParserPlugin, BoxedLogParser)>,
+}
+
+impl ParserRegistry {
+    /// Built-in parsers that the config leaves enabled
+    pub fn from_config(config: &ParsersConfig) -> Self {
+        Self::from_plugins(BUILTIN_PARSERS, config)
+    }
+
+    /// Every built-in parser, experimental ones included, whatever the config says
+    pub fn all() -> Self {
+        Self::from_plugins(BUILTIN_PARSERS, &ParsersConfig {
+            experimental: BUILTIN_PARSERS.iter().map(|plugin| plugin.name.to_string()).collect(),
+            ..Default::default()
+        })
+    }
+
+    fn from_plugins(plugins: &'static [ParserPlugin], config: &ParsersConfig) -> Self {
+        let listed = |list: &[String], name: &str| list.iter().any(|entry| entry.eq_ignore_ascii_case(name));
+        let parsers = plugins
+            .iter()
+            .filter(|plugin| !listed(&config.disabled, plugin.name))
+            .filter(|plugin| !plugin.experimental || listed(&config.experimental, plugin.name))
+            .map(|plugin| (plugin, (plugin.build)(config)))
+            .collect();
+        Self { parsers }
+    }
+
+    pub fn plugins(&self) -> impl Iterator<Item = &'static ParserPlugin> + '_ {
+        self.parsers.iter().map(|(plugin, _)| *plugin)
+    }
+
+    /// Parser registered for a `main.json` language
+    pub fn for_language(&self, language: &str) -> Option<&(dyn LogParserTrait + Send + Sync)> {
+        self.plugin_for_language(language).map(|(_, parser)| parser)
+    }
+
+    /// Parser registered for a `main.json` language, with its plugin
+    pub fn plugin_for_language(&self, language: &str) -> Option<(&'static ParserPlugin, &(dyn LogParserTrait + Send + Sync))> {
+        let language = language.trim().to_lowercase();
+        self.parsers
+            .iter()
+            .find(|(plugin, _)| plugin.languages.contains(&language.as_str()))
+            .map(|(plugin, parser)| (*plugin, parser.as_ref()))
+    }
+
+    /// Parser registered under a plugin name
+    pub fn by_name(&self, name: &str) -> Option<&(dyn LogParserTrait + Send + Sync)> {
+        self.parsers
+            .iter()
+            .find(|(plugin, _)| plugin.name.eq_ignore_ascii_case(name))
+            .map(|(_, parser)| parser.as_ref())
+    }
+
+    /// First parser whose content sniff accepts the log, with its plugin
+    pub fn detect(&self, content: &str) -> Option<(&'static ParserPlugin, &(dyn LogParserTrait + Send + Sync))> {
+        self.parsers
+            .iter()
+            .find(|(plugin, _)| (plugin.detect)(content))
+            .map(|(plugin, parser)| (*plugin, parser.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_PARSERS: &[ParserPlugin] = &[
+        ParserPlugin {
+            name: "rust",
+            languages: &["rust"],
+            frameworks: &["cargo"],
+            experimental: false,
+            detect: detect_rust,
+            build: |_| Box::new(RustLogParser::new()),
+        },
+        ParserPlugin {
+            name: "go",
+            languages: &["go"],
+            frameworks: &["gotest"],
+            experimental: true,
+            detect: |content| content.contains("--- PASS:"),
+            build: |_| Box::new(RustLogParser::new()),
+        },
+    ];
+
+    #[test]
+    fn test_builtin_languages() {
+        let registry = ParserRegistry::from_config(&ParsersConfig::default());
+        assert_eq!(registry.for_language("TypeScript").map(|p| p.get_language()), Some("javascript"));
+        assert_eq!(registry.for_language("python").map(|p| p.get_language()), Some("python"));
+        assert_eq!(registry.for_language("golang").map(|p| p.get_language()), Some("go"));
+        assert_eq!(registry.for_language("Java").map(|p| p.get_language()), Some("java"));
+        assert_eq!(registry.for_language("C++").map(|p| p.get_language()), Some("cpp"));
+        assert!(registry.for_language("cobol").is_none());
+        assert!(registry.plugin_for_language("py").is_none());
+        assert!(registry.plugin_for_language("Golang").is_some_and(|(plugin, _)| plugin.accepts_framework("gotestsum")));
+        assert!(registry.plugin_for_language("rust").is_some_and(|(plugin, _)| !plugin.accepts_framework("pytest")));
+        assert_eq!((canonical_language("TS"), canonical_language("golang"), canonical_language("Cobol")), ("javascript".to_string(), "go".to_string(), "cobol".to_string()));
+        assert_eq!(registry.detect("running 2 tests\ntest a ... ok\ntest result: ok.").map(|(plugin, _)| plugin.name), Some("rust"));
+    }
+
+    #[test]
+    fn test_experimental_and_disabled_parsers() {
+        let default = ParserRegistry::from_plugins(TEST_PARSERS, &ParsersConfig::default());
+        assert!(default.for_language("go").is_none());
+
+        let config = ParsersConfig { experimental: vec!["go".to_string()], disabled: vec!["rust".to_string()], ..Default::default() };
+        let registry = ParserRegistry::from_plugins(TEST_PARSERS, &config);
+        assert!(registry.for_language("go").is_some());
+        assert!(registry.for_language("rust").is_none());
+        assert_eq!(registry.plugins().map(|plugin| plugin.name).collect::<Vec<_>>(), vec!["go"]);
+    }
+}
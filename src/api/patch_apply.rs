@@ -0,0 +1,69 @@
+//! Patch application failures (`git apply`, `git am`, `patch`) spotted in stage logs (rule C9)
+//!
+//! A patch that didn't apply leaves the stage running against the wrong code, so its
+//! statuses say nothing about the change under review.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Stages whose logs are scanned; base runs before any patch is applied
+pub const PATCHED_STAGES: &[&str] = &["before", "after", "agent"];
+
+lazy_static! {
+    // Commands or harness messages naming the patch about to be applied
+    static ref PATCH_NAME: Regex = Regex::new(
+        r"(?:git\s+apply|git\s+am|patch\s+(?:-\S+\s+)*(?:-i|--input(?:=|\s+)|<))\s*(?:-\S+\s+)*([^\s<]+\.(?:patch|diff))|(?i)applying(?: patch)?:?\s+([^\s]+\.(?:patch|diff))"
+    ).unwrap();
+    static ref PATCH_FAILURE: Regex = Regex::new(
+        r"error: patch failed:|error: .+: patch does not apply|CONFLICT \(|Hunk #\d+ FAILED|hunks? FAILED|Patch failed at \d+"
+    ).unwrap();
+}
+
+/// Failed patch applications in one stage log, as "stage: patch: evidence"
+pub fn detect_patch_failures(stage: &str, content: &str) -> Vec<String> {
+    let mut current_patch: Option<String> = None;
+    let mut out: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if let Some(caps) = PATCH_NAME.captures(line) {
+            current_patch = caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str().to_string());
+        }
+        if !PATCH_FAILURE.is_match(line) {
+            continue;
+        }
+        let patch = current_patch.as_deref().unwrap_or("patch");
+        let example = format!("{}: {}: {}", stage, patch, line.trim());
+        if !out.contains(&example) {
+            out.push(example);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_git_apply_and_patch_failures() {
+        let log = "+ git apply -v /tmp/test.patch\nChecking patch tests/test_a.py...\nerror: patch failed: tests/test_a.py:12\nerror: tests/test_a.py: patch does not apply\n";
+        assert_eq!(
+            detect_patch_failures("after", log),
+            vec![
+                "after: /tmp/test.patch: error: patch failed: tests/test_a.py:12",
+                "after: /tmp/test.patch: error: tests/test_a.py: patch does not apply",
+            ]
+        );
+
+        let log = "patching file src/lib.rs\nHunk #2 FAILED at 40.\n1 out of 2 hunks FAILED -- saving rejects to file src/lib.rs.rej\n";
+        assert_eq!(detect_patch_failures("before", log).len(), 2);
+
+        let log = "Applying: fix parser\nCONFLICT (content): Merge conflict in src/parser.py";
+        assert_eq!(detect_patch_failures("agent", log), vec!["agent: patch: CONFLICT (content): Merge conflict in src/parser.py"]);
+    }
+
+    #[test]
+    fn test_ignores_clean_logs() {
+        let log = "+ git apply -v /tmp/test.patch\nApplied patch tests/test_a.py cleanly.\ntests/test_a.py::test_conflict_resolution PASSED";
+        assert!(detect_patch_failures("after", log).is_empty());
+    }
+}
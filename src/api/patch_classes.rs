@@ -0,0 +1,160 @@
+//! Reviewer classification of a deliverable's patch files for C7
+//!
+//! C7 needs to know which patches change the source and which add tests, and guesses it
+//! from the file name: `gold`, `src` or `source` without `test` is a source patch, anything
+//! else a test patch. Deliverables name their patches freely, so a reviewer can set the
+//! class of each file instead — `source`, `test`, or `ignore` to leave it out of the check.
+//! The choices are stored with the deliverable (`<folder>/reviews/patch_classes.json` in the
+//! review store), keyed by the patch's path from the `patches/` folder on, and every later
+//! analysis uses them. A change re-runs C7 only; there is no separate rule for F2P tests
+//! missing from the test patch.
+
+use std::collections::HashMap;
+
+use crate::api::analysis_store::deliverable_folder_id;
+use crate::api::storage::review_store;
+use crate::app::types::{PatchFile, PATCH_CLASSES};
+
+pub const PATCH_CLASSES_FILE: &str = "reviews/patch_classes.json";
+
+// Attempts before giving up on classes that keep changing under us
+const PATCH_CLASSES_WRITE_ATTEMPTS: usize = 8;
+
+/// Diff or patch file in the deliverable's patches folder
+pub fn is_patch_file(path: &str) -> bool {
+    let path_lower = path.to_lowercase();
+    path_lower.contains("patches/") && (path_lower.ends_with(".diff") || path_lower.ends_with(".patch"))
+}
+
+/// File name of a patch path
+pub fn patch_file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Key reviewer classes are stored under: the path from the `patches/` folder on, e.g.
+/// `patches/v2/fix.patch`, the same for a cached path and one relative to the deliverable
+pub fn patch_key(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let start = path.to_ascii_lowercase().find("patches/").unwrap_or(0);
+    path[start..].to_string()
+}
+
+/// Class C7 guesses from the file name
+pub fn guessed_patch_class(file_name: &str) -> &'static str {
+    let file_name = file_name.to_lowercase();
+    // Golden source diffs typically contain "gold", "golden", "src", "source"
+    // Test diffs typically contain "test"
+    if (file_name.contains("gold") || file_name.contains("src") || file_name.contains("source")) && !file_name.contains("test") {
+        "source"
+    } else {
+        "test"
+    }
+}
+
+/// The reviewer's class for the patch at `path`, else the guessed one
+pub fn patch_class<'a>(path: &str, classes: &'a HashMap<String, String>) -> &'a str {
+    classes.get(&patch_key(path)).map(String::as_str).unwrap_or_else(|| guessed_patch_class(patch_file_name(path)))
+}
+
+fn parse_patch_classes(content: Option<&str>) -> Result<HashMap<String, String>, String> {
+    content
+        .map(serde_json::from_str)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|e| format!("Failed to parse {}: {}", PATCH_CLASSES_FILE, e))
+}
+
+/// Classes recorded for the deliverable by patch key, empty when there are none
+pub fn load_patch_classes(file_paths: &[String]) -> Result<HashMap<String, String>, String> {
+    let Some(folder_id) = deliverable_folder_id(file_paths) else {
+        return Ok(HashMap::new());
+    };
+    parse_patch_classes(review_store()?.load(&folder_id, PATCH_CLASSES_FILE)?.as_deref())
+}
+
+/// Record the class of one patch file by its key; an empty class goes back to the guess.
+/// Redone on the newer classes when another reviewer saved in between
+pub fn save_patch_class(file_paths: &[String], key: &str, class: &str) -> Result<HashMap<String, String>, String> {
+    if !class.is_empty() && !PATCH_CLASSES.contains(&class) {
+        return Err(format!("Unknown patch class '{}'", class));
+    }
+    if !file_paths.iter().any(|path| is_patch_file(path) && patch_key(path) == key) {
+        return Err(format!("'{}' is not a patch file of this deliverable", key));
+    }
+    let folder_id = deliverable_folder_id(file_paths).ok_or("No deliverable folder to store patch classes in")?;
+
+    let store = review_store()?;
+    for _ in 0..PATCH_CLASSES_WRITE_ATTEMPTS {
+        let stored = store.load(&folder_id, PATCH_CLASSES_FILE)?;
+        let mut classes = parse_patch_classes(stored.as_deref())?;
+        if class.is_empty() {
+            classes.remove(key);
+        } else {
+            classes.insert(key.to_string(), class.to_string());
+        }
+        let content = serde_json::to_string_pretty(&classes).map_err(|e| format!("Failed to serialize patch classes: {}", e))?;
+        if store.save_if_unchanged(&folder_id, PATCH_CLASSES_FILE, stored.as_deref(), &content)? {
+            return Ok(classes);
+        }
+    }
+    Err("The patch classes kept changing while saving; try again".to_string())
+}
+
+/// The deliverable's patch files with the class C7 will use for each
+pub fn patch_files(file_paths: &[String], classes: &HashMap<String, String>) -> Vec<PatchFile> {
+    file_paths
+        .iter()
+        .filter(|path| is_patch_file(path))
+        .map(|path| {
+            let name = patch_file_name(path);
+            PatchFile {
+                name: name.to_string(),
+                path: patch_key(path),
+                class: patch_class(path, classes).to_string(),
+                guessed: guessed_patch_class(name).to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reviewer_classes_replace_the_guess() {
+        let classes: HashMap<String, String> = [
+            ("patches/fix.patch".to_string(), "source".to_string()),
+            ("patches/gold_src.diff".to_string(), "ignore".to_string()),
+        ].into_iter().collect();
+        assert_eq!(patch_class("/tmp/f/patches/fix.patch", &classes), "source");
+        // Keyed by path, so a namesake in another folder keeps its guess
+        assert_eq!(patch_class("/tmp/f/patches/v2/fix.patch", &classes), "test");
+        assert_eq!(patch_class("/tmp/f/patches/gold_src.diff", &classes), "ignore");
+        assert_eq!(patch_class("/tmp/f/patches/golden_source.diff", &classes), "source");
+        assert_eq!(patch_class("/tmp/f/patches/source_tests.diff", &classes), "test");
+
+        let paths = vec!["f/patches/fix.patch".to_string(), "f/logs/base.log".to_string()];
+        assert_eq!(patch_files(&paths, &classes), vec![PatchFile {
+            name: "fix.patch".to_string(),
+            path: "patches/fix.patch".to_string(),
+            class: "source".to_string(),
+            guessed: "test".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_save_patch_class_validates() {
+        let paths = vec!["f/patches/fix.patch".to_string()];
+        assert!(save_patch_class(&paths, "patches/fix.patch", "golden").is_err());
+        assert!(save_patch_class(&paths, "patches/other.patch", "test").is_err());
+        assert!(save_patch_class(&paths, "fix.patch", "test").is_err());
+    }
+
+    #[test]
+    fn test_unreadable_classes_are_an_error() {
+        assert_eq!(parse_patch_classes(None).unwrap(), HashMap::new());
+        assert!(parse_patch_classes(Some("{\"patches/fix.patch\": 3}")).is_err());
+        assert_eq!(patch_key("C:\\cache\\f\\Patches\\fix.patch"), "Patches/fix.patch");
+    }
+}
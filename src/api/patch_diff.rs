@@ -0,0 +1,225 @@
+//! Unified diffs of the deliverable's patches folder, parsed for the Patches tab
+//!
+//! Both `git diff` output (`diff --git` headers, optional `index`/mode lines) and plain
+//! `diff -u` output are read. Hunk bodies are consumed by the counts in their `@@` header,
+//! so a removed line that happens to start with `--- ` is not taken for a new file.
+
+use std::collections::HashMap;
+
+use crate::api::patch_classes::{is_patch_file, patch_class, patch_file_name};
+use crate::api::storage::deliverable_cache_dir;
+use crate::app::number_format::format_size;
+use crate::app::types::{DiffFile, DiffHunk, DiffLineKind, ParsedPatch, PatchDiffLine};
+
+// Larger patches are vendored code or generated files, not something to read side by side
+const MAX_PATCH_BYTES: u64 = 2 * 1024 * 1024;
+
+fn strip_prefix_path(path: &str) -> String {
+    // `--- a/src/lib.rs\t2024-01-01 …` carries a timestamp after a tab in plain diffs
+    let path = path.split('\t').next().unwrap_or(path).trim();
+    if path == "/dev/null" {
+        return path.to_string();
+    }
+    path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")).unwrap_or(path).to_string()
+}
+
+fn new_file(old_path: String, new_path: String) -> DiffFile {
+    DiffFile { old_path, new_path, hunks: Vec::new(), added: 0, removed: 0, binary: false }
+}
+
+// `-a,b +c,d` start lines and counts; a missing count is 1
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let ranges = line.strip_prefix("@@ ")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(' ')?;
+    let range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = range(old.strip_prefix('-')?)?;
+    let (new_start, new_count) = range(new.strip_prefix('+')?)?;
+    Some((old_start, old_count, new_start, new_count))
+}
+
+/// Files and hunks of a unified diff, in the order they appear
+pub fn parse_unified_diff(content: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = Vec::new();
+    // Lines left in the current hunk on each side
+    let mut old_left = 0usize;
+    let mut new_left = 0usize;
+    let mut old_line = 0usize;
+    let mut new_line = 0usize;
+    // Set by `diff --git` until its `---` line names the file again
+    let mut git_header = false;
+
+    for line in content.lines() {
+        if old_left > 0 || new_left > 0 {
+            let body_line = match line.chars().next() {
+                Some('+') => Some((DiffLineKind::Added, &line[1..])),
+                Some('-') => Some((DiffLineKind::Removed, &line[1..])),
+                Some(' ') => Some((DiffLineKind::Context, &line[1..])),
+                // "\ No newline at end of file"
+                Some('\\') => continue,
+                // Some tools strip the space of empty context lines
+                None => Some((DiffLineKind::Context, "")),
+                // The header counted more lines than the hunk has; read this one as a header
+                Some(_) => None,
+            };
+            let hunk = files.last_mut().and_then(|file| file.hunks.last_mut());
+            if let (Some((kind, text)), Some(hunk)) = (body_line, hunk) {
+                let (old, new) = match kind {
+                    DiffLineKind::Added => (None, Some(new_line)),
+                    DiffLineKind::Removed => (Some(old_line), None),
+                    DiffLineKind::Context => (Some(old_line), Some(new_line)),
+                };
+                if old.is_some() {
+                    old_line += 1;
+                    old_left = old_left.saturating_sub(1);
+                }
+                if new.is_some() {
+                    new_line += 1;
+                    new_left = new_left.saturating_sub(1);
+                }
+                hunk.lines.push(PatchDiffLine { kind, old_line: old, new_line: new, text: text.to_string() });
+                continue;
+            }
+            old_left = 0;
+            new_left = 0;
+        }
+
+        if let Some(paths) = line.strip_prefix("diff --git ") {
+            let (old, new) = paths.split_once(" b/").map(|(old, new)| (old.to_string(), format!("b/{}", new))).unwrap_or_else(|| (paths.to_string(), paths.to_string()));
+            files.push(new_file(strip_prefix_path(&old), strip_prefix_path(&new)));
+            git_header = true;
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            if git_header {
+                if let Some(file) = files.last_mut() {
+                    file.old_path = strip_prefix_path(path);
+                }
+            } else {
+                files.push(new_file(strip_prefix_path(path), strip_prefix_path(path)));
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(file) = files.last_mut() {
+                file.new_path = strip_prefix_path(path);
+            }
+            git_header = false;
+        } else if line.starts_with("Binary files ") || line == "GIT binary patch" {
+            if let Some(file) = files.last_mut() {
+                file.binary = true;
+            }
+        } else if let Some((old_start, old_count, new_start, new_count)) = parse_hunk_header(line) {
+            let Some(file) = files.last_mut() else { continue };
+            file.hunks.push(DiffHunk { header: line.to_string(), lines: Vec::new() });
+            git_header = false;
+            old_line = old_start;
+            new_line = new_start;
+            old_left = old_count;
+            new_left = new_count;
+        }
+    }
+
+    for file in &mut files {
+        let lines = file.hunks.iter().flat_map(|hunk| hunk.lines.iter());
+        let (added, removed) = lines.fold((0, 0), |(added, removed), line| match line.kind {
+            DiffLineKind::Added => (added + 1, removed),
+            DiffLineKind::Removed => (added, removed + 1),
+            DiffLineKind::Context => (added, removed),
+        });
+        file.added = added;
+        file.removed = removed;
+    }
+    files
+}
+
+fn read_patch(path: &std::path::Path) -> Result<Vec<DiffFile>, String> {
+    let size = std::fs::metadata(path).map_err(|e| format!("Failed to read the patch: {}", e))?.len();
+    if size > MAX_PATCH_BYTES {
+        return Err(format!("Too large to show ({})", format_size(size)));
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read the patch: {}", e))?;
+    Ok(parse_unified_diff(&String::from_utf8_lossy(&bytes)))
+}
+
+/// Every patch file of the deliverable, parsed, with the class C7 uses for it
+pub fn load_patches(file_paths: &[String], classes: &HashMap<String, String>) -> Vec<ParsedPatch> {
+    let cache_dir = deliverable_cache_dir();
+    file_paths
+        .iter()
+        .filter(|path| is_patch_file(path))
+        .map(|path| {
+            let parsed = match &cache_dir {
+                Some(cache_dir) => read_patch(&cache_dir.join(path)),
+                None => Err("No deliverable cache folder".to_string()),
+            };
+            let (files, error) = match parsed {
+                Ok(files) => (files, None),
+                Err(e) => (Vec::new(), Some(e)),
+            };
+            ParsedPatch {
+                name: patch_file_name(path).to_string(),
+                class: patch_class(path, classes).to_string(),
+                files,
+                error,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_diff_with_line_numbers() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n\
+                    index 1111111..2222222 100644\n\
+                    --- a/src/lib.rs\n\
+                    +++ b/src/lib.rs\n\
+                    @@ -10,4 +10,4 @@ fn parse() {\n \
+                    let a = 1;\n\
+                    --- removed line that looks like a header\n\
+                    +let b = 2;\n \
+                    let c = 3;\n\
+                    \n\
+                    diff --git a/tests/new.rs b/tests/new.rs\n\
+                    new file mode 100644\n\
+                    --- /dev/null\n\
+                    +++ b/tests/new.rs\n\
+                    @@ -0,0 +1 @@\n\
+                    +#[test] fn t() {}\n";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!((files[0].old_path.as_str(), files[0].new_path.as_str()), ("src/lib.rs", "src/lib.rs"));
+        assert_eq!((files[0].added, files[0].removed), (1, 1));
+        let lines = &files[0].hunks[0].lines;
+        assert_eq!(lines[1], PatchDiffLine {
+            kind: DiffLineKind::Removed,
+            old_line: Some(11),
+            new_line: None,
+            text: "-- removed line that looks like a header".to_string(),
+        });
+        assert_eq!((lines[2].old_line, lines[2].new_line), (None, Some(11)));
+        assert_eq!((lines[4].old_line, lines[4].new_line), (Some(13), Some(13)));
+        assert_eq!((files[1].old_path.as_str(), files[1].new_path.as_str()), ("/dev/null", "tests/new.rs"));
+        assert_eq!(files[1].hunks[0].lines[0].new_line, Some(1));
+    }
+
+    #[test]
+    fn test_parse_plain_diff_and_binary() {
+        let diff = "--- old/a.txt\t2024-01-01 00:00:00\n\
+                    +++ new/a.txt\t2024-01-02 00:00:00\n\
+                    @@ -1 +1 @@\n\
+                    -x\n\
+                    +y\n\
+                    diff --git a/logo.png b/logo.png\n\
+                    Binary files a/logo.png and b/logo.png differ\n";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].new_path, "new/a.txt");
+        assert_eq!((files[0].added, files[0].removed), (1, 1));
+        assert!(files[1].binary);
+        assert!(files[1].hunks.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! Keeping client-supplied file paths inside the download cache
+//!
+//! Server functions take the deliverable's `file_paths` back from the browser and join them
+//! onto the download cache, so a crafted request could otherwise read any file the server
+//! can (`../../etc/passwd`, an absolute path, or a symlink planted in a deliverable). Every
+//! path must be relative, free of `.`/`..` components, inside a single deliverable folder,
+//! and still inside that folder once symlinks are resolved.
+
+use std::path::{Component, Path};
+
+use crate::api::storage::deliverable_cache_dir;
+use crate::app::types::{ApiError, ApiErrorCode};
+
+// Nearest part of `path` that exists, with symlinks resolved
+fn canonical_existing_ancestor(path: &Path) -> Option<std::path::PathBuf> {
+    path.ancestors().find(|ancestor| ancestor.exists()).and_then(|ancestor| std::fs::canonicalize(ancestor).ok())
+}
+
+/// Check `file_paths` against the cache folder `root`; the error names the first offending path
+pub fn check_paths_under(root: &Path, file_paths: &[String]) -> Result<(), String> {
+    let mut folder_id: Option<&str> = None;
+    for path in file_paths {
+        let relative = Path::new(path);
+        if path.is_empty() || relative.components().any(|component| !matches!(component, Component::Normal(_))) {
+            return Err(format!("{} is not a path inside the deliverable", path));
+        }
+        let first = path.split('/').next().unwrap_or_default();
+        match folder_id {
+            None => folder_id = Some(first),
+            Some(folder_id) if folder_id != first => return Err(format!("{} is outside deliverable {}", path, folder_id)),
+            Some(_) => {}
+        }
+    }
+    let Some(folder_id) = folder_id else {
+        return Ok(());
+    };
+
+    // A deliverable that isn't downloaded yet can only be bounded by the cache itself
+    let folder = root.join(folder_id);
+    let bound = canonical_existing_ancestor(&folder).ok_or("The download cache is missing")?;
+    for path in file_paths {
+        let resolved = canonical_existing_ancestor(&root.join(path)).ok_or("The download cache is missing")?;
+        if !resolved.starts_with(&bound) {
+            return Err(format!("{} resolves outside deliverable {}", path, folder_id));
+        }
+    }
+    Ok(())
+}
+
+/// Reject file paths from a request that reach outside one downloaded deliverable
+pub fn check_deliverable_paths(file_paths: &[String]) -> Result<(), ApiError> {
+    let root = deliverable_cache_dir()
+        .ok_or_else(|| ApiError::new(ApiErrorCode::Storage, "No deliverable cache folder"))?;
+    check_paths_under(&root, file_paths).map_err(|e| {
+        eprintln!("Rejected file paths: {}", e);
+        ApiError::new(ApiErrorCode::Forbidden, "The request named files outside the deliverable").with_details(e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_traversal_absolute_and_mixed_folders() {
+        let root = tempfile::TempDir::new().unwrap();
+        let paths = |list: &[&str]| list.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        assert!(check_paths_under(root.path(), &paths(&["abc/logs/after.log", "abc/main/abc.json"])).is_ok());
+        assert!(check_paths_under(root.path(), &paths(&["abc/../../etc/passwd"])).is_err());
+        assert!(check_paths_under(root.path(), &paths(&["/etc/passwd"])).is_err());
+        assert!(check_paths_under(root.path(), &paths(&["./abc/logs/after.log"])).is_err());
+        assert!(check_paths_under(root.path(), &paths(&["abc/logs/after.log", "xyz/logs/after.log"])).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_symlinks_out_of_the_deliverable() {
+        let root = tempfile::TempDir::new().unwrap();
+        let outside = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(root.path().join("abc/logs")).unwrap();
+        std::fs::write(root.path().join("abc/logs/after.log"), "ok").unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("abc/logs/escape")).unwrap();
+        assert!(check_paths_under(root.path(), &["abc/logs/after.log".to_string()]).is_ok());
+        assert!(check_paths_under(root.path(), &["abc/logs/escape/secret".to_string()]).is_err());
+    }
+}
@@ -1,6 +1,5 @@
 use regex::Regex;
-use std::collections::HashSet;
-use std::fs;
+use std::collections::{HashMap, HashSet};
 use lazy_static::lazy_static;
 
 use super::log_parser::{LogParserTrait, ParsedLog};
@@ -47,6 +46,16 @@ lazy_static! {
     static ref PYTEST_XFAIL_RE: Regex = Regex::new(r"^(\d*)XFAIL\s+(.+?)(?:\s+-\s+(.*))?$")
         .expect("Failed to compile PYTEST_XFAIL_RE regex");
     
+    // Unexpected passes of xfail tests: summary `XPASS id - reason`, verbose `id XPASS [ 50%]`,
+    // and strict ones reported as failures carrying `[XPASS(strict)]`
+    static ref PYTEST_XPASS_RE: Regex = Regex::new(r"^XPASS\s+(.+?)(?:\s+-\s+.*)?$").unwrap();
+    static ref PYTEST_XPASS_VERBOSE_RE: Regex = Regex::new(r"^(.+?)\s+XPASS(?:\s|$)").unwrap();
+    static ref PYTEST_STRICT_XPASS_RE: Regex = Regex::new(r"^FAILED\s+(.+?)\s+-\s+\[XPASS\(strict\)\]").unwrap();
+
+    // pytest-rerunfailures: verbose `id RERUN [ 50%]` and summary `RERUN id - reason`
+    static ref PYTEST_RERUN_RE: Regex = Regex::new(r"^RERUN\s+(.+?)(?:\s+-\s+.*)?$").unwrap();
+    static ref PYTEST_RERUN_VERBOSE_RE: Regex = Regex::new(r"^(.+?)\s+RERUN(?:\s+\[\s*\d+%\s*\])?$").unwrap();
+
     static ref PYTEST_OPTIONS_RE: Regex = Regex::new(r"(.*?)\[(.*)\]")
         .expect("Failed to compile PYTEST_OPTIONS_RE regex");
     
@@ -105,6 +114,11 @@ lazy_static! {
         .expect("Failed to compile MATPLOTLIB_MOUSE_BUTTON_RE regex");
 }
 
+// Frameworks that can be pinned by a run script or detection rule; names match `parse_with_framework`
+pub(crate) const PINNABLE_FRAMEWORKS: &[&str] = &[
+    "django", "seaborn", "sympy", "matplotlib", "pytest_enhanced", "pytest_options", "pytest_v2",
+];
+
 pub struct PythonLogParser;
 
 impl PythonLogParser {
@@ -129,24 +143,45 @@ impl PythonLogParser {
         
         // Check for pytest indicators
         if content.contains("pytest") || content.contains("PASSED") || content.contains("FAILED") || content.contains("XFAIL") {
-            // Check if it has XFAIL or complex parametrized tests (enhanced format)
-            if content.contains("XFAIL") || (content.contains("[") && content.contains("%]")) {
-                return "pytest_enhanced".to_string();
-            }
-            // Check if it's pytest v2 format (with ANSI codes)
-            if ANSI_ESCAPE_RE.is_match(content) {
-                return "pytest_v2".to_string();
-            }
-            // Check if it has options format
-            if content.contains("[") && content.contains("]") {
-                return "pytest_options".to_string();
-            }
-            return "pytest".to_string();
+            return self.detect_pytest_variant(content);
         }
         
         // Default to pytest_v2 for best compatibility
         "pytest_v2".to_string()
     }
+
+    // Pick among the pytest output flavours once pytest is known to be the runner
+    fn detect_pytest_variant(&self, content: &str) -> String {
+        // Check if it has XFAIL or complex parametrized tests (enhanced format)
+        if content.contains("XFAIL") || (content.contains("[") && content.contains("%]")) {
+            return "pytest_enhanced".to_string();
+        }
+        // Check if it's pytest v2 format (with ANSI codes)
+        if ANSI_ESCAPE_RE.is_match(content) {
+            return "pytest_v2".to_string();
+        }
+        // Check if it has options format
+        if content.contains("[") && content.contains("]") {
+            return "pytest_options".to_string();
+        }
+        "pytest".to_string()
+    }
+
+    fn parse_with_framework(&self, framework: &str, content: &str) -> ParsedLog {
+        let mut parsed = match framework {
+            "django" => parse_log_django(content),
+            "seaborn" => parse_log_seaborn(content),
+            "sympy" => parse_log_sympy(content),
+            "matplotlib" => parse_log_matplotlib(content),
+            "pytest_enhanced" => parse_log_pytest_enhanced(content),
+            "pytest_options" => parse_log_pytest_options(content),
+            "pytest_v2" => parse_log_pytest_v2(content),
+            _ => parse_log_pytest(content),
+        };
+        apply_xpasses(content, &mut parsed);
+        apply_reruns(content, &mut parsed);
+        parsed.with_parser(framework)
+    }
 }
 
 impl LogParserTrait for PythonLogParser {
@@ -154,22 +189,15 @@ impl LogParserTrait for PythonLogParser {
         "python"
     }
 
-    fn parse_log_file(&self, file_path: &str) -> Result<ParsedLog, String> {
-        let content = fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read log file {}: {}", file_path, e))?;
+    fn parse_content(&self, _file_path: &str, content: &str, framework: Option<&str>) -> Result<ParsedLog, String> {
+        // A pinned framework tells us the runner; plain pytest still needs its output flavour detected
+        let framework = match framework {
+            Some("pytest") => self.detect_pytest_variant(content),
+            Some(name) if PINNABLE_FRAMEWORKS.contains(&name) => name.to_string(),
+            _ => self.detect_framework(content),
+        };
 
-        let framework = self.detect_framework(&content);
-        
-        match framework.as_str() {
-            "django" => Ok(parse_log_django(&content)),
-            "seaborn" => Ok(parse_log_seaborn(&content)),
-            "sympy" => Ok(parse_log_sympy(&content)),
-            "matplotlib" => Ok(parse_log_matplotlib(&content)),
-            "pytest_enhanced" => Ok(parse_log_pytest_enhanced(&content)),
-            "pytest_options" => Ok(parse_log_pytest_options(&content)),
-            "pytest_v2" => Ok(parse_log_pytest_v2(&content)),
-            _ => Ok(parse_log_pytest(&content)),
-        }
+        Ok(self.parse_with_framework(&framework, content))
     }
 }
 
@@ -211,7 +239,7 @@ fn parse_log_pytest(log: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn parse_log_pytest_options(log: &str) -> ParsedLog {
@@ -270,7 +298,7 @@ fn parse_log_pytest_options(log: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn parse_log_django(log: &str) -> ParsedLog {
@@ -390,7 +418,7 @@ fn parse_log_django(log: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn parse_log_seaborn(log: &str) -> ParsedLog {
@@ -428,7 +456,7 @@ fn parse_log_seaborn(log: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn parse_log_sympy(log: &str) -> ParsedLog {
@@ -468,7 +496,7 @@ fn parse_log_sympy(log: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn parse_log_matplotlib(log: &str) -> ParsedLog {
@@ -594,7 +622,7 @@ fn parse_log_pytest_v2(log: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn parse_log_pytest_enhanced(log: &str) -> ParsedLog {
@@ -674,7 +702,78 @@ fn parse_log_pytest_enhanced(log: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
+}
+
+// XFAIL stays ignored, but an xfail test that passed is a real signal: non-strict XPASS counts
+// as passed and strict XPASS as failed, and both are recorded in `xpassed`
+fn apply_xpasses(log: &str, parsed: &mut ParsedLog) {
+    for line in clean_ansi_escapes(log).lines() {
+        let line = line.trim();
+        if let Some(captures) = PYTEST_STRICT_XPASS_RE.captures(line) {
+            let test_case = captures[1].to_string();
+            parsed.passed.remove(&test_case);
+            parsed.ignored.remove(&test_case);
+            parsed.failed.insert(test_case.clone());
+            parsed.xpassed.insert(test_case);
+        } else if let Some(captures) = PYTEST_XPASS_RE.captures(line).or_else(|| PYTEST_XPASS_VERBOSE_RE.captures(line)) {
+            let test_case = captures[1].to_string();
+            // Already failed means the run treated it as strict
+            if !parsed.failed.contains(&test_case) {
+                parsed.ignored.remove(&test_case);
+                parsed.passed.insert(test_case.clone());
+            }
+            parsed.xpassed.insert(test_case);
+        }
+    }
+    parsed.all.extend(parsed.xpassed.iter().cloned());
+}
+
+// Tests retried by a rerun plugin keep their attempt history; the last non-RERUN status is
+// the one that counts. Verbose lines are preferred so the short summary isn't double-counted.
+fn apply_reruns(log: &str, parsed: &mut ParsedLog) {
+    let mut verbose: HashMap<String, Vec<String>> = HashMap::new();
+    let mut summary: HashMap<String, Vec<String>> = HashMap::new();
+
+    for line in clean_ansi_escapes(log).lines() {
+        let line = line.trim();
+        if let Some(captures) = PYTEST_RERUN_RE.captures(line) {
+            summary.entry(captures[1].to_string()).or_default().push("RERUN".to_string());
+        } else if let Some(captures) = PYTEST_RERUN_VERBOSE_RE.captures(line) {
+            verbose.entry(captures[1].to_string()).or_default().push("RERUN".to_string());
+        } else if let Some(captures) = PYTEST_STATUS_WITH_PERCENTAGE_RE.captures(line) {
+            verbose.entry(captures[1].to_string()).or_default().push(captures[2].to_string());
+        } else if let Some(captures) = PYTEST_STATUS_RE.captures(line) {
+            summary.entry(captures[2].to_string()).or_default().push(captures[1].to_string());
+        }
+    }
+
+    let rerun = |history: &Vec<String>| history.iter().any(|status| status == "RERUN");
+    let mut names: Vec<&String> = verbose.iter().chain(summary.iter())
+        .filter(|(_, history)| rerun(history))
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        let history = match verbose.get(name) {
+            Some(history) if rerun(history) => history,
+            _ => &summary[name],
+        };
+        if let Some(last) = history.iter().rev().find(|status| *status != "RERUN") {
+            parsed.passed.remove(name);
+            parsed.failed.remove(name);
+            parsed.ignored.remove(name);
+            match last.as_str() {
+                "PASSED" => { parsed.passed.insert(name.clone()); }
+                "FAILED" | "ERROR" => { parsed.failed.insert(name.clone()); }
+                _ => { parsed.ignored.insert(name.clone()); }
+            }
+            parsed.all.insert(name.clone());
+        }
+        parsed.attempts.insert(name.clone(), history.clone());
+    }
 }
 
 fn clean_ansi_escapes(text: &str) -> String {
@@ -884,6 +983,48 @@ PASSED tests/test_simple.py::test_basic
         assert!(result.passed.contains("tests/test_simple.py::test_basic"));
     }
 
+    #[test]
+    fn test_xpass_classification() {
+        let log_content = r#"
+tests/test_math.py::test_rounding XFAIL (float precision) [ 25%]
+tests/test_math.py::test_overflow XPASS (fixed upstream) [ 50%]
+XPASS tests/test_math.py::test_underflow - fixed upstream
+FAILED tests/test_math.py::test_strict - [XPASS(strict)] should still fail
+"#;
+
+        let parser = PythonLogParser::new();
+        let result = parser.parse_with_framework("pytest_enhanced", log_content);
+
+        assert!(result.ignored.contains("tests/test_math.py::test_rounding"));
+        assert!(!result.xpassed.contains("tests/test_math.py::test_rounding"));
+        assert!(result.passed.contains("tests/test_math.py::test_overflow"));
+        assert!(result.passed.contains("tests/test_math.py::test_underflow"));
+        assert!(result.failed.contains("tests/test_math.py::test_strict"));
+        assert_eq!(result.xpassed.len(), 3);
+    }
+
+    #[test]
+    fn test_rerun_history_uses_final_status() {
+        let log_content = r#"
+tests/test_net.py::test_fetch RERUN [ 50%]
+tests/test_net.py::test_fetch RERUN [ 50%]
+tests/test_net.py::test_fetch PASSED [ 50%]
+tests/test_net.py::test_upload RERUN [100%]
+tests/test_net.py::test_upload FAILED [100%]
+RERUN tests/test_net.py::test_fetch - ConnectionError
+FAILED tests/test_net.py::test_upload - ConnectionError
+"#;
+
+        let parser = PythonLogParser::new();
+        let result = parser.parse_with_framework("pytest_enhanced", log_content);
+
+        assert!(result.passed.contains("tests/test_net.py::test_fetch"));
+        assert!(!result.failed.contains("tests/test_net.py::test_fetch"));
+        assert!(result.failed.contains("tests/test_net.py::test_upload"));
+        assert_eq!(result.attempts["tests/test_net.py::test_fetch"], vec!["RERUN", "RERUN", "PASSED"]);
+        assert_eq!(result.attempts["tests/test_net.py::test_upload"], vec!["RERUN", "FAILED"]);
+    }
+
     #[test]
     fn test_clean_ansi_escapes() {
         let input = "[32mPASSED[0m test_name";
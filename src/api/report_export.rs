@@ -0,0 +1,189 @@
+//! Downloadable report of an analysis, for attaching the verdict to the review ticket
+//!
+//! The JSON file is the full `LogAnalysisResult`, so it can be loaded back or diffed. The
+//! Markdown file is for people: the verdict, every rule with its examples, each listed test's
+//! status per stage, and the per-log counts the Debug tab shows.
+
+use std::collections::HashMap;
+
+use crate::app::number_format::format_count;
+use crate::app::types::{rule_description, ExportedReport, LogAnalysisResult, LogCount, StageStatusSummary};
+
+// The ticket wants the verdict, not every offending test of a large suite
+const MAX_EXAMPLES_PER_RULE: usize = 50;
+
+// Table cells can't hold pipes or line breaks
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn code_cell(value: &str) -> String {
+    format!("`{}`", cell(&value.replace('`', "'")))
+}
+
+fn rule_table(analysis: &LogAnalysisResult) -> String {
+    let mut table = String::from("| Rule | Description | Result | Examples |\n|---|---|---|---|\n");
+    for (code, violation) in analysis.rule_violations.entries() {
+        // Results from before `rules_evaluated` was recorded list none
+        let checked = analysis.rules_evaluated.is_empty() || analysis.rules_evaluated.iter().any(|rule| rule == code);
+        let result = if !checked { "not checked" } else if violation.has_problem { "❌ violated" } else { "✅ ok" };
+        table.push_str(&format!("| {} | {} | {} | {} |\n", code, cell(rule_description(code)), result, format_count(violation.examples.len())));
+    }
+    table
+}
+
+fn status_table(statuses: &HashMap<String, StageStatusSummary>) -> String {
+    let mut names: Vec<&String> = statuses.keys().collect();
+    names.sort();
+    let mut table = String::from("| Test | Base | Before | After | Agent | Report |\n|---|---|---|---|---|---|\n");
+    for name in names {
+        let status = &statuses[name];
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            code_cell(name),
+            cell(&status.base),
+            cell(&status.before),
+            cell(&status.after),
+            cell(&status.agent),
+            cell(&status.report)
+        ));
+    }
+    table
+}
+
+fn counts_table(log_counts: &[LogCount]) -> String {
+    let mut table = String::from("| Log | Parser | Passed | Failed | Ignored | All |\n|---|---|---|---|---|---|\n");
+    for count in log_counts {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            cell(&count.label),
+            cell(count.parser.as_deref().unwrap_or("—")),
+            format_count(count.passed),
+            format_count(count.failed),
+            format_count(count.ignored),
+            format_count(count.all)
+        ));
+    }
+    table
+}
+
+/// Render the analysis as a Markdown report
+pub fn format_markdown_report(instance_id: &str, task_id: &str, analysis: &LogAnalysisResult) -> String {
+    let violations = analysis.rule_violations.entries();
+    let violated: Vec<_> = violations.iter().filter(|(_, v)| v.has_problem).collect();
+
+    let mut report = format!("# Analysis report for {}\n\n", if instance_id.is_empty() { "deliverable" } else { instance_id });
+    if !task_id.is_empty() {
+        report.push_str(&format!("**Task:** `{}`\n\n", task_id));
+    }
+    if violated.is_empty() {
+        report.push_str("**Verdict:** no rule violations found\n\n");
+    } else {
+        let codes: Vec<&str> = violated.iter().map(|(code, _)| *code).collect();
+        report.push_str(&format!("**Verdict:** {} rule(s) violated ({})\n\n", violated.len(), codes.join(", ")));
+    }
+
+    report.push_str("## Rules\n\n");
+    report.push_str(&rule_table(analysis));
+    report.push('\n');
+    for (code, violation) in violated {
+        if violation.examples.is_empty() {
+            continue;
+        }
+        report.push_str(&format!("### {} examples\n\n", code));
+        for example in violation.examples.iter().take(MAX_EXAMPLES_PER_RULE) {
+            report.push_str(&format!("- `{}`\n", example.replace('`', "'")));
+        }
+        if violation.examples.len() > MAX_EXAMPLES_PER_RULE {
+            report.push_str(&format!("- … and {} more\n", format_count(violation.examples.len() - MAX_EXAMPLES_PER_RULE)));
+        }
+        report.push('\n');
+    }
+
+    report.push_str(&format!("## F2P tests ({})\n\n", format_count(analysis.test_statuses.f2p.len())));
+    report.push_str(&status_table(&analysis.test_statuses.f2p));
+    report.push_str(&format!("\n## P2P tests ({})\n\n", format_count(analysis.test_statuses.p2p.len())));
+    report.push_str(&status_table(&analysis.test_statuses.p2p));
+
+    report.push_str("\n## Log counts\n\n");
+    report.push_str(&counts_table(&analysis.debug_info.log_counts));
+    if let Some(agent_log) = &analysis.debug_info.agent_log {
+        report.push_str(&format!("\nAgent log: `{}`\n", agent_log));
+    }
+    if !analysis.debug_info.missing_stages.is_empty() {
+        report.push_str(&format!("\nMissing logs: {}\n", analysis.debug_info.missing_stages.join(", ")));
+    }
+
+    if !analysis.overrides.is_empty() {
+        report.push_str(&format!("\n## Manual status overrides ({})\n\n", analysis.overrides.len()));
+        for o in &analysis.overrides {
+            report.push_str(&format!("- `{}` [{}]: {}\n", o.test_name.replace('`', "'"), o.stage, o.describe()));
+        }
+    }
+    report
+}
+
+/// Both files of the report
+pub fn export_report(instance_id: &str, task_id: &str, analysis: &LogAnalysisResult) -> Result<ExportedReport, String> {
+    Ok(ExportedReport {
+        json: serde_json::to_string_pretty(analysis).map_err(|e| format!("Failed to serialize the analysis: {}", e))?,
+        markdown: format_markdown_report(instance_id, task_id, analysis),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(base: &str, after: &str) -> StageStatusSummary {
+        StageStatusSummary {
+            base: base.to_string(),
+            before: "failed".to_string(),
+            after: after.to_string(),
+            agent: "passed".to_string(),
+            report: "passed".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_status_table_is_sorted_and_escaped() {
+        let statuses: HashMap<String, StageStatusSummary> = [
+            ("tests/b.py::test_b".to_string(), summary("passed", "passed")),
+            ("tests/a.py::test_a[x|y]".to_string(), summary("missing", "failed")),
+        ].into_iter().collect();
+        let table = status_table(&statuses);
+        let rows: Vec<&str> = table.lines().skip(2).collect();
+        assert_eq!(rows, vec![
+            "| `tests/a.py::test_a[x\\|y]` | missing | failed | failed | passed | passed |",
+            "| `tests/b.py::test_b` | passed | failed | passed | passed | passed |",
+        ]);
+    }
+
+    #[test]
+    fn test_counts_table_shows_unknown_parser() {
+        let counts = vec![LogCount {
+            label: "after".to_string(),
+            passed: 10,
+            failed: 2,
+            ignored: 1,
+            all: 13,
+            parser: None,
+            warnings: vec![],
+        }];
+        assert!(counts_table(&counts).ends_with("| after | — | 10 | 2 | 1 | 13 |\n"));
+    }
+
+    #[test]
+    fn test_counts_table_separates_thousands() {
+        let counts = vec![LogCount {
+            label: "base".to_string(),
+            passed: 1_234_567,
+            failed: 0,
+            ignored: 1_000,
+            all: 1_235_567,
+            parser: Some("pytest".to_string()),
+            warnings: vec![],
+        }];
+        assert!(counts_table(&counts).ends_with("| base | pytest | 1,234,567 | 0 | 1,000 | 1,235,567 |\n"));
+    }
+}
@@ -0,0 +1,283 @@
+//! report.json schema adapters
+//!
+//! report.json has shipped in several layouts. Each known layout has an explicit adapter
+//! and the first adapter that recognizes the document wins, so a new variant is one more
+//! entry in `ADAPTERS` instead of another branch in the rule checks.
+//!
+//! Known layouts:
+//! - `msft`: `{"schema_version": "2.0", "tests": [{"name": "...", "outcome": "passed"}]}`,
+//!   at the root or under the instance ID
+//! - `swebench_tests_status`: `{"<instance>": {"tests_status": {"FAIL_TO_PASS": {"success": [], "failure": []}}}}`
+//! - `legacy_results`: `{"results": [{"test_name": "...", "status": "failed"}]}` (or `test_results`)
+//! - `tests_map`: `{"tests": {"<test>": {"status": "passed"}}}`
+//! - `flat_map`: `{"<test>": "passed"}`
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::app::types::ReportCategories;
+
+pub const SCHEMA_MSFT: &str = "msft";
+pub const SCHEMA_SWEBENCH: &str = "swebench_tests_status";
+pub const SCHEMA_LEGACY_RESULTS: &str = "legacy_results";
+pub const SCHEMA_TESTS_MAP: &str = "tests_map";
+pub const SCHEMA_FLAT_MAP: &str = "flat_map";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReportStatuses {
+    /// Adapter that recognized the document, with its version when the report declares one
+    pub schema: String,
+    pub passed: HashSet<String>,
+    pub failed: HashSet<String>,
+}
+
+impl ReportStatuses {
+    fn new(schema: &str) -> Self {
+        Self { schema: schema.to_string(), ..Default::default() }
+    }
+
+    fn record(&mut self, test_name: &str, status: &str) {
+        match normalize_status(status) {
+            Some(true) => { self.passed.insert(test_name.to_string()); }
+            Some(false) => { self.failed.insert(test_name.to_string()); }
+            None => {}
+        }
+    }
+
+    pub fn status_of(&self, test_name: &str) -> &'static str {
+        if self.failed.contains(test_name) {
+            "failed"
+        } else if self.passed.contains(test_name) {
+            "passed"
+        } else {
+            "missing"
+        }
+    }
+}
+
+// Some(true) for a pass, Some(false) for a failure, None for skips and anything unrecognized
+fn normalize_status(status: &str) -> Option<bool> {
+    match status.to_lowercase().as_str() {
+        "passed" | "pass" | "success" | "ok" => Some(true),
+        "failed" | "fail" | "failure" | "error" => Some(false),
+        _ => None,
+    }
+}
+
+fn str_field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| value.get(*key).and_then(|v| v.as_str()))
+}
+
+// The report body is either the root or nested under the instance ID
+fn root_or_nested(report: &Value, is_body: impl Fn(&Value) -> bool) -> Option<&Value> {
+    if is_body(report) {
+        return Some(report);
+    }
+    report.as_object()?.values().find(|value| is_body(value))
+}
+
+fn parse_msft(report: &Value) -> Option<ReportStatuses> {
+    let body = root_or_nested(report, |v| {
+        v.get("schema_version").is_some() && v.get("tests").is_some_and(|t| t.is_array())
+    })?;
+    let version = match body.get("schema_version")? {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let mut out = ReportStatuses::new(&format!("{} v{}", SCHEMA_MSFT, version));
+    for test in body.get("tests")?.as_array()? {
+        if let (Some(name), Some(status)) = (
+            str_field(test, &["name", "nodeid", "test_name"]),
+            str_field(test, &["outcome", "status", "result"]),
+        ) {
+            out.record(name, status);
+        }
+    }
+    Some(out)
+}
+
+fn parse_swebench(report: &Value) -> Option<ReportStatuses> {
+    let body = root_or_nested(report, |v| v.get("tests_status").is_some_and(|t| t.is_object()))?;
+    let mut out = ReportStatuses::new(SCHEMA_SWEBENCH);
+    for category in body.get("tests_status")?.as_object()?.values() {
+        for (key, status) in [("success", "passed"), ("failure", "failed")] {
+            let names = category.get(key).and_then(|v| v.as_array()).into_iter().flatten();
+            for name in names.filter_map(|v| v.as_str()) {
+                out.record(name, status);
+            }
+        }
+    }
+    Some(out)
+}
+
+/// Number of names in tests_status' FAIL_TO_PASS and PASS_TO_PASS, success and failure together
+pub fn tests_status_list_counts(report: &Value) -> Option<(usize, usize)> {
+    let body = root_or_nested(report, |v| v.get("tests_status").is_some_and(|t| t.is_object()))?;
+    let tests_status = body.get("tests_status")?;
+    let count = |category: &str| -> usize {
+        ["success", "failure"]
+            .iter()
+            .filter_map(|key| tests_status.get(category)?.get(*key)?.as_array().map(|names| names.len()))
+            .sum()
+    };
+    Some((count("FAIL_TO_PASS"), count("PASS_TO_PASS")))
+}
+
+fn parse_legacy_results(report: &Value) -> Option<ReportStatuses> {
+    let results = report
+        .get("results")
+        .or_else(|| report.get("test_results"))
+        .and_then(|r| r.as_array())?;
+    let mut out = ReportStatuses::new(SCHEMA_LEGACY_RESULTS);
+    for result in results {
+        if let (Some(name), Some(status)) = (str_field(result, &["test_name"]), str_field(result, &["status"])) {
+            out.record(name, status);
+        }
+    }
+    Some(out)
+}
+
+fn parse_tests_map(report: &Value) -> Option<ReportStatuses> {
+    let tests = report.get("tests").and_then(|t| t.as_object())?;
+    let mut out = ReportStatuses::new(SCHEMA_TESTS_MAP);
+    for (name, data) in tests {
+        if let Some(status) = str_field(data, &["status"]) {
+            out.record(name, status);
+        }
+    }
+    Some(out)
+}
+
+// Catch-all, so it only claims documents with at least one recognizable status
+fn parse_flat_map(report: &Value) -> Option<ReportStatuses> {
+    let mut out = ReportStatuses::new(SCHEMA_FLAT_MAP);
+    for (name, status) in report.as_object()? {
+        if let Some(status) = status.as_str() {
+            out.record(name, status);
+        }
+    }
+    if out.passed.is_empty() && out.failed.is_empty() {
+        None
+    } else {
+        Some(out)
+    }
+}
+
+/// F2P and P2P successes and failures. A `tests_status` report's own categories are used as
+/// listed; any other layout has only statuses, so `f2p` and `p2p` are split by them and tests
+/// the report doesn't mention are left out
+pub fn report_categories(report: &Value, f2p: &[String], p2p: &[String]) -> Result<ReportCategories, String> {
+    if let Some(tests_status) = root_or_nested(report, |v| v.get("tests_status").is_some_and(|t| t.is_object())).and_then(|body| body.get("tests_status")) {
+        let list = |category: &str, key: &str| -> Vec<String> {
+            tests_status
+                .get(category)
+                .and_then(|c| c.get(key))
+                .and_then(|names| names.as_array())
+                .map(|names| names.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        return Ok(ReportCategories {
+            schema: SCHEMA_SWEBENCH.to_string(),
+            from_report: true,
+            f2p_success: list("FAIL_TO_PASS", "success"),
+            f2p_failure: list("FAIL_TO_PASS", "failure"),
+            p2p_success: list("PASS_TO_PASS", "success"),
+            p2p_failure: list("PASS_TO_PASS", "failure"),
+        });
+    }
+
+    let statuses = parse_report(report)?;
+    let split = |tests: &[String], status: &str| -> Vec<String> {
+        tests.iter().filter(|test| statuses.status_of(test) == status).cloned().collect()
+    };
+    Ok(ReportCategories {
+        schema: statuses.schema.clone(),
+        from_report: false,
+        f2p_success: split(f2p, "passed"),
+        f2p_failure: split(f2p, "failed"),
+        p2p_success: split(p2p, "passed"),
+        p2p_failure: split(p2p, "failed"),
+    })
+}
+
+type Adapter = fn(&Value) -> Option<ReportStatuses>;
+
+// Most specific layouts first
+const ADAPTERS: &[Adapter] = &[parse_msft, parse_swebench, parse_legacy_results, parse_tests_map, parse_flat_map];
+
+/// Test statuses from report.json, or a diagnostic describing the unrecognized layout
+pub fn parse_report(report: &Value) -> Result<ReportStatuses, String> {
+    if let Some(statuses) = ADAPTERS.iter().find_map(|adapter| adapter(report)) {
+        return Ok(statuses);
+    }
+
+    let shape = match report.as_object() {
+        Some(obj) => {
+            let mut keys: Vec<&str> = obj.keys().map(String::as_str).take(10).collect();
+            keys.sort_unstable();
+            format!("top-level keys: {}", if keys.is_empty() { "none".to_string() } else { keys.join(", ") })
+        }
+        None => "top level is not an object".to_string(),
+    };
+    Err(format!(
+        "Unknown report.json schema ({}); report statuses are treated as missing and C6 is skipped",
+        shape
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_each_known_schema_is_recognized() {
+        let cases = [
+            (json!({"schema_version": "2.1", "tests": [{"name": "t_a", "outcome": "PASSED"}, {"nodeid": "t_b", "outcome": "failed"}]}), "msft v2.1"),
+            (json!({"repo__name-1": {"schema_version": 2, "tests": [{"name": "t_a", "status": "pass"}, {"name": "t_b", "status": "error"}]}}), "msft v2"),
+            (json!({"repo__name-1": {"tests_status": {"FAIL_TO_PASS": {"success": ["t_a"], "failure": ["t_b"]}}}}), SCHEMA_SWEBENCH),
+            (json!({"results": [{"test_name": "t_a", "status": "success"}, {"test_name": "t_b", "status": "fail"}]}), SCHEMA_LEGACY_RESULTS),
+            (json!({"tests": {"t_a": {"status": "passed"}, "t_b": {"status": "failed"}}}), SCHEMA_TESTS_MAP),
+            (json!({"t_a": "passed", "t_b": "failed"}), SCHEMA_FLAT_MAP),
+        ];
+
+        for (report, schema) in cases {
+            let statuses = parse_report(&report).unwrap();
+            assert_eq!(statuses.schema, schema);
+            assert_eq!((statuses.status_of("t_a"), statuses.status_of("t_b"), statuses.status_of("t_c")), ("passed", "failed", "missing"));
+        }
+    }
+
+    #[test]
+    fn test_tests_status_list_counts() {
+        let report = json!({"repo__name-1": {"tests_status": {
+            "FAIL_TO_PASS": {"success": ["t_a"], "failure": ["t_b"]},
+            "PASS_TO_PASS": {"success": ["t_c"], "failure": []}
+        }}});
+        assert_eq!(tests_status_list_counts(&report), Some((2, 1)));
+        assert_eq!(tests_status_list_counts(&json!({"t_a": "passed"})), None);
+    }
+
+    #[test]
+    fn test_report_categories_split_other_layouts_by_the_lists() {
+        let f2p = vec!["t_a".to_string(), "t_b".to_string()];
+        let p2p = vec!["t_c".to_string(), "t_d".to_string()];
+        let categories = report_categories(&json!({"t_a": "passed", "t_b": "failed", "t_c": "passed"}), &f2p, &p2p).unwrap();
+        assert_eq!(categories.schema, SCHEMA_FLAT_MAP);
+        assert!(!categories.from_report);
+        assert_eq!((categories.f2p_success, categories.f2p_failure), (vec!["t_a".to_string()], vec!["t_b".to_string()]));
+        assert_eq!((categories.p2p_success, categories.p2p_failure), (vec!["t_c".to_string()], vec![]));
+
+        let listed = json!({"repo__name-1": {"tests_status": {"FAIL_TO_PASS": {"success": ["t_x"], "failure": []}}}});
+        let categories = report_categories(&listed, &f2p, &p2p).unwrap();
+        assert!(categories.from_report);
+        assert_eq!(categories.f2p_success, vec!["t_x".to_string()]);
+    }
+
+    #[test]
+    fn test_unknown_schema_diagnostic() {
+        let err = parse_report(&json!({"summary": {"total": 3}, "version": 1})).unwrap_err();
+        assert!(err.starts_with("Unknown report.json schema (top-level keys: summary, version)"));
+    }
+}
@@ -0,0 +1,279 @@
+//! One zip a reviewer can attach to the task record
+//!
+//! The bundle holds the report export (`analysis.json`, `report.md`), every listed test with
+//! its status per stage and the rules it trips (`tests.csv`), and, under `excerpts/`, the log
+//! lines behind each violation: the first mention of the test in the stage log the rule read,
+//! or the exact lines for C5 duplicates. Rules about patches or the test lists themselves
+//! have no log lines to show.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::api::log_analysis::log_slices;
+use crate::api::report_export::export_report;
+use crate::app::types::{example_test_name, LogAnalysisResult, LogSlice, StageStatusSummary};
+
+// Enough to see the test's output without attaching whole logs
+const EXCERPT_RADIUS: usize = 20;
+// A rule with thousands of examples doesn't need an excerpt for each
+const MAX_EXCERPTS_PER_RULE: usize = 10;
+// A test repeated hundreds of times in a log is made clear by its first few repeats
+const MAX_EXCERPTS_PER_DUPLICATE: usize = 5;
+
+// Stage logs a rule's examples come from
+fn rule_stages(code: &str) -> &'static [&'static str] {
+    match code {
+        "C1" => &["base"],
+        "C2" => &["after"],
+        "C3" => &["before"],
+        "C4" => &["base", "before"],
+        "C6" => &["agent"],
+        "C11" => &["after", "agent"],
+        _ => &[],
+    }
+}
+
+// Test name of a rule example; `None` for notes
+fn example_name(example: &str) -> Option<&str> {
+    if example.starts_with("Not evaluated") {
+        return None;
+    }
+    Some(example_test_name(example)).filter(|name| !name.is_empty())
+}
+
+/// A log excerpt the bundle should carry for a violation
+#[derive(Debug, PartialEq)]
+pub struct ExcerptRef {
+    pub code: String,
+    pub stage: String,
+    pub test_name: String,
+    // Exact line when the analysis recorded one, else the test's first mention
+    pub line: Option<usize>,
+}
+
+impl ExcerptRef {
+    fn file_name(&self, index: usize) -> String {
+        let test: String = self
+            .test_name
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' { c } else { '_' })
+            .take(80)
+            .collect();
+        format!("excerpts/{}-{}-{:02}-{}.txt", self.code, self.stage, index + 1, test)
+    }
+}
+
+/// Excerpts for the violated rules, in rule order
+pub fn violation_excerpts(analysis: &LogAnalysisResult) -> Vec<ExcerptRef> {
+    let mut excerpts = Vec::new();
+    for (code, violation) in analysis.rule_violations.entries() {
+        if !violation.has_problem {
+            continue;
+        }
+        if code == "C5" {
+            // One excerpt per repeated occurrence, at the line it was found on
+            excerpts.extend(analysis.debug_info.duplicates.iter().take(MAX_EXCERPTS_PER_RULE).flat_map(|duplicate| {
+                duplicate.lines.iter().take(MAX_EXCERPTS_PER_DUPLICATE).map(|line| ExcerptRef {
+                    code: code.to_string(),
+                    stage: duplicate.stage.clone(),
+                    test_name: duplicate.test_name.clone(),
+                    line: Some(*line),
+                })
+            }));
+            continue;
+        }
+        let names = violation.examples.iter().filter_map(|example| example_name(example)).take(MAX_EXCERPTS_PER_RULE);
+        for name in names {
+            excerpts.extend(rule_stages(code).iter().map(|stage| ExcerptRef {
+                code: code.to_string(),
+                stage: stage.to_string(),
+                test_name: name.to_string(),
+                line: None,
+            }));
+        }
+    }
+    excerpts
+}
+
+fn render_excerpt(excerpt: &ExcerptRef, slice: &LogSlice) -> String {
+    let mut out = format!("{} in the {} log: {}\n\n", excerpt.code, excerpt.stage, excerpt.test_name);
+    for (offset, line) in slice.lines.iter().enumerate() {
+        let number = slice.first_line + offset;
+        let marker = if number == slice.line { '>' } else { ' ' };
+        out.push_str(&format!("{} {:>6} | {}\n", marker, number, line));
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Every listed test with its stage statuses and the codes of the rules naming it
+pub fn tests_csv(analysis: &LogAnalysisResult) -> String {
+    let mut rules_by_test: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (code, violation) in analysis.rule_violations.entries() {
+        for name in violation.examples.iter().filter_map(|example| example_name(example)) {
+            let codes = rules_by_test.entry(name).or_default();
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+    }
+    for duplicate in &analysis.debug_info.duplicates {
+        let codes = rules_by_test.entry(duplicate.test_name.as_str()).or_default();
+        if !codes.contains(&"C5") {
+            codes.push("C5");
+        }
+    }
+
+    let mut csv = String::from("type,test,base,before,after,agent,report,rules\n");
+    let mut push_rows = |test_type: &str, statuses: &HashMap<String, StageStatusSummary>| {
+        let mut names: Vec<&String> = statuses.keys().collect();
+        names.sort();
+        for name in names {
+            let status = &statuses[name];
+            let rules = rules_by_test.get(name.as_str()).map(|codes| codes.join(" ")).unwrap_or_default();
+            let fields = [test_type, name.as_str(), status.base.as_str(), status.before.as_str(), status.after.as_str(), status.agent.as_str(), status.report.as_str(), rules.as_str()];
+            csv.push_str(&fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+            csv.push('\n');
+        }
+    };
+    push_rows("F2P", &analysis.test_statuses.f2p);
+    push_rows("P2P", &analysis.test_statuses.p2p);
+    csv
+}
+
+/// The zipped bundle for `analysis` of the deliverable at `file_paths`
+pub fn build_review_bundle(file_paths: &[String], instance_id: &str, task_id: &str, analysis: &LogAnalysisResult) -> Result<Vec<u8>, String> {
+    let report = export_report(instance_id, task_id, analysis)?;
+    let mut files = vec![
+        ("analysis.json".to_string(), report.json),
+        ("report.md".to_string(), report.markdown),
+        ("tests.csv".to_string(), tests_csv(analysis)),
+    ];
+
+    let excerpts = violation_excerpts(analysis);
+    let anchors: Vec<(String, Option<usize>, String)> = excerpts
+        .iter()
+        .map(|excerpt| (excerpt.stage.clone(), excerpt.line, excerpt.test_name.clone()))
+        .collect();
+    let slices = log_slices(file_paths.to_vec(), analysis.debug_info.agent_log.clone(), &anchors, EXCERPT_RADIUS)?;
+    let mut per_rule_stage: HashMap<(String, String), usize> = HashMap::new();
+    for (excerpt, slice) in excerpts.iter().zip(slices) {
+        // A log that is gone or never mentions the test is simply not excerpted
+        let Some(slice) = slice else { continue };
+        let index = per_rule_stage.entry((excerpt.code.clone(), excerpt.stage.clone())).or_default();
+        files.push((excerpt.file_name(*index), render_excerpt(excerpt, &slice)));
+        *index += 1;
+    }
+
+    zip_files(files)
+}
+
+fn zip_files(files: Vec<(String, String)>) -> Result<Vec<u8>, String> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in files {
+        zip.start_file(name, options).map_err(|e| format!("Failed to write the bundle: {}", e))?;
+        zip.write_all(content.as_bytes()).map_err(|e| format!("Failed to write the bundle: {}", e))?;
+    }
+    let cursor = zip.finish().map_err(|e| format!("Failed to write the bundle: {}", e))?;
+    Ok(cursor.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_name_drops_explanations_and_notes() {
+        assert_eq!(example_name("tests::a (skipped in after)"), Some("tests::a"));
+        assert_eq!(example_name("tests::b"), Some("tests::b"));
+        assert_eq!(example_name("Not evaluated: after log missing"), None);
+        // Django names carry their class in parentheses
+        assert_eq!(example_name("test_x (app.tests.ModelTest)"), Some("test_x (app.tests.ModelTest)"));
+        assert_eq!(example_name("test_x (app.tests.ModelTest) (skipped in after)"), Some("test_x (app.tests.ModelTest)"));
+        assert_eq!(example_name("test_y[a (b)] (skipped in agent)"), Some("test_y[a (b)]"));
+    }
+
+    fn analysis_with_violations() -> LogAnalysisResult {
+        use crate::app::types::{DuplicateTest, RuleViolation};
+
+        let mut analysis = LogAnalysisResult::default();
+        analysis.rule_violations.c4_p2p_missing_in_base_and_not_passing_in_before = RuleViolation {
+            has_problem: true,
+            examples: vec!["tests::a (base: missing, before: failed)".to_string()],
+        };
+        analysis.rule_violations.c11_f2p_skipped_in_after_or_agent = RuleViolation {
+            has_problem: true,
+            examples: (0..15).map(|n| format!("tests::skip_{} (skipped in after)", n)).collect(),
+        };
+        analysis.rule_violations.c5_duplicates_in_same_log = RuleViolation { has_problem: true, examples: vec![] };
+        analysis.debug_info.duplicates = vec![DuplicateTest {
+            stage: "after".to_string(),
+            test_name: "tests::a".to_string(),
+            lines: (1..=20).collect(),
+        }];
+        let status = |after: &str| StageStatusSummary {
+            base: "missing".to_string(),
+            before: "failed".to_string(),
+            after: after.to_string(),
+            agent: "passed".to_string(),
+            report: "passed".to_string(),
+        };
+        analysis.test_statuses.p2p.insert("tests::a".to_string(), status("passed"));
+        analysis.test_statuses.f2p.insert("tests::skip_0".to_string(), status("skipped"));
+        analysis
+    }
+
+    #[test]
+    fn test_violation_excerpts_cover_rule_stages_with_caps() {
+        let excerpts = violation_excerpts(&analysis_with_violations());
+        let count = |code: &str| excerpts.iter().filter(|excerpt| excerpt.code == code).count();
+        // C4 reads base and before
+        assert_eq!(count("C4"), 2);
+        assert!(excerpts.iter().any(|excerpt| excerpt.code == "C4" && excerpt.stage == "before" && excerpt.test_name == "tests::a" && excerpt.line.is_none()));
+        assert_eq!(count("C5"), MAX_EXCERPTS_PER_DUPLICATE);
+        assert_eq!(excerpts.iter().find(|excerpt| excerpt.code == "C5").unwrap().line, Some(1));
+        // Ten examples, each in after and agent
+        assert_eq!(count("C11"), MAX_EXCERPTS_PER_RULE * 2);
+    }
+
+    #[test]
+    fn test_tests_csv_lists_statuses_and_rules() {
+        let csv = tests_csv(&analysis_with_violations());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "type,test,base,before,after,agent,report,rules");
+        assert_eq!(lines[1], "F2P,tests::skip_0,missing,failed,skipped,passed,passed,C11");
+        assert_eq!(lines[2], "P2P,tests::a,missing,failed,passed,passed,passed,C4 C5");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_zip_files_round_trip() {
+        use std::io::Read;
+
+        let files = vec![
+            ("tests.csv".to_string(), "type,test\n".to_string()),
+            ("excerpts/C2-after-01-tests__a.txt".to_string(), "> 1 | boom\n".to_string()),
+        ];
+        let bytes = zip_files(files).unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 2);
+        let mut content = String::new();
+        archive.by_name("excerpts/C2-after-01-tests__a.txt").unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "> 1 | boom\n");
+    }
+
+    #[test]
+    fn test_csv_field_quotes_separators() {
+        assert_eq!(csv_field("test_a"), "test_a");
+        assert_eq!(csv_field("test[a,b]"), "\"test[a,b]\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,146 @@
+//! Two reviewers on one deliverable
+//!
+//! Open review tabs send a heartbeat, so each reviewer sees who else has the deliverable
+//! open. Every recorded decision is appended to the deliverable's decision log
+//! (`<folder>/reviews/decision_log.json` in the review store); the latest entry is the
+//! verdict that stands, and an entry that replaces another reviewer's verdict names it, so
+//! the log shows how a conflict was resolved.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+use crate::api::analysis_store::deliverable_folder_id;
+use crate::api::storage::review_store;
+use crate::app::types::{DecisionLogEntry, ReviewDecision, ReviewPresence};
+
+pub const DECISION_LOG_FILE: &str = "reviews/decision_log.json";
+/// A reviewer counts as present this long after their last heartbeat
+pub const PRESENCE_TTL_SECS: u64 = 90;
+
+lazy_static! {
+    // folder id -> reviewer -> last heartbeat; presence is short-lived, so it stays in memory
+    static ref PRESENCE: Mutex<HashMap<String, HashMap<String, u64>>> = Mutex::new(HashMap::new());
+    // Serializes load-append-save of decision logs within this server
+    static ref DECISION_LOG_LOCK: Mutex<()> = Mutex::new(());
+}
+
+fn same_reviewer(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Record the reviewer's heartbeat and return the other reviewers still present, dropping stale ones
+pub fn touch_presence(seen: &mut HashMap<String, u64>, reviewer: &str, now: u64) -> Vec<String> {
+    seen.retain(|name, last| now.saturating_sub(*last) <= PRESENCE_TTL_SECS && !same_reviewer(name, reviewer));
+    let mut others: Vec<String> = seen.keys().cloned().collect();
+    others.sort();
+    seen.insert(reviewer.trim().to_string(), now);
+    others
+}
+
+/// The standing decision when it was recorded by someone other than `reviewer`
+pub fn other_decision(log: &[DecisionLogEntry], reviewer: &str) -> Option<DecisionLogEntry> {
+    log.last().filter(|entry| !same_reviewer(&entry.reviewer, reviewer)).cloned()
+}
+
+/// Append the decision to the log, noting the other reviewer's verdict it replaces
+pub fn append_decision(log: &mut Vec<DecisionLogEntry>, decision: &ReviewDecision, now: u64) -> DecisionLogEntry {
+    let replaced = other_decision(log, &decision.reviewer);
+    let entry = DecisionLogEntry {
+        reviewer: decision.reviewer.trim().to_string(),
+        verdict: decision.verdict.clone(),
+        comment: decision.comment.clone(),
+        decided_at: now,
+        supersedes_reviewer: replaced.as_ref().map(|entry| entry.reviewer.clone()),
+        supersedes_verdict: replaced.map(|entry| entry.verdict),
+    };
+    log.push(entry.clone());
+    entry
+}
+
+/// Decisions recorded on the deliverable, oldest first
+pub fn load_decision_log(file_paths: &[String]) -> Result<Vec<DecisionLogEntry>, String> {
+    match deliverable_folder_id(file_paths) {
+        Some(folder_id) => load_folder_decision_log(&folder_id),
+        None => Ok(vec![]),
+    }
+}
+
+/// Decisions recorded on the deliverable cached in `folder_id`, oldest first
+pub fn load_folder_decision_log(folder_id: &str) -> Result<Vec<DecisionLogEntry>, String> {
+    match review_store()?.load(folder_id, DECISION_LOG_FILE)? {
+        // An unreadable log is reported, never replaced by one holding only the new decision
+        Some(content) => serde_json::from_str(&content).map_err(|e| format!("Failed to parse decision log: {}", e)),
+        None => Ok(vec![]),
+    }
+}
+
+/// Add a recorded decision to the deliverable's decision log
+pub fn log_decision(file_paths: &[String], decision: &ReviewDecision, now: u64) -> Result<DecisionLogEntry, String> {
+    let folder_id = deliverable_folder_id(file_paths).ok_or("No deliverable folder to log the decision in")?;
+    let _guard = DECISION_LOG_LOCK.lock().map_err(|_| "Decision log lock poisoned".to_string())?;
+    let mut log = load_decision_log(file_paths)?;
+    let entry = append_decision(&mut log, decision, now);
+    let content = serde_json::to_string_pretty(&log).map_err(|e| format!("Failed to serialize decision log: {}", e))?;
+    review_store()?.save(&folder_id, DECISION_LOG_FILE, &content)?;
+    Ok(entry)
+}
+
+/// Heartbeat from a reviewer with the deliverable open
+pub fn review_presence(file_paths: &[String], reviewer: &str, now: u64) -> Result<ReviewPresence, String> {
+    if reviewer.trim().is_empty() {
+        return Err("Reviewer name is required".to_string());
+    }
+    let folder_id = deliverable_folder_id(file_paths).ok_or("No deliverable folder to track reviewers on")?;
+    let others = {
+        let mut presence = PRESENCE.lock().map_err(|_| "Presence lock poisoned".to_string())?;
+        touch_presence(presence.entry(folder_id).or_default(), reviewer, now)
+    };
+    Ok(ReviewPresence {
+        others,
+        other_decision: other_decision(&load_decision_log(file_paths)?, reviewer),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(reviewer: &str, verdict: &str) -> ReviewDecision {
+        ReviewDecision {
+            deliverable_link: "https://drive.google.com/drive/folders/abc".to_string(),
+            instance_id: "repo__1".to_string(),
+            reviewer: reviewer.to_string(),
+            verdict: verdict.to_string(),
+            comment: String::new(),
+            violation_summary: String::new(),
+            review_seconds: None,
+        }
+    }
+
+    #[test]
+    fn test_presence_expires() {
+        let mut seen = HashMap::new();
+        assert!(touch_presence(&mut seen, "Sam", 100).is_empty());
+        assert_eq!(touch_presence(&mut seen, "Alex", 120), vec!["Sam"]);
+        assert_eq!(touch_presence(&mut seen, "sam", 150), vec!["Alex"]);
+        assert!(touch_presence(&mut seen, "Alex", 150 + PRESENCE_TTL_SECS + 1).is_empty());
+    }
+
+    #[test]
+    fn test_last_decision_wins_and_is_logged() {
+        let mut log = vec![];
+        let first = append_decision(&mut log, &decision("Sam", "approved"), 10);
+        assert_eq!(first.supersedes_reviewer, None);
+        assert_eq!(other_decision(&log, "alex").map(|entry| entry.verdict), Some("approved".to_string()));
+        assert_eq!(other_decision(&log, "sam"), None);
+
+        let second = append_decision(&mut log, &decision("Alex", "rejected"), 20);
+        assert_eq!((second.supersedes_reviewer.as_deref(), second.supersedes_verdict.as_deref()), (Some("Sam"), Some("approved")));
+        // Revising your own decision replaces nobody's
+        let third = append_decision(&mut log, &decision("Alex", "needs_changes"), 30);
+        assert_eq!(third.supersedes_reviewer, None);
+        assert_eq!(log.len(), 3);
+    }
+}
@@ -0,0 +1,186 @@
+//! The team's review queue
+//!
+//! Leads queue deliverable links and assign each to a reviewer; reviewers pick their work
+//! from "my queue" on the landing page, and recording a decision on a queued deliverable
+//! closes its item. The queue is one document in the review store, so a team sharing a
+//! Postgres backend shares one queue; every change is written only if the document is still
+//! the one it was made from, so two servers claiming the same item cannot both win.
+
+use crate::api::storage::review_store;
+use crate::app::types::{QueueItem, QueueStatus};
+
+// Not a deliverable folder: Drive folder ids never start with an underscore
+const QUEUE_FOLDER: &str = "_team";
+const QUEUE_FILE: &str = "review_queue.json";
+// Attempts before giving up on a queue that keeps changing under us
+const QUEUE_WRITE_ATTEMPTS: usize = 8;
+
+/// Identity of a deliverable link: the Drive folder id when it has one, so pasted
+/// `?usp=sharing` variants match the link the reviewer loaded
+pub fn queue_link_key(link: &str) -> String {
+    let link = link.trim();
+    if let Some(rest) = link.split("folders/").nth(1) {
+        return rest.split(['/', '?']).next().unwrap_or("").to_string();
+    }
+    link.split(['?', '#']).next().unwrap_or("").trim_end_matches('/').to_string()
+}
+
+fn same_reviewer(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Queue the link for `assignee`; a link that is already open is reassigned instead of queued twice
+pub fn enqueue(queue: &mut Vec<QueueItem>, deliverable_link: &str, assignee: &str, added_by: &str, now: u64) -> Result<QueueItem, String> {
+    let deliverable_link = deliverable_link.trim();
+    let key = queue_link_key(deliverable_link);
+    if key.is_empty() {
+        return Err("A deliverable link is required".to_string());
+    }
+    if assignee.trim().is_empty() {
+        return Err("Choose a reviewer to assign the deliverable to".to_string());
+    }
+    if let Some(item) = queue.iter_mut().find(|item| item.status != QueueStatus::Done && queue_link_key(&item.deliverable_link) == key) {
+        item.assignee = assignee.trim().to_string();
+        item.status = QueueStatus::Assigned;
+        return Ok(item.clone());
+    }
+    let item = QueueItem {
+        id: queue.iter().map(|item| item.id).max().unwrap_or(0) + 1,
+        deliverable_link: deliverable_link.to_string(),
+        assignee: assignee.trim().to_string(),
+        added_by: added_by.trim().to_string(),
+        status: QueueStatus::Assigned,
+        verdict: None,
+        added_at: now,
+        decided_at: None,
+    };
+    queue.push(item.clone());
+    Ok(item)
+}
+
+/// Close the open items for the decided link; returns whether any was closed
+pub fn complete(queue: &mut [QueueItem], deliverable_link: &str, verdict: &str, now: u64) -> bool {
+    let key = queue_link_key(deliverable_link);
+    let mut closed = false;
+    for item in queue.iter_mut().filter(|item| item.status != QueueStatus::Done && queue_link_key(&item.deliverable_link) == key) {
+        item.status = QueueStatus::Done;
+        item.verdict = Some(verdict.to_string());
+        item.decided_at = Some(now);
+        closed = true;
+    }
+    closed
+}
+
+/// The reviewer's items: open ones oldest first, then the ones they closed, newest first
+pub fn queue_for(queue: &[QueueItem], reviewer: &str) -> Vec<QueueItem> {
+    let mut open: Vec<QueueItem> = queue.iter().filter(|item| same_reviewer(&item.assignee, reviewer) && item.status != QueueStatus::Done).cloned().collect();
+    open.sort_by_key(|item| item.added_at);
+    let mut done: Vec<QueueItem> = queue.iter().filter(|item| same_reviewer(&item.assignee, reviewer) && item.status == QueueStatus::Done).cloned().collect();
+    done.sort_by_key(|item| std::cmp::Reverse(item.decided_at));
+    open.extend(done);
+    open
+}
+
+fn parse_queue(content: Option<&str>) -> Result<Vec<QueueItem>, String> {
+    match content {
+        Some(content) => serde_json::from_str(content).map_err(|e| format!("Failed to parse review queue: {}", e)),
+        None => Ok(vec![]),
+    }
+}
+
+/// Every item in the queue, empty when nothing was queued yet
+pub fn load_queue() -> Result<Vec<QueueItem>, String> {
+    parse_queue(review_store()?.load(QUEUE_FOLDER, QUEUE_FILE)?.as_deref())
+}
+
+/// Apply `change` to the stored queue and save it, redoing the change on the newer queue
+/// when another writer saved in between
+pub fn update_queue<T>(mut change: impl FnMut(&mut Vec<QueueItem>) -> Result<T, String>) -> Result<T, String> {
+    let store = review_store()?;
+    for _ in 0..QUEUE_WRITE_ATTEMPTS {
+        let stored = store.load(QUEUE_FOLDER, QUEUE_FILE)?;
+        let mut queue = parse_queue(stored.as_deref())?;
+        let changed = change(&mut queue)?;
+        let content = serde_json::to_string_pretty(&queue).map_err(|e| format!("Failed to serialize review queue: {}", e))?;
+        if store.save_if_unchanged(QUEUE_FOLDER, QUEUE_FILE, stored.as_deref(), &content)? {
+            return Ok(changed);
+        }
+    }
+    Err("The review queue kept changing while saving; try again".to_string())
+}
+
+/// Claim an assigned item for review; false when it was already claimed or closed
+pub fn claim(queue: &mut [QueueItem], id: u64) -> bool {
+    match queue.iter_mut().find(|item| item.id == id && item.status == QueueStatus::Assigned) {
+        Some(item) => {
+            item.status = QueueStatus::InReview;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Mark an item as being reviewed once its reviewer opens it
+pub fn start_item(id: u64) -> Result<bool, String> {
+    update_queue(|queue| Ok(claim(queue, id)))
+}
+
+/// Drop an item from the queue
+pub fn remove_item(id: u64) -> Result<(), String> {
+    update_queue(|queue| {
+        queue.retain(|item| item.id != id);
+        Ok(())
+    })
+}
+
+/// Close the queued item for a recorded decision; decisions on unqueued deliverables leave the queue alone
+pub fn complete_queued_review(deliverable_link: &str, verdict: &str, now: u64) -> Result<(), String> {
+    update_queue(|queue| {
+        complete(queue, deliverable_link, verdict, now);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_key_matches_folder_variants() {
+        assert_eq!(queue_link_key("https://drive.google.com/drive/folders/abc123?usp=sharing"), "abc123");
+        assert_eq!(queue_link_key(" https://drive.google.com/drive/u/0/folders/abc123/ "), "abc123");
+        assert_eq!(queue_link_key("https://platform.example.com/tasks/42/?tab=files"), "https://platform.example.com/tasks/42");
+    }
+
+    #[test]
+    fn test_queue_assigns_and_completes() {
+        let mut queue = vec![];
+        let first = enqueue(&mut queue, "https://drive.google.com/drive/folders/aaa", "Sam", "Lead", 10).unwrap();
+        enqueue(&mut queue, "https://drive.google.com/drive/folders/bbb", "sam", "Lead", 20).unwrap();
+        enqueue(&mut queue, "https://drive.google.com/drive/folders/ccc", "Alex", "Lead", 5).unwrap();
+        assert!(enqueue(&mut queue, " ", "Sam", "Lead", 30).is_err());
+        assert!(enqueue(&mut queue, "https://drive.google.com/drive/folders/ddd", "", "Lead", 30).is_err());
+
+        // Re-queueing an open link reassigns it
+        let reassigned = enqueue(&mut queue, "https://drive.google.com/drive/folders/ccc?usp=sharing", "Sam", "Lead", 40).unwrap();
+        assert_eq!((reassigned.id, queue.len()), (3, 3));
+
+        let mine: Vec<u64> = queue_for(&queue, "SAM").iter().map(|item| item.id).collect();
+        assert_eq!(mine, vec![3, 1, 2]);
+
+        assert!(complete(&mut queue, "https://drive.google.com/drive/folders/aaa", "approved", 50));
+        assert!(!complete(&mut queue, "https://drive.google.com/drive/folders/zzz", "approved", 50));
+        let mine = queue_for(&queue, "Sam");
+        assert_eq!(mine.iter().map(|item| item.id).collect::<Vec<_>>(), vec![3, 2, first.id]);
+        assert_eq!((mine[2].status, mine[2].verdict.as_deref(), mine[2].decided_at), (QueueStatus::Done, Some("approved"), Some(50)));
+
+        assert!(claim(&mut queue, 2));
+        assert!(!claim(&mut queue, 2));
+        assert!(!claim(&mut queue, first.id));
+        assert_eq!(queue_for(&queue, "Sam")[1].status, QueueStatus::InReview);
+
+        // A done link can be queued again for a second look
+        let again = enqueue(&mut queue, "https://drive.google.com/drive/folders/aaa", "Alex", "Lead", 60).unwrap();
+        assert_eq!(again.id, 4);
+    }
+}
@@ -0,0 +1,140 @@
+//! Reviewer-platform task links (`https://swebench-reviewer.turing.com/<id>`)
+//!
+//! Issues reference tasks by their platform link rather than the Drive folder. The
+//! folder is resolved through the mapping API configured in the `reviewer_platform`
+//! section of the server config, which is expected to answer with JSON containing a
+//! Drive folder link or ID (`drive_folder_url`, `drive_link`, `drive_folder_id` or
+//! `folder_id`) and optionally the canonical `task_id`.
+
+use crate::api::server_config::{load_server_config, ReviewerPlatformConfig};
+use crate::app::types::PlatformTask;
+use crate::drive::extract_drive_folder_id;
+
+pub const DEFAULT_PLATFORM_HOST: &str = "swebench-reviewer.turing.com";
+
+fn platform_hosts(config: &ReviewerPlatformConfig) -> Vec<String> {
+    if config.hosts.is_empty() {
+        vec![DEFAULT_PLATFORM_HOST.to_string()]
+    } else {
+        config.hosts.iter().map(|h| h.trim().to_lowercase()).collect()
+    }
+}
+
+/// Extract the task ID from a reviewer-platform link on one of the given hosts
+pub fn parse_platform_task_id(link: &str, hosts: &[String]) -> Option<String> {
+    let link = link.trim();
+    let without_scheme = link
+        .strip_prefix("https://")
+        .or_else(|| link.strip_prefix("http://"))
+        .unwrap_or(link);
+
+    let (host, path) = without_scheme.split_once('/')?;
+    let host = host.to_lowercase();
+    if !hosts.contains(&host) {
+        return None;
+    }
+
+    let id = path
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("")
+        .trim_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or("");
+
+    if id.is_empty() {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+/// Pull the folder ID and task ID out of a mapping API response
+pub fn parse_mapping_response(task_id: &str, json: &serde_json::Value) -> Option<PlatformTask> {
+    let field = |name: &str| json.get(name).and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+
+    let folder_id = field("drive_folder_url")
+        .or_else(|| field("drive_link"))
+        .and_then(extract_drive_folder_id)
+        .or_else(|| field("drive_folder_id").or_else(|| field("folder_id")).map(|s| s.to_string()))?;
+
+    Some(PlatformTask {
+        task_id: field("task_id").unwrap_or(task_id).to_string(),
+        folder_id,
+    })
+}
+
+/// Resolve a reviewer-platform link to its Drive folder via the mapping API
+pub async fn resolve_platform_link(link: &str) -> Result<PlatformTask, String> {
+    let config = load_server_config().reviewer_platform;
+    let task_id = parse_platform_task_id(link, &platform_hosts(&config))
+        .ok_or_else(|| format!("Not a reviewer-platform link: {}", link))?;
+
+    let mapping_url = config
+        .mapping_url
+        .as_deref()
+        .filter(|u| !u.trim().is_empty())
+        .ok_or("Reviewer-platform links need `reviewer_platform.mapping_url` in the server config")?;
+    let url = mapping_url.replace("{id}", &urlencoding::encode(&task_id));
+
+    let mut request = reqwest::Client::new()
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(15));
+    if let Some(token) = config.token.as_deref().filter(|t| !t.is_empty()) {
+        request = request.bearer_auth(token);
+    }
+
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach the reviewer-platform mapping API: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("Mapping API responded with {} for task {}", resp.status(), task_id));
+    }
+
+    let json: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse mapping API response: {}", e))?;
+
+    parse_mapping_response(&task_id, &json)
+        .ok_or_else(|| format!("Mapping API returned no Drive folder for task {}", task_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_platform_task_id() {
+        let hosts = vec![DEFAULT_PLATFORM_HOST.to_string()];
+        assert_eq!(
+            parse_platform_task_id("https://swebench-reviewer.turing.com/12345", &hosts).as_deref(),
+            Some("12345")
+        );
+        assert_eq!(
+            parse_platform_task_id("https://SWEBENCH-REVIEWER.turing.com/tasks/abc-9/?tab=logs", &hosts).as_deref(),
+            Some("abc-9")
+        );
+        assert_eq!(parse_platform_task_id("https://swebench-reviewer.turing.com/", &hosts), None);
+        assert_eq!(parse_platform_task_id("https://drive.google.com/drive/folders/abc", &hosts), None);
+    }
+
+    #[test]
+    fn test_parse_mapping_response() {
+        let json = serde_json::json!({ "drive_folder_url": "https://drive.google.com/drive/folders/xyz?usp=sharing" });
+        assert_eq!(
+            parse_mapping_response("42", &json),
+            Some(PlatformTask { task_id: "42".to_string(), folder_id: "xyz".to_string() })
+        );
+
+        let json = serde_json::json!({ "folder_id": "f1", "task_id": "T-42" });
+        assert_eq!(
+            parse_mapping_response("42", &json),
+            Some(PlatformTask { task_id: "T-42".to_string(), folder_id: "f1".to_string() })
+        );
+
+        assert_eq!(parse_mapping_response("42", &serde_json::json!({})), None);
+    }
+}
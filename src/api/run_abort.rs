@@ -0,0 +1,75 @@
+//! Infrastructure aborts (OOM kills, timeouts) spotted in stage logs
+//!
+//! When a run is killed part-way, every test after that point simply has no result,
+//! which looks the same as a patch that broke collection. Flagging the stage lets the
+//! reviewer tell the two apart.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::StageAbort;
+
+pub const ABORT_REASON_KILLED: &str = "killed";
+pub const ABORT_REASON_TIMEOUT: &str = "timeout";
+
+lazy_static! {
+    static ref ABORT_PATTERNS: Vec<(Regex, &'static str)> = vec![
+        // Shell job report (`script.sh: line 3:  1234 Killed  pytest ...`) or `Killed` on its own line
+        (Regex::new(r"^\s*Killed\s*$|: line \d+:\s+\d+ Killed\b").unwrap(), ABORT_REASON_KILLED),
+        // Runner reports of a SIGKILLed child (cargo, Go, Python's subprocess), the kernel OOM
+        // killer, and container runtimes
+        (Regex::new(r"\(signal: 9, SIGKILL|signal: killed$|<Signals\.SIGKILL: 9>|Out of memory: Kill(?:ed)? process|\bOOMKilled\b|exit(?:ed with)? code:? 137\b").unwrap(), ABORT_REASON_KILLED),
+        (Regex::new(r"timeout: sending signal").unwrap(), ABORT_REASON_TIMEOUT),
+        // Evaluation harness markers
+        (Regex::new(r"(?i)timeout error:|test runtime exceeded|exceeded the timeout|harness timed out|timed out after \d+").unwrap(), ABORT_REASON_TIMEOUT),
+    ];
+}
+
+/// First abort marker in the log, as (reason, offending line)
+pub fn detect_run_abort(content: &str) -> Option<(&'static str, String)> {
+    content.lines().find_map(|line| {
+        ABORT_PATTERNS
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(line))
+            .map(|(_, reason)| (*reason, line.trim().to_string()))
+    })
+}
+
+/// Abort flag for one stage log
+pub fn stage_abort(stage: &str, content: &str) -> Option<StageAbort> {
+    detect_run_abort(content).map(|(reason, evidence)| StageAbort {
+        stage: stage.to_string(),
+        reason: reason.to_string(),
+        evidence,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_kills_and_timeouts() {
+        let oom = "test a ... ok\n/eval.sh: line 12:  4242 Killed                  cargo test\n";
+        assert_eq!(detect_run_abort(oom), Some((ABORT_REASON_KILLED, "/eval.sh: line 12:  4242 Killed                  cargo test".to_string())));
+
+        let cargo = "error: test failed, to rerun pass `--lib`\nCaused by:\n  process didn't exit successfully: `target/debug/deps/x` (signal: 9, SIGKILL: kill)";
+        assert_eq!(detect_run_abort(cargo).map(|(reason, _)| reason), Some(ABORT_REASON_KILLED));
+
+        let timeout = "collected 12 items\ntimeout: sending signal TERM to command 'pytest'";
+        assert_eq!(detect_run_abort(timeout).map(|(reason, _)| reason), Some(ABORT_REASON_TIMEOUT));
+    }
+
+    #[test]
+    fn test_ignores_test_names_mentioning_kills() {
+        let log = "tests/test_proc.py::test_process_killed PASSED\ntests/test_proc.py::test_timeout_handling PASSED\n\
+                   test_worker (tests.ProcTest) ... Killed workers are restarted ok\n\
+                   --- PASS: TestSIGKILLHandling (0.01s)\n\
+                   test signals::forwards_sigkill_to_children ... ok";
+        assert_eq!(detect_run_abort(log), None);
+
+        let kernel = "[ 812.4] Out of memory: Killed process 4242 (python) total-vm:8123456kB";
+        assert_eq!(detect_run_abort(kernel).map(|(reason, _)| reason), Some(ABORT_REASON_KILLED));
+        assert_eq!(detect_run_abort("running tests\nKilled\n").map(|(reason, _)| reason), Some(ABORT_REASON_KILLED));
+    }
+}
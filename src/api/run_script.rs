@@ -0,0 +1,216 @@
+//! Framework hints inferred from the run scripts shipped with a deliverable
+//!
+//! Some deliverables include the script (`run_tests.sh`, `eval.sh`, ...) or CI
+//! config that produced the logs. When present, the exact test command tells us
+//! which framework ran and from which directory, which is more reliable than
+//! guessing from log content alone.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::RunScriptHints;
+
+/// File names recognized as run scripts when found at the root of a deliverable folder
+pub const RUN_SCRIPT_NAMES: &[&str] = &[
+    "run_tests.sh",
+    "run_test.sh",
+    "run_script.sh",
+    "run.sh",
+    "test.sh",
+    "eval.sh",
+];
+
+/// Extensions recognized as CI configuration when found at the root of a deliverable folder
+pub const CI_CONFIG_EXTENSIONS: &[&str] = &[".yml", ".yaml"];
+
+/// Relative folder under which run scripts are stored once downloaded
+pub const RUN_SCRIPT_FOLDER: &str = "scripts";
+
+lazy_static! {
+    static ref CD_RE: Regex = Regex::new(r#"^\s*(?:pushd|cd)\s+["']?([^\s"';&|]+)["']?"#).unwrap();
+    static ref WORKING_DIRECTORY_RE: Regex = Regex::new(r#"^\s*-?\s*working[-_]directory:\s*["']?([^\s"']+)["']?"#).unwrap();
+    static ref YAML_RUN_RE: Regex = Regex::new(r"^\s*-?\s*run:\s*[|>]?-?\s*(.*)$").unwrap();
+}
+
+/// Returns true if a file name found at the deliverable root looks like a run script or CI config
+pub fn is_run_script_name(file_name: &str) -> bool {
+    let lower = file_name.to_lowercase();
+    RUN_SCRIPT_NAMES.contains(&lower.as_str())
+        || CI_CONFIG_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+}
+
+/// Map a single shell command to a parser/framework name understood by the language parsers
+///
+/// Returned names match the framework keys used by `RustLogParser`, `PythonLogParser`
+/// `JavaScriptLogParser`, `GoLogParser`, `JavaLogParser` and `CppLogParser` ("nextest", "cargo", "pytest", "jest",
+/// "gotest", "maven", "ctest", ...).
+pub fn framework_from_command(command: &str) -> Option<String> {
+    let cmd = command.to_lowercase();
+
+    // Order matters: more specific runners first
+    let rules: &[(&str, &str)] = &[
+        ("cargo nextest", "nextest"),
+        ("cargo test", "cargo"),
+        ("runtests.py", "django"),
+        ("manage.py test", "django"),
+        ("bin/test", "sympy"),
+        ("pytest", "pytest"),
+        ("playwright test", "playwright"),
+        ("cypress run", "cypress"),
+        ("vitest", "vitest"),
+        ("jest", "jest"),
+        ("karma", "karma"),
+        ("mocha", "mocha"),
+        ("jasmine", "jasmine"),
+        ("qunit", "qunit"),
+        ("npx ava", "ava"),
+        ("tap ", "tap"),
+        ("gotestsum", "gotestsum"),
+        ("go test", "gotest"),
+        ("mvn ", "maven"),
+        ("mvnw ", "maven"),
+        ("gradle", "gradle"),
+        ("ctest", "ctest"),
+        ("--gtest_", "gtest"),
+    ];
+
+    rules
+        .iter()
+        .find(|(needle, _)| cmd.contains(needle))
+        .map(|(_, framework)| framework.to_string())
+}
+
+/// Parse the contents of a run script or CI config and extract the test command hints
+///
+/// The last recognizable test command wins, since setup steps usually come first.
+/// The working directory is the last `cd`/`pushd`/`working-directory:` seen before it.
+pub fn infer_from_run_script(source_file: &str, content: &str) -> Option<RunScriptHints> {
+    let mut current_dir: Option<String> = None;
+    let mut hints: Option<RunScriptHints> = None;
+
+    for raw_line in content.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(caps) = WORKING_DIRECTORY_RE.captures(line) {
+            current_dir = Some(caps[1].to_string());
+            continue;
+        }
+
+        // CI configs put the command after `run:`
+        let command = YAML_RUN_RE
+            .captures(line)
+            .map(|caps| caps[1].trim().to_string())
+            .unwrap_or_else(|| line.to_string());
+
+        // A line may chain `cd dir && cmd`
+        for part in command.split("&&") {
+            let part = part.trim();
+            if let Some(caps) = CD_RE.captures(part) {
+                current_dir = Some(caps[1].to_string());
+                continue;
+            }
+            if let Some(framework) = framework_from_command(part) {
+                hints = Some(RunScriptHints {
+                    source_file: source_file.to_string(),
+                    command: part.to_string(),
+                    framework,
+                    working_dir: current_dir.clone(),
+                });
+            }
+        }
+    }
+
+    hints
+}
+
+/// Find a run script among the deliverable files and infer framework hints from it
+pub fn find_run_script_hints(file_paths: &[String]) -> Option<RunScriptHints> {
+    let folder = format!("{}/", RUN_SCRIPT_FOLDER);
+    let mut scripts: Vec<&String> = file_paths
+        .iter()
+        .filter(|path| path.replace('\\', "/").contains(&folder))
+        .collect();
+
+    // Prefer shell scripts over CI configs since they hold the exact command
+    scripts.sort_by_key(|path| {
+        let lower = path.to_lowercase();
+        CI_CONFIG_EXTENSIONS.iter().any(|ext| lower.ends_with(ext))
+    });
+
+    for path in scripts {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Failed to read run script {}: {}", path, e);
+                continue;
+            }
+        };
+        let file_name = std::path::Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.clone());
+        if let Some(hints) = infer_from_run_script(&file_name, &content) {
+            return Some(hints);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framework_from_command() {
+        assert_eq!(framework_from_command("cargo nextest run --no-fail-fast").as_deref(), Some("nextest"));
+        assert_eq!(framework_from_command("cargo test --workspace -- --nocapture").as_deref(), Some("cargo"));
+        assert_eq!(framework_from_command("./tests/runtests.py --verbosity 2").as_deref(), Some("django"));
+        assert_eq!(framework_from_command("python -m pytest -rA tests/").as_deref(), Some("pytest"));
+        assert_eq!(framework_from_command("npx vitest run").as_deref(), Some("vitest"));
+        assert_eq!(framework_from_command("yarn jest --ci").as_deref(), Some("jest"));
+        assert_eq!(framework_from_command("go test -v -json ./...").as_deref(), Some("gotest"));
+        assert_eq!(framework_from_command("./gradlew test --tests com.acme.CalcTest").as_deref(), Some("gradle"));
+        assert_eq!(framework_from_command("ctest --output-on-failure -j4").as_deref(), Some("ctest"));
+        assert_eq!(framework_from_command("pip install -e .").as_deref(), None);
+    }
+
+    #[test]
+    fn test_infer_from_shell_script() {
+        let script = r#"#!/bin/bash
+set -e
+cd /app
+pip install -e .
+cd packages/core && npx jest --ci --verbose
+"#;
+        let hints = infer_from_run_script("run_tests.sh", script).unwrap();
+        assert_eq!(hints.framework, "jest");
+        assert_eq!(hints.command, "npx jest --ci --verbose");
+        assert_eq!(hints.working_dir.as_deref(), Some("packages/core"));
+    }
+
+    #[test]
+    fn test_infer_from_ci_config() {
+        let config = r#"
+jobs:
+  test:
+    steps:
+      - uses: actions/checkout@v4
+      - working-directory: crates/engine
+        run: cargo nextest run
+"#;
+        let hints = infer_from_run_script("ci.yml", config).unwrap();
+        assert_eq!(hints.framework, "nextest");
+        assert_eq!(hints.working_dir.as_deref(), Some("crates/engine"));
+    }
+
+    #[test]
+    fn test_is_run_script_name() {
+        assert!(is_run_script_name("run_tests.sh"));
+        assert!(is_run_script_name("CI.yaml"));
+        assert!(!is_run_script_name("instance.json"));
+    }
+}
@@ -1,6 +1,5 @@
 use regex::Regex;
 use std::collections::HashSet;
-use std::fs;
 use lazy_static::lazy_static;
 
 use super::log_parser::{LogParserTrait, ParsedLog};
@@ -77,6 +76,10 @@ lazy_static! {
     static ref SINGLE_LINE_STATUS_AT_START_RE: Regex = Regex::new(r"(?i)^(ok|FAILED|ignored|error)").unwrap();
     static ref SIMPLE_PATTERN_RE: Regex = Regex::new(r"(?i)(?:\d+)?test\s+[^\s]+(?:::[^\s]+)*\s*\.{2,}\s*(ok|FAILED|ignored|error)").unwrap();
     
+    // `#[should_panic]` tests: libtest appends " - should panic" to the name, and a passing one
+    // prints its (expected) panic message before `ok`
+    static ref SHOULD_PANIC_RE: Regex = Regex::new(r"(?im)test\s+(\S+)\s+-\s+should panic\s+\.\.\.\s+(ok|FAILED|ignored)\b").unwrap();
+
     // Pattern for tests that have diagnostic info after the "..." but before status
     static ref TEST_WITH_DIAGNOSTICS_RE: Regex = Regex::new(r"(?i)(?:\d+)?test\s+(.+?)\s+\.\.\.\s*(?:error:|$)").unwrap();
 }
@@ -94,22 +97,23 @@ impl LogParserTrait for RustLogParser {
         "rust"
     }
 
-    fn parse_log_file(&self, file_path: &str) -> Result<ParsedLog, String> {
-        let content = fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read log file {}: {}", file_path, e))?;
-
-        // Check for nextest format first
-        if looks_nextest_format(&content) {
-            return Ok(parse_nextest_log(&content));
+    fn parse_content(&self, _file_path: &str, content: &str, framework: Option<&str>) -> Result<ParsedLog, String> {
+        // Pinned to nextest, skip the format sniffing; pinned to plain cargo test, never route
+        // to the nextest parser; otherwise check for nextest format first
+        match framework {
+            Some("nextest") => return Ok(parse_nextest_log(content).with_parser("nextest")),
+            Some("cargo") => {}
+            _ if looks_nextest_format(content) => return Ok(parse_nextest_log(content).with_parser("nextest")),
+            _ => {}
         }
 
         // Switch to ANSI/single-line parser when appropriate
-        if looks_single_line_like(&content) {
-            return Ok(parse_rust_log_single_line(&content));
+        if looks_single_line_like(content) {
+            return Ok(parse_rust_log_single_line(content).with_parser("cargo_single_line"));
         }
 
         // Use the full multi-line parser
-        parse_rust_log_file(&content)
+        parse_rust_log_file(content).map(|parsed| parsed.with_parser("cargo"))
     }
 }
 
@@ -138,7 +142,7 @@ fn looks_single_line_like(text: &str) -> bool {
     (has_ansi && line_count <= 10 && test_count >= line_count / 2)
 }
 
-fn looks_nextest_format(text: &str) -> bool {
+pub(crate) fn looks_nextest_format(text: &str) -> bool {
     // Check for nextest-specific patterns
     let nextest_indicators = [
         "Nextest run ID",
@@ -292,7 +296,7 @@ fn parse_nextest_log(text: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn parse_rust_log_single_line(text: &str) -> ParsedLog {
@@ -463,12 +467,14 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
         }
     }
 
+    apply_should_panic(&clean, &mut passed, &mut failed, &mut ignored);
+
     let mut all = HashSet::new();
     all.extend(passed.iter().cloned());
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, ..Default::default() }
 }
 
 fn strip_ansi_color_codes(s: &str) -> String {
@@ -877,6 +883,8 @@ fn parse_rust_log_file(text: &str) -> Result<ParsedLog, String> {
             collecting = false;
         }
     }
+
+    apply_should_panic(&strip_ansi_color_codes(text), &mut passed, &mut failed, &mut ignored);
     
     let mut all = HashSet::new();
     all.extend(passed.iter().cloned());
@@ -888,9 +896,33 @@ fn parse_rust_log_file(text: &str) -> Result<ParsedLog, String> {
         failed,
         ignored,
         all,
+        ..Default::default()
     })
 }
 
+// The libtest status line is authoritative for should_panic tests: their expected panic output
+// must not mark them failed, and the " - should panic" suffix is not part of the test name
+fn apply_should_panic(
+    text: &str,
+    passed: &mut HashSet<String>,
+    failed: &mut HashSet<String>,
+    ignored: &mut HashSet<String>,
+) {
+    for cap in SHOULD_PANIC_RE.captures_iter(text) {
+        let name = cap.get(1).unwrap().as_str().to_string();
+        let suffixed = format!("{} - should panic", name);
+        for set in [&mut *passed, &mut *failed, &mut *ignored] {
+            set.remove(&suffixed);
+            set.remove(&name);
+        }
+        match cap.get(2).unwrap().as_str().to_lowercase().as_str() {
+            "ok" => { passed.insert(name); }
+            "failed" => { failed.insert(name); }
+            _ => { ignored.insert(name); }
+        }
+    }
+}
+
 // Helper function to check if an error status is part of diagnostic messages
 fn is_diagnostic_error(status: &str, line: &str) -> bool {
     if status != "error" {
@@ -999,3 +1031,38 @@ fn extract_test_name_from_nextest_line(full_line: &str) -> String {
     // If no patterns match, return the original
     trimmed.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_panic_tests_pass() {
+        let log = "running 3 tests\n\
+                   thread 'parser::rejects_empty' panicked at src/parser.rs:42:9:\n\
+                   empty input\n\
+                   test parser::rejects_empty - should panic ... ok\n\
+                   test parser::rejects_nul - should panic ... FAILED\n\
+                   test parser::parses ... ok\n\
+                   \n\
+                   failures:\n\
+                   \n\
+                   ---- parser::rejects_nul stdout ----\n\
+                   note: test did not panic as expected\n\
+                   \n\
+                   failures:\n    parser::rejects_nul\n\
+                   \n\
+                   test result: FAILED. 2 passed; 1 failed; 0 ignored";
+
+        let parsed = parse_rust_log_file(log).unwrap();
+        assert!(parsed.passed.contains("parser::rejects_empty"));
+        assert!(!parsed.failed.contains("parser::rejects_empty"));
+        assert!(parsed.failed.contains("parser::rejects_nul"));
+        assert!(parsed.passed.contains("parser::parses"));
+        assert!(!parsed.all.iter().any(|name| name.ends_with("should panic")));
+
+        let single_line = parse_rust_log_single_line(log);
+        assert!(single_line.passed.contains("parser::rejects_empty"));
+        assert!(!single_line.failed.contains("parser::rejects_empty"));
+    }
+}
@@ -0,0 +1,87 @@
+//! Parsing a pasted log snippet with the same parsers the analysis uses
+//!
+//! The snippet is written to a temporary file and handed to the registry's parser for the
+//! chosen language, or to the first one whose content sniff accepts it, so what the Debug
+//! tab shows is exactly what a stage log with those lines would produce.
+
+use std::io::Write;
+
+use crate::api::parser_registry::ParserRegistry;
+use crate::api::server_config::load_server_config;
+use crate::app::types::{ScratchParse, ScratchParsedTest};
+
+// A snippet is a few screens of log, not a whole stage log
+pub const MAX_SNIPPET_BYTES: usize = 512 * 1024;
+
+/// Tests in `snippet` with their statuses, in the order their first mention appears
+pub fn scratch_parse(registry: &ParserRegistry, snippet: &str, language: Option<&str>) -> Result<ScratchParse, String> {
+    if snippet.len() > MAX_SNIPPET_BYTES {
+        return Err(format!("Snippets are limited to {} KB", MAX_SNIPPET_BYTES / 1024));
+    }
+    let (parser_name, parser) = match language.filter(|language| !language.trim().is_empty()) {
+        Some(language) => (
+            language.trim().to_lowercase(),
+            registry.for_language(language).ok_or_else(|| format!("No parser available for language: {}", language))?,
+        ),
+        None => match registry.detect(snippet) {
+            Some((plugin, parser)) => (plugin.name.to_string(), parser),
+            None => return Ok(ScratchParse::default()),
+        },
+    };
+
+    let mut file = tempfile::NamedTempFile::new().map_err(|e| format!("Failed to create temp file: {}", e))?;
+    file.write_all(snippet.as_bytes()).map_err(|e| format!("Failed to write temp file: {}", e))?;
+    let path = file.path().to_string_lossy().to_string();
+    let parsed = parser.parse_log_file(&path)?;
+
+    let lines: Vec<&str> = snippet.lines().collect();
+    let mut tests: Vec<ScratchParsedTest> = parsed
+        .all
+        .iter()
+        .map(|name| {
+            let status = if parsed.failed.contains(name) {
+                "failed"
+            } else if parsed.passed.contains(name) {
+                "passed"
+            } else {
+                "ignored"
+            };
+            ScratchParsedTest {
+                name: name.clone(),
+                status: status.to_string(),
+                line: lines.iter().position(|line| line.contains(name.as_str())).map(|index| index + 1),
+            }
+        })
+        .collect();
+    tests.sort_by(|a, b| (a.line.is_none(), a.line, &a.name).cmp(&(b.line.is_none(), b.line, &b.name)));
+    Ok(ScratchParse { parser: parsed.parser.unwrap_or(parser_name), tests })
+}
+
+/// `scratch_parse` with the parsers this server has enabled
+pub fn scratch_parse_with_config(snippet: &str, language: Option<&str>) -> Result<ScratchParse, String> {
+    scratch_parse(&ParserRegistry::from_config(&load_server_config().parsers), snippet, language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_parser_and_orders_by_line() {
+        let snippet = "running 2 tests\n\
+                       test tests::b ... FAILED\n\
+                       test tests::a ... ok\n\
+                       \n\
+                       test result: FAILED. 1 passed; 1 failed; 0 ignored\n";
+        let parsed = scratch_parse(&ParserRegistry::all(), snippet, None).unwrap();
+        let rows: Vec<(&str, &str, Option<usize>)> = parsed.tests.iter().map(|test| (test.name.as_str(), test.status.as_str(), test.line)).collect();
+        assert_eq!(rows, vec![("tests::b", "failed", Some(2)), ("tests::a", "passed", Some(3))]);
+    }
+
+    #[test]
+    fn test_unrecognized_snippet_and_unknown_language() {
+        let registry = ParserRegistry::all();
+        assert!(scratch_parse(&registry, "hello world", None).unwrap().tests.is_empty());
+        assert!(scratch_parse(&registry, "hello world", Some("cobol")).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Spellings of a test name the log search looks for
+//!
+//! Logs rarely print a listed name verbatim: mocha prints only the title after ` - `, Jest
+//! the part after ` > `, pytest the node id after `::`, and parametrized tests may drop
+//! their parameters. The search tries the full name and the suite-less forms; the other
+//! variants are only counted, so a reviewer can tell which spelling a parser would need.
+
+use crate::api::test_name::canonical_test_name;
+use crate::app::types::{NameVariant, VariantHits};
+
+// Trailing `[params]` or `(params)` of a parametrized test, e.g. `test_add[1-2]`
+fn strip_parameters(name: &str) -> &str {
+    let trimmed = name.trim_end();
+    for (open, close) in [('[', ']'), ('(', ')')] {
+        if trimmed.ends_with(close) {
+            if let Some(start) = trimmed.rfind(open).filter(|start| *start > 0) {
+                return trimmed[..start].trim_end();
+            }
+        }
+    }
+    name
+}
+
+/// The test name's variants in the order they're tried; repeats of an earlier term are dropped
+pub fn search_variants(test_name: &str) -> Vec<NameVariant> {
+    let after = |separator: &str| test_name.rsplit(separator).next().unwrap_or(test_name).to_string();
+    let candidates = [
+        ("full name", test_name.to_string(), true),
+        ("after \" - \"", after(" - "), true),
+        ("after \" > \"", after(" > "), true),
+        ("after \"::\"", after("::"), false),
+        ("without parameters", strip_parameters(test_name).to_string(), false),
+    ];
+    let mut variants: Vec<NameVariant> = Vec::new();
+    for (label, term, searched) in candidates {
+        if term.is_empty() || variants.iter().any(|variant| variant.term == term) {
+            continue;
+        }
+        variants.push(NameVariant { label: label.to_string(), term, searched, hits: Vec::new() });
+    }
+    variants
+}
+
+/// Terms the log search matches lines against
+pub fn searched_terms(test_name: &str) -> Vec<String> {
+    search_variants(test_name).into_iter().filter(|variant| variant.searched).map(|variant| variant.term).collect()
+}
+
+/// Add each variant's hits in one stage log, matched in canonical form like the search does
+pub fn count_variant_hits(variants: &mut [NameVariant], stage: &str, content: &str) {
+    let terms: Vec<String> = variants.iter().map(|variant| canonical_test_name(&variant.term)).collect();
+    let mut hits: Vec<VariantHits> = variants.iter().map(|_| VariantHits { stage: stage.to_string(), ..Default::default() }).collect();
+    for (line_number, line) in content.lines().enumerate() {
+        let canonical_line = canonical_test_name(line);
+        for (term, hit) in terms.iter().zip(hits.iter_mut()) {
+            if canonical_line.contains(term.as_str()) {
+                hit.count += 1;
+                hit.first_line.get_or_insert(line_number + 1);
+            }
+        }
+    }
+    for (variant, hit) in variants.iter_mut().zip(hits) {
+        variant.hits.push(hit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants_of_a_parametrized_pytest_id() {
+        let variants = search_variants("tests/test_math.py::test_add[1-2]");
+        let terms: Vec<(&str, &str, bool)> = variants.iter().map(|variant| (variant.label.as_str(), variant.term.as_str(), variant.searched)).collect();
+        assert_eq!(terms, vec![
+            ("full name", "tests/test_math.py::test_add[1-2]", true),
+            ("after \"::\"", "test_add[1-2]", false),
+            ("without parameters", "tests/test_math.py::test_add", false),
+        ]);
+        assert_eq!(searched_terms("Suite > adds numbers"), vec!["Suite > adds numbers", "adds numbers"]);
+    }
+
+    #[test]
+    fn test_counts_hits_per_variant() {
+        let mut variants = search_variants("Suite > adds numbers");
+        count_variant_hits(&mut variants, "after", "RUN Suite\n  ✓ adds numbers\nPASS Suite > adds numbers\n");
+        let hits: Vec<(usize, Option<usize>)> = variants.iter().map(|variant| (variant.hits[0].count, variant.hits[0].first_line)).collect();
+        assert_eq!(hits, vec![(1, Some(3)), (2, Some(2))]);
+        assert_eq!(variants[0].hits[0].stage, "after");
+    }
+}
@@ -0,0 +1,273 @@
+//! Server-side configuration file
+//!
+//! Optional JSON file read from `SWE_REVIEWER_CONFIG` (default `swe-reviewer.config.json`
+//! in the working directory). Missing files or sections fall back to defaults, so a
+//! deployment only needs to list what it actually uses.
+
+use serde::{Deserialize, Serialize};
+
+pub const SERVER_CONFIG_ENV: &str = "SWE_REVIEWER_CONFIG";
+pub const DEFAULT_SERVER_CONFIG_PATH: &str = "swe-reviewer.config.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ServerConfig {
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub github: GitHubConfig,
+    #[serde(default)]
+    pub swebench: SweBenchDatasetConfig,
+    #[serde(default)]
+    pub analysis_api: AnalysisApiConfig,
+    #[serde(default)]
+    pub sheets: SheetsConfig,
+    #[serde(default)]
+    pub reviewer_platform: ReviewerPlatformConfig,
+    #[serde(default)]
+    pub llm: LlmConfig,
+    #[serde(default)]
+    pub log_discovery: LogDiscoveryConfig,
+    #[serde(default)]
+    pub noise_filter: NoiseFilterConfig,
+    #[serde(default)]
+    pub rule_checks: RuleChecksConfig,
+    #[serde(default)]
+    pub parsers: ParsersConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    /// Defaults per `main.json` language, e.g. `"java": { "search_context": 12 }`; a key also
+    /// covers the other languages routed to the same parser (`javascript` covers `typescript`)
+    #[serde(default)]
+    pub languages: std::collections::HashMap<String, LanguageConfig>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LanguageConfig {
+    /// Lines shown above and below each log search hit, defaults to 5
+    #[serde(default)]
+    pub search_context: Option<usize>,
+    /// Noise regexes for this language's logs, applied on top of the `noise_filter` ones
+    #[serde(default)]
+    pub noise_patterns: Vec<String>,
+    /// Regex rewrites applied to test names on both sides before listed tests are matched to
+    /// log results, e.g. `{ "pattern": "\\(\\)$", "replacement": "" }` to drop Java's `()`
+    #[serde(default)]
+    pub name_rewrites: Vec<NameRewriteConfig>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct NameRewriteConfig {
+    pub pattern: String,
+    /// Replacement text; `$1`-style group references work
+    #[serde(default)]
+    pub replacement: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LimitsConfig {
+    /// Largest single file a deliverable may contain, in MB, defaults to 512; 0 turns the limit off
+    #[serde(default)]
+    pub max_file_mb: Option<u64>,
+    /// Largest total download per deliverable, in MB, defaults to 2048; 0 turns the limit off
+    #[serde(default)]
+    pub max_total_mb: Option<u64>,
+    /// Most files downloaded per deliverable, defaults to 500; 0 turns the limit off
+    #[serde(default)]
+    pub max_files: Option<usize>,
+    /// Deliverables a batch validates, downloads and analyzes at once, defaults to 3
+    #[serde(default)]
+    pub batch_concurrency: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct StorageConfig {
+    /// `files` (default, next to the cached deliverable), `sqlite` or `postgres`
+    #[serde(default)]
+    pub backend: Option<String>,
+    /// SQLite database file, defaults to `swe-reviewer.db` in the working directory
+    #[serde(default)]
+    pub sqlite_path: Option<String>,
+    /// Postgres connection string, e.g. `postgres://reviewer:secret@db/swe_reviewer`
+    #[serde(default)]
+    pub postgres_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ParsersConfig {
+    /// Experimental parsers to enable, by plugin name
+    #[serde(default)]
+    pub experimental: Vec<String>,
+    /// Parsers to turn off, by plugin name
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// How Playwright retries combine: `any_fail` (default) fails a test when any attempt failed,
+    /// `final_attempt` keeps the last attempt's status
+    #[serde(default)]
+    pub playwright_retry_policy: Option<String>,
+    /// Name Jest tests `path/to/file.test.ts::title` after their suite's PASS/FAIL header, so equal
+    /// titles in different files don't collide
+    #[serde(default)]
+    pub jest_file_names: bool,
+    /// JSON file of framework detection rules, re-read on every analysis (see `api::detection_rules`)
+    #[serde(default)]
+    pub detection_rules_file: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RuleChecksConfig {
+    #[serde(default)]
+    pub duplicates: DuplicateDetectionConfig,
+    #[serde(default)]
+    pub p2p_breadth: P2pBreadthConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct P2pBreadthConfig {
+    /// C10 fires when P2P lists under this percentage of the base log's tests, defaults to 1; 0 turns the rule off
+    #[serde(default)]
+    pub min_percent: Option<f64>,
+    /// Base logs with fewer tests than this are not checked, defaults to 200
+    #[serde(default)]
+    pub min_base_tests: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DuplicateDetectionConfig {
+    /// Occurrences fewer than this many lines apart count as duplicates, defaults to 10; 0 turns the check off
+    #[serde(default)]
+    pub min_distance: Option<usize>,
+    /// Lines compared on each side of an occurrence, defaults to 2; 0 turns the context comparison off
+    #[serde(default)]
+    pub context_window: Option<usize>,
+    /// Only flag tests whose occurrences disagree (one passed, one failed)
+    #[serde(default)]
+    pub require_status_conflict: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NoiseFilterConfig {
+    /// Regexes for build/install noise lines excluded before parsing; an empty list keeps the defaults
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// Turn noise filtering off entirely
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LogDiscoveryConfig {
+    /// File-name patterns (case-insensitive, `*` wildcard) per stage log; an empty list keeps the defaults
+    #[serde(default)]
+    pub base: Vec<String>,
+    #[serde(default)]
+    pub before: Vec<String>,
+    #[serde(default)]
+    pub after: Vec<String>,
+    /// Agent-run logs; the earliest matching pattern picks the run analyzed by default
+    #[serde(default)]
+    pub agent: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LlmConfig {
+    /// OpenAI-compatible API base, e.g. `https://api.openai.com/v1`; summaries are disabled when unset
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model name, defaults to `gpt-4o-mini`
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ReviewerPlatformConfig {
+    /// Hosts whose links are treated as reviewer-platform task links, defaults to `swebench-reviewer.turing.com`
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    /// Mapping API resolving a task ID to its Drive folder; `{id}` is replaced with the task ID
+    #[serde(default)]
+    pub mapping_url: Option<String>,
+    /// Optional bearer token for the mapping API
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SheetsConfig {
+    /// Spreadsheet that review decisions are appended to; must be shared with the service account
+    #[serde(default)]
+    pub spreadsheet_id: Option<String>,
+    /// A1 range to append to, defaults to `Decisions!A:F`
+    #[serde(default)]
+    pub range: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AnalysisApiConfig {
+    /// Bearer tokens accepted by `POST /api/analyze`; the endpoint is disabled when empty
+    #[serde(default)]
+    pub tokens: Vec<String>,
+    /// Directories whose files may be analyzed by path; uploaded bundles are always allowed
+    #[serde(default)]
+    pub allowed_roots: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SweBenchDatasetConfig {
+    /// Local copy of the dataset (JSON array or JSONL, one instance per line)
+    #[serde(default)]
+    pub dataset_path: Option<String>,
+    /// Query the Hugging Face datasets server when the instance isn't found locally
+    #[serde(default)]
+    pub fetch_remote: bool,
+    /// Dataset name used for remote lookups, defaults to `princeton-nlp/SWE-bench`
+    #[serde(default)]
+    pub dataset_name: Option<String>,
+    /// Dataset split used for remote lookups, defaults to `test`
+    #[serde(default)]
+    pub split: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct GitHubConfig {
+    /// Token used to post comments when the reviewer doesn't supply one, and to download GitHub Actions artifacts
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct NotificationsConfig {
+    /// Slack incoming-webhook URL
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+    /// Microsoft Teams incoming-webhook URL
+    #[serde(default)]
+    pub teams_webhook_url: Option<String>,
+    /// URLs sent a JSON summary of every finished analysis and reviewer decision
+    #[serde(default)]
+    pub webhook_urls: Vec<String>,
+    /// Shared secret sent with those as `X-Webhook-Secret`
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+/// Load the server config, re-reading the file so edits apply without a restart
+pub fn load_server_config() -> ServerConfig {
+    let path = std::env::var(SERVER_CONFIG_ENV)
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_SERVER_CONFIG_PATH.to_string());
+
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return ServerConfig::default(),
+    };
+
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        eprintln!("Failed to parse server config {}: {}", path, e);
+        ServerConfig::default()
+    })
+}
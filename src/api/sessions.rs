@@ -0,0 +1,123 @@
+//! Review sessions kept on the server, so `/review/{session_id}` resumes a review
+//!
+//! Once a deliverable is downloaded the page gives the review a session id and saves the
+//! same snapshot it keeps in sessionStorage under `_sessions/<id>.json` of the review store
+//! after every change. Unlike the tab's copy it survives closing the browser and can be
+//! opened on another machine. The analysis isn't copied into the session: the deliverable's
+//! persisted `analysis_result.json` is handed back with it when it still fits the session.
+//! Each snapshot carries a revision, and one older than the stored snapshot is refused, so a
+//! save that arrives late can't roll the session back.
+
+use crate::api::analysis_store::load_analysis_result;
+use crate::api::storage::review_store;
+use crate::app::session_state::{ResumedSession, ReviewSession};
+
+// Not a deliverable folder: Drive folder ids never start with an underscore
+const SESSIONS_FOLDER: &str = "_sessions";
+
+/// Session ids are hyphenated v4 UUIDs picked by the page; anything else isn't used as a store key
+pub fn is_valid_session_id(id: &str) -> bool {
+    id.len() == 36 && uuid::Uuid::parse_str(id).is_ok()
+}
+
+fn session_key(id: &str) -> Result<String, String> {
+    if !is_valid_session_id(id) {
+        return Err(format!("Invalid review session id: {}", id));
+    }
+    Ok(format!("{}.json", id.to_lowercase()))
+}
+
+// Attempts before giving up on a session that keeps changing under us
+const SESSION_WRITE_ATTEMPTS: usize = 8;
+
+/// Whether a snapshot may replace the stored one: only a newer revision does
+pub fn is_newer_session(session: &ReviewSession, stored: Option<&ReviewSession>) -> bool {
+    stored.is_none_or(|stored| session.revision > stored.revision)
+}
+
+/// Save the snapshot unless the stored one is as new; false when it was refused as stale
+pub fn save_review_session(id: &str, session: &ReviewSession) -> Result<bool, String> {
+    let key = session_key(id)?;
+    let content = serde_json::to_string_pretty(session).map_err(|e| format!("Failed to serialize review session: {}", e))?;
+    let store = review_store()?;
+    for _ in 0..SESSION_WRITE_ATTEMPTS {
+        let stored_content = store.load(SESSIONS_FOLDER, &key)?;
+        let stored: Option<ReviewSession> = stored_content.as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| format!("Failed to parse review session {}: {}", id, e))?;
+        if !is_newer_session(session, stored.as_ref()) {
+            return Ok(false);
+        }
+        if store.save_if_unchanged(SESSIONS_FOLDER, &key, stored_content.as_deref(), &content)? {
+            return Ok(true);
+        }
+    }
+    Err("The review session kept changing while saving; try again".to_string())
+}
+
+pub fn load_review_session(id: &str) -> Result<Option<ReviewSession>, String> {
+    let key = session_key(id)?;
+    let Some(content) = review_store()?.load(SESSIONS_FOLDER, &key)? else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse review session {}: {}", id, e))
+}
+
+// The stored analysis answers the session only when it ran on the same agent log and the
+// reviewer hasn't edited the test lists; otherwise the page analyzes again
+fn analysis_fits_session(session: &ReviewSession, analysis_agent_log: Option<&str>) -> bool {
+    let same_agent_log = session.selected_agent_log.is_none() || session.selected_agent_log.as_deref() == analysis_agent_log;
+    same_agent_log && session.custom_test_lists.is_none()
+}
+
+/// The session with the deliverable's stored analysis, when there is one that fits it
+pub fn resume_review_session(id: &str) -> Result<Option<ResumedSession>, String> {
+    let Some(session) = load_review_session(id)? else {
+        return Ok(None);
+    };
+    let analysis = load_analysis_result(&session.file_paths)
+        .filter(|analysis| analysis_fits_session(&session, analysis.debug_info.agent_log.as_deref()));
+    Ok(Some(ResumedSession { session, analysis }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_uuid_session_ids_are_keys() {
+        assert_eq!(session_key("6F9619FF-8B86-4011-B42D-00C04FC964FF").unwrap(), "6f9619ff-8b86-4011-b42d-00c04fc964ff.json");
+        assert!(session_key("../abc123/reviews/analysis_result").is_err());
+        assert!(session_key("6f9619ff8b864011b42d00c04fc964ff").is_err());
+        assert!(!is_valid_session_id(""));
+    }
+
+    #[test]
+    fn test_stored_analysis_must_fit_the_session() {
+        let session: ReviewSession = serde_json::from_value(serde_json::json!({
+            "deliverable_link": "https://drive.google.com/drive/folders/abc",
+            "file_paths": ["abc/main.json"],
+            "platform_task_id": "",
+            "current_selection": "fail_to_pass",
+            "selected_fail_to_pass_index": 0,
+            "selected_pass_to_pass_index": 0,
+            "active_main_tab": "manual_checker",
+            "active_tab": "base",
+            "selected_agent_log": "agent_run_2.log",
+            "report_selected_test_name": "",
+            "search_stages": [],
+            "custom_test_lists": null
+        })).unwrap();
+        assert!(analysis_fits_session(&session, Some("agent_run_2.log")));
+        assert!(!analysis_fits_session(&session, Some("agent_run_1.log")));
+
+        // Snapshots without a revision read as revision 0
+        let newer = ReviewSession { revision: 3, ..session.clone() };
+        assert!(is_newer_session(&session, None));
+        assert!(is_newer_session(&newer, Some(&session)));
+        assert!(!is_newer_session(&session, Some(&newer)) && !is_newer_session(&newer, Some(&newer)));
+    }
+}
@@ -0,0 +1,98 @@
+//! Google Sheets export of review decisions
+//!
+//! Appends one row per decision (deliverable ID, reviewer, verdict, violation summary,
+//! timestamp, review minutes) to the spreadsheet named in the `sheets` section of the server config,
+//! using the same service account as the Drive integration.
+
+use serde_json::json;
+
+use crate::api::server_config::load_server_config;
+use crate::app::types::{format_utc_seconds, ReviewDecision};
+use crate::auth::get_access_token;
+use crate::drive::extract_drive_folder_id;
+
+const DEFAULT_RANGE: &str = "Decisions!A:F";
+
+pub fn decision_row(decision: &ReviewDecision, timestamp: u64) -> Vec<String> {
+    let deliverable_id = extract_drive_folder_id(&decision.deliverable_link)
+        .unwrap_or_else(|| decision.deliverable_link.clone());
+    vec![
+        deliverable_id,
+        decision.reviewer.clone(),
+        decision.verdict.clone(),
+        decision.violation_summary.clone(),
+        format_utc_seconds(timestamp),
+        decision.review_seconds.map(|secs| format!("{:.1}", secs as f64 / 60.0)).unwrap_or_default(),
+    ]
+}
+
+/// Append the decision to the configured sheet; a no-op when no spreadsheet is configured
+pub async fn append_decision_row(decision: &ReviewDecision) -> Result<(), String> {
+    let config = load_server_config().sheets;
+    let Some(spreadsheet_id) = config.spreadsheet_id.filter(|id| !id.trim().is_empty()) else {
+        return Ok(());
+    };
+    let range = config.range.unwrap_or_else(|| DEFAULT_RANGE.to_string());
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let access_token = get_access_token()
+        .await
+        .map_err(|e| format!("Failed to get access token: {}", e))?;
+
+    let url = format!(
+        "https://sheets.googleapis.com/v4/spreadsheets/{}/values/{}:append?valueInputOption=RAW&insertDataOption=INSERT_ROWS",
+        urlencoding::encode(&spreadsheet_id),
+        urlencoding::encode(&range)
+    );
+
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .bearer_auth(access_token)
+        .timeout(std::time::Duration::from_secs(15))
+        .json(&json!({ "values": [decision_row(decision, timestamp)] }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to append to sheet: {}", e))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("Sheets API responded with {}: {}", status, body));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_timestamps() {
+        assert_eq!(format_utc_seconds(0), "1970-01-01 00:00:00");
+        assert_eq!(format_utc_seconds(951_782_400), "2000-02-29 00:00:00");
+        assert_eq!(format_utc_seconds(1_700_000_000), "2023-11-14 22:13:20");
+        assert_eq!(crate::app::types::format_utc(1_700_000_000), "2023-11-14 22:13 UTC");
+    }
+
+    #[test]
+    fn test_decision_row_uses_folder_id() {
+        let decision = ReviewDecision {
+            deliverable_link: "https://drive.google.com/drive/folders/abc123?usp=sharing".to_string(),
+            instance_id: "repo__name-1".to_string(),
+            reviewer: "sam".to_string(),
+            verdict: "approved".to_string(),
+            comment: String::new(),
+            violation_summary: "clean".to_string(),
+            review_seconds: Some(750),
+        };
+        assert_eq!(
+            decision_row(&decision, 0),
+            vec!["abc123", "sam", "approved", "clean", "1970-01-01 00:00:00", "12.5"]
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Why a test was skipped, recovered from the log text
+//!
+//! P2P tests skipped for environment reasons (missing optional dependency, wrong OS)
+//! are a common review question, so the reason printed by the runner is kept next to
+//! the `ignored` status. Runs as a pass over the log once the language parser has
+//! decided which tests were ignored.
+
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Reason shown for Rust tests marked `#[ignore]` without a message
+pub const RUST_IGNORE_REASON: &str = "#[ignore]";
+
+lazy_static! {
+    // pytest -v: `tests/test_a.py::test_x SKIPPED (needs network) [ 50%]`
+    static ref PYTEST_VERBOSE_SKIP_RE: Regex = Regex::new(r"^(\S+::\S+)\s+(?:SKIPPED|XFAIL)\s+\((.+?)\)(?:\s+\[\s*\d+%\])?$").unwrap();
+    // pytest -rA: `SKIPPED tests/test_a.py::test_x - needs network`, `XFAIL tests/test_a.py::test_y - flaky`
+    static ref PYTEST_NAMED_SKIP_RE: Regex = Regex::new(r"^(?:SKIPPED|XFAIL)\s+(\S+::\S+)\s+-\s+(.+)$").unwrap();
+    // pytest -rs: `SKIPPED [2] tests/test_a.py:14: needs network`
+    static ref PYTEST_LOCATION_SKIP_RE: Regex = Regex::new(r"^SKIPPED\s+\[(\d+)\]\s+([^:\s]+):\d+:\s+(.+)$").unwrap();
+    // cargo test: `test foo ... ignored` or `test foo ... ignored, needs a GPU`
+    static ref RUST_IGNORED_RE: Regex = Regex::new(r"^test\s+(\S+)\s+\.\.\.\s+ignored(?:,\s*(.+))?$").unwrap();
+    // Jest/Vitest: `○ skipped title`, `✎ todo title`, `↓ title [skipped]`
+    static ref JS_SKIP_RE: Regex = Regex::new(r"^(?:○\s+(skipped)|✎\s+(todo)|↓)\s+(.+?)(?:\s+\[skipped\])?$").unwrap();
+}
+
+/// Map each ignored test to the skip reason found in the log, when the runner printed one
+pub fn extract_skip_reasons(content: &str, ignored: &HashSet<String>) -> HashMap<String, String> {
+    let mut reasons: HashMap<String, String> = HashMap::new();
+    // File-level pytest summaries only name a location; resolved once named reasons are known
+    let mut location_skips: Vec<(usize, String, String)> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if let Some(caps) = PYTEST_VERBOSE_SKIP_RE.captures(line).or_else(|| PYTEST_NAMED_SKIP_RE.captures(line)) {
+            let name = caps[1].to_string();
+            if ignored.contains(&name) {
+                reasons.entry(name).or_insert_with(|| caps[2].trim().to_string());
+            }
+        } else if let Some(caps) = PYTEST_LOCATION_SKIP_RE.captures(line) {
+            let count = caps[1].parse().unwrap_or(0);
+            location_skips.push((count, caps[2].to_string(), caps[3].trim().to_string()));
+        } else if let Some(caps) = RUST_IGNORED_RE.captures(line) {
+            let name = caps[1].to_string();
+            if ignored.contains(&name) {
+                let reason = caps.get(2).map_or(RUST_IGNORE_REASON.to_string(), |m| m.as_str().trim().to_string());
+                reasons.entry(name).or_insert(reason);
+            }
+        } else if let Some(caps) = JS_SKIP_RE.captures(line) {
+            let reason = caps.get(1).or_else(|| caps.get(2)).map_or("skipped", |m| m.as_str());
+            let title = &caps[3];
+            // JS test names carry their describe path, so match on the trailing title
+            for name in ignored.iter().filter(|name| name.as_str() == title || name.ends_with(&format!(" {}", title))) {
+                reasons.entry(name.clone()).or_insert_with(|| reason.to_string());
+            }
+        }
+    }
+
+    // Only attribute a file-level reason when its count accounts for every unexplained skip in that file
+    for (count, file, reason) in location_skips {
+        let unexplained: Vec<&String> = ignored
+            .iter()
+            .filter(|name| name.starts_with(&format!("{}::", file)) && !reasons.contains_key(*name))
+            .collect();
+        if !unexplained.is_empty() && unexplained.len() == count {
+            for name in unexplained {
+                reasons.insert(name.clone(), reason.clone());
+            }
+        }
+    }
+
+    reasons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> HashSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_pytest_skip_reasons() {
+        let log = "tests/test_io.py::test_read SKIPPED (needs network) [ 33%]\n\
+                   SKIPPED [2] tests/test_gpu.py:10: CUDA not available\n\
+                   XFAIL tests/test_io.py::test_flaky - known race";
+        let ignored = names(&["tests/test_io.py::test_read", "tests/test_io.py::test_flaky", "tests/test_gpu.py::test_a", "tests/test_gpu.py::test_b"]);
+
+        let reasons = extract_skip_reasons(log, &ignored);
+        assert_eq!(reasons["tests/test_io.py::test_read"], "needs network");
+        assert_eq!(reasons["tests/test_io.py::test_flaky"], "known race");
+        assert_eq!(reasons["tests/test_gpu.py::test_a"], "CUDA not available");
+        assert_eq!(reasons["tests/test_gpu.py::test_b"], "CUDA not available");
+    }
+
+    #[test]
+    fn test_rust_and_jest_skip_reasons() {
+        let log = "test net::fetch ... ignored, requires network\n\
+                   test slow::bench ... ignored\n\
+                   ✎ todo handles empty input\n\
+                   ○ skipped renders footer";
+        let ignored = names(&["net::fetch", "slow::bench", "Parser handles empty input", "Layout renders footer"]);
+
+        let reasons = extract_skip_reasons(log, &ignored);
+        assert_eq!(reasons["net::fetch"], "requires network");
+        assert_eq!(reasons["slow::bench"], RUST_IGNORE_REASON);
+        assert_eq!(reasons["Parser handles empty input"], "todo");
+        assert_eq!(reasons["Layout renders footer"], "skipped");
+    }
+}
@@ -0,0 +1,128 @@
+//! Reviewer overrides of computed test statuses
+//!
+//! Stored with the deliverable (`<folder>/reviews/status_overrides.json` in the review store) so every
+//! analysis of that deliverable — the UI, exports and the HTTP API — sees the same statuses
+//! and recomputes the rules from them.
+
+use std::collections::HashMap;
+
+use crate::api::analysis_store::deliverable_folder_id;
+use crate::api::storage::{review_store, ReviewStore};
+use crate::app::types::StatusOverride;
+
+pub const STATUS_OVERRIDES_FILE: &str = "reviews/status_overrides.json";
+/// Stages whose computed status can be overridden
+pub const OVERRIDABLE_STAGES: &[&str] = &["base", "before", "after", "agent"];
+/// Statuses a reviewer can set
+pub const OVERRIDE_STATUSES: &[&str] = &["passed", "failed", "ignored", "missing"];
+
+// Reads of the overrides file a save retries when another reviewer saved in between
+const STATUS_OVERRIDES_WRITE_ATTEMPTS: usize = 8;
+
+// An unreadable file is an error, never an empty list a save would write over
+fn parse_status_overrides(content: Option<&str>) -> Result<Vec<StatusOverride>, String> {
+    content
+        .map(serde_json::from_str)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|e| format!("Failed to parse {}: {}", STATUS_OVERRIDES_FILE, e))
+}
+
+/// Overrides recorded for the deliverable, empty when there are none
+pub fn load_status_overrides(file_paths: &[String]) -> Result<Vec<StatusOverride>, String> {
+    let Some(folder_id) = deliverable_folder_id(file_paths) else {
+        return Ok(vec![]);
+    };
+    parse_status_overrides(review_store()?.load(&folder_id, STATUS_OVERRIDES_FILE)?.as_deref())
+}
+
+/// Record an override, replacing any earlier one for the same test and stage; an empty status removes it
+pub fn save_status_override(file_paths: &[String], status_override: StatusOverride) -> Result<Vec<StatusOverride>, String> {
+    if !OVERRIDABLE_STAGES.contains(&status_override.stage.as_str()) {
+        return Err(format!("Stage '{}' cannot be overridden", status_override.stage));
+    }
+    if !status_override.status.is_empty() && !OVERRIDE_STATUSES.contains(&status_override.status.as_str()) {
+        return Err(format!("Unknown status '{}'", status_override.status));
+    }
+    if !status_override.status.is_empty() && (status_override.author.trim().is_empty() || status_override.reason.trim().is_empty()) {
+        return Err("An override needs the reviewer's name and a reason".to_string());
+    }
+    let folder_id = deliverable_folder_id(file_paths).ok_or("No deliverable folder to store overrides in")?;
+    save_override_in(review_store()?.as_ref(), &folder_id, status_override)
+}
+
+fn save_override_in(store: &dyn ReviewStore, folder_id: &str, status_override: StatusOverride) -> Result<Vec<StatusOverride>, String> {
+    for _ in 0..STATUS_OVERRIDES_WRITE_ATTEMPTS {
+        let stored = store.load(folder_id, STATUS_OVERRIDES_FILE)?;
+        let mut overrides = parse_status_overrides(stored.as_deref())?;
+        overrides.retain(|o| !(o.test_name == status_override.test_name && o.stage == status_override.stage));
+        if !status_override.status.is_empty() {
+            overrides.push(status_override.clone());
+        }
+        let content = serde_json::to_string_pretty(&overrides).map_err(|e| format!("Failed to serialize overrides: {}", e))?;
+        if store.save_if_unchanged(folder_id, STATUS_OVERRIDES_FILE, stored.as_deref(), &content)? {
+            return Ok(overrides);
+        }
+    }
+    Err("The status overrides kept changing while saving; try again".to_string())
+}
+
+/// Replace computed statuses of one stage with the reviewer's, for tests the stage map knows
+pub fn apply_status_overrides(statuses: &mut HashMap<String, String>, stage: &str, overrides: &[StatusOverride], universe: &[String]) {
+    for o in overrides.iter().filter(|o| o.stage == stage && universe.contains(&o.test_name)) {
+        statuses.insert(o.test_name.clone(), o.status.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::storage::FileStore;
+
+    fn status_override(test_name: &str, stage: &str, status: &str) -> StatusOverride {
+        StatusOverride {
+            test_name: test_name.to_string(),
+            stage: stage.to_string(),
+            status: status.to_string(),
+            reason: "parser missed it".to_string(),
+            author: "reviewer".to_string(),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_apply_status_overrides() {
+        let universe = vec!["a".to_string(), "b".to_string()];
+        let mut after: HashMap<String, String> = [("a".to_string(), "failed".to_string()), ("b".to_string(), "passed".to_string())].into_iter().collect();
+        let overrides = vec![
+            status_override("a", "after", "passed"),
+            status_override("b", "before", "failed"),
+            status_override("unknown", "after", "failed"),
+        ];
+
+        apply_status_overrides(&mut after, "after", &overrides, &universe);
+        assert_eq!(after["a"], "passed");
+        assert_eq!(after["b"], "passed");
+        assert!(!after.contains_key("unknown"));
+    }
+
+    #[test]
+    fn test_save_status_override_validates() {
+        let paths = vec!["folder/logs/base.log".to_string()];
+        assert!(save_status_override(&paths, status_override("a", "report", "passed")).is_err());
+        assert!(save_status_override(&paths, status_override("a", "after", "flaky")).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_overrides_are_not_replaced() {
+        let root = tempfile::TempDir::new().unwrap();
+        let store = FileStore::new(root.path().to_path_buf());
+        save_override_in(&store, "task-1", status_override("a", "after", "passed")).unwrap();
+        let saved = save_override_in(&store, "task-1", status_override("b", "before", "failed")).unwrap();
+        assert_eq!(saved.len(), 2);
+
+        store.save("task-1", STATUS_OVERRIDES_FILE, "[{\"test_name\": ").unwrap();
+        assert!(save_override_in(&store, "task-1", status_override("c", "after", "passed")).is_err());
+        assert_eq!(store.load("task-1", STATUS_OVERRIDES_FILE).unwrap().as_deref(), Some("[{\"test_name\": "));
+    }
+}
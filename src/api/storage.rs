@@ -0,0 +1,393 @@
+//! Where reviewer state is persisted
+//!
+//! Analysis results, review timers and status overrides are small JSON documents keyed by
+//! the deliverable's cache folder and a name such as `reviews/analysis_result.json`. By
+//! default they are files next to the cached deliverable; `storage.backend` in the server
+//! config puts them in SQLite (single-user laptop) or Postgres (shared team server)
+//! instead. Callers only see `ReviewStore`, so every deployment runs the same code.
+//!
+//! The database backends are cargo features (`sqlite`, `postgres`) so a build only links the
+//! client it deploys with.
+
+use std::path::PathBuf;
+#[cfg(feature = "postgres")]
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+use lazy_static::lazy_static;
+
+use crate::api::server_config::{load_server_config, StorageConfig};
+
+pub const DEFAULT_SQLITE_PATH: &str = "swe-reviewer.db";
+
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+const CREATE_TABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS review_documents (
+    folder_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    content TEXT NOT NULL,
+    PRIMARY KEY (folder_id, key)
+)";
+
+/// JSON documents stored per deliverable folder
+pub trait ReviewStore: Send + Sync {
+    fn load(&self, folder_id: &str, key: &str) -> Result<Option<String>, String>;
+    /// Write the document, replacing any earlier version
+    fn save(&self, folder_id: &str, key: &str, content: &str) -> Result<(), String>;
+    fn remove(&self, folder_id: &str, key: &str) -> Result<(), String>;
+    /// Write the document only if it still holds `expected` (`None`: not stored yet), in one
+    /// step against the backend; false when another writer changed it first
+    fn save_if_unchanged(&self, folder_id: &str, key: &str, expected: Option<&str>, content: &str) -> Result<bool, String>;
+    /// The document stored under `key` for every deliverable that has one
+    fn load_all(&self, key: &str) -> Result<Vec<String>, String>;
+}
+
+/// Files under the deliverable cache, `<root>/<folder_id>/<key>`
+pub struct FileStore {
+    root: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+impl ReviewStore for FileStore {
+    fn load(&self, folder_id: &str, key: &str) -> Result<Option<String>, String> {
+        let path = self.root.join(folder_id).join(key);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    fn save(&self, folder_id: &str, key: &str, content: &str) -> Result<(), String> {
+        let path = self.root.join(folder_id).join(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn remove(&self, folder_id: &str, key: &str) -> Result<(), String> {
+        let path = self.root.join(folder_id).join(key);
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("Failed to remove {}: {}", path.display(), e)),
+            _ => Ok(()),
+        }
+    }
+
+    fn save_if_unchanged(&self, folder_id: &str, key: &str, expected: Option<&str>, content: &str) -> Result<bool, String> {
+        // Files are only shared by this server, so its lock makes the check and write one step
+        let _guard = FILE_WRITE_LOCK.lock().map_err(|_| "File store lock poisoned".to_string())?;
+        if self.load(folder_id, key)?.as_deref() != expected {
+            return Ok(false);
+        }
+        self.save(folder_id, key, content)?;
+        Ok(true)
+    }
+
+    fn load_all(&self, key: &str) -> Result<Vec<String>, String> {
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Ok(vec![]);
+        };
+        Ok(entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| std::fs::read_to_string(entry.path().join(key)).ok())
+            .collect())
+    }
+}
+
+/// One SQLite database file for every deliverable
+#[cfg(feature = "sqlite")]
+pub struct SqliteStore {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteStore {
+    pub fn open(path: &str) -> Result<Self, String> {
+        let connection = rusqlite::Connection::open(path).map_err(|e| format!("Failed to open SQLite database {}: {}", path, e))?;
+        connection.execute(CREATE_TABLE_SQL, []).map_err(|e| format!("Failed to create SQLite tables: {}", e))?;
+        Ok(Self { connection: Mutex::new(connection) })
+    }
+
+    fn with_connection<T>(&self, f: impl FnOnce(&rusqlite::Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+        let connection = self.connection.lock().map_err(|_| "SQLite connection poisoned".to_string())?;
+        f(&connection).map_err(|e| format!("SQLite error: {}", e))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ReviewStore for SqliteStore {
+    fn load(&self, folder_id: &str, key: &str) -> Result<Option<String>, String> {
+        use rusqlite::OptionalExtension;
+        self.with_connection(|connection| {
+            connection
+                .query_row(
+                    "SELECT content FROM review_documents WHERE folder_id = ?1 AND key = ?2",
+                    [folder_id, key],
+                    |row| row.get(0),
+                )
+                .optional()
+        })
+    }
+
+    fn save(&self, folder_id: &str, key: &str, content: &str) -> Result<(), String> {
+        self.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO review_documents (folder_id, key, content) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (folder_id, key) DO UPDATE SET content = excluded.content",
+                [folder_id, key, content],
+            )
+        })
+        .map(|_| ())
+    }
+
+    fn remove(&self, folder_id: &str, key: &str) -> Result<(), String> {
+        self.with_connection(|connection| {
+            connection.execute("DELETE FROM review_documents WHERE folder_id = ?1 AND key = ?2", [folder_id, key])
+        })
+        .map(|_| ())
+    }
+
+    fn save_if_unchanged(&self, folder_id: &str, key: &str, expected: Option<&str>, content: &str) -> Result<bool, String> {
+        self.with_connection(|connection| match expected {
+            Some(expected) => connection.execute(
+                "UPDATE review_documents SET content = ?4 WHERE folder_id = ?1 AND key = ?2 AND content = ?3",
+                [folder_id, key, expected, content],
+            ),
+            None => connection.execute(
+                "INSERT INTO review_documents (folder_id, key, content) VALUES (?1, ?2, ?3) ON CONFLICT (folder_id, key) DO NOTHING",
+                [folder_id, key, content],
+            ),
+        })
+        .map(|changed| changed == 1)
+    }
+
+    fn load_all(&self, key: &str) -> Result<Vec<String>, String> {
+        self.with_connection(|connection| {
+            let mut statement = connection.prepare("SELECT content FROM review_documents WHERE key = ?1")?;
+            let rows = statement.query_map([key], |row| row.get(0))?;
+            rows.collect()
+        })
+    }
+}
+
+/// A Postgres database shared by every reviewer on the server
+#[cfg(feature = "postgres")]
+pub struct PostgresStore {
+    jobs: mpsc::Sender<PostgresJob>,
+}
+
+#[cfg(feature = "postgres")]
+type PostgresJob = Box<dyn FnOnce(&mut postgres::Client) + Send>;
+
+#[cfg(feature = "postgres")]
+impl PostgresStore {
+    // The blocking client runs its own runtime, which panics inside a tokio task whatever the
+    // runtime flavor, so the connection lives on a thread of its own and callers queue work
+    pub fn connect(url: &str) -> Result<Self, String> {
+        let url = url.to_string();
+        let (jobs, queued) = mpsc::channel::<PostgresJob>();
+        let (ready, connected) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("postgres-store".to_string())
+            .spawn(move || {
+                let mut client = match postgres::Client::connect(&url, postgres::NoTls) {
+                    Ok(client) => client,
+                    Err(e) => {
+                        let _ = ready.send(Err(format!("Failed to connect to Postgres: {}", e)));
+                        return;
+                    }
+                };
+                if let Err(e) = client.batch_execute(CREATE_TABLE_SQL) {
+                    let _ = ready.send(Err(format!("Failed to create Postgres tables: {}", e)));
+                    return;
+                }
+                let _ = ready.send(Ok(()));
+                for job in queued {
+                    job(&mut client);
+                }
+            })
+            .map_err(|e| format!("Failed to start the Postgres thread: {}", e))?;
+        connected.recv().map_err(|_| "Postgres thread stopped while connecting".to_string())??;
+        Ok(Self { jobs })
+    }
+
+    fn with_client<T: Send + 'static>(&self, f: impl FnOnce(&mut postgres::Client) -> Result<T, postgres::Error> + Send + 'static) -> Result<T, String> {
+        let (reply, response) = mpsc::channel();
+        self.jobs
+            .send(Box::new(move |client| {
+                let _ = reply.send(f(client));
+            }))
+            .map_err(|_| "Postgres thread stopped".to_string())?;
+        response.recv().map_err(|_| "Postgres thread stopped".to_string())?.map_err(|e| format!("Postgres error: {}", e))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ReviewStore for PostgresStore {
+    fn load(&self, folder_id: &str, key: &str) -> Result<Option<String>, String> {
+        let (folder_id, key) = (folder_id.to_string(), key.to_string());
+        self.with_client(move |client| {
+            client
+                .query_opt("SELECT content FROM review_documents WHERE folder_id = $1 AND key = $2", &[&folder_id, &key])
+                .map(|row| row.map(|row| row.get(0)))
+        })
+    }
+
+    fn save(&self, folder_id: &str, key: &str, content: &str) -> Result<(), String> {
+        let (folder_id, key, content) = (folder_id.to_string(), key.to_string(), content.to_string());
+        self.with_client(move |client| {
+            client.execute(
+                "INSERT INTO review_documents (folder_id, key, content) VALUES ($1, $2, $3)
+                 ON CONFLICT (folder_id, key) DO UPDATE SET content = excluded.content",
+                &[&folder_id, &key, &content],
+            )
+        })
+        .map(|_| ())
+    }
+
+    fn remove(&self, folder_id: &str, key: &str) -> Result<(), String> {
+        let (folder_id, key) = (folder_id.to_string(), key.to_string());
+        self.with_client(move |client| {
+            client.execute("DELETE FROM review_documents WHERE folder_id = $1 AND key = $2", &[&folder_id, &key])
+        })
+        .map(|_| ())
+    }
+
+    fn save_if_unchanged(&self, folder_id: &str, key: &str, expected: Option<&str>, content: &str) -> Result<bool, String> {
+        let (folder_id, key, content) = (folder_id.to_string(), key.to_string(), content.to_string());
+        let expected = expected.map(str::to_string);
+        self.with_client(move |client| match expected {
+            Some(expected) => client.execute(
+                "UPDATE review_documents SET content = $4 WHERE folder_id = $1 AND key = $2 AND content = $3",
+                &[&folder_id, &key, &expected, &content],
+            ),
+            None => client.execute(
+                "INSERT INTO review_documents (folder_id, key, content) VALUES ($1, $2, $3) ON CONFLICT (folder_id, key) DO NOTHING",
+                &[&folder_id, &key, &content],
+            ),
+        })
+        .map(|changed| changed == 1)
+    }
+
+    fn load_all(&self, key: &str) -> Result<Vec<String>, String> {
+        let key = key.to_string();
+        self.with_client(move |client| {
+            client
+                .query("SELECT content FROM review_documents WHERE key = $1", &[&key])
+                .map(|rows| rows.iter().map(|row| row.get(0)).collect())
+        })
+    }
+}
+
+/// Folder the deliverable is cached in, shared by every reviewer file stored with it
+pub fn deliverable_cache_dir() -> Option<PathBuf> {
+    use tempfile::TempDir;
+    let temp_dir = TempDir::new().ok()?;
+    let temp_path = temp_dir.path().to_string_lossy().to_string();
+    Some(std::path::Path::new(&temp_path).parent()?.join("swe-reviewer-temp"))
+}
+
+fn open_store(config: &StorageConfig) -> Result<Arc<dyn ReviewStore>, String> {
+    match config.backend.as_deref().unwrap_or("files") {
+        "files" => {
+            let root = deliverable_cache_dir().ok_or("No deliverable cache folder to store reviews in")?;
+            Ok(Arc::new(FileStore::new(root)))
+        }
+        #[cfg(feature = "sqlite")]
+        "sqlite" => Ok(Arc::new(SqliteStore::open(config.sqlite_path.as_deref().unwrap_or(DEFAULT_SQLITE_PATH))?)),
+        #[cfg(feature = "postgres")]
+        "postgres" => {
+            let url = config.postgres_url.as_deref().ok_or("storage.postgres_url is required for the postgres backend")?;
+            Ok(Arc::new(PostgresStore::connect(url)?))
+        }
+        #[cfg(not(feature = "sqlite"))]
+        "sqlite" => Err("Storage backend 'sqlite' needs a build with the `sqlite` feature".to_string()),
+        #[cfg(not(feature = "postgres"))]
+        "postgres" => Err("Storage backend 'postgres' needs a build with the `postgres` feature".to_string()),
+        other => Err(format!("Unknown storage backend '{}'", other)),
+    }
+}
+
+lazy_static! {
+    // Reopened only when the storage section of the config changes
+    static ref STORE: Mutex<Option<(StorageConfig, Arc<dyn ReviewStore>)>> = Mutex::new(None);
+    // Guards the check-then-write of `FileStore::save_if_unchanged`
+    static ref FILE_WRITE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// The store configured under `storage`, opened on first use
+pub fn review_store() -> Result<Arc<dyn ReviewStore>, String> {
+    let config = load_server_config().storage;
+    let mut cached = STORE.lock().map_err(|_| "Review store lock poisoned".to_string())?;
+    if let Some((cached_config, store)) = cached.as_ref() {
+        if *cached_config == config {
+            return Ok(store.clone());
+        }
+    }
+    let store = open_store(&config)?;
+    *cached = Some((config, store.clone()));
+    Ok(store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(store: &dyn ReviewStore) {
+        assert_eq!(store.load("task-1", "reviews/a.json").unwrap(), None);
+        store.save("task-1", "reviews/a.json", "{\"v\":1}").unwrap();
+        store.save("task-1", "reviews/a.json", "{\"v\":2}").unwrap();
+        store.save("task-2", "reviews/a.json", "{\"v\":3}").unwrap();
+        store.save("task-2", "reviews/b.json", "{}").unwrap();
+        assert_eq!(store.load("task-1", "reviews/a.json").unwrap().as_deref(), Some("{\"v\":2}"));
+
+        let mut all = store.load_all("reviews/a.json").unwrap();
+        all.sort();
+        assert_eq!(all, vec!["{\"v\":2}", "{\"v\":3}"]);
+
+        store.remove("task-1", "reviews/a.json").unwrap();
+        store.remove("task-1", "reviews/a.json").unwrap();
+        assert_eq!(store.load("task-1", "reviews/a.json").unwrap(), None);
+
+        // A writer that read a stale version loses
+        assert!(store.save_if_unchanged("task-1", "reviews/a.json", None, "{\"v\":4}").unwrap());
+        assert!(!store.save_if_unchanged("task-1", "reviews/a.json", None, "{\"v\":5}").unwrap());
+        assert!(!store.save_if_unchanged("task-1", "reviews/a.json", Some("{\"v\":1}"), "{\"v\":5}").unwrap());
+        assert!(store.save_if_unchanged("task-1", "reviews/a.json", Some("{\"v\":4}"), "{\"v\":5}").unwrap());
+        assert_eq!(store.load("task-1", "reviews/a.json").unwrap().as_deref(), Some("{\"v\":5}"));
+    }
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let root = tempfile::TempDir::new().unwrap();
+        round_trip(&FileStore::new(root.path().to_path_buf()));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_store_round_trip() {
+        round_trip(&SqliteStore::open(":memory:").unwrap());
+    }
+
+    // Needs a throwaway database in SWE_REVIEWER_TEST_POSTGRES_URL, whose documents it deletes
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_postgres_store_round_trip() {
+        let Ok(url) = std::env::var("SWE_REVIEWER_TEST_POSTGRES_URL") else {
+            eprintln!("SWE_REVIEWER_TEST_POSTGRES_URL not set, skipping");
+            return;
+        };
+        // Server functions call the store from async tasks, on either runtime flavor
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        runtime.block_on(async {
+            let store = PostgresStore::connect(&url).unwrap();
+            store.with_client(|client| client.batch_execute("DELETE FROM review_documents")).unwrap();
+            round_trip(&store);
+        });
+    }
+}
@@ -0,0 +1,279 @@
+//! Cross-check a deliverable against the official SWE-bench dataset record (rule C8)
+//!
+//! The record is looked up in a configured local copy of the dataset first and, when
+//! enabled, fetched from the Hugging Face datasets server. Deliverables whose instance
+//! isn't part of the dataset are left untouched.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::api::server_config::{load_server_config, SweBenchDatasetConfig};
+use crate::app::types::{LogAnalysisResult, RuleViolation};
+
+const DEFAULT_DATASET_NAME: &str = "princeton-nlp/SWE-bench";
+const DEFAULT_SPLIT: &str = "test";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DatasetInstance {
+    pub instance_id: String,
+    pub fail_to_pass: Vec<String>,
+    pub pass_to_pass: Vec<String>,
+}
+
+// Parsed local dataset, keyed by path and modification time so edits are picked up
+type DatasetCache = Option<(String, Option<SystemTime>, Arc<HashMap<String, DatasetInstance>>)>;
+
+static LOCAL_DATASET_CACHE: once_cell::sync::Lazy<Mutex<DatasetCache>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(None));
+
+// The dataset stores test lists either as JSON arrays or as JSON-encoded strings
+fn parse_test_list(value: Option<&serde_json::Value>) -> Vec<String> {
+    match value {
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        Some(serde_json::Value::String(encoded)) => {
+            serde_json::from_str::<Vec<String>>(encoded).unwrap_or_default()
+        }
+        _ => vec![],
+    }
+}
+
+pub fn parse_dataset_instance(row: &serde_json::Value) -> Option<DatasetInstance> {
+    let instance_id = row.get("instance_id")?.as_str()?.to_string();
+    Some(DatasetInstance {
+        instance_id,
+        fail_to_pass: parse_test_list(row.get("FAIL_TO_PASS")),
+        pass_to_pass: parse_test_list(row.get("PASS_TO_PASS")),
+    })
+}
+
+fn parse_dataset_file(content: &str) -> HashMap<String, DatasetInstance> {
+    let rows: Vec<serde_json::Value> = match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(serde_json::Value::Array(rows)) => rows,
+        // Not a single JSON document: treat as JSONL
+        _ => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect(),
+    };
+
+    rows.iter()
+        .filter_map(parse_dataset_instance)
+        .map(|instance| (instance.instance_id.clone(), instance))
+        .collect()
+}
+
+fn load_local_dataset(path: &str) -> Option<Arc<HashMap<String, DatasetInstance>>> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+
+    let mut cache = LOCAL_DATASET_CACHE.lock().ok()?;
+    if let Some((cached_path, cached_modified, dataset)) = cache.as_ref() {
+        if cached_path == path && *cached_modified == modified {
+            return Some(dataset.clone());
+        }
+    }
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Failed to read SWE-bench dataset {}: {}", path, e);
+            return None;
+        }
+    };
+    let dataset = Arc::new(parse_dataset_file(&content));
+    println!("Loaded {} SWE-bench instances from {}", dataset.len(), path);
+    *cache = Some((path.to_string(), modified, dataset.clone()));
+    Some(dataset)
+}
+
+async fn fetch_remote_instance(config: &SweBenchDatasetConfig, instance_id: &str) -> Option<DatasetInstance> {
+    let dataset = config.dataset_name.as_deref().unwrap_or(DEFAULT_DATASET_NAME);
+    let split = config.split.as_deref().unwrap_or(DEFAULT_SPLIT);
+    let filter = format!("\"instance_id\"='{}'", instance_id.replace('\'', "''"));
+    let url = format!(
+        "https://datasets-server.huggingface.co/filter?dataset={}&config=default&split={}&where={}",
+        urlencoding::encode(dataset),
+        urlencoding::encode(split),
+        urlencoding::encode(&filter)
+    );
+
+    let resp = match reqwest::Client::new()
+        .get(&url)
+        .timeout(std::time::Duration::from_secs(15))
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => resp,
+        Ok(resp) => {
+            eprintln!("SWE-bench dataset lookup for {} responded with {}", instance_id, resp.status());
+            return None;
+        }
+        Err(e) => {
+            eprintln!("Failed to fetch SWE-bench dataset record for {}: {}", instance_id, e);
+            return None;
+        }
+    };
+
+    let json: serde_json::Value = resp.json().await.ok()?;
+    json["rows"]
+        .as_array()?
+        .iter()
+        .filter_map(|row| parse_dataset_instance(&row["row"]))
+        .find(|instance| instance.instance_id == instance_id)
+}
+
+/// Look up the dataset record for an instance, local copy first
+pub async fn find_dataset_instance(instance_id: &str) -> Option<DatasetInstance> {
+    let config = load_server_config().swebench;
+
+    if let Some(path) = config.dataset_path.as_deref().filter(|p| !p.is_empty()) {
+        if let Some(instance) = load_local_dataset(path).and_then(|d| d.get(instance_id).cloned()) {
+            return Some(instance);
+        }
+    }
+
+    if config.fetch_remote {
+        return fetch_remote_instance(&config, instance_id).await;
+    }
+
+    None
+}
+
+/// Compare the deliverable's lists with the dataset record and describe each discrepancy
+pub fn compare_with_dataset(
+    fail_to_pass: &[String],
+    pass_to_pass: &[String],
+    dataset: &DatasetInstance,
+) -> Vec<String> {
+    let mut discrepancies = Vec::new();
+
+    let lists = [
+        ("F2P", "FAIL_TO_PASS", fail_to_pass, &dataset.fail_to_pass),
+        ("P2P", "PASS_TO_PASS", pass_to_pass, &dataset.pass_to_pass),
+    ];
+    for (label, dataset_label, ours, theirs) in lists {
+        let ours_set: HashSet<&String> = ours.iter().collect();
+        let theirs_set: HashSet<&String> = theirs.iter().collect();
+
+        let mut missing: Vec<&String> = theirs_set.difference(&ours_set).copied().collect();
+        missing.sort();
+        for test in missing {
+            discrepancies.push(format!("{} (in dataset {} but missing from deliverable {})", test, dataset_label, label));
+        }
+
+        let mut extra: Vec<&String> = ours_set.difference(&theirs_set).copied().collect();
+        extra.sort();
+        for test in extra {
+            discrepancies.push(format!("{} (in deliverable {} but not in dataset {})", test, label, dataset_label));
+        }
+    }
+
+    discrepancies
+}
+
+// Instance ID from main.json, falling back to the key of a SWE-bench style report.json
+fn resolve_instance_id(abs_paths: &[String]) -> Option<String> {
+    let read_json = |needle: &str| -> Option<serde_json::Value> {
+        let path = abs_paths.iter().find(|p| p.to_lowercase().contains(needle))?;
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    };
+
+    if let Some(id) = read_json("main/")
+        .and_then(|json| json.get("instance_id").and_then(|v| v.as_str()).map(|s| s.to_string()))
+        .filter(|id| !id.is_empty())
+    {
+        return Some(id);
+    }
+
+    read_json("report.json").and_then(|json| {
+        json.as_object()?
+            .iter()
+            .find(|(_, v)| v.get("tests_status").is_some())
+            .map(|(k, _)| k.clone())
+    })
+}
+
+/// Run the C8 check and record the result in the analysis
+pub async fn cross_check_with_dataset(file_paths: &[String], analysis: &mut LogAnalysisResult) {
+    use tempfile::TempDir;
+
+    let base_temp_dir = match TempDir::new() {
+        Ok(temp_dir) => temp_dir.path().parent().unwrap().join("swe-reviewer-temp"),
+        Err(_) => return,
+    };
+    let abs_paths: Vec<String> = file_paths
+        .iter()
+        .map(|rel| base_temp_dir.join(rel).to_string_lossy().to_string())
+        .collect();
+
+    let Some(instance_id) = resolve_instance_id(&abs_paths) else {
+        return;
+    };
+    let Some(dataset) = find_dataset_instance(&instance_id).await else {
+        return;
+    };
+
+    let mut fail_to_pass: Vec<String> = analysis.test_statuses.f2p.keys().cloned().collect();
+    let mut pass_to_pass: Vec<String> = analysis.test_statuses.p2p.keys().cloned().collect();
+    fail_to_pass.sort();
+    pass_to_pass.sort();
+
+    let discrepancies = compare_with_dataset(&fail_to_pass, &pass_to_pass, &dataset);
+    println!("C8 check: {} discrepancies against dataset instance {}", discrepancies.len(), instance_id);
+
+    analysis.rule_violations.c8_test_lists_differ_from_dataset = RuleViolation {
+        has_problem: !discrepancies.is_empty(),
+        examples: discrepancies,
+    };
+    if !analysis.rules_evaluated.iter().any(|code| code == "C8") {
+        analysis.rules_evaluated.push("C8".to_string());
+        analysis.rules_evaluated.sort_by_key(|code| code[1..].parse::<u32>().unwrap_or(0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dataset_instance_with_encoded_lists() {
+        let row = serde_json::json!({
+            "instance_id": "astropy__astropy-12907",
+            "FAIL_TO_PASS": "[\"test_a\", \"test_b\"]",
+            "PASS_TO_PASS": ["test_c"],
+        });
+        let instance = parse_dataset_instance(&row).unwrap();
+        assert_eq!(instance.fail_to_pass, vec!["test_a", "test_b"]);
+        assert_eq!(instance.pass_to_pass, vec!["test_c"]);
+    }
+
+    #[test]
+    fn test_parse_dataset_file_jsonl() {
+        let content = "{\"instance_id\": \"a\", \"FAIL_TO_PASS\": [], \"PASS_TO_PASS\": []}\n{\"instance_id\": \"b\", \"FAIL_TO_PASS\": [\"t\"], \"PASS_TO_PASS\": []}\n";
+        let dataset = parse_dataset_file(content);
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset["b"].fail_to_pass, vec!["t"]);
+    }
+
+    #[test]
+    fn test_compare_with_dataset() {
+        let dataset = DatasetInstance {
+            instance_id: "x".to_string(),
+            fail_to_pass: vec!["f1".to_string(), "f2".to_string()],
+            pass_to_pass: vec!["p1".to_string()],
+        };
+        let discrepancies = compare_with_dataset(
+            &["f1".to_string(), "f3".to_string()],
+            &["p1".to_string()],
+            &dataset,
+        );
+        assert_eq!(discrepancies, vec![
+            "f2 (in dataset FAIL_TO_PASS but missing from deliverable F2P)".to_string(),
+            "f3 (in deliverable F2P but not in dataset FAIL_TO_PASS)".to_string(),
+        ]);
+    }
+}
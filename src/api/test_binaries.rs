@@ -0,0 +1,113 @@
+//! Test binaries in a cargo log
+//!
+//! `cargo test` runs one binary per target (unit tests, each integration test file,
+//! doc-tests), each opening with a `Running` or `Doc-tests` line and its own
+//! `running N tests`. Splitting the log there gives a per-binary breakdown and keeps the
+//! same test name in two binaries from counting as a duplicate. A binary that shows up
+//! again means several runs were concatenated into one log.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::TestBinaryRun;
+
+lazy_static! {
+    // `Running unittests src/lib.rs (target/debug/deps/foo-1a2b3c4d)` or `Running tests/cli.rs (target/debug/deps/cli-4d5e6f7a)`
+    static ref RUNNING_BINARY_RE: Regex = Regex::new(r"^\s*Running\s+(?:unittests\s+)?(\S+)\s+\((?:[^()]*/)?([^/()\s]+?)(?:-[0-9a-f]{8,})?(?:\.exe)?\)").unwrap();
+    // Older cargo: `Running target/debug/deps/foo-1a2b3c4d`
+    static ref LEGACY_RUNNING_RE: Regex = Regex::new(r"^\s*Running\s+(?:\S*/)?deps/([^/\s]+?)(?:-[0-9a-f]{8,})?(?:\.exe)?\s*$").unwrap();
+    static ref DOC_TESTS_RE: Regex = Regex::new(r"^\s*Doc-tests\s+(\S+)").unwrap();
+    // compiletest-style `=== Running tests/ui/foo.rs`
+    static ref UI_RUNNING_RE: Regex = Regex::new(r"(?i)===\s*Running\s+(.+\.(?:rs|fixed))").unwrap();
+    static ref RUNNING_COUNT_RE: Regex = Regex::new(r"^\s*running (\d+) tests?").unwrap();
+    static ref RESULT_RE: Regex = Regex::new(r"^\s*test (\S+)(?: - should panic)? \.\.\. (ok|FAILED|ignored)").unwrap();
+}
+
+/// Label of the test binary whose output starts at this line
+pub fn binary_boundary(line: &str) -> Option<String> {
+    if let Some(captures) = RUNNING_BINARY_RE.captures(line) {
+        return Some(format!("{} ({})", &captures[1], &captures[2]));
+    }
+    if let Some(captures) = LEGACY_RUNNING_RE.captures(line) {
+        return Some(captures[1].to_string());
+    }
+    if let Some(captures) = DOC_TESTS_RE.captures(line) {
+        return Some(format!("doc-tests {}", &captures[1]));
+    }
+    UI_RUNNING_RE.captures(line).map(|captures| captures[1].to_string())
+}
+
+/// Counts per binary in log order; empty when the log names no binaries
+pub fn test_binary_runs(content: &str) -> Vec<TestBinaryRun> {
+    let mut runs: Vec<TestBinaryRun> = Vec::new();
+    for line in content.lines() {
+        if let Some(binary) = binary_boundary(line) {
+            let run = runs.iter().filter(|existing| existing.binary == binary).count() + 1;
+            runs.push(TestBinaryRun {
+                stage: String::new(),
+                binary,
+                run,
+                declared: None,
+                passed: 0,
+                failed: 0,
+                ignored: 0,
+            });
+            continue;
+        }
+        // Output before the first binary line can't be attributed
+        let Some(current) = runs.last_mut() else { continue };
+        if let Some(captures) = RUNNING_COUNT_RE.captures(line) {
+            if current.declared.is_none() {
+                current.declared = captures[1].parse().ok();
+            }
+        } else if let Some(captures) = RESULT_RE.captures(line) {
+            match &captures[2] {
+                "ok" => current.passed += 1,
+                "FAILED" => current.failed += 1,
+                _ => current.ignored += 1,
+            }
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_boundaries() {
+        assert_eq!(binary_boundary("     Running unittests src/lib.rs (target/debug/deps/parser-1a2b3c4d5e6f7a8b)"), Some("src/lib.rs (parser)".to_string()));
+        assert_eq!(binary_boundary("     Running tests/cli.rs (target/debug/deps/cli-0f1e2d3c4b5a6978)"), Some("tests/cli.rs (cli)".to_string()));
+        assert_eq!(binary_boundary("     Running target/debug/deps/parser-1a2b3c4d5e6f7a8b"), Some("parser".to_string()));
+        assert_eq!(binary_boundary("   Doc-tests parser"), Some("doc-tests parser".to_string()));
+        assert_eq!(binary_boundary("test running::works ... ok"), None);
+    }
+
+    #[test]
+    fn test_breakdown_and_concatenated_runs() {
+        let log = "   Compiling parser v0.1.0\n\
+                   \x20    Running unittests src/lib.rs (target/debug/deps/parser-1a2b3c4d)\n\
+                   running 2 tests\n\
+                   test lexer::works ... ok\n\
+                   test lexer::fails ... FAILED\n\
+                   \x20    Running tests/cli.rs (target/debug/deps/cli-4d5e6f7a)\n\
+                   running 1 test\n\
+                   test help ... ignored\n\
+                   \x20    Running unittests src/lib.rs (target/debug/deps/parser-1a2b3c4d)\n\
+                   running 2 tests\n\
+                   test lexer::works ... ok\n";
+
+        let runs = test_binary_runs(log);
+        let summary: Vec<(&str, usize, Option<usize>, usize, usize, usize)> = runs
+            .iter()
+            .map(|r| (r.binary.as_str(), r.run, r.declared, r.passed, r.failed, r.ignored))
+            .collect();
+        assert_eq!(summary, vec![
+            ("src/lib.rs (parser)", 1, Some(2), 1, 1, 0),
+            ("tests/cli.rs (cli)", 1, Some(1), 0, 0, 1),
+            ("src/lib.rs (parser)", 2, Some(2), 1, 0, 0),
+        ]);
+        assert!(test_binary_runs("test a ... ok\n").is_empty());
+    }
+}
@@ -471,6 +471,45 @@ pub fn detect_js_testing_framework(project_path: &str) -> String {
     "vitest".to_string()
 }
 
+/// Detect if a Java test name exists in diff content
+///
+/// Names come as `com.acme.CalcTest.testAdd`, `CalcTest#testAdd` or a bare class name;
+/// the last segment is matched against a method declaration, or against a class
+/// declaration when it names a class.
+pub fn contains_exact_java_test_name(diff_content: &str, test_name: &str) -> bool {
+    let last = test_name.rsplit(['.', '#']).next().unwrap_or(test_name).trim_end_matches("()");
+    if last.is_empty() {
+        return false;
+    }
+    let pattern = if last.starts_with(|c: char| c.is_ascii_uppercase()) {
+        format!(r"(?m)^[+\-\s].*\b(?:class|interface|enum|record)\s+{}\b", regex::escape(last))
+    } else {
+        format!(r"(?m)^[+\-\s].*\bvoid\s+{}\s*\(", regex::escape(last))
+    };
+    Regex::new(&pattern).map(|re| re.is_match(diff_content)).unwrap_or(false)
+}
+
+/// Detect if a C++ test name exists in diff content
+///
+/// GoogleTest names (`Suite.Name`, parameterized as `Prefix/Suite.Name/0`) are matched against
+/// `TEST`, `TEST_F` and `TEST_P` declarations; anything else is taken as a Catch2 test case name.
+pub fn contains_exact_cpp_test_name(diff_content: &str, test_name: &str) -> bool {
+    let gtest = test_name
+        .rsplit_once('/')
+        .filter(|(_, index)| index.chars().all(|c| c.is_ascii_digit()))
+        .map_or(test_name, |(name, _)| name);
+    let gtest = gtest.rsplit('/').next().unwrap_or(gtest);
+    let pattern = match gtest.split_once('.') {
+        Some((suite, name)) if !suite.is_empty() && !name.is_empty() && !test_name.contains(' ') => format!(
+            r"(?m)^[+\-\s].*\b(?:TYPED_)?TEST(?:_F|_P)?\(\s*{}\s*,\s*{}\s*\)",
+            regex::escape(suite),
+            regex::escape(name)
+        ),
+        _ => format!(r#"(?m)^[+\-\s].*\b(?:TEST_CASE|SCENARIO)\(\s*"{}""#, regex::escape(test_name)),
+    };
+    Regex::new(&pattern).map(|re| re.is_match(diff_content)).unwrap_or(false)
+}
+
 /// Main entry point for language-specific test detection
 /// 
 /// This function dispatches to the appropriate language-specific test detection
@@ -488,6 +527,8 @@ pub fn contains_exact_test_name(diff_content: &str, test_name: &str, language: &
         "python" => contains_exact_python_test_name(diff_content, test_name),
         "rust" => contains_exact_rust_test_name(diff_content, test_name),
         "javascript" | "typescript" => contains_exact_js_test_name(diff_content, test_name),
+        "java" => contains_exact_java_test_name(diff_content, test_name),
+        "cpp" | "c++" => contains_exact_cpp_test_name(diff_content, test_name),
         _ => {
             // Default to Rust behavior for unknown languages
             contains_exact_rust_test_name(diff_content, test_name)
@@ -601,6 +642,14 @@ test('test_js_function', () => {
         assert!(contains_exact_test_name(rust_diff, "test_rust_function", "rust"));
         assert!(contains_exact_test_name(python_diff, "test_python_function", "python"));
         assert!(contains_exact_test_name(js_diff, "test_js_function", "javascript"));
+        let java_diff = "+public class CalcTest {\n+    @Test\n+    void testAdd() {\n";
+        assert!(contains_exact_test_name(java_diff, "com.acme.CalcTest.testAdd", "java"));
+        assert!(contains_exact_test_name(java_diff, "com.acme.CalcTest", "java"));
+        assert!(!contains_exact_test_name(java_diff, "com.acme.CalcTest.testSub", "java"));
+        let cpp_diff = "+TEST_P(VectorTest, Grows) {\n+TEST_CASE(\"Factorials are computed\", \"[factorial]\") {\n";
+        assert!(contains_exact_test_name(cpp_diff, "Sizes/VectorTest.Grows/0", "c++"));
+        assert!(contains_exact_test_name(cpp_diff, "Factorials are computed", "cpp"));
+        assert!(!contains_exact_test_name(cpp_diff, "VectorTest.Shrinks", "cpp"));
         
         // Test case insensitive language matching
         assert!(contains_exact_test_name(rust_diff, "test_rust_function", "RUST"));
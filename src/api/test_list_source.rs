@@ -0,0 +1,76 @@
+//! Reading the F2P/P2P lists out of main.json
+//!
+//! main.json has carried the lists under both the lowercase and the SWE-bench
+//! (`FAIL_TO_PASS`) spelling, sometimes as a JSON-encoded string rather than an array,
+//! and with stray whitespace or repeated names. Lists are normalized here once, for the
+//! checker and the analysis alike, and what changed is recorded for the provenance display.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::app::types::ListProvenance;
+
+pub const FAIL_TO_PASS_KEYS: &[&str] = &["fail_to_pass", "FAIL_TO_PASS"];
+pub const PASS_TO_PASS_KEYS: &[&str] = &["pass_to_pass", "PASS_TO_PASS"];
+
+/// The list under the first of `keys` present in main.json, trimmed and without duplicates
+pub fn read_test_list(main_json: &Value, keys: &[&str]) -> (Vec<String>, ListProvenance) {
+    let mut provenance = ListProvenance::default();
+    let Some((key, value)) = keys.iter().find_map(|key| main_json.get(*key).map(|value| (*key, value))) else {
+        return (Vec::new(), provenance);
+    };
+    provenance.key = Some(key.to_string());
+
+    let entries: Vec<Value> = match value {
+        Value::Array(entries) => entries.clone(),
+        Value::String(encoded) => {
+            provenance.decoded_from_string = true;
+            serde_json::from_str(encoded).unwrap_or_default()
+        }
+        _ => Vec::new(),
+    };
+
+    let mut tests: Vec<String> = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for raw in entries.iter().filter_map(|entry| entry.as_str()) {
+        provenance.raw_count += 1;
+        let name = raw.trim();
+        if name != raw {
+            provenance.normalized += 1;
+        }
+        if name.is_empty() || !seen.insert(name) {
+            provenance.duplicates_removed += 1;
+            continue;
+        }
+        tests.push(name.to_string());
+    }
+    provenance.count = tests.len();
+    (tests, provenance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_test_list_normalizes() {
+        let main_json = serde_json::json!({ "fail_to_pass": [" a::b", "a::b", "c::d", ""] });
+        let (tests, provenance) = read_test_list(&main_json, FAIL_TO_PASS_KEYS);
+        assert_eq!(tests, vec!["a::b", "c::d"]);
+        assert_eq!(provenance.key.as_deref(), Some("fail_to_pass"));
+        assert_eq!((provenance.raw_count, provenance.duplicates_removed, provenance.normalized, provenance.count), (4, 2, 1, 2));
+    }
+
+    #[test]
+    fn test_read_test_list_swebench_spelling() {
+        let main_json = serde_json::json!({ "PASS_TO_PASS": "[\"x\", \"y\"]" });
+        let (tests, provenance) = read_test_list(&main_json, PASS_TO_PASS_KEYS);
+        assert_eq!(tests, vec!["x", "y"]);
+        assert_eq!(provenance.key.as_deref(), Some("PASS_TO_PASS"));
+        assert!(provenance.decoded_from_string);
+
+        let (tests, provenance) = read_test_list(&serde_json::json!({}), PASS_TO_PASS_KEYS);
+        assert!(tests.is_empty() && provenance.key.is_none());
+    }
+}
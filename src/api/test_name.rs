@@ -0,0 +1,72 @@
+//! Canonical form of test names for matching
+//!
+//! The same test can be spelled differently in main.json and in a log: smart quotes
+//! pasted from a document, non-breaking spaces, or composed vs decomposed accents.
+//! Both sides are compared in NFC with quotes and whitespace canonicalized.
+
+use unicode_normalization::UnicodeNormalization;
+
+/// NFC-normalize, map typographic quotes to ASCII and collapse whitespace runs to one space
+pub fn canonical_test_name(name: &str) -> String {
+    let mapped: String = name
+        .nfc()
+        .map(|c| match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => '"',
+            c if c.is_whitespace() => ' ',
+            c => c,
+        })
+        .collect();
+    mapped.split(' ').filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+/// Joiner JavaScript test names use between suites and the test title
+pub const JS_SUITE_SEPARATOR: &str = " > ";
+
+/// Rewrite the suite joiners JS runners print (` - ` from mocha, `::` from Cypress and
+/// Playwright, ` › ` from Jest) to ` > `, so the same test matches whichever was used
+pub fn canonical_js_separators(name: &str) -> String {
+    let joined = name
+        .replace(" - ", JS_SUITE_SEPARATOR)
+        .replace(" \u{203A} ", JS_SUITE_SEPARATOR)
+        .replace("::", JS_SUITE_SEPARATOR);
+    joined.split(' ').filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" ")
+}
+
+/// Drop a leading JS/TS file segment (`src/a.test.ts > title`) from a name already passed
+/// through `canonical_js_separators`; other names come back unchanged
+pub fn strip_js_test_file(name: &str) -> &str {
+    const EXTENSIONS: &[&str] = &[".js", ".jsx", ".ts", ".tsx", ".mjs", ".cjs", ".mts", ".cts"];
+    match name.split_once(JS_SUITE_SEPARATOR) {
+        Some((file, title)) if !file.contains(' ') && EXTENSIONS.iter().any(|ext| file.ends_with(ext)) => title,
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_test_name() {
+        assert_eq!(canonical_test_name("test_caf\u{0065}\u{0301}"), canonical_test_name("test_caf\u{00E9}"));
+        assert_eq!(canonical_test_name("renders \u{201C}hello\u{201D} \u{2019}s"), "renders \"hello\" 's");
+        assert_eq!(canonical_test_name(" Suite >\u{00A0}\u{00A0}handles\ttabs "), "Suite > handles tabs");
+    }
+
+    #[test]
+    fn test_canonical_js_separators() {
+        let expected = "Auth > login > rejects bad passwords";
+        assert_eq!(canonical_js_separators("Auth - login - rejects bad passwords"), expected);
+        assert_eq!(canonical_js_separators("Auth::login::rejects bad passwords"), expected);
+        assert_eq!(canonical_js_separators("Auth :: login \u{203A} rejects bad passwords"), expected);
+        assert_eq!(canonical_js_separators(expected), expected);
+    }
+
+    #[test]
+    fn test_strip_js_test_file() {
+        assert_eq!(strip_js_test_file("src/sum.test.ts > adds > handles zero"), "adds > handles zero");
+        assert_eq!(strip_js_test_file("adds > handles zero"), "adds > handles zero");
+        assert_eq!(strip_js_test_file("Auth > login"), "Auth > login");
+    }
+}
@@ -0,0 +1,133 @@
+//! Per-test durations printed by the runners
+//!
+//! Timings are collected in a pass over the log once the language parser has named
+//! the tests, then used for the slowest-tests list and to flag tests whose runtime
+//! exploded between the before and after runs.
+
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::{RuntimeRegression, TestTiming};
+
+/// Slowest tests listed per stage
+pub const SLOWEST_TESTS_PER_STAGE: usize = 10;
+/// After must be at least this many times slower than before to count as exploded
+pub const RUNTIME_REGRESSION_RATIO: f64 = 5.0;
+/// ...and slower by at least this much, so millisecond jitter is ignored
+pub const RUNTIME_REGRESSION_MIN_MS: f64 = 1000.0;
+
+lazy_static! {
+    // pytest --durations: `0.52s call     tests/test_a.py::test_x`
+    static ref PYTEST_DURATION_RE: Regex = Regex::new(r"^(\d+(?:\.\d+)?)s\s+(?:call|setup|teardown)\s+(\S.*?)\s*$").unwrap();
+    // cargo test --report-time: `test foo ... ok <0.012s>`
+    static ref CARGO_TIME_RE: Regex = Regex::new(r"^test\s+(\S+)\s+\.\.\.\s+\w+\s+<(\d+(?:\.\d+)?)s>").unwrap();
+    // nextest: `PASS [   0.012s] crate tests::foo`
+    static ref NEXTEST_TIME_RE: Regex = Regex::new(r"^(?:PASS|FAIL|SLOW|TIMEOUT)\s+\[\s*(\d+(?:\.\d+)?)s\]\s+(.+?)\s*$").unwrap();
+    // Jest/Mocha/Vitest: `✓ renders footer (12 ms)`, `✓ renders footer 12ms`
+    static ref JS_TIME_RE: Regex = Regex::new(r"^[✓✔√×✕✗]\s+(.+?)\s+\(?(\d+(?:\.\d+)?)\s*(ms|s)\)?$").unwrap();
+}
+
+fn to_millis(value: &str, unit: &str) -> f64 {
+    let value: f64 = value.parse().unwrap_or(0.0);
+    if unit == "s" { value * 1000.0 } else { value }
+}
+
+// Test names whose last segment matches the printed title; runners abbreviate crate or describe paths
+fn matching_names<'a>(printed: &str, names: &'a HashSet<String>) -> Vec<&'a String> {
+    if let Some(name) = names.get(printed) {
+        return vec![name];
+    }
+    names
+        .iter()
+        .filter(|name| printed.ends_with(&format!(" {}", name)) || name.ends_with(&format!(" {}", printed)))
+        .collect()
+}
+
+/// Duration in milliseconds for each parsed test the runner printed a time for
+pub fn extract_durations(content: &str, names: &HashSet<String>) -> HashMap<String, f64> {
+    let mut durations: HashMap<String, f64> = HashMap::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(caps) = PYTEST_DURATION_RE.captures(line) {
+            // setup/call/teardown are listed separately; the test's cost is their sum
+            if let Some(name) = names.get(&caps[2]) {
+                *durations.entry(name.clone()).or_insert(0.0) += to_millis(&caps[1], "s");
+            }
+        } else if let Some(caps) = CARGO_TIME_RE.captures(line) {
+            if let Some(name) = names.get(&caps[1]) {
+                durations.insert(name.clone(), to_millis(&caps[2], "s"));
+            }
+        } else if let Some(caps) = NEXTEST_TIME_RE.captures(line) {
+            for name in matching_names(&caps[2], names) {
+                durations.insert(name.clone(), to_millis(&caps[1], "s"));
+            }
+        } else if let Some(caps) = JS_TIME_RE.captures(line) {
+            for name in matching_names(&caps[1], names) {
+                durations.entry(name.clone()).or_insert_with(|| to_millis(&caps[2], &caps[3]));
+            }
+        }
+    }
+
+    durations
+}
+
+/// The slowest tests of one stage, slowest first
+pub fn slowest_tests(stage: &str, durations: &HashMap<String, f64>) -> Vec<TestTiming> {
+    let mut timings: Vec<TestTiming> = durations
+        .iter()
+        .map(|(name, millis)| TestTiming { stage: stage.to_string(), test_name: name.clone(), millis: *millis })
+        .collect();
+    timings.sort_by(|a, b| b.millis.total_cmp(&a.millis).then_with(|| a.test_name.cmp(&b.test_name)));
+    timings.truncate(SLOWEST_TESTS_PER_STAGE);
+    timings
+}
+
+/// Tests that got dramatically slower from the before run to the after run
+pub fn runtime_regressions(before: &HashMap<String, f64>, after: &HashMap<String, f64>) -> Vec<RuntimeRegression> {
+    let mut regressions: Vec<RuntimeRegression> = after
+        .iter()
+        .filter_map(|(name, &after_ms)| {
+            let before_ms = *before.get(name)?;
+            let exploded = after_ms >= before_ms * RUNTIME_REGRESSION_RATIO
+                && after_ms - before_ms >= RUNTIME_REGRESSION_MIN_MS;
+            exploded.then(|| RuntimeRegression { test_name: name.clone(), before_ms, after_ms })
+        })
+        .collect();
+    regressions.sort_by(|a, b| (b.after_ms - b.before_ms).total_cmp(&(a.after_ms - a.before_ms)));
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_durations() {
+        let log = "test parser::parses ... ok <0.250s>\n\
+                   2.50s call     tests/test_io.py::test_read\n\
+                   0.50s setup    tests/test_io.py::test_read\n\
+                   ✓ renders footer (12 ms)";
+        let names: HashSet<String> = ["parser::parses", "tests/test_io.py::test_read", "Layout renders footer"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let durations = extract_durations(log, &names);
+        assert_eq!(durations["parser::parses"], 250.0);
+        assert_eq!(durations["tests/test_io.py::test_read"], 3000.0);
+        assert_eq!(durations["Layout renders footer"], 12.0);
+    }
+
+    #[test]
+    fn test_runtime_regressions() {
+        let before: HashMap<String, f64> = [("slow".to_string(), 200.0), ("jitter".to_string(), 2.0)].into_iter().collect();
+        let after: HashMap<String, f64> = [("slow".to_string(), 4000.0), ("jitter".to_string(), 40.0)].into_iter().collect();
+
+        let regressions = runtime_regressions(&before, &after);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].test_name, "slow");
+    }
+}
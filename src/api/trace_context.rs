@@ -0,0 +1,172 @@
+//! Stack traces around search hits
+//!
+//! A hit inside (or just above) a long stack trace gets five lines of frames either side,
+//! which rarely include what failed. The trace's extent is found from its frame lines, and
+//! only its message lines (the exception, assertion or panic text), its first and last frames
+//! and the frames around the hit are sent; the page folds the rest behind an expander and
+//! reads it from the log when opened.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::app::types::TraceContext;
+
+lazy_static! {
+    // Python `File "x.py", line 3, in f`, Java `at pkg.Cls.m(Cls.java:12)`, JS
+    // `at Object.<anonymous> (/app/x.js:3:5)`, Rust backtrace `  12: std::panicking::begin_panic`
+    // and `at ./src/lib.rs:4:5`, Go `\t/src/x.go:12 +0x1d`. `at` frames must end in a call or a
+    // `file:line` so prose such as "at least one test failed" isn't taken for one
+    static ref FRAME_RE: Regex = Regex::new(
+        r#"^\s*(?:File ".+", line \d+|at [\w$.<>/]+\([^)]*\)\s*$|at (?:async )?(?:\S+(?: \[as \S+\])? )?\(?\S+:\d+(?::\d+)?\)?\s*$|\d+: \S|/\S+\.go:\d+|\.\.\. \d+ more)"#
+    ).unwrap();
+    static ref TRACE_START_RE: Regex = Regex::new(
+        r"^\s*(?:Traceback \(most recent call last\)|stack backtrace:|Stack trace:|goroutine \d+ \[|Caused by:|During handling of the above exception|The above exception was the direct cause)"
+    ).unwrap();
+    // Python puts the exception below its frames: `AssertionError: expected 2, got 3`
+    static ref PY_EXCEPTION_RE: Regex = Regex::new(r"^[\w.]+(?:Error|Exception|Warning|Exit|Interrupt|Failure)\b").unwrap();
+    static ref MESSAGE_RE: Regex = Regex::new(
+        r"(?i)(?:assert|error|exception|panicked|expected|actual|\bleft\b|\bright\b|failed|failure)"
+    ).unwrap();
+}
+
+/// Traces up to this long fit the regular context window and are left alone
+const MIN_TRACE_LINES: usize = 15;
+/// How far below a hit a trace may start and still belong to it
+const TRACE_LOOKAHEAD: usize = 5;
+/// Message lines kept when the trace is collapsed
+const MAX_MESSAGE_LINES: usize = 6;
+/// Trace lines sent either side of the hit
+const LINES_AROUND_HIT: usize = 3;
+
+fn is_frame(line: &str) -> bool {
+    FRAME_RE.is_match(line) || TRACE_START_RE.is_match(line)
+}
+
+// Source lines, `^^^^` markers and Go function names sit indented under (or right above) a frame
+fn is_frame_detail(line: &str) -> bool {
+    !line.trim().is_empty() && (line.starts_with("  ") || line.starts_with('\t'))
+}
+
+// Lines [start, end) of the trace containing line `index`, if it is in one
+fn trace_bounds(lines: &[&str], index: usize) -> Option<(usize, usize)> {
+    let in_trace = |i: usize| {
+        is_frame(lines[i])
+            || (is_frame_detail(lines[i]) && ((i > 0 && is_frame(lines[i - 1])) || (i + 1 < lines.len() && is_frame(lines[i + 1]))))
+    };
+    if !in_trace(index) {
+        return None;
+    }
+    let mut start = index;
+    while start > 0 && in_trace(start - 1) {
+        start -= 1;
+    }
+    let mut end = index + 1;
+    while end < lines.len() && in_trace(end) {
+        end += 1;
+    }
+    // The message sits on the line just above bare frames (Java, JS), above a Rust backtrace
+    // as the panic line, or below a Python traceback
+    if start > 0 && (FRAME_RE.is_match(lines[start]) || lines[start - 1].contains("panicked")) && !lines[start - 1].trim().is_empty() {
+        start -= 1;
+    }
+    if end < lines.len() && PY_EXCEPTION_RE.is_match(lines[end]) {
+        end += 1;
+    }
+    Some((start, end))
+}
+
+// Lines of the trace worth showing collapsed: its message lines and its first and last frames
+fn key_lines(trace: &[&str]) -> Vec<usize> {
+    let frames: Vec<usize> = (0..trace.len()).filter(|i| FRAME_RE.is_match(trace[*i])).collect();
+    let mut keep: Vec<usize> = (0..trace.len())
+        .filter(|i| !FRAME_RE.is_match(trace[*i]) && !is_frame_detail(trace[*i]) && !trace[*i].trim().is_empty())
+        .filter(|i| MESSAGE_RE.is_match(trace[*i]) || TRACE_START_RE.is_match(trace[*i]) || *i == 0 || *i + 1 == trace.len())
+        .take(MAX_MESSAGE_LINES)
+        .collect();
+    for frame in [frames.first(), frames.last()].into_iter().flatten() {
+        keep.push(*frame);
+        // The frame's source line, for Python
+        if trace.get(frame + 1).is_some_and(|line| is_frame_detail(line) && !FRAME_RE.is_match(line)) {
+            keep.push(frame + 1);
+        }
+    }
+    keep.sort_unstable();
+    keep.dedup();
+    keep
+}
+
+/// The long stack trace the hit on `hit` (0-based) sits in or sits just above, if there is one
+pub fn trace_around(lines: &[&str], hit: usize) -> Option<TraceContext> {
+    let (start, end) = trace_bounds(lines, hit).or_else(|| {
+        (hit + 1..(hit + 1 + TRACE_LOOKAHEAD).min(lines.len()))
+            .find(|i| TRACE_START_RE.is_match(lines[*i]) || FRAME_RE.is_match(lines[*i]))
+            .and_then(|i| trace_bounds(lines, i))
+            .filter(|(start, _)| *start > hit)
+    })?;
+    if end - start <= MIN_TRACE_LINES {
+        return None;
+    }
+    let trace = &lines[start..end];
+    let mut keep = key_lines(trace);
+    if let Some(hit) = hit.checked_sub(start).filter(|hit| *hit < trace.len()) {
+        keep.extend(hit.saturating_sub(LINES_AROUND_HIT)..(hit + LINES_AROUND_HIT + 1).min(trace.len()));
+        keep.sort_unstable();
+        keep.dedup();
+    }
+    Some(TraceContext {
+        start_line: start + 1,
+        len: trace.len(),
+        lines: keep.into_iter().map(|i| (i, trace[i].to_string())).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn python_trace(frames: usize) -> String {
+        let mut log = String::from("tests/test_calc.py::test_div FAILED\nTraceback (most recent call last):\n");
+        for i in 0..frames {
+            log.push_str(&format!("  File \"/app/calc/mod{}.py\", line {}, in call{}\n    return call{}()\n", i, i + 10, i, i + 1));
+        }
+        log.push_str("AssertionError: expected 2, got 3\n\nnext test output\n");
+        log
+    }
+
+    #[test]
+    fn test_long_trace_keeps_message_and_outer_frames() {
+        let log = python_trace(12);
+        let lines: Vec<&str> = log.lines().collect();
+        let hit = lines.iter().position(|line| line.contains("mod6.py")).unwrap();
+        let trace = trace_around(&lines, hit).unwrap();
+        assert_eq!((trace.start_line, trace.len), (2, 26));
+        let kept: Vec<&str> = trace.lines.iter().map(|(_, line)| line.as_str()).collect();
+        assert_eq!(kept.first(), Some(&"Traceback (most recent call last):"));
+        assert_eq!(kept.last(), Some(&"AssertionError: expected 2, got 3"));
+        assert!(kept.iter().any(|line| line.contains("mod0.py")) && kept.iter().any(|line| line.contains("mod11.py")));
+        // The hit's own frame and its neighbours, but not the middle of the trace
+        assert!(kept.iter().any(|line| line.contains("mod6.py")) && kept.iter().any(|line| line.contains("mod5.py")));
+        assert!(!kept.iter().any(|line| line.contains("mod3.py")));
+        assert!(trace.lines.len() < trace.len);
+
+        // The test's own line, just above the trace, gets the trace but no frames around it
+        let above = trace_around(&lines, 0).unwrap();
+        assert_eq!(above.start_line, 2);
+        assert!(!above.lines.iter().any(|(_, line)| line.contains("mod6.py")));
+    }
+
+    #[test]
+    fn test_short_traces_and_plain_lines_are_left_alone() {
+        let log = python_trace(3);
+        let lines: Vec<&str> = log.lines().collect();
+        assert!(trace_around(&lines, 3).is_none());
+        let plain = ["test a ... ok", "test b ... ok", "test result: ok"];
+        assert!(trace_around(&plain, 1).is_none());
+
+        assert!(is_frame("    at com.acme.CalcTest.testDiv(CalcTest.java:42)"));
+        assert!(is_frame("    at Object.<anonymous> (/app/src/calc.test.js:3:5)"));
+        assert!(is_frame("             at ./src/lib.rs:4:5"));
+        assert!(!is_frame("  at least one test failed"));
+        assert!(!is_frame("    at the end of the run: 3 retries"));
+    }
+}
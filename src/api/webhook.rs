@@ -0,0 +1,146 @@
+//! Outbound webhooks fired when an analysis (or a reviewer decision) completes
+//!
+//! Configure in the `notifications` section of the server config:
+//! - `webhook_urls`: URLs to POST to
+//! - `webhook_secret`: optional shared secret sent as `X-Webhook-Secret`
+//!
+//! Delivery is best effort: failures are logged and never fail the request that
+//! triggered them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::server_config::{load_server_config, NotificationsConfig};
+use crate::app::types::{LogAnalysisResult, ReviewDecision};
+
+pub const EVENT_ANALYSIS_COMPLETED: &str = "analysis_completed";
+pub const EVENT_REVIEW_DECISION: &str = "review_decision";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ViolationCount {
+    pub rule: String,
+    pub has_problem: bool,
+    pub count: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AnalysisSummary {
+    pub event: String,
+    pub deliverable_link: String,
+    pub instance_id: String,
+    pub verdict: String,
+    // Schema of the analysis the summary was built from, and the rules it checked
+    #[serde(default)]
+    pub schema_version: u32,
+    #[serde(default)]
+    pub rules_evaluated: Vec<String>,
+    pub violations: Vec<ViolationCount>,
+    pub f2p_count: usize,
+    pub p2p_count: usize,
+    pub timestamp: u64,
+}
+
+impl AnalysisSummary {
+    pub fn from_analysis(event: &str, deliverable_link: &str, instance_id: &str, analysis: &LogAnalysisResult) -> Self {
+        let violations = analysis
+            .rule_violations
+            .entries()
+            .into_iter()
+            .map(|(rule, violation)| ViolationCount {
+                rule: rule.to_string(),
+                has_problem: violation.has_problem,
+                count: violation.examples.len(),
+            })
+            .collect();
+
+        let verdict = if analysis.rule_violations.has_any_problem() {
+            "violations_found"
+        } else {
+            "clean"
+        };
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            event: event.to_string(),
+            deliverable_link: deliverable_link.to_string(),
+            instance_id: instance_id.to_string(),
+            verdict: verdict.to_string(),
+            schema_version: analysis.schema_version,
+            rules_evaluated: analysis.rules_evaluated.clone(),
+            violations,
+            f2p_count: analysis.test_statuses.f2p.len(),
+            p2p_count: analysis.test_statuses.p2p.len(),
+            timestamp,
+        }
+    }
+}
+
+pub fn configured_webhook_urls(config: &NotificationsConfig) -> Vec<String> {
+    config.webhook_urls.iter().map(|url| url.trim().to_string()).filter(|url| !url.is_empty()).collect()
+}
+
+/// POST a JSON payload to every configured webhook URL
+pub async fn send_webhook_event<T: Serialize>(payload: &T) {
+    let config = load_server_config().notifications;
+    let urls = configured_webhook_urls(&config);
+    if urls.is_empty() {
+        return;
+    }
+
+    let secret = config.webhook_secret.filter(|s| !s.is_empty());
+    let client = reqwest::Client::new();
+
+    for url in urls {
+        let mut request = client
+            .post(&url)
+            .timeout(std::time::Duration::from_secs(10))
+            .json(payload);
+        if let Some(ref secret) = secret {
+            request = request.header("X-Webhook-Secret", secret);
+        }
+
+        match request.send().await {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => eprintln!("Webhook {} responded with {}", url, resp.status()),
+            Err(e) => eprintln!("Failed to deliver webhook to {}: {}", url, e),
+        }
+    }
+}
+
+/// Fire-and-forget notification so the analysis response isn't held up by slow receivers
+pub fn notify_analysis_completed(deliverable_link: &str, instance_id: &str, analysis: &LogAnalysisResult) {
+    if configured_webhook_urls(&load_server_config().notifications).is_empty() {
+        return;
+    }
+
+    let summary = AnalysisSummary::from_analysis(EVENT_ANALYSIS_COMPLETED, deliverable_link, instance_id, analysis);
+    tokio::spawn(async move {
+        send_webhook_event(&summary).await;
+    });
+}
+
+#[derive(Serialize)]
+struct DecisionEvent<'a> {
+    event: &'a str,
+    #[serde(flatten)]
+    decision: &'a ReviewDecision,
+    timestamp: u64,
+}
+
+/// Forward a recorded reviewer decision to the configured webhooks
+pub async fn notify_review_decision(decision: &ReviewDecision) {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    send_webhook_event(&DecisionEvent {
+        event: EVENT_REVIEW_DECISION,
+        decision,
+        timestamp,
+    })
+    .await;
+}
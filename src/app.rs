@@ -2,9 +2,9 @@ use leptos::prelude::*;
 use leptos_meta::{provide_meta_context, MetaTags, Stylesheet, Title};
 use leptos_router::{
     components::{Route, Router, Routes},
-    ParamSegment, StaticSegment,
+    ParamSegment, SsrMode, StaticSegment,
 };
-use crate::components::ThemeToggle;
+use crate::components::{DarkMode, ThemeToggle};
 
 use crate::app::types::ProcessingResult;
 
@@ -16,11 +16,62 @@ pub mod search_results;
 pub mod file_viewer;
 pub mod test_checker;
 pub mod log_search_results;
+pub mod context_diff;
 pub mod deliverable_checker_interface;
 pub mod deliverable_checker;
 pub mod playground;
 pub mod report_tab;
+pub mod github_comment;
+pub mod review_decision;
+pub mod review_queue;
+pub mod junit_export;
+pub mod llm_summary;
+pub mod coverage_panel;
+pub mod run_abort_notice;
+pub mod filtered_run_notice;
+pub mod xpass_notice;
+pub mod timing_panel;
+pub mod duplicates_panel;
+pub mod log_preview;
+pub mod debug_tab;
+pub mod copy_analysis;
+pub mod custom_test_lists;
+pub mod status_override;
+pub mod patch_failure_notice;
+pub mod scratch_parser;
+pub mod structure_checklist;
+pub mod error_blocks;
+pub mod agent_log_tab;
+pub mod test_list_provenance;
+pub mod session_state;
+pub mod analysis_tab;
+pub mod analysis_run_diff;
+pub mod dashboard;
+pub mod summary_page;
+pub mod test_name_inspector;
+pub mod analysis_phases;
+pub mod report_matrix;
+pub mod trace_context;
+pub mod batch_checker;
+pub mod environment_drift_notice;
+pub mod download_progress;
+pub mod log_window;
+pub mod patch_classes;
+pub mod report_export;
+pub mod patches_tab;
+pub mod number_format;
+pub mod review_bundle;
+pub mod command_palette;
 use deliverable_checker::{DeliverableCheckerPage, DeliverableCheckerPageProps};
+use review_decision::ReviewDecisionPanel;
+use dashboard::DashboardPage;
+use summary_page::SummaryPage;
+use batch_checker::BatchCheckerPage;
+use github_comment::GitHubCommentPanel;
+use junit_export::JUnitExportButton;
+use report_export::ReportExportButton;
+use review_bundle::ReviewBundleButton;
+use llm_summary::LlmSummaryPanel;
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
     view! {
@@ -44,6 +95,7 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
 pub fn App() -> impl IntoView {
     // Provides context that manages stylesheets, titles, meta tags, etc.
     provide_meta_context();
+    provide_context(DarkMode(RwSignal::new(false)));
 
     view! {
         // injects a stylesheet into the document <head>
@@ -74,8 +126,20 @@ pub fn MainApp() -> impl IntoView {
                     <div class="flex justify-between items-center h-14">
                         <div class="flex items-center">
                             <h1 class="text-xl font-semibold text-gray-900 dark:text-white">
-                                "SWE Reviewer"
+                                <a href="/">"SWE Reviewer"</a>
                             </h1>
+                            <a
+                                href="/dashboard"
+                                class="ml-4 text-sm text-blue-600 hover:text-blue-800 underline dark:text-blue-400 dark:hover:text-blue-300"
+                            >
+                                "Dashboard"
+                            </a>
+                            <a
+                                href="/batch"
+                                class="ml-4 text-sm text-blue-600 hover:text-blue-800 underline dark:text-blue-400 dark:hover:text-blue-300"
+                            >
+                                "Batch"
+                            </a>
                         </div>
                         <Show when=move || current_deliverable.get().is_some() fallback=|| view!{ <div></div> }>
                             <span class="text-xl font-black text-gray-700 dark:text-white">
@@ -94,10 +158,10 @@ pub fn MainApp() -> impl IntoView {
                                                         "rust" => "/icons/rust.png".to_string(),
                                                         "javascript" | "typescript" => "/icons/javascript.png".to_string(),
                                                         "python" => "/icons/python.png".to_string(),
-                                                        "go" => "/icons/go.png".to_string(),
+                                                        "go" | "golang" => "/icons/go.png".to_string(),
                                                         "java" => "/icons/java.png".to_string(),
                                                         "ruby" => "/icons/ruby.png".to_string(),
-                                                        "c++" => "/icons/cpp.png".to_string(),
+                                                        "c++" | "cpp" => "/icons/cpp.png".to_string(),
                                                         "c#" => "/icons/csharp.png".to_string(),
                                                         _ => "/icons/empty.png".to_string(),
                                                     }
@@ -110,6 +174,11 @@ pub fn MainApp() -> impl IntoView {
                                         <span>"]"</span>
                                     </span>
                                 </Show>
+                                <Show when=move || current_deliverable.get().is_some_and(|d| !d.display_task_id().is_empty())>
+                                    <span class="ml-2 px-2 py-0.5 rounded bg-blue-100 dark:bg-blue-900/40 text-blue-800 dark:text-blue-200 text-base font-bold">
+                                        {move || current_deliverable.get().map_or(String::new(), |d| format!("Task {}", d.display_task_id()))}
+                                    </span>
+                                </Show>
                             </span>
                         </Show>
                         
@@ -182,6 +251,12 @@ pub fn MainApp() -> impl IntoView {
                                         "SWE URL"
                                     </a>
                                 </Show>
+                                <JUnitExportButton current_deliverable=current_deliverable/>
+                                <ReportExportButton current_deliverable=current_deliverable/>
+                                <ReviewBundleButton current_deliverable=current_deliverable/>
+                                <LlmSummaryPanel current_deliverable=current_deliverable/>
+                                <GitHubCommentPanel current_deliverable=current_deliverable/>
+                                <ReviewDecisionPanel current_deliverable=current_deliverable/>
                             </div>
                         </Show>
                         <div class="ml-2">
@@ -196,7 +271,12 @@ pub fn MainApp() -> impl IntoView {
             <div class="w-full bg-white dark:bg-gray-800" style="height: calc(100vh - 57px);">
                 <Routes fallback=|| "Page not found.".into_view()>
                     <Route path=StaticSegment("") view=move || DeliverableCheckerPage(DeliverableCheckerPageProps { current_deliverable: current_deliverable.clone() }) />
-                    <Route path=ParamSegment("deliverable_id") view=move || DeliverableCheckerPage(DeliverableCheckerPageProps { current_deliverable: current_deliverable.clone() }) />
+                    <Route path=StaticSegment("dashboard") view=DashboardPage />
+                    <Route path=StaticSegment("batch") view=BatchCheckerPage />
+                    // Waits for the stored analysis so shared links get the full page, meta tags included
+                    <Route path=(StaticSegment("summary"), ParamSegment("folder_id")) view=SummaryPage ssr=SsrMode::Async />
+                    <Route path=(StaticSegment("review"), ParamSegment("session_id")) view=move || DeliverableCheckerPage(DeliverableCheckerPageProps { current_deliverable }) />
+                    <Route path=ParamSegment("deliverable_id") view=move || DeliverableCheckerPage(DeliverableCheckerPageProps { current_deliverable }) />
                 </Routes>
             </div>
         </div>
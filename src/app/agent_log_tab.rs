@@ -0,0 +1,163 @@
+use leptos::prelude::*;
+use leptos::prelude::Effect;
+use leptos::task::spawn_local;
+
+use super::error_blocks::{find_error_blocks, ErrorBlock};
+use super::file_operations::{handle_get_file_contents, load_file_contents};
+use super::types::{FileContents, LoadedFileTypes, ProcessingResult};
+
+fn kind_class(kind: &str) -> &'static str {
+    match kind {
+        "traceback" => "bg-orange-100 dark:bg-orange-900/40 text-orange-800 dark:text-orange-200",
+        "panic" => "bg-red-100 dark:bg-red-900/40 text-red-800 dark:text-red-200",
+        "assertion" => "bg-purple-100 dark:bg-purple-900/40 text-purple-800 dark:text-purple-200",
+        _ => "bg-yellow-100 dark:bg-yellow-900/40 text-yellow-800 dark:text-yellow-200",
+    }
+}
+
+// The log as plain text runs and highlighted error blocks; block spans supply their own line breaks.
+// Rendered once per log: picking a block only flips its class, through `block_refs` for scrolling
+fn render_log(content: &str, blocks: &[ErrorBlock], selected_block: RwSignal<Option<usize>>, block_refs: StoredValue<Vec<NodeRef<leptos::html::Span>>>) -> AnyView {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let mut next_line = 0;
+    let mut parts = Vec::new();
+    let mut refs = Vec::with_capacity(blocks.len());
+    for (index, block) in blocks.iter().enumerate() {
+        let text_before = lines[next_line..block.start_line - 1].join("\n");
+        let block_text = lines[block.start_line - 1..block.end_line].join("\n");
+        next_line = block.end_line;
+        let node_ref = NodeRef::<leptos::html::Span>::new();
+        refs.push(node_ref);
+        let class = move || if selected_block.get() == Some(index) {
+            "block bg-red-900/60 ring-2 ring-red-400"
+        } else {
+            "block bg-red-900/40"
+        };
+        parts.push(view! { {text_before}<span node_ref=node_ref class=class>{block_text}</span> }.into_any());
+    }
+    block_refs.set_value(refs);
+    let text_after = lines[next_line.min(lines.len())..].join("\n");
+    view! { {parts}{text_after} }.into_any()
+}
+
+#[component]
+pub fn AgentLogTab(
+    file_contents: RwSignal<FileContents>,
+    loading_files: RwSignal<bool>,
+    loaded_file_types: RwSignal<LoadedFileTypes>,
+    result: RwSignal<Option<ProcessingResult>>,
+    // Agent run picked in the Report tab; `None` is the default run the Input tab loads
+    selected_agent_log: RwSignal<Option<String>>,
+) -> impl IntoView {
+    let selected_block = RwSignal::new(None::<usize>);
+    let block_refs = StoredValue::new(Vec::<NodeRef<leptos::html::Span>>::new());
+    // The picked run's log, tagged with its name so a late response for another run is ignored
+    let picked_log = RwSignal::new(None::<(String, Option<String>)>);
+    let loading_picked = RwSignal::new(false);
+    let content = Memo::new(move |_| match selected_agent_log.get() {
+        Some(name) => picked_log.with(|picked| picked.as_ref().filter(|(picked, _)| *picked == name).and_then(|(_, content)| content.clone())),
+        None => file_contents.with(|contents| contents.agent.as_ref().map(|file| file.content.clone())),
+    });
+    let blocks = Memo::new(move |_| content.get().map(|content| find_error_blocks(&content)).unwrap_or_default());
+
+    Effect::new(move |_| {
+        let Some(name) = selected_agent_log.get() else {
+            if !loaded_file_types.with_untracked(|loaded| loaded.is_loaded("agent")) && result.with_untracked(|r| r.is_some()) {
+                load_file_contents(result, file_contents, loading_files, loaded_file_types, Some(vec!["agent".to_string()]));
+            }
+            return;
+        };
+        if picked_log.with_untracked(|picked| picked.as_ref().is_some_and(|(picked, _)| *picked == name)) {
+            return;
+        }
+        let Some(deliverable) = result.get_untracked() else { return };
+        // Only the picked run's file, so the server's default-run lookup lands on it
+        let file_paths: Vec<String> = deliverable.file_paths.into_iter()
+            .filter(|path| path.rsplit(['/', '\\']).next() == Some(name.as_str()))
+            .collect();
+        loading_picked.set(true);
+        spawn_local(async move {
+            let content = handle_get_file_contents("agent".to_string(), file_paths).await
+                .ok()
+                .filter(|content| !(content.starts_with("No ") && content.contains("file found")));
+            if selected_agent_log.get_untracked().as_deref() == Some(name.as_str()) {
+                picked_log.set(Some((name, content)));
+                loading_picked.set(false);
+            }
+        });
+    });
+
+    // A new log has new blocks
+    Effect::new(move |_| {
+        content.track();
+        selected_block.set(None);
+    });
+
+    Effect::new(move |_| {
+        let Some(index) = selected_block.get() else { return };
+        if let Some(element) = block_refs.with_value(|refs| refs.get(index).and_then(|node_ref| node_ref.get_untracked())) {
+            element.scroll_into_view();
+        }
+    });
+
+    // Keyed by position and line range, so picking a block or reloading the same log keeps the rows
+    let index = view! {
+        {move || blocks.with(|blocks| blocks.is_empty()).then(|| view! {
+            <div class="px-3 py-2 text-xs text-gray-500 dark:text-gray-400">"No tracebacks, panics or failed assertions found"</div>
+        })}
+        <For
+            each=move || blocks.get().into_iter().enumerate()
+            key=|(index, block)| (*index, block.start_line, block.end_line)
+            let((index, block))
+        >
+            <button
+                on:click=move |_| selected_block.set(Some(index))
+                class=move || {
+                    if selected_block.get() == Some(index) {
+                        "w-full px-3 py-2 text-left text-xs border-b border-gray-200 dark:border-gray-600 bg-white dark:bg-gray-800"
+                    } else {
+                        "w-full px-3 py-2 text-left text-xs border-b border-gray-200 dark:border-gray-600 hover:bg-gray-200 dark:hover:bg-gray-600"
+                    }
+                }
+            >
+                <div class="flex items-center gap-2">
+                    <span class=format!("px-1.5 rounded {}", kind_class(block.kind))>{block.kind}</span>
+                    <span class="text-gray-500 dark:text-gray-400">{format!("line {}", block.start_line)}</span>
+                </div>
+                <div class="mt-1 font-mono break-all text-gray-800 dark:text-gray-200">{block.title}</div>
+            </button>
+        </For>
+    };
+
+    view! {
+        <div class="flex h-full">
+            <div class="w-72 bg-gray-100 dark:bg-gray-700 border-r border-gray-200 dark:border-gray-600 flex flex-col overflow-y-auto">
+                <div class="px-3 py-2 text-sm font-semibold text-gray-900 dark:text-white border-b border-gray-200 dark:border-gray-600">
+                    {move || format!("Error blocks ({})", blocks.get().len())}
+                </div>
+                {index}
+            </div>
+            <div class="flex-1 flex flex-col p-4 overflow-hidden">
+                {move || {
+                    if loading_files.get() || loading_picked.get() {
+                        return view! {
+                            <div class="flex items-center justify-center h-full text-gray-600 dark:text-gray-300">"Loading agent log..."</div>
+                        }.into_any();
+                    }
+                    match content.get() {
+                        Some(content) => view! {
+                            <div class="flex-1 min-h-0 overflow-auto rounded-lg border border-gray-200 dark:border-gray-700 bg-gray-900 text-gray-100">
+                                <pre class="p-4 text-sm font-mono whitespace-pre-wrap">
+                                    {render_log(&content, &blocks.get(), selected_block, block_refs)}
+                                </pre>
+                            </div>
+                        }.into_any(),
+                        None => view! {
+                            <div class="flex items-center justify-center h-full text-gray-500 dark:text-gray-400">"No agent log in this deliverable"</div>
+                        }.into_any(),
+                    }
+                }}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,67 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{AnalysisPhase, AnalysisProgress, ApiError};
+
+const PROGRESS_POLL_MS: u64 = 500;
+
+#[server]
+pub async fn handle_analysis_progress(progress_id: String) -> Result<Option<AnalysisProgress>, ApiError> {
+    use crate::api::analysis_progress::analysis_progress;
+    Ok(analysis_progress(&progress_id))
+}
+
+// Fetch the run's phases once; answers for a run that's no longer the current one are dropped
+pub async fn refresh_analysis_progress(progress_id: String, progress: RwSignal<Option<AnalysisProgress>>) {
+    if let Ok(Some(found)) = handle_analysis_progress(progress_id).await {
+        if progress.with_untracked(|current| current.as_ref().is_some_and(|current| current.id == found.id)) {
+            progress.set(Some(found));
+        }
+    }
+}
+
+/// Make `progress_id` the current run and poll its phases until the returned handle is cleared
+pub fn watch_analysis_progress(progress_id: String, progress: RwSignal<Option<AnalysisProgress>>) -> Option<IntervalHandle> {
+    progress.set(Some(AnalysisProgress { id: progress_id.clone(), phases: Vec::new() }));
+    set_interval_with_handle(
+        move || spawn_local(refresh_analysis_progress(progress_id.clone(), progress)),
+        std::time::Duration::from_millis(PROGRESS_POLL_MS),
+    ).ok()
+}
+
+fn phase_view(phase: AnalysisPhase) -> AnyView {
+    let (marker, class) = match phase.status.as_str() {
+        "done" => ("✓", "text-green-700 dark:text-green-300"),
+        "active" => ("…", "text-blue-600 dark:text-blue-400 font-medium"),
+        "failed" => ("✗", "text-red-600 dark:text-red-400 font-medium"),
+        "skipped" => ("–", "text-gray-400 dark:text-gray-500"),
+        _ => ("○", "text-gray-500 dark:text-gray-400"),
+    };
+    let elapsed = match phase.status.as_str() {
+        "done" | "active" | "failed" => format!("{:.1}s", phase.elapsed_ms as f64 / 1000.0),
+        "skipped" => "no log".to_string(),
+        _ => String::new(),
+    };
+    view! {
+        <li class=format!("flex items-center justify-between gap-4 {}", class)>
+            <span><span class="inline-block w-4">{marker}</span>{phase.label}</span>
+            <span class="text-xs tabular-nums">{elapsed}</span>
+        </li>
+    }.into_any()
+}
+
+// Phases of the running (or last failed) analysis, so a stuck step can be named
+#[component]
+pub fn AnalysisPhases(progress: RwSignal<Option<AnalysisProgress>>) -> impl IntoView {
+    move || {
+        let phases = progress.get().map(|progress| progress.phases).unwrap_or_default();
+        if phases.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        view! {
+            <ul class="w-64 text-sm space-y-1 text-left">
+                {phases.into_iter().map(phase_view).collect_view()}
+            </ul>
+        }.into_any()
+    }
+}
@@ -0,0 +1,111 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{format_utc, AnalysisRunDiff, AnalysisRunInfo, ApiError, ProcessingResult};
+
+#[server]
+pub async fn handle_analysis_run_diff(file_paths: Vec<String>) -> Result<Option<AnalysisRunDiff>, ApiError> {
+    use crate::api::analysis_history::latest_run_diff;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    latest_run_diff(&file_paths).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to load the analysis history").with_details(e))
+}
+
+fn describe_run(run: &AnalysisRunInfo) -> String {
+    let mut parts = vec![format_utc(run.analyzed_at)];
+    if !run.parser.is_empty() {
+        parts.push(format!("{} parser", run.parser));
+    }
+    if !run.app_version.is_empty() {
+        parts.push(format!("v{}", run.app_version));
+    }
+    parts.join(", ")
+}
+
+fn verdict_label(violated: bool) -> &'static str {
+    if violated { "FAIL" } else { "PASS" }
+}
+
+fn status_label(status: &str) -> String {
+    if status.is_empty() { "not listed".to_string() } else { status.to_string() }
+}
+
+// Body of the diff, type-erased to keep the view type shallow
+fn run_diff_view(diff: AnalysisRunDiff) -> AnyView {
+    let rules = diff.rule_changes.into_iter().map(|change| {
+        let examples = change.removed_examples.into_iter().map(|example| ("-", example))
+            .chain(change.added_examples.into_iter().map(|example| ("+", example)))
+            .map(|(marker, example)| {
+                let row_class = if marker == "-" {
+                    "font-mono text-xs text-red-700 dark:text-red-300"
+                } else {
+                    "font-mono text-xs text-green-700 dark:text-green-300"
+                };
+                view! { <li class=row_class>{format!("{} {}", marker, example)}</li> }
+            })
+            .collect_view();
+        view! {
+            <li class="py-1">
+                <span class="font-semibold text-gray-900 dark:text-white">{change.code}</span>
+                <span class="ml-2 text-gray-700 dark:text-gray-300">
+                    {format!("{} → {}", verdict_label(change.was_violated), verdict_label(change.is_violated))}
+                </span>
+                <ul class="ml-4">{examples}</ul>
+            </li>
+        }
+    }).collect_view();
+
+    let statuses = diff.status_changes.into_iter().map(|change| view! {
+        <tr>
+            <td class="pr-3 font-mono break-all">{change.test_name}</td>
+            <td class="pr-3 uppercase">{change.list}</td>
+            <td class="pr-3">{change.stage}</td>
+            <td class="pr-3">{format!("{} → {}", status_label(&change.previous), status_label(&change.current))}</td>
+        </tr>
+    }).collect_view();
+
+    view! {
+        <div class="space-y-2 text-xs text-gray-700 dark:text-gray-300">
+            <div>{format!("Previous run: {}", describe_run(&diff.previous))}</div>
+            <div>{format!("Latest run: {}", describe_run(&diff.current))}</div>
+            <ul class="divide-y divide-gray-100 dark:divide-gray-700">{rules}</ul>
+            <table class="w-full text-left">
+                <tbody>{statuses}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+// Changes in statuses and rule results since the deliverable's previous analysis, when it was
+// analyzed before with a different outcome (e.g. before a parser fix)
+#[component]
+pub fn AnalysisRunDiffPanel(result: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let diff = RwSignal::new(None::<AnalysisRunDiff>);
+
+    Effect::new(move |_| {
+        let Some(deliverable) = result.get_untracked() else {
+            return;
+        };
+        spawn_local(async move {
+            match handle_analysis_run_diff(deliverable.file_paths).await {
+                Ok(found) => diff.set(found),
+                Err(e) => leptos::logging::log!("Failed to load analysis history: {}", e),
+            }
+        });
+    });
+
+    move || diff.get().map(|found| {
+        let summary = format!(
+            "Changed since the previous analysis: {} rules, {} test statuses",
+            found.rule_changes.len(),
+            found.status_changes.len(),
+        );
+        view! {
+            <details class="mb-3 p-3 rounded-lg border border-blue-200 dark:border-blue-800 bg-blue-50/50 dark:bg-blue-900/20">
+                <summary class="cursor-pointer text-sm text-gray-800 dark:text-gray-200">{summary}</summary>
+                <div class="mt-2 max-h-72 overflow-y-auto">{run_diff_view(found)}</div>
+            </details>
+        }
+    })
+}
@@ -0,0 +1,338 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::analysis_phases::AnalysisPhases;
+use super::analysis_run_diff::AnalysisRunDiffPanel;
+use super::log_preview::LogPreview;
+use super::number_format::format_count;
+use super::patch_classes::PatchClassesPanel;
+use super::types::{example_test_name, rule_description, AnalysisProgress, ApiError, LogAnalysisResult, LogSearchResults, ProcessingResult, RuleViolation, RECHECKABLE_RULES};
+
+// Checks one rule of the stored analysis again and stores the new verdict; the logs aren't parsed again
+#[server]
+pub async fn handle_recheck_rule(file_paths: Vec<String>, code: String) -> Result<RuleViolation, ApiError> {
+    use crate::api::analysis_store::{load_analysis_result, save_analysis_result};
+    use crate::api::log_analysis::recheck_rule;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    let mut analysis = load_analysis_result(&file_paths)
+        .ok_or_else(|| ApiError::new(ApiErrorCode::NotFound, "No stored analysis to re-check; analyze the logs first"))?;
+    recheck_rule(file_paths.clone(), &code, &mut analysis)
+        .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, format!("Failed to re-check {}", code)).with_details(e))?;
+    if let Err(e) = save_analysis_result(&file_paths, &analysis) {
+        eprintln!("Failed to persist analysis result: {}", e);
+    }
+    analysis.rule_violations.get_mut(&code)
+        .cloned()
+        .ok_or_else(|| ApiError::new(ApiErrorCode::Internal, format!("Unknown rule: {}", code)))
+}
+
+fn copy_to_clipboard(text: String, copied: RwSignal<Option<String>>, key: String) {
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(window) = web_sys::window() {
+            let promise = window.navigator().clipboard().write_text(&text);
+            let future = wasm_bindgen_futures::JsFuture::from(promise);
+            wasm_bindgen_futures::spawn_local(async move {
+                match future.await {
+                    Ok(_) => copied.set(Some(key)),
+                    Err(e) => leptos::logging::log!("Failed to copy to clipboard: {:?}", e),
+                }
+            });
+        }
+    }
+    #[cfg(not(feature = "hydrate"))]
+    let _ = (text, copied, key);
+}
+
+// Formats a rule's example list can be copied in, as (key, button label, tooltip)
+const COPY_FORMATS: &[(&str, &str, &str)] = &[
+    ("plain", "List", "Copy as a plain list, one example per line"),
+    ("markdown", "Markdown", "Copy as Markdown bullets"),
+    ("json", "JSON", "Copy as a JSON array"),
+];
+
+fn format_examples(examples: &[String], format: &str) -> String {
+    match format {
+        "markdown" => examples
+            .iter()
+            .map(|example| {
+                let test_name = example_test_name(example);
+                format!("- `{}`{}", test_name, &example[test_name.len()..])
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "json" => serde_json::to_string_pretty(examples).unwrap_or_default(),
+        _ => examples.join("\n"),
+    }
+}
+
+// Log whose lines back a rule's examples, for hover previews and deep links
+fn rule_stage(code: &str) -> Option<&'static str> {
+    match code {
+        "C1" | "C4" => Some("base"),
+        "C2" => Some("after"),
+        "C3" => Some("before"),
+        "C6" => Some("agent"),
+        _ => None,
+    }
+}
+
+// One card per rule: state, description and the offending examples
+#[component]
+pub fn AnalysisTab(
+    log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
+    log_analysis_loading: RwSignal<bool>,
+    fail_to_pass_tests: RwSignal<Vec<String>>,
+    pass_to_pass_tests: RwSignal<Vec<String>>,
+    current_selection: RwSignal<String>,
+    selected_fail_to_pass_index: RwSignal<usize>,
+    selected_pass_to_pass_index: RwSignal<usize>,
+    active_main_tab: RwSignal<String>,
+    search_for_test: impl Fn(String) + Send + Sync + 'static + Copy,
+    search_results: RwSignal<LogSearchResults>,
+    search_stages: RwSignal<Vec<String>>,
+    focused_stage: RwSignal<Option<String>>,
+    analysis_progress: RwSignal<Option<AnalysisProgress>>,
+    run_analysis: impl Fn() + Send + Sync + 'static + Copy,
+    result: RwSignal<Option<ProcessingResult>>,
+) -> impl IntoView {
+    // Example (or "<code>:all") that was copied last, to flip its button label
+    let copied = RwSignal::new(None::<String>);
+
+    // Select the test in the Tests tab and bring up its lines in the log panes, scrolled to the
+    // first hit in `stage` (ticking that log if needed)
+    let jump_to_test = move |test_name: String, stage: Option<&'static str>| {
+        if let Some(index) = fail_to_pass_tests.with_untracked(|tests| tests.iter().position(|t| *t == test_name)) {
+            current_selection.set("fail_to_pass".to_string());
+            selected_fail_to_pass_index.set(index);
+        } else if let Some(index) = pass_to_pass_tests.with_untracked(|tests| tests.iter().position(|t| *t == test_name)) {
+            current_selection.set("pass_to_pass".to_string());
+            selected_pass_to_pass_index.set(index);
+        }
+        if let Some(stage) = stage {
+            if !search_stages.with_untracked(|stages| stages.iter().any(|s| s == stage)) {
+                search_stages.update(|stages| stages.push(stage.to_string()));
+            }
+            focused_stage.set(Some(stage.to_string()));
+        }
+        // Old hits would otherwise be scrolled to before the new ones arrive
+        search_results.set(LogSearchResults::default());
+        active_main_tab.set("manual_checker".to_string());
+        search_for_test(test_name);
+    };
+
+    let patch_classes_open = RwSignal::new(false);
+    // Rule being re-checked, and the last re-check that failed as (code, message)
+    let rechecking = RwSignal::new(None::<&'static str>);
+    let recheck_error = RwSignal::new(None::<(&'static str, String)>);
+    let recheck = move |code: &'static str| {
+        let Some(file_paths) = result.with_untracked(|deliverable| deliverable.as_ref().map(|deliverable| deliverable.file_paths.clone())) else {
+            return;
+        };
+        rechecking.set(Some(code));
+        recheck_error.set(None);
+        spawn_local(async move {
+            match handle_recheck_rule(file_paths, code.to_string()).await {
+                // Only this rule's verdict changes; the rest of the analysis is left as it was
+                Ok(violation) => log_analysis_result.update(|analysis| {
+                    if let Some(rule) = analysis.as_mut().and_then(|analysis| analysis.rule_violations.get_mut(code)) {
+                        *rule = violation;
+                    }
+                }),
+                Err(e) => recheck_error.set(Some((code, e.to_string()))),
+            }
+            rechecking.set(None);
+        });
+    };
+
+    let is_listed = move |test_name: &str| {
+        fail_to_pass_tests.with(|tests| tests.iter().any(|t| t == test_name))
+            || pass_to_pass_tests.with(|tests| tests.iter().any(|t| t == test_name))
+    };
+
+    move || {
+        let Some(analysis) = log_analysis_result.get() else {
+            if log_analysis_loading.get() {
+                return view! {
+                    <div class="flex flex-col items-center justify-center gap-3 h-full text-gray-500 dark:text-gray-400">
+                        "Analyzing logs..."
+                        <AnalysisPhases progress=analysis_progress />
+                    </div>
+                }.into_any();
+            }
+            // Automatic analysis is off, or the last run failed
+            return view! {
+                <div class="flex flex-col items-center justify-center gap-3 h-full text-gray-500 dark:text-gray-400">
+                    "No analysis yet"
+                    <AnalysisPhases progress=analysis_progress />
+                    <button
+                        on:click=move |_| run_analysis()
+                        class="text-sm px-3 py-1 rounded bg-blue-600 text-white hover:bg-blue-700"
+                    >
+                        "Analyze logs"
+                    </button>
+                </div>
+            }.into_any();
+        };
+
+        let agent_log = analysis.debug_info.agent_log.clone();
+        // The cache folder is the deliverable's Drive folder id, stable across reviewers
+        let summary_href = result.with_untracked(|deliverable| {
+            deliverable.as_ref()
+                .and_then(|deliverable| deliverable.file_paths.first())
+                .and_then(|path| path.split('/').next())
+                .map(|folder_id| format!("/summary/{}", folder_id))
+        });
+        let cards = analysis.rule_violations.entries().into_iter().map(|(code, violation)| {
+            let (card_class, badge_class, badge) = if violation.has_problem {
+                (
+                    "rounded-lg border border-red-200 dark:border-red-800 bg-red-50/50 dark:bg-red-900/20 p-3 flex flex-col min-h-0",
+                    "px-2 py-0.5 rounded text-xs font-semibold bg-red-600 text-white",
+                    "FAIL",
+                )
+            } else {
+                (
+                    "rounded-lg border border-gray-200 dark:border-gray-700 bg-white dark:bg-gray-800 p-3 flex flex-col min-h-0",
+                    "px-2 py-0.5 rounded text-xs font-semibold bg-green-600 text-white",
+                    "PASS",
+                )
+            };
+            let copy_all = (!violation.examples.is_empty()).then(|| {
+                let buttons = COPY_FORMATS.iter().map(|(format, label, title)| {
+                    let key = format!("{}:{}", code, format);
+                    let key_for_label = key.clone();
+                    let text = format_examples(&violation.examples, format);
+                    view! {
+                        <button
+                            on:click=move |_| copy_to_clipboard(text.clone(), copied, key.clone())
+                            on:mouseleave=move |_| copied.set(None)
+                            title=*title
+                            class="text-xs px-2 py-0.5 border-l first:border-l-0 border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700"
+                        >
+                            {move || if copied.get().as_deref() == Some(key_for_label.as_str()) { "Copied" } else { *label }}
+                        </button>
+                    }
+                }).collect_view();
+                view! {
+                    <div class="flex items-center gap-1 text-xs text-gray-500 dark:text-gray-400">
+                        "Copy all:"
+                        <div class="flex rounded border border-gray-300 dark:border-gray-600 overflow-hidden">{buttons}</div>
+                    </div>
+                }
+            });
+
+            let examples = violation.examples.iter().cloned().map(|example| {
+                let test_name = example_test_name(&example).to_string();
+                let stage = rule_stage(code);
+                let listed = is_listed(&test_name);
+                let jump = listed.then(|| {
+                    let test_name = test_name.clone();
+                    view! {
+                        <button
+                            on:click=move |_| jump_to_test(test_name.clone(), stage)
+                            title="Show this test in the log panes"
+                            class="text-xs text-blue-600 dark:text-blue-400 hover:underline flex-shrink-0"
+                        >
+                            "Show in logs"
+                        </button>
+                    }
+                });
+                let example_for_copy = example.clone();
+                let example_for_label = example.clone();
+                let text = if listed {
+                    let test_name = test_name.clone();
+                    view! {
+                        <button
+                            on:click=move |_| jump_to_test(test_name.clone(), stage)
+                            class="font-mono text-xs break-all text-left text-gray-800 dark:text-gray-200 hover:text-blue-600 dark:hover:text-blue-400 hover:underline"
+                        >
+                            {example}
+                        </button>
+                    }.into_any()
+                } else {
+                    let span_class = if stage.is_some() {
+                        "font-mono text-xs break-all text-gray-800 dark:text-gray-200 cursor-help"
+                    } else {
+                        "font-mono text-xs break-all text-gray-800 dark:text-gray-200"
+                    };
+                    view! { <span class=span_class>{example}</span> }.into_any()
+                };
+                let label = match stage {
+                    Some(stage) => view! {
+                        <LogPreview result=result stage=stage.to_string() agent_log=agent_log.clone() line=None test_name=Some(test_name.clone())>
+                            {text}
+                        </LogPreview>
+                    }.into_any(),
+                    None => text,
+                };
+                view! {
+                    <li class="flex items-center gap-2 py-0.5">
+                        <div class="flex-1 min-w-0">{label}</div>
+                        {jump}
+                        <button
+                            on:click=move |_| copy_to_clipboard(example_for_copy.clone(), copied, example_for_copy.clone())
+                            on:mouseleave=move |_| copied.set(None)
+                            class="text-xs text-gray-500 dark:text-gray-400 hover:text-gray-800 dark:hover:text-gray-200 flex-shrink-0"
+                        >
+                            {move || if copied.get().as_deref() == Some(example_for_label.as_str()) { "Copied" } else { "Copy" }}
+                        </button>
+                    </li>
+                }
+            }).collect_view();
+
+            let recheck_button = RECHECKABLE_RULES.contains(&code).then(|| view! {
+                <button
+                    on:click=move |_| recheck(code)
+                    disabled=move || rechecking.get().is_some()
+                    title="Check this rule again from the stored statuses and the files as they are now"
+                    class="text-xs px-2 py-0.5 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 disabled:opacity-50"
+                >
+                    {move || if rechecking.get() == Some(code) { "Checking…" } else { "Re-check" }}
+                </button>
+            });
+            let recheck_failure = recheck_error.get()
+                .filter(|(failed, _)| *failed == code)
+                .map(|(_, message)| view! { <div class="mt-1 text-xs text-red-600 dark:text-red-400">{message}</div> });
+
+            view! {
+                <div class=card_class>
+                    <div class="flex items-center gap-2">
+                        <span class="font-semibold text-gray-900 dark:text-white">{code}</span>
+                        <span class=badge_class>{badge}</span>
+                        <span class="text-xs text-gray-500 dark:text-gray-400">{format!("{} examples", format_count(violation.examples.len()))}</span>
+                        <div class="ml-auto flex items-center gap-2">
+                            {copy_all}
+                            {recheck_button}
+                        </div>
+                    </div>
+                    <div class="mt-1 text-sm text-gray-700 dark:text-gray-300">{rule_description(code)}</div>
+                    {recheck_failure}
+                    <ul class="mt-2 overflow-y-auto max-h-48 divide-y divide-gray-100 dark:divide-gray-700">{examples}</ul>
+                </div>
+            }
+        }).collect_view();
+
+        view! {
+            <div class="h-full overflow-y-auto p-4">
+                <div class="mb-3 flex items-center gap-4 text-sm text-gray-700 dark:text-gray-300">
+                    {format!("Violated rules: {}", analysis.rule_violations.summary())}
+                    {summary_href.map(|href| view! {
+                        <a
+                            href=href
+                            target="_blank"
+                            title="Read-only summary that renders without the app, for sharing"
+                            class="text-blue-600 hover:text-blue-800 underline dark:text-blue-400 dark:hover:text-blue-300"
+                        >
+                            "Shareable summary"
+                        </a>
+                    })}
+                </div>
+                <AnalysisRunDiffPanel result=result />
+                <PatchClassesPanel result=result recheck=recheck is_open=patch_classes_open />
+                <div class="grid grid-cols-1 lg:grid-cols-2 gap-3">{cards}</div>
+            </div>
+        }.into_any()
+    }
+}
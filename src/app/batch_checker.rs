@@ -0,0 +1,189 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, BatchItem, BatchStatus, ACTIONS_FOLDER_PREFIX};
+
+const BATCH_POLL_MS: u64 = 1000;
+
+// Served at /api/batch_analyze so scripts can start a batch the same way the page does
+#[server(endpoint = "batch_analyze")]
+pub async fn handle_batch_analyze(links: Vec<String>) -> Result<BatchStatus, ApiError> {
+    use crate::api::batch::start_batch;
+    use crate::app::types::ApiErrorCode;
+    start_batch(links).map_err(|e| ApiError::new(ApiErrorCode::InvalidLink, e))
+}
+
+#[server]
+pub async fn handle_batch_status(batch_id: String) -> Result<Option<BatchStatus>, ApiError> {
+    use crate::api::batch::batch_status;
+    Ok(batch_status(&batch_id))
+}
+
+fn is_finished(item: &BatchItem) -> bool {
+    item.status == "done" || item.status == "failed"
+}
+
+fn status_cell(item: &BatchItem) -> AnyView {
+    let class = match item.status.as_str() {
+        "done" => "text-green-700 dark:text-green-300",
+        "failed" => "text-red-600 dark:text-red-400 font-medium",
+        "queued" => "text-gray-500 dark:text-gray-400",
+        _ => "text-blue-600 dark:text-blue-400",
+    };
+    let status = item.status.clone();
+    let error = item.error.clone();
+    view! {
+        <td class="py-1 pr-4 align-top">
+            <div class=class>{status}</div>
+            {error.map(|error| view! { <div class="text-xs text-red-600 dark:text-red-400 break-words">{error}</div> })}
+        </td>
+    }.into_any()
+}
+
+fn rules_cell(item: &BatchItem) -> AnyView {
+    match (&item.summary, item.violated.is_empty()) {
+        (Some(_), true) => view! { <td class="py-1 pr-4 align-top text-green-700 dark:text-green-300">"clean"</td> }.into_any(),
+        (Some(summary), false) => {
+            let badges = item.violated.iter().cloned().map(|code| view! {
+                <span class="px-1.5 py-0.5 rounded text-xs font-semibold bg-red-600 text-white">{code}</span>
+            }).collect_view();
+            view! {
+                <td class="py-1 pr-4 align-top" title=summary.clone()>
+                    <div class="flex flex-wrap gap-1">{badges}</div>
+                </td>
+            }.into_any()
+        }
+        (None, _) => view! { <td class="py-1 pr-4 align-top text-gray-400 dark:text-gray-500">"–"</td> }.into_any(),
+    }
+}
+
+// Drive deliverables open on their folder route; GitHub Actions runs have no route to open
+fn open_cell(item: &BatchItem) -> AnyView {
+    match item.folder_id.clone().filter(|folder_id| !folder_id.starts_with(ACTIONS_FOLDER_PREFIX)) {
+        Some(folder_id) if item.status == "done" => view! {
+            <td class="py-1 align-top">
+                <a href=format!("/{}", folder_id) target="_blank" class="text-blue-600 dark:text-blue-400 hover:underline">"Open"</a>
+            </td>
+        }.into_any(),
+        _ => view! { <td class="py-1 align-top"></td> }.into_any(),
+    }
+}
+
+fn batch_table(batch: BatchStatus) -> AnyView {
+    let finished = batch.items.iter().filter(|item| is_finished(item)).count();
+    let violating = batch.items.iter().filter(|item| !item.violated.is_empty()).count();
+    let failed = batch.items.iter().filter(|item| item.status == "failed").count();
+    let rows = batch.items.iter().enumerate().map(|(index, item)| view! {
+        <tr class="border-t border-gray-200 dark:border-gray-700">
+            <td class="py-1 pr-4 align-top text-gray-500 dark:text-gray-400">{index + 1}</td>
+            <td class="py-1 pr-4 align-top font-mono text-xs break-all text-gray-900 dark:text-white">{item.link.clone()}</td>
+            {status_cell(item)}
+            {rules_cell(item)}
+            {open_cell(item)}
+        </tr>
+    }).collect_view();
+    view! {
+        <div class="space-y-2">
+            <div class="text-sm text-gray-700 dark:text-gray-300">
+                {format!("{} of {} finished · {} with rule violations · {} failed", finished, batch.items.len(), violating, failed)}
+            </div>
+            <table class="w-full text-sm">
+                <thead>
+                    <tr class="text-left text-xs font-semibold text-gray-500 dark:text-gray-400">
+                        <th class="pb-1 pr-4">"#"</th>
+                        <th class="pb-1 pr-4">"Deliverable"</th>
+                        <th class="pb-1 pr-4">"Status"</th>
+                        <th class="pb-1 pr-4">"Violated rules"</th>
+                        <th class="pb-1"></th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+#[component]
+pub fn BatchCheckerPage() -> impl IntoView {
+    let links_text = RwSignal::new(String::new());
+    let batch = RwSignal::new(None::<BatchStatus>);
+    let error = RwSignal::new(None::<ApiError>);
+    let starting = RwSignal::new(false);
+    let poll = StoredValue::new(None::<IntervalHandle>);
+
+    let stop_polling = move || {
+        if let Some(handle) = poll.get_value() {
+            handle.clear();
+            poll.set_value(None);
+        }
+    };
+
+    let refresh = move |batch_id: String| {
+        spawn_local(async move {
+            if let Ok(Some(status)) = handle_batch_status(batch_id).await {
+                if status.items.iter().all(is_finished) {
+                    stop_polling();
+                }
+                batch.set(Some(status));
+            }
+        });
+    };
+
+    let start = move |_| {
+        let links: Vec<String> = links_text
+            .get_untracked()
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|link| !link.is_empty())
+            .map(str::to_string)
+            .collect();
+        stop_polling();
+        starting.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_batch_analyze(links).await {
+                Ok(status) => {
+                    let batch_id = status.id.clone();
+                    batch.set(Some(status));
+                    let handle = set_interval_with_handle(
+                        move || refresh(batch_id.clone()),
+                        std::time::Duration::from_millis(BATCH_POLL_MS),
+                    ).ok();
+                    poll.set_value(handle);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            starting.set(false);
+        });
+    };
+
+    on_cleanup(stop_polling);
+
+    view! {
+        <div class="h-full overflow-y-auto p-6 max-w-6xl mx-auto space-y-4">
+            <h2 class="text-lg font-semibold text-gray-900 dark:text-white">"Batch analysis"</h2>
+            <p class="text-sm text-gray-600 dark:text-gray-400">
+                "Paste deliverable links, one per line. Each is validated, downloaded and analyzed on the server, a few at a time."
+            </p>
+            <textarea
+                rows="6"
+                prop:value=move || links_text.get()
+                on:input=move |ev| links_text.set(event_target_value(&ev))
+                placeholder="https://drive.google.com/drive/folders/..."
+                class="w-full px-3 py-2 font-mono text-xs border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+            ></textarea>
+            <div class="flex items-center gap-3">
+                <button
+                    on:click=start
+                    disabled=move || starting.get() || links_text.get().trim().is_empty()
+                    class="text-sm px-3 py-1 rounded bg-blue-600 text-white hover:bg-blue-700 disabled:opacity-50"
+                >
+                    {move || if starting.get() { "Starting…" } else { "Analyze all" }}
+                </button>
+                {move || error.get().map(|e| view! {
+                    <span class="text-sm text-red-600 dark:text-red-400">{e.to_string()}</span>
+                })}
+            </div>
+            {move || batch.get().map(batch_table)}
+        </div>
+    }
+}
@@ -0,0 +1,276 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::report_export::handle_export_report;
+use super::review_bundle::handle_export_bundle;
+use super::types::{ProcessingResult, MAIN_TABS};
+use crate::components::DarkMode;
+
+// Long test lists would make every keystroke re-render thousands of rows
+const MAX_VISIBLE_COMMANDS: usize = 50;
+
+#[derive(Clone, PartialEq)]
+enum PaletteAction {
+    Tab(&'static str),
+    // Test type ("fail_to_pass"/"pass_to_pass"), index in its list, name
+    Test(&'static str, usize, String),
+    RunAnalysis,
+    Refresh,
+    ExportReport,
+    ExportBundle,
+    ToggleTheme,
+}
+
+#[derive(Clone, PartialEq)]
+struct Command {
+    label: String,
+    group: &'static str,
+    action: PaletteAction,
+}
+
+fn command(label: impl Into<String>, group: &'static str, action: PaletteAction) -> Command {
+    Command { label: label.into(), group, action }
+}
+
+// Every word of the query appears in the label, in any order
+fn matches_query(label: &str, query: &str) -> bool {
+    let label = label.to_lowercase();
+    query.to_lowercase().split_whitespace().all(|word| label.contains(word))
+}
+
+// Save a data URL through a throwaway link, the way the export buttons' links do
+fn download(href: String, file_name: String) {
+    #[cfg(feature = "hydrate")]
+    {
+        use wasm_bindgen::JsCast;
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else { return };
+        let Ok(link) = document.create_element("a") else { return };
+        let _ = link.set_attribute("href", &href);
+        let _ = link.set_attribute("download", &file_name);
+        if let Ok(link) = link.dyn_into::<web_sys::HtmlElement>() {
+            link.click();
+        }
+    }
+    #[cfg(not(feature = "hydrate"))]
+    let _ = (href, file_name);
+}
+
+/// Ctrl/Cmd-K palette over the checker's tabs, tests and actions
+#[allow(clippy::too_many_arguments)]
+#[component]
+pub fn CommandPalette(
+    active_main_tab: RwSignal<String>,
+    fail_to_pass_tests: RwSignal<Vec<String>>,
+    pass_to_pass_tests: RwSignal<Vec<String>>,
+    current_selection: RwSignal<String>,
+    selected_fail_to_pass_index: RwSignal<usize>,
+    selected_pass_to_pass_index: RwSignal<usize>,
+    search_for_test: impl Fn(String) + Send + Sync + 'static + Copy,
+    rerun_analysis: impl Fn() + Send + Sync + 'static + Copy,
+    refresh_deliverable: impl Fn() + Send + Sync + 'static + Copy,
+    result: RwSignal<Option<ProcessingResult>>,
+) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let query = RwSignal::new(String::new());
+    let highlighted = RwSignal::new(0usize);
+    // Progress or failure of an export started from the palette
+    let status = RwSignal::new(None::<String>);
+    let input_ref = NodeRef::<leptos::html::Input>::new();
+    let dark_mode = expect_context::<DarkMode>();
+
+    let open = move || {
+        query.set(String::new());
+        highlighted.set(0);
+        status.set(None);
+        is_open.set(true);
+    };
+
+    let handle = window_event_listener(leptos::ev::keydown, move |ev| {
+        if (ev.ctrl_key() || ev.meta_key()) && ev.key().eq_ignore_ascii_case("k") {
+            ev.prevent_default();
+            if is_open.get_untracked() {
+                is_open.set(false);
+            } else {
+                open();
+            }
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    // Type straight away once the palette is on screen
+    Effect::new(move |_| {
+        if let Some(input) = input_ref.get() {
+            let _ = input.focus();
+        }
+    });
+
+    let commands = Memo::new(move |_| {
+        let mut commands: Vec<Command> = MAIN_TABS
+            .iter()
+            .map(|&(key, label)| command(format!("Go to {}", label), "Tab", PaletteAction::Tab(key)))
+            .collect();
+        commands.push(command("Run analysis again", "Action", PaletteAction::RunAnalysis));
+        commands.push(command("Refresh deliverable from Drive", "Action", PaletteAction::Refresh));
+        commands.push(command("Export report (Markdown)", "Action", PaletteAction::ExportReport));
+        commands.push(command("Export review bundle", "Action", PaletteAction::ExportBundle));
+        commands.push(command("Toggle dark mode", "Action", PaletteAction::ToggleTheme));
+        for (index, name) in fail_to_pass_tests.get().into_iter().enumerate() {
+            commands.push(command(name.clone(), "F2P", PaletteAction::Test("fail_to_pass", index, name)));
+        }
+        for (index, name) in pass_to_pass_tests.get().into_iter().enumerate() {
+            commands.push(command(name.clone(), "P2P", PaletteAction::Test("pass_to_pass", index, name)));
+        }
+        commands
+    });
+
+    let visible = Memo::new(move |_| {
+        let query = query.get();
+        commands.with(|commands| {
+            commands
+                .iter()
+                .filter(|command| matches_query(&format!("{} {}", command.group, command.label), &query))
+                .take(MAX_VISIBLE_COMMANDS)
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let export = move |bundle: bool| {
+        let Some(deliverable) = result.get_untracked() else {
+            status.set(Some("No deliverable loaded".to_string()));
+            return;
+        };
+        let base_name = if deliverable.instance_id.is_empty() { "deliverable".to_string() } else { deliverable.instance_id.clone() };
+        status.set(Some(if bundle { "Building the review bundle..." } else { "Exporting the report..." }.to_string()));
+        spawn_local(async move {
+            let (file_paths, instance_id, task_id) = (deliverable.file_paths.clone(), deliverable.instance_id.clone(), deliverable.display_task_id());
+            let exported = if bundle {
+                handle_export_bundle(file_paths, instance_id, task_id)
+                    .await
+                    .map(|encoded| (format!("data:application/zip;base64,{}", encoded), format!("{}-review-bundle.zip", base_name)))
+            } else {
+                handle_export_report(file_paths, instance_id, task_id)
+                    .await
+                    .map(|report| (format!("data:text/markdown;charset=utf-8,{}", urlencoding::encode(&report.markdown)), format!("{}-report.md", base_name)))
+            };
+            match exported {
+                Ok((href, file_name)) => {
+                    download(href, file_name);
+                    status.set(None);
+                    is_open.set(false);
+                }
+                Err(e) => status.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let run = move |action: PaletteAction| {
+        match action {
+            PaletteAction::Tab(tab) => active_main_tab.set(tab.to_string()),
+            PaletteAction::Test(test_type, index, name) => {
+                active_main_tab.set("manual_checker".to_string());
+                current_selection.set(test_type.to_string());
+                if test_type == "fail_to_pass" {
+                    selected_fail_to_pass_index.set(index);
+                } else {
+                    selected_pass_to_pass_index.set(index);
+                }
+                search_for_test(name);
+            }
+            PaletteAction::RunAnalysis => {
+                active_main_tab.set("analysis".to_string());
+                rerun_analysis();
+            }
+            PaletteAction::Refresh => refresh_deliverable(),
+            PaletteAction::ToggleTheme => dark_mode.toggle(),
+            // Stays open to show progress, closes once the file is saved
+            PaletteAction::ExportReport => return export(false),
+            PaletteAction::ExportBundle => return export(true),
+        }
+        is_open.set(false);
+    };
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let count = visible.with_untracked(|visible| visible.len());
+        match ev.key().as_str() {
+            "ArrowDown" if count > 0 => {
+                ev.prevent_default();
+                highlighted.update(|index| *index = (*index + 1) % count);
+            }
+            "ArrowUp" if count > 0 => {
+                ev.prevent_default();
+                highlighted.update(|index| *index = (*index + count - 1) % count);
+            }
+            "Enter" => {
+                ev.prevent_default();
+                let picked = visible.with_untracked(|visible| visible.get(highlighted.get_untracked()).map(|command| command.action.clone()));
+                if let Some(action) = picked {
+                    run(action);
+                }
+            }
+            "Escape" => is_open.set(false),
+            _ => {}
+        }
+    };
+
+    let rows = move || {
+        let current = highlighted.get();
+        visible.get().into_iter().enumerate().map(|(index, command)| {
+            let class = if index == current {
+                "flex items-center gap-3 px-3 py-1.5 cursor-pointer bg-blue-50 dark:bg-blue-900/40 text-blue-700 dark:text-blue-300"
+            } else {
+                "flex items-center gap-3 px-3 py-1.5 cursor-pointer text-gray-800 dark:text-gray-200 hover:bg-gray-50 dark:hover:bg-gray-700"
+            };
+            let action = command.action.clone();
+            view! {
+                <li class=class on:mouseenter=move |_| highlighted.set(index) on:click=move |_| run(action.clone())>
+                    <span class="w-14 flex-shrink-0 text-xs text-gray-400 dark:text-gray-500">{command.group}</span>
+                    <span class="truncate font-mono text-sm">{command.label}</span>
+                </li>
+            }
+        }).collect_view()
+    };
+
+    move || is_open.get().then(|| view! {
+        <div class="fixed inset-0 z-50 flex items-start justify-center pt-24 bg-black/30" on:click=move |_| is_open.set(false)>
+            <div
+                class="w-full max-w-xl rounded-lg border border-gray-200 dark:border-gray-700 bg-white dark:bg-gray-800 shadow-xl overflow-hidden"
+                on:click=|ev| ev.stop_propagation()
+            >
+                <input
+                    node_ref=input_ref
+                    type="text"
+                    placeholder="Type a tab, action or test name"
+                    prop:value=move || query.get()
+                    on:input=move |ev| {
+                        query.set(event_target_value(&ev));
+                        highlighted.set(0);
+                    }
+                    on:keydown=on_keydown
+                    class="w-full px-4 py-3 border-b border-gray-200 dark:border-gray-700 bg-transparent text-gray-900 dark:text-white placeholder-gray-500 dark:placeholder-gray-400 focus:outline-none"
+                />
+                <ul class="max-h-80 overflow-y-auto py-1">
+                    {rows}
+                </ul>
+                <div class="flex justify-between px-3 py-1.5 border-t border-gray-200 dark:border-gray-700 text-xs text-gray-500 dark:text-gray-400">
+                    <span>{move || status.get().unwrap_or_else(|| "↑↓ to pick, Enter to run, Esc to close".to_string())}</span>
+                    <span>"Ctrl/⌘ K"</span>
+                </div>
+            </div>
+        </div>
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_query() {
+        assert!(matches_query("F2P tests::parse_header", ""));
+        assert!(matches_query("Tab Go to Agent Log", "agent go"));
+        assert!(matches_query("Tab Go to Agent Log", "  LOG  "));
+        assert!(!matches_query("Tab Go to Agent Log", "agent debug"));
+        assert!(!matches_query("P2P tests::parse", "parse_header"));
+    }
+}
@@ -0,0 +1,86 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ContextDiff, DiffLineKind, ProcessingResult};
+
+#[server]
+pub async fn handle_context_diff(file_paths: Vec<String>, test_name: String) -> Result<ContextDiff, ApiError> {
+    use crate::api::log_analysis::context_diff;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    context_diff(file_paths, test_name).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to diff the logs").with_details(e))
+}
+
+// Diff body, type-erased to keep the view type shallow
+fn diff_view(diff: ContextDiff) -> AnyView {
+    if diff.before_anchor.is_none() && diff.after_anchor.is_none() {
+        return view! { <div class="text-sm text-gray-500 dark:text-gray-400">"The test isn't mentioned in the before or after log"</div> }.into_any();
+    }
+    if diff.lines.iter().all(|line| line.kind == DiffLineKind::Context) {
+        return view! { <div class="text-sm text-gray-500 dark:text-gray-400">"No difference around this test between before and after"</div> }.into_any();
+    }
+    let number = |line: Option<usize>| line.map(|line| line.to_string()).unwrap_or_default();
+    let rows = diff.lines.into_iter().map(|line| {
+        let (row_class, marker) = match line.kind {
+            DiffLineKind::Context => ("flex text-gray-600 dark:text-gray-400", " "),
+            DiffLineKind::Removed => ("flex bg-red-50 dark:bg-red-900/30 text-red-800 dark:text-red-200", "-"),
+            DiffLineKind::Added => ("flex bg-green-50 dark:bg-green-900/30 text-green-800 dark:text-green-200", "+"),
+        };
+        view! {
+            <div class=row_class>
+                <span class="w-10 text-right pr-2 text-gray-400 dark:text-gray-500 flex-shrink-0 select-none">{number(line.before_line)}</span>
+                <span class="w-10 text-right pr-2 text-gray-400 dark:text-gray-500 flex-shrink-0 select-none">{number(line.after_line)}</span>
+                <span class="w-4 flex-shrink-0 select-none">{marker}</span>
+                <span class="flex-1 whitespace-pre-wrap break-all">{line.text}</span>
+            </div>
+        }
+    }).collect_view();
+    view! { <div class="font-mono text-xs">{rows}</div> }.into_any()
+}
+
+// Unified diff of the selected test's failure context, before log against after log
+#[component]
+pub fn ContextDiffView(
+    result: RwSignal<Option<ProcessingResult>>,
+    selected_test: Signal<Option<String>>,
+) -> impl IntoView {
+    let diff = RwSignal::new(None::<ContextDiff>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let test_name = selected_test.get();
+        diff.set(None);
+        error.set(None);
+        let (Some(test_name), Some(deliverable)) = (test_name, result.get_untracked()) else {
+            return;
+        };
+        loading.set(true);
+        spawn_local(async move {
+            match handle_context_diff(deliverable.file_paths, test_name.clone()).await {
+                // A newer selection may have landed while this one was in flight
+                Ok(found) if selected_test.get_untracked().as_deref() == Some(test_name.as_str()) => diff.set(Some(found)),
+                Ok(_) => {}
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            loading.set(false);
+        });
+    });
+
+    view! {
+        <div class="flex-1 min-h-0 overflow-auto p-4">
+            {move || {
+                if let Some(e) = error.get() {
+                    view! { <div class="text-sm text-red-600 dark:text-red-400">{e}</div> }.into_any()
+                } else if let Some(found) = diff.get() {
+                    diff_view(found)
+                } else if loading.get() {
+                    view! { <div class="text-sm text-gray-500 dark:text-gray-400">"Diffing before and after..."</div> }.into_any()
+                } else {
+                    view! { <div class="text-sm text-gray-500 dark:text-gray-400">"Select a test to diff its context"</div> }.into_any()
+                }
+            }}
+        </div>
+    }
+}
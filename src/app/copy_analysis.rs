@@ -0,0 +1,53 @@
+use leptos::prelude::*;
+
+use super::types::LogAnalysisResult;
+
+#[component]
+pub fn CopyAnalysisButton(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    let copied = RwSignal::new(false);
+
+    let copy = move |_| {
+        let Some(analysis) = log_analysis_result.get_untracked() else {
+            return;
+        };
+        let text = match serde_json::to_string_pretty(&analysis) {
+            Ok(text) => text,
+            Err(e) => {
+                leptos::logging::log!("Failed to serialize analysis: {:?}", e);
+                return;
+            }
+        };
+        leptos::logging::log!("Copying analysis JSON ({} chars)", text.len());
+        #[cfg(feature = "hydrate")]
+        {
+            if let Some(window) = web_sys::window() {
+                let promise = window.navigator().clipboard().write_text(&text);
+                let future = wasm_bindgen_futures::JsFuture::from(promise);
+                wasm_bindgen_futures::spawn_local(async move {
+                    match future.await {
+                        Ok(_) => copied.set(true),
+                        Err(e) => leptos::logging::log!("Failed to copy to clipboard: {:?}", e),
+                    }
+                });
+            }
+        }
+        #[cfg(not(feature = "hydrate"))]
+        let _ = text;
+    };
+
+    move || {
+        if log_analysis_result.with(|analysis| analysis.is_none()) {
+            return view! { <div></div> }.into_any();
+        }
+        view! {
+            <button
+                on:click=copy
+                on:mouseleave=move |_| copied.set(false)
+                title="Copy the full analysis as JSON"
+                class="text-sm px-2 py-0.5 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 whitespace-nowrap"
+            >
+                {move || if copied.get() { "Copied" } else { "Copy analysis" }}
+            </button>
+        }.into_any()
+    }
+}
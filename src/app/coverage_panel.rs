@@ -0,0 +1,89 @@
+use leptos::prelude::*;
+
+use super::types::{CoverageSummary, LogAnalysisResult};
+
+fn coverage_percent(covered: usize, total: usize) -> String {
+    if total == 0 {
+        "0%".to_string()
+    } else {
+        format!("{:.0}%", covered as f64 * 100.0 / total as f64)
+    }
+}
+
+// Popover body, type-erased to keep the view type shallow
+fn coverage_details(coverage: CoverageSummary) -> AnyView {
+    let rows = coverage.files.into_iter().map(|file| {
+        let row_class = if file.lines_covered == 0 { "text-red-700 dark:text-red-300" } else { "text-gray-800 dark:text-gray-100" };
+        let lines = if file.in_report {
+            format!("{}/{} lines ({})", file.lines_covered, file.lines_total, coverage_percent(file.lines_covered, file.lines_total))
+        } else {
+            "not in report".to_string()
+        };
+        view! {
+            <tr class=row_class>
+                <td class="font-mono pr-2 break-all">{file.path}</td>
+                <td class="text-right whitespace-nowrap">{lines}</td>
+            </tr>
+        }
+    }).collect_view();
+
+    let warnings = if coverage.warnings.is_empty() {
+        view! { <div></div> }.into_any()
+    } else {
+        let items = coverage.warnings.into_iter().map(|warning| view! {
+            <li class="font-mono break-all text-yellow-800 dark:text-yellow-200">{warning}</li>
+        }).collect_view();
+        view! {
+            <div>
+                <div class="text-xs font-semibold text-yellow-700 dark:text-yellow-300 mb-1">"F2P tests with uncovered targets"</div>
+                <ul class="text-xs space-y-1">{items}</ul>
+            </div>
+        }.into_any()
+    };
+
+    view! {
+        <div class="absolute left-0 mt-2 w-[32rem] max-h-96 overflow-y-auto z-50 p-3 space-y-3 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+            <div>
+                <div class="text-xs font-semibold text-gray-500 dark:text-gray-400 mb-1">"Golden patch files"</div>
+                <table class="w-full text-xs">
+                    <tbody>{rows}</tbody>
+                </table>
+            </div>
+            {warnings}
+        </div>
+    }.into_any()
+}
+
+#[component]
+pub fn CoveragePanel(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let coverage = move || -> Option<CoverageSummary> {
+        log_analysis_result.get().and_then(|analysis| analysis.coverage)
+    };
+
+    move || {
+        let Some(summary) = coverage() else {
+            return view! { <div></div> }.into_any();
+        };
+        let warning_count = summary.warnings.len();
+        let button_class = if warning_count > 0 {
+            "text-sm px-2 py-0.5 rounded border border-yellow-300 dark:border-yellow-700 bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200"
+        } else {
+            "text-sm px-2 py-0.5 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700"
+        };
+        let label = if warning_count > 0 {
+            format!("Coverage ({} warning{})", warning_count, if warning_count == 1 { "" } else { "s" })
+        } else {
+            "Coverage".to_string()
+        };
+
+        view! {
+            <div class="relative">
+                <button on:click=move |_| is_open.update(|open| *open = !*open) class=button_class>
+                    {label}
+                </button>
+                {move || if is_open.get() { coverage_details(summary.clone()) } else { view! { <div></div> }.into_any() }}
+            </div>
+        }.into_any()
+    }
+}
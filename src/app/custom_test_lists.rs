@@ -0,0 +1,131 @@
+use leptos::prelude::*;
+
+use super::types::CustomTestLists;
+
+// Read an uploaded text/CSV file into `target`
+fn read_uploaded_file(ev: leptos::ev::Event, target: impl Fn(String) + 'static) {
+    #[cfg(feature = "hydrate")]
+    {
+        use wasm_bindgen::JsCast;
+        let Some(input) = ev.target().and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) else {
+            return;
+        };
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        let future = wasm_bindgen_futures::JsFuture::from(file.text());
+        wasm_bindgen_futures::spawn_local(async move {
+            match future.await {
+                Ok(text) => target(text.as_string().unwrap_or_default()),
+                Err(e) => leptos::logging::log!("Failed to read uploaded test list: {:?}", e),
+            }
+        });
+    }
+    #[cfg(not(feature = "hydrate"))]
+    let _ = (ev, target);
+}
+
+// One list editor: a textarea plus a file picker that fills it
+fn list_editor(label: &'static str, text: RwSignal<String>) -> AnyView {
+    view! {
+        <div>
+            <div class="flex items-center justify-between mb-1">
+                <span class="text-xs font-semibold text-gray-500 dark:text-gray-400">{label}</span>
+                <input
+                    type="file"
+                    accept=".txt,.csv,.json,text/plain,text/csv,application/json"
+                    on:change=move |ev| read_uploaded_file(ev, move |content| text.set(content))
+                    class="text-xs text-gray-600 dark:text-gray-300"
+                />
+            </div>
+            <textarea
+                prop:value=move || text.get()
+                on:input=move |ev| text.set(event_target_value(&ev))
+                placeholder="One test per line, a CSV with the name in the first column, or a JSON array"
+                class="w-full h-28 px-2 py-1 font-mono text-xs border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+            />
+        </div>
+    }.into_any()
+}
+
+#[component]
+pub fn CustomTestListsPanel(
+    custom_test_lists: RwSignal<Option<CustomTestLists>>,
+    apply: impl Fn() + Send + Sync + 'static + Copy,
+) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let fail_to_pass = RwSignal::new(String::new());
+    let pass_to_pass = RwSignal::new(String::new());
+    let replace = RwSignal::new(false);
+
+    let open = move |_| {
+        let current = custom_test_lists.get_untracked().unwrap_or_default();
+        fail_to_pass.set(current.fail_to_pass);
+        pass_to_pass.set(current.pass_to_pass);
+        replace.set(current.replace);
+        is_open.update(|open| *open = !*open);
+    };
+
+    let submit = move |_| {
+        let lists = CustomTestLists {
+            fail_to_pass: fail_to_pass.get_untracked(),
+            pass_to_pass: pass_to_pass.get_untracked(),
+            replace: replace.get_untracked(),
+        };
+        let empty = lists.fail_to_pass.trim().is_empty() && lists.pass_to_pass.trim().is_empty();
+        custom_test_lists.set(if empty { None } else { Some(lists) });
+        is_open.set(false);
+        apply();
+    };
+
+    let clear = move |_| {
+        is_open.set(false);
+        if custom_test_lists.get_untracked().is_some() {
+            custom_test_lists.set(None);
+            apply();
+        }
+    };
+
+    view! {
+        <div class="relative">
+            <button
+                on:click=open
+                class=move || if custom_test_lists.get().is_some() {
+                    "text-sm px-2 py-0.5 rounded border border-blue-300 dark:border-blue-700 bg-blue-50 dark:bg-blue-900/30 text-blue-800 dark:text-blue-200 whitespace-nowrap"
+                } else {
+                    "text-sm px-2 py-0.5 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 whitespace-nowrap"
+                }
+            >
+                {move || if custom_test_lists.get().is_some() { "Custom tests (on)" } else { "Custom tests" }}
+            </button>
+            <Show when=move || is_open.get()>
+                <div class="absolute left-0 mt-2 w-[32rem] z-50 p-3 space-y-3 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+                    {list_editor("Fail to pass", fail_to_pass)}
+                    {list_editor("Pass to pass", pass_to_pass)}
+                    <label class="flex items-center gap-2 text-xs text-gray-700 dark:text-gray-200">
+                        <input
+                            type="checkbox"
+                            prop:checked=move || replace.get()
+                            on:change=move |ev| replace.set(event_target_checked(&ev))
+                        />
+                        "Replace main.json's lists (a list left empty keeps main.json's)"
+                    </label>
+                    <div class="flex gap-2">
+                        <button
+                            on:click=submit
+                            class="text-xs px-2 py-1 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                        >
+                            "Apply"
+                        </button>
+                        <button
+                            on:click=clear
+                            class="text-xs px-2 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200"
+                        >
+                            "Use main.json only"
+                        </button>
+                    </div>
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,111 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::number_format::format_ratio;
+use super::types::{DashboardSummary, ParserQuality};
+
+const STAGES: &[&str] = &["base", "before", "after", "agent"];
+
+#[server]
+pub async fn handle_dashboard_summary() -> Result<DashboardSummary, ServerFnError> {
+    use crate::api::analysis_store::{review_effort_summary_of, stored_analyses};
+    use crate::api::parser_quality::aggregate_match_rates;
+    // Reading every stored analysis is the slow part, so both sections share one pass
+    let analyses = tokio::task::spawn_blocking(stored_analyses)
+        .await
+        .map_err(|e| ServerFnError::new(format!("Failed to read stored analyses: {}", e)))?;
+    Ok(DashboardSummary {
+        parser_quality: aggregate_match_rates(&analyses),
+        review_effort: review_effort_summary_of(&analyses),
+    })
+}
+
+fn parser_quality_table(rows: Vec<ParserQuality>) -> AnyView {
+    if rows.is_empty() {
+        return view! { <p class="text-sm text-gray-500 dark:text-gray-400">"No analyses stored yet"</p> }.into_any();
+    }
+    let body = rows.into_iter().map(|row| {
+        let cells = STAGES.iter().map(|stage| {
+            let rate = row.stages.iter().find(|rate| rate.stage == *stage).cloned().unwrap_or_default();
+            // Below 95% the parser is probably losing tests, not the deliverable
+            let cell_class = if rate.fraction().is_some_and(|fraction| fraction < 0.95) {
+                "py-1 pr-4 text-right font-semibold text-yellow-700 dark:text-yellow-300"
+            } else {
+                "py-1 pr-4 text-right text-gray-700 dark:text-gray-300"
+            };
+            view! {
+                <td class=cell_class>
+                    <div>{format!("F2P {}", format_ratio(rate.f2p_resolved, rate.f2p_total))}</div>
+                    <div>{format!("P2P {}", format_ratio(rate.p2p_resolved, rate.p2p_total))}</div>
+                </td>
+            }
+        }).collect_view();
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+                <td class="py-1 pr-4 text-gray-900 dark:text-white">{row.language}</td>
+                <td class="py-1 pr-4 font-mono text-gray-900 dark:text-white">{row.parser}</td>
+                <td class="py-1 pr-4 text-right text-gray-700 dark:text-gray-300">{row.analyses}</td>
+                {cells}
+            </tr>
+        }
+    }).collect_view();
+    let headers = STAGES.iter().map(|stage| view! { <th class="pb-1 pr-4 text-right capitalize">{*stage}</th> }).collect_view();
+    view! {
+        <table class="w-full text-xs">
+            <thead>
+                <tr class="text-left font-semibold text-gray-500 dark:text-gray-400">
+                    <th class="pb-1 pr-4">"Language"</th>
+                    <th class="pb-1 pr-4">"Parser"</th>
+                    <th class="pb-1 pr-4 text-right">"Analyses"</th>
+                    {headers}
+                </tr>
+            </thead>
+            <tbody>{body}</tbody>
+        </table>
+    }.into_any()
+}
+
+// Team-wide numbers across every deliverable analyzed on this server
+#[component]
+pub fn DashboardPage() -> impl IntoView {
+    let summary = RwSignal::new(None::<DashboardSummary>);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        spawn_local(async move {
+            match handle_dashboard_summary().await {
+                Ok(loaded) => summary.set(Some(loaded)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    });
+
+    view! {
+        <div class="h-full overflow-y-auto p-6 space-y-6">
+            <section>
+                <h2 class="text-lg font-semibold text-gray-900 dark:text-white">"Parser match quality"</h2>
+                <p class="mb-2 text-sm text-gray-500 dark:text-gray-400">
+                    "Share of listed F2P/P2P tests each stage's log resolved to a status other than missing, pooled per language and parser"
+                </p>
+                {move || match summary.get() {
+                    Some(summary) => parser_quality_table(summary.parser_quality),
+                    None => view! { <p class="text-sm text-gray-500 dark:text-gray-400">"Loading..."</p> }.into_any(),
+                }}
+            </section>
+            {move || summary.get().map(|summary| summary.review_effort).filter(|summary| summary.reviews > 0).map(|summary| view! {
+                <section>
+                    <h2 class="text-lg font-semibold text-gray-900 dark:text-white">"Review time"</h2>
+                    <p class="text-sm text-gray-700 dark:text-gray-300">
+                        {format!(
+                            "{} decisions, median {:.1} min, mean {:.1} min",
+                            summary.reviews,
+                            summary.median_seconds as f64 / 60.0,
+                            summary.mean_seconds as f64 / 60.0,
+                        )}
+                    </p>
+                </section>
+            })}
+            {move || error.get().map(|e| view! { <p class="text-sm text-red-600 dark:text-red-400">{e}</p> })}
+        </div>
+    }
+}
@@ -0,0 +1,346 @@
+use leptos::prelude::*;
+
+use super::number_format::{format_count, format_percent, format_ratio, format_size};
+use super::scratch_parser::ScratchParser;
+use super::types::{AnalysisSettings, AnalysisSource, BrowserRun, DebugInfo, LogAnalysisResult, ParseWarning, StageMatchRate, TestBinaryRun};
+
+// Body of the tab, type-erased to keep the view type shallow
+fn debug_details(debug_info: DebugInfo) -> AnyView {
+    let rows = debug_info.log_counts.into_iter().map(|count| {
+        let warnings = if count.warnings.is_empty() {
+            view! { <span class="text-gray-400 dark:text-gray-500">"—"</span> }.into_any()
+        } else {
+            let items = count.warnings.into_iter().map(|warning| view! {
+                <li class="text-yellow-800 dark:text-yellow-200">{warning}</li>
+            }).collect_view();
+            view! { <ul class="space-y-1">{items}</ul> }.into_any()
+        };
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+                <td class="py-2 pr-4 font-medium text-gray-900 dark:text-white">{count.label}</td>
+                <td class="py-2 pr-4 text-right text-green-700 dark:text-green-300">{format_count(count.passed)}</td>
+                <td class="py-2 pr-4 text-right text-red-700 dark:text-red-300">{format_count(count.failed)}</td>
+                <td class="py-2 pr-4 text-right text-yellow-700 dark:text-yellow-300">{format_count(count.ignored)}</td>
+                <td class="py-2 pr-4 text-right text-gray-800 dark:text-gray-100">{format_count(count.all)}</td>
+                <td class="py-2 pr-4 text-right text-gray-800 dark:text-gray-100">{format_percent(count.passed, count.all)}</td>
+                <td class="py-2 pr-4 font-mono text-gray-800 dark:text-gray-100">{count.parser.unwrap_or_else(|| "unknown".to_string())}</td>
+                <td class="py-2">{warnings}</td>
+            </tr>
+        }
+    }).collect_view();
+
+    let missing_rows = debug_info.missing_stages.into_iter().map(|stage| view! {
+        <tr class="border-t border-gray-200 dark:border-gray-700 text-gray-500 dark:text-gray-400">
+            <td class="py-2 pr-4 font-medium">{stage}</td>
+            <td class="py-2" colspan="7">"No log in the deliverable"</td>
+        </tr>
+    }).collect_view();
+
+    view! {
+        <div class="p-4">
+            <table class="w-full text-sm">
+                <thead>
+                    <tr class="text-left text-xs font-semibold text-gray-500 dark:text-gray-400">
+                        <th class="pb-2 pr-4">"Stage"</th>
+                        <th class="pb-2 pr-4 text-right">"Passed"</th>
+                        <th class="pb-2 pr-4 text-right">"Failed"</th>
+                        <th class="pb-2 pr-4 text-right">"Ignored"</th>
+                        <th class="pb-2 pr-4 text-right">"All"</th>
+                        <th class="pb-2 pr-4 text-right">"Pass rate"</th>
+                        <th class="pb-2 pr-4">"Parser"</th>
+                        <th class="pb-2">"Warnings"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {rows}
+                    {missing_rows}
+                </tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+// What the parsers skipped or preprocessing rewrote, per stage; notes about a log as a whole
+// are listed with its counts
+fn parse_warnings_details(warnings: Vec<ParseWarning>) -> AnyView {
+    let warnings: Vec<ParseWarning> = warnings.into_iter().filter(|warning| !warning.kind.is_log_note()).collect();
+    if warnings.is_empty() {
+        return ().into_any();
+    }
+    let rows = warnings.into_iter().map(|warning| {
+        let message_class = if warning.kind.may_change_outcome() {
+            "py-1 pr-4 text-yellow-800 dark:text-yellow-200"
+        } else {
+            "py-1 pr-4 text-gray-800 dark:text-gray-100"
+        };
+        let examples = warning.examples.into_iter().map(|example| view! {
+            <li class="font-mono break-all">{example}</li>
+        }).collect_view();
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+                <td class="py-1 pr-4 font-medium text-gray-500 dark:text-gray-400 whitespace-nowrap">{warning.stage}</td>
+                <td class=message_class>{warning.message}</td>
+                <td class="py-1 text-gray-600 dark:text-gray-300"><ul class="space-y-0.5">{examples}</ul></td>
+            </tr>
+        }
+    }).collect_view();
+    view! {
+        <div class="px-4 pb-4">
+            <div class="text-sm font-semibold text-gray-900 dark:text-white mb-1">"Parse quality"</div>
+            <table class="w-full text-xs"><tbody>{rows}</tbody></table>
+        </div>
+    }.into_any()
+}
+
+// Karma browsers and Playwright projects, so a failure that only one of them saw can be traced
+fn browser_runs_details(runs: Vec<BrowserRun>) -> AnyView {
+    if runs.is_empty() {
+        return ().into_any();
+    }
+    let rows = runs.into_iter().map(|run| {
+        let failed_tests = run.failed_tests.into_iter().map(|name| view! {
+            <li class="font-mono break-all">{name}</li>
+        }).collect_view();
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+                <td class="py-1 pr-4 font-medium text-gray-500 dark:text-gray-400 whitespace-nowrap">{run.stage}</td>
+                <td class="py-1 pr-4 font-mono text-gray-800 dark:text-gray-100">{run.browser}</td>
+                <td class="py-1 pr-4 text-right text-green-700 dark:text-green-300">{run.passed}</td>
+                <td class="py-1 pr-4 text-right text-red-700 dark:text-red-300">{run.failed}</td>
+                <td class="py-1 pr-4 text-right text-yellow-700 dark:text-yellow-300">{run.ignored}</td>
+                <td class="py-1 text-red-800 dark:text-red-200"><ul class="space-y-0.5">{failed_tests}</ul></td>
+            </tr>
+        }
+    }).collect_view();
+    view! {
+        <div class="px-4 pb-4">
+            <div class="text-sm font-semibold text-gray-900 dark:text-white mb-1">"Browsers"</div>
+            <table class="w-full text-xs">
+                <thead>
+                    <tr class="text-left font-semibold text-gray-500 dark:text-gray-400">
+                        <th class="pb-1 pr-4">"Stage"</th>
+                        <th class="pb-1 pr-4">"Browser"</th>
+                        <th class="pb-1 pr-4 text-right">"Passed"</th>
+                        <th class="pb-1 pr-4 text-right">"Failed"</th>
+                        <th class="pb-1 pr-4 text-right">"Ignored"</th>
+                        <th class="pb-1">"Failed tests"</th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+// Cargo test binaries in log order; a binary listed twice in a stage means the log holds concatenated runs
+fn test_binaries_details(binaries: Vec<TestBinaryRun>) -> AnyView {
+    if binaries.is_empty() {
+        return ().into_any();
+    }
+    let rows = binaries.into_iter().map(|binary| {
+        let run_class = if binary.run > 1 {
+            "py-1 pr-4 text-right font-semibold text-yellow-700 dark:text-yellow-300"
+        } else {
+            "py-1 pr-4 text-right text-gray-700 dark:text-gray-300"
+        };
+        let declared = binary.declared.map(format_count).unwrap_or_else(|| "—".to_string());
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+                <td class="py-1 pr-4 font-medium text-gray-500 dark:text-gray-400 whitespace-nowrap">{binary.stage}</td>
+                <td class="py-1 pr-4 font-mono text-gray-800 dark:text-gray-100 break-all">{binary.binary}</td>
+                <td class=run_class>{binary.run}</td>
+                <td class="py-1 pr-4 text-right text-gray-700 dark:text-gray-300">{declared}</td>
+                <td class="py-1 pr-4 text-right text-green-700 dark:text-green-300">{format_count(binary.passed)}</td>
+                <td class="py-1 pr-4 text-right text-red-700 dark:text-red-300">{format_count(binary.failed)}</td>
+                <td class="py-1 text-right text-yellow-700 dark:text-yellow-300">{format_count(binary.ignored)}</td>
+            </tr>
+        }
+    }).collect_view();
+    view! {
+        <div class="px-4 pb-4">
+            <div class="text-sm font-semibold text-gray-900 dark:text-white mb-1">"Test binaries"</div>
+            <table class="w-full text-xs">
+                <thead>
+                    <tr class="text-left font-semibold text-gray-500 dark:text-gray-400">
+                        <th class="pb-1 pr-4">"Stage"</th>
+                        <th class="pb-1 pr-4">"Binary"</th>
+                        <th class="pb-1 pr-4 text-right">"Run"</th>
+                        <th class="pb-1 pr-4 text-right">"Declared"</th>
+                        <th class="pb-1 pr-4 text-right">"Passed"</th>
+                        <th class="pb-1 pr-4 text-right">"Failed"</th>
+                        <th class="pb-1 text-right">"Ignored"</th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+fn match_rates_details(rates: Vec<StageMatchRate>) -> AnyView {
+    if rates.is_empty() {
+        return ().into_any();
+    }
+    let rows = rates.into_iter().map(|rate| {
+        let cell_class = |fraction: Option<f64>| if fraction.is_some_and(|fraction| fraction < 1.0) {
+            "py-1 pr-4 text-right font-semibold text-yellow-700 dark:text-yellow-300"
+        } else {
+            "py-1 pr-4 text-right text-gray-700 dark:text-gray-300"
+        };
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700">
+                <td class="py-1 pr-4 font-medium text-gray-500 dark:text-gray-400">{rate.stage.clone()}</td>
+                <td class=cell_class(rate.f2p_fraction())>{format_ratio(rate.f2p_resolved, rate.f2p_total)}</td>
+                <td class=cell_class(rate.p2p_fraction())>{format_ratio(rate.p2p_resolved, rate.p2p_total)}</td>
+            </tr>
+        }
+    }).collect_view();
+    view! {
+        <div class="px-4 pb-4">
+            <div class="text-sm font-semibold text-gray-900 dark:text-white mb-1">"Parser match quality"</div>
+            <div class="text-xs text-gray-500 dark:text-gray-400 mb-1">"Listed tests each log resolved to a status other than missing"</div>
+            <table class="w-full text-xs">
+                <thead>
+                    <tr class="text-left font-semibold text-gray-500 dark:text-gray-400">
+                        <th class="pb-1 pr-4">"Stage"</th>
+                        <th class="pb-1 pr-4 text-right">"F2P"</th>
+                        <th class="pb-1 pr-4 text-right">"P2P"</th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+// Options the analysis ran with, as recorded in the persisted result
+fn settings_details(settings: AnalysisSettings) -> AnyView {
+    let or_default = |value: Option<String>| value.unwrap_or_else(|| "—".to_string());
+    let list_or_default = |values: Vec<String>, default: &str| if values.is_empty() { default.to_string() } else { values.join(", ") };
+    let custom_lists = match settings.custom_test_lists {
+        Some(lists) if lists.replace => "replacing main.json's lists".to_string(),
+        Some(_) => "added to main.json's lists".to_string(),
+        None => "—".to_string(),
+    };
+    let mut patch_classes: Vec<String> = settings.patch_classes.iter().map(|(name, class)| format!("{}: {}", name, class)).collect();
+    patch_classes.sort();
+    let patch_classes = list_or_default(patch_classes, "guessed from file names");
+    let entries: Vec<(&'static str, String)> = vec![
+        ("Language", settings.language),
+        ("Parser", settings.parser),
+        ("Pinned framework", or_default(settings.pinned_framework)),
+        ("Detection rules", or_default(settings.detection_rules_file)),
+        ("Agent log", or_default(settings.agent_log)),
+        ("Custom test lists", custom_lists),
+        ("Duplicate heuristics", format!(
+            "min distance {}, context window {}{}",
+            settings.duplicate_min_distance,
+            settings.duplicate_context_window,
+            if settings.duplicate_require_status_conflict { ", status conflicts only" } else { "" }
+        )),
+        ("P2P breadth (C10)", if settings.p2p_breadth_min_percent > 0.0 {
+            format!("under {}% of base tests, base logs of {}+ tests", settings.p2p_breadth_min_percent, settings.p2p_breadth_min_base_tests)
+        } else {
+            "off".to_string()
+        }),
+        ("Noise filter", if settings.noise_filter_disabled {
+            "off".to_string()
+        } else if settings.language_noise_patterns.is_empty() {
+            list_or_default(settings.noise_patterns, "built-in patterns")
+        } else {
+            format!("{}, plus {}", list_or_default(settings.noise_patterns, "built-in patterns"), settings.language_noise_patterns.join(", "))
+        }),
+        ("Name rewrites", list_or_default(settings.name_rewrites, "—")),
+        ("Search context", if settings.search_context == 0 { "—".to_string() } else { format!("{} lines", settings.search_context) }),
+        ("Patch classes (C7)", patch_classes),
+        ("Experimental parsers", list_or_default(settings.experimental_parsers, "—")),
+        ("Disabled parsers", list_or_default(settings.disabled_parsers, "—")),
+        ("Jest file names", if settings.jest_file_names { "on" } else { "off" }.to_string()),
+        ("Playwright retries", if settings.playwright_retry_policy.is_empty() { "—".to_string() } else { settings.playwright_retry_policy }),
+        ("Version", settings.app_version),
+    ];
+    let rows = entries.into_iter().map(|(label, value)| view! {
+        <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+            <td class="py-1 pr-4 font-medium text-gray-500 dark:text-gray-400 whitespace-nowrap">{label}</td>
+            <td class="py-1 font-mono break-all text-gray-800 dark:text-gray-100">{value}</td>
+        </tr>
+    }).collect_view();
+    view! {
+        <div class="px-4 pb-4">
+            <div class="text-sm font-semibold text-gray-900 dark:text-white mb-1">"Analysis settings"</div>
+            <table class="w-full text-xs"><tbody>{rows}</tbody></table>
+        </div>
+    }.into_any()
+}
+
+// Files the analysis read; the short hash is enough to spot a changed log, the full one is in the tooltip
+fn sources_details(sources: Vec<AnalysisSource>) -> AnyView {
+    if sources.is_empty() {
+        return ().into_any();
+    }
+    let rows = sources.into_iter().map(|source| {
+        let short_hash = source.sha256.chars().take(12).collect::<String>();
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+                <td class="py-1 pr-4 font-medium text-gray-500 dark:text-gray-400 whitespace-nowrap">{source.role}</td>
+                <td class="py-1 pr-4 font-mono text-gray-800 dark:text-gray-100 break-all">{source.path}</td>
+                <td class="py-1 pr-4 font-mono text-gray-700 dark:text-gray-300">{source.parser.unwrap_or_else(|| "—".to_string())}</td>
+                <td class="py-1 pr-4 text-right text-gray-700 dark:text-gray-300 whitespace-nowrap">{format_size(source.size)}</td>
+                <td class="py-1 font-mono text-gray-700 dark:text-gray-300" title=source.sha256>{short_hash}</td>
+            </tr>
+        }
+    }).collect_view();
+    view! {
+        <div class="px-4 pb-4">
+            <div class="text-sm font-semibold text-gray-900 dark:text-white mb-1">"Sources"</div>
+            <table class="w-full text-xs">
+                <thead>
+                    <tr class="text-left font-semibold text-gray-500 dark:text-gray-400">
+                        <th class="pb-1 pr-4">"Role"</th>
+                        <th class="pb-1 pr-4">"File"</th>
+                        <th class="pb-1 pr-4">"Parser"</th>
+                        <th class="pb-1 pr-4 text-right">"Size"</th>
+                        <th class="pb-1">"SHA-256"</th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+#[component]
+pub fn DebugTab(
+    log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
+    fail_to_pass_tests: RwSignal<Vec<String>>,
+    pass_to_pass_tests: RwSignal<Vec<String>>,
+) -> impl IntoView {
+    view! {
+        <div class="h-full overflow-auto">
+            {move || match log_analysis_result.get() {
+                Some(analysis) => {
+                    let parse_warnings = analysis.debug_info.parse_warnings.clone();
+                    let browser_runs = analysis.debug_info.browser_runs.clone();
+                    let test_binaries = analysis.debug_info.test_binaries.clone();
+                    let match_rates = analysis.match_rates.clone();
+                    let sources = analysis.sources.clone();
+                    view! {
+                        {debug_details(analysis.debug_info)}
+                        {match_rates_details(match_rates)}
+                        {parse_warnings_details(parse_warnings)}
+                        {browser_runs_details(browser_runs)}
+                        {test_binaries_details(test_binaries)}
+                        {analysis.settings.map(settings_details)}
+                        {sources_details(sources)}
+                    }.into_any()
+                }
+                None => view! {
+                    <div class="p-4 text-center text-gray-500 dark:text-gray-400">
+                        "Log analysis has not finished yet"
+                    </div>
+                }.into_any(),
+            }}
+            <ScratchParser fail_to_pass_tests=fail_to_pass_tests pass_to_pass_tests=pass_to_pass_tests/>
+        </div>
+    }
+}
@@ -3,26 +3,78 @@ use leptos::prelude::Effect;
 use std::collections::HashMap;
 
 use super::types::*;
-use super::processing::handle_submit;
+use super::processing::{handle_check_downloads, handle_submit, handle_refresh, handle_resolve_drive_link, handle_resolve_platform_link};
 use super::file_operations::load_file_contents;
-use super::test_lists::load_test_lists;
+use super::test_lists::{load_test_lists, reload_test_lists};
 use super::search_results::search_for_test;
 use super::deliverable_checker_interface::DeliverableCheckerInterface;
+use super::structure_checklist::StructureChecklist;
+use super::analysis_phases::{refresh_analysis_progress, watch_analysis_progress, AnalysisPhases};
+use super::download_progress::DownloadProgressBars;
+use super::review_queue::ReviewQueuePanel;
+use super::session_state::{clear_session, handle_resume_review_session, handle_save_review_session, load_session, save_session, show_session_url, ReviewSession};
 use leptos::Params;
 use leptos_router::params::Params;
 use leptos_router::hooks::use_params;
+use leptos_router::hooks::use_query_map;
 use leptos_router::hooks::use_navigate;
 
 use leptos::task::spawn_local;
 
-#[derive(Params, PartialEq)]
+// How long the review must stay unchanged before its snapshot is saved on the server
+const SESSION_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(1500);
+
+#[cfg(feature = "hydrate")]
+const AUTO_ANALYZE_STORAGE_KEY: &str = "auto_analyze";
+
+#[derive(PartialEq)]
 struct DeliverableCheckerParams {
     deliverable_id: Option<String>,
+    session_id: Option<String>,
+}
+impl Params for DeliverableCheckerParams {
+    fn from_map(map: &leptos_router::params::ParamsMap) -> Result<Self, leptos_router::params::ParamsError> {
+        use leptos_router::params::IntoParam;
+        Ok(Self {
+            deliverable_id: IntoParam::into_param(map.get_str("deliverable_id"), "deliverable_id")?,
+            session_id: IntoParam::into_param(map.get_str("session_id"), "session_id")?,
+        })
+    }
 }
 #[server]
-pub async fn handle_analyze_logs(file_paths: Vec<String>) -> Result<LogAnalysisResult, ServerFnError> {
-    use crate::api::log_analysis::{analyze_logs};
-    Ok(analyze_logs(file_paths).unwrap())
+pub async fn handle_analyze_logs(
+    file_paths: Vec<String>,
+    deliverable_link: String,
+    instance_id: String,
+    agent_log: Option<String>,
+    custom_test_lists: Option<CustomTestLists>,
+    progress_id: Option<String>,
+) -> Result<LogAnalysisResult, ApiError> {
+    use crate::api::analysis_progress::{ProgressReporter, PHASE_FINISH};
+    use crate::api::log_analysis::{analyze_logs_with_progress};
+    use crate::api::analysis_history::record_analysis_snapshot;
+    use crate::api::analysis_store::save_analysis_result;
+    use crate::api::swebench_dataset::cross_check_with_dataset;
+    use crate::api::webhook::notify_analysis_completed;
+    use crate::api::path_guard::check_deliverable_paths;
+    check_deliverable_paths(&file_paths)?;
+    let progress = ProgressReporter::start(progress_id);
+    let mut analysis = analyze_logs_with_progress(file_paths.clone(), agent_log, custom_test_lists, &progress)
+        .map_err(|e| {
+            progress.fail();
+            ApiError::new(ApiErrorCode::ParseFailure, "Failed to analyze logs").with_details(e)
+        })?;
+    progress.begin(PHASE_FINISH);
+    cross_check_with_dataset(&file_paths, &mut analysis).await;
+    if let Err(e) = save_analysis_result(&file_paths, &analysis) {
+        eprintln!("Failed to persist analysis result: {}", e);
+    }
+    if let Err(e) = record_analysis_snapshot(&file_paths, &analysis) {
+        eprintln!("Failed to record analysis snapshot: {}", e);
+    }
+    notify_analysis_completed(&deliverable_link, &instance_id, &analysis);
+    progress.done(PHASE_FINISH);
+    Ok(analysis)
 }
 
 #[component]
@@ -33,13 +85,20 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
             .read()
             .as_ref()
             .ok()
-            .and_then(|params| if let Some(deliverable_id) = &params.deliverable_id {
-                Some(format!("https://drive.google.com/drive/folders/{}", deliverable_id))
-            } else {
-                None
-            })
+            .and_then(|params| params.deliverable_id.as_ref().map(|deliverable_id| format!("https://drive.google.com/drive/folders/{}", deliverable_id)))
             .unwrap_or_default();
             leptos::logging::log!("Deliverable ID: {}", deliverable_id);
+    // Set when the page was opened as /review/{session_id}
+    let route_session_id = params
+        .read_untracked()
+        .as_ref()
+        .ok()
+        .and_then(|params| params.session_id.clone());
+    // Set when the deliverable was opened through a reviewer-platform link
+    let platform_task_id = StoredValue::new(use_query_map()
+        .read_untracked()
+        .get("task")
+        .unwrap_or_default());
     let initial_deliverable_link = RwSignal::new(deliverable_id.clone());
     let deliverable_link = RwSignal::new(deliverable_id);
     let is_processing = RwSignal::new(false);
@@ -47,13 +106,39 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
     let stages = RwSignal::new(HashMap::from([
         (ProcessingStage::Validating, StageStatus::Pending),
         (ProcessingStage::Downloading, StageStatus::Pending),
+        (ProcessingStage::Extracting, StageStatus::Pending),
         (ProcessingStage::LoadingTests, StageStatus::Pending),
+        (ProcessingStage::Analyzing, StageStatus::Pending),
     ]));
+    // Run the analysis as part of loading, before the checker opens; the reviewer can turn it off
+    let auto_analyze = RwSignal::new(true);
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(win) = web_sys::window() {
+            if let Ok(Some(local_storage)) = win.local_storage() {
+                if let Ok(Some(value)) = local_storage.get_item(AUTO_ANALYZE_STORAGE_KEY) {
+                    auto_analyze.set(value != "off");
+                }
+            }
+        }
+    }
     let result = RwSignal::new(None::<ProcessingResult>);
-    let error = RwSignal::new(None::<String>);
+    let error = RwSignal::new(None::<ApiError>);
+    let structure = RwSignal::new(Vec::<StructureCheckItem>::new());
+    // Whether the last download was the logs-only fallback, so it isn't offered again
+    let logs_only_attempted = RwSignal::new(false);
 
     let log_analysis_result = RwSignal::new(None::<LogAnalysisResult>);
     let log_analysis_loading = RwSignal::new(false);
+    // Server-side session of the review as (deliverable link, session id), the revision of its
+    // latest snapshot, and the server save waiting for the reviewer to pause
+    let review_session = RwSignal::new(None::<(String, String)>);
+    let session_revision = RwSignal::new(0u64);
+    let pending_session_save = StoredValue::new(None::<TimeoutHandle>);
+    // Stored analysis handed back with a resumed session, used instead of analyzing again
+    let resumed_analysis = RwSignal::new(None::<LogAnalysisResult>);
+    // Agent log picked in the Report tab; None analyzes the default run
+    let selected_agent_log = RwSignal::new(None::<String>);
     
     let active_tab = RwSignal::new("base".to_string());
     let active_main_tab = RwSignal::new("manual_checker".to_string());
@@ -63,6 +148,7 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
     
     let fail_to_pass_tests = RwSignal::new(Vec::<String>::new());
     let pass_to_pass_tests = RwSignal::new(Vec::<String>::new());
+    let test_list_provenance = RwSignal::new(None::<TestListProvenance>);
     let selected_fail_to_pass_index = RwSignal::new(0usize);
     let selected_pass_to_pass_index = RwSignal::new(0usize);
     let current_selection = RwSignal::new("fail_to_pass".to_string());
@@ -74,14 +160,37 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
         base_results: Vec::new(),
         before_results: Vec::new(),
         after_results: Vec::new(),
+        agent_results: Vec::new(),
     });
     let search_result_indices = RwSignal::new(HashMap::from([
         ("base".to_string(), 0usize),
         ("before".to_string(), 0usize),
         ("after".to_string(), 0usize),
+        ("agent".to_string(), 0usize),
     ]));
+    // Log columns the reviewer ticked in the search UI, and the test they were last searched for;
+    // `searched_text` marks a query typed in the search box rather than a selected test
+    let search_stages = RwSignal::new(default_search_stages());
+    let searched_test = RwSignal::new(String::new());
+    let searched_text = RwSignal::new(false);
+    // Lines of context around each hit the reviewer picked; `None` keeps the language's default
+    let search_context = RwSignal::new(None::<usize>);
+    // Search build/install output the noise filter hides from the analysis
+    let search_include_noise = RwSignal::new(false);
+    // How the query is matched, and why the last search didn't run (an invalid regex)
+    let search_mode = RwSignal::new(SearchMode::default());
+    let search_error = RwSignal::new(None::<String>);
+    // Log column a rule-example deep link asked to scroll to its first hit
+    let focused_stage = RwSignal::new(None::<String>);
+    // Phases of the running analysis, or of the last one if it failed
+    let analysis_progress = RwSignal::new(None::<AnalysisProgress>);
+    let download_progress = RwSignal::new(None::<DownloadProgress>);
     
     let report_selected_test_name = RwSignal::new(String::new());
+    let is_refreshing = RwSignal::new(false);
+    // Reviewer-supplied test lists applied over main.json's
+    let custom_test_lists = RwSignal::new(None::<CustomTestLists>);
+    let refresh_error = RwSignal::new(None::<String>);
 
     let _update_stage_status = move |stage: ProcessingStage, status: StageStatus| {
         stages.update(|stages| {
@@ -92,20 +201,32 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
     let trigger_log_analysis_fn = move || {
             if let Some(processing_result) = result.get() {
                 let file_paths = processing_result.file_paths.clone();
+                let deliverable_link = processing_result.deliverable_link.clone();
+                let instance_id = processing_result.instance_id.clone();
+                let agent_log = selected_agent_log.get_untracked();
+                let custom_lists = custom_test_lists.get_untracked();
                 leptos::logging::log!("Starting log analysis for Rust with {} files", file_paths.len());
                 
                 log_analysis_loading.set(true);
                 log_analysis_result.set(None);
+                let progress_id = uuid::Uuid::new_v4().to_string();
+                let progress_poll = watch_analysis_progress(progress_id.clone(), analysis_progress);
                 
                 spawn_local(async move {
                     leptos::logging::log!("Calling analyze_logs API endpoint...");
-                    let resp = handle_analyze_logs(file_paths).await;
+                    let resp = handle_analyze_logs(file_paths, deliverable_link, instance_id, agent_log, custom_lists, Some(progress_id.clone())).await;
+                    if let Some(handle) = progress_poll {
+                        handle.clear();
+                    }
                     match resp {
                         Ok(analysis_result) => {
+                            analysis_progress.set(None);
                             log_analysis_result.set(Some(analysis_result));
                         },
                         Err(e) => {
                             leptos::logging::log!("Failed to parse log analysis response: {:?}", e);
+                            // Keep the phases on screen with the one that failed
+                            refresh_analysis_progress(progress_id, analysis_progress).await;
                             log_analysis_result.set(None);
                         }
                     }
@@ -116,15 +237,57 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
             }
     };
     
-    let search_for_test_fn = move |test_name: String| {
-        search_for_test(result, test_name, search_results, search_result_indices);
+    // Re-run the analysis when the reviewer switches agent runs
+    Effect::new(move |prev: Option<Option<String>>| {
+        let agent_log = selected_agent_log.get();
+        // A restored session runs the analysis itself once its lists are back
+        if prev.is_some_and(|prev| prev != agent_log) && result.get_untracked().is_some() && !is_refreshing.get_untracked() {
+            untrack(trigger_log_analysis_fn);
+        }
+        agent_log
+    });
+
+    let run_search = move |query: String, typed: bool| {
+        searched_test.set(query.clone());
+        searched_text.set(typed);
+        // A selected test is always looked up by name; the picked mode is for typed queries
+        let mode = if typed { search_mode.get_untracked() } else { SearchMode::TestName };
+        search_for_test(result, query, mode, search_stages.get_untracked(), selected_agent_log.get_untracked(), search_context.get_untracked(), search_include_noise.get_untracked(), search_results, search_result_indices, search_error);
     };
+    let search_for_test_fn = move |test_name: String| run_search(test_name, false);
+    let search_query_fn = move |query: String| run_search(query, true);
+
+    // Fill newly ticked columns (or the newly picked agent run, context size, search mode or noise
+    // setting) for the search on screen
+    Effect::new(move |prev: Option<(Vec<String>, Option<String>, Option<usize>, SearchMode, bool)>| {
+        let current = (search_stages.get(), selected_agent_log.get(), search_context.get(), search_mode.get(), search_include_noise.get());
+        let query = searched_test.get_untracked();
+        if prev.is_some_and(|prev| prev != current) && !query.is_empty() {
+            untrack(|| run_search(query, searched_text.get_untracked()));
+        }
+        current
+    });
     
     let load_test_lists_fn = move || {
-        load_test_lists(result, fail_to_pass_tests, pass_to_pass_tests, current_selection, search_for_test_fn, trigger_log_analysis_fn, is_processing, current_stage, stages);
+        load_test_lists(result, fail_to_pass_tests, pass_to_pass_tests, test_list_provenance, current_selection, search_for_test_fn, trigger_log_analysis_fn, custom_test_lists.get_untracked(), is_processing, current_stage, stages, auto_analyze.get_untracked());
     };
 
-    let handle_submit_fn = move || {
+    // Close the loading stages once the automatic analysis is back
+    Effect::new(move |_| {
+        if current_stage.get() != Some(ProcessingStage::Analyzing) || log_analysis_loading.get() {
+            return;
+        }
+        let status = if log_analysis_result.with_untracked(|analysis| analysis.is_some()) { StageStatus::Completed } else { StageStatus::Error };
+        stages.update(|stages| {
+            stages.insert(ProcessingStage::Analyzing, status);
+        });
+        current_stage.set(None);
+        is_processing.set(false);
+    });
+
+    // `logs_only` skips everything but main.json and the logs, for deliverables over the size limits
+    let handle_submit_fn = move |logs_only: bool| {
+        logs_only_attempted.set(logs_only);
         handle_submit(
             deliverable_link,
             is_processing,
@@ -132,17 +295,68 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
             stages,
             result,
             error,
+            structure,
+            download_progress,
+            platform_task_id.get_value(),
+            logs_only,
             load_test_lists_fn,
         );
     };
 
-    let manual_submit_fn = move |_| {
+    // Re-fetch the same deliverable without the reset: selection, filters and tabs stay as they are
+    let refresh_deliverable_fn = move || {
+        handle_refresh(result, is_refreshing, refresh_error, move || {
+            file_contents.set(FileContents::default());
+            loaded_file_types.set(LoadedFileTypes::default());
+            let mut reload = vec!["main_json".to_string()];
+            if active_main_tab.get_untracked() == "input" {
+                reload.push(active_tab.get_untracked());
+            }
+            load_file_contents(result, file_contents, loading_files, loaded_file_types, Some(reload));
+            reload_test_lists(
+                result,
+                fail_to_pass_tests,
+                pass_to_pass_tests,
+                test_list_provenance,
+                current_selection,
+                selected_fail_to_pass_index,
+                selected_pass_to_pass_index,
+                search_for_test_fn,
+                trigger_log_analysis_fn,
+                custom_test_lists.get_untracked(),
+                is_refreshing,
+            );
+        });
+    };
+
+    // Rebuild the test lists and the analysis after the reviewer's lists change
+    let apply_custom_test_lists_fn = move || {
+        is_refreshing.set(true);
+        reload_test_lists(
+            result,
+            fail_to_pass_tests,
+            pass_to_pass_tests,
+            test_list_provenance,
+            current_selection,
+            selected_fail_to_pass_index,
+            selected_pass_to_pass_index,
+            search_for_test_fn,
+            trigger_log_analysis_fn,
+            custom_test_lists.get_untracked(),
+            is_refreshing,
+        );
+    };
+
+    let submit_link_fn = move || {
         let link = deliverable_link.get().trim().to_string();
         if link.is_empty() {
             return;
         }
 
-        if link.contains("drive.google.com/drive/folders/") {
+        if ActionsRun::parse(&link).is_some() {
+            // GitHub Actions runs have no Drive folder route; they're processed in place
+            handle_submit_fn(false);
+        } else if link.contains("drive.google.com/drive/folders/") {
             let folder_id = link.split("folders/").nth(1)
                 .and_then(|s| s.split(|c| c == '/' || c == '?').next())
                 .unwrap_or("")
@@ -160,13 +374,55 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
                 navigate_fn(&format!("/{}", folder_id), Default::default());
                 is_processing.set(false);
             } else {
-                error.set(Some("Invalid folder ID extracted from link".to_string()));
+                error.set(Some(ApiError::new(ApiErrorCode::InvalidLink, "Invalid folder ID extracted from link")));
             }
+        } else if link.contains("drive.google.com") || link.contains("docs.google.com") {
+            // open?id=, file and shortcut links; the server finds the folder they belong to
+            let navigate_fn = use_navigate();
+            is_processing.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match handle_resolve_drive_link(link).await {
+                    Ok(folder_id) => {
+                        leptos::logging::log!("Resolved Drive link to folder {}", folder_id);
+                        initial_deliverable_link.set(format!("https://drive.google.com/drive/folders/{}", folder_id));
+                        navigate_fn(&format!("/{}", folder_id), Default::default());
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+                is_processing.set(false);
+            });
+        } else if link.starts_with("http://") || link.starts_with("https://") {
+            // Possibly a reviewer-platform task link; the server maps it to its Drive folder
+            let navigate_fn = use_navigate();
+            is_processing.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match handle_resolve_platform_link(link).await {
+                    Ok(task) => {
+                        leptos::logging::log!("Resolved platform task {} to folder {}", task.task_id, task.folder_id);
+                        navigate_fn(
+                            &format!("/{}?task={}", task.folder_id, urlencoding::encode(&task.task_id)),
+                            Default::default(),
+                        );
+                    }
+                    Err(e) => error.set(Some(ApiError::new(ApiErrorCode::InvalidLink, e.to_string()))),
+                }
+                is_processing.set(false);
+            });
         } else {
-            error.set(Some("Please enter a valid Google Drive folder link (https://drive.google.com/drive/folders/...".to_string()));
+            error.set(Some(ApiError::new(ApiErrorCode::InvalidLink, "Please enter a Google Drive folder or file link (https://drive.google.com/...)")));
         }
     };
 
+    let manual_submit_fn = move |_| submit_link_fn();
+
+    // Queue items load through the same path as a pasted link
+    let open_queued_link_fn = move |link: String| {
+        deliverable_link.set(link);
+        submit_link_fn();
+    };
+
     let reset_state = move || {
         deliverable_link.set(String::new());
         is_processing.set(false);
@@ -174,10 +430,14 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
         stages.set(HashMap::from([
             (ProcessingStage::Validating, StageStatus::Pending),
             (ProcessingStage::Downloading, StageStatus::Pending),
+            (ProcessingStage::Extracting, StageStatus::Pending),
             (ProcessingStage::LoadingTests, StageStatus::Pending),
+            (ProcessingStage::Analyzing, StageStatus::Pending),
         ]));
         result.set(None);
         error.set(None);
+        structure.set(Vec::new());
+        analysis_progress.set(None);
         
         active_tab.set("base".to_string());
         active_main_tab.set("manual_checker".to_string());
@@ -186,6 +446,7 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
         loaded_file_types.set(LoadedFileTypes::default());
         fail_to_pass_tests.set(Vec::new());
         pass_to_pass_tests.set(Vec::new());
+        test_list_provenance.set(None);
         selected_fail_to_pass_index.set(0);
         selected_pass_to_pass_index.set(0);
         current_selection.set("fail_to_pass".to_string());
@@ -195,17 +456,163 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
             base_results: Vec::new(),
             before_results: Vec::new(),
             after_results: Vec::new(),
+            agent_results: Vec::new(),
         });
         search_result_indices.set(HashMap::from([
             ("base".to_string(), 0usize),
             ("before".to_string(), 0usize),
             ("after".to_string(), 0usize),
+            ("agent".to_string(), 0usize),
         ]));
+        searched_test.set(String::new());
         log_analysis_result.set(None);
         log_analysis_loading.set(false);
+        selected_agent_log.set(None);
         report_selected_test_name.set(String::new());
+        is_refreshing.set(false);
+        refresh_error.set(None);
+        custom_test_lists.set(None);
+        review_session.set(None);
+        session_revision.set(0);
+        resumed_analysis.set(None);
+        clear_session();
+    };
+
+    let analyze_or_resume_fn = move || {
+        if let Some(analysis) = resumed_analysis.get_untracked() {
+            resumed_analysis.set(None);
+            log_analysis_result.set(Some(analysis));
+        } else {
+            trigger_log_analysis_fn();
+        }
+    };
+
+    // Put the checker back in a saved state; download again only when the server lost the files
+    let restore_session_fn = move |session: ReviewSession| {
+        let link = session.deliverable_link.clone();
+        // Later snapshots must outrank the one being restored
+        session_revision.set(session.revision);
+        is_processing.set(true);
+        spawn_local(async move {
+            let files_present = handle_check_downloads(session.file_paths.clone()).await.unwrap_or(false);
+            is_processing.set(false);
+            if !files_present {
+                clear_session();
+                resumed_analysis.set(None);
+                handle_submit_fn(session.logs_only);
+                return;
+            }
+            leptos::logging::log!("Restoring review session for {}", link);
+            is_refreshing.set(true);
+            current_selection.set(session.current_selection.clone());
+            selected_fail_to_pass_index.set(session.selected_fail_to_pass_index);
+            selected_pass_to_pass_index.set(session.selected_pass_to_pass_index);
+            active_main_tab.set(session.active_main_tab.clone());
+            active_tab.set(session.active_tab.clone());
+            selected_agent_log.set(session.selected_agent_log.clone());
+            report_selected_test_name.set(session.report_selected_test_name.clone());
+            search_stages.set(session.search_stages.clone());
+            custom_test_lists.set(session.custom_test_lists.clone());
+            result.set(Some(session.processing_result()));
+            reload_test_lists(
+                result,
+                fail_to_pass_tests,
+                pass_to_pass_tests,
+                test_list_provenance,
+                current_selection,
+                selected_fail_to_pass_index,
+                selected_pass_to_pass_index,
+                search_for_test_fn,
+                analyze_or_resume_fn,
+                session.custom_test_lists,
+                is_refreshing,
+            );
+        });
+    };
+
+    // Pick a reloaded tab up where the reviewer left it
+    let restore_or_submit_fn = move |link: String| {
+        match load_session(&link) {
+            Some(session) => restore_session_fn(session),
+            None => handle_submit_fn(false),
+        }
     };
 
+    // Reopen a server-side session from /review/{session_id}
+    if let Some(session_id) = route_session_id {
+        Effect::new(move |_| {
+            let session_id = session_id.clone();
+            is_processing.set(true);
+            spawn_local(async move {
+                match handle_resume_review_session(session_id.clone()).await {
+                    Ok(resumed) => {
+                        leptos::logging::log!("Resuming review session {}", session_id);
+                        deliverable_link.set(resumed.session.deliverable_link.clone());
+                        review_session.set(Some((resumed.session.deliverable_link.clone(), session_id)));
+                        resumed_analysis.set(resumed.analysis);
+                        restore_session_fn(resumed.session);
+                    }
+                    Err(e) => {
+                        is_processing.set(false);
+                        error.set(Some(e));
+                    }
+                }
+            });
+        });
+    }
+
+    // Snapshot the review after every change so an accidental reload can restore it
+    Effect::new(move |_| {
+        let Some(current) = result.get() else { return };
+        if is_processing.get() {
+            return;
+        }
+        let session = ReviewSession {
+            deliverable_link: current.deliverable_link.clone(),
+            file_paths: current.file_paths,
+            platform_task_id: current.platform_task_id,
+            current_selection: current_selection.get(),
+            selected_fail_to_pass_index: selected_fail_to_pass_index.get(),
+            selected_pass_to_pass_index: selected_pass_to_pass_index.get(),
+            active_main_tab: active_main_tab.get(),
+            active_tab: active_tab.get(),
+            selected_agent_log: selected_agent_log.get(),
+            report_selected_test_name: report_selected_test_name.get(),
+            search_stages: search_stages.get(),
+            custom_test_lists: custom_test_lists.get(),
+            logs_only: current.logs_only,
+            revision: session_revision.get_untracked() + 1,
+        };
+        session_revision.set(session.revision);
+        save_session(&session);
+
+        // The first snapshot of a deliverable gives the review its session id
+        let session_id = match review_session.get_untracked() {
+            Some((link, session_id)) if link == current.deliverable_link => session_id,
+            _ => {
+                let session_id = uuid::Uuid::new_v4().to_string();
+                review_session.set(Some((current.deliverable_link.clone(), session_id.clone())));
+                session_id
+            }
+        };
+        show_session_url(&session_id);
+        // Clicking through tests changes the snapshot many times a second; only the last one
+        // after a pause goes to the server
+        if let Some(pending) = pending_session_save.get_value() {
+            pending.clear();
+        }
+        let save = move || {
+            spawn_local(async move {
+                match handle_save_review_session(session_id, session).await {
+                    Ok(true) => {}
+                    Ok(false) => leptos::logging::log!("A newer snapshot of the review session is already saved"),
+                    Err(e) => leptos::logging::log!("Failed to save the review session: {:?}", e),
+                }
+            });
+        };
+        pending_session_save.set_value(set_timeout_with_handle(save, SESSION_SAVE_DELAY).ok());
+    });
+
     Effect::new(move |_| {
         let link = deliverable_link.get();
         let initial_link = initial_deliverable_link.get();
@@ -216,7 +623,7 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
             && deliverable_link.get().starts_with("https://drive.google.com/drive/folders/") {
             leptos::logging::log!("Auto-submitting for deliverable from route: {}", link);
             initial_deliverable_link.set(String::new());
-            handle_submit_fn();
+            restore_or_submit_fn(link);
         }
     });
 
@@ -309,7 +716,7 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
                                         on:input=move |ev| {
                                             deliverable_link.set(event_target_value(&ev))
                                         }
-                                        placeholder="Enter Google Drive folder link"
+                                        placeholder="Enter Google Drive folder or reviewer-platform link"
                                         class="w-full px-4 py-2 text-md border-2 border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-white placeholder-gray-500 dark:placeholder-gray-400 focus:outline-none focus:border-blue-500 dark:focus:border-blue-400 transition-colors"
                                         disabled=move || is_processing.get()
                                     />
@@ -329,6 +736,27 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
                                 </button>
                             </div>
 
+                            <label class="mt-3 flex items-center justify-center gap-2 text-sm text-gray-600 dark:text-gray-300 cursor-pointer">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || auto_analyze.get()
+                                    on:change=move |_| {
+                                        let enabled = !auto_analyze.get_untracked();
+                                        auto_analyze.set(enabled);
+                                        #[cfg(feature = "hydrate")]
+                                        {
+                                            if let Some(win) = web_sys::window() {
+                                                if let Ok(Some(local_storage)) = win.local_storage() {
+                                                    let _ = local_storage.set_item(AUTO_ANALYZE_STORAGE_KEY, if enabled { "on" } else { "off" });
+                                                }
+                                            }
+                                        }
+                                    }
+                                    disabled=move || is_processing.get()
+                                />
+                                "Analyze logs automatically after download"
+                            </label>
+
                             {move || {
                                 error
                                     .get()
@@ -336,12 +764,31 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
                                         view! {
                                             <div class="flex gap-4 justify-center">
                                             <div class="w-full max-w-2xl mt-4 p-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg">
-                                                <p class="text-red-600 dark:text-red-400">{err}</p>
+                                                <p class="text-red-600 dark:text-red-400">{err.message.clone()}</p>
+                                                <p class="mt-1 text-sm text-red-700 dark:text-red-300">{err.guidance()}</p>
+                                                {err.details.clone().map(|details| view! {
+                                                    <details class="mt-2 text-xs text-red-700 dark:text-red-300">
+                                                        <summary class="cursor-pointer">"Details"</summary>
+                                                        <pre class="mt-1 whitespace-pre-wrap break-all">{details}</pre>
+                                                    </details>
+                                                })}
+                                                {(err.code == ApiErrorCode::TooLarge && !logs_only_attempted.get()).then(|| view! {
+                                                    <button
+                                                        on:click=move |_| handle_submit_fn(true)
+                                                        class="mt-3 text-sm px-3 py-1 rounded bg-red-600 text-white hover:bg-red-700"
+                                                    >
+                                                        "Download logs only"
+                                                    </button>
+                                                })}
                                             </div>
                                             </div>
                                         }
                                     }).into_any()
                             }}
+
+                            <Show when=move || !is_processing.get()>
+                                <ReviewQueuePanel open_link=open_queued_link_fn />
+                            </Show>
                         </div>
 
                         {move || {
@@ -393,6 +840,30 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
                                                 }>Downloading</span>
                                             </div>
 
+                                            <Show when=move || ActionsRun::parse(&deliverable_link.get()).is_some()>
+                                                <div class="flex items-center justify-center gap-2">
+                                                    {move || render_icon(
+                                                        ProcessingStage::Extracting,
+                                                        stages
+                                                            .get()
+                                                            .get(&ProcessingStage::Extracting)
+                                                            .cloned()
+                                                            .unwrap_or(StageStatus::Pending),
+                                                    )}
+                                                    <span class=move || {
+                                                        let status = stages
+                                                            .get()
+                                                            .get(&ProcessingStage::Extracting)
+                                                            .cloned()
+                                                            .unwrap_or(StageStatus::Pending);
+                                                        format!(
+                                                            "text-lg font-medium {}",
+                                                            get_stage_text_class(status),
+                                                        )
+                                                    }>Extracting</span>
+                                                </div>
+                                            </Show>
+
                                             <div class="flex items-center justify-center gap-2">
                                                 {render_icon(
                                                     ProcessingStage::LoadingTests,
@@ -414,7 +885,42 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
                                                     )
                                                 }>Loading tests</span>
                                             </div>
+
+                                            <Show when=move || auto_analyze.get()>
+                                                <div class="flex items-center justify-center gap-2">
+                                                    {move || render_icon(
+                                                        ProcessingStage::Analyzing,
+                                                        stages
+                                                            .get()
+                                                            .get(&ProcessingStage::Analyzing)
+                                                            .cloned()
+                                                            .unwrap_or(StageStatus::Pending),
+                                                    )}
+                                                    <span class=move || {
+                                                        let status = stages
+                                                            .get()
+                                                            .get(&ProcessingStage::Analyzing)
+                                                            .cloned()
+                                                            .unwrap_or(StageStatus::Pending);
+                                                        format!(
+                                                            "text-lg font-medium {}",
+                                                            get_stage_text_class(status),
+                                                        )
+                                                    }>"Analyzing…"</span>
+                                                </div>
+                                            </Show>
                                         </div>
+                                        <Show when=move || current_stage.get() == Some(ProcessingStage::Downloading)>
+                                            <div class="flex justify-center mt-4">
+                                                <DownloadProgressBars progress=download_progress />
+                                            </div>
+                                        </Show>
+                                        <Show when=move || current_stage.get() == Some(ProcessingStage::Analyzing)>
+                                            <div class="flex justify-center mt-4">
+                                                <AnalysisPhases progress=analysis_progress />
+                                            </div>
+                                        </Show>
+                                        <StructureChecklist structure=structure />
                                     </div>
                                 }.into_any()
                             } else {
@@ -433,6 +939,7 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
             <Show
                 when=move || {
                     result.get().is_some()
+                        && !is_processing.get()
                         && (!fail_to_pass_tests.get().is_empty()
                             || !pass_to_pass_tests.get().is_empty())
                 }
@@ -448,18 +955,34 @@ pub fn DeliverableCheckerPage(current_deliverable: RwSignal<Option<ProcessingRes
                     fail_to_pass_filter=fail_to_pass_filter
                     pass_to_pass_filter=pass_to_pass_filter
                     search_for_test=search_for_test_fn
+                    search_query=search_query_fn
                     active_tab=active_tab
                     active_main_tab=active_main_tab
                     search_results=search_results
                     search_result_indices=search_result_indices
+                    search_stages=search_stages
+                    search_context=search_context
+                    search_include_noise=search_include_noise
+                    search_mode=search_mode
+                    search_error=search_error
+                    focused_stage=focused_stage
+                    analysis_progress=analysis_progress
                     file_contents=file_contents
                     loading_files=loading_files
                     reset_state=reset_state
+                    refresh_deliverable=refresh_deliverable_fn
+                    is_refreshing=is_refreshing
+                    refresh_error=refresh_error
+                    custom_test_lists=custom_test_lists
+                    test_list_provenance=test_list_provenance
+                    apply_custom_test_lists=apply_custom_test_lists_fn
+                    rerun_analysis=trigger_log_analysis_fn
                     log_analysis_result=log_analysis_result
                     log_analysis_loading=log_analysis_loading
                     loaded_file_types=loaded_file_types
                     result=result
                     report_selected_test_name=report_selected_test_name
+                    selected_agent_log=selected_agent_log
                 />
             </Show>
         </div>
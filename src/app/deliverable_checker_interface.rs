@@ -1,13 +1,30 @@
 use leptos::prelude::*;
 use std::collections::HashMap;
 use leptos_router::hooks::use_navigate;
-use super::types::{LogSearchResults, FileContents, LogAnalysisResult};
+use super::types::{example_test_name, AnalysisProgress, CustomTestLists, LogSearchResults, FileContents, LogAnalysisResult, SearchMode, TestListProvenance, MAIN_TABS};
 use super::test_checker::TestChecker;
 use super::log_search_results::LogSearchResults as LogSearchResultsComponent;
 use super::file_viewer::FileViewer;
 use super::types::LoadedFileTypes;
 use super::test_checker::RuleViolationInfo;
 use super::report_tab::ReportTab;
+use super::coverage_panel::CoveragePanel;
+use super::run_abort_notice::RunAbortNotice;
+use super::filtered_run_notice::FilteredRunNotice;
+use super::patch_failure_notice::PatchFailureNotice;
+use super::environment_drift_notice::EnvironmentDriftNotice;
+use super::xpass_notice::XPassNotice;
+use super::timing_panel::TimingPanel;
+use super::duplicates_panel::DuplicatesPanel;
+use super::debug_tab::DebugTab;
+use super::agent_log_tab::AgentLogTab;
+use super::analysis_tab::AnalysisTab;
+use super::patches_tab::PatchesTab;
+use super::copy_analysis::CopyAnalysisButton;
+use super::custom_test_lists::CustomTestListsPanel;
+use super::test_list_provenance::TestListProvenancePanel;
+use super::status_override::StatusOverrideEditor;
+use super::command_palette::CommandPalette;
 #[cfg(feature = "hydrate")]
 use web_sys;
 #[cfg(feature = "hydrate")]
@@ -23,24 +40,54 @@ pub fn DeliverableCheckerInterface(
     fail_to_pass_filter: RwSignal<String>,
     pass_to_pass_filter: RwSignal<String>,
     search_for_test: impl Fn(String) + Send + Sync + 'static + Copy,
+    // A query typed in the log search box, matched in `search_mode`
+    search_query: impl Fn(String) + Send + Sync + 'static + Copy,
     active_tab: RwSignal<String>,
     active_main_tab: RwSignal<String>,
     search_results: RwSignal<LogSearchResults>,
     search_result_indices: RwSignal<HashMap<String, usize>>,
+    search_stages: RwSignal<Vec<String>>,
+    search_context: RwSignal<Option<usize>>,
+    search_include_noise: RwSignal<bool>,
+    search_mode: RwSignal<SearchMode>,
+    search_error: RwSignal<Option<String>>,
+    focused_stage: RwSignal<Option<String>>,
+    analysis_progress: RwSignal<Option<AnalysisProgress>>,
     file_contents: RwSignal<FileContents>,
     loading_files: RwSignal<bool>,
     reset_state: impl Fn() + Send + Sync + 'static + Copy,
+    refresh_deliverable: impl Fn() + Send + Sync + 'static + Copy,
+    is_refreshing: RwSignal<bool>,
+    refresh_error: RwSignal<Option<String>>,
+    custom_test_lists: RwSignal<Option<CustomTestLists>>,
+    test_list_provenance: RwSignal<Option<TestListProvenance>>,
+    apply_custom_test_lists: impl Fn() + Send + Sync + 'static + Copy,
+    rerun_analysis: impl Fn() + Send + Sync + 'static + Copy,
     log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
     log_analysis_loading: RwSignal<bool>,
     loaded_file_types: RwSignal<LoadedFileTypes>,
     result: RwSignal<Option<super::types::ProcessingResult>>,
     report_selected_test_name: RwSignal<String>,
+    selected_agent_log: RwSignal<Option<String>>,
 ) -> impl IntoView {
     let navigate_fn = use_navigate();
+    // Log line the input viewer jumps to when opened from a link
+    let highlight_line = RwSignal::new(None::<usize>);
     let manual_tab_active = move || active_main_tab.get() == "manual_checker";
     let playground_tab_active = move || active_main_tab.get() == "playground";
     let input_tab_active = move || active_main_tab.get() == "input";
     let report_tab_active = move || active_main_tab.get() == "report";
+    let debug_tab_active = move || active_main_tab.get() == "debug";
+    let agent_log_tab_active = move || active_main_tab.get() == "agent_log";
+    let analysis_tab_active = move || active_main_tab.get() == "analysis";
+    let patches_tab_active = move || active_main_tab.get() == "patches";
+    let selected_test = Signal::derive(move || {
+        if current_selection.get() == "fail_to_pass" {
+            fail_to_pass_tests.get().get(selected_fail_to_pass_index.get()).cloned()
+        } else {
+            pass_to_pass_tests.get().get(selected_pass_to_pass_index.get()).cloned()
+        }
+    });
     let get_selected_test_violations = move || -> Vec<RuleViolationInfo> {
         let analysis = log_analysis_result.get();
         if let Some(analysis) = analysis {
@@ -110,8 +157,7 @@ pub fn DeliverableCheckerInterface(
                 
                 if rule_checks.c6_test_marked_failed_in_report_but_passing_in_agent.has_problem {
                     if rule_checks.c6_test_marked_failed_in_report_but_passing_in_agent.examples.iter().any(|example| {
-                        let clean_example = example.split(" (").next().unwrap_or(example);
-                        *clean_example == test_name
+                        example_test_name(example) == test_name
                     }) {
                         violated_rules.push(RuleViolationInfo {
                             rule_name: "c6_test_marked_failed_in_report_but_passing_in_agent".to_string(),
@@ -125,12 +171,7 @@ pub fn DeliverableCheckerInterface(
                     let matches = rule_checks.c7_f2p_tests_in_golden_source_diff.examples.iter()
                         .any(|example| {
                             // C7 examples have format: "test_name (found as 'function_name' in file but not in test diffs)"
-                            // Extract the test name before the first " (" to get exact match
-                            if let Some(test_part) = example.split(" (").next() {
-                                test_part == test_name
-                            } else {
-                                example == &test_name
-                            }
+                            example_test_name(example) == test_name
                         });
                     if matches {
                         violated_rules.push(RuleViolationInfo {
@@ -141,6 +182,36 @@ pub fn DeliverableCheckerInterface(
                     }
                 }
                 
+                if rule_checks.c8_test_lists_differ_from_dataset.has_problem {
+                    let matches = rule_checks.c8_test_lists_differ_from_dataset.examples.iter()
+                        .any(|example| {
+                            // C8 examples have format: "test_name (in dataset FAIL_TO_PASS but missing from deliverable F2P)"
+                            example_test_name(example) == test_name
+                        });
+                    if matches {
+                        violated_rules.push(RuleViolationInfo {
+                            rule_name: "c8_test_lists_differ_from_dataset".to_string(),
+                            description: "Test lists differ from the official SWE-bench dataset instance".to_string(),
+                            examples: rule_checks.c8_test_lists_differ_from_dataset.examples.clone(),
+                        });
+                    }
+                }
+                
+                if test_type == "fail_to_pass" && rule_checks.c11_f2p_skipped_in_after_or_agent.has_problem {
+                    let matches = rule_checks.c11_f2p_skipped_in_after_or_agent.examples.iter()
+                        .any(|example| {
+                            // C11 examples have format: "test_name (skipped in after)"
+                            example_test_name(example) == test_name
+                        });
+                    if matches {
+                        violated_rules.push(RuleViolationInfo {
+                            rule_name: "c11_f2p_skipped_in_after_or_agent".to_string(),
+                            description: "Fail-to-pass tests skipped in after or agent log, so the fix was never exercised".to_string(),
+                            examples: rule_checks.c11_f2p_skipped_in_after_or_agent.examples.clone(),
+                        });
+                    }
+                }
+                
                 violated_rules
             } else {
                 Vec::new()
@@ -163,6 +234,7 @@ pub fn DeliverableCheckerInterface(
                         loading_files=loading_files
                         loaded_file_types=loaded_file_types
                         result=result
+                        highlight_line=highlight_line
                     />
                 }.into_any()
             } else if report_tab_active() {
@@ -174,8 +246,43 @@ pub fn DeliverableCheckerInterface(
                         loaded_file_types=loaded_file_types
                         log_analysis_result=log_analysis_result
                         selected_test_name=report_selected_test_name
+                        selected_agent_log=selected_agent_log
+                    />
+                }.into_any()
+            } else if agent_log_tab_active() {
+                view! {
+                    <AgentLogTab
+                        file_contents=file_contents
+                        loading_files=loading_files
+                        loaded_file_types=loaded_file_types
+                        result=result
+                        selected_agent_log=selected_agent_log
                     />
                 }.into_any()
+            } else if analysis_tab_active() {
+                view! {
+                    <AnalysisTab
+                        log_analysis_result=log_analysis_result
+                        log_analysis_loading=log_analysis_loading
+                        fail_to_pass_tests=fail_to_pass_tests
+                        pass_to_pass_tests=pass_to_pass_tests
+                        current_selection=current_selection
+                        selected_fail_to_pass_index=selected_fail_to_pass_index
+                        selected_pass_to_pass_index=selected_pass_to_pass_index
+                        active_main_tab=active_main_tab
+                        search_for_test=search_for_test
+                        search_results=search_results
+                        search_stages=search_stages
+                        focused_stage=focused_stage
+                        analysis_progress=analysis_progress
+                        run_analysis=rerun_analysis
+                        result=result
+                    />
+                }.into_any()
+            } else if patches_tab_active() {
+                view! { <PatchesTab result=result/> }.into_any()
+            } else if debug_tab_active() {
+                view! { <DebugTab log_analysis_result=log_analysis_result fail_to_pass_tests=fail_to_pass_tests pass_to_pass_tests=pass_to_pass_tests/> }.into_any()
             } else if playground_tab_active() {
                 use super::playground::Playground;
                 view! {
@@ -207,6 +314,16 @@ pub fn DeliverableCheckerInterface(
                         <LogSearchResultsComponent
                             search_results=search_results
                             search_result_indices=search_result_indices
+                            search_stages=search_stages
+                            search_context=search_context
+                            search_include_noise=search_include_noise
+                            search_mode=search_mode
+                            search_error=search_error
+                            search_query=search_query
+                            focused_stage=focused_stage
+                            result=result
+                            selected_test=selected_test
+                            agent_log=selected_agent_log
                         />
                     </>
                 }.into_any()
@@ -216,10 +333,23 @@ pub fn DeliverableCheckerInterface(
 
     view! {
         <div class="flex flex-col h-full overflow-hidden">
+            <CommandPalette
+                active_main_tab=active_main_tab
+                fail_to_pass_tests=fail_to_pass_tests
+                pass_to_pass_tests=pass_to_pass_tests
+                current_selection=current_selection
+                selected_fail_to_pass_index=selected_fail_to_pass_index
+                selected_pass_to_pass_index=selected_pass_to_pass_index
+                search_for_test=search_for_test
+                rerun_analysis=rerun_analysis
+                refresh_deliverable=refresh_deliverable
+                result=result
+            />
             <div class="flex-row flex justify-between bg-white dark:bg-gray-800 h-12 rounded-lg border border-gray-200 dark:border-gray-700 px-4 py-1 shadow-sm mb-1">
                 // Single line with back button, centered title, and copy functionality
                 <div class="flex flex-row items-center justify-between gap-4 w-full relative">
                     // Back button - now navigates to root
+                    <div class="flex items-center gap-3">
                     <button
                         on:click=move |_| {
                             reset_state();
@@ -232,90 +362,104 @@ pub fn DeliverableCheckerInterface(
                         </svg>
                         Check another
                     </button>
+                    <button
+                        on:click=move |_| refresh_deliverable()
+                        disabled=move || is_refreshing.get() || log_analysis_loading.get()
+                        title=move || refresh_error.get().unwrap_or_else(|| "Validate and download this deliverable again, keeping the current view".to_string())
+                        class=move || {
+                            if refresh_error.get().is_some() {
+                                "flex items-center gap-2 transition-colors text-sm whitespace-nowrap text-red-600 dark:text-red-400 hover:text-red-700 dark:hover:text-red-300"
+                            } else {
+                                "flex items-center gap-2 transition-colors text-sm whitespace-nowrap text-blue-600 dark:text-blue-400 hover:text-blue-700 dark:hover:text-blue-300 disabled:text-gray-400 disabled:cursor-not-allowed"
+                            }
+                        }
+                    >
+                        <svg class=move || if is_refreshing.get() { "w-4 h-4 animate-spin" } else { "w-4 h-4" } fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 4v5h.582m15.356 2A8.001 8.001 0 004.582 9m0 0H9m11 11v-5h-.581m0 0a8.003 8.003 0 01-15.357-2m15.357 2H15" />
+                        </svg>
+                        {move || if refresh_error.get().is_some() { "Refresh failed" } else { "Refresh deliverable" }}
+                    </button>
+                    {move || result.with(|deliverable| deliverable.as_ref().is_some_and(|deliverable| deliverable.logs_only)).then(|| view! {
+                        <span
+                            class="text-sm px-2 py-0.5 rounded border border-yellow-300 dark:border-yellow-700 bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200 whitespace-nowrap"
+                            title="The deliverable was over the server's size limits, so only main.json and the logs were downloaded; report.json, patches, coverage and run scripts are missing"
+                        >
+                            "Logs only"
+                        </span>
+                    })}
+                    <CoveragePanel log_analysis_result=log_analysis_result/>
+                    <RunAbortNotice log_analysis_result=log_analysis_result/>
+                    <FilteredRunNotice log_analysis_result=log_analysis_result/>
+                    <PatchFailureNotice log_analysis_result=log_analysis_result/>
+                    <EnvironmentDriftNotice log_analysis_result=log_analysis_result/>
+                    <XPassNotice log_analysis_result=log_analysis_result/>
+                    <TimingPanel log_analysis_result=log_analysis_result/>
+                    <DuplicatesPanel
+                        log_analysis_result=log_analysis_result
+                        active_main_tab=active_main_tab
+                        active_tab=active_tab
+                        highlight_line=highlight_line
+                        result=result
+                    />
+                    <CopyAnalysisButton log_analysis_result=log_analysis_result/>
+                    <CustomTestListsPanel custom_test_lists=custom_test_lists apply=apply_custom_test_lists/>
+                    <TestListProvenancePanel provenance=test_list_provenance/>
+                    </div>
 
                     // Title - Centered
                         <div class="flex justify-center absolute left-1/2 transform -translate-x-1/2">
                         <div class="flex space-x-1 bg-gray-100 dark:bg-gray-700 p-1 rounded">
-                            <button
-                                on:click=move |_| {
-                                    active_main_tab.set("manual_checker".to_string());
-                                }
-                                class=move || {
-                                    if manual_tab_active() {
-                                        "px-5 py-1 rounded font-medium text-sm transition-all duration-200 bg-white dark:bg-gray-800 text-blue-600 dark:text-blue-400 shadow-sm"
-                                            .to_string()
-                                    } else {
-                                        "px-5 py-1 rounded font-medium text-sm transition-all duration-200 text-gray-600 dark:text-gray-300 hover:text-gray-900 dark:hover:text-white hover:bg-gray-200 dark:hover:bg-gray-600"
-                                            .to_string()
-                                    }
-                                }
-                            >
-                                <div class="flex items-center gap-2">
-                                    <span>"Tests"</span>
-                                    <Show
-                                        when=move || log_analysis_loading.get()
-                                        fallback=|| view! { <div></div> }.into_any()
-                                    >
-                                        {view! {
-                                            <div class="w-4 h-4">
-                                                <svg class="animate-spin text-blue-500" fill="none" viewBox="0 0 24 24">
-                                                    <circle class="opacity-25" cx="12" cy="12" r="10" stroke="currentColor" stroke-width="4"></circle>
-                                                    <path class="opacity-75" fill="currentColor" d="M4 12a8 8 0 018-8V0C5.373 0 0 5.373 0 12h4zm2 5.291A7.962 7.962 0 014 12H0c0 3.042 1.135 5.824 3 7.938l3-2.647z"></path>
-                                                </svg>
-                                            </div>
-                                        }.into_any()}
-                                    </Show>
-                                </div>
-                            </button>
-                            <button
-                                on:click=move |_| {
-                                    active_main_tab.set("report".to_string());
-                                }
-                                class=move || {
-                                    if report_tab_active() {
-                                        "px-5 py-1 rounded font-medium text-sm transition-all duration-200 bg-white dark:bg-gray-800 text-blue-600 dark:text-blue-400 shadow-sm"
-                                            .to_string()
-                                    } else {
-                                        "px-5 py-1 rounded font-medium text-sm transition-all duration-200 text-gray-600 dark:text-gray-300 hover:text-gray-900 dark:hover:text-white hover:bg-gray-200 dark:hover:bg-gray-600"
-                                            .to_string()
-                                    }
-                                }
-                            >
-                                Report.json
-                            </button>
-                                <button
-                                    on:click=move |_| {
-                                        active_main_tab.set("playground".to_string());
-                                    }
-                                    class=move || {
-                                        if playground_tab_active() {
-                                            "px-5 py-1 rounded font-medium text-sm transition-all duration-200 bg-white dark:bg-gray-800 text-blue-600 dark:text-blue-400 shadow-sm"
-                                                .to_string()
-                                        } else {
-                                            "px-5 py-1 rounded font-medium text-sm transition-all duration-200 text-gray-600 dark:text-gray-300 hover:text-gray-900 dark:hover:text-white hover:bg-gray-200 dark:hover:bg-gray-600"
-                                                .to_string()
+                            {MAIN_TABS.iter().map(|&(key, label)| {
+                                // Tests shows a spinner while the analysis runs, Analysis a dot when a rule fails
+                                let badge = match key {
+                                    "manual_checker" => view! {
+                                        <Show
+                                            when=move || log_analysis_loading.get()
+                                            fallback=|| view! { <div></div> }.into_any()
+                                        >
+                                            {view! {
+                                                <div class="w-4 h-4">
+                                                    <svg class="animate-spin text-blue-500" fill="none" viewBox="0 0 24 24">
+                                                        <circle class="opacity-25" cx="12" cy="12" r="10" stroke="currentColor" stroke-width="4"></circle>
+                                                        <path class="opacity-75" fill="currentColor" d="M4 12a8 8 0 018-8V0C5.373 0 0 5.373 0 12h4zm2 5.291A7.962 7.962 0 014 12H0c0 3.042 1.135 5.824 3 7.938l3-2.647z"></path>
+                                                    </svg>
+                                                </div>
+                                            }.into_any()}
+                                        </Show>
+                                    }.into_any(),
+                                    "analysis" => view! {
+                                        {move || log_analysis_result.with(|analysis| {
+                                            analysis.as_ref().filter(|a| a.rule_violations.has_any_problem()).map(|_| view! {
+                                                <span class="w-2 h-2 rounded-full bg-red-500"></span>
+                                            })
+                                        })}
+                                    }.into_any(),
+                                    _ => ().into_any(),
+                                };
+                                view! {
+                                    <button
+                                        on:click=move |_| {
+                                            if key == "input" {
+                                                highlight_line.set(None);
+                                                active_tab.set("base".to_string());
+                                            }
+                                            active_main_tab.set(key.to_string());
                                         }
-                                    }
-                                >
-                                    Playground
-                                </button>
-                            <button
-                                on:click=move |_| {
-                                    active_main_tab.set("input".to_string());
-                                    active_tab.set("base".to_string());
-                                }
-                                class=move || {
-                                    if input_tab_active() {
-                                        "px-5 py-1 rounded font-medium text-sm transition-all duration-200 bg-white dark:bg-gray-800 text-blue-600 dark:text-blue-400 shadow-sm"
-                                            .to_string()
-                                    } else {
-                                        "px-5 py-1 rounded font-medium text-sm transition-all duration-200 text-gray-600 dark:text-gray-300 hover:text-gray-900 dark:hover:text-white hover:bg-gray-200 dark:hover:bg-gray-600"
-                                            .to_string()
-                                    }
+                                        class=move || {
+                                            if active_main_tab.get() == key {
+                                                "px-5 py-1 rounded font-medium text-sm transition-all duration-200 bg-white dark:bg-gray-800 text-blue-600 dark:text-blue-400 shadow-sm"
+                                            } else {
+                                                "px-5 py-1 rounded font-medium text-sm transition-all duration-200 text-gray-600 dark:text-gray-300 hover:text-gray-900 dark:hover:text-white hover:bg-gray-200 dark:hover:bg-gray-600"
+                                            }
+                                        }
+                                    >
+                                        <div class="flex items-center gap-2">
+                                            <span>{label}</span>
+                                            {badge}
+                                        </div>
+                                    </button>
                                 }
-                            >
-                                Input
-                            </button>
+                            }).collect_view()}
                         </div>
                     </div>
 
@@ -394,6 +538,12 @@ pub fn DeliverableCheckerInterface(
                                             <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 16H6a2 2 0 01-2-2V6a2 2 0 012-2h8a2 2 0 012 2v2m-6 12h8a2 2 0 002-2v-8a2 2 0 00-2-2h-8a2 2 0 00-2 2v8a2 2 0 002 2z" />
                                         </svg>
                                     </button>
+                                    <StatusOverrideEditor
+                                        test_name=selected_test
+                                        result=result
+                                        log_analysis_result=log_analysis_result
+                                        rerun_analysis=rerun_analysis
+                                    />
                                 </div>
                                 <div class="ml-2 space-y-0 max-h-24 overflow-y-hidden">
                                     {move || {
@@ -0,0 +1,98 @@
+use leptos::prelude::*;
+
+use super::number_format::format_size;
+use super::types::{DownloadFileProgress, DownloadProgress};
+
+/// The open progress stream of a download; `close` stops the updates
+pub struct DownloadWatch {
+    #[cfg(feature = "hydrate")]
+    source: Option<web_sys::EventSource>,
+}
+
+impl DownloadWatch {
+    pub fn close(self) {
+        #[cfg(feature = "hydrate")]
+        {
+            if let Some(source) = self.source {
+                source.close();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "hydrate")]
+fn open_progress_stream(progress_id: &str, progress: RwSignal<Option<DownloadProgress>>) -> Option<web_sys::EventSource> {
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::JsCast;
+
+    let source = web_sys::EventSource::new(&format!("/api/download_progress/{}", progress_id)).ok()?;
+    let id = progress_id.to_string();
+    let stream = source.clone();
+    let on_message = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(move |event: web_sys::MessageEvent| {
+        let Some(data) = event.data().as_string() else { return };
+        let Ok(update) = serde_json::from_str::<DownloadProgress>(&data) else { return };
+        // The server ends the stream here; closing keeps the browser from reconnecting
+        if update.finished {
+            stream.close();
+        }
+        // Answers for a download that's no longer the current one are dropped
+        if progress.with_untracked(|current| current.as_ref().is_some_and(|current| current.id == id)) {
+            progress.set(Some(update));
+        }
+    });
+    source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+    Some(source)
+}
+
+/// Make `progress_id` the current download and follow its files over server-sent events
+pub fn watch_download_progress(progress_id: &str, progress: RwSignal<Option<DownloadProgress>>) -> DownloadWatch {
+    progress.set(Some(DownloadProgress { id: progress_id.to_string(), ..Default::default() }));
+    DownloadWatch {
+        #[cfg(feature = "hydrate")]
+        source: open_progress_stream(progress_id, progress),
+    }
+}
+
+fn file_view(file: DownloadFileProgress) -> AnyView {
+    let percent = file.percent();
+    let size = match file.total {
+        Some(total) if !file.done => format!("{} / {}", format_size(file.bytes), format_size(total)),
+        Some(total) => format_size(total),
+        None if file.done || file.bytes > 0 => format_size(file.bytes),
+        None => String::new(),
+    };
+    let bar_class = if file.done { "h-1.5 rounded bg-green-500" } else { "h-1.5 rounded bg-blue-500" };
+    view! {
+        <li class="space-y-0.5">
+            <div class="flex items-center justify-between gap-4 text-xs text-gray-700 dark:text-gray-300">
+                <span class="truncate font-mono" title=file.name.clone()>{file.name.clone()}</span>
+                <span class="tabular-nums whitespace-nowrap text-gray-500 dark:text-gray-400">{size}</span>
+            </div>
+            <div class="h-1.5 rounded bg-gray-200 dark:bg-gray-700">
+                <div class=bar_class style=format!("width: {}%", percent.unwrap_or(0))></div>
+            </div>
+        </li>
+    }.into_any()
+}
+
+// One bar per file of the running download, so a large deliverable shows where it is
+#[component]
+pub fn DownloadProgressBars(progress: RwSignal<Option<DownloadProgress>>) -> impl IntoView {
+    move || {
+        let files = progress.get().map(|progress| progress.files).unwrap_or_default();
+        if files.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let done = files.iter().filter(|file| file.done).count();
+        let total = files.len();
+        view! {
+            <div class="w-96 text-left">
+                <div class="text-sm text-gray-600 dark:text-gray-400 mb-2">{format!("{} of {} files", done, total)}</div>
+                <ul class="space-y-2 max-h-64 overflow-y-auto pr-1">
+                    {files.into_iter().map(file_view).collect_view()}
+                </ul>
+            </div>
+        }.into_any()
+    }
+}
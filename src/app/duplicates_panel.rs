@@ -0,0 +1,164 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use std::collections::HashMap;
+
+use super::log_preview::LogPreview;
+use super::types::{ApiError, DetectedDuplicate, DuplicateOccurrence, DuplicateTest, LogAnalysisResult, ProcessingResult};
+
+// Served at /api/detect_duplicates so tooling can check one log without a full analysis
+#[server(endpoint = "detect_duplicates")]
+pub async fn handle_detect_duplicates(file_path: String, language: Option<String>) -> Result<Vec<DetectedDuplicate>, ApiError> {
+    use crate::api::log_analysis::detect_duplicates;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(std::slice::from_ref(&file_path))?;
+    detect_duplicates(file_path, language)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to read the log").with_details(e))
+}
+
+// Deliverable path of the log the analysis read for `stage`
+fn stage_log_path(analysis: &LogAnalysisResult, file_paths: &[String], stage: &str) -> Option<String> {
+    let source = analysis.sources.iter().find(|source| source.role == stage)?;
+    file_paths.iter().find(|path| path.ends_with(&format!("/{}", source.path))).cloned()
+}
+
+fn find_occurrence<'a>(occurrences: &'a HashMap<String, Vec<DetectedDuplicate>>, stage: &str, test_name: &str, line: usize) -> Option<&'a DuplicateOccurrence> {
+    occurrences
+        .get(stage)?
+        .iter()
+        .filter(|duplicate| duplicate.test_name == test_name)
+        .flat_map(|duplicate| duplicate.occurrences.iter())
+        .find(|occurrence| occurrence.line == line)
+}
+
+// Popover body, type-erased to keep the view type shallow
+#[allow(clippy::too_many_arguments)]
+fn duplicate_details(
+    duplicates: Vec<DuplicateTest>,
+    // Status and context of each occurrence, by stage, once fetched
+    occurrences: HashMap<String, Vec<DetectedDuplicate>>,
+    agent_log: Option<String>,
+    result: RwSignal<Option<ProcessingResult>>,
+    is_open: RwSignal<bool>,
+    active_main_tab: RwSignal<String>,
+    active_tab: RwSignal<String>,
+    highlight_line: RwSignal<Option<usize>>,
+) -> AnyView {
+    let rows = duplicates.into_iter().map(|duplicate| {
+        let links = duplicate.lines.into_iter().map(|line| {
+            let stage = duplicate.stage.clone();
+            let preview_stage = duplicate.stage.clone();
+            let occurrence = find_occurrence(&occurrences, &duplicate.stage, &duplicate.test_name, line);
+            let status = occurrence.map(|occurrence| occurrence.status.clone());
+            let context = occurrence
+                .map(|occurrence| occurrence.context_before.iter().chain(occurrence.context_after.iter()).cloned().collect::<Vec<_>>().join("\n"))
+                .unwrap_or_default();
+            view! {
+                <LogPreview result=result stage=preview_stage agent_log=agent_log.clone() line=Some(line) test_name=None>
+                    <button
+                        on:click=move |_| {
+                            is_open.set(false);
+                            highlight_line.set(Some(line));
+                            active_tab.set(stage.clone());
+                            active_main_tab.set("input".to_string());
+                        }
+                        title=context
+                        class="text-blue-600 dark:text-blue-400 hover:underline"
+                    >
+                        {format!("L{}", line)}
+                    </button>
+                    {status.map(|status| view! { <span class="ml-0.5 text-gray-500 dark:text-gray-400">{status}</span> })}
+                </LogPreview>
+            }
+        }).collect_view();
+        view! {
+            <tr class="text-gray-800 dark:text-gray-100 align-top">
+                <td class="pr-2 whitespace-nowrap text-gray-500 dark:text-gray-400">{duplicate.stage}</td>
+                <td class="font-mono pr-2 break-all">{duplicate.test_name}</td>
+                <td class="text-right">
+                    <div class="flex flex-wrap justify-end gap-x-2">{links}</div>
+                </td>
+            </tr>
+        }
+    }).collect_view();
+
+    view! {
+        <div class="absolute left-0 mt-2 w-[32rem] max-h-96 overflow-y-auto z-50 p-3 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+            <div class="text-xs font-semibold text-gray-500 dark:text-gray-400 mb-1">"Tests reported more than once in the same log"</div>
+            <table class="w-full text-xs">
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+#[component]
+pub fn DuplicatesPanel(
+    log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
+    active_main_tab: RwSignal<String>,
+    active_tab: RwSignal<String>,
+    highlight_line: RwSignal<Option<usize>>,
+    result: RwSignal<Option<ProcessingResult>>,
+) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let occurrences = RwSignal::new(HashMap::<String, Vec<DetectedDuplicate>>::new());
+
+    // A new analysis may have read other logs; reopening the list fetches them again
+    Effect::new(move |_| {
+        log_analysis_result.track();
+        occurrences.set(HashMap::new());
+        is_open.set(false);
+    });
+
+    // Fetch each duplicated log's occurrences the first time the list is opened
+    Effect::new(move |_| {
+        if !is_open.get() {
+            return;
+        }
+        let (Some(analysis), Some(deliverable)) = (log_analysis_result.get_untracked(), result.get_untracked()) else { return };
+        let language = analysis.settings.as_ref().map(|settings| settings.language.clone());
+        let mut stages: Vec<String> = analysis.debug_info.duplicates.iter().map(|duplicate| duplicate.stage.clone()).collect();
+        stages.sort();
+        stages.dedup();
+        for stage in stages {
+            if occurrences.with_untracked(|loaded| loaded.contains_key(&stage)) {
+                continue;
+            }
+            let Some(path) = stage_log_path(&analysis, &deliverable.file_paths, &stage) else { continue };
+            let language = language.clone();
+            spawn_local(async move {
+                if let Ok(found) = handle_detect_duplicates(path, language).await {
+                    occurrences.update(|loaded| {
+                        loaded.insert(stage, found);
+                    });
+                }
+            });
+        }
+    });
+
+    move || {
+        let (duplicates, agent_log) = log_analysis_result.get()
+            .map(|analysis| (analysis.debug_info.duplicates, analysis.debug_info.agent_log))
+            .unwrap_or_default();
+        if duplicates.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let label = format!("Duplicates ({})", duplicates.len());
+
+        view! {
+            <div class="relative">
+                <button
+                    on:click=move |_| is_open.update(|open| *open = !*open)
+                    class="text-sm px-2 py-0.5 rounded border border-yellow-300 dark:border-yellow-700 bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200"
+                >
+                    {label}
+                </button>
+                {move || if is_open.get() {
+                    duplicate_details(duplicates.clone(), occurrences.get(), agent_log.clone(), result, is_open, active_main_tab, active_tab, highlight_line)
+                } else {
+                    view! { <div></div> }.into_any()
+                }}
+            </div>
+        }.into_any()
+    }
+}
@@ -0,0 +1,36 @@
+use leptos::prelude::*;
+
+use super::types::{EnvironmentDrift, LogAnalysisResult};
+
+fn drift_details(drift: &EnvironmentDrift) -> String {
+    let versions = drift.versions
+        .iter()
+        .map(|(stage, version)| format!("{} {}", stage, version))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{}: {}", drift.tool, versions)
+}
+
+#[component]
+pub fn EnvironmentDriftNotice(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    move || {
+        let drift = log_analysis_result.get().map(|analysis| analysis.debug_info.environment_drift).unwrap_or_default();
+        if drift.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let label = format!(
+            "Environment drift: {}",
+            drift.iter().map(|drift| drift.tool.as_str()).collect::<Vec<_>>().join(", ")
+        );
+        let details = drift.iter().map(drift_details).collect::<Vec<_>>().join("\n");
+
+        view! {
+            <span
+                class="text-sm px-2 py-0.5 rounded border border-yellow-300 dark:border-yellow-700 bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200 whitespace-nowrap"
+                title=format!("Stages ran on different versions, so base failures may come from the environment\n{}", details)
+            >
+                {label}
+            </span>
+        }.into_any()
+    }
+}
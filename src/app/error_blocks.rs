@@ -0,0 +1,150 @@
+//! Error regions of an agent log
+//!
+//! Finds Python tracebacks, Rust panics, failed assertions and compiler errors so the
+//! Agent Log tab can highlight them and list them in its index. Like the scratch parser
+//! this runs in the browser, over the log the Input tab already loads.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// One highlighted region, lines are 1-based and inclusive
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorBlock {
+    // "traceback", "panic", "assertion" or "error"
+    pub kind: &'static str,
+    pub start_line: usize,
+    pub end_line: usize,
+    // Line shown in the index, usually the exception or panic message
+    pub title: String,
+}
+
+// Longest block kept for a single error; the rest of a runaway region stays unhighlighted
+const MAX_BLOCK_LINES: usize = 60;
+const MAX_TITLE_CHARS: usize = 160;
+
+lazy_static! {
+    static ref ANSI_RE: Regex = Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+    static ref PANIC_RE: Regex = Regex::new(r"thread '[^']*' panicked at").unwrap();
+    static ref ASSERTION_RE: Regex = Regex::new(
+        r"AssertionError|assertion failed|assertion `left == right` failed|^E\s+assert\b|expect\(.*\)\.\w+\("
+    ).unwrap();
+    static ref COMPILER_ERROR_RE: Regex = Regex::new(r"^error(\[E\d+\])?: ").unwrap();
+}
+
+fn clean(line: &str) -> String {
+    ANSI_RE.replace_all(line.trim_end_matches('\r'), "").to_string()
+}
+
+fn is_indented(line: &str) -> bool {
+    line.starts_with(' ') || line.starts_with('\t')
+}
+
+fn title_of(line: &str) -> String {
+    let line = line.trim();
+    match line.char_indices().nth(MAX_TITLE_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+// Last line of a block starting at `start`: keep going while `continues` accepts the next line
+fn extend_while(lines: &[String], start: usize, continues: impl Fn(&str) -> bool) -> usize {
+    let mut end = start;
+    while end + 1 < lines.len() && end + 1 - start < MAX_BLOCK_LINES && continues(&lines[end + 1]) {
+        end += 1;
+    }
+    end
+}
+
+// Block starting at line index `i`, as (kind, last line index, title)
+fn block_at(lines: &[String], i: usize) -> Option<(&'static str, usize, String)> {
+    let line = &lines[i];
+    if line.contains("Traceback (most recent call last):") {
+        // Frames are indented; the exception line that closes the traceback is not
+        let frames_end = extend_while(lines, i, is_indented);
+        let end = if frames_end + 1 < lines.len() && !lines[frames_end + 1].trim().is_empty() { frames_end + 1 } else { frames_end };
+        return Some(("traceback", end, title_of(&lines[end])));
+    }
+    if PANIC_RE.is_match(line) {
+        let end = extend_while(lines, i, |next| !next.trim().is_empty() && !PANIC_RE.is_match(next));
+        // Since Rust 1.73 the message is on the line after the location
+        let title = if line.trim_end().ends_with(':') && end > i { &lines[i + 1] } else { line };
+        return Some(("panic", end, title_of(title)));
+    }
+    if COMPILER_ERROR_RE.is_match(line) {
+        let end = extend_while(lines, i, |next| is_indented(next) || next.starts_with("-->") || next.trim_start().starts_with('|'));
+        return Some(("error", end, title_of(line)));
+    }
+    if ASSERTION_RE.is_match(line) {
+        let end = extend_while(lines, i, |next| {
+            is_indented(next) || next.starts_with("E ") || next.starts_with("left") || next.starts_with("right")
+        });
+        return Some(("assertion", end, title_of(line)));
+    }
+    None
+}
+
+/// Error regions of the log in order; regions never overlap
+pub fn find_error_blocks(content: &str) -> Vec<ErrorBlock> {
+    let lines: Vec<String> = content.split('\n').map(clean).collect();
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        match block_at(&lines, i) {
+            Some((kind, end, title)) => {
+                blocks.push(ErrorBlock { kind, start_line: i + 1, end_line: end + 1, title });
+                i = end + 1;
+            }
+            None => i += 1,
+        }
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_tracebacks_and_panics() {
+        let log = "collecting ...\n\
+                   Traceback (most recent call last):\n\
+                   \x20 File \"app.py\", line 3, in <module>\n\
+                   \x20   main()\n\
+                   ValueError: bad input\n\
+                   \n\
+                   thread 'parser::works' panicked at src/parser.rs:10:5:\n\
+                   \x1b[31mcalled `Option::unwrap()` on a `None` value\x1b[0m\n\
+                   note: run with `RUST_BACKTRACE=1`\n\
+                   \n\
+                   done";
+        let blocks = find_error_blocks(log);
+        assert_eq!(blocks, vec![
+            ErrorBlock { kind: "traceback", start_line: 2, end_line: 5, title: "ValueError: bad input".to_string() },
+            ErrorBlock { kind: "panic", start_line: 7, end_line: 9, title: "called `Option::unwrap()` on a `None` value".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_finds_compiler_errors_and_assertions() {
+        let log = "error[E0308]: mismatched types\n\
+                   \x20--> src/lib.rs:4:5\n\
+                   \x20 |\n\
+                   warning: unused import\n\
+                   E       assert 1 == 2\n\
+                   E        +  where 1 = f()\n\
+                   ok";
+        let blocks = find_error_blocks(log);
+        let spans: Vec<(&str, usize, usize)> = blocks.iter().map(|block| (block.kind, block.start_line, block.end_line)).collect();
+        assert_eq!(spans, vec![("error", 1, 3), ("assertion", 5, 6)]);
+        assert!(find_error_blocks("all 3 tests passed").is_empty());
+    }
+
+    #[test]
+    fn test_caps_runaway_blocks() {
+        let frames = "  frame\n".repeat(200);
+        let log = format!("Traceback (most recent call last):\n{}RuntimeError: boom", frames);
+        let blocks = find_error_blocks(&log);
+        assert_eq!(blocks[0].end_line - blocks[0].start_line, MAX_BLOCK_LINES);
+    }
+}
@@ -1,12 +1,15 @@
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use super::types::{FileContents, FileContent, ProcessingResult, LoadedFileTypes};
+use super::types::{ApiError, FileContents, FileContent, ProcessingResult, LoadedFileTypes};
 
 #[server]
-pub async fn handle_get_file_contents(file_type: String, file_paths: Vec<String>) -> Result<String, ServerFnError> {
+pub async fn handle_get_file_contents(file_type: String, file_paths: Vec<String>) -> Result<String, ApiError> {
     use crate::api::file_operations::{get_file_contents};
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
     get_file_contents(file_type, file_paths)
-        .map_err(|e| ServerFnError::ServerError(e))
+        .map_err(|e| ApiError::new(ApiErrorCode::MissingFile, e))
 }
 
 pub fn load_file_contents(
@@ -47,7 +50,7 @@ pub fn load_file_contents(
             match content {
                 Ok(content) => {
                     // Check if this is a "not found" message for optional files
-                    let is_optional = matches!(file_type.as_str(), "agent" | "report");
+                    let is_optional = matches!(file_type.as_str(), "base" | "before" | "after" | "agent" | "report");
                     let is_not_found = content.starts_with("No ") && content.contains("file found");
                     
                     if is_optional && is_not_found {
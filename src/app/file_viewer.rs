@@ -10,7 +10,10 @@ pub fn FileViewer(
     loading_files: RwSignal<bool>,
     loaded_file_types: RwSignal<LoadedFileTypes>,
     result: RwSignal<Option<super::types::ProcessingResult>>,
+    // 1-based line to highlight and scroll to in the active log, set by links such as the Duplicates panel
+    highlight_line: RwSignal<Option<usize>>,
 ) -> impl IntoView {
+    let highlight_ref = NodeRef::<leptos::html::Span>::new();
     let input_tabs = vec![
         ("base", "Base"),
         ("before", "Before"),
@@ -35,6 +38,12 @@ pub fn FileViewer(
         }
     });
 
+    Effect::new(move |_| {
+        if let Some(element) = highlight_ref.get() {
+            element.scroll_into_view();
+        }
+    });
+
     view! {
         <div class="flex h-full">
             <div class="w-48 bg-gray-100 dark:bg-gray-700 border-r border-gray-200 dark:border-gray-600 flex flex-col">
@@ -55,6 +64,7 @@ pub fn FileViewer(
                                     }
                                 }
                                 on:click=move |_| {
+                                    highlight_line.set(None);
                                     active_tab.set(key_clone.clone());
                                 }
                             >
@@ -74,6 +84,7 @@ pub fn FileViewer(
                             Some(file_content) => {
                                 let text = file_content.content.clone();
                                 let file_type = file_content.file_type.clone();
+                                let (text, highlighted, text_after) = split_at_line(&text, highlight_line.get());
                                 view! {
                                     <>
                                         <div class="flex-1 min-h-0 overflow-auto rounded-lg border border-gray-200 dark:border-gray-700 bg-gray-900 text-gray-100">
@@ -87,6 +98,10 @@ pub fn FileViewer(
                                                 }
                                             }>
                                                 {text}
+                                                {highlighted.map(|line| view! {
+                                                    <span node_ref=highlight_ref class="block bg-yellow-700 text-white">{line}</span>
+                                                })}
+                                                {text_after}
                                             </pre>
                                         </div>
                                     </>
@@ -118,3 +133,20 @@ pub fn FileViewer(
         </div>
     }
 }
+
+// Text before, at and after a 1-based line, so the target line can be rendered as its own element
+fn split_at_line(text: &str, line: Option<usize>) -> (String, Option<String>, String) {
+    let Some(line) = line.filter(|line| *line >= 1) else {
+        return (text.to_string(), None, String::new());
+    };
+    let lines: Vec<&str> = text.split('\n').collect();
+    if line > lines.len() {
+        return (text.to_string(), None, String::new());
+    }
+    // The highlighted span is a block, so it supplies the line breaks on either side
+    (
+        lines[..line - 1].join("\n"),
+        Some(lines[line - 1].to_string()),
+        lines[line..].join("\n"),
+    )
+}
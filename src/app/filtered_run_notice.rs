@@ -0,0 +1,31 @@
+use leptos::prelude::*;
+
+use super::types::LogAnalysisResult;
+
+#[component]
+pub fn FilteredRunNotice(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    move || {
+        let filtered = log_analysis_result.get().map(|analysis| analysis.debug_info.filtered_stages).unwrap_or_default();
+        if filtered.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let label = format!(
+            "Suite filtered: {}",
+            filtered.iter().map(|filter| filter.stage.as_str()).collect::<Vec<_>>().join(", ")
+        );
+        let details = filtered
+            .iter()
+            .map(|filter| format!("{} ({} listed tests missing): {}", filter.stage, filter.missing_tests, filter.evidence))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        view! {
+            <span
+                class="text-sm px-2 py-0.5 rounded border border-yellow-300 dark:border-yellow-700 bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200 whitespace-nowrap"
+                title=format!("Suite filtered, results incomplete: the run never reached these tests\n{}", details)
+            >
+                {label}
+            </span>
+        }.into_any()
+    }
+}
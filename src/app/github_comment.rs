@@ -0,0 +1,187 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ProcessingResult};
+
+#[server]
+pub async fn handle_github_comment_preview(
+    file_paths: Vec<String>,
+    deliverable_link: String,
+    instance_id: String,
+    task_id: String,
+) -> Result<String, ApiError> {
+    use crate::api::github::format_github_comment;
+    use crate::api::log_analysis::analyze_logs;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+
+    let analysis = analyze_logs(file_paths)
+        .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to analyze logs").with_details(e))?;
+    Ok(format_github_comment(&deliverable_link, &instance_id, &task_id, &analysis))
+}
+
+#[server]
+pub async fn handle_post_github_comment(
+    repo: String,
+    issue_number: String,
+    body: String,
+    token: Option<String>,
+) -> Result<String, ApiError> {
+    use crate::api::github::post_github_comment;
+    use crate::app::types::ApiErrorCode;
+    post_github_comment(&repo, &issue_number, &body, token)
+        .await
+        .map_err(|e| ApiError::new(ApiErrorCode::Network, "Failed to post GitHub comment").with_details(e))
+}
+
+#[component]
+pub fn GitHubCommentPanel(current_deliverable: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let body = RwSignal::new(String::new());
+    let repo = RwSignal::new(String::new());
+    let issue_number = RwSignal::new(String::new());
+    let token = RwSignal::new(String::new());
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+    let posted_url = RwSignal::new(None::<String>);
+
+    let generate = move || {
+        let Some(deliverable) = current_deliverable.get_untracked() else {
+            return;
+        };
+        // Default the target to the task's issue, falling back to the PR
+        repo.set(deliverable.repo.clone());
+        issue_number.set(if !deliverable.issue_id.is_empty() { deliverable.issue_id.clone() } else { deliverable.pr_id.clone() });
+
+        busy.set(true);
+        error.set(None);
+        posted_url.set(None);
+        spawn_local(async move {
+            match handle_github_comment_preview(
+                deliverable.file_paths.clone(),
+                deliverable.deliverable_link.clone(),
+                deliverable.instance_id.clone(),
+                deliverable.display_task_id(),
+            ).await {
+                Ok(markdown) => body.set(markdown),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    let post = move |_| {
+        let token_value = token.get().trim().to_string();
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            let token = if token_value.is_empty() { None } else { Some(token_value) };
+            match handle_post_github_comment(repo.get_untracked(), issue_number.get_untracked(), body.get_untracked(), token).await {
+                Ok(url) => posted_url.set(Some(url)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    let copy = move |_| {
+        let text = body.get();
+        leptos::logging::log!("Copying GitHub comment ({} chars)", text.len());
+        #[cfg(feature = "hydrate")]
+        {
+            if let Some(window) = web_sys::window() {
+                let promise = window.navigator().clipboard().write_text(&text);
+                let future = wasm_bindgen_futures::JsFuture::from(promise);
+                wasm_bindgen_futures::spawn_local(async move {
+                    if let Err(e) = future.await {
+                        leptos::logging::log!("Failed to copy to clipboard: {:?}", e);
+                    }
+                });
+            }
+        }
+    };
+
+    view! {
+        <div class="relative">
+            <button
+                on:click=move |_| {
+                    let open = !is_open.get();
+                    is_open.set(open);
+                    if open && body.get().is_empty() {
+                        generate();
+                    }
+                }
+                class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700"
+            >
+                "GitHub comment"
+            </button>
+            <Show when=move || is_open.get()>
+                <div class="absolute right-0 mt-2 w-[32rem] z-50 p-3 space-y-2 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+                    <textarea
+                        prop:value=move || body.get()
+                        on:input=move |ev| body.set(event_target_value(&ev))
+                        class="w-full h-64 px-2 py-1 font-mono text-xs border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    <div class="flex gap-2">
+                        <button
+                            on:click=move |_| generate()
+                            disabled=move || busy.get()
+                            class="text-xs px-2 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 disabled:opacity-50"
+                        >
+                            "Regenerate"
+                        </button>
+                        <button
+                            on:click=copy
+                            class="text-xs px-2 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200"
+                        >
+                            "Copy markdown"
+                        </button>
+                    </div>
+                    <div class="flex gap-2">
+                        <input
+                            type="text"
+                            placeholder="owner/repo"
+                            prop:value=move || repo.get()
+                            on:input=move |ev| repo.set(event_target_value(&ev))
+                            class="flex-1 px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                        />
+                        <input
+                            type="text"
+                            placeholder="Issue/PR #"
+                            prop:value=move || issue_number.get()
+                            on:input=move |ev| issue_number.set(event_target_value(&ev))
+                            class="w-24 px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                        />
+                    </div>
+                    <input
+                        type="password"
+                        placeholder="GitHub token (optional if configured on server)"
+                        prop:value=move || token.get()
+                        on:input=move |ev| token.set(event_target_value(&ev))
+                        class="w-full px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    <Show when=move || error.get().is_some()>
+                        <div class="text-xs text-red-600 dark:text-red-400">{move || error.get().unwrap_or_default()}</div>
+                    </Show>
+                    <Show when=move || posted_url.get().is_some()>
+                        <a
+                            href=move || posted_url.get().unwrap_or_default()
+                            target="_blank"
+                            class="block text-xs text-blue-600 hover:text-blue-800 underline dark:text-blue-400"
+                        >
+                            "Comment posted"
+                        </a>
+                    </Show>
+                    <button
+                        on:click=post
+                        disabled=move || busy.get() || body.get().is_empty()
+                        class="w-full text-sm px-3 py-1 rounded bg-gray-900 text-white hover:bg-gray-700 disabled:opacity-50"
+                    >
+                        {move || if busy.get() { "Working..." } else { "Post to GitHub" }}
+                    </button>
+                </div>
+            </Show>
+        </div>
+    }
+}
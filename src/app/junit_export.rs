@@ -0,0 +1,81 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ProcessingResult};
+
+#[server]
+pub async fn handle_export_junit(file_paths: Vec<String>, instance_id: String, task_id: String) -> Result<String, ApiError> {
+    use crate::api::junit::format_junit_xml;
+    use crate::api::log_analysis::analyze_logs;
+    use crate::api::swebench_dataset::cross_check_with_dataset;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+
+    let mut analysis = analyze_logs(file_paths.clone())
+        .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to analyze logs").with_details(e))?;
+    cross_check_with_dataset(&file_paths, &mut analysis).await;
+    Ok(format_junit_xml(&instance_id, &task_id, &analysis))
+}
+
+#[component]
+pub fn JUnitExportButton(current_deliverable: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let xml = RwSignal::new(None::<String>);
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    // Drop a stale export when the reviewer moves to another deliverable
+    Effect::new(move |_| {
+        current_deliverable.track();
+        xml.set(None);
+        error.set(None);
+    });
+
+    let export = move |_| {
+        let Some(deliverable) = current_deliverable.get_untracked() else {
+            return;
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_export_junit(deliverable.file_paths.clone(), deliverable.instance_id.clone(), deliverable.display_task_id()).await {
+                Ok(report) => xml.set(Some(report)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    let file_name = move || {
+        current_deliverable
+            .get()
+            .map(|d| format!("{}-junit.xml", if d.instance_id.is_empty() { "deliverable".to_string() } else { d.instance_id }))
+            .unwrap_or_else(|| "junit.xml".to_string())
+    };
+
+    view! {
+        <div class="flex items-center gap-2">
+            <Show
+                when=move || xml.get().is_some()
+                fallback=move || view! {
+                    <button
+                        on:click=export
+                        disabled=move || busy.get()
+                        title=move || error.get().unwrap_or_else(|| "Export rule checks as JUnit XML".to_string())
+                        class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 disabled:opacity-50"
+                    >
+                        {move || if busy.get() { "Exporting..." } else if error.get().is_some() { "JUnit XML (failed)" } else { "JUnit XML" }}
+                    </button>
+                }
+            >
+                <a
+                    href=move || format!("data:application/xml;charset=utf-8,{}", urlencoding::encode(&xml.get().unwrap_or_default()))
+                    download=file_name
+                    class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-blue-600 hover:text-blue-800 dark:text-blue-400"
+                >
+                    "Download JUnit XML"
+                </a>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,107 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ProcessingResult};
+
+#[server]
+pub async fn handle_llm_summary_enabled() -> Result<bool, ApiError> {
+    use crate::api::llm_summary::is_enabled;
+    Ok(is_enabled())
+}
+
+#[server]
+pub async fn handle_llm_summary(file_paths: Vec<String>) -> Result<String, ApiError> {
+    use crate::api::llm_summary::generate_failure_summary;
+    use crate::api::log_analysis::analyze_logs;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+
+    let analysis = analyze_logs(file_paths.clone())
+        .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to analyze logs").with_details(e))?;
+    generate_failure_summary(&file_paths, &analysis)
+        .await
+        .map_err(|e| ApiError::new(ApiErrorCode::Network, "Failed to generate summary").with_details(e))
+}
+
+#[component]
+pub fn LlmSummaryPanel(current_deliverable: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let enabled = RwSignal::new(false);
+    let is_open = RwSignal::new(false);
+    let summary = RwSignal::new(None::<String>);
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    // The panel only appears when the server has an LLM endpoint configured
+    Effect::new(move |_| {
+        spawn_local(async move {
+            if let Ok(true) = handle_llm_summary_enabled().await {
+                enabled.set(true);
+            }
+        });
+    });
+
+    // A summary belongs to one deliverable
+    Effect::new(move |_| {
+        current_deliverable.track();
+        summary.set(None);
+        error.set(None);
+    });
+
+    let generate = move || {
+        let Some(deliverable) = current_deliverable.get_untracked() else {
+            return;
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_llm_summary(deliverable.file_paths.clone()).await {
+                Ok(text) => summary.set(Some(text)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <Show when=move || enabled.get()>
+            <div class="relative">
+                <button
+                    on:click=move |_| {
+                        let open = !is_open.get();
+                        is_open.set(open);
+                        if open && summary.get().is_none() && !busy.get() {
+                            generate();
+                        }
+                    }
+                    class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700"
+                >
+                    "AI summary"
+                </button>
+                <Show when=move || is_open.get()>
+                    <div class="absolute right-0 mt-2 w-96 z-50 p-3 space-y-2 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+                        <div class="text-xs text-gray-500 dark:text-gray-400">
+                            "Generated by a language model from the rule violations and log excerpts. Advisory only."
+                        </div>
+                        <Show when=move || busy.get()>
+                            <div class="text-sm text-gray-600 dark:text-gray-300">"Generating summary..."</div>
+                        </Show>
+                        <Show when=move || error.get().is_some()>
+                            <div class="text-xs text-red-600 dark:text-red-400">{move || error.get().unwrap_or_default()}</div>
+                        </Show>
+                        <Show when=move || summary.get().is_some()>
+                            <p class="text-sm whitespace-pre-wrap text-gray-800 dark:text-gray-100">{move || summary.get().unwrap_or_default()}</p>
+                        </Show>
+                        <button
+                            on:click=move |_| generate()
+                            disabled=move || busy.get()
+                            class="text-xs px-2 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 disabled:opacity-50"
+                        >
+                            "Regenerate"
+                        </button>
+                    </div>
+                </Show>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,95 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::search_results::handle_get_log_slice;
+use super::types::{LogSlice, ProcessingResult};
+
+const PREVIEW_RADIUS: usize = 3;
+
+// Popover body, type-erased to keep the view type shallow
+fn slice_view(slice: LogSlice) -> AnyView {
+    let anchor = slice.line;
+    let rows = slice.lines.into_iter().enumerate().map(|(offset, text)| {
+        let number = slice.first_line + offset;
+        let row_class = if number == anchor {
+            "bg-yellow-100 dark:bg-yellow-900/40 text-gray-900 dark:text-white"
+        } else {
+            "text-gray-700 dark:text-gray-300"
+        };
+        view! {
+            <div class=row_class>
+                <span class="inline-block w-12 pr-2 text-right text-gray-400 dark:text-gray-500 select-none">{number}</span>
+                {text}
+            </div>
+        }
+    }).collect_view();
+    view! { <pre class="text-xs font-mono whitespace-pre-wrap break-all">{rows}</pre> }.into_any()
+}
+
+// Shows a few log lines around `line` (or the first line naming `test_name`) while the
+// pointer rests on the children; the slice is fetched on first hover and kept
+#[component]
+pub fn LogPreview(
+    result: RwSignal<Option<ProcessingResult>>,
+    // base, before, after or agent
+    stage: String,
+    // Agent log the analysis used, when the deliverable has several
+    agent_log: Option<String>,
+    line: Option<usize>,
+    test_name: Option<String>,
+    children: Children,
+) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let requested = RwSignal::new(false);
+    let slice = RwSignal::new(None::<LogSlice>);
+    let unavailable = RwSignal::new(false);
+    // Viewport position of the pointer; the popover is fixed so scrolling lists don't clip it
+    let position = RwSignal::new((0, 0));
+
+    let fetch = move || {
+        if requested.get_untracked() {
+            return;
+        }
+        let Some(deliverable) = result.get_untracked() else {
+            return;
+        };
+        requested.set(true);
+        let (stage, agent_log, test_name) = (stage.clone(), agent_log.clone(), test_name.clone());
+        spawn_local(async move {
+            match handle_get_log_slice(deliverable.file_paths, stage, agent_log, line, test_name, PREVIEW_RADIUS).await {
+                Ok(Some(found)) => slice.set(Some(found)),
+                _ => unavailable.set(true),
+            }
+        });
+    };
+
+    view! {
+        <span
+            class="inline-block"
+            on:mouseenter=move |ev| {
+                position.set((ev.client_x(), ev.client_y()));
+                is_open.set(true);
+                fetch();
+            }
+            on:mouseleave=move |_| is_open.set(false)
+        >
+            {children()}
+            {move || is_open.get().then(|| {
+                let body = match slice.get() {
+                    Some(found) => slice_view(found),
+                    None if unavailable.get() => view! { <div class="text-xs text-gray-500 dark:text-gray-400">"Not found in the log"</div> }.into_any(),
+                    None => view! { <div class="text-xs text-gray-500 dark:text-gray-400">"Loading..."</div> }.into_any(),
+                };
+                let (x, y) = position.get();
+                view! {
+                    <div
+                        style=format!("left: {}px; top: {}px", x + 12, y + 12)
+                        class="fixed w-[36rem] max-w-[80vw] z-50 p-2 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg pointer-events-none"
+                    >
+                        {body}
+                    </div>
+                }
+            })}
+        </span>
+    }
+}
@@ -1,7 +1,32 @@
 use leptos::prelude::*;
 use std::collections::HashMap;
-use super::types::LogSearchResults;
+use super::types::{LogSearchResults, ProcessingResult, SearchMode, SearchResult, LOG_SEARCH_STAGES, SEARCH_CONTEXT_SIZES};
 use super::search_results::navigate_search_result;
+use super::context_diff::ContextDiffView;
+use super::log_window::LogWindow;
+use super::test_name_inspector::TestNameInspector;
+use super::trace_context::TraceContextView;
+use super::number_format::format_compact_count;
+
+// The hit's line with the matched text marked; the whole line stays highlighted when the
+// mode couldn't say where it matched
+fn matched_line(result: &SearchResult) -> AnyView {
+    let line = result.line_content.clone();
+    match result.match_range {
+        Some((start, end)) if start < end && line.is_char_boundary(start) && line.get(..end).is_some() => {
+            let (prefix, rest) = line.split_at(start);
+            let (matched, suffix) = rest.split_at(end - start);
+            view! {
+                <span class="flex-1">
+                    {prefix.to_string()}
+                    <mark class="bg-yellow-400 dark:bg-yellow-600 text-gray-900 dark:text-white rounded-sm">{matched.to_string()}</mark>
+                    {suffix.to_string()}
+                </span>
+            }.into_any()
+        }
+        _ => view! { <span class="flex-1">{line}</span> }.into_any(),
+    }
+}
 
 #[component]
 pub fn LogColumn(
@@ -9,8 +34,26 @@ pub fn LogColumn(
     title: &'static str,
     search_results: RwSignal<LogSearchResults>,
     search_result_indices: RwSignal<HashMap<String, usize>>,
+    focused_stage: RwSignal<Option<String>>,
+    // Set to this column and the current hit's line by "Open at line"
+    opened_line: RwSignal<Option<(String, usize)>>,
+    // Where a folded stack trace is read from when expanded
+    deliverable: RwSignal<Option<ProcessingResult>>,
+    agent_log: RwSignal<Option<String>>,
     container_class: &'static str,
 ) -> impl IntoView {
+    let highlight_ref = NodeRef::<leptos::html::Div>::new();
+
+    // A deep link from a rule example asked for this column: bring its first hit on screen once it arrives
+    Effect::new(move |_| {
+        if let Some(element) = highlight_ref.get() {
+            if focused_stage.with_untracked(|stage| stage.as_deref() == Some(log_key)) {
+                element.scroll_into_view();
+                focused_stage.set(None);
+            }
+        }
+    });
+
     view! {
         <div class=container_class>
             <div class="bg-gray-50 dark:bg-gray-700 px-4 py-2 border-b border-gray-200 dark:border-gray-600 flex items-center justify-between">
@@ -18,9 +61,10 @@ pub fn LogColumn(
                     {title} " (" {move || {
                         let results = search_results.get();
                         match log_key {
-                            "base" => results.base_results.len().to_string(),
-                            "before" => results.before_results.len().to_string(),
-                            "after" => results.after_results.len().to_string(),
+                            "base" => format_compact_count(results.base_results.len()),
+                            "before" => format_compact_count(results.before_results.len()),
+                            "after" => format_compact_count(results.after_results.len()),
+                            "agent" => format_compact_count(results.agent_results.len()),
                             _ => "0".to_string(),
                         }
                     }} " results)"
@@ -33,12 +77,31 @@ pub fn LogColumn(
                         "base" => results.base_results.len(),
                         "before" => results.before_results.len(),
                         "after" => results.after_results.len(),
+                        "agent" => results.agent_results.len(),
                         _ => 0,
                     };
                     
+                    let current_line = match log_key {
+                        "base" => results.base_results.get(current_index),
+                        "before" => results.before_results.get(current_index),
+                        "after" => results.after_results.get(current_index),
+                        "agent" => results.agent_results.get(current_index),
+                        _ => None,
+                    }.map(|result| result.line_number);
+                    let open_button = current_line.map(|line| view! {
+                        <button
+                            on:click=move |_| opened_line.set(Some((log_key.to_string(), line)))
+                            title="Read the log around this hit"
+                            class="text-xs text-blue-600 dark:text-blue-400 hover:underline"
+                        >
+                            {format!("Open at line {}", line)}
+                        </button>
+                    });
+
                     if total_results > 1 {
                         view! {
                             <div class="flex items-center gap-1">
+                                {open_button}
                                 <button
                                     on:click=move |_| navigate_search_result(log_key, "prev", search_results, search_result_indices)
                                     class="px-1 py-0 text-gray-500 hover:text-gray-700 dark:hover:text-gray-300"
@@ -57,7 +120,7 @@ pub fn LogColumn(
                             </div>
                         }.into_any()
                     } else {
-                        view! {}.into_any()
+                        view! { <div class="flex items-center gap-1">{open_button}</div> }.into_any()
                     }
                 }}
             </div>
@@ -71,6 +134,7 @@ pub fn LogColumn(
                         "base" => results.base_results.clone(),
                         "before" => results.before_results.clone(),
                         "after" => results.after_results.clone(),
+                        "agent" => results.agent_results.clone(),
                         _ => Vec::new(),
                     };
 
@@ -79,11 +143,24 @@ pub fn LogColumn(
                     }
 
                     if let Some(result) = items.get(current_index) {
+                        if let Some(trace) = result.trace.clone() {
+                            let file_paths = deliverable.get_untracked().map(|deliverable| deliverable.file_paths).unwrap_or_default();
+                            return view! {
+                                <TraceContextView
+                                    result=result.clone()
+                                    trace=trace
+                                    file_paths=file_paths
+                                    stage=log_key.to_string()
+                                    agent_log=agent_log.get_untracked()
+                                    highlight_ref=highlight_ref
+                                />
+                            }.into_any();
+                        }
                         let start_line_number = result.line_number - result.context_before.len();
                         let context_before_items = result.context_before.clone().into_iter().enumerate().collect::<Vec<_>>();
                         let context_after_items = result.context_after.clone().into_iter().enumerate().collect::<Vec<_>>();
                         let context_before_len = context_before_items.len();
-                        let line_content = result.line_content.clone();
+                        let line_content = matched_line(result);
 
                         view! {
                             <div class="font-mono text-xs">
@@ -104,11 +181,11 @@ pub fn LogColumn(
                                     }
                                 />
                                 // Highlighted match
-                                <div class="flex bg-yellow-200 dark:bg-yellow-800 text-gray-900 dark:text-gray-100 font-bold">
+                                <div node_ref=highlight_ref class="flex bg-yellow-200 dark:bg-yellow-800 text-gray-900 dark:text-gray-100 font-bold">
                                     <span class="w-12 text-right pr-2 text-gray-700 dark:text-gray-300 flex-shrink-0">
                                         {start_line_number + context_before_len}
                                     </span>
-                                    <span class="flex-1">{line_content}</span>
+                                    {line_content}
                                 </div>
                                 // Context after
                                 <For
@@ -141,30 +218,160 @@ pub fn LogColumn(
 pub fn LogSearchResults(
     search_results: RwSignal<LogSearchResults>,
     search_result_indices: RwSignal<HashMap<String, usize>>,
+    search_stages: RwSignal<Vec<String>>,
+    search_context: RwSignal<Option<usize>>,
+    search_include_noise: RwSignal<bool>,
+    search_mode: RwSignal<SearchMode>,
+    search_error: RwSignal<Option<String>>,
+    search_query: impl Fn(String) + Send + Sync + 'static + Copy,
+    focused_stage: RwSignal<Option<String>>,
+    result: RwSignal<Option<ProcessingResult>>,
+    selected_test: Signal<Option<String>>,
+    agent_log: RwSignal<Option<String>>,
 ) -> impl IntoView {
+    // Starts as the selected test; the reviewer can type anything else and press Enter
+    let query = RwSignal::new(String::new());
+    Effect::new(move |_| {
+        if let Some(test_name) = selected_test.get() {
+            query.set(test_name);
+        }
+    });
+    // Replace the columns with a before/after diff of the selected test's context, or with
+    // its name's search variants; one at a time
+    let show_diff = RwSignal::new(false);
+    let show_variants = RwSignal::new(false);
+    let opened_line = RwSignal::new(None::<(String, usize)>);
+    let toggle_stage = move |stage: &'static str| {
+        search_stages.update(|stages| {
+            if let Some(position) = stages.iter().position(|s| s == stage) {
+                stages.remove(position);
+            } else {
+                stages.push(stage.to_string());
+            }
+        });
+    };
+
     view! {
-        <div class="h-1/2 flex flex-row">
-            <LogColumn
-                log_key="base"
-                title="Base Log"
-                search_results=search_results
-                search_result_indices=search_result_indices
-                container_class="w-1/3 border-r border-gray-200 dark:border-gray-700 flex flex-col"
-            />
-            <LogColumn
-                log_key="before"
-                title="Before Log"
-                search_results=search_results
-                search_result_indices=search_result_indices
-                container_class="w-1/3 border-r border-gray-200 dark:border-gray-700 flex flex-col"
-            />
-            <LogColumn
-                log_key="after"
-                title="After Log"
-                search_results=search_results
-                search_result_indices=search_result_indices
-                container_class="w-1/3 flex flex-col"
-            />
+        <div class="h-1/2 flex flex-col">
+            <div class="flex items-center gap-2 px-4 py-1 border-b border-gray-200 dark:border-gray-700 text-xs text-gray-600 dark:text-gray-300">
+                <input
+                    type="text"
+                    prop:value=move || query.get()
+                    on:input=move |ev| query.set(event_target_value(&ev))
+                    on:keydown=move |ev| {
+                        if ev.key() == "Enter" && !query.get_untracked().is_empty() {
+                            search_query(query.get_untracked());
+                        }
+                    }
+                    placeholder="Search the logs"
+                    class="w-64 px-1 py-0 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white font-mono"
+                />
+                <select
+                    on:change=move |ev| {
+                        if let Some(mode) = SearchMode::from_key(&event_target_value(&ev)) {
+                            search_mode.set(mode);
+                        }
+                    }
+                    class="px-1 py-0 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+                >
+                    {SearchMode::ALL.iter().map(|&mode| view! {
+                        <option value=mode.key() selected=move || search_mode.get() == mode>{mode.label()}</option>
+                    }).collect_view()}
+                </select>
+                {move || search_error.get().map(|e| view! { <span class="text-red-600 dark:text-red-400">{e}</span> })}
+                <span class="ml-2">"Search in:"</span>
+                {LOG_SEARCH_STAGES.iter().map(|&(stage, title)| {
+                    view! {
+                        <label class="flex items-center gap-1 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || search_stages.get().iter().any(|s| s == stage)
+                                on:change=move |_| toggle_stage(stage)
+                            />
+                            {title}
+                        </label>
+                    }
+                }).collect_view()}
+                <label class="ml-4 flex items-center gap-1">
+                    "Context:"
+                    <select
+                        on:change=move |ev| search_context.set(event_target_value(&ev).parse().ok())
+                        class="px-1 py-0 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+                    >
+                        <option value="" selected=move || search_context.get().is_none()>"Default"</option>
+                        {SEARCH_CONTEXT_SIZES.iter().map(|&size| view! {
+                            <option value=size.to_string() selected=move || search_context.get() == Some(size)>
+                                {format!("{} lines", size)}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <label class="flex items-center gap-1 cursor-pointer" title="Also search the build and install lines the analysis skips as noise">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || search_include_noise.get()
+                        on:change=move |_| search_include_noise.update(|include| *include = !*include)
+                    />
+                    "Include noise"
+                </label>
+                <label class="ml-auto flex items-center gap-1 cursor-pointer">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || show_diff.get()
+                        on:change=move |_| {
+                            show_diff.update(|show| *show = !*show);
+                            show_variants.set(false);
+                        }
+                    />
+                    "Diff before → after"
+                </label>
+                <label class="flex items-center gap-1 cursor-pointer">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || show_variants.get()
+                        on:change=move |_| {
+                            show_variants.update(|show| *show = !*show);
+                            show_diff.set(false);
+                        }
+                    />
+                    "Name variants"
+                </label>
+            </div>
+            <div class="flex-1 min-h-0 flex flex-row divide-x divide-gray-200 dark:divide-gray-700">
+                {move || {
+                    if show_diff.get() {
+                        return view! { <ContextDiffView result=result selected_test=selected_test/> }.into_any();
+                    }
+                    if show_variants.get() {
+                        return view! { <TestNameInspector result=result selected_test=selected_test agent_log=agent_log/> }.into_any();
+                    }
+                    let selected = search_stages.get();
+                    let columns: Vec<(&'static str, &'static str)> = LOG_SEARCH_STAGES
+                        .iter()
+                        .copied()
+                        .filter(|(stage, _)| selected.iter().any(|s| s == stage))
+                        .collect();
+                    if columns.is_empty() {
+                        return view! {
+                            <div class="flex-1 flex items-center justify-center text-sm text-gray-500 dark:text-gray-400">"Tick a log above to search it"</div>
+                        }.into_any();
+                    }
+                    columns.into_iter().map(|(stage, title)| view! {
+                        <LogColumn
+                            log_key=stage
+                            title=title
+                            search_results=search_results
+                            search_result_indices=search_result_indices
+                            focused_stage=focused_stage
+                            opened_line=opened_line
+                            deliverable=result
+                            agent_log=agent_log
+                            container_class="flex-1 min-w-0 flex flex-col"
+                        />
+                    }).collect_view().into_any()
+                }}
+            </div>
+            <LogWindow result=result agent_log=agent_log opened=opened_line/>
         </div>
     }.into_any()
 }
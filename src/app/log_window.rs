@@ -0,0 +1,127 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::search_results::handle_get_log_slice;
+use super::types::{LogSlice, ProcessingResult, LOG_SEARCH_STAGES};
+
+// Lines each side of the hit; "Show more" steps through these up to the server's cap
+const WINDOW_RADII: [usize; 3] = [100, 250, 500];
+
+fn window_rows(slice: LogSlice, anchor_ref: NodeRef<leptos::html::Div>) -> AnyView {
+    let anchor = slice.line;
+    let rows = slice.lines.into_iter().enumerate().map(|(offset, text)| {
+        let number = slice.first_line + offset;
+        if number == anchor {
+            view! {
+                <div node_ref=anchor_ref class="flex bg-yellow-200 dark:bg-yellow-800 text-gray-900 dark:text-gray-100 font-bold">
+                    <span class="w-14 text-right pr-2 text-gray-700 dark:text-gray-300 flex-shrink-0 select-none">{number}</span>
+                    <span class="flex-1 whitespace-pre-wrap break-all">{text}</span>
+                </div>
+            }.into_any()
+        } else {
+            view! {
+                <div class="flex text-gray-700 dark:text-gray-300">
+                    <span class="w-14 text-right pr-2 text-gray-400 dark:text-gray-500 flex-shrink-0 select-none">{number}</span>
+                    <span class="flex-1 whitespace-pre-wrap break-all">{text}</span>
+                </div>
+            }.into_any()
+        }
+    }).collect_view();
+    view! { <div class="font-mono text-xs">{rows}</div> }.into_any()
+}
+
+// Reads a few hundred lines of a log around a search hit, so a long stack trace can be
+// followed without leaving the page; the hit is highlighted and scrolled to
+#[component]
+pub fn LogWindow(
+    result: RwSignal<Option<ProcessingResult>>,
+    agent_log: RwSignal<Option<String>>,
+    // Log column and 1-based line of the opened hit; `None` closes the window
+    opened: RwSignal<Option<(String, usize)>>,
+) -> impl IntoView {
+    let radius_step = RwSignal::new(0usize);
+    let slice = RwSignal::new(None::<LogSlice>);
+    let loading = RwSignal::new(false);
+    let unavailable = RwSignal::new(false);
+    let anchor_ref = NodeRef::<leptos::html::Div>::new();
+
+    // Fetch whenever a hit is opened or the window widened
+    Effect::new(move |_| {
+        let Some((stage, line)) = opened.get() else {
+            slice.set(None);
+            return;
+        };
+        let radius = WINDOW_RADII[radius_step.get()];
+        let Some(deliverable) = result.get_untracked() else { return };
+        let agent_log = agent_log.get_untracked();
+        loading.set(true);
+        unavailable.set(false);
+        spawn_local(async move {
+            let fetched = handle_get_log_slice(deliverable.file_paths, stage.clone(), agent_log, Some(line), None, radius).await;
+            // The window was closed or moved to another hit while this loaded
+            if opened.get_untracked() != Some((stage, line)) {
+                return;
+            }
+            match fetched {
+                Ok(Some(found)) => slice.set(Some(found)),
+                _ => {
+                    slice.set(None);
+                    unavailable.set(true);
+                }
+            }
+            loading.set(false);
+        });
+    });
+
+    Effect::new(move |_| {
+        if let Some(element) = anchor_ref.get() {
+            element.scroll_into_view();
+        }
+    });
+
+    let close = move |_| {
+        radius_step.set(0);
+        opened.set(None);
+    };
+
+    move || opened.get().map(|(stage, line)| {
+        let title = LOG_SEARCH_STAGES
+            .iter()
+            .find(|(key, _)| *key == stage)
+            .map(|(_, title)| *title)
+            .unwrap_or("Log");
+        view! {
+            <div class="fixed inset-0 z-50 flex items-center justify-center bg-black/40" on:click=close>
+                <div
+                    class="w-[64rem] max-w-[90vw] h-[80vh] flex flex-col bg-white dark:bg-gray-800 rounded-lg shadow-xl"
+                    on:click=|ev| ev.stop_propagation()
+                >
+                    <div class="flex items-center justify-between gap-4 px-4 py-2 border-b border-gray-200 dark:border-gray-700">
+                        <h4 class="font-medium text-gray-900 dark:text-white text-sm">{format!("{}, line {}", title, line)}</h4>
+                        <div class="flex items-center gap-3 text-xs text-gray-500 dark:text-gray-400">
+                            {move || slice.get().map(|slice| {
+                                let last_line = slice.first_line + slice.lines.len().saturating_sub(1);
+                                format!("Lines {}–{}", slice.first_line, last_line)
+                            })}
+                            <button
+                                on:click=move |_| radius_step.update(|step| *step += 1)
+                                disabled={move || loading.get() || radius_step.get() + 1 >= WINDOW_RADII.len()}
+                                class="px-2 py-0.5 rounded border border-gray-300 dark:border-gray-600 hover:bg-gray-100 dark:hover:bg-gray-700 disabled:opacity-50"
+                            >
+                                "Show more"
+                            </button>
+                            <button on:click=close class="px-2 py-0.5 text-gray-600 dark:text-gray-300 hover:text-gray-900 dark:hover:text-white">"Close"</button>
+                        </div>
+                    </div>
+                    <div class="flex-1 overflow-auto p-4">
+                        {move || match slice.get() {
+                            Some(found) => window_rows(found, anchor_ref),
+                            None if unavailable.get() => view! { <div class="text-sm text-gray-500 dark:text-gray-400">"Line not found in the log"</div> }.into_any(),
+                            None => view! { <div class="text-sm text-gray-500 dark:text-gray-400">"Loading..."</div> }.into_any(),
+                        }}
+                    </div>
+                </div>
+            </div>
+        }
+    })
+}
@@ -0,0 +1,116 @@
+// Counts, sizes and rates for the views and the exported reports
+//
+// The output doesn't depend on the browser's locale: the server renders the same strings the
+// client hydrates, and exported reports read the same wherever they were made.
+
+const THOUSANDS_SEPARATOR: char = ',';
+
+// `1234567` as `1,234,567`
+pub fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(THOUSANDS_SEPARATOR);
+        }
+        out.push(digit);
+    }
+    out
+}
+
+// Short form for badges and summaries: `999`, `12.3k`, `4.5M`. Rounds to the shown tenth
+// before picking the unit, so 999,950 reads `1.0M` rather than `1000.0k`
+pub fn format_compact_count(count: usize) -> String {
+    if count < 10_000 {
+        return format_count(count);
+    }
+    let tenths_of_k = (count + 50) / 100;
+    if tenths_of_k < 10_000 {
+        return format!("{}.{}k", tenths_of_k / 10, tenths_of_k % 10);
+    }
+    let tenths_of_m = (count + 50_000) / 100_000;
+    format!("{}.{}M", tenths_of_m / 10, tenths_of_m % 10)
+}
+
+// Binary units, one decimal from KB up; like the counts, rounded before the unit is picked
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while unit + 1 < UNITS.len() && (value * 10.0).round() >= 10_240.0 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+// `part` of `total` in percent, or a dash when there is nothing to divide by. Rounding never
+// shows a partial share as 0% or 100%, so a single failure among thousands still shows
+pub fn format_percent(part: usize, total: usize) -> String {
+    if total == 0 {
+        return "—".to_string();
+    }
+    let percent = part as f64 * 100.0 / total as f64;
+    if part > 0 && percent < 0.1 {
+        "<0.1%".to_string()
+    } else if part < total && percent > 99.9 {
+        ">99.9%".to_string()
+    } else if part == 0 || part == total {
+        format!("{:.0}%", percent)
+    } else {
+        format!("{:.1}%", percent)
+    }
+}
+
+// `resolved/total (pct)`, or a dash when there is no total
+pub fn format_ratio(part: usize, total: usize) -> String {
+    if total == 0 {
+        return "—".to_string();
+    }
+    format!("{}/{} ({})", format_count(part), format_count(total), format_percent(part, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1_000), "1,000");
+        assert_eq!(format_count(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_compact_count() {
+        assert_eq!(format_compact_count(9_999), "9,999");
+        assert_eq!(format_compact_count(10_000), "10.0k");
+        assert_eq!(format_compact_count(12_345), "12.3k");
+        assert_eq!(format_compact_count(999_949), "999.9k");
+        assert_eq!(format_compact_count(999_950), "1.0M");
+        assert_eq!(format_compact_count(4_560_000), "4.6M");
+    }
+
+    #[test]
+    fn test_format_percent() {
+        assert_eq!(format_percent(1, 0), "—");
+        assert_eq!(format_percent(0, 10), "0%");
+        assert_eq!(format_percent(10, 10), "100%");
+        assert_eq!(format_percent(1, 3), "33.3%");
+        assert_eq!(format_percent(1, 5_000), "<0.1%");
+        assert_eq!(format_percent(4_999, 5_000), ">99.9%");
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(1_023), "1023 B");
+        assert_eq!(format_size(1_024), "1.0 KB");
+        assert_eq!(format_size(1_536), "1.5 KB");
+        assert_eq!(format_size(1024 * 1024 - 1), "1.0 MB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+}
@@ -0,0 +1,128 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, PatchFile, ProcessingResult, PATCH_CLASSES};
+
+#[server]
+pub async fn handle_list_patch_files(file_paths: Vec<String>) -> Result<Vec<PatchFile>, ApiError> {
+    use crate::api::patch_classes::{load_patch_classes, patch_files};
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    let classes = load_patch_classes(&file_paths)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to load the patch classes").with_details(e))?;
+    Ok(patch_files(&file_paths, &classes))
+}
+
+// `path` is the patch's key from `PatchFile`; an empty class goes back to the file-name guess
+#[server]
+pub async fn handle_save_patch_class(file_paths: Vec<String>, path: String, class: String) -> Result<Vec<PatchFile>, ApiError> {
+    use crate::api::patch_classes::{patch_files, save_patch_class};
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    let classes = save_patch_class(&file_paths, &path, &class)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to save the patch class").with_details(e))?;
+    Ok(patch_files(&file_paths, &classes))
+}
+
+// Class picker for each patch file C7 reads; a change is stored and C7 checked again with it
+#[component]
+pub fn PatchClassesPanel(
+    result: RwSignal<Option<ProcessingResult>>,
+    // Re-checks a rule of the stored analysis and updates its card
+    recheck: impl Fn(&'static str) + Send + Sync + 'static + Copy,
+    // Owned by the Analysis tab, so the list stays open while the tab redraws the re-checked card
+    is_open: RwSignal<bool>,
+) -> impl IntoView {
+    let patches = RwSignal::new(Vec::<PatchFile>::new());
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let Some(deliverable) = result.get() else { return };
+        spawn_local(async move {
+            match handle_list_patch_files(deliverable.file_paths).await {
+                Ok(found) => patches.set(found),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    });
+
+    let save = move |path: String, class: String| {
+        let Some(deliverable) = result.get_untracked() else { return };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_save_patch_class(deliverable.file_paths, path, class).await {
+                Ok(updated) => {
+                    patches.set(updated);
+                    recheck("C7");
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    move || {
+        let files = patches.get();
+        if files.is_empty() {
+            return error.get().map(|e| view! { <div class="mb-3 text-xs text-red-600 dark:text-red-400">{e}</div> }).into_any();
+        }
+        let open = is_open.get();
+        let reclassified = files.iter().filter(|file| file.class != file.guessed).count();
+        let rows = files.into_iter().map(|file| {
+            let path = file.path.clone();
+            let guessed = file.guessed.clone();
+            let options = PATCH_CLASSES.iter().map(|&class| {
+                let label = if class == file.guessed { format!("{} (guessed)", class) } else { class.to_string() };
+                view! { <option value=class selected=class == file.class>{label}</option> }
+            }).collect_view();
+            let name_class = if file.class != file.guessed {
+                "py-1 pr-4 font-mono break-all text-purple-700 dark:text-purple-300"
+            } else {
+                "py-1 pr-4 font-mono break-all text-gray-800 dark:text-gray-200"
+            };
+            view! {
+                <tr class="border-t border-gray-200 dark:border-gray-700">
+                    <td class=name_class title=file.path.clone()>{file.name.clone()}</td>
+                    <td class="py-1">
+                        <select
+                            on:change=move |ev| {
+                                let class = event_target_value(&ev);
+                                // Picking the guess again drops the stored class
+                                let class = if class == guessed { String::new() } else { class };
+                                save(path.clone(), class);
+                            }
+                            disabled=move || busy.get()
+                            class="px-1 py-0.5 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white disabled:opacity-50"
+                        >
+                            {options}
+                        </select>
+                    </td>
+                </tr>
+            }
+        }).collect_view();
+        view! {
+            <div class="mb-3 rounded-lg border border-gray-200 dark:border-gray-700 bg-white dark:bg-gray-800 p-3 text-xs">
+                <button
+                    on:click=move |_| is_open.update(|open| *open = !*open)
+                    class="flex items-center gap-2 text-sm font-medium text-gray-900 dark:text-white"
+                >
+                    {if open { "▾" } else { "▸" }}
+                    "Patch classes (C7)"
+                    <span class="text-xs font-normal text-gray-500 dark:text-gray-400">
+                        {if reclassified > 0 { format!("{} set by a reviewer", reclassified) } else { "guessed from file names".to_string() }}
+                    </span>
+                </button>
+                {open.then(|| view! {
+                    <table class="mt-2 w-full">
+                        <tbody>{rows}</tbody>
+                    </table>
+                    {move || error.get().map(|e| view! { <div class="mt-1 text-red-600 dark:text-red-400">{e}</div> })}
+                })}
+            </div>
+        }.into_any()
+    }
+}
@@ -0,0 +1,29 @@
+use leptos::prelude::*;
+
+use super::types::LogAnalysisResult;
+
+#[component]
+pub fn PatchFailureNotice(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    move || {
+        let failures = log_analysis_result
+            .get()
+            .map(|analysis| analysis.rule_violations.c9_patch_failed_to_apply.examples)
+            .unwrap_or_default();
+        if failures.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        // Examples are "stage: patch: evidence"
+        let mut stages: Vec<&str> = failures.iter().filter_map(|f| f.split(": ").next()).collect();
+        stages.dedup();
+        let label = format!("Patch failed to apply: {}", stages.join(", "));
+
+        view! {
+            <span
+                class="text-sm px-2 py-0.5 rounded border border-red-300 dark:border-red-700 bg-red-50 dark:bg-red-900/30 text-red-800 dark:text-red-200 whitespace-nowrap"
+                title=format!("C9: these stages ran without the patch fully applied\n{}", failures.join("\n"))
+            >
+                {label}
+            </span>
+        }.into_any()
+    }
+}
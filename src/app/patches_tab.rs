@@ -0,0 +1,107 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::components::diff_viewer::DiffViewer;
+use super::types::{ApiError, ParsedPatch, ProcessingResult};
+
+#[server]
+pub async fn handle_list_patches(file_paths: Vec<String>) -> Result<Vec<ParsedPatch>, ApiError> {
+    use crate::api::patch_classes::load_patch_classes;
+    use crate::api::patch_diff::load_patches;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    let classes = load_patch_classes(&file_paths)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to load the patch classes").with_details(e))?;
+    Ok(load_patches(&file_paths, &classes))
+}
+
+// Files past this many start collapsed, so a large patch doesn't render every hunk at once
+const OPEN_FILES: usize = 5;
+
+fn class_badge(class: &str) -> &'static str {
+    match class {
+        "source" => "px-1.5 rounded text-xs bg-blue-100 dark:bg-blue-900/40 text-blue-800 dark:text-blue-200",
+        "ignore" => "px-1.5 rounded text-xs bg-gray-100 dark:bg-gray-700 text-gray-600 dark:text-gray-300",
+        _ => "px-1.5 rounded text-xs bg-purple-100 dark:bg-purple-900/40 text-purple-800 dark:text-purple-200",
+    }
+}
+
+fn patch_view(patch: ParsedPatch) -> AnyView {
+    if let Some(error) = patch.error {
+        return view! { <div class="text-sm text-red-600 dark:text-red-400">{error}</div> }.into_any();
+    }
+    if patch.files.is_empty() {
+        return view! { <div class="text-sm text-gray-500 dark:text-gray-400">"No file changes in this patch"</div> }.into_any();
+    }
+    patch.files.into_iter().enumerate().map(|(index, file)| view! {
+        <DiffViewer file=file initially_open=index < OPEN_FILES/>
+    }).collect_view().into_any()
+}
+
+// The deliverable's golden and test patches, read side by side
+#[component]
+pub fn PatchesTab(result: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let patches = RwSignal::new(Vec::<ParsedPatch>::new());
+    let selected = RwSignal::new(0usize);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let Some(deliverable) = result.get() else { return };
+        loading.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_list_patches(deliverable.file_paths).await {
+                Ok(found) => {
+                    selected.set(0);
+                    patches.set(found);
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            loading.set(false);
+        });
+    });
+
+    view! {
+        <div class="h-full flex">
+            <div class="w-64 flex-shrink-0 border-r border-gray-200 dark:border-gray-700 overflow-y-auto p-2">
+                {move || {
+                    if loading.get() {
+                        return view! { <div class="text-sm text-gray-500 dark:text-gray-400">"Loading..."</div> }.into_any();
+                    }
+                    if let Some(e) = error.get() {
+                        return view! { <div class="text-sm text-red-600 dark:text-red-400">{e}</div> }.into_any();
+                    }
+                    let list = patches.get();
+                    if list.is_empty() {
+                        return view! { <div class="text-sm text-gray-500 dark:text-gray-400">"No patch files in this deliverable"</div> }.into_any();
+                    }
+                    list.into_iter().enumerate().map(|(index, patch)| {
+                        let added: usize = patch.files.iter().map(|file| file.added).sum();
+                        let removed: usize = patch.files.iter().map(|file| file.removed).sum();
+                        let row_class = move || if selected.get() == index {
+                            "w-full text-left px-2 py-1.5 rounded bg-blue-50 dark:bg-blue-900/30"
+                        } else {
+                            "w-full text-left px-2 py-1.5 rounded hover:bg-gray-100 dark:hover:bg-gray-700"
+                        };
+                        view! {
+                            <button on:click=move |_| selected.set(index) class=row_class>
+                                <div class="font-mono text-sm text-gray-900 dark:text-white break-all">{patch.name.clone()}</div>
+                                <div class="flex items-center gap-2 mt-0.5 text-xs text-gray-500 dark:text-gray-400">
+                                    <span class=class_badge(&patch.class)>{patch.class.clone()}</span>
+                                    {format!("{} file(s)", patch.files.len())}
+                                    <span class="text-green-700 dark:text-green-400">{format!("+{}", added)}</span>
+                                    <span class="text-red-600 dark:text-red-400">{format!("−{}", removed)}</span>
+                                </div>
+                            </button>
+                        }
+                    }).collect_view().into_any()
+                }}
+            </div>
+            <div class="flex-1 min-w-0 overflow-y-auto p-3">
+                {move || patches.get().into_iter().nth(selected.get()).map(patch_view)}
+            </div>
+        </div>
+    }
+}
@@ -1,46 +1,102 @@
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use super::types::{ValidationResult, DownloadResult, ProcessingResult, ProcessingStage, StageStatus, FileInfo};
+use super::types::{ActionsRun, ApiError, ApiErrorCode, StructureCheckItem, ValidationResult, DownloadProgress, DownloadResult, ProcessingResult, ProcessingStage, StageStatus, FileInfo, PlatformTask};
+use super::download_progress::watch_download_progress;
 use std::collections::HashMap;
 
 #[server]
-pub async fn handle_validate_deliverable(deliverable_link: String) -> Result<ValidationResult, ServerFnError> {
+pub async fn handle_validate_deliverable(deliverable_link: String, logs_only: bool) -> Result<ValidationResult, ApiError> {
     use crate::api::deliverable::{validate_deliverable_impl};
-    match validate_deliverable_impl(deliverable_link).await {
+    match validate_deliverable_impl(deliverable_link, logs_only).await {
         Ok(result) => Ok(result),
-        Err(e) => Err(ServerFnError::ServerError(format!("Failed to validate deliverable: {}", e)))
+        Err(e) => Err(ApiError { message: format!("Failed to validate deliverable: {}", e.message), ..e })
     }
 }
 
 
 #[server]
-pub async fn handle_download_deliverable(files_to_download: Vec<FileInfo>, folder_id: String) -> Result<DownloadResult, ServerFnError> {
+pub async fn handle_resolve_drive_link(link: String) -> Result<String, ApiError> {
+    use crate::api::deliverable::resolve_deliverable_link;
+    resolve_deliverable_link(&link).await
+}
+
+
+#[server]
+pub async fn handle_resolve_platform_link(link: String) -> Result<PlatformTask, ServerFnError> {
+    use crate::api::reviewer_platform::resolve_platform_link;
+    match resolve_platform_link(&link).await {
+        Ok(task) => Ok(task),
+        Err(e) => Err(ServerFnError::ServerError(format!("Failed to resolve reviewer-platform link: {}", e)))
+    }
+}
+
+
+// `progress_id` names the job `/api/download_progress/{job_id}` streams, when the page watches it
+#[server]
+pub async fn handle_download_deliverable(files_to_download: Vec<FileInfo>, folder_id: String, progress_id: Option<String>) -> Result<DownloadResult, ApiError> {
     use crate::api::deliverable::{download_deliverable_impl};
-    match download_deliverable_impl(files_to_download, folder_id).await {
+    use crate::api::download_progress::DownloadReporter;
+    let progress = DownloadReporter::start(progress_id, &files_to_download);
+    let result = download_deliverable_impl(files_to_download, folder_id, &progress).await;
+    progress.end();
+    match result {
         Ok(result) => Ok(result),
-        Err(e) => Err(ServerFnError::ServerError(format!("Failed to download deliverable: {}", e)))
+        Err(e) => Err(ApiError { message: format!("Failed to download deliverable: {}", e.message), ..e })
     }
 }
 
 
+// Unpacks a downloaded GitHub Actions run into the deliverable layout
+#[server]
+pub async fn handle_extract_artifacts(folder_id: String, logs_only: bool) -> Result<DownloadResult, ApiError> {
+    use crate::api::github_actions::extract_actions_artifacts;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ACTIONS_FOLDER_PREFIX;
+    check_deliverable_paths(std::slice::from_ref(&folder_id))?;
+    if !folder_id.starts_with(ACTIONS_FOLDER_PREFIX) {
+        return Err(ApiError::new(ApiErrorCode::InvalidLink, "Only GitHub Actions runs have artifacts to extract"));
+    }
+    match extract_actions_artifacts(&folder_id, logs_only) {
+        Ok(result) => Ok(result),
+        Err(e) => Err(ApiError { message: format!("Failed to extract artifacts: {}", e.message), ..e })
+    }
+}
+
+
+// Lets a restored session skip the download when the server still has the files
+#[server]
+pub async fn handle_check_downloads(file_paths: Vec<String>) -> Result<bool, ApiError> {
+    use crate::api::deliverable::downloads_present;
+    use crate::api::path_guard::check_deliverable_paths;
+    check_deliverable_paths(&file_paths)?;
+    downloads_present(&file_paths)
+}
+
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle_submit(
     deliverable_link: RwSignal<String>,
     is_processing: RwSignal<bool>,
     current_stage: RwSignal<Option<ProcessingStage>>,
     stages: RwSignal<HashMap<ProcessingStage, StageStatus>>,
     result: RwSignal<Option<ProcessingResult>>,
-    error: RwSignal<Option<String>>,
+    error: RwSignal<Option<ApiError>>,
+    structure: RwSignal<Vec<StructureCheckItem>>,
+    download_progress: RwSignal<Option<DownloadProgress>>,
+    platform_task_id: String,
+    logs_only: bool,
     load_test_lists: impl Fn() + Send + Sync + 'static + Copy,
 ) {
     let link = deliverable_link.get().trim().to_string();
     if link.is_empty() {
-        error.set(Some("Please enter a deliverable link".to_string()));
+        error.set(Some(ApiError::new(ApiErrorCode::InvalidLink, "Please enter a deliverable link")));
         return;
     }
 
     is_processing.set(true);
     error.set(None);
     result.set(None);
+    structure.set(Vec::new());
 
     let update_stage_status = move |stage: ProcessingStage, status: StageStatus| {
         stages.update(|stages| {
@@ -53,17 +109,44 @@ pub fn handle_submit(
         current_stage.set(Some(ProcessingStage::Validating));
         update_stage_status(ProcessingStage::Validating, StageStatus::Active);
 
-        let validation_result = handle_validate_deliverable(link.clone()).await;
+        let validation_result = handle_validate_deliverable(link.clone(), logs_only).await;
 
         match validation_result {
             Ok(validation_data) => {
                 update_stage_status(ProcessingStage::Validating, StageStatus::Completed);
+                structure.set(validation_data.structure.clone());
 
                 // Stage 2: Downloading
                 current_stage.set(Some(ProcessingStage::Downloading));
                 update_stage_status(ProcessingStage::Downloading, StageStatus::Active);
 
-                let download_result = handle_download_deliverable(validation_data.files_to_download, validation_data.folder_id).await;
+                let folder_id = validation_data.folder_id.clone();
+                let progress_id = uuid::Uuid::new_v4().to_string();
+                let progress_watch = watch_download_progress(&progress_id, download_progress);
+                let download_result = handle_download_deliverable(validation_data.files_to_download, validation_data.folder_id, Some(progress_id)).await;
+                progress_watch.close();
+
+                // Stage 2b: Extracting, only for GitHub Actions runs
+                let download_result = match download_result {
+                    Ok(_) if ActionsRun::parse(&link).is_some() => {
+                        update_stage_status(ProcessingStage::Downloading, StageStatus::Completed);
+                        current_stage.set(Some(ProcessingStage::Extracting));
+                        update_stage_status(ProcessingStage::Extracting, StageStatus::Active);
+                        match handle_extract_artifacts(folder_id, logs_only).await {
+                            Ok(extracted) => {
+                                update_stage_status(ProcessingStage::Extracting, StageStatus::Completed);
+                                Ok(extracted)
+                            }
+                            Err(e) => {
+                                error.set(Some(e));
+                                update_stage_status(ProcessingStage::Extracting, StageStatus::Error);
+                                current_stage.set(None);
+                                return;
+                            }
+                        }
+                    }
+                    other => other,
+                };
 
                 match download_result {
                     Ok(download_data) => {
@@ -84,6 +167,8 @@ pub fn handle_submit(
                             gold_patch: String::new(),
                             test_patch: String::new(),
                             language: String::new(),
+                            platform_task_id: platform_task_id.clone(),
+                            logs_only,
                         };
 
                         result.set(Some(processing_result));
@@ -96,14 +181,14 @@ pub fn handle_submit(
                         load_test_lists();
                     }
                     Err(e) => {
-                        error.set(Some(e.to_string()));
+                        error.set(Some(e));
                         update_stage_status(ProcessingStage::Downloading, StageStatus::Error);
                         current_stage.set(None);
                     }
                 }
             }
             Err(e) => {
-                error.set(Some(e.to_string()));
+                error.set(Some(e));
                 update_stage_status(ProcessingStage::Validating, StageStatus::Error);
                 current_stage.set(None);
                 is_processing.set(false);
@@ -111,3 +196,50 @@ pub fn handle_submit(
         }
     });
 }
+
+// Validate and download the current deliverable again, keeping everything derived from main.json so the
+// checker stays mounted; `on_refreshed` runs once the new files are in place
+pub fn handle_refresh(
+    result: RwSignal<Option<ProcessingResult>>,
+    is_refreshing: RwSignal<bool>,
+    refresh_error: RwSignal<Option<String>>,
+    on_refreshed: impl Fn() + Send + Sync + 'static,
+) {
+    let Some(previous) = result.get_untracked() else {
+        return;
+    };
+
+    is_refreshing.set(true);
+    refresh_error.set(None);
+
+    spawn_local(async move {
+        let validation_data = match handle_validate_deliverable(previous.deliverable_link.clone(), previous.logs_only).await {
+            Ok(validation_data) => validation_data,
+            Err(e) => {
+                refresh_error.set(Some(format!("{} — {}", e, e.guidance())));
+                is_refreshing.set(false);
+                return;
+            }
+        };
+
+        let folder_id = validation_data.folder_id.clone();
+        let mut download_result = handle_download_deliverable(validation_data.files_to_download, validation_data.folder_id, None).await;
+        if download_result.is_ok() && ActionsRun::parse(&previous.deliverable_link).is_some() {
+            download_result = handle_extract_artifacts(folder_id, previous.logs_only).await;
+        }
+
+        match download_result {
+            Ok(download_data) => {
+                result.set(Some(ProcessingResult {
+                    file_paths: download_data.downloaded_files.iter().map(|f| f.path.clone()).collect(),
+                    ..previous
+                }));
+                on_refreshed();
+            }
+            Err(e) => {
+                refresh_error.set(Some(format!("{} — {}", e, e.guidance())));
+                is_refreshing.set(false);
+            }
+        }
+    });
+}
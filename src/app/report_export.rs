@@ -0,0 +1,107 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ExportedReport, ProcessingResult};
+
+// Served at /api/export_report. The stored analysis is exported, so the reviewer's lists and
+// overrides are in it; a deliverable never analyzed is analyzed first
+#[server(endpoint = "export_report")]
+pub async fn handle_export_report(file_paths: Vec<String>, instance_id: String, task_id: String) -> Result<ExportedReport, ApiError> {
+    use crate::api::analysis_store::load_analysis_result;
+    use crate::api::log_analysis::analyze_logs;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::api::report_export::export_report;
+    use crate::api::swebench_dataset::cross_check_with_dataset;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+
+    let analysis = match load_analysis_result(&file_paths) {
+        Some(analysis) => analysis,
+        None => {
+            let paths_for_analysis = file_paths.clone();
+            let mut analysis = tokio::task::spawn_blocking(move || analyze_logs(paths_for_analysis))
+                .await
+                .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Analysis task failed").with_details(e))?
+                .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to analyze logs").with_details(e))?;
+            cross_check_with_dataset(&file_paths, &mut analysis).await;
+            analysis
+        }
+    };
+    export_report(&instance_id, &task_id, &analysis)
+        .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Failed to export the report").with_details(e))
+}
+
+fn download_link(href: String, file_name: String, label: &'static str) -> AnyView {
+    view! {
+        <a
+            href=href
+            download=file_name
+            class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-blue-600 hover:text-blue-800 dark:text-blue-400"
+        >
+            {label}
+        </a>
+    }.into_any()
+}
+
+#[component]
+pub fn ReportExportButton(current_deliverable: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let report = RwSignal::new(None::<ExportedReport>);
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    // Drop a stale export when the reviewer moves to another deliverable
+    Effect::new(move |_| {
+        current_deliverable.track();
+        report.set(None);
+        error.set(None);
+    });
+
+    let export = move |_| {
+        let Some(deliverable) = current_deliverable.get_untracked() else {
+            return;
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_export_report(deliverable.file_paths.clone(), deliverable.instance_id.clone(), deliverable.display_task_id()).await {
+                Ok(exported) => report.set(Some(exported)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    let base_name = move || {
+        current_deliverable
+            .get()
+            .map(|d| if d.instance_id.is_empty() { "deliverable".to_string() } else { d.instance_id })
+            .unwrap_or_else(|| "deliverable".to_string())
+    };
+
+    move || match report.get() {
+        Some(exported) => view! {
+            <div class="flex items-center gap-2">
+                {download_link(
+                    format!("data:application/json;charset=utf-8,{}", urlencoding::encode(&exported.json)),
+                    format!("{}-report.json", base_name()),
+                    "Report JSON",
+                )}
+                {download_link(
+                    format!("data:text/markdown;charset=utf-8,{}", urlencoding::encode(&exported.markdown)),
+                    format!("{}-report.md", base_name()),
+                    "Report Markdown",
+                )}
+            </div>
+        }.into_any(),
+        None => view! {
+            <button
+                on:click=export
+                disabled=move || busy.get()
+                title=move || error.get().unwrap_or_else(|| "Export the analysis as JSON and Markdown".to_string())
+                class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 disabled:opacity-50"
+            >
+                {move || if busy.get() { "Exporting..." } else if error.get().is_some() { "Export (failed)" } else { "Export" }}
+            </button>
+        }.into_any(),
+    }
+}
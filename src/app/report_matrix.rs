@@ -0,0 +1,212 @@
+use leptos::prelude::*;
+
+use super::types::LogAnalysisResult;
+
+// Agent log statuses as (key, column label); a test the analysis has no status for is missing
+const AGENT_COLUMNS: &[(&str, &str)] = &[
+    ("passed", "Passed"),
+    ("failed", "Failed"),
+    ("ignored", "Ignored"),
+    ("missing", "Missing"),
+];
+
+/// One report.json category, e.g. the FAIL_TO_PASS successes
+#[derive(Clone, Copy)]
+pub struct ReportCategory {
+    pub label: &'static str,
+    pub test_type: &'static str,
+    // Whether report.json says the tests in this category passed
+    pub report_passed: bool,
+    pub tests: RwSignal<Vec<String>>,
+}
+
+fn agent_status(test_name: &str, test_type: &str, analysis: &LogAnalysisResult) -> &'static str {
+    let statuses = if test_type == "fail_to_pass" {
+        &analysis.test_statuses.f2p
+    } else {
+        &analysis.test_statuses.p2p
+    };
+    match statuses.get(test_name).map(|summary| summary.agent.as_str()) {
+        Some("passed") => "passed",
+        Some("failed") => "failed",
+        Some("ignored") => "ignored",
+        _ => "missing",
+    }
+}
+
+// Tests of one category split by their agent status, in `AGENT_COLUMNS` order
+fn split_by_agent_status(tests: &[String], test_type: &str, analysis: &LogAnalysisResult) -> Vec<Vec<String>> {
+    let mut cells = vec![Vec::new(); AGENT_COLUMNS.len()];
+    for test in tests {
+        let status = agent_status(test, test_type, analysis);
+        let column = AGENT_COLUMNS.iter().position(|(key, _)| *key == status).unwrap_or(AGENT_COLUMNS.len() - 1);
+        cells[column].push(test.clone());
+    }
+    cells
+}
+
+// The C6 cells: report.json and the agent log disagree on whether the test passed
+fn is_mismatch(report_passed: bool, agent_status: &str) -> bool {
+    matches!((report_passed, agent_status), (true, "failed") | (false, "passed"))
+}
+
+fn is_agreement(report_passed: bool, agent_status: &str) -> bool {
+    matches!((report_passed, agent_status), (true, "passed") | (false, "failed"))
+}
+
+fn cell_class(report_passed: bool, agent_status: &str, count: usize, expanded: bool) -> String {
+    let color = if count == 0 {
+        "text-gray-400 dark:text-gray-500 cursor-default"
+    } else if is_mismatch(report_passed, agent_status) {
+        "bg-red-100 dark:bg-red-900/40 text-red-800 dark:text-red-200 font-semibold hover:bg-red-200 dark:hover:bg-red-900/60"
+    } else if is_agreement(report_passed, agent_status) {
+        "bg-green-50 dark:bg-green-900/30 text-green-800 dark:text-green-200 hover:bg-green-100 dark:hover:bg-green-900/50"
+    } else {
+        "bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200 hover:bg-yellow-100 dark:hover:bg-yellow-900/50"
+    };
+    let ring = if expanded { "ring-2 ring-blue-500 dark:ring-blue-400" } else { "" };
+    format!("w-full px-2 py-1 rounded tabular-nums {} {}", color, ring)
+}
+
+/// report.json categories crossed with the agent log statuses; a cell opens the tests in it and
+/// picking one selects it in the report lists
+pub fn cross_check_matrix<F>(
+    categories: [ReportCategory; 4],
+    log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
+    open: RwSignal<bool>,
+    expanded: RwSignal<Option<(usize, usize)>>,
+    pick_test: F,
+) -> AnyView
+where
+    F: Fn(String, &'static str) + Copy + Send + Sync + 'static,
+{
+    let Some(analysis) = log_analysis_result.get() else {
+        return view! { <div></div> }.into_any();
+    };
+    let cells: Vec<Vec<Vec<String>>> = categories
+        .iter()
+        .map(|category| split_by_agent_status(&category.tests.get(), category.test_type, &analysis))
+        .collect();
+    if cells.iter().flatten().all(Vec::is_empty) {
+        return view! { <div></div> }.into_any();
+    }
+    let mismatches: usize = categories
+        .iter()
+        .zip(&cells)
+        .flat_map(|(category, row)| {
+            AGENT_COLUMNS
+                .iter()
+                .zip(row)
+                .filter(|((key, _), _)| is_mismatch(category.report_passed, key))
+                .map(|(_, tests)| tests.len())
+        })
+        .sum();
+    let summary = match mismatches {
+        0 => "no mismatches".to_string(),
+        1 => "1 mismatch".to_string(),
+        count => format!("{} mismatches", count),
+    };
+    let summary_class = if mismatches > 0 { "text-red-700 dark:text-red-300 font-medium" } else { "text-green-700 dark:text-green-300" };
+    let is_open = open.get();
+    let header = view! {
+        <button
+            on:click=move |_| open.update(|open| *open = !*open)
+            class="flex items-center gap-2 w-full px-4 py-1 text-xs text-left text-gray-700 dark:text-gray-300 hover:bg-gray-50 dark:hover:bg-gray-700"
+        >
+            <span>{if is_open { "▾" } else { "▸" }}</span>
+            <span>"Cross-check: report.json × agent log"</span>
+            <span class=summary_class>{format!("({})", summary)}</span>
+        </button>
+    };
+    if !is_open {
+        return view! {
+            <div class="border-b border-gray-200 dark:border-gray-700">{header}</div>
+        }.into_any();
+    }
+
+    let current = expanded.get();
+    let column_headers = AGENT_COLUMNS
+        .iter()
+        .map(|(_, label)| view! { <th class="px-2 py-1 font-medium">{format!("Agent {}", label.to_lowercase())}</th> })
+        .collect_view();
+    let rows = categories.iter().zip(&cells).enumerate().map(|(row_index, (category, row))| {
+        let report_passed = category.report_passed;
+        let row_cells = AGENT_COLUMNS.iter().zip(row).enumerate().map(|(column_index, ((key, label), tests))| {
+            let count = tests.len();
+            let is_expanded = current == Some((row_index, column_index));
+            let title = if is_mismatch(report_passed, key) {
+                format!("C6: {} in report.json but {} in the agent log", if report_passed { "passed" } else { "failed" }, label.to_lowercase())
+            } else {
+                format!("{} · agent {}", category.label, label.to_lowercase())
+            };
+            view! {
+                <td class="px-1 py-0.5 text-center">
+                    <button
+                        disabled=count == 0
+                        title=title
+                        on:click=move |_| expanded.update(|expanded| {
+                            *expanded = if *expanded == Some((row_index, column_index)) { None } else { Some((row_index, column_index)) };
+                        })
+                        class=cell_class(report_passed, key, count, is_expanded)
+                    >
+                        {count.to_string()}
+                    </button>
+                </td>
+            }
+        }).collect_view();
+        view! {
+            <tr>
+                <th class="px-2 py-1 font-medium text-left whitespace-nowrap">{format!("{} ({})", category.label, category.tests.get_untracked().len())}</th>
+                {row_cells}
+            </tr>
+        }
+    }).collect_view();
+
+    let expanded_list = match current.and_then(|(row, column)| Some((categories.get(row)?, AGENT_COLUMNS.get(column)?, cells.get(row)?.get(column)?))) {
+        Some((category, (_, label), tests)) if !tests.is_empty() => {
+            let test_type = category.test_type;
+            let items = tests.iter().cloned().map(|name| {
+                let name_for_click = name.clone();
+                view! {
+                    <li
+                        on:click=move |_| pick_test(name_for_click.clone(), test_type)
+                        class="px-2 py-0.5 font-mono break-all cursor-pointer hover:bg-gray-100 dark:hover:bg-gray-700"
+                    >
+                        {name}
+                    </li>
+                }
+            }).collect_view();
+            view! {
+                <div class="flex-1 min-w-0">
+                    <div class="text-xs font-medium text-gray-700 dark:text-gray-300 mb-1">
+                        {format!("{} · agent {} ({})", category.label, label.to_lowercase(), tests.len())}
+                    </div>
+                    <ul class="max-h-40 overflow-auto text-xs text-gray-800 dark:text-gray-200 border border-gray-200 dark:border-gray-700 rounded">
+                        {items}
+                    </ul>
+                </div>
+            }.into_any()
+        }
+        _ => view! {
+            <div class="flex-1 text-xs text-gray-500 dark:text-gray-400 self-center">"Pick a cell to list its tests"</div>
+        }.into_any(),
+    };
+
+    view! {
+        <div class="border-b border-gray-200 dark:border-gray-700">
+            {header}
+            <div class="flex items-start gap-4 px-4 pb-2">
+                <table class="text-xs text-gray-700 dark:text-gray-300">
+                    <thead>
+                        <tr>
+                            <th class="px-2 py-1 font-medium text-left">"report.json"</th>
+                            {column_headers}
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+                {expanded_list}
+            </div>
+        </div>
+    }.into_any()
+}
@@ -1,9 +1,12 @@
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 
-use super::types::{ProcessingResult, FileContents, LoadedFileTypes, LogAnalysisResult, SearchResult};
+use super::types::{example_test_name, ApiError, ProcessingResult, FileContents, LoadedFileTypes, LogAnalysisResult, ReportCategories, SearchResult};
 use super::file_operations::load_file_contents;
 use super::search_results::handle_search_agent_logs;
+use super::report_matrix::{cross_check_matrix, ReportCategory};
+use super::trace_context::TraceContextView;
+use super::number_format::format_count;
 
 fn render_status_icon(status: &str) -> AnyView {
     match status {
@@ -26,44 +29,14 @@ fn render_status_icon(status: &str) -> AnyView {
     }
 }
 
-fn parse_report_lists(content: &str) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
-    let mut f2p_success: Vec<String> = Vec::new();
-    let mut f2p_failure: Vec<String> = Vec::new();
-    let mut p2p_success: Vec<String> = Vec::new();
-    let mut p2p_failure: Vec<String> = Vec::new();
-
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(content) {
-        // Try to find tests_status at root or one level nested
-        let mut tests_status: Option<serde_json::Value> = None;
-        if let Some(ts) = json.get("tests_status").cloned() { tests_status = Some(ts); }
-        if tests_status.is_none() {
-            if let Some(obj) = json.as_object() {
-                for (_k, v) in obj {
-                    if let Some(ts) = v.get("tests_status") { tests_status = Some(ts.clone()); break; }
-                }
-            }
-        }
-
-        if let Some(ts) = tests_status {
-            let empty: Vec<serde_json::Value> = vec![];
-            // FAIL_TO_PASS
-            if let Some(f2p) = ts.get("FAIL_TO_PASS") {
-                f2p_success = f2p.get("success").and_then(|a| a.as_array()).unwrap_or(&empty)
-                    .iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect();
-                f2p_failure = f2p.get("failure").and_then(|a| a.as_array()).unwrap_or(&empty)
-                    .iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect();
-            }
-            // PASS_TO_PASS
-            if let Some(p2p) = ts.get("PASS_TO_PASS") {
-                p2p_success = p2p.get("success").and_then(|a| a.as_array()).unwrap_or(&empty)
-                    .iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect();
-                p2p_failure = p2p.get("failure").and_then(|a| a.as_array()).unwrap_or(&empty)
-                    .iter().filter_map(|v| v.as_str()).map(|s| s.to_string()).collect();
-            }
-        }
-    }
-
-    (f2p_success, f2p_failure, p2p_success, p2p_failure)
+#[server]
+pub async fn handle_report_categories(file_paths: Vec<String>) -> Result<Option<ReportCategories>, ApiError> {
+    use crate::api::log_analysis::report_categories;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    report_categories(file_paths)
+        .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to read report.json").with_details(e))
 }
 
 fn get_stage_status(
@@ -88,6 +61,81 @@ fn get_stage_status(
     } else { "not_supported".to_string() }
 }
 
+// Status of a test in every agent run, for deliverables that ship several agent logs
+fn agent_run_statuses(test_name: &str, analysis: &Option<LogAnalysisResult>) -> Vec<(String, String)> {
+    analysis
+        .as_ref()
+        .map(|analysis| {
+            analysis
+                .agent_runs
+                .iter()
+                .map(|run| (run.log_name.clone(), run.statuses.get(test_name).cloned().unwrap_or_else(|| "missing".to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+const ALL_RUNS: &str = "__all_runs__";
+
+fn agent_run_picker(
+    log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
+    selected_agent_log: RwSignal<Option<String>>,
+    show_all_runs: RwSignal<bool>,
+) -> AnyView {
+    let Some(analysis) = log_analysis_result.get() else {
+        return view! { <div></div> }.into_any();
+    };
+    if analysis.agent_runs.len() < 2 {
+        return view! { <div></div> }.into_any();
+    }
+    let current = if show_all_runs.get() {
+        ALL_RUNS.to_string()
+    } else {
+        analysis.debug_info.agent_log.clone().unwrap_or_default()
+    };
+    let options = analysis.agent_runs.iter().map(|run| {
+        let name = run.log_name.clone();
+        let label = name.clone();
+        let selected = name == current;
+        view! { <option value=name selected=selected>{label}</option> }
+    }).collect_view();
+
+    view! {
+        <div class="flex items-center gap-2 px-4 py-1 border-b border-gray-200 dark:border-gray-700 text-xs text-gray-700 dark:text-gray-300">
+            <span>{format!("{} agent runs:", analysis.agent_runs.len())}</span>
+            <select
+                on:change=move |ev| {
+                    let value = event_target_value(&ev);
+                    if value == ALL_RUNS {
+                        show_all_runs.set(true);
+                    } else {
+                        show_all_runs.set(false);
+                        selected_agent_log.set(Some(value));
+                    }
+                }
+                class="px-2 py-0.5 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-800 text-gray-900 dark:text-white"
+            >
+                {options}
+                <option value=ALL_RUNS selected=current == ALL_RUNS>"All runs"</option>
+            </select>
+        </div>
+    }.into_any()
+}
+
+fn report_schema_notice(log_analysis_result: RwSignal<Option<LogAnalysisResult>>, categories: RwSignal<Option<ReportCategories>>) -> AnyView {
+    let split_notice = categories.get().filter(|categories| !categories.from_report).map(|categories| {
+        format!("report.json uses the {} layout, which has no F2P/P2P categories; its statuses are split by the test lists", categories.schema)
+    });
+    match log_analysis_result.get().and_then(|analysis| analysis.debug_info.report_diagnostic).or(split_notice) {
+        Some(diagnostic) => view! {
+            <div class="px-4 py-1 border-b border-yellow-200 dark:border-yellow-800 bg-yellow-50 dark:bg-yellow-900/30 text-xs text-yellow-800 dark:text-yellow-200">
+                {diagnostic}
+            </div>
+        }.into_any(),
+        None => view! { <div></div> }.into_any(),
+    }
+}
+
 #[component]
 pub fn ReportTab(
     result: RwSignal<Option<ProcessingResult>>,
@@ -96,8 +144,10 @@ pub fn ReportTab(
     loaded_file_types: RwSignal<LoadedFileTypes>,
     log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
     selected_test_name: RwSignal<String>,
+    selected_agent_log: RwSignal<Option<String>>,
 ) -> impl IntoView {
     let selected_test_type = RwSignal::new(String::from("fail_to_pass"));
+    let show_all_runs = RwSignal::new(false);
 
     let agent_results = RwSignal::new(Vec::<SearchResult>::new());
     let agent_index = RwSignal::new(0usize);
@@ -121,53 +171,98 @@ pub fn ReportTab(
         }
     });
 
-    // Parse lists from report json when it is available
+    // Categories from report.json, whatever its layout, once it is available
+    let report_categories = RwSignal::new(None::<ReportCategories>);
     let f2p_success = RwSignal::new(Vec::<String>::new());
     let f2p_failure = RwSignal::new(Vec::<String>::new());
     let p2p_success = RwSignal::new(Vec::<String>::new());
     let p2p_failure = RwSignal::new(Vec::<String>::new());
+    // Deliverable the lists above belong to
+    let loaded_categories_for = RwSignal::new(None::<Vec<String>>);
 
     // Search agent log when selected test changes (declare before using below)
     let trigger_agent_search = move |test_name: String| {
         if result.get().is_none() || test_name.is_empty() { return; }
         let res = result.get().unwrap();
+        let agent_log = selected_agent_log.get_untracked();
         spawn_local(async move {
-            if let Ok(items) = handle_search_agent_logs(res.file_paths, test_name).await {
+            if let Ok(items) = handle_search_agent_logs(res.file_paths, test_name, agent_log).await {
                 agent_results.set(items);
                 agent_index.set(0);
             }
         });
     };
 
-    Effect::new({
-        let file_contents = file_contents.clone();
-        let selected_test_name = selected_test_name.clone();
-        let selected_test_type = selected_test_type.clone();
-        move |_| {
-            if let Some(report) = &file_contents.get().report {
-                let (a,b,c,d) = parse_report_lists(&report.content);
-                f2p_success.set(a.clone());
-                f2p_failure.set(b.clone());
-                p2p_success.set(c.clone());
-                p2p_failure.set(d.clone());
+    // Show the newly picked agent run's lines for the current test
+    Effect::new(move |prev: Option<Option<String>>| {
+        let agent_log = selected_agent_log.get();
+        if prev.is_some_and(|prev| prev != agent_log) {
+            untrack(|| trigger_agent_search(selected_test_name.get()));
+        }
+        agent_log
+    });
 
-                // Auto-select first available test in priority order if nothing selected yet
-                if selected_test_name.get().is_empty() {
-                    let pick = if let Some(first) = a.first() { Some((first.clone(), "fail_to_pass")) }
-                        else if let Some(first) = b.first() { Some((first.clone(), "fail_to_pass")) }
-                        else if let Some(first) = c.first() { Some((first.clone(), "pass_to_pass")) }
-                        else if let Some(first) = d.first() { Some((first.clone(), "pass_to_pass")) }
-                        else { None };
-                    if let Some((name, ty)) = pick {
-                        selected_test_name.set(name.clone());
-                        selected_test_type.set(ty.to_string());
-                        trigger_agent_search(name);
-                    }
+    // Refetch when a new analysis lands, since custom test lists change how other layouts split
+    Effect::new(move |_| {
+        let res = result.get();
+        log_analysis_result.track();
+        // Drop the previous deliverable's lists right away
+        if loaded_categories_for.get_untracked() != res.as_ref().map(|res| res.file_paths.clone()) {
+            loaded_categories_for.set(None);
+            report_categories.set(None);
+            f2p_success.set(Vec::new());
+            f2p_failure.set(Vec::new());
+            p2p_success.set(Vec::new());
+            p2p_failure.set(Vec::new());
+        }
+        let Some(res) = res else { return };
+        let file_paths = res.file_paths;
+        spawn_local(async move {
+            let categories = handle_report_categories(file_paths.clone()).await;
+            // Ignore responses for a deliverable that is no longer open
+            if result.get_untracked().map(|res| res.file_paths) != Some(file_paths.clone()) { return; }
+            let Ok(Some(categories)) = categories else { return };
+            loaded_categories_for.set(Some(file_paths));
+            f2p_success.set(categories.f2p_success.clone());
+            f2p_failure.set(categories.f2p_failure.clone());
+            p2p_success.set(categories.p2p_success.clone());
+            p2p_failure.set(categories.p2p_failure.clone());
+
+            // Auto-select first available test in priority order if nothing selected yet
+            if selected_test_name.get_untracked().is_empty() {
+                let pick = [
+                    (&categories.f2p_success, "fail_to_pass"),
+                    (&categories.f2p_failure, "fail_to_pass"),
+                    (&categories.p2p_success, "pass_to_pass"),
+                    (&categories.p2p_failure, "pass_to_pass"),
+                ]
+                .into_iter()
+                .find_map(|(tests, ty)| tests.first().map(|first| (first.clone(), ty)));
+                if let Some((name, ty)) = pick {
+                    selected_test_name.set(name.clone());
+                    selected_test_type.set(ty.to_string());
+                    trigger_agent_search(name);
                 }
             }
-        }
+            report_categories.set(Some(categories));
+        });
     });
 
+    // Cross-check matrix: report categories against agent statuses
+    let matrix_categories = [
+        ReportCategory { label: "F2P success", test_type: "fail_to_pass", report_passed: true, tests: f2p_success },
+        ReportCategory { label: "F2P failure", test_type: "fail_to_pass", report_passed: false, tests: f2p_failure },
+        ReportCategory { label: "P2P success", test_type: "pass_to_pass", report_passed: true, tests: p2p_success },
+        ReportCategory { label: "P2P failure", test_type: "pass_to_pass", report_passed: false, tests: p2p_failure },
+    ];
+    let matrix_open = RwSignal::new(false);
+    let matrix_cell = RwSignal::new(None::<(usize, usize)>);
+    let pick_test = move |name: String, test_type: &'static str| {
+        selected_test_name.set(name.clone());
+        selected_test_type.set(test_type.to_string());
+        trigger_agent_search(name);
+    };
+
     // Filters
     let f2p_success_filter = RwSignal::new(String::new());
     let p2p_success_filter = RwSignal::new(String::new());
@@ -181,8 +276,7 @@ pub fn ReportTab(
             let c6 = &analysis.rule_violations.c6_test_marked_failed_in_report_but_passing_in_agent;
             if !c6.has_problem { return false; }
             for example in &c6.examples {
-                let clean_example = example.split(" (").next().unwrap_or(example);
-                if clean_example == test_name { return true; }
+                if example_test_name(example) == test_name { return true; }
             }
         }
         false
@@ -194,7 +288,7 @@ pub fn ReportTab(
                 <div class="bg-gray-50 dark:bg-gray-700 px-4 py-2 border-b border-gray-200 dark:border-gray-600">
                     <div class="flex items-center justify-between gap-3">
                         <h4 class="font-medium text-gray-900 dark:text-white text-sm flex-shrink-0">
-                            {title} " (" {move || tests_signal.get().len().to_string()} ")"
+                            {title} " (" {move || format_count(tests_signal.get().len())} ")"
                         </h4>
                         <input
                             type="text"
@@ -255,14 +349,22 @@ pub fn ReportTab(
                                         }
                                     >
                                         <div class="truncate pr-2">{name}</div>
-                                        <div class="flex items-center gap-1 flex-shrink-0" title="Report | Agent">
+                                        <div class="flex items-center gap-1 flex-shrink-0" title="Report | Agent run(s)">
                                             {move || {
                                                 let status_report = get_stage_status(&t_name_for_status_for_report, "report", &analysis.get(), test_type);
-                                                let status_agent = get_stage_status(&t_name_for_status_for_agent, "agent", &analysis.get(), test_type);
+                                                let agent_icons = if show_all_runs.get() {
+                                                    agent_run_statuses(&t_name_for_status_for_agent, &analysis.get())
+                                                        .iter()
+                                                        .map(|(_, status)| render_status_icon(status))
+                                                        .collect_view()
+                                                        .into_any()
+                                                } else {
+                                                    render_status_icon(&get_stage_status(&t_name_for_status_for_agent, "agent", &analysis.get(), test_type))
+                                                };
                                                 view! {
                                                     <div class="flex items-center gap-1">
                                                         {render_status_icon(&status_report)}
-                                                        {render_status_icon(&status_agent)}
+                                                        {agent_icons}
                                                     </div>
                                                 }.into_any()
                                             }}
@@ -282,6 +384,11 @@ pub fn ReportTab(
         let idx = agent_index.get();
         let content: AnyView = if items.is_empty() {
             view! { <div class="text-gray-500 dark:text-gray-400 text-sm">No matches found</div> }.into_any()
+        } else if let Some(trace) = items[idx].trace.clone() {
+            let file_paths = result.get_untracked().map(|result| result.file_paths).unwrap_or_default();
+            view! {
+                <TraceContextView result=items[idx].clone() trace=trace file_paths=file_paths stage="agent".to_string() agent_log=selected_agent_log.get_untracked() />
+            }.into_any()
         } else {
             let result = items[idx].clone();
             let context_before_items = result.context_before.clone();
@@ -325,106 +432,124 @@ pub fn ReportTab(
                     </div>
                 }
             >
-                <div class="w-full h-full grid grid-cols-3 grid-rows-2 gap-0 divide-x divide-y divide-gray-200 dark:divide-gray-700">
-                    <div class="p-0 overflow-hidden">
-                        {render_list(f2p_success, "fail_to_pass", "F2P Success", f2p_success_filter)}
-                    </div>
-                    <div class="p-0 overflow-hidden">
-                        <div class="bg-gray-50 dark:bg-gray-700 px-3 py-1 border-b border-gray-200 dark:border-gray-600 text-sm font-medium text-gray-900 dark:text-white">Status</div>
-                        <div class="p-3 text-sm text-gray-700 dark:text-gray-200">
-                            {move || {
-                                let name = selected_test_name.get();
-                                if !name.is_empty() && is_c6_violation(&name) {
-                                    return view! {
-                                        <div class="p-2 bg-red-50 dark:bg-red-900/30 border border-red-200 dark:border-red-800 rounded text-xs text-red-800 dark:text-red-200">
-                                            <div class="font-medium">{"C6: Test marked failed in report but passing in agent"}</div>
-                                            <div class="opacity-80">{name.clone()}</div>
-                                        </div>
-                                    }.into_any();
-                                }
+                <div class="w-full h-full flex flex-col">
+                    {move || report_schema_notice(log_analysis_result, report_categories)}
+                    {move || agent_run_picker(log_analysis_result, selected_agent_log, show_all_runs)}
+                    {move || cross_check_matrix(matrix_categories, log_analysis_result, matrix_open, matrix_cell, pick_test)}
+                    <div class="flex-1 min-h-0 w-full grid grid-cols-3 grid-rows-2 gap-0 divide-x divide-y divide-gray-200 dark:divide-gray-700">
+                        <div class="p-0 overflow-hidden">
+                            {render_list(f2p_success, "fail_to_pass", "F2P Success", f2p_success_filter)}
+                        </div>
+                        <div class="p-0 overflow-hidden">
+                            <div class="bg-gray-50 dark:bg-gray-700 px-3 py-1 border-b border-gray-200 dark:border-gray-600 text-sm font-medium text-gray-900 dark:text-white">Status</div>
+                            <div class="p-3 text-sm text-gray-700 dark:text-gray-200">
+                                {move || {
+                                    let name = selected_test_name.get();
+                                    if !name.is_empty() && is_c6_violation(&name) {
+                                        return view! {
+                                            <div class="p-2 bg-red-50 dark:bg-red-900/30 border border-red-200 dark:border-red-800 rounded text-xs text-red-800 dark:text-red-200">
+                                                <div class="font-medium">{"C6: Test marked failed in report but passing in agent"}</div>
+                                                <div class="opacity-80">{name.clone()}</div>
+                                            </div>
+                                        }.into_any();
+                                    }
 
-                                // No selection yet — show a green success hint
-                                if name.is_empty() {
-                                    return view! {
-                                        <div class="p-2 bg-green-50 dark:bg-green-900/30 border border-green-200 dark:border-green-800 rounded text-xs text-green-800 dark:text-green-200">
-                                            <span>"Looks Good."</span>
-                                        </div>
-                                    }.into_any();
-                                }
+                                    // No selection yet — show a green success hint
+                                    if name.is_empty() {
+                                        return view! {
+                                            <div class="p-2 bg-green-50 dark:bg-green-900/30 border border-green-200 dark:border-green-800 rounded text-xs text-green-800 dark:text-green-200">
+                                                <span>"Looks Good."</span>
+                                            </div>
+                                        }.into_any();
+                                    }
 
-                                let analysis = log_analysis_result.get();
-                                let test_type = selected_test_type.get();
-                                let report_status = get_stage_status(&name, "report", &analysis, &test_type);
-                                let agent_status = get_stage_status(&name, "agent", &analysis, &test_type);
+                                    let analysis = log_analysis_result.get();
+                                    let test_type = selected_test_type.get();
+                                    let report_status = get_stage_status(&name, "report", &analysis, &test_type);
 
-                                if report_status == "not_supported" || agent_status == "not_supported" {
-                                    view! {
-                                        <div class="p-2 bg-yellow-50 dark:bg-yellow-900/30 border border-yellow-200 dark:border-yellow-800 rounded text-xs text-yellow-800 dark:text-yellow-200">
-                                            <span>"Looks like we don't support this log format yet, but you can go through them manually."</span>
-                                        </div>
-                                    }.into_any()
-                                } else {
-                                    view! {
-                                        <div class="p-2 bg-green-50 dark:bg-green-900/30 border border-green-200 dark:border-green-800 rounded text-xs text-green-800 dark:text-green-200">
-                                            <span>{format!(
-                                                "Test {} is {} in report.json and {} in agent log, so this looks good",
-                                                name, report_status, agent_status
-                                            )}</span>
-                                        </div>
-                                    }.into_any()
-                                }
-                            }}
+                                    if show_all_runs.get() {
+                                        let runs = agent_run_statuses(&name, &analysis).into_iter().map(|(log_name, status)| view! {
+                                            <li class="flex items-center gap-2">{render_status_icon(&status)}<span class="font-mono break-all">{log_name}</span><span>{status}</span></li>
+                                        }).collect_view();
+                                        return view! {
+                                            <div class="p-2 border border-gray-200 dark:border-gray-700 rounded text-xs space-y-1">
+                                                <div>{format!("Test {} is {} in report.json. Agent runs:", name, report_status)}</div>
+                                                <ul class="space-y-1">{runs}</ul>
+                                            </div>
+                                        }.into_any();
+                                    }
+
+                                    let agent_status = get_stage_status(&name, "agent", &analysis, &test_type);
+
+                                    if report_status == "not_supported" || agent_status == "not_supported" {
+                                        view! {
+                                            <div class="p-2 bg-yellow-50 dark:bg-yellow-900/30 border border-yellow-200 dark:border-yellow-800 rounded text-xs text-yellow-800 dark:text-yellow-200">
+                                                <span>"Looks like we don't support this log format yet, but you can go through them manually."</span>
+                                            </div>
+                                        }.into_any()
+                                    } else {
+                                        view! {
+                                            <div class="p-2 bg-green-50 dark:bg-green-900/30 border border-green-200 dark:border-green-800 rounded text-xs text-green-800 dark:text-green-200">
+                                                <span>{format!(
+                                                    "Test {} is {} in report.json and {} in agent log, so this looks good",
+                                                    name, report_status, agent_status
+                                                )}</span>
+                                            </div>
+                                        }.into_any()
+                                    }
+                                }}
+                            </div>
+                        </div>
+                        <div class="p-0 overflow-hidden">
+                            {render_list(p2p_success, "pass_to_pass", "P2P Success", p2p_success_filter)}
                         </div>
-                    </div>
-                    <div class="p-0 overflow-hidden">
-                        {render_list(p2p_success, "pass_to_pass", "P2P Success", p2p_success_filter)}
-                    </div>
 
-                    <div class="p-0 overflow-hidden">
-                        {render_list(f2p_failure, "fail_to_pass", "F2P Failures", f2p_failure_filter)}
-                    </div>
-                    <div class="p-0 overflow-hidden">
-                        <div class="bg-gray-50 dark:bg-gray-700 px-3 py-1 border-b border-gray-200 dark:border-gray-600 text-sm font-medium text-gray-900 dark:text-white flex items-center justify-between">
-                            <h4 class="font-medium text-gray-900 dark:text-white text-sm">
+                        <div class="p-0 overflow-hidden">
+                            {render_list(f2p_failure, "fail_to_pass", "F2P Failures", f2p_failure_filter)}
+                        </div>
+                        <div class="p-0 overflow-hidden">
+                            <div class="bg-gray-50 dark:bg-gray-700 px-3 py-1 border-b border-gray-200 dark:border-gray-600 text-sm font-medium text-gray-900 dark:text-white flex items-center justify-between">
+                                <h4 class="font-medium text-gray-900 dark:text-white text-sm">
+                                    {move || {
+                                        let count = agent_results.get().len();
+                                        format!("Agent Log ({} results)", count)
+                                    }}
+                                </h4>
                                 {move || {
-                                    let count = agent_results.get().len();
-                                    format!("Agent Log ({} results)", count)
+                                    let total = agent_results.get().len();
+                                    let current = agent_index.get();
+                                    if total > 1 {
+                                        view! {
+                                            <div class="flex items-center gap-1">
+                                                <button
+                                                    on:click=move |_| {
+                                                        let len = agent_results.get().len();
+                                                        if len > 0 { agent_index.set((agent_index.get() + len - 1) % len); }
+                                                    }
+                                                    class="px-1 py-0 text-gray-500 hover:text-gray-700 dark:hover:text-gray-300"
+                                                >
+                                                    "←"
+                                                </button>
+                                                <span class="text-xs text-gray-500">{format!("{}/{}", current + 1, total)}</span>
+                                                <button
+                                                    on:click=move |_| {
+                                                        let len = agent_results.get().len();
+                                                        if len > 0 { agent_index.set((agent_index.get() + 1) % len); }
+                                                    }
+                                                    class="px-1 py-0 text-gray-500 hover:text-gray-700 dark:hover:text-gray-300"
+                                                >
+                                                    "→"
+                                                </button>
+                                            </div>
+                                        }.into_any()
+                                    } else { view! { <div></div> }.into_any() }
                                 }}
-                            </h4>
-                            {move || {
-                                let total = agent_results.get().len();
-                                let current = agent_index.get();
-                                if total > 1 {
-                                    view! {
-                                        <div class="flex items-center gap-1">
-                                            <button
-                                                on:click=move |_| {
-                                                    let len = agent_results.get().len();
-                                                    if len > 0 { agent_index.set((agent_index.get() + len - 1) % len); }
-                                                }
-                                                class="px-1 py-0 text-gray-500 hover:text-gray-700 dark:hover:text-gray-300"
-                                            >
-                                                "←"
-                                            </button>
-                                            <span class="text-xs text-gray-500">{format!("{}/{}", current + 1, total)}</span>
-                                            <button
-                                                on:click=move |_| {
-                                                    let len = agent_results.get().len();
-                                                    if len > 0 { agent_index.set((agent_index.get() + 1) % len); }
-                                                }
-                                                class="px-1 py-0 text-gray-500 hover:text-gray-700 dark:hover:text-gray-300"
-                                            >
-                                                "→"
-                                            </button>
-                                        </div>
-                                    }.into_any()
-                                } else { view! { <div></div> }.into_any() }
-                            }}
+                            </div>
+                            <div class="h-full">{render_agent_highlight()}</div>
+                        </div>
+                        <div class="p-0 overflow-hidden">
+                            {render_list(p2p_failure, "pass_to_pass", "P2P Failures", p2p_failure_filter)}
                         </div>
-                        <div class="h-full">{render_agent_highlight()}</div>
-                    </div>
-                    <div class="p-0 overflow-hidden">
-                        {render_list(p2p_failure, "pass_to_pass", "P2P Failures", p2p_failure_filter)}
                     </div>
                 </div>
             </Show>
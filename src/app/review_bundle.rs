@@ -0,0 +1,96 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ProcessingResult};
+
+// Served at /api/export_bundle. Returns the zip base64-encoded; like the report export it is
+// built from the stored analysis, analyzing the deliverable first when it never was
+#[server(endpoint = "export_bundle")]
+pub async fn handle_export_bundle(file_paths: Vec<String>, instance_id: String, task_id: String) -> Result<String, ApiError> {
+    use base64::Engine;
+    use crate::api::analysis_store::load_analysis_result;
+    use crate::api::log_analysis::analyze_logs;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::api::review_bundle::build_review_bundle;
+    use crate::api::swebench_dataset::cross_check_with_dataset;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+
+    let analysis = match load_analysis_result(&file_paths) {
+        Some(analysis) => analysis,
+        None => {
+            let paths_for_analysis = file_paths.clone();
+            let mut analysis = tokio::task::spawn_blocking(move || analyze_logs(paths_for_analysis))
+                .await
+                .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Analysis task failed").with_details(e))?
+                .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to analyze logs").with_details(e))?;
+            cross_check_with_dataset(&file_paths, &mut analysis).await;
+            analysis
+        }
+    };
+    // Reads every stage log the excerpts come from
+    let bundle = tokio::task::spawn_blocking(move || build_review_bundle(&file_paths, &instance_id, &task_id, &analysis))
+        .await
+        .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Bundle task failed").with_details(e))?
+        .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Failed to build the review bundle").with_details(e))?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bundle))
+}
+
+#[component]
+pub fn ReviewBundleButton(current_deliverable: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let bundle = RwSignal::new(None::<String>);
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    // Drop a stale bundle when the reviewer moves to another deliverable
+    Effect::new(move |_| {
+        current_deliverable.track();
+        bundle.set(None);
+        error.set(None);
+    });
+
+    let export = move |_| {
+        let Some(deliverable) = current_deliverable.get_untracked() else {
+            return;
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_export_bundle(deliverable.file_paths.clone(), deliverable.instance_id.clone(), deliverable.display_task_id()).await {
+                Ok(encoded) => bundle.set(Some(encoded)),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    let file_name = move || {
+        let base = current_deliverable
+            .get()
+            .map(|d| if d.instance_id.is_empty() { "deliverable".to_string() } else { d.instance_id })
+            .unwrap_or_else(|| "deliverable".to_string());
+        format!("{}-review-bundle.zip", base)
+    };
+
+    move || match bundle.get() {
+        Some(encoded) => view! {
+            <a
+                href=format!("data:application/zip;base64,{}", encoded)
+                download=file_name()
+                class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-blue-600 hover:text-blue-800 dark:text-blue-400"
+            >
+                "Download bundle"
+            </a>
+        }.into_any(),
+        None => view! {
+            <button
+                on:click=export
+                disabled=move || busy.get()
+                title=move || error.get().unwrap_or_else(|| "Zip the analysis, report, test CSV and the log excerpts behind each violation".to_string())
+                class="text-sm px-3 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 disabled:opacity-50"
+            >
+                {move || if busy.get() { "Bundling..." } else if error.get().is_some() { "Export bundle (failed)" } else { "Export bundle" }}
+            </button>
+        }.into_any(),
+    }
+}
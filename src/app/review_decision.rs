@@ -0,0 +1,331 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+#[cfg(feature = "hydrate")]
+use web_sys::window;
+
+use super::types::{ApiError, DecisionLogEntry, ProcessingResult, ReviewDecision, ReviewEffortSummary, ReviewPresence};
+
+#[cfg(feature = "hydrate")]
+pub const REVIEWER_STORAGE_KEY: &str = "reviewer_name";
+
+#[cfg(feature = "ssr")]
+pub(crate) fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Starts timing the review the first time a deliverable is loaded; reloads keep the running timer
+#[server]
+pub async fn handle_start_review_timer(file_paths: Vec<String>) -> Result<(), ApiError> {
+    use crate::api::analysis_store::start_review_timer;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    start_review_timer(&file_paths, now_secs()).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to start the review timer").with_details(e))
+}
+
+#[server]
+pub async fn handle_review_effort_summary() -> Result<ReviewEffortSummary, ApiError> {
+    use crate::api::analysis_store::review_effort_summary;
+    Ok(review_effort_summary())
+}
+
+// Heartbeat of an open review; also reports a decision someone else recorded meanwhile
+#[server]
+pub async fn handle_review_presence(file_paths: Vec<String>, reviewer: String) -> Result<ReviewPresence, ApiError> {
+    use crate::api::review_conflicts::review_presence;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    review_presence(&file_paths, &reviewer, now_secs()).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to update review presence").with_details(e))
+}
+
+// Comfortably inside the server's presence TTL
+const PRESENCE_HEARTBEAT_SECS: u64 = 30;
+
+fn format_minutes(seconds: u64) -> String {
+    format!("{:.1} min", seconds as f64 / 60.0)
+}
+
+#[server]
+pub async fn handle_record_decision(decision: ReviewDecision, file_paths: Vec<String>) -> Result<(), ApiError> {
+    use crate::api::analysis_store::stop_review_timer;
+    use crate::api::log_analysis::analyze_logs;
+    use crate::api::notifications::notify_decision;
+    use crate::api::review_conflicts::log_decision;
+    use crate::api::review_queue::complete_queued_review;
+    use crate::api::sheets::append_decision_row;
+    use crate::api::webhook::notify_review_decision;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+
+    if decision.reviewer.trim().is_empty() {
+        return Err(ApiError::new(ApiErrorCode::Internal, "Reviewer name is required"));
+    }
+    if !matches!(decision.verdict.as_str(), "approved" | "rejected" | "needs_changes") {
+        return Err(ApiError::new(ApiErrorCode::Internal, format!("Unknown verdict: {}", decision.verdict)));
+    }
+
+    let mut decision = decision;
+    if !file_paths.is_empty() {
+        decision.violation_summary = match analyze_logs(file_paths.clone()) {
+            Ok(analysis) => analysis.rule_violations.summary(),
+            Err(e) => format!("analysis failed: {}", e),
+        };
+        decision.review_seconds = stop_review_timer(&file_paths, &decision.verdict, now_secs()).map(|effort| effort.seconds);
+        match log_decision(&file_paths, &decision, now_secs()) {
+            Ok(DecisionLogEntry { supersedes_reviewer: Some(previous), supersedes_verdict, .. }) => eprintln!(
+                "{}'s decision ({}) on {} replaces {}'s ({})",
+                decision.reviewer, decision.verdict, decision.deliverable_link, previous, supersedes_verdict.unwrap_or_default(),
+            ),
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to log decision: {}", e),
+        }
+    }
+    if let Err(e) = complete_queued_review(&decision.deliverable_link, &decision.verdict, now_secs()) {
+        eprintln!("Failed to close the review queue item: {}", e);
+    }
+
+    notify_decision(&decision).await;
+    notify_review_decision(&decision).await;
+    if let Err(e) = append_decision_row(&decision).await {
+        eprintln!("Failed to record decision in Google Sheets: {}", e);
+    }
+    Ok(())
+}
+
+#[component]
+pub fn ReviewDecisionPanel(current_deliverable: RwSignal<Option<ProcessingResult>>) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let reviewer = RwSignal::new(String::new());
+    let verdict = RwSignal::new("approved".to_string());
+    let comment = RwSignal::new(String::new());
+    let submitting = RwSignal::new(false);
+    let status = RwSignal::new(None::<Result<String, String>>);
+    let effort_summary = RwSignal::new(None::<ReviewEffortSummary>);
+    let presence = RwSignal::new(ReviewPresence::default());
+    // Another reviewer's decision the reviewer was warned about; the next submit overwrites it
+    let conflict = RwSignal::new(None::<DecisionLogEntry>);
+
+    let send_heartbeat = move || {
+        let name = reviewer.get_untracked().trim().to_string();
+        let file_paths = current_deliverable.with_untracked(|d| d.as_ref().map(|d| d.file_paths.clone()).unwrap_or_default());
+        if name.is_empty() || file_paths.is_empty() {
+            presence.set(ReviewPresence::default());
+            return;
+        }
+        spawn_local(async move {
+            if let Ok(found) = handle_review_presence(file_paths, name).await {
+                presence.set(found);
+            }
+        });
+    };
+
+    if let Ok(handle) = set_interval_with_handle(send_heartbeat, std::time::Duration::from_secs(PRESENCE_HEARTBEAT_SECS)) {
+        on_cleanup(move || handle.clear());
+    }
+
+    // Start the review timer for each newly loaded deliverable
+    Effect::new(move |prev: Option<Vec<String>>| {
+        let file_paths = current_deliverable.with(|d| d.as_ref().map(|d| d.file_paths.clone()).unwrap_or_default());
+        if prev.as_ref() != Some(&file_paths) {
+            conflict.set(None);
+            send_heartbeat();
+        }
+        if !file_paths.is_empty() && prev.as_ref() != Some(&file_paths) {
+            let paths = file_paths.clone();
+            spawn_local(async move {
+                if let Err(e) = handle_start_review_timer(paths).await {
+                    leptos::logging::log!("Failed to start review timer: {}", e);
+                }
+            });
+        }
+        file_paths
+    });
+
+    let load_effort_summary = move || {
+        spawn_local(async move {
+            if let Ok(summary) = handle_review_effort_summary().await {
+                effort_summary.set(Some(summary));
+            }
+        });
+    };
+
+    // Remember the reviewer name between sessions
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(win) = window() {
+            if let Ok(Some(local_storage)) = win.local_storage() {
+                if let Ok(Some(name)) = local_storage.get_item(REVIEWER_STORAGE_KEY) {
+                    reviewer.set(name);
+                }
+            }
+        }
+    }
+
+    let submit = move |_| {
+        let Some(deliverable) = current_deliverable.get() else {
+            return;
+        };
+        let reviewer_name = reviewer.get().trim().to_string();
+        if reviewer_name.is_empty() {
+            status.set(Some(Err("Enter your name first".to_string())));
+            return;
+        }
+
+        #[cfg(feature = "hydrate")]
+        {
+            if let Some(win) = window() {
+                if let Ok(Some(local_storage)) = win.local_storage() {
+                    let _ = local_storage.set_item(REVIEWER_STORAGE_KEY, &reviewer_name);
+                }
+            }
+        }
+
+        let decision = ReviewDecision {
+            deliverable_link: deliverable.deliverable_link.clone(),
+            instance_id: deliverable.instance_id.clone(),
+            reviewer: reviewer_name,
+            verdict: verdict.get(),
+            comment: comment.get(),
+            violation_summary: String::new(),
+            review_seconds: None,
+        };
+        let file_paths = deliverable.file_paths.clone();
+        let warned = conflict.get_untracked().is_some();
+
+        submitting.set(true);
+        status.set(None);
+        spawn_local(async move {
+            // Stop once if someone else decided since the panel was last refreshed
+            if !warned {
+                if let Ok(found) = handle_review_presence(file_paths.clone(), decision.reviewer.clone()).await {
+                    let other = found.other_decision.clone();
+                    presence.set(found);
+                    if other.is_some() {
+                        conflict.set(other);
+                        submitting.set(false);
+                        return;
+                    }
+                }
+            }
+            match handle_record_decision(decision, file_paths).await {
+                Ok(()) => {
+                    status.set(Some(Ok("Decision recorded".to_string())));
+                    is_open.set(false);
+                    conflict.set(None);
+                    load_effort_summary();
+                    send_heartbeat();
+                }
+                Err(e) => status.set(Some(Err(e.to_string()))),
+            }
+            submitting.set(false);
+        });
+    };
+
+    view! {
+        <div class="relative">
+            <button
+                on:click=move |_| {
+                    is_open.update(|open| *open = !*open);
+                    if is_open.get_untracked() {
+                        load_effort_summary();
+                    }
+                }
+                class="text-sm px-3 py-1 rounded bg-blue-600 text-white hover:bg-blue-700"
+            >
+                "Decision"
+            </button>
+            <Show when=move || !presence.with(|p| p.others.is_empty())>
+                <span
+                    class="ml-2 text-xs text-yellow-600 dark:text-yellow-400"
+                    title="Other reviewers with this deliverable open"
+                >
+                    {move || format!("Also open: {}", presence.with(|p| p.others.join(", ")))}
+                </span>
+            </Show>
+            <Show when=move || status.get().is_some() && !is_open.get()>
+                <span class=move || match status.get() {
+                    Some(Ok(_)) => "ml-2 text-xs text-green-600 dark:text-green-400",
+                    _ => "ml-2 text-xs text-red-600 dark:text-red-400",
+                }>
+                    {move || match status.get() {
+                        Some(Ok(msg)) | Some(Err(msg)) => msg,
+                        None => String::new(),
+                    }}
+                </span>
+            </Show>
+            <Show when=move || is_open.get()>
+                <div class="absolute right-0 mt-2 w-72 z-50 p-3 space-y-2 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+                    <input
+                        type="text"
+                        placeholder="Reviewer name"
+                        prop:value=move || reviewer.get()
+                        on:input=move |ev| reviewer.set(event_target_value(&ev))
+                        class="w-full px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    <select
+                        prop:value=move || verdict.get()
+                        on:change=move |ev| verdict.set(event_target_value(&ev))
+                        class="w-full px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    >
+                        <option value="approved">"Approved"</option>
+                        <option value="needs_changes">"Needs changes"</option>
+                        <option value="rejected">"Rejected"</option>
+                    </select>
+                    <textarea
+                        placeholder="Comment (optional)"
+                        prop:value=move || comment.get()
+                        on:input=move |ev| comment.set(event_target_value(&ev))
+                        class="w-full px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                        rows="3"
+                    />
+                    {move || conflict.get().or_else(|| presence.get().other_decision).map(|other| view! {
+                        <div class="text-xs p-2 rounded bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200">
+                            {format!(
+                                "{} already recorded \"{}\"; recording yours replaces it, and the decision log keeps both",
+                                other.reviewer,
+                                other.verdict.replace('_', " "),
+                            )}
+                        </div>
+                    })}
+                    <Show when=move || matches!(status.get(), Some(Err(_)))>
+                        <div class="text-xs text-red-600 dark:text-red-400">
+                            {move || match status.get() {
+                                Some(Err(msg)) => msg,
+                                _ => String::new(),
+                            }}
+                        </div>
+                    </Show>
+                    <button
+                        on:click=submit
+                        disabled=move || submitting.get()
+                        class="w-full text-sm px-3 py-1 rounded bg-blue-600 text-white hover:bg-blue-700 disabled:opacity-50"
+                    >
+                        {move || if submitting.get() {
+                            "Submitting..."
+                        } else if conflict.get().is_some() {
+                            "Record anyway"
+                        } else {
+                            "Record decision"
+                        }}
+                    </button>
+                    {move || effort_summary.get().filter(|summary| summary.reviews > 0).map(|summary| view! {
+                        <div class="text-xs text-gray-500 dark:text-gray-400">
+                            {format!(
+                                "Review time across {} decisions: median {}, mean {}, total {}",
+                                summary.reviews,
+                                format_minutes(summary.median_seconds),
+                                format_minutes(summary.mean_seconds),
+                                format_minutes(summary.total_seconds),
+                            )}
+                        </div>
+                    })}
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,270 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+#[cfg(feature = "hydrate")]
+use web_sys::window;
+
+#[cfg(feature = "hydrate")]
+use super::review_decision::REVIEWER_STORAGE_KEY;
+use super::types::{ApiError, QueueItem, QueueStatus};
+
+// The reviewer's items when `reviewer` is given, else the whole queue newest first
+#[server]
+pub async fn handle_review_queue(reviewer: Option<String>) -> Result<Vec<QueueItem>, ApiError> {
+    use crate::api::review_queue::{load_queue, queue_for};
+    use crate::app::types::ApiErrorCode;
+    let mut queue = load_queue().map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to load the review queue").with_details(e))?;
+    match reviewer {
+        Some(reviewer) => Ok(queue_for(&queue, &reviewer)),
+        None => {
+            queue.sort_by_key(|item| std::cmp::Reverse(item.added_at));
+            Ok(queue)
+        }
+    }
+}
+
+#[server]
+pub async fn handle_enqueue_review(deliverable_link: String, assignee: String, added_by: String) -> Result<QueueItem, ApiError> {
+    use crate::api::review_queue::{enqueue, update_queue};
+    use crate::app::review_decision::now_secs;
+    use crate::app::types::ApiErrorCode;
+    update_queue(|queue| enqueue(queue, &deliverable_link, &assignee, &added_by, now_secs())).map_err(|e| ApiError::new(ApiErrorCode::Storage, e))
+}
+
+// False when the item was no longer waiting to be started
+#[server]
+pub async fn handle_start_queue_item(id: u64) -> Result<bool, ApiError> {
+    use crate::api::review_queue::start_item;
+    use crate::app::types::ApiErrorCode;
+    start_item(id).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to update the review queue").with_details(e))
+}
+
+#[server]
+pub async fn handle_remove_queue_item(id: u64) -> Result<(), ApiError> {
+    use crate::api::review_queue::remove_item;
+    use crate::app::types::ApiErrorCode;
+    remove_item(id).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to update the review queue").with_details(e))
+}
+
+fn status_badge(item: &QueueItem) -> AnyView {
+    let (class, label) = match (item.status, item.verdict.as_deref()) {
+        (QueueStatus::Done, Some("approved")) => ("bg-green-100 dark:bg-green-900/40 text-green-800 dark:text-green-200", "Approved".to_string()),
+        (QueueStatus::Done, Some("rejected")) => ("bg-red-100 dark:bg-red-900/40 text-red-800 dark:text-red-200", "Rejected".to_string()),
+        (QueueStatus::Done, Some("needs_changes")) => ("bg-yellow-100 dark:bg-yellow-900/40 text-yellow-800 dark:text-yellow-200", "Needs changes".to_string()),
+        (QueueStatus::Done, _) => ("bg-gray-100 dark:bg-gray-700 text-gray-700 dark:text-gray-300", "Done".to_string()),
+        (QueueStatus::InReview, _) => ("bg-blue-100 dark:bg-blue-900/40 text-blue-800 dark:text-blue-200", "In review".to_string()),
+        (QueueStatus::Assigned, _) => ("bg-gray-100 dark:bg-gray-700 text-gray-700 dark:text-gray-300", "Assigned".to_string()),
+    };
+    view! { <span class=format!("px-2 py-0.5 rounded text-xs font-medium {}", class)>{label}</span> }.into_any()
+}
+
+// "My queue" on the landing page, plus the lead's form for queueing and assigning deliverables
+#[component]
+pub fn ReviewQueuePanel(
+    // Loads a deliverable link the way the landing page's Submit does
+    open_link: impl Fn(String) + Send + Sync + 'static + Copy,
+) -> impl IntoView {
+    let reviewer = RwSignal::new(String::new());
+    let my_items = RwSignal::new(Vec::<QueueItem>::new());
+    let all_items = RwSignal::new(Vec::<QueueItem>::new());
+    let show_all = RwSignal::new(false);
+    let new_link = RwSignal::new(String::new());
+    let new_assignee = RwSignal::new(String::new());
+    let error = RwSignal::new(None::<String>);
+
+    // Same name the decision panel records verdicts under
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(win) = window() {
+            if let Ok(Some(local_storage)) = win.local_storage() {
+                if let Ok(Some(name)) = local_storage.get_item(REVIEWER_STORAGE_KEY) {
+                    reviewer.set(name);
+                }
+            }
+        }
+    }
+
+    let reload = move || {
+        let name = reviewer.get_untracked().trim().to_string();
+        let with_all = show_all.get_untracked();
+        spawn_local(async move {
+            if !name.is_empty() {
+                match handle_review_queue(Some(name)).await {
+                    Ok(items) => my_items.set(items),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            } else {
+                my_items.set(vec![]);
+            }
+            if with_all {
+                match handle_review_queue(None).await {
+                    Ok(items) => all_items.set(items),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            }
+        });
+    };
+
+    Effect::new(move |_| reload());
+
+    let save_name = move |name: String| {
+        reviewer.set(name.clone());
+        #[cfg(feature = "hydrate")]
+        {
+            if let Some(win) = window() {
+                if let Ok(Some(local_storage)) = win.local_storage() {
+                    let _ = local_storage.set_item(REVIEWER_STORAGE_KEY, name.trim());
+                }
+            }
+        }
+        reload();
+    };
+
+    let open_item = move |item: QueueItem| {
+        if item.status == QueueStatus::Assigned {
+            spawn_local(async move {
+                match handle_start_queue_item(item.id).await {
+                    Ok(true) => {}
+                    // Someone else started it in the meantime; show the queue as it is now
+                    Ok(false) => reload(),
+                    Err(e) => leptos::logging::log!("Failed to mark queue item as in review: {}", e),
+                }
+            });
+        }
+        open_link(item.deliverable_link);
+    };
+
+    let add_item = move |_| {
+        let (link, assignee, added_by) = (new_link.get(), new_assignee.get(), reviewer.get());
+        error.set(None);
+        spawn_local(async move {
+            match handle_enqueue_review(link, assignee, added_by).await {
+                Ok(_) => {
+                    new_link.set(String::new());
+                    reload();
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let remove = move |id: u64| {
+        spawn_local(async move {
+            match handle_remove_queue_item(id).await {
+                Ok(()) => reload(),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let my_queue_view = move || -> AnyView {
+        if reviewer.get().trim().is_empty() {
+            return view! { <p class="text-sm text-gray-500 dark:text-gray-400">"Enter your name to see the deliverables assigned to you"</p> }.into_any();
+        }
+        let items = my_items.get();
+        if items.is_empty() {
+            return view! { <p class="text-sm text-gray-500 dark:text-gray-400">"Nothing assigned to you"</p> }.into_any();
+        }
+        items.into_iter().map(|item| {
+            let is_done = item.status == QueueStatus::Done;
+            let badge = status_badge(&item);
+            let link = item.deliverable_link.clone();
+            let link_title = link.clone();
+            let added_by = item.added_by.clone();
+            view! {
+                <li class="flex items-center gap-3 py-1.5">
+                    {badge}
+                    <span class="flex-1 min-w-0 truncate text-sm text-gray-800 dark:text-gray-200" title=link_title>{link}</span>
+                    <span class="text-xs text-gray-500 dark:text-gray-400">{format!("from {}", added_by)}</span>
+                    <button
+                        on:click=move |_| open_item(item.clone())
+                        class=if is_done {
+                            "text-xs px-2 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700"
+                        } else {
+                            "text-xs px-2 py-1 rounded bg-blue-600 text-white hover:bg-blue-700"
+                        }
+                    >
+                        {if is_done { "Reopen" } else { "Open" }}
+                    </button>
+                </li>
+            }
+        }).collect_view().into_any()
+    };
+
+    let all_items_view = move || -> AnyView {
+        let items = all_items.get();
+        if items.is_empty() {
+            return view! { <p class="text-sm text-gray-500 dark:text-gray-400">"The queue is empty"</p> }.into_any();
+        }
+        items.into_iter().map(|item| {
+            let id = item.id;
+            let badge = status_badge(&item);
+            view! {
+                <li class="flex items-center gap-3 py-1.5">
+                    {badge}
+                    <span class="flex-1 min-w-0 truncate text-sm text-gray-800 dark:text-gray-200" title=item.deliverable_link.clone()>{item.deliverable_link.clone()}</span>
+                    <span class="text-xs text-gray-500 dark:text-gray-400">{item.assignee.clone()}</span>
+                    <button
+                        on:click=move |_| remove(id)
+                        class="text-xs px-2 py-1 rounded text-red-600 dark:text-red-400 hover:bg-red-50 dark:hover:bg-red-900/20"
+                    >
+                        "Remove"
+                    </button>
+                </li>
+            }
+        }).collect_view().into_any()
+    };
+
+    view! {
+        <div class="w-full max-w-2xl mx-auto mt-8 p-4 text-left bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg">
+            <div class="flex items-center justify-between gap-3 mb-3">
+                <h3 class="text-lg font-semibold text-gray-900 dark:text-white">"My queue"</h3>
+                <input
+                    type="text"
+                    placeholder="Your name"
+                    prop:value=move || reviewer.get()
+                    on:change=move |ev| save_name(event_target_value(&ev))
+                    class="w-48 px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                />
+            </div>
+            <ul class="divide-y divide-gray-100 dark:divide-gray-700">{my_queue_view}</ul>
+
+            <details class="mt-4">
+                <summary
+                    on:click=move |_| {
+                        show_all.update(|open| *open = !*open);
+                        reload();
+                    }
+                    class="cursor-pointer text-sm text-gray-600 dark:text-gray-300"
+                >
+                    "Assign deliverables"
+                </summary>
+                <div class="mt-3 flex gap-2">
+                    <input
+                        type="text"
+                        placeholder="Deliverable link"
+                        prop:value=move || new_link.get()
+                        on:input=move |ev| new_link.set(event_target_value(&ev))
+                        class="flex-1 px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    <input
+                        type="text"
+                        placeholder="Reviewer"
+                        prop:value=move || new_assignee.get()
+                        on:input=move |ev| new_assignee.set(event_target_value(&ev))
+                        class="w-36 px-2 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    <button
+                        on:click=add_item
+                        disabled=move || new_link.get().trim().is_empty() || new_assignee.get().trim().is_empty()
+                        class="px-3 py-1 text-sm rounded bg-blue-600 text-white hover:bg-blue-700 disabled:bg-gray-400 disabled:cursor-not-allowed"
+                    >
+                        "Assign"
+                    </button>
+                </div>
+                <ul class="mt-3 divide-y divide-gray-100 dark:divide-gray-700">{all_items_view}</ul>
+            </details>
+
+            {move || error.get().map(|e| view! { <p class="mt-2 text-sm text-red-600 dark:text-red-400">{e}</p> })}
+        </div>
+    }
+}
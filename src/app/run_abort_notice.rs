@@ -0,0 +1,36 @@
+use leptos::prelude::*;
+
+use super::types::{LogAnalysisResult, StageAbort};
+
+fn abort_label(abort: &StageAbort) -> String {
+    let reason = match abort.reason.as_str() {
+        "timeout" => "timed out",
+        _ => "killed",
+    };
+    format!("{} {}", abort.stage, reason)
+}
+
+#[component]
+pub fn RunAbortNotice(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    move || {
+        let aborted = log_analysis_result.get().map(|analysis| analysis.debug_info.aborted_stages).unwrap_or_default();
+        if aborted.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let label = format!("Run aborted: {}", aborted.iter().map(abort_label).collect::<Vec<_>>().join(", "));
+        let details = aborted
+            .iter()
+            .map(|abort| format!("{}: {}", abort.stage, abort.evidence))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        view! {
+            <span
+                class="text-sm px-2 py-0.5 rounded border border-red-300 dark:border-red-700 bg-red-50 dark:bg-red-900/30 text-red-800 dark:text-red-200 whitespace-nowrap"
+                title=format!("Missing results in these stages come from the infrastructure, not the patch\n{}", details)
+            >
+                {label}
+            </span>
+        }.into_any()
+    }
+}
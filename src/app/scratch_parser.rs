@@ -0,0 +1,126 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ScratchParse, ScratchParsedTest};
+
+// Parses the snippet with the server's registry, the parser the analysis itself would use
+#[server]
+pub async fn handle_scratch_parse(snippet: String) -> Result<ScratchParse, ApiError> {
+    use crate::api::scratch_parse::scratch_parse_with_config;
+    use crate::app::types::ApiErrorCode;
+    tokio::task::spawn_blocking(move || scratch_parse_with_config(&snippet, None))
+        .await
+        .map_err(|e| ApiError::new(ApiErrorCode::Internal, "Scratch parse task failed").with_details(e))?
+        .map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Failed to parse the snippet").with_details(e))
+}
+
+fn status_class(status: &str) -> &'static str {
+    match status {
+        "passed" => "text-green-700 dark:text-green-300",
+        "failed" => "text-red-700 dark:text-red-300",
+        _ => "text-yellow-700 dark:text-yellow-300",
+    }
+}
+
+// Which test list a parsed name belongs to, if any
+fn list_label(name: &str, fail_to_pass: &[String], pass_to_pass: &[String]) -> &'static str {
+    if fail_to_pass.iter().any(|test| test == name) {
+        "F2P"
+    } else if pass_to_pass.iter().any(|test| test == name) {
+        "P2P"
+    } else {
+        "—"
+    }
+}
+
+fn parsed_rows(tests: Vec<ScratchParsedTest>, fail_to_pass: Vec<String>, pass_to_pass: Vec<String>) -> AnyView {
+    if tests.is_empty() {
+        return view! {
+            <div class="text-xs text-gray-500 dark:text-gray-400">"No test lines recognized in the snippet"</div>
+        }.into_any();
+    }
+    let rows = tests.into_iter().map(|test| {
+        let list = list_label(&test.name, &fail_to_pass, &pass_to_pass);
+        let status_class = format!("py-1 pr-4 {}", status_class(&test.status));
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700">
+                <td class="py-1 pr-4 text-right text-gray-500 dark:text-gray-400">{test.line.map(|line| line.to_string()).unwrap_or_default()}</td>
+                <td class="py-1 pr-4 font-mono break-all text-gray-900 dark:text-white">{test.name}</td>
+                <td class=status_class>{test.status}</td>
+                <td class="py-1 text-gray-600 dark:text-gray-300">{list}</td>
+            </tr>
+        }
+    }).collect_view();
+    view! {
+        <table class="w-full text-xs">
+            <thead>
+                <tr class="text-left font-semibold text-gray-500 dark:text-gray-400">
+                    <th class="pb-1 pr-4 text-right">"Line"</th>
+                    <th class="pb-1 pr-4">"Test"</th>
+                    <th class="pb-1 pr-4">"Status"</th>
+                    <th class="pb-1">"List"</th>
+                </tr>
+            </thead>
+            <tbody>{rows}</tbody>
+        </table>
+    }.into_any()
+}
+
+#[component]
+pub fn ScratchParser(
+    fail_to_pass_tests: RwSignal<Vec<String>>,
+    pass_to_pass_tests: RwSignal<Vec<String>>,
+) -> impl IntoView {
+    let snippet = RwSignal::new(String::new());
+    let parsed = RwSignal::new(None::<ScratchParse>);
+    let parsing = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    let parse = move |_| {
+        let content = snippet.get_untracked();
+        parsing.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_scratch_parse(content).await {
+                Ok(result) => parsed.set(Some(result)),
+                Err(e) => {
+                    parsed.set(None);
+                    error.set(Some(e.to_string()));
+                }
+            }
+            parsing.set(false);
+        });
+    };
+
+    view! {
+        <div class="p-4 border-t border-gray-200 dark:border-gray-700 space-y-2">
+            <div class="flex items-center justify-between">
+                <span class="text-sm font-semibold text-gray-900 dark:text-white">"Scratch parser"</span>
+                <span class="text-xs text-gray-500 dark:text-gray-400">
+                    {move || parsed.get().map(|parsed| {
+                        if parsed.parser.is_empty() {
+                            "No parser recognized the snippet".to_string()
+                        } else {
+                            format!("{} test(s) recognized by the {} parser", parsed.tests.len(), parsed.parser)
+                        }
+                    })}
+                </span>
+            </div>
+            <textarea
+                prop:value=move || snippet.get()
+                on:input=move |ev| snippet.set(event_target_value(&ev))
+                placeholder="Paste a log snippet to see which tests and statuses are extracted"
+                class="w-full h-32 px-2 py-1 font-mono text-xs border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+            />
+            <button
+                on:click=parse
+                disabled=move || parsing.get() || snippet.get().trim().is_empty()
+                class="px-3 py-1 text-xs rounded bg-blue-600 text-white hover:bg-blue-700 disabled:bg-gray-400 disabled:cursor-not-allowed"
+            >
+                {move || if parsing.get() { "Parsing..." } else { "Parse" }}
+            </button>
+            {move || error.get().map(|e| view! { <div class="text-xs text-red-600 dark:text-red-400">{e}</div> })}
+            {move || parsed.get().map(|parsed| parsed_rows(parsed.tests, fail_to_pass_tests.get(), pass_to_pass_tests.get()))}
+        </div>
+    }
+}
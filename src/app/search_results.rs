@@ -1,25 +1,58 @@
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use std::collections::HashMap;
-use super::types::{LogSearchResults, ProcessingResult};
+use super::types::{ApiError, LogSearchResults, ProcessingResult, SearchMode};
 
+// Only the logs in `stages` are read, so unticked columns cost nothing on large deliverables;
+// `context_lines` replaces the language's context around each hit, and `include_noise` keeps the
+// build/install lines the analysis skips. `query` is matched in `mode`, and a query the mode
+// can't use (an empty one, an invalid regex) is a parse failure
 #[server]
-pub async fn handle_search_logs(file_paths: Vec<String>, test_name: String) -> Result<LogSearchResults, ServerFnError> {
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_search_logs(file_paths: Vec<String>, query: String, mode: SearchMode, stages: Vec<String>, agent_log: Option<String>, context_lines: Option<usize>, include_noise: bool) -> Result<LogSearchResults, ApiError> {
+    use crate::api::line_matcher::LineMatcher;
     use crate::api::log_analysis::{search_logs};
-    Ok(search_logs(file_paths, test_name).unwrap())
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    let matcher = LineMatcher::new(&query, mode).map_err(|e| ApiError::new(ApiErrorCode::ParseFailure, "Invalid search query").with_details(e))?;
+    search_logs(file_paths, &matcher, &stages, agent_log, context_lines, include_noise)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to search the logs").with_details(e))
 }
 
 #[server]
-pub async fn handle_search_agent_logs(file_paths: Vec<String>, test_name: String) -> Result<Vec<super::types::SearchResult>, ServerFnError> {
+pub async fn handle_search_agent_logs(file_paths: Vec<String>, test_name: String, agent_log: Option<String>) -> Result<Vec<super::types::SearchResult>, ApiError> {
     use crate::api::log_analysis::{search_agent_log};
-    Ok(search_agent_log(file_paths, test_name).unwrap())
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    search_agent_log(file_paths, test_name, agent_log)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to search the agent log").with_details(e))
 }
 
+// Hover previews fetch only the lines they show; the anchor is a line number or the first line mentioning a test
+#[server]
+pub async fn handle_get_log_slice(file_paths: Vec<String>, stage: String, agent_log: Option<String>, line: Option<usize>, test_name: Option<String>, radius: usize) -> Result<Option<super::types::LogSlice>, ApiError> {
+    use crate::api::log_analysis::{log_slice};
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    log_slice(file_paths, stage, agent_log, line, test_name, radius)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to read the log").with_details(e))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn search_for_test(
     result: RwSignal<Option<ProcessingResult>>,
-    test_name: String,
+    query: String,
+    mode: SearchMode,
+    stages: Vec<String>,
+    agent_log: Option<String>,
+    context_lines: Option<usize>,
+    include_noise: bool,
     search_results: RwSignal<LogSearchResults>,
     search_result_indices: RwSignal<HashMap<String, usize>>,
+    search_error: RwSignal<Option<String>>,
 ) {
     if result.get().is_none() {
         return;
@@ -31,14 +64,20 @@ pub fn search_for_test(
     }
     
     spawn_local(async move {
-            let results = handle_search_logs(result_data.file_paths, test_name).await;
-            if let Ok(results) = results {
-                search_results.set(results);
-                search_result_indices.set(HashMap::from([
-                    ("base".to_string(), 0usize),
-                    ("before".to_string(), 0usize),
-                    ("after".to_string(), 0usize),
-                ]));
+            let results = handle_search_logs(result_data.file_paths, query, mode, stages, agent_log, context_lines, include_noise).await;
+            match results {
+                Ok(results) => {
+                    search_error.set(None);
+                    search_results.set(results);
+                    search_result_indices.set(HashMap::from([
+                        ("base".to_string(), 0usize),
+                        ("before".to_string(), 0usize),
+                        ("after".to_string(), 0usize),
+                        ("agent".to_string(), 0usize),
+                    ]));
+                }
+                // Keep the last results on screen under the error
+                Err(e) => search_error.set(Some(e.to_string())),
             }
     });
 }
@@ -57,6 +96,7 @@ pub fn navigate_search_result(
         "base" => results.base_results.len().saturating_sub(1),
         "before" => results.before_results.len().saturating_sub(1),
         "after" => results.after_results.len().saturating_sub(1),
+        "agent" => results.agent_results.len().saturating_sub(1),
         _ => 0,
     };
     
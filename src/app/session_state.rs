@@ -0,0 +1,142 @@
+//! Review state kept in sessionStorage so a reload lands back where the reviewer was
+//!
+//! Only what cannot be rebuilt cheaply is stored: the downloaded file paths and the
+//! reviewer's selections. Everything read from main.json is parsed again after the
+//! restore, and the snapshot is ignored when the server no longer has the files.
+//! The same snapshot is saved on the server under the review's session id, which
+//! `/review/{session_id}` reopens from any browser.
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::types::{ApiError, CustomTestLists, LogAnalysisResult, ProcessingResult};
+
+#[cfg(feature = "hydrate")]
+const SESSION_STORAGE_KEY: &str = "deliverable_session";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReviewSession {
+    pub deliverable_link: String,
+    pub file_paths: Vec<String>,
+    pub platform_task_id: String,
+    pub current_selection: String,
+    pub selected_fail_to_pass_index: usize,
+    pub selected_pass_to_pass_index: usize,
+    pub active_main_tab: String,
+    pub active_tab: String,
+    pub selected_agent_log: Option<String>,
+    pub report_selected_test_name: String,
+    pub search_stages: Vec<String>,
+    pub custom_test_lists: Option<CustomTestLists>,
+    #[serde(default)]
+    pub logs_only: bool,
+    // Raised with every snapshot of the review; the server keeps the highest it has seen
+    #[serde(default)]
+    pub revision: u64,
+}
+
+impl ReviewSession {
+    // The result to restore; main.json fields stay empty until main.json is loaded again
+    pub fn processing_result(&self) -> ProcessingResult {
+        ProcessingResult {
+            file_paths: self.file_paths.clone(),
+            deliverable_link: self.deliverable_link.clone(),
+            instance_id: String::new(),
+            task_id: String::new(),
+            pr_id: String::new(),
+            issue_id: String::new(),
+            repo: String::new(),
+            problem_statement: String::new(),
+            conversation: Vec::new(),
+            gold_patch: String::new(),
+            test_patch: String::new(),
+            language: String::new(),
+            platform_task_id: self.platform_task_id.clone(),
+            logs_only: self.logs_only,
+        }
+    }
+}
+
+/// A server-side session with the deliverable's stored analysis, when it still fits the session
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResumedSession {
+    pub session: ReviewSession,
+    pub analysis: Option<LogAnalysisResult>,
+}
+
+// False when the server already holds a newer snapshot of the session, which it keeps
+#[server]
+pub async fn handle_save_review_session(session_id: String, session: ReviewSession) -> Result<bool, ApiError> {
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::api::sessions::save_review_session;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&session.file_paths)?;
+    save_review_session(&session_id, &session)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to save the review session").with_details(e))
+}
+
+#[server]
+pub async fn handle_resume_review_session(session_id: String) -> Result<ResumedSession, ApiError> {
+    use crate::api::sessions::resume_review_session;
+    use crate::app::types::ApiErrorCode;
+    resume_review_session(&session_id)
+        .map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to load the review session").with_details(e))?
+        .ok_or_else(|| ApiError::new(ApiErrorCode::NotFound, "This review session doesn't exist or has been removed"))
+}
+
+/// Show `/review/{session_id}` in the address bar without reloading the page
+pub fn show_session_url(session_id: &str) {
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(win) = web_sys::window() {
+            if let Ok(history) = win.history() {
+                let url = format!("/review/{}", session_id);
+                let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+            }
+        }
+    }
+    #[cfg(not(feature = "hydrate"))]
+    let _ = session_id;
+}
+
+pub fn save_session(session: &ReviewSession) {
+    #[cfg(feature = "hydrate")]
+    {
+        let Ok(serialized) = serde_json::to_string(session) else { return };
+        if let Some(win) = web_sys::window() {
+            if let Ok(Some(session_storage)) = win.session_storage() {
+                let _ = session_storage.set_item(SESSION_STORAGE_KEY, &serialized);
+            }
+        }
+    }
+    #[cfg(not(feature = "hydrate"))]
+    let _ = session;
+}
+
+/// The snapshot saved for this deliverable link, if the tab has one
+pub fn load_session(deliverable_link: &str) -> Option<ReviewSession> {
+    #[cfg(feature = "hydrate")]
+    {
+        let session_storage = web_sys::window()?.session_storage().ok()??;
+        let serialized = session_storage.get_item(SESSION_STORAGE_KEY).ok()??;
+        serde_json::from_str::<ReviewSession>(&serialized)
+            .ok()
+            .filter(|session| session.deliverable_link == deliverable_link && !session.file_paths.is_empty())
+    }
+    #[cfg(not(feature = "hydrate"))]
+    {
+        let _ = deliverable_link;
+        None
+    }
+}
+
+pub fn clear_session() {
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(win) = web_sys::window() {
+            if let Ok(Some(session_storage)) = win.session_storage() {
+                let _ = session_storage.remove_item(SESSION_STORAGE_KEY);
+            }
+        }
+    }
+}
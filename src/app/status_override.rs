@@ -0,0 +1,169 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+#[cfg(feature = "hydrate")]
+use web_sys::window;
+
+#[cfg(feature = "hydrate")]
+use super::review_decision::REVIEWER_STORAGE_KEY;
+use super::types::{ApiError, LogAnalysisResult, ProcessingResult, StatusOverride};
+
+#[server]
+pub async fn handle_save_status_override(file_paths: Vec<String>, status_override: StatusOverride) -> Result<Vec<StatusOverride>, ApiError> {
+    use crate::api::status_overrides::save_status_override;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let status_override = StatusOverride { created_at, ..status_override };
+    save_status_override(&file_paths, status_override).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to save the status override").with_details(e))
+}
+
+#[component]
+pub fn StatusOverrideEditor(
+    #[prop(into)] test_name: Signal<Option<String>>,
+    result: RwSignal<Option<ProcessingResult>>,
+    log_analysis_result: RwSignal<Option<LogAnalysisResult>>,
+    rerun_analysis: impl Fn() + Send + Sync + 'static + Copy,
+) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+    let stage = RwSignal::new("after".to_string());
+    let status = RwSignal::new("passed".to_string());
+    let reason = RwSignal::new(String::new());
+    let author = RwSignal::new(String::new());
+    let busy = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    // Shares the reviewer name remembered by the review decision panel
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(win) = window() {
+            if let Ok(Some(local_storage)) = win.local_storage() {
+                if let Ok(Some(name)) = local_storage.get_item(REVIEWER_STORAGE_KEY) {
+                    author.set(name);
+                }
+            }
+        }
+    }
+
+    let existing = move || -> Option<StatusOverride> {
+        let test_name = test_name.get()?;
+        log_analysis_result.get()?.override_for(&test_name, &stage.get()).cloned()
+    };
+
+    let save = move |new_status: String| {
+        let (Some(test_name), Some(deliverable)) = (test_name.get_untracked(), result.get_untracked()) else {
+            return;
+        };
+        let author_name = author.get_untracked().trim().to_string();
+        #[cfg(feature = "hydrate")]
+        {
+            if let Some(win) = window() {
+                if let Ok(Some(local_storage)) = win.local_storage() {
+                    let _ = local_storage.set_item(REVIEWER_STORAGE_KEY, &author_name);
+                }
+            }
+        }
+        let status_override = StatusOverride {
+            test_name,
+            stage: stage.get_untracked(),
+            status: new_status,
+            reason: reason.get_untracked().trim().to_string(),
+            author: author_name,
+            created_at: 0,
+        };
+        busy.set(true);
+        error.set(None);
+        spawn_local(async move {
+            match handle_save_status_override(deliverable.file_paths.clone(), status_override).await {
+                Ok(_) => {
+                    is_open.set(false);
+                    reason.set(String::new());
+                    // Statuses and rule checks are recomputed server-side with the override applied
+                    untrack(rerun_analysis);
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            busy.set(false);
+        });
+    };
+
+    view! {
+        <div class="relative">
+            <button
+                on:click=move |_| is_open.update(|open| *open = !*open)
+                disabled=move || test_name.get().is_none() || log_analysis_result.get().is_none()
+                class="text-xs px-2 py-1 rounded border border-purple-300 dark:border-purple-700 text-purple-700 dark:text-purple-300 hover:bg-purple-50 dark:hover:bg-purple-900/30 whitespace-nowrap disabled:opacity-50"
+                title="Correct the computed status of the selected test"
+            >
+                "Override"
+            </button>
+            <Show when=move || is_open.get()>
+                <div class="absolute right-0 mt-2 w-80 z-50 p-3 space-y-2 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg text-xs">
+                    <div class="font-mono break-all text-gray-700 dark:text-gray-200">{move || test_name.get().unwrap_or_default()}</div>
+                    <div class="flex gap-2">
+                        <select
+                            prop:value=move || stage.get()
+                            on:change=move |ev| stage.set(event_target_value(&ev))
+                            class="flex-1 px-1 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                        >
+                            <option value="base">"base"</option>
+                            <option value="before">"before"</option>
+                            <option value="after">"after"</option>
+                            <option value="agent">"agent"</option>
+                        </select>
+                        <select
+                            prop:value=move || status.get()
+                            on:change=move |ev| status.set(event_target_value(&ev))
+                            class="flex-1 px-1 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                        >
+                            <option value="passed">"passed"</option>
+                            <option value="failed">"failed"</option>
+                            <option value="ignored">"ignored"</option>
+                            <option value="missing">"missing"</option>
+                        </select>
+                    </div>
+                    <input
+                        type="text"
+                        prop:value=move || author.get()
+                        on:input=move |ev| author.set(event_target_value(&ev))
+                        placeholder="Your name"
+                        class="w-full px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    <textarea
+                        prop:value=move || reason.get()
+                        on:input=move |ev| reason.set(event_target_value(&ev))
+                        placeholder="Why, e.g. \"actually passed, parser missed it\""
+                        class="w-full h-16 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700 text-gray-900 dark:text-white"
+                    />
+                    {move || existing().map(|o| view! {
+                        <div class="text-purple-700 dark:text-purple-300">{format!("Current override: {}", o.describe())}</div>
+                    })}
+                    {move || error.get().map(|e| view! { <div class="text-red-600 dark:text-red-400">{e}</div> })}
+                    <div class="flex gap-2">
+                        <button
+                            on:click=move |_| save(status.get_untracked())
+                            disabled=move || busy.get() || reason.get().trim().is_empty() || author.get().trim().is_empty()
+                            class="px-2 py-1 rounded bg-purple-600 hover:bg-purple-700 text-white disabled:opacity-50"
+                        >
+                            "Save override"
+                        </button>
+                        <Show when=move || existing().is_some()>
+                            <button
+                                on:click=move |_| save(String::new())
+                                disabled=move || busy.get()
+                                class="px-2 py-1 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 disabled:opacity-50"
+                            >
+                                "Remove override"
+                            </button>
+                        </Show>
+                    </div>
+                </div>
+            </Show>
+        </div>
+    }
+}
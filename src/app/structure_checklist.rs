@@ -0,0 +1,45 @@
+use leptos::prelude::*;
+
+use super::types::{StructureCheckItem, StructureItemStatus};
+
+fn status_badge(item: &StructureCheckItem) -> (&'static str, &'static str) {
+    match item.status {
+        StructureItemStatus::Present => ("present", "text-green-700 dark:text-green-300"),
+        StructureItemStatus::Missing if item.required => ("missing", "text-red-700 dark:text-red-300"),
+        StructureItemStatus::Missing => ("not provided", "text-gray-500 dark:text-gray-400"),
+        StructureItemStatus::Unexpected => ("unexpected", "text-yellow-700 dark:text-yellow-300"),
+    }
+}
+
+// Layout checklist of the deliverable folder, filled in once validation succeeds
+#[component]
+pub fn StructureChecklist(structure: RwSignal<Vec<StructureCheckItem>>) -> impl IntoView {
+    move || {
+        let items = structure.get();
+        if items.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let unexpected = items.iter().filter(|item| item.status == StructureItemStatus::Unexpected).count();
+        let rows = items.into_iter().map(|item| {
+            let (label, class) = status_badge(&item);
+            view! {
+                <tr class="border-t border-gray-200 dark:border-gray-700">
+                    <td class="py-1 pr-4 font-mono break-all text-gray-900 dark:text-white">{item.path}</td>
+                    <td class=format!("py-1 pr-4 whitespace-nowrap {}", class)>{label}</td>
+                    <td class="py-1 text-gray-600 dark:text-gray-300 break-all">{item.note.unwrap_or_default()}</td>
+                </tr>
+            }
+        }).collect_view();
+
+        view! {
+            <details class="max-w-2xl mx-auto mt-4 text-left text-xs" open={unexpected > 0}>
+                <summary class="cursor-pointer text-sm text-gray-700 dark:text-gray-300">
+                    {format!("Folder structure — {} unexpected item(s)", unexpected)}
+                </summary>
+                <table class="w-full mt-2">
+                    <tbody>{rows}</tbody>
+                </table>
+            </details>
+        }.into_any()
+    }
+}
@@ -0,0 +1,164 @@
+use leptos::prelude::*;
+use leptos_meta::{Meta, Title};
+use leptos_router::hooks::use_params_map;
+
+use super::number_format::format_count;
+use super::types::{format_utc, rule_description, DeliverableSummary};
+
+// Drive folder ids; anything else would let the path escape the review store
+#[cfg(feature = "ssr")]
+fn is_folder_id(folder_id: &str) -> bool {
+    !folder_id.is_empty() && folder_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[server]
+pub async fn handle_deliverable_summary(folder_id: String) -> Result<Option<DeliverableSummary>, ServerFnError> {
+    use crate::api::analysis_store::load_folder_analysis_result;
+    use crate::api::review_conflicts::load_folder_decision_log;
+    if !is_folder_id(&folder_id) {
+        return Ok(None);
+    }
+    Ok(load_folder_analysis_result(&folder_id).map(|analysis| DeliverableSummary {
+        decision: load_folder_decision_log(&folder_id).unwrap_or_else(|e| {
+            eprintln!("Failed to load the decision log of {}: {}", folder_id, e);
+            vec![]
+        }).pop(),
+        folder_id,
+        analysis,
+    }))
+}
+
+fn verdict_text(verdict: &str) -> String {
+    match verdict {
+        "approved" => "Approved".to_string(),
+        "rejected" => "Rejected".to_string(),
+        "needs_changes" => "Needs changes".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// One-line description for link previews
+fn preview_description(summary: &DeliverableSummary) -> String {
+    let verdict = summary.decision.as_ref()
+        .map(|decision| format!("{} by {}", verdict_text(&decision.verdict), decision.reviewer))
+        .unwrap_or_else(|| "No decision yet".to_string());
+    format!(
+        "{}. Violated rules: {}. {} F2P, {} P2P tests.",
+        verdict,
+        summary.analysis.rule_violations.summary(),
+        summary.analysis.test_statuses.f2p.len(),
+        summary.analysis.test_statuses.p2p.len(),
+    )
+}
+
+fn summary_view(summary: DeliverableSummary) -> AnyView {
+    let title = format!("Deliverable {} — SWE Reviewer", summary.folder_id);
+    let description = preview_description(&summary);
+    let analysis = summary.analysis;
+    let failing_after = analysis.test_statuses.f2p.values().chain(analysis.test_statuses.p2p.values())
+        .filter(|statuses| statuses.after == "failed")
+        .count();
+    let analyzed_at = analysis.settings.as_ref().map(|settings| format_utc(settings.analyzed_at));
+
+    let verdict = match summary.decision {
+        Some(decision) => {
+            let badge_class = match decision.verdict.as_str() {
+                "approved" => "px-2 py-0.5 rounded text-sm font-semibold bg-green-600 text-white",
+                "rejected" => "px-2 py-0.5 rounded text-sm font-semibold bg-red-600 text-white",
+                _ => "px-2 py-0.5 rounded text-sm font-semibold bg-yellow-500 text-white",
+            };
+            let comment = (!decision.comment.is_empty()).then(|| view! {
+                <p class="mt-1 text-sm text-gray-700 dark:text-gray-300 whitespace-pre-wrap">{decision.comment.clone()}</p>
+            });
+            view! {
+                <div>
+                    <span class=badge_class>{verdict_text(&decision.verdict)}</span>
+                    <span class="ml-2 text-sm text-gray-600 dark:text-gray-400">
+                        {format!("by {} on {}", decision.reviewer, format_utc(decision.decided_at))}
+                    </span>
+                    {comment}
+                </div>
+            }.into_any()
+        }
+        None => view! { <span class="text-sm text-gray-500 dark:text-gray-400">"No decision recorded yet"</span> }.into_any(),
+    };
+
+    let cards = analysis.rule_violations.entries().into_iter().map(|(code, violation)| {
+        let evaluated = analysis.rules_evaluated.iter().any(|evaluated| evaluated == code);
+        let (card_class, badge_class, badge) = if violation.has_problem {
+            ("rounded-lg border border-red-200 dark:border-red-800 bg-red-50/50 dark:bg-red-900/20 p-3", "px-2 py-0.5 rounded text-xs font-semibold bg-red-600 text-white", "FAIL")
+        } else if evaluated {
+            ("rounded-lg border border-gray-200 dark:border-gray-700 bg-white dark:bg-gray-800 p-3", "px-2 py-0.5 rounded text-xs font-semibold bg-green-600 text-white", "PASS")
+        } else {
+            ("rounded-lg border border-gray-200 dark:border-gray-700 bg-white dark:bg-gray-800 p-3", "px-2 py-0.5 rounded text-xs font-semibold bg-gray-400 text-white", "SKIPPED")
+        };
+        let examples = violation.examples.iter().take(10).map(|example| view! {
+            <li class="font-mono text-xs break-all text-gray-800 dark:text-gray-200">{example.clone()}</li>
+        }).collect_view();
+        let more = violation.examples.len().saturating_sub(10);
+        view! {
+            <div class=card_class>
+                <div class="flex items-center gap-2">
+                    <span class="font-semibold text-gray-900 dark:text-white">{code}</span>
+                    <span class=badge_class>{badge}</span>
+                    <span class="text-xs text-gray-500 dark:text-gray-400">{format!("{} examples", format_count(violation.examples.len()))}</span>
+                </div>
+                <div class="mt-1 text-sm text-gray-700 dark:text-gray-300">{rule_description(code)}</div>
+                <ul class="mt-2">{examples}</ul>
+                {(more > 0).then(|| view! { <div class="text-xs text-gray-500 dark:text-gray-400">{format!("and {} more", more)}</div> })}
+            </div>
+        }
+    }).collect_view();
+
+    view! {
+        <Title text=title.clone()/>
+        <Meta property="og:title" content=title/>
+        <Meta property="og:description" content=description.clone()/>
+        <Meta name="description" content=description/>
+        <div class="h-full overflow-y-auto p-6 space-y-4">
+            <div class="flex items-center gap-4">
+                <h2 class="text-xl font-semibold text-gray-900 dark:text-white">{format!("Deliverable {}", summary.folder_id)}</h2>
+                <a
+                    href=format!("/{}", summary.folder_id)
+                    class="text-sm text-blue-600 hover:text-blue-800 underline dark:text-blue-400 dark:hover:text-blue-300"
+                >
+                    "Open in reviewer"
+                </a>
+            </div>
+            {verdict}
+            <div class="flex flex-wrap gap-4 text-sm text-gray-700 dark:text-gray-300">
+                <span>{format!("{} F2P tests", format_count(analysis.test_statuses.f2p.len()))}</span>
+                <span>{format!("{} P2P tests", format_count(analysis.test_statuses.p2p.len()))}</span>
+                <span>{format!("{} failing in after", failing_after)}</span>
+                <span>{format!("Violated rules: {}", analysis.rule_violations.summary())}</span>
+                {analyzed_at.map(|at| view! { <span>{format!("Analyzed {}", at)}</span> })}
+            </div>
+            <div class="grid grid-cols-1 lg:grid-cols-2 gap-3">{cards}</div>
+        </div>
+    }.into_any()
+}
+
+// Read-only summary of a stored analysis at `/summary/<folder_id>`, rendered on the server
+// so shared links show the verdict and rules before (or without) hydration
+#[component]
+pub fn SummaryPage() -> impl IntoView {
+    let params = use_params_map();
+    let summary = Resource::new(
+        move || params.read().get("folder_id").unwrap_or_default(),
+        handle_deliverable_summary,
+    );
+
+    view! {
+        <Suspense fallback=|| view! { <div class="p-6 text-gray-500 dark:text-gray-400">"Loading..."</div> }>
+            {move || Suspend::new(async move {
+                match summary.await {
+                    Ok(Some(found)) => summary_view(found),
+                    Ok(None) => view! {
+                        <div class="p-6 text-gray-500 dark:text-gray-400">"No stored analysis for this deliverable"</div>
+                    }.into_any(),
+                    Err(e) => view! { <div class="p-6 text-red-600 dark:text-red-400">{e.to_string()}</div> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
@@ -0,0 +1,88 @@
+use leptos::prelude::*;
+
+use super::types::{ListProvenance, TestListProvenance};
+
+fn list_row(label: &'static str, list: ListProvenance, report_count: Option<usize>) -> AnyView {
+    let mut changes = Vec::new();
+    if list.decoded_from_string {
+        changes.push("decoded from a JSON string".to_string());
+    }
+    if list.duplicates_removed > 0 {
+        changes.push(format!("{} duplicate/blank removed", list.duplicates_removed));
+    }
+    if list.normalized > 0 {
+        changes.push(format!("{} trimmed", list.normalized));
+    }
+    let report = report_count.map(|count| count.to_string()).unwrap_or_else(|| "—".to_string());
+    let report_class = match report_count {
+        Some(count) if count != list.count => "py-1 text-red-700 dark:text-red-300 font-semibold",
+        _ => "py-1 text-gray-700 dark:text-gray-200",
+    };
+    view! {
+        <tr class="border-t border-gray-200 dark:border-gray-700">
+            <td class="py-1 pr-3 font-semibold text-gray-700 dark:text-gray-200">{label}</td>
+            <td class="py-1 pr-3 font-mono text-gray-700 dark:text-gray-200">{list.key.unwrap_or_else(|| "(missing)".to_string())}</td>
+            <td class="py-1 pr-3 text-gray-700 dark:text-gray-200">{format!("{} → {}", list.raw_count, list.count)}</td>
+            <td class="py-1 pr-3 text-gray-500 dark:text-gray-400">{if changes.is_empty() { "—".to_string() } else { changes.join(", ") }}</td>
+            <td class=report_class>{report}</td>
+        </tr>
+    }.into_any()
+}
+
+// Where the F2P/P2P lists were read from, flagged when report.json's tests_status disagrees
+#[component]
+pub fn TestListProvenancePanel(provenance: RwSignal<Option<TestListProvenance>>) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+
+    move || {
+        let Some(current) = provenance.get() else {
+            return view! { <div></div> }.into_any();
+        };
+        let mismatches = current.count_mismatches();
+        let button_class = if mismatches.is_empty() {
+            "text-sm px-2 py-0.5 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700 whitespace-nowrap"
+        } else {
+            "text-sm px-2 py-0.5 rounded border border-red-300 dark:border-red-700 bg-red-50 dark:bg-red-900/30 text-red-800 dark:text-red-200 whitespace-nowrap"
+        };
+        let label = if mismatches.is_empty() { "List source" } else { "List source ≠ report" };
+        let warning = (!mismatches.is_empty()).then(|| {
+            let details: Vec<String> = mismatches
+                .iter()
+                .map(|(list, main_count, report_count)| format!("{}: {} in main.json, {} in report.json", list, main_count, report_count))
+                .collect();
+            format!("Counts differ from report.json's tests_status — {}", details.join("; "))
+        });
+
+        view! {
+            <div class="relative">
+                <button on:click=move |_| is_open.update(|open| *open = !*open) class=button_class>
+                    {label}
+                </button>
+                <Show when=move || is_open.get()>
+                    <div class="absolute left-0 mt-2 w-[36rem] z-50 p-3 space-y-2 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+                        <div class="text-xs text-gray-600 dark:text-gray-300">
+                            "Read from " <span class="font-mono">{current.source_file.clone()}</span>
+                            {current.custom_lists_applied.then_some(" — custom lists applied on top")}
+                        </div>
+                        <table class="w-full text-xs">
+                            <thead>
+                                <tr class="text-left font-semibold text-gray-500 dark:text-gray-400">
+                                    <th class="pb-1 pr-3">"List"</th>
+                                    <th class="pb-1 pr-3">"JSON key"</th>
+                                    <th class="pb-1 pr-3">"Entries"</th>
+                                    <th class="pb-1 pr-3">"Normalization"</th>
+                                    <th class="pb-1">"report.json"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {list_row("F2P", current.fail_to_pass.clone(), current.report_fail_to_pass)}
+                                {list_row("P2P", current.pass_to_pass.clone(), current.report_pass_to_pass)}
+                            </tbody>
+                        </table>
+                        {warning.clone().map(|warning| view! { <div class="text-xs text-red-700 dark:text-red-300">{warning}</div> })}
+                    </div>
+                </Show>
+            </div>
+        }.into_any()
+    }
+}
@@ -1,24 +1,33 @@
 use leptos::prelude::*;
 use leptos::task::spawn_local;
-use super::types::{ProcessingResult, TestLists, ProcessingStage, StageStatus};
+use super::types::{ApiError, CustomTestLists, ProcessingResult, TestListProvenance, TestLists, ProcessingStage, StageStatus};
 use std::collections::HashMap;
 
 #[server]
-pub async fn handle_get_test_lists(file_paths: Vec<String>) -> Result<TestLists, ServerFnError> {
+pub async fn handle_get_test_lists(file_paths: Vec<String>, custom_test_lists: Option<CustomTestLists>) -> Result<TestLists, ApiError> {
+    use crate::api::custom_test_lists::apply_custom_test_lists;
     use crate::api::file_operations::{get_test_lists};
-    Ok(get_test_lists(file_paths).unwrap())
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    let test_lists = get_test_lists(file_paths).map_err(|e| ApiError::new(ApiErrorCode::MissingFile, e))?;
+    Ok(apply_custom_test_lists(test_lists, custom_test_lists.as_ref()))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn load_test_lists(
     result: RwSignal<Option<ProcessingResult>>,
     fail_to_pass_tests: RwSignal<Vec<String>>,
     pass_to_pass_tests: RwSignal<Vec<String>>,
+    test_list_provenance: RwSignal<Option<TestListProvenance>>,
     current_selection: RwSignal<String>,
     search_for_test: impl Fn(String) + Send + Sync + 'static + Copy,
     trigger_log_analysis: impl Fn() + Send + Sync + 'static + Copy,
+    custom_test_lists: Option<CustomTestLists>,
     is_processing: RwSignal<bool>,
     current_stage: RwSignal<Option<ProcessingStage>>,
     stages: RwSignal<HashMap<ProcessingStage, StageStatus>>,
+    auto_analyze: bool,
 ) {
     if result.get().is_none() {
         return;
@@ -30,8 +39,9 @@ pub fn load_test_lists(
     }
     
     spawn_local(async move {
-        let test_lists = handle_get_test_lists(result_data.file_paths).await;
+        let test_lists = handle_get_test_lists(result_data.file_paths, custom_test_lists).await;
         if let Ok(test_lists) = test_lists {
+            test_list_provenance.set(test_lists.provenance);
             fail_to_pass_tests.set(test_lists.fail_to_pass);
             pass_to_pass_tests.set(test_lists.pass_to_pass);
             
@@ -50,12 +60,71 @@ pub fn load_test_lists(
             stages.update(|stages| {
                 stages.insert(ProcessingStage::LoadingTests, StageStatus::Completed);
             });
-            current_stage.set(None);
-            is_processing.set(false);
-            
-            // Trigger log analysis after test lists are loaded
+
+            if !auto_analyze {
+                current_stage.set(None);
+                is_processing.set(false);
+                return;
+            }
+
+            // Analyze before showing the checker; the page finishes the stage once the analysis is back
             leptos::logging::log!("Test lists loaded successfully, triggering log analysis");
+            stages.update(|stages| {
+                stages.insert(ProcessingStage::Analyzing, StageStatus::Active);
+            });
+            current_stage.set(Some(ProcessingStage::Analyzing));
+            trigger_log_analysis();
+        }
+    });
+}
+
+// Reload the test lists after a refresh, keeping the selected test when it is still listed
+#[allow(clippy::too_many_arguments)]
+pub fn reload_test_lists(
+    result: RwSignal<Option<ProcessingResult>>,
+    fail_to_pass_tests: RwSignal<Vec<String>>,
+    pass_to_pass_tests: RwSignal<Vec<String>>,
+    test_list_provenance: RwSignal<Option<TestListProvenance>>,
+    current_selection: RwSignal<String>,
+    selected_fail_to_pass_index: RwSignal<usize>,
+    selected_pass_to_pass_index: RwSignal<usize>,
+    search_for_test: impl Fn(String) + Send + Sync + 'static + Copy,
+    trigger_log_analysis: impl Fn() + Send + Sync + 'static + Copy,
+    custom_test_lists: Option<CustomTestLists>,
+    is_refreshing: RwSignal<bool>,
+) {
+    let Some(result_data) = result.get_untracked() else {
+        is_refreshing.set(false);
+        return;
+    };
+
+    let previous_f2p = fail_to_pass_tests.get_untracked().get(selected_fail_to_pass_index.get_untracked()).cloned();
+    let previous_p2p = pass_to_pass_tests.get_untracked().get(selected_pass_to_pass_index.get_untracked()).cloned();
+
+    spawn_local(async move {
+        if let Ok(test_lists) = handle_get_test_lists(result_data.file_paths, custom_test_lists).await {
+            let keep_index = |tests: &[String], previous: Option<String>, index: usize| {
+                previous
+                    .and_then(|name| tests.iter().position(|test| *test == name))
+                    .unwrap_or_else(|| index.min(tests.len().saturating_sub(1)))
+            };
+            selected_fail_to_pass_index.set(keep_index(&test_lists.fail_to_pass, previous_f2p, selected_fail_to_pass_index.get_untracked()));
+            selected_pass_to_pass_index.set(keep_index(&test_lists.pass_to_pass, previous_p2p, selected_pass_to_pass_index.get_untracked()));
+            test_list_provenance.set(test_lists.provenance);
+            fail_to_pass_tests.set(test_lists.fail_to_pass);
+            pass_to_pass_tests.set(test_lists.pass_to_pass);
+
+            let (tests, index) = if current_selection.get_untracked() == "fail_to_pass" {
+                (fail_to_pass_tests.get_untracked(), selected_fail_to_pass_index.get_untracked())
+            } else {
+                (pass_to_pass_tests.get_untracked(), selected_pass_to_pass_index.get_untracked())
+            };
+            if let Some(test_name) = tests.get(index) {
+                search_for_test(test_name.clone());
+            }
+
             trigger_log_analysis();
         }
+        is_refreshing.set(false);
     });
 }
@@ -0,0 +1,119 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::types::{ApiError, ProcessingResult, TestNameInspection, LOG_SEARCH_STAGES};
+
+#[server]
+pub async fn handle_inspect_test_name(file_paths: Vec<String>, test_name: String, agent_log: Option<String>) -> Result<TestNameInspection, ApiError> {
+    use crate::api::log_analysis::inspect_test_name;
+    use crate::api::path_guard::check_deliverable_paths;
+    use crate::app::types::ApiErrorCode;
+    check_deliverable_paths(&file_paths)?;
+    inspect_test_name(file_paths, test_name, agent_log).map_err(|e| ApiError::new(ApiErrorCode::Storage, "Failed to search the logs").with_details(e))
+}
+
+// Variants by stage log, type-erased to keep the view type shallow
+fn inspection_view(inspection: TestNameInspection) -> AnyView {
+    let stages: Vec<(&'static str, &'static str)> = LOG_SEARCH_STAGES
+        .iter()
+        .copied()
+        .filter(|(stage, _)| inspection.variants.first().is_some_and(|variant| variant.hits.iter().any(|hit| hit.stage == *stage)))
+        .collect();
+    let headers = stages.iter().map(|(_, title)| view! { <th class="pb-1 pr-4 text-right">{*title}</th> }).collect_view();
+    let rows = inspection.variants.into_iter().map(|variant| {
+        let cells = stages.iter().map(|(stage, _)| {
+            let hit = variant.hits.iter().find(|hit| hit.stage == *stage).cloned().unwrap_or_default();
+            let (cell_class, text) = match hit.first_line {
+                Some(line) => ("py-1 pr-4 text-right text-green-700 dark:text-green-300", format!("{} (line {})", hit.count, line)),
+                None => ("py-1 pr-4 text-right text-gray-400 dark:text-gray-500", "0".to_string()),
+            };
+            view! { <td class=cell_class>{text}</td> }
+        }).collect_view();
+        let label_class = if variant.searched {
+            "py-1 pr-4 text-gray-900 dark:text-white"
+        } else {
+            "py-1 pr-4 text-gray-500 dark:text-gray-400"
+        };
+        view! {
+            <tr class="border-t border-gray-200 dark:border-gray-700 align-top">
+                <td class=label_class>
+                    {variant.label}
+                    {(!variant.searched).then(|| view! { <span class="ml-1 text-xs">"(not searched)"</span> })}
+                </td>
+                <td class="py-1 pr-4 font-mono break-all text-gray-800 dark:text-gray-200">{variant.term}</td>
+                {cells}
+            </tr>
+        }
+    }).collect_view();
+    let canonical_note = (inspection.canonical != inspection.test_name).then(|| view! {
+        <div class="mb-2 text-gray-600 dark:text-gray-300">
+            "Matched in canonical form: "
+            <span class="font-mono break-all">{inspection.canonical.clone()}</span>
+        </div>
+    });
+
+    view! {
+        <div class="text-xs">
+            {canonical_note}
+            <table class="w-full text-left">
+                <thead>
+                    <tr class="font-semibold text-gray-500 dark:text-gray-400">
+                        <th class="pb-1 pr-4">"Variant"</th>
+                        <th class="pb-1 pr-4">"Term"</th>
+                        {headers}
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+// How the selected test's name is split into search variants and which of them each log
+// mentions, so a parser that misses the test can be reported with the spelling it needs
+#[component]
+pub fn TestNameInspector(
+    result: RwSignal<Option<ProcessingResult>>,
+    selected_test: Signal<Option<String>>,
+    agent_log: RwSignal<Option<String>>,
+) -> impl IntoView {
+    let inspection = RwSignal::new(None::<TestNameInspection>);
+    let loading = RwSignal::new(false);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let test_name = selected_test.get();
+        let agent_log = agent_log.get();
+        inspection.set(None);
+        error.set(None);
+        let (Some(test_name), Some(deliverable)) = (test_name, result.get_untracked()) else {
+            return;
+        };
+        loading.set(true);
+        spawn_local(async move {
+            match handle_inspect_test_name(deliverable.file_paths, test_name.clone(), agent_log).await {
+                // A newer selection may have landed while this one was in flight
+                Ok(found) if selected_test.get_untracked().as_deref() == Some(test_name.as_str()) => inspection.set(Some(found)),
+                Ok(_) => {}
+                Err(e) => error.set(Some(e.to_string())),
+            }
+            loading.set(false);
+        });
+    });
+
+    view! {
+        <div class="flex-1 min-h-0 overflow-auto p-4">
+            {move || {
+                if let Some(e) = error.get() {
+                    view! { <div class="text-sm text-red-600 dark:text-red-400">{e}</div> }.into_any()
+                } else if let Some(found) = inspection.get() {
+                    inspection_view(found)
+                } else if loading.get() {
+                    view! { <div class="text-sm text-gray-500 dark:text-gray-400">"Searching the logs for each variant..."</div> }.into_any()
+                } else {
+                    view! { <div class="text-sm text-gray-500 dark:text-gray-400">"Select a test to inspect its name"</div> }.into_any()
+                }
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,101 @@
+use leptos::prelude::*;
+
+use super::types::{LogAnalysisResult, RuntimeRegression, TestTiming};
+
+fn format_millis(millis: f64) -> String {
+    if millis >= 1000.0 {
+        format!("{:.2}s", millis / 1000.0)
+    } else {
+        format!("{:.0}ms", millis)
+    }
+}
+
+// Popover body, type-erased to keep the view type shallow
+fn timing_details(slowest: Vec<TestTiming>, regressions: Vec<RuntimeRegression>) -> AnyView {
+    let regression_section = if regressions.is_empty() {
+        view! { <div></div> }.into_any()
+    } else {
+        let rows = regressions.into_iter().map(|regression| view! {
+            <tr class="text-red-700 dark:text-red-300">
+                <td class="font-mono pr-2 break-all">{regression.test_name}</td>
+                <td class="text-right whitespace-nowrap">
+                    {format!("{} → {}", format_millis(regression.before_ms), format_millis(regression.after_ms))}
+                </td>
+            </tr>
+        }).collect_view();
+        view! {
+            <div>
+                <div class="text-xs font-semibold text-red-700 dark:text-red-300 mb-1">"Runtime exploded (before → after)"</div>
+                <table class="w-full text-xs">
+                    <tbody>{rows}</tbody>
+                </table>
+            </div>
+        }.into_any()
+    };
+
+    let mut stages: Vec<String> = Vec::new();
+    for timing in &slowest {
+        if !stages.contains(&timing.stage) {
+            stages.push(timing.stage.clone());
+        }
+    }
+    let stage_sections = stages.into_iter().map(|stage| {
+        let rows = slowest.iter().filter(|timing| timing.stage == stage).map(|timing| view! {
+            <tr class="text-gray-800 dark:text-gray-100">
+                <td class="font-mono pr-2 break-all">{timing.test_name.clone()}</td>
+                <td class="text-right whitespace-nowrap">{format_millis(timing.millis)}</td>
+            </tr>
+        }).collect_view();
+        view! {
+            <div>
+                <div class="text-xs font-semibold text-gray-500 dark:text-gray-400 mb-1">{format!("Slowest in {}", stage)}</div>
+                <table class="w-full text-xs">
+                    <tbody>{rows}</tbody>
+                </table>
+            </div>
+        }
+    }).collect_view();
+
+    view! {
+        <div class="absolute left-0 mt-2 w-[32rem] max-h-96 overflow-y-auto z-50 p-3 space-y-3 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg">
+            {regression_section}
+            {stage_sections}
+        </div>
+    }.into_any()
+}
+
+#[component]
+pub fn TimingPanel(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    let is_open = RwSignal::new(false);
+
+    move || {
+        let Some(analysis) = log_analysis_result.get() else {
+            return view! { <div></div> }.into_any();
+        };
+        let slowest = analysis.debug_info.slowest_tests;
+        let regressions = analysis.debug_info.runtime_regressions;
+        if slowest.is_empty() && regressions.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let regression_count = regressions.len();
+        let button_class = if regression_count > 0 {
+            "text-sm px-2 py-0.5 rounded border border-red-300 dark:border-red-700 bg-red-50 dark:bg-red-900/30 text-red-800 dark:text-red-200"
+        } else {
+            "text-sm px-2 py-0.5 rounded border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-200 hover:bg-gray-100 dark:hover:bg-gray-700"
+        };
+        let label = if regression_count > 0 {
+            format!("Timings ({} slower)", regression_count)
+        } else {
+            "Timings".to_string()
+        };
+
+        view! {
+            <div class="relative">
+                <button on:click=move |_| is_open.update(|open| *open = !*open) class=button_class>
+                    {label}
+                </button>
+                {move || if is_open.get() { timing_details(slowest.clone(), regressions.clone()) } else { view! { <div></div> }.into_any() }}
+            </div>
+        }.into_any()
+    }
+}
@@ -0,0 +1,132 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::search_results::handle_get_log_slice;
+use super::types::{SearchResult, TraceContext};
+
+enum TraceRow {
+    Line { number: usize, text: String, hit: bool },
+    // Trace lines folded away while collapsed
+    Gap(usize),
+}
+
+// The context above the trace, the hit, and the trace lines at hand (its key lines, or the
+// whole trace once read) with the folded stretches counted
+fn trace_rows(result: &SearchResult, trace: &TraceContext, lines: &[(usize, String)]) -> Vec<TraceRow> {
+    let mut rows = Vec::new();
+    let first_before = result.line_number - result.context_before.len();
+    for (i, text) in result.context_before.iter().enumerate() {
+        if first_before + i < trace.start_line {
+            rows.push(TraceRow::Line { number: first_before + i, text: text.clone(), hit: false });
+        }
+    }
+    if result.line_number < trace.start_line {
+        rows.push(TraceRow::Line { number: result.line_number, text: result.line_content.clone(), hit: true });
+        for (i, text) in result.context_after.iter().enumerate() {
+            if result.line_number + 1 + i < trace.start_line {
+                rows.push(TraceRow::Line { number: result.line_number + 1 + i, text: text.clone(), hit: false });
+            }
+        }
+    }
+
+    let hit_index = result.line_number.checked_sub(trace.start_line).filter(|index| *index < trace.len);
+    let mut next = 0;
+    for (i, text) in lines {
+        if *i > next {
+            rows.push(TraceRow::Gap(i - next));
+        }
+        rows.push(TraceRow::Line { number: trace.start_line + i, text: text.clone(), hit: hit_index == Some(*i) });
+        next = i + 1;
+    }
+    if trace.len > next {
+        rows.push(TraceRow::Gap(trace.len - next));
+    }
+    rows
+}
+
+/// A search hit in a long stack trace: the trace's message lines, outer frames and the frames
+/// around the hit, the rest folded behind an expander that reads it from `stage`'s log
+#[component]
+pub fn TraceContextView(
+    result: SearchResult,
+    trace: TraceContext,
+    file_paths: Vec<String>,
+    stage: String,
+    agent_log: Option<String>,
+    #[prop(optional)] highlight_ref: Option<NodeRef<leptos::html::Div>>,
+) -> impl IntoView {
+    let highlight_ref = highlight_ref.unwrap_or_default();
+    let expanded = RwSignal::new(false);
+    // The whole trace, once read
+    let full = RwSignal::new(None::<Vec<(usize, String)>>);
+    let error = RwSignal::new(None::<String>);
+    let total = trace.len;
+
+    let (start_line, len) = (trace.start_line, trace.len);
+    let source = StoredValue::new((file_paths, stage, agent_log));
+    let toggle = move || {
+        expanded.update(|expanded| *expanded = !*expanded);
+        if !expanded.get_untracked() || full.get_untracked().is_some() {
+            return;
+        }
+        let (file_paths, stage, agent_log) = source.get_value();
+        spawn_local(async move {
+            let middle = start_line + len / 2;
+            match handle_get_log_slice(file_paths, stage, agent_log, Some(middle), None, len / 2 + 1).await {
+                Ok(Some(slice)) => full.set(Some(
+                    slice.lines.into_iter().enumerate()
+                        .filter_map(|(i, text)| (slice.first_line + i).checked_sub(start_line).filter(|index| *index < len).map(|index| (index, text)))
+                        .collect(),
+                )),
+                Ok(None) => error.set(Some("The log no longer holds this trace".to_string())),
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    move || {
+        let shown = match (expanded.get(), full.get()) {
+            (true, Some(full)) => full,
+            _ => trace.lines.clone(),
+        };
+        let rows = trace_rows(&result, &trace, &shown).into_iter().map(|row| match row {
+            TraceRow::Line { number, text, hit: true } => view! {
+                <div node_ref=highlight_ref class="flex bg-yellow-200 dark:bg-yellow-800 text-gray-900 dark:text-gray-100 font-bold">
+                    <span class="w-12 text-right pr-2 text-gray-700 dark:text-gray-300 flex-shrink-0">{number}</span>
+                    <span class="flex-1">{text}</span>
+                </div>
+            }.into_any(),
+            TraceRow::Line { number, text, hit: false } => view! {
+                <div class="flex text-gray-500 dark:text-gray-400">
+                    <span class="w-12 text-right pr-2 text-gray-400 dark:text-gray-500 flex-shrink-0">{number}</span>
+                    <span class="flex-1">{text}</span>
+                </div>
+            }.into_any(),
+            TraceRow::Gap(count) => view! {
+                <button
+                    on:click=move |_| if !expanded.get_untracked() { toggle() }
+                    class="ml-12 text-gray-400 dark:text-gray-500 hover:text-blue-600 dark:hover:text-blue-400"
+                >
+                    {format!("⋯ {} trace lines", count)}
+                </button>
+            }.into_any(),
+        }).collect_view();
+
+        view! {
+            <div class="font-mono text-xs">
+                <div class="flex items-center gap-2 mb-1 font-sans text-gray-500 dark:text-gray-400">
+                    <span>{format!("Stack trace, {} lines", total)}</span>
+                    <button
+                        on:click=move |_| toggle()
+                        class="text-blue-600 dark:text-blue-400 hover:underline"
+                    >
+                        {if expanded.get() { "Collapse" } else { "Show full trace" }}
+                    </button>
+                    {move || (expanded.get() && full.get().is_none() && error.get().is_none()).then(|| view! { <span>"Loading..."</span> })}
+                    {move || error.get().map(|e| view! { <span class="text-red-600 dark:text-red-400">{e}</span> })}
+                </div>
+                {rows}
+            </div>
+        }.into_any()
+    }
+}
@@ -11,6 +11,29 @@ pub struct FileInfo {
 pub struct ValidationResult {
     pub files_to_download: Vec<FileInfo>,
     pub folder_id: String,
+    // Layout checklist of the deliverable folder, shown before the download starts
+    #[serde(default)]
+    pub structure: Vec<StructureCheckItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StructureItemStatus {
+    Present,
+    Missing,
+    Unexpected,
+}
+
+// One line of the folder layout checklist
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StructureCheckItem {
+    // Path relative to the deliverable folder, e.g. "logs/" or "results/report.json"
+    pub path: String,
+    pub status: StructureItemStatus,
+    pub required: bool,
+    // Matched file names, or what was expected when missing
+    #[serde(default)]
+    pub note: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -24,6 +47,80 @@ pub struct DownloadResult {
     pub downloaded_files: Vec<FileInfo>,
 }
 
+// Category of a failed validate/download/analyze call
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorCode {
+    InvalidLink,
+    PermissionDenied,
+    NotFound,
+    MissingFile,
+    Network,
+    Storage,
+    ParseFailure,
+    TooLarge,
+    Forbidden,
+    Internal,
+}
+
+// Error returned by the validate/download/analyze server functions, serialized as `{code, message, details}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ApiError {
+    pub code: ApiErrorCode,
+    pub message: String,
+    // Underlying error text or the files that were found, when there is more to say
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(code: ApiErrorCode, message: impl Into<String>) -> Self {
+        Self { code, message: message.into(), details: None }
+    }
+
+    pub fn with_details(mut self, details: impl std::fmt::Display) -> Self {
+        self.details = Some(details.to_string());
+        self
+    }
+
+    // What the reviewer can do about it
+    pub fn guidance(&self) -> &'static str {
+        match self.code {
+            ApiErrorCode::InvalidLink => "Paste the link of the deliverable's Google Drive folder, its reviewer-platform task, or the GitHub Actions run that produced it.",
+            ApiErrorCode::PermissionDenied => "The reviewer service account can't open this folder; ask the owner to share it.",
+            ApiErrorCode::NotFound => "The folder or file no longer exists on Drive; check the link or ask for a fresh deliverable.",
+            ApiErrorCode::MissingFile => "The deliverable is incomplete; ask the submitter to upload the missing file and refresh.",
+            ApiErrorCode::Network => "Drive could not be reached or returned an unexpected response; try again in a moment.",
+            ApiErrorCode::Storage => "The server could not write its local cache; contact whoever runs this instance.",
+            ApiErrorCode::ParseFailure => "The logs or main.json could not be parsed; check them in the Input tab.",
+            ApiErrorCode::TooLarge => "The deliverable is over this server's size limits; open it with only main.json and the logs, or ask whoever runs this instance to raise them.",
+            ApiErrorCode::Forbidden => "The request named files outside the downloaded deliverable; reload the page and open the deliverable again.",
+            ApiErrorCode::Internal => "Something went wrong on the server; try again or contact whoever runs this instance.",
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl leptos::server_fn::error::FromServerFnError for ApiError {
+    type Encoder = leptos::server_fn::codec::JsonEncoding;
+
+    fn from_server_fn_error(value: leptos::server_fn::error::ServerFnErrorErr) -> Self {
+        use leptos::server_fn::error::ServerFnErrorErr;
+        let code = match value {
+            ServerFnErrorErr::Request(_) | ServerFnErrorErr::Response(_) => ApiErrorCode::Network,
+            _ => ApiErrorCode::Internal,
+        };
+        ApiError::new(code, value.to_string())
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ProcessingResult {
     pub file_paths: Vec<String>,
@@ -38,6 +135,30 @@ pub struct ProcessingResult {
     pub gold_patch: String,
     pub test_patch: String,
     pub language: String,
+    // Task ID from a reviewer-platform link, when the deliverable was opened through one
+    #[serde(default)]
+    pub platform_task_id: String,
+    // Only main.json and the logs were downloaded, because the whole deliverable was over the size limits
+    #[serde(default)]
+    pub logs_only: bool,
+}
+
+impl ProcessingResult {
+    // Prefer the reviewer-platform task ID, falling back to main.json's task_id
+    pub fn display_task_id(&self) -> String {
+        if !self.platform_task_id.is_empty() {
+            self.platform_task_id.clone()
+        } else {
+            self.task_id.clone()
+        }
+    }
+}
+
+// Reviewer-platform task resolved to its Drive folder
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlatformTask {
+    pub task_id: String,
+    pub folder_id: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -51,6 +172,63 @@ pub struct ConversationEntry {
 pub struct TestLists {
     pub fail_to_pass: Vec<String>,
     pub pass_to_pass: Vec<String>,
+    #[serde(default)]
+    pub provenance: Option<TestListProvenance>,
+}
+
+// How one main.json list was read
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ListProvenance {
+    // JSON key the list came from; None when main.json has no such list
+    pub key: Option<String>,
+    // Entries as written in main.json
+    pub raw_count: usize,
+    // Repeated or blank entries dropped
+    pub duplicates_removed: usize,
+    // Entries whose surrounding whitespace was trimmed
+    pub normalized: usize,
+    // The list was a JSON-encoded string instead of an array
+    pub decoded_from_string: bool,
+    // Entries left after normalization
+    pub count: usize,
+}
+
+// Where the F2P/P2P lists came from, compared with report.json's tests_status
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TestListProvenance {
+    pub source_file: String,
+    pub fail_to_pass: ListProvenance,
+    pub pass_to_pass: ListProvenance,
+    // Entries in report.json's FAIL_TO_PASS/PASS_TO_PASS, when the report has tests_status
+    #[serde(default)]
+    pub report_fail_to_pass: Option<usize>,
+    #[serde(default)]
+    pub report_pass_to_pass: Option<usize>,
+    // Reviewer lists were applied on top of main.json's
+    #[serde(default)]
+    pub custom_lists_applied: bool,
+}
+
+impl TestListProvenance {
+    /// Lists whose main.json count differs from report.json's, as (label, main.json, report.json)
+    pub fn count_mismatches(&self) -> Vec<(&'static str, usize, usize)> {
+        [
+            ("F2P", self.fail_to_pass.count, self.report_fail_to_pass),
+            ("P2P", self.pass_to_pass.count, self.report_pass_to_pass),
+        ]
+        .into_iter()
+        .filter_map(|(label, count, report)| report.filter(|report| *report != count).map(|report| (label, count, report)))
+        .collect()
+    }
+}
+
+// Test lists pasted or uploaded by the reviewer, as raw text (one name per line, CSV or a JSON array)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CustomTestLists {
+    pub fail_to_pass: String,
+    pub pass_to_pass: String,
+    // Use these lists instead of main.json's; otherwise they are added to them
+    pub replace: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -59,15 +237,170 @@ pub struct SearchResult {
     pub line_content: String,
     pub context_before: Vec<String>,
     pub context_after: Vec<String>,
+    // Set when the hit sits in or just above a long stack trace
+    #[serde(default)]
+    pub trace: Option<TraceContext>,
+    // Mode the line matched in
+    #[serde(default)]
+    pub mode: SearchMode,
+    // Byte range of the matched text in `line_content`; `None` when only the whole line is known
+    // to match, e.g. a test name matched after canonicalizing the line
+    #[serde(default)]
+    pub match_range: Option<(usize, usize)>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+// How the log search matches lines against the query
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMode {
+    // The query's test-name variants, compared in canonical form
+    #[default]
+    TestName,
+    Plain,
+    CaseInsensitive,
+    Regex,
+}
+
+impl SearchMode {
+    pub const ALL: [SearchMode; 4] = [SearchMode::TestName, SearchMode::Plain, SearchMode::CaseInsensitive, SearchMode::Regex];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            SearchMode::TestName => "test_name",
+            SearchMode::Plain => "plain",
+            SearchMode::CaseInsensitive => "case_insensitive",
+            SearchMode::Regex => "regex",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.key() == key)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchMode::TestName => "Test name (smart)",
+            SearchMode::Plain => "Plain text",
+            SearchMode::CaseInsensitive => "Ignore case",
+            SearchMode::Regex => "Regex",
+        }
+    }
+}
+
+// A long stack trace around a search hit, shown collapsed to its key lines; the rest of the
+// trace is read from the log when the reviewer expands it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TraceContext {
+    // 1-based line number of the trace's first line
+    pub start_line: usize,
+    // Lines in the whole trace
+    pub len: usize,
+    // Message lines, the first and last frames and the frames around the hit, as
+    // (index into the trace, text)
+    pub lines: Vec<(usize, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct LogSearchResults {
     pub base_results: Vec<SearchResult>,
     pub before_results: Vec<SearchResult>,
     pub after_results: Vec<SearchResult>,
+    #[serde(default)]
+    pub agent_results: Vec<SearchResult>,
+}
+
+// A few lines around one line of a stage log, for hover previews
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LogSlice {
+    // 1-based line the slice is centred on
+    pub line: usize,
+    // 1-based number of `lines[0]`
+    pub first_line: usize,
+    pub lines: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    Context,
+    Removed,
+    Added,
+}
+
+// One line of a before/after diff, with its 1-based number in each log it appears in
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+    pub before_line: Option<usize>,
+    pub after_line: Option<usize>,
 }
 
+// A test's surrounding lines in the before log diffed against the after log
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ContextDiff {
+    // Line each window is anchored on; `None` when the test isn't in that log
+    pub before_anchor: Option<usize>,
+    pub after_anchor: Option<usize>,
+    pub lines: Vec<DiffLine>,
+}
+
+// Hits of one spelling of a test name in one stage log
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct VariantHits {
+    pub stage: String,
+    pub count: usize,
+    // 1-based line of the first hit
+    pub first_line: Option<usize>,
+}
+
+// One spelling of a test name, e.g. the part after ` > `, and where it shows up
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct NameVariant {
+    pub label: String,
+    pub term: String,
+    // Whether the log search looks for this variant; the rest are shown for comparison
+    pub searched: bool,
+    // One entry per stage log the deliverable has
+    pub hits: Vec<VariantHits>,
+}
+
+// How a test name is split into search variants, for filing precise parser issues
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TestNameInspection {
+    pub test_name: String,
+    pub canonical: String,
+    pub variants: Vec<NameVariant>,
+}
+
+// Main tabs of the checker in tab-bar order, keyed as `active_main_tab` stores them
+pub const MAIN_TABS: [(&str, &str); 8] = [
+    ("manual_checker", "Tests"),
+    ("analysis", "Analysis"),
+    ("report", "Report.json"),
+    ("playground", "Playground"),
+    ("input", "Input"),
+    ("agent_log", "Agent Log"),
+    ("patches", "Patches"),
+    ("debug", "Debug"),
+];
+
+// Logs the search UI can show as columns, in display order
+pub const LOG_SEARCH_STAGES: [(&str, &str); 4] = [
+    ("base", "Base Log"),
+    ("before", "Before Log"),
+    ("after", "After Log"),
+    ("agent", "Agent Log"),
+];
+
+// Columns searched until the reviewer picks others
+pub fn default_search_stages() -> Vec<String> {
+    vec!["base".to_string(), "before".to_string(), "after".to_string()]
+}
+
+// Context sizes the search UI offers besides the language's default
+pub const SEARCH_CONTEXT_SIZES: [usize; 3] = [10, 25, 50];
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct FileContent {
     pub content: String,
@@ -107,7 +440,46 @@ impl FileContents {
 pub enum ProcessingStage {
     Validating,
     Downloading,
+    // Unpacking a GitHub Actions run's artifacts into the deliverable layout
+    Extracting,
     LoadingTests,
+    Analyzing,
+}
+
+// Download cache folders of GitHub Actions runs start with this instead of a Drive folder id
+pub const ACTIONS_FOLDER_PREFIX: &str = "gha-";
+
+// GitHub Actions run a deliverable link points at, e.g. `https://github.com/owner/repo/actions/runs/123`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ActionsRun {
+    pub owner: String,
+    pub repo: String,
+    pub run_id: u64,
+}
+
+impl ActionsRun {
+    // Also accepts links to one of the run's jobs or attempts
+    pub fn parse(link: &str) -> Option<ActionsRun> {
+        let path = link.trim().split(['?', '#']).next()?;
+        let path = path.strip_prefix("https://github.com/").or_else(|| path.strip_prefix("http://github.com/"))?;
+        let parts: Vec<&str> = path.split('/').collect();
+        let valid_name = |name: &str| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) && name != "." && name != "..";
+        match parts.as_slice() {
+            [owner, repo, "actions", "runs", run_id, ..] if valid_name(owner) && valid_name(repo) => Some(ActionsRun {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                run_id: run_id.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    // Download cache folder. Dots are not allowed in folder ids, so `_`, `.` and `-` are
+    // escaped and `-` is left to separate owner, repo and run, which keeps ids one-to-one
+    pub fn folder_id(&self) -> String {
+        let escape = |name: &str| name.replace('_', "_u").replace('.', "_d").replace('-', "_h");
+        format!("{}{}-{}-{}", ACTIONS_FOLDER_PREFIX, escape(&self.owner), escape(&self.repo), self.run_id)
+    }
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -119,14 +491,316 @@ pub enum StageStatus {
 }
 
 // Log analysis types
-#[derive(Serialize, Deserialize, Clone, Debug)]
+
+// Bumped whenever `LogAnalysisResult` changes in a way older readers or persisted results
+// need a migration for (see `api::analysis_migration`)
+pub const ANALYSIS_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct LogAnalysisResult {
+    // 0 for results persisted before versioning
+    #[serde(default)]
+    pub schema_version: u32,
+    // Codes (C1..C11) of the rules actually checked; a rule that was skipped, e.g. C8 without
+    // a dataset, reads as clean in `rule_violations` but is missing here
+    #[serde(default)]
+    pub rules_evaluated: Vec<String>,
     pub test_statuses: GroupedTestStatuses,
     pub rule_violations: RuleViolations,
     pub debug_info: DebugInfo,
+    #[serde(default)]
+    pub coverage: Option<CoverageSummary>,
+    // One entry per agent log, only when the deliverable ships more than one run
+    #[serde(default)]
+    pub agent_runs: Vec<AgentRunStatuses>,
+    // Test name -> stage -> skip reason printed by the runner, for tests ignored in that stage
+    #[serde(default)]
+    pub skip_reasons: std::collections::HashMap<String, std::collections::HashMap<String, String>>,
+    // Test name -> stage -> status of each attempt, for tests retried by a rerun plugin
+    #[serde(default)]
+    pub attempts: std::collections::HashMap<String, std::collections::HashMap<String, Vec<String>>>,
+    // Reviewer overrides already applied to `test_statuses` and the rule checks
+    #[serde(default)]
+    pub overrides: Vec<StatusOverride>,
+    // Options the analysis ran with; absent for results produced before they were recorded
+    #[serde(default)]
+    pub settings: Option<AnalysisSettings>,
+    // Time the reviewer spent on the deliverable, set once a decision is recorded
+    #[serde(default)]
+    pub review_effort: Option<ReviewEffort>,
+    // How many listed tests each stage's log resolved, from `test_statuses`
+    #[serde(default)]
+    pub match_rates: Vec<StageMatchRate>,
+    // Files the analysis read, so an exported result can be traced back to its inputs
+    #[serde(default)]
+    pub sources: Vec<AnalysisSource>,
+    // Where the F2P/P2P lists the rules ran against came from
+    #[serde(default)]
+    pub test_list_provenance: Option<TestListProvenance>,
+}
+
+// One deliverable file an analysis read and what it was read as
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AnalysisSource {
+    // "main", "base", "before", "after", "agent", "agent_run", "report" or "run_script"
+    pub role: String,
+    // Path inside the deliverable folder, e.g. `logs/after.log`
+    pub path: String,
+    // Parser or framework that read the log; empty for files that aren't logs
+    #[serde(default)]
+    pub parser: Option<String>,
+    pub size: u64,
+    // Hex SHA-256 of the file as read
+    pub sha256: String,
+}
+
+// Progress of one analysis phase, as polled while `handle_analyze_logs` runs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AnalysisPhase {
+    pub key: String,
+    pub label: String,
+    // "pending", "active", "done", "skipped" or "failed"
+    pub status: String,
+    // Time spent so far while active, in total once done
+    #[serde(default)]
+    pub elapsed_ms: u64,
 }
 
+// Phases of one analysis run, in the order they run
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AnalysisProgress {
+    pub id: String,
+    pub phases: Vec<AnalysisPhase>,
+}
+
+// One file of a running download; `total` is the size Drive reported, when it did
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DownloadFileProgress {
+    pub name: String,
+    pub bytes: u64,
+    pub total: Option<u64>,
+    pub done: bool,
+}
+
+impl DownloadFileProgress {
+    /// Share downloaded, 0-100, when the size is known
+    pub fn percent(&self) -> Option<u64> {
+        match self.total {
+            _ if self.done => Some(100),
+            Some(total) if total > 0 => Some((self.bytes * 100 / total).min(100)),
+            _ => None,
+        }
+    }
+}
+
+// Files of one deliverable download, in download order
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DownloadProgress {
+    pub id: String,
+    pub files: Vec<DownloadFileProgress>,
+    // Set once the download ended, successfully or not
+    pub finished: bool,
+}
+
+// One deliverable of a batch; `status` is queued, validating, downloading, analyzing, done or failed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BatchItem {
+    pub link: String,
+    pub status: String,
+    // Cache folder once validated, which is also the checker route for Drive folders
+    pub folder_id: Option<String>,
+    pub error: Option<String>,
+    // Set when the analysis finished: `RuleViolations::summary()` and the violated rule codes
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub violated: Vec<String>,
+}
+
+// A batch of deliverable links analyzed together, in the order they were given
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct BatchStatus {
+    pub id: String,
+    pub items: Vec<BatchItem>,
+}
+
+// Review time of one deliverable, from loading it to recording the decision (seconds since the epoch)
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReviewEffort {
+    pub started_at: u64,
+    pub decided_at: u64,
+    pub seconds: u64,
+    pub verdict: String,
+}
+
+// Review time across every deliverable with a recorded decision on this server
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReviewEffortSummary {
+    pub reviews: usize,
+    pub total_seconds: u64,
+    pub mean_seconds: u64,
+    pub median_seconds: u64,
+}
+
+// Numbers on the dashboard, computed from one pass over the stored analyses
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DashboardSummary {
+    pub parser_quality: Vec<ParserQuality>,
+    pub review_effort: ReviewEffortSummary,
+}
+
+// Everything besides the deliverable's files that shaped an analysis, kept with the
+// persisted result so it can be reproduced and audited
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AnalysisSettings {
+    // Language from main.json
+    pub language: String,
+    // Parser that handled the logs; detected from the logs when the language has none
+    pub parser: String,
+    // Framework pinned by the deliverable's run script
+    #[serde(default)]
+    pub pinned_framework: Option<String>,
+    // Detection rules file the analysis loaded; unset when none was configured or it failed to load
+    #[serde(default)]
+    pub detection_rules_file: Option<String>,
+    #[serde(default)]
+    pub agent_log: Option<String>,
+    // Reviewer-edited test lists, as entered
+    #[serde(default)]
+    pub custom_test_lists: Option<CustomTestLists>,
+    pub duplicate_min_distance: usize,
+    pub duplicate_context_window: usize,
+    pub duplicate_require_status_conflict: bool,
+    // C10 threshold in percent of the base log's tests; 0 when the rule is off
+    #[serde(default)]
+    pub p2p_breadth_min_percent: f64,
+    #[serde(default)]
+    pub p2p_breadth_min_base_tests: usize,
+    pub noise_filter_disabled: bool,
+    // Configured noise patterns; empty means the built-in defaults
+    #[serde(default)]
+    pub noise_patterns: Vec<String>,
+    // Noise patterns the language's config entry adds to those
+    #[serde(default)]
+    pub language_noise_patterns: Vec<String>,
+    // Test-name rewrites from the language's config entry, as `pattern → replacement`
+    #[serde(default)]
+    pub name_rewrites: Vec<String>,
+    // Lines around each search hit; 0 for analyses saved before it was configurable
+    #[serde(default)]
+    pub search_context: usize,
+    // Reviewer classes of patch files for C7, by path from `patches/`; unlisted patches are classed by name
+    #[serde(default)]
+    pub patch_classes: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub experimental_parsers: Vec<String>,
+    #[serde(default)]
+    pub disabled_parsers: Vec<String>,
+    // `any_fail` or `final_attempt`
+    #[serde(default)]
+    pub playwright_retry_policy: String,
+    #[serde(default)]
+    pub jest_file_names: bool,
+    // Unix seconds
+    #[serde(default)]
+    pub analyzed_at: u64,
+    #[serde(default)]
+    pub app_version: String,
+}
+
+impl LogAnalysisResult {
+    /// The suite whose summary implied this stage's pass, when no per-test line was printed
+    pub fn inferred_suite(&self, test_name: &str, stage: &str) -> Option<&str> {
+        self.debug_info
+            .inferred_passes
+            .iter()
+            .find(|inferred| inferred.test_name == test_name && inferred.stage == stage)
+            .map(|inferred| inferred.suite.as_str())
+    }
+
+    pub fn override_for(&self, test_name: &str, stage: &str) -> Option<&StatusOverride> {
+        self.overrides.iter().find(|o| o.test_name == test_name && o.stage == stage)
+    }
+}
+
+// A reviewer's correction of one test's computed status in one stage
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusOverride {
+    pub test_name: String,
+    // base, before, after or agent
+    pub stage: String,
+    // New status; empty when removing an override
+    pub status: String,
+    pub reason: String,
+    pub author: String,
+    // Unix seconds, set by the server
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+// `YYYY-MM-DD HH:MM UTC` of a Unix timestamp, for tooltips and exports
+pub fn format_utc(timestamp: u64) -> String {
+    let (date, seconds) = utc_date(timestamp);
+    format!("{} {:02}:{:02} UTC", date, seconds / 3_600, (seconds % 3_600) / 60)
+}
+
+// `YYYY-MM-DD HH:MM:SS` of a Unix timestamp, for rows read by other tools
+pub fn format_utc_seconds(timestamp: u64) -> String {
+    let (date, seconds) = utc_date(timestamp);
+    format!("{} {:02}:{:02}:{:02}", date, seconds / 3_600, (seconds % 3_600) / 60, seconds % 60)
+}
+
+// `YYYY-MM-DD` of a Unix timestamp and the seconds into that day
+fn utc_date(timestamp: u64) -> (String, u64) {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+    // Civil-from-days (Howard Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (format!("{:04}-{:02}-{:02}", year, month, day), seconds)
+}
+
+impl StatusOverride {
+    pub fn created_at_utc(&self) -> String {
+        format_utc(self.created_at)
+    }
+
+    // One-line provenance, e.g. `passed by alice on 2025-01-02 10:00 UTC: parser missed it`
+    pub fn describe(&self) -> String {
+        format!("{} by {} on {}: {}", self.status, self.author, self.created_at_utc(), self.reason)
+    }
+}
+
+// Per-test statuses from a single agent run
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AgentRunStatuses {
+    pub log_name: String,
+    pub statuses: std::collections::HashMap<String, String>,
+}
+
+// Coverage for golden-patch files, present when the deliverable ships a coverage report
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CoverageSummary {
+    pub files: Vec<FileCoverage>,
+    // Soft warnings, not rule violations
+    pub warnings: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FileCoverage {
+    pub path: String,
+    pub lines_total: usize,
+    pub lines_covered: usize,
+    pub in_report: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct RuleViolations {
     pub c1_failed_in_base_present_in_p2p: RuleViolation,
     pub c2_failed_in_after_present_in_f2p_or_p2p: RuleViolation,
@@ -135,18 +809,425 @@ pub struct RuleViolations {
     pub c5_duplicates_in_same_log: RuleViolation,
     pub c6_test_marked_failed_in_report_but_passing_in_agent: RuleViolation,
     pub c7_f2p_tests_in_golden_source_diff: RuleViolation,
+    #[serde(default)]
+    pub c8_test_lists_differ_from_dataset: RuleViolation,
+    #[serde(default)]
+    pub c9_patch_failed_to_apply: RuleViolation,
+    #[serde(default)]
+    pub c10_p2p_list_too_narrow: RuleViolation,
+    #[serde(default)]
+    pub c11_f2p_skipped_in_after_or_agent: RuleViolation,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct RuleViolation {
     pub has_problem: bool,
     pub examples: Vec<String>,
 }
 
+impl RuleViolations {
+    // Rules paired with their short codes, in C1..C11 order
+    pub fn entries(&self) -> Vec<(&'static str, &RuleViolation)> {
+        vec![
+            ("C1", &self.c1_failed_in_base_present_in_p2p),
+            ("C2", &self.c2_failed_in_after_present_in_f2p_or_p2p),
+            ("C3", &self.c3_f2p_success_in_before),
+            ("C4", &self.c4_p2p_missing_in_base_and_not_passing_in_before),
+            ("C5", &self.c5_duplicates_in_same_log),
+            ("C6", &self.c6_test_marked_failed_in_report_but_passing_in_agent),
+            ("C7", &self.c7_f2p_tests_in_golden_source_diff),
+            ("C8", &self.c8_test_lists_differ_from_dataset),
+            ("C9", &self.c9_patch_failed_to_apply),
+            ("C10", &self.c10_p2p_list_too_narrow),
+            ("C11", &self.c11_f2p_skipped_in_after_or_agent),
+        ]
+    }
+
+    pub fn get_mut(&mut self, code: &str) -> Option<&mut RuleViolation> {
+        match code {
+            "C1" => Some(&mut self.c1_failed_in_base_present_in_p2p),
+            "C2" => Some(&mut self.c2_failed_in_after_present_in_f2p_or_p2p),
+            "C3" => Some(&mut self.c3_f2p_success_in_before),
+            "C4" => Some(&mut self.c4_p2p_missing_in_base_and_not_passing_in_before),
+            "C5" => Some(&mut self.c5_duplicates_in_same_log),
+            "C6" => Some(&mut self.c6_test_marked_failed_in_report_but_passing_in_agent),
+            "C7" => Some(&mut self.c7_f2p_tests_in_golden_source_diff),
+            "C8" => Some(&mut self.c8_test_lists_differ_from_dataset),
+            "C9" => Some(&mut self.c9_patch_failed_to_apply),
+            "C10" => Some(&mut self.c10_p2p_list_too_narrow),
+            "C11" => Some(&mut self.c11_f2p_skipped_in_after_or_agent),
+            _ => None,
+        }
+    }
+
+    pub fn has_any_problem(&self) -> bool {
+        self.entries().iter().any(|(_, violation)| violation.has_problem)
+    }
+
+    // One-line summary of the violated rules with their example counts
+    pub fn summary(&self) -> String {
+        let violated: Vec<String> = self
+            .entries()
+            .into_iter()
+            .filter(|(_, violation)| violation.has_problem)
+            .map(|(code, violation)| format!("{} ({})", code, violation.examples.len()))
+            .collect();
+        if violated.is_empty() {
+            "clean".to_string()
+        } else {
+            violated.join(", ")
+        }
+    }
+}
+
+// Rules the Analysis tab can check again on their own, from the stored statuses and the files
+// on disk; C5 and C9 need the raw logs and C8 the dataset, so they rerun with the whole analysis
+pub const RECHECKABLE_RULES: &[&str] = &["C1", "C2", "C3", "C4", "C6", "C7", "C10", "C11"];
+
+// Classes a reviewer can give a patch file for C7; `ignore` leaves the patch out of the check
+pub const PATCH_CLASSES: &[&str] = &["source", "test", "ignore"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PatchFile {
+    pub name: String,
+    // Path from the `patches/` folder on, the key its class is stored under
+    pub path: String,
+    // Class C7 uses: the reviewer's if set, else `guessed`
+    pub class: String,
+    // Class guessed from the file name
+    pub guessed: String,
+}
+
+// One test the scratch parser found in a pasted snippet
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScratchParsedTest {
+    pub name: String,
+    // "passed", "failed" or "ignored"
+    pub status: String,
+    // 1-based line of the snippet that first mentions the test
+    pub line: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ScratchParse {
+    // Parser or framework that read the snippet; empty when none recognized it
+    pub parser: String,
+    pub tests: Vec<ScratchParsedTest>,
+}
+
+// report.json's verdicts split the way the Report tab cross-checks them
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReportCategories {
+    // Layout the report was read with, see `report_schema`
+    pub schema: String,
+    // Whether the report itself lists FAIL_TO_PASS/PASS_TO_PASS; otherwise its statuses were
+    // split by the deliverable's test lists
+    pub from_report: bool,
+    pub f2p_success: Vec<String>,
+    pub f2p_failure: Vec<String>,
+    pub p2p_success: Vec<String>,
+    pub p2p_failure: Vec<String>,
+}
+
+// One line of a patch's diff hunk
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PatchDiffLine {
+    pub kind: DiffLineKind,
+    // 1-based line in the old file, absent for added lines
+    pub old_line: Option<usize>,
+    // 1-based line in the new file, absent for removed lines
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DiffHunk {
+    // The `@@ -a,b +c,d @@ …` line as written
+    pub header: String,
+    pub lines: Vec<PatchDiffLine>,
+}
+
+// One file changed by a patch
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DiffFile {
+    // `/dev/null` for created files
+    pub old_path: String,
+    // `/dev/null` for deleted files
+    pub new_path: String,
+    pub hunks: Vec<DiffHunk>,
+    pub added: usize,
+    pub removed: usize,
+    pub binary: bool,
+}
+
+// A patch file of the deliverable, parsed for the Patches tab
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ParsedPatch {
+    pub name: String,
+    // Class C7 uses for it, see `PatchFile`
+    pub class: String,
+    pub files: Vec<DiffFile>,
+    // Why the patch couldn't be shown, e.g. too large or unreadable
+    pub error: Option<String>,
+}
+
+// Human-readable description for a rule code as returned by `RuleViolations::entries`
+pub fn rule_description(code: &str) -> &'static str {
+    match code {
+        "C1" => "Pass-to-pass tests that failed in base",
+        "C2" => "Tests that failed in after but are present in F2P or P2P",
+        "C3" => "Fail-to-pass tests that succeeded in before",
+        "C4" => "Pass-to-pass tests missing in base and not passing in before",
+        "C5" => "Duplicate test entries in the same log",
+        "C6" => "Tests marked failed in report but passing in agent log",
+        "C7" => "Fail-to-pass tests present in golden source diff",
+        "C8" => "Test lists differ from the official SWE-bench dataset instance",
+        "C9" => "A patch failed to apply in a before/after/agent run",
+        "C10" => "P2P list is a suspiciously small share of the tests in the base log",
+        "C11" => "Fail-to-pass tests skipped in after or agent log, so the fix was never exercised",
+        _ => "Unknown rule",
+    }
+}
+
+// Test name of a rule example, dropping the ` (…)` note the rule appended, e.g.
+// "test_a (base: failed)". A Django-style ` (module.Class)` suffix is part of the name,
+// as is a ` (` inside pytest's `[…]` parameters
+pub fn example_test_name(example: &str) -> &str {
+    for (index, _) in example.match_indices(" (") {
+        let before = &example[..index];
+        if before.matches('[').count() > before.matches(']').count() {
+            continue;
+        }
+        let rest = &example[index + 2..];
+        let is_class_suffix = rest.find(')').is_some_and(|close| {
+            let (class, after) = (&rest[..close], &rest[close + 1..]);
+            class.contains('.')
+                && class.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                && (after.is_empty() || after.starts_with(" ("))
+        });
+        if !is_class_suffix {
+            return &example[..index];
+        }
+    }
+    example
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct DebugInfo {
     pub log_counts: Vec<LogCount>,
     pub duplicate_examples_per_log: std::collections::HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub run_script: Option<RunScriptHints>,
+    // File name of the agent log behind the `agent` stage column
+    #[serde(default)]
+    pub agent_log: Option<String>,
+    // Stages (base/before/after) whose log the deliverable lacks
+    #[serde(default)]
+    pub missing_stages: Vec<String>,
+    // report.json layout that was recognized, e.g. `swebench_tests_status` or `msft v2`
+    #[serde(default)]
+    pub report_schema: Option<String>,
+    // Set when report.json exists but matches no known layout
+    #[serde(default)]
+    pub report_diagnostic: Option<String>,
+    // Stages whose run was killed or timed out, so missing results are infrastructure, not the patch
+    #[serde(default)]
+    pub aborted_stages: Vec<StageAbort>,
+    // Stages whose run was narrowed by a test filter, so listed tests never ran
+    #[serde(default)]
+    pub filtered_stages: Vec<StageFilter>,
+    // Tests marked xfail that passed, kept apart so xfail-heavy suites don't hide regressions
+    #[serde(default)]
+    pub xpassed: Vec<XPassedTest>,
+    // Slowest tests per stage, from durations the runner printed
+    #[serde(default)]
+    pub slowest_tests: Vec<TestTiming>,
+    // Tests whose runtime exploded between the before and after runs
+    #[serde(default)]
+    pub runtime_regressions: Vec<RuntimeRegression>,
+    // C5 duplicates with the log lines of each occurrence
+    #[serde(default)]
+    pub duplicates: Vec<DuplicateTest>,
+    // Lines the parsers skipped or merged, so reviewers can tell when parse quality is questionable
+    #[serde(default)]
+    pub parse_warnings: Vec<ParseWarning>,
+    // Karma results per browser and Playwright results per project, before repeats were merged
+    #[serde(default)]
+    pub browser_runs: Vec<BrowserRun>,
+    // Passes read off a Vitest suite summary rather than a per-test line
+    #[serde(default)]
+    pub inferred_passes: Vec<InferredPass>,
+    // Cargo test binaries per stage in log order; a repeated binary means concatenated runs
+    #[serde(default)]
+    pub test_binaries: Vec<TestBinaryRun>,
+    // Tool and platform versions that differ between the base, before and after logs
+    #[serde(default)]
+    pub environment_drift: Vec<EnvironmentDrift>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InferredPass {
+    pub stage: String,
+    pub test_name: String,
+    // Test file whose collapsed summary implied the pass
+    pub suite: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BrowserRun {
+    pub stage: String,
+    // As the runner prints it, e.g. `Chrome Headless 120.0.6099.109 (Linux x86_64)` or `chromium`
+    pub browser: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    #[serde(default)]
+    pub failed_tests: Vec<String>,
+}
+
+// What a `ParseWarning` is about
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseWarningKind {
+    // A note from the language parser itself, e.g. a test that started but never reported
+    ParserNote,
+    // No test results recognized in the log
+    NoResults,
+    // Tests reported as both passed and failed
+    ConflictingStatus,
+    // Result-like lines whose status no parser recognized
+    UnrecognizedStatus,
+    // Test names that only differ by case or whitespace
+    NameCollision,
+    // Lines blanked by the noise filter
+    NoiseStripped,
+    // Status lines moved back onto their test line
+    LinesStitched,
+    // Passes inferred from collapsed suite summaries
+    InferredPasses,
+}
+
+impl ParseWarningKind {
+    // Notes about the log as a whole, shown next to its counts rather than under parse quality
+    pub fn is_log_note(self) -> bool {
+        matches!(self, Self::ParserNote | Self::NoResults | Self::ConflictingStatus)
+    }
+
+    // Dropped lines and name collisions can change rule outcomes; noise and stitching counts are informational
+    pub fn may_change_outcome(self) -> bool {
+        matches!(self, Self::UnrecognizedStatus | Self::NameCollision)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ParseWarning {
+    pub stage: String,
+    pub kind: ParseWarningKind,
+    pub message: String,
+    // Lines or names affected
+    pub count: usize,
+    // A few of them, as they appear in the log
+    #[serde(default)]
+    pub examples: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DuplicateTest {
+    pub stage: String,
+    pub test_name: String,
+    // 1-based line numbers, as shown in the log viewer
+    pub lines: Vec<usize>,
+}
+
+// One report of a duplicated test in a log
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DuplicateOccurrence {
+    // 1-based, as shown in the log viewer
+    pub line: usize,
+    // Status as printed, e.g. `ok` or `FAILED`
+    pub status: String,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+}
+
+// A test reported more than once in one log, with every occurrence
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DetectedDuplicate {
+    pub test_name: String,
+    // Test binary or file the occurrences were printed under, when the log names one
+    pub source: Option<String>,
+    pub occurrences: Vec<DuplicateOccurrence>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TestTiming {
+    pub stage: String,
+    pub test_name: String,
+    pub millis: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RuntimeRegression {
+    pub test_name: String,
+    pub before_ms: f64,
+    pub after_ms: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct XPassedTest {
+    pub stage: String,
+    pub test_name: String,
+    // Strict xfail, so the unexpected pass was counted as a failure
+    pub strict: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StageAbort {
+    pub stage: String,
+    // `killed` (OOM / SIGKILL) or `timeout`
+    pub reason: String,
+    // Log line that gave it away
+    pub evidence: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StageFilter {
+    pub stage: String,
+    // Filtering command, `N filtered out` summary or `running 0 tests`
+    pub evidence: String,
+    // Listed tests with no result in this stage
+    pub missing_tests: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EnvironmentDrift {
+    // `python`, `pytest`, `rustc`, `node`, `platform`, ...
+    pub tool: String,
+    // (stage, version) for every stage whose log printed one, in stage order
+    pub versions: Vec<(String, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TestBinaryRun {
+    pub stage: String,
+    // Source file and crate, e.g. `tests/cli.rs (cli)`, or `doc-tests foo`
+    pub binary: String,
+    // 1 for the first time this binary ran in the log, 2 for a rerun, ...
+    pub run: usize,
+    // Count from `running N tests`
+    pub declared: Option<usize>,
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+}
+
+// Framework and working directory pinned by a run script shipped with the deliverable
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RunScriptHints {
+    pub source_file: String,
+    pub command: String,
+    pub framework: String,
+    pub working_dir: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -156,8 +1237,16 @@ pub struct LogCount {
     pub failed: usize,
     pub ignored: usize,
     pub all: usize,
+    // Parser/framework that produced the results, e.g. `pytest_v2` or `nextest`
+    #[serde(default)]
+    pub parser: Option<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
+/// Stage status for every test when the deliverable has no log for that stage
+pub const NO_LOG_STATUS: &str = "no_log";
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct StageStatusSummary {
     pub base: String,
@@ -167,12 +1256,85 @@ pub struct StageStatusSummary {
     pub report: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+impl StageStatusSummary {
+    pub fn status_for(&self, stage: &str) -> Option<&str> {
+        match stage {
+            "base" => Some(&self.base),
+            "before" => Some(&self.before),
+            "after" => Some(&self.after),
+            "agent" => Some(&self.agent),
+            "report" => Some(&self.report),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct GroupedTestStatuses {
     pub f2p: std::collections::HashMap<String, StageStatusSummary>,
     pub p2p: std::collections::HashMap<String, StageStatusSummary>,
 }
 
+// Stages whose logs are parsed, in pipeline order
+const LOG_STAGES: &[&str] = &["base", "before", "after", "agent"];
+
+impl GroupedTestStatuses {
+    // Listed tests each stage's log resolved to a status other than `missing`; tests of a
+    // stage without a log are left out, so a missing log doesn't read as a parser miss.
+    // F2P tests are left out of the before stage too: most are added by the test patch, so
+    // missing there is expected
+    pub fn match_rates(&self) -> Vec<StageMatchRate> {
+        let count = |tests: &std::collections::HashMap<String, StageStatusSummary>, stage: &str| {
+            let statuses: Vec<&str> = tests.values().filter_map(|summary| summary.status_for(stage)).filter(|status| *status != NO_LOG_STATUS).collect();
+            (statuses.iter().filter(|status| **status != "missing").count(), statuses.len())
+        };
+        LOG_STAGES
+            .iter()
+            .map(|stage| {
+                let (f2p_resolved, f2p_total) = if *stage == "before" { (0, 0) } else { count(&self.f2p, stage) };
+                let (p2p_resolved, p2p_total) = count(&self.p2p, stage);
+                StageMatchRate { stage: stage.to_string(), f2p_resolved, f2p_total, p2p_resolved, p2p_total }
+            })
+            .filter(|rate| rate.f2p_total + rate.p2p_total > 0)
+            .collect()
+    }
+}
+
+// Parser match quality of one stage: listed tests resolved to a status, out of those listed
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct StageMatchRate {
+    pub stage: String,
+    pub f2p_resolved: usize,
+    pub f2p_total: usize,
+    pub p2p_resolved: usize,
+    pub p2p_total: usize,
+}
+
+impl StageMatchRate {
+    // Resolved share of F2P and P2P tests together, `None` when the stage listed none
+    pub fn fraction(&self) -> Option<f64> {
+        let total = self.f2p_total + self.p2p_total;
+        (total > 0).then(|| (self.f2p_resolved + self.p2p_resolved) as f64 / total as f64)
+    }
+
+    pub fn f2p_fraction(&self) -> Option<f64> {
+        (self.f2p_total > 0).then(|| self.f2p_resolved as f64 / self.f2p_total as f64)
+    }
+
+    pub fn p2p_fraction(&self) -> Option<f64> {
+        (self.p2p_total > 0).then(|| self.p2p_resolved as f64 / self.p2p_total as f64)
+    }
+}
+
+// Match quality pooled over every stored analysis of one language and parser
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ParserQuality {
+    pub language: String,
+    pub parser: String,
+    pub analyses: usize,
+    pub stages: Vec<StageMatchRate>,
+}
+
 #[derive(Clone, Default)]
 pub struct LoadedFileTypes {
     pub base: bool,
@@ -215,3 +1377,131 @@ pub struct DeliverableInfo {
     pub instance_id: String,
     pub task_id: String,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReviewDecision {
+    pub deliverable_link: String,
+    pub instance_id: String,
+    pub reviewer: String,
+    // "approved", "rejected" or "needs_changes"
+    pub verdict: String,
+    pub comment: String,
+    // Filled in on the server from the analysis, e.g. "C1 (3), C5 (1)" or "clean"
+    #[serde(default)]
+    pub violation_summary: String,
+    // Filled in on the server from the review timer started when the deliverable was loaded
+    #[serde(default)]
+    pub review_seconds: Option<u64>,
+}
+
+// A deliverable a lead queued for a reviewer; the reviewer's decision on it closes the item
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueStatus {
+    Assigned,
+    InReview,
+    Done,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct QueueItem {
+    pub id: u64,
+    pub deliverable_link: String,
+    pub assignee: String,
+    pub added_by: String,
+    pub status: QueueStatus,
+    // Verdict of the decision that closed the item
+    #[serde(default)]
+    pub verdict: Option<String>,
+    pub added_at: u64,
+    #[serde(default)]
+    pub decided_at: Option<u64>,
+}
+
+// One recorded decision in a deliverable's decision log; the latest entry is the verdict that stands
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DecisionLogEntry {
+    pub reviewer: String,
+    pub verdict: String,
+    pub comment: String,
+    pub decided_at: u64,
+    // Another reviewer's decision this one replaced (last write wins)
+    #[serde(default)]
+    pub supersedes_reviewer: Option<String>,
+    #[serde(default)]
+    pub supersedes_verdict: Option<String>,
+}
+
+// Who else has the deliverable open, and the decision someone else already recorded on it
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ReviewPresence {
+    pub others: Vec<String>,
+    pub other_decision: Option<DecisionLogEntry>,
+}
+
+// Statuses and rule results of one analysis run, kept so re-runs of a deliverable can be compared
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AnalysisSnapshot {
+    // Unix seconds
+    pub analyzed_at: u64,
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub parser: String,
+    pub rules_evaluated: Vec<String>,
+    pub test_statuses: GroupedTestStatuses,
+    pub rule_violations: RuleViolations,
+}
+
+// A test whose status in one stage differs between two runs; empty when the run didn't list it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StatusChange {
+    pub test_name: String,
+    // "f2p" or "p2p"
+    pub list: String,
+    pub stage: String,
+    pub previous: String,
+    pub current: String,
+}
+
+// A rule whose verdict or examples differ between two runs
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RuleChange {
+    pub code: String,
+    pub was_violated: bool,
+    pub is_violated: bool,
+    pub added_examples: Vec<String>,
+    pub removed_examples: Vec<String>,
+}
+
+// What changed between the previous and the latest analysis of a deliverable
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AnalysisRunDiff {
+    pub previous: AnalysisRunInfo,
+    pub current: AnalysisRunInfo,
+    pub status_changes: Vec<StatusChange>,
+    pub rule_changes: Vec<RuleChange>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AnalysisRunInfo {
+    pub analyzed_at: u64,
+    pub app_version: String,
+    pub parser: String,
+}
+
+// What the shareable summary page shows for a deliverable with a stored analysis
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DeliverableSummary {
+    pub folder_id: String,
+    pub analysis: LogAnalysisResult,
+    // The decision that stands, if one was recorded
+    pub decision: Option<DecisionLogEntry>,
+}
+
+// The analysis of a deliverable as downloadable files: the full result as JSON and a Markdown summary
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportedReport {
+    pub json: String,
+    pub markdown: String,
+}
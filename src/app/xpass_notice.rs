@@ -0,0 +1,35 @@
+use leptos::prelude::*;
+
+use super::types::LogAnalysisResult;
+
+#[component]
+pub fn XPassNotice(log_analysis_result: RwSignal<Option<LogAnalysisResult>>) -> impl IntoView {
+    move || {
+        let xpassed = log_analysis_result.get().map(|analysis| analysis.debug_info.xpassed).unwrap_or_default();
+        if xpassed.is_empty() {
+            return view! { <div></div> }.into_any();
+        }
+        let strict = xpassed.iter().filter(|test| test.strict).count();
+        let label = if strict > 0 {
+            format!("XPASS: {} ({} strict)", xpassed.len(), strict)
+        } else {
+            format!("XPASS: {}", xpassed.len())
+        };
+        let details = xpassed
+            .iter()
+            .map(|test| format!("{}{}: {}", test.stage, if test.strict { " (strict, failed)" } else { "" }, test.test_name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let class = if strict > 0 {
+            "text-sm px-2 py-0.5 rounded border border-red-300 dark:border-red-700 bg-red-50 dark:bg-red-900/30 text-red-800 dark:text-red-200 whitespace-nowrap"
+        } else {
+            "text-sm px-2 py-0.5 rounded border border-yellow-300 dark:border-yellow-700 bg-yellow-50 dark:bg-yellow-900/30 text-yellow-800 dark:text-yellow-200 whitespace-nowrap"
+        };
+
+        view! {
+            <span class=class title=format!("Expected failures that passed\n{}", details)>
+                {label}
+            </span>
+        }.into_any()
+    }
+}
@@ -62,7 +62,8 @@ async fn fetch_new_token() -> Result<String> {
     let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
     let claims = Claims {
         iss: service_account.client_email.clone(),
-        scope: "https://www.googleapis.com/auth/drive.readonly".to_string(),
+        // Sheets access is only used to append review decisions to a configured spreadsheet
+        scope: "https://www.googleapis.com/auth/drive.readonly https://www.googleapis.com/auth/spreadsheets".to_string(),
         aud: service_account.token_uri.clone(),
         exp: now + 3600, // Token expires in 1 hour
         iat: now,
@@ -117,6 +118,14 @@ async fn fetch_new_token() -> Result<String> {
     Ok(access_token)
 }
 
+/// Email of the service account, which deliverable folders must be shared with
+#[cfg(feature = "ssr")]
+pub fn service_account_email() -> Option<String> {
+    let credentials_path = std::env::var("GOOGLE_APPLICATION_CREDENTIALS").ok()?;
+    let key_content = std::fs::read_to_string(credentials_path).ok()?;
+    serde_json::from_str::<ServiceAccountKey>(&key_content).ok().map(|key| key.client_email)
+}
+
 /// Initialize service account auth (just validates that credentials exist)
 #[cfg(feature = "ssr")]
 pub async fn init_service_account_auth() -> Result<()> {
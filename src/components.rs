@@ -1,3 +1,5 @@
+pub mod diff_viewer;
+
 use leptos::*;
 use leptos::prelude::*;
 #[cfg(feature = "hydrate")]
@@ -7,94 +9,68 @@ use wasm_bindgen::JsCast;
 #[cfg(feature = "hydrate")]
 use web_sys::{window, HtmlElement};
 
-#[component]
-pub fn ThemeToggle() -> impl IntoView {
-    // Create signal that defaults to light mode on server
-    let is_dark = RwSignal::new(false);
+/// Whether the dark theme is on; `App` provides it so the header toggle and the command
+/// palette flip the same state
+#[derive(Clone, Copy)]
+pub struct DarkMode(pub RwSignal<bool>);
 
-    // Client-side initialization - only runs in browser
-    #[cfg(feature = "hydrate")]
-    {
-        let is_dark = is_dark.clone();
-        spawn_local(async move {
-            // Check local storage first
+impl DarkMode {
+    /// Switch the theme, remembering the choice in local storage
+    pub fn set(&self, dark: bool) {
+        self.0.set(dark);
+        #[cfg(feature = "hydrate")]
+        {
             if let Some(win) = window() {
                 if let Ok(Some(local_storage)) = win.local_storage() {
-                    if let Ok(Some(value)) = local_storage.get_item("theme") {
-                        let dark = value == "dark";
-                        is_dark.set(dark);
-                        // Apply theme class immediately
-                        if let Some(document) = win.document() {
-                            if let Some(html_el) = document
-                                .get_elements_by_tag_name("html")
-                                .item(0)
-                                .and_then(|el| el.dyn_into::<HtmlElement>().ok())
-                            {
-                                if dark {
-                                    let _ = html_el.class_list().add_1("dark");
-                                } else {
-                                    let _ = html_el.class_list().remove_1("dark");
-                                }
-                            }
-                        }
-                        return;
-                    }
+                    let _ = local_storage.set_item("theme", if dark { "dark" } else { "light" });
                 }
             }
+            apply_theme_class(dark);
+        }
+    }
 
-            // Default to light mode if no local storage value (simpler than match_media for now)
-            is_dark.set(false);
-            if let Some(document) = window()
-                .and_then(|w| w.document())
-            {
-                if let Some(html_el) = document
-                    .get_elements_by_tag_name("html")
-                    .item(0)
-                    .and_then(|el| el.dyn_into::<HtmlElement>().ok())
-                {
-                    let _ = html_el.class_list().remove_1("dark");
-                }
-            }
-        });
+    pub fn toggle(&self) {
+        self.set(!self.0.get_untracked());
     }
+}
 
-    // Toggle action - only runs on client
-    let toggle_theme = Action::new(move |_: &()| async move {
-        #[cfg(feature = "hydrate")]
-        {
-            let new_dark = !is_dark.get();
-            is_dark.set(new_dark);
-            let new_theme = if new_dark { "dark" } else { "light" };
-            
-            // Update local storage
-            if let Some(win) = window() {
-                if let Ok(Some(local_storage)) = win.local_storage() {
-                    let _ = local_storage.set_item("theme", new_theme);
-                }
-            }
-            
-            // Update html class
-            if let Some(document) = window()
-                .and_then(|w| w.document())
-            {
-                if let Some(html_el) = document
-                    .get_elements_by_tag_name("html")
-                    .item(0)
-                    .and_then(|el| el.dyn_into::<HtmlElement>().ok())
-                {
-                    if new_dark {
-                        let _ = html_el.class_list().add_1("dark");
-                    } else {
-                        let _ = html_el.class_list().remove_1("dark");
-                    }
-                }
-            }
+// Add or remove the `dark` class Tailwind's dark variants key on
+#[cfg(feature = "hydrate")]
+fn apply_theme_class(dark: bool) {
+    if let Some(html_el) = window()
+        .and_then(|w| w.document())
+        .and_then(|document| document.get_elements_by_tag_name("html").item(0))
+        .and_then(|el| el.dyn_into::<HtmlElement>().ok())
+    {
+        if dark {
+            let _ = html_el.class_list().add_1("dark");
+        } else {
+            let _ = html_el.class_list().remove_1("dark");
         }
+    }
+}
+
+#[component]
+pub fn ThemeToggle() -> impl IntoView {
+    // Light mode on the server; the browser restores the stored choice
+    let dark_mode = expect_context::<DarkMode>();
+    let is_dark = dark_mode.0;
+
+    // Client-side initialization - only runs in browser
+    #[cfg(feature = "hydrate")]
+    spawn_local(async move {
+        // Default to light mode if no local storage value (simpler than match_media for now)
+        let dark = window()
+            .and_then(|win| win.local_storage().ok().flatten())
+            .and_then(|local_storage| local_storage.get_item("theme").ok().flatten())
+            .is_some_and(|value| value == "dark");
+        is_dark.set(dark);
+        apply_theme_class(dark);
     });
 
     view! {
         <button
-            on:click=move |_| { toggle_theme.dispatch(()); }
+            on:click=move |_| dark_mode.toggle()
             class="p-2 rounded-lg text-gray-400 hover:text-gray-600 dark:text-gray-300 dark:hover:text-gray-100 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 dark:focus:ring-offset-gray-900"
             aria-label="Toggle dark mode"
         >
@@ -0,0 +1,198 @@
+use leptos::prelude::*;
+
+use crate::app::types::{DiffFile, DiffLineKind, PatchDiffLine};
+
+// Keywords and comment/string syntax for the languages deliverables are written in
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comment: &'static str,
+    quotes: &'static [char],
+}
+
+const RUST: Syntax = Syntax {
+    keywords: &["as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while"],
+    line_comment: "//",
+    // Single quotes are lifetimes as often as chars
+    quotes: &['"'],
+};
+
+const PYTHON: Syntax = Syntax {
+    keywords: &["and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True", "try", "while", "with", "yield"],
+    line_comment: "#",
+    quotes: &['"', '\''],
+};
+
+const C_LIKE: Syntax = Syntax {
+    keywords: &["abstract", "async", "await", "boolean", "break", "case", "catch", "class", "const", "continue", "default", "defer", "delete", "do", "else", "enum", "export", "extends", "false", "final", "finally", "for", "func", "function", "go", "if", "implements", "import", "instanceof", "interface", "let", "new", "null", "package", "private", "protected", "public", "return", "static", "struct", "switch", "this", "throw", "throws", "true", "try", "type", "typeof", "undefined", "var", "void", "while", "yield"],
+    line_comment: "//",
+    quotes: &['"', '\'', '`'],
+};
+
+const PLAIN: Syntax = Syntax { keywords: &[], line_comment: "", quotes: &[] };
+
+fn syntax_for(path: &str) -> &'static Syntax {
+    match path.rsplit('.').next().unwrap_or("") {
+        "rs" => &RUST,
+        "py" | "pyi" => &PYTHON,
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "go" | "java" | "kt" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" => &C_LIKE,
+        _ => &PLAIN,
+    }
+}
+
+// The line split into (Tailwind class, text) tokens
+fn highlight(text: &str, syntax: &Syntax) -> Vec<(&'static str, String)> {
+    let mut tokens: Vec<(&'static str, String)> = Vec::new();
+    let mut push = |class: &'static str, token: String| match tokens.last_mut() {
+        Some((last_class, last)) if *last_class == class => last.push_str(&token),
+        _ => tokens.push((class, token)),
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let rest: String = chars[i..].iter().collect();
+        if !syntax.line_comment.is_empty() && rest.starts_with(syntax.line_comment) {
+            push("text-gray-500 dark:text-gray-400 italic", rest);
+            break;
+        }
+        let c = chars[i];
+        if syntax.quotes.contains(&c) {
+            let mut end = i + 1;
+            while end < chars.len() && chars[end] != c {
+                end += if chars[end] == '\\' { 2 } else { 1 };
+            }
+            let end = (end + 1).min(chars.len());
+            push("text-green-700 dark:text-green-400", chars[i..end].iter().collect());
+            i = end;
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let class = if syntax.keywords.contains(&word.as_str()) {
+                "text-purple-700 dark:text-purple-400"
+            } else if c.is_ascii_digit() {
+                "text-orange-700 dark:text-orange-400"
+            } else {
+                ""
+            };
+            push(class, word);
+        } else {
+            push("", c.to_string());
+            i += 1;
+        }
+    }
+    tokens
+}
+
+// Hunk lines as (old, new) rows: context on both sides, a removed run next to the added run after it
+fn side_by_side(lines: &[PatchDiffLine]) -> Vec<(Option<PatchDiffLine>, Option<PatchDiffLine>)> {
+    let mut rows = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].kind == DiffLineKind::Context {
+            rows.push((Some(lines[i].clone()), Some(lines[i].clone())));
+            i += 1;
+            continue;
+        }
+        let removed: Vec<PatchDiffLine> = lines[i..].iter().take_while(|line| line.kind == DiffLineKind::Removed).cloned().collect();
+        i += removed.len();
+        let added: Vec<PatchDiffLine> = lines[i..].iter().take_while(|line| line.kind == DiffLineKind::Added).cloned().collect();
+        i += added.len();
+        for row in 0..removed.len().max(added.len()) {
+            rows.push((removed.get(row).cloned(), added.get(row).cloned()));
+        }
+    }
+    rows
+}
+
+fn diff_cell(line: Option<PatchDiffLine>, syntax: &'static Syntax, old_side: bool) -> AnyView {
+    let Some(line) = line else {
+        return view! {
+            <td class="w-10 bg-gray-50 dark:bg-gray-800/50"></td>
+            <td class="bg-gray-50 dark:bg-gray-800/50"></td>
+        }.into_any();
+    };
+    let (row_class, marker) = match line.kind {
+        DiffLineKind::Removed => ("bg-red-50 dark:bg-red-900/30", "-"),
+        DiffLineKind::Added => ("bg-green-50 dark:bg-green-900/30", "+"),
+        DiffLineKind::Context => ("", " "),
+    };
+    let number = if old_side { line.old_line } else { line.new_line };
+    let tokens = highlight(&line.text, syntax).into_iter().map(|(class, token)| view! { <span class=class>{token}</span> }).collect_view();
+    view! {
+        <td class=format!("w-10 pr-2 text-right align-top select-none text-gray-400 dark:text-gray-500 {}", row_class)>
+            {number.map(|number| number.to_string()).unwrap_or_default()}
+        </td>
+        <td class=format!("pr-2 align-top whitespace-pre-wrap break-all text-gray-800 dark:text-gray-200 {}", row_class)>
+            <span class="select-none text-gray-400 dark:text-gray-500">{marker}</span>
+            {tokens}
+        </td>
+    }.into_any()
+}
+
+/// One file of a parsed patch, old version left and new version right
+#[component]
+pub fn DiffViewer(file: DiffFile, #[prop(default = true)] initially_open: bool) -> impl IntoView {
+    let is_open = RwSignal::new(initially_open);
+    let path = if file.old_path == file.new_path || file.old_path == "/dev/null" {
+        file.new_path.clone()
+    } else if file.new_path == "/dev/null" {
+        format!("{} (deleted)", file.old_path)
+    } else {
+        format!("{} → {}", file.old_path, file.new_path)
+    };
+    let syntax = syntax_for(if file.new_path == "/dev/null" { &file.old_path } else { &file.new_path });
+    let added = file.added;
+    let removed = file.removed;
+    let binary = file.binary;
+    let hunks = file.hunks;
+
+    view! {
+        <div class="mb-3 rounded-lg border border-gray-200 dark:border-gray-700 bg-white dark:bg-gray-900 overflow-hidden">
+            <button
+                on:click=move |_| is_open.update(|open| *open = !*open)
+                class="w-full flex items-center gap-3 px-3 py-1.5 bg-gray-50 dark:bg-gray-800 border-b border-gray-200 dark:border-gray-700 text-left text-sm"
+            >
+                <span class="text-gray-500">{move || if is_open.get() { "▾" } else { "▸" }}</span>
+                <span class="font-mono text-gray-900 dark:text-white break-all">{path}</span>
+                <span class="ml-auto text-xs text-green-700 dark:text-green-400">{format!("+{}", added)}</span>
+                <span class="text-xs text-red-600 dark:text-red-400">{format!("−{}", removed)}</span>
+            </button>
+            {move || is_open.get().then(|| {
+                if binary {
+                    return view! { <div class="px-3 py-2 text-xs text-gray-500 dark:text-gray-400">"Binary file not shown"</div> }.into_any();
+                }
+                let bodies = hunks.iter().map(|hunk| {
+                    let rows = side_by_side(&hunk.lines).into_iter().map(|(old, new)| view! {
+                        <tr>
+                            {diff_cell(old, syntax, true)}
+                            {diff_cell(new, syntax, false)}
+                        </tr>
+                    }).collect_view();
+                    view! {
+                        <tbody>
+                            <tr>
+                                <td colspan="4" class="px-2 py-0.5 bg-blue-50 dark:bg-blue-900/30 text-blue-700 dark:text-blue-300">{hunk.header.clone()}</td>
+                            </tr>
+                            {rows}
+                        </tbody>
+                    }
+                }).collect_view();
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="w-full table-fixed font-mono text-xs">
+                            <colgroup>
+                                <col class="w-10"/>
+                                <col/>
+                                <col class="w-10"/>
+                                <col/>
+                            </colgroup>
+                            {bodies}
+                        </table>
+                    </div>
+                }.into_any()
+            })}
+        </div>
+    }
+}
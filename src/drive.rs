@@ -20,6 +20,33 @@ pub fn extract_drive_folder_id(link: &str) -> Option<String> {
     None
 }
 
+const FOLDER_MIME_TYPE: &str = "application/vnd.google-apps.folder";
+const SHORTCUT_MIME_TYPE: &str = "application/vnd.google-apps.shortcut";
+
+/// Whether a link is on one of Google's Drive/Docs hosts
+pub fn is_drive_link(link: &str) -> bool {
+    link.contains("drive.google.com") || link.contains("docs.google.com")
+}
+
+/// ID of the item a Drive link points at, whether a folder, a file or a shortcut
+///
+/// Covers `/drive/folders/<id>`, `/drive/u/0/folders/<id>`, `open?id=<id>`, `uc?id=<id>`,
+/// `/file/d/<id>/view` and the Docs `/d/<id>/edit` forms.
+pub fn extract_drive_item_id(link: &str) -> Option<String> {
+    for marker in ["/folders/", "/file/d/", "/d/", "?id=", "&id="] {
+        if let Some(start) = link.find(marker) {
+            let id: String = link[start + marker.len()..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                .collect();
+            if !id.is_empty() {
+                return Some(id);
+            }
+        }
+    }
+    None
+}
+
 pub async fn get_shared_drives(access_token: &str) -> Result<Vec<(String, String)>> {
     let client = reqwest::Client::new();
     let url = "https://www.googleapis.com/drive/v3/drives?fields=drives(id,name)";
@@ -53,7 +80,7 @@ pub async fn get_folder_contents(folder_id: &str, access_token: &str) -> Result<
     let encoded_query = urlencoding::encode(&query);
 
     let personal_url = format!(
-        "https://www.googleapis.com/drive/v3/files?q={}&fields=files(id,name,mimeType)&supportsAllDrives=true",
+        "https://www.googleapis.com/drive/v3/files?q={}&fields=files(id,name,mimeType,size)&supportsAllDrives=true",
         encoded_query
     );
 
@@ -83,7 +110,7 @@ pub async fn get_folder_contents(folder_id: &str, access_token: &str) -> Result<
 
     for (drive_name, drive_id) in shared_drives {
         let shared_url = format!(
-            "https://www.googleapis.com/drive/v3/files?q={}&fields=files(id,name,mimeType)&driveId={}&includeItemsFromAllDrives=true&supportsAllDrives=true&corpora=drive",
+            "https://www.googleapis.com/drive/v3/files?q={}&fields=files(id,name,mimeType,size)&driveId={}&includeItemsFromAllDrives=true&supportsAllDrives=true&corpora=drive",
             encoded_query, drive_id
         );
 
@@ -132,4 +159,93 @@ pub async fn get_folder_metadata(folder_id: &str, access_token: &str) -> Result<
     }
 
     resp.json().await.map_err(|e| anyhow!("JSON parse error: {}", e))
-}
\ No newline at end of file
+}
+
+/// Why the service account can't download a file, or `None` when it can
+pub async fn file_access_problem(file_id: &str, access_token: &str) -> Result<Option<String>> {
+    let url = format!(
+        "https://www.googleapis.com/drive/v3/files/{}?fields=id,capabilities(canDownload)&supportsAllDrives=true",
+        file_id
+    );
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .get(&url)
+        .header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Ok(Some(resp.status().to_string()));
+    }
+
+    let meta: serde_json::Value = resp.json().await.map_err(|e| anyhow!("JSON parse error: {}", e))?;
+    if meta["capabilities"]["canDownload"].as_bool() == Some(false) {
+        return Ok(Some("download disabled by the owner".to_string()));
+    }
+    Ok(None)
+}
+
+async fn get_item_metadata(item_id: &str, access_token: &str) -> Result<serde_json::Value> {
+    let url = format!(
+        "https://www.googleapis.com/drive/v3/files/{}?fields=id,name,mimeType,parents,shortcutDetails(targetId,targetMimeType)&supportsAllDrives=true",
+        item_id
+    );
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .get(&url)
+        .header(AUTHORIZATION, format!("Bearer {}", access_token))
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(anyhow!("Failed to get item metadata: {}", resp.status()));
+    }
+
+    resp.json().await.map_err(|e| anyhow!("JSON parse error: {}", e))
+}
+
+/// Folder a Drive item stands for: the folder itself, a shortcut's target, or a file's parent
+pub async fn resolve_deliverable_folder(item_id: &str, access_token: &str) -> Result<String> {
+    let mut item_id = item_id.to_string();
+    // A shortcut to a file takes two hops: the target, then its parent
+    for _ in 0..3 {
+        let meta = get_item_metadata(&item_id, access_token).await?;
+        let mime_type = meta["mimeType"].as_str().unwrap_or("");
+        if mime_type == FOLDER_MIME_TYPE {
+            return Ok(item_id);
+        }
+        item_id = if mime_type == SHORTCUT_MIME_TYPE {
+            meta["shortcutDetails"]["targetId"].as_str()
+                .ok_or_else(|| anyhow!("Shortcut {} has no target", item_id))?
+                .to_string()
+        } else {
+            meta["parents"][0].as_str()
+                .ok_or_else(|| anyhow!("File {} has no parent folder the service account can see", item_id))?
+                .to_string()
+        };
+    }
+    Err(anyhow!("Could not resolve {} to a folder", item_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_drive_item_id_formats() {
+        let cases = [
+            ("https://drive.google.com/drive/folders/1AbC-d_E?usp=sharing", "1AbC-d_E"),
+            ("https://drive.google.com/drive/u/0/folders/1AbC#x", "1AbC"),
+            ("https://drive.google.com/open?id=1AbC&usp=drive_fs", "1AbC"),
+            ("https://drive.google.com/uc?export=download&id=1AbC", "1AbC"),
+            ("https://drive.google.com/file/d/1AbC/view?usp=sharing", "1AbC"),
+            ("https://docs.google.com/document/d/1AbC/edit", "1AbC"),
+        ];
+        for (link, id) in cases {
+            assert_eq!(extract_drive_item_id(link).as_deref(), Some(id), "{}", link);
+        }
+        assert_eq!(extract_drive_item_id("https://drive.google.com/drive/my-drive"), None);
+    }
+}
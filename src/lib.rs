@@ -1,3 +1,5 @@
+#![recursion_limit = "256"]
+
 pub mod app;
 pub mod components;
 #[cfg(feature = "ssr")]
@@ -6,6 +8,8 @@ pub mod api;
 pub mod auth;
 #[cfg(feature = "ssr")]
 pub mod drive;
+#[cfg(feature = "ssr")]
+pub mod static_assets;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -2,12 +2,24 @@
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
+    use axum::extract::DefaultBodyLimit;
+    use axum::routing::{get, post};
     use axum::Router;
     use leptos::logging::log;
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
+    use swe_reviewer_web::api::analysis_api::{analyze_handler, ANALYZE_ROUTE, MAX_REQUEST_BYTES};
+    use swe_reviewer_web::api::download_progress::{download_progress_handler, DOWNLOAD_PROGRESS_ROUTE};
+    use swe_reviewer_web::api::parser_corpus::{run_parser_corpus_command, PARSER_CORPUS_COMMAND};
     use swe_reviewer_web::app::*;
     use swe_reviewer_web::auth::init_service_account_auth;
+    use swe_reviewer_web::static_assets::bundle_service;
+
+    // `parser-corpus <dir>` checks the parsers against a fixture corpus instead of serving, see api::parser_corpus
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some(PARSER_CORPUS_COMMAND) {
+        std::process::exit(run_parser_corpus_command(&args[1..]));
+    }
 
     // Initialize service account authentication
     if let Err(e) = init_service_account_auth().await {
@@ -24,12 +36,20 @@ async fn main() {
     // Generate the list of routes in your Leptos App
     let routes = generate_route_list(App);
 
+    // Compressed, cacheable client bundle; see static_assets
+    let bundle = bundle_service(&leptos_options.site_root, &leptos_options.site_pkg_dir, leptos_options.hash_files);
+
     // Create main router with LeptosOptions state
     let app = Router::new()
+        // Direct analysis API for automation, see api::analysis_api
+        .route(ANALYZE_ROUTE, post(analyze_handler).layer(DefaultBodyLimit::max(MAX_REQUEST_BYTES)))
+        // Live per-file download progress as server-sent events, see api::download_progress
+        .route(DOWNLOAD_PROGRESS_ROUTE, get(download_progress_handler))
         .leptos_routes(&leptos_options, routes, {
             let leptos_options = leptos_options.clone();
             move || shell(leptos_options.clone())
         })
+        .nest_service(&format!("/{}", leptos_options.site_pkg_dir), bundle)
         .fallback(leptos_axum::file_and_error_handler(shell))
         .with_state(leptos_options);
 
@@ -0,0 +1,38 @@
+//! Serving the client bundle
+//!
+//! The WASM bundle, its JS glue and the CSS under `/<site-pkg-dir>` make up nearly all of a
+//! reviewer's first load. A release build made with `cargo leptos build --release --precompress`
+//! leaves `.br` and `.gz` files next to them, which are sent as is to browsers that accept
+//! them; without those (e.g. under `cargo leptos watch`) the files are compressed per request.
+//! With `hash-files` on, bundle file names change with their content, so browsers may keep
+//! them for a year; otherwise they revalidate on each load and get a 304 when unchanged.
+
+use std::path::Path;
+
+use axum::http::{header, HeaderValue};
+use tower_http::compression::Compression;
+use tower_http::services::ServeDir;
+use tower_http::set_header::SetResponseHeader;
+
+pub const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+pub const REVALIDATE_CACHE_CONTROL: &str = "no-cache";
+
+/// Cache-Control for bundle files, depending on whether their names carry a content hash
+pub fn bundle_cache_control(hash_files: bool) -> &'static str {
+    if hash_files {
+        IMMUTABLE_CACHE_CONTROL
+    } else {
+        REVALIDATE_CACHE_CONTROL
+    }
+}
+
+/// Service for `/<site_pkg_dir>`, to mount with `Router::nest_service`
+pub fn bundle_service(site_root: &str, site_pkg_dir: &str, hash_files: bool) -> Compression<SetResponseHeader<ServeDir, HeaderValue>> {
+    let files = ServeDir::new(Path::new(site_root).join(site_pkg_dir))
+        .precompressed_br()
+        .precompressed_gzip();
+    let cached = SetResponseHeader::if_not_present(files, header::CACHE_CONTROL, HeaderValue::from_static(bundle_cache_control(hash_files)));
+    // Leaves responses that already carry a Content-Encoding, i.e. precompressed files, alone
+    Compression::new(cached)
+}
+